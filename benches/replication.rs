@@ -28,6 +28,9 @@ fn replicate<C: BenchmarkComponent>(c: &mut Criterion) {
         g.bench_function(BenchmarkId::new("mutations_send", clients_count), |b| {
             b.iter_custom(|iter| mutations_send::<C>(iter, clients_count))
         });
+        g.bench_function(BenchmarkId::new("churn_send", clients_count), |b| {
+            b.iter_custom(|iter| churn_send::<C>(iter, clients_count))
+        });
     }
 
     g.bench_function("changes_receive", |b| {
@@ -118,6 +121,57 @@ fn mutations_send<C: BenchmarkComponent>(iter: u64, clients_count: usize) -> Dur
     elapsed
 }
 
+fn churn_send<C: BenchmarkComponent>(iter: u64, clients_count: usize) -> Duration {
+    let mut server_app = create_app::<C>();
+    let mut client_apps = Vec::new();
+    for _ in 0..clients_count {
+        client_apps.push(create_app::<C>());
+    }
+
+    for client_app in &mut client_apps {
+        server_app.connect_client(client_app);
+    }
+
+    server_app
+        .world_mut()
+        .spawn_batch(vec![(Replicated, C::default()); ENTITIES]);
+    let mut query = server_app
+        .world_mut()
+        .query_filtered::<Entity, With<Replicated>>();
+
+    server_app.update();
+    for client_app in &mut client_apps {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+    }
+
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..iter {
+        // Despawn half of the entities to move the rest to different rows.
+        let despawned: Vec<_> = query.iter(server_app.world()).step_by(2).collect();
+        for entity in despawned {
+            server_app.world_mut().despawn(entity);
+        }
+        server_app
+            .world_mut()
+            .spawn_batch(vec![(Replicated, C::default()); ENTITIES / 2]);
+
+        let instant = Instant::now();
+        server_app.update();
+        elapsed += instant.elapsed();
+
+        for client_app in &mut client_apps {
+            server_app.exchange_with_client(client_app);
+            client_app.update();
+
+            let mut remote = client_app.world_mut().query::<&Remote>();
+            assert_eq!(remote.iter(client_app.world()).len(), ENTITIES);
+        }
+    }
+
+    elapsed
+}
+
 fn changes_receive<C: BenchmarkComponent>(iter: u64) -> Duration {
     let mut elapsed = Duration::ZERO;
     for _ in 0..iter {
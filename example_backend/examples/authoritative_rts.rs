@@ -26,7 +26,7 @@ use bevy::{
     platform::collections::HashMap,
     prelude::*,
 };
-use bevy_replicon::prelude::*;
+use bevy_replicon::{prelude::*, server::visibility::debug::VisibilityDebug};
 use bevy_replicon_example_backend::{ExampleClient, ExampleServer, RepliconExampleBackendPlugins};
 use clap::{Parser, ValueEnum};
 use pathfinding::prelude::*;
@@ -69,6 +69,7 @@ fn main() {
             (
                 draw_selection.run_if(|r: Res<Selection>| r.active),
                 draw_selected,
+                draw_hidden.run_if(in_state(ServerState::Running)),
             ),
         )
         .run();
@@ -560,6 +561,32 @@ fn draw_selected(mut gizmos: Gizmos, units: Query<(&GlobalTransform, &Command),
     }
 }
 
+/// Draws rings in the color of each player's team around units with data hidden from them.
+///
+/// Helps to debug [`VisibilityFilter`] for [`Team`].
+fn draw_hidden(
+    mut gizmos: Gizmos,
+    visibility_debug: VisibilityDebug,
+    players: Query<(Entity, &Team), With<Player>>,
+    units: Query<&GlobalTransform, With<Unit>>,
+) {
+    for (index, (client, team)) in players.iter().enumerate() {
+        let Ok(entities) = visibility_debug.for_client(client) else {
+            continue;
+        };
+
+        let radius = 20.0 + 4.0 * index as f32;
+        for info in entities {
+            if info.hidden_by().next().is_none() {
+                continue;
+            }
+            if let Ok(transform) = units.get(info.entity()) {
+                gizmos.circle_2d(transform.translation().truncate(), radius, team.color());
+            }
+        }
+    }
+}
+
 /// Picks a team by ID.
 ///
 /// If it's [`ClientId::Server`], uses the value from resource.
@@ -0,0 +1,243 @@
+//! Headless harness that measures replication performance.
+//!
+//! Instead of a real transport, clients are connected using the in-memory exchange
+//! from [`ServerTestAppExt`], so numbers don't depend on the network and are reproducible
+//! for the same arguments. The server replicates entities with a single component of
+//! the configured size and mutates a fraction of them on each tick.
+//!
+//! Reports timings for collecting changes and sending messages on the server and for
+//! receiving on clients, as well as the number of sent messages and bytes. Intended for comparing performance between changes, so run
+//! it in release mode:
+//!
+//! ```bash
+//! cargo run -p bevy_replicon_example_backend --release --example bench_server -- --clients 20
+//! ```
+
+use std::time::{Duration, Instant};
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use clap::Parser;
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
+
+fn main() {
+    let cli = Cli::parse();
+    let mut rng = Rng::with_seed(cli.seed);
+
+    let mut server_app = create_app();
+    let mut client_apps: Vec<_> = (0..cli.clients).map(|_| create_app()).collect();
+    for client_app in &mut client_apps {
+        server_app.connect_client(client_app);
+    }
+
+    let component_size = cli.component_size;
+    server_app.world_mut().spawn_batch(
+        (0..cli.entities).map(move |_| (Replicated, Payload(vec![0; component_size]))),
+    );
+
+    let initial = run_tick(&mut server_app, &mut client_apps);
+    for client_app in &mut client_apps {
+        let mut remote = client_app.world_mut().query::<&Remote>();
+        assert_eq!(
+            remote.iter(client_app.world()).len(),
+            cli.entities,
+            "all entities should be replicated in the initial tick"
+        );
+    }
+
+    let mut payloads = server_app.world_mut().query::<&mut Payload>();
+    let mut summary = Summary::default();
+    for _ in 0..cli.ticks {
+        for mut payload in payloads.iter_mut(server_app.world_mut()) {
+            if !payload.is_empty() && rng.f32() < cli.mutation_rate {
+                let index = rng.usize(..payload.len());
+                payload[index] = payload[index].wrapping_add(1);
+            }
+        }
+
+        summary.add(run_tick(&mut server_app, &mut client_apps));
+    }
+
+    println!(
+        "{} clients, {} entities, {} bytes per component, {} mutation rate",
+        cli.clients, cli.entities, cli.component_size, cli.mutation_rate
+    );
+    println!("initial tick:");
+    initial.print(cli.clients);
+    if cli.ticks > 0 {
+        println!("average over {} ticks:", cli.ticks);
+        summary.average(cli.ticks).print(cli.clients);
+        println!("slowest tick:");
+        summary.max.print(cli.clients);
+    }
+}
+
+/// Updates the server, exchanges messages with all clients and updates them.
+fn run_tick(server_app: &mut App, client_apps: &mut [App]) -> TickStats {
+    let instant = Instant::now();
+    server_app.update();
+    let server_update = instant.elapsed();
+
+    let send_stats = *server_app.world().resource::<ReplicationSendStats>();
+    let server_messages = server_app.world().resource::<ServerMessages>();
+    let messages = server_messages.iter_sent().len();
+    let bytes = server_messages
+        .iter_sent()
+        .map(|(_, _, message)| message.len())
+        .sum();
+
+    let mut receive = Duration::ZERO;
+    let mut client_update = Duration::ZERO;
+    for client_app in client_apps {
+        server_app.exchange_with_client(client_app);
+
+        let instant = Instant::now();
+        client_app.update();
+        client_update += instant.elapsed();
+        receive += client_app
+            .world()
+            .resource::<ReplicationApplyStats>()
+            .apply_time;
+    }
+
+    TickStats {
+        server_update,
+        collect: send_stats.collect_time,
+        send: send_stats.send_time,
+        client_update,
+        receive,
+        messages,
+        bytes,
+    }
+}
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+    ))
+    .init_resource::<ReplicationSendStats>()
+    .init_resource::<ReplicationApplyStats>()
+    .replicate::<Payload>()
+    .finish();
+
+    app
+}
+
+#[derive(Default, Clone, Copy)]
+struct TickStats {
+    /// Time spent in the server update.
+    server_update: Duration,
+
+    /// Time spent collecting changes on the server.
+    collect: Duration,
+
+    /// Time spent writing messages on the server.
+    send: Duration,
+
+    /// Total time spent in client updates.
+    client_update: Duration,
+
+    /// Total time spent receiving and applying replication on clients.
+    receive: Duration,
+
+    /// Number of messages sent by the server.
+    messages: usize,
+
+    /// Total size of messages sent by the server.
+    bytes: usize,
+}
+
+impl TickStats {
+    fn print(&self, clients: usize) {
+        let per_client = clients.max(1);
+        println!("  server update: {:?}", self.server_update);
+        println!("    collect: {:?}", self.collect);
+        println!("    send: {:?}", self.send);
+        println!(
+            "  client updates: {:?} ({:?} per client)",
+            self.client_update,
+            self.client_update / per_client as u32
+        );
+        println!(
+            "    receive: {:?} ({:?} per client)",
+            self.receive,
+            self.receive / per_client as u32
+        );
+        println!(
+            "  sent: {} messages, {} bytes ({} per client)",
+            self.messages,
+            self.bytes,
+            self.bytes / per_client
+        );
+    }
+}
+
+#[derive(Default)]
+struct Summary {
+    total: TickStats,
+    max: TickStats,
+}
+
+impl Summary {
+    fn add(&mut self, stats: TickStats) {
+        self.total.server_update += stats.server_update;
+        self.total.collect += stats.collect;
+        self.total.send += stats.send;
+        self.total.client_update += stats.client_update;
+        self.total.receive += stats.receive;
+        self.total.messages += stats.messages;
+        self.total.bytes += stats.bytes;
+
+        if stats.server_update + stats.client_update
+            > self.max.server_update + self.max.client_update
+        {
+            self.max = stats;
+        }
+    }
+
+    fn average(&self, ticks: u32) -> TickStats {
+        TickStats {
+            server_update: self.total.server_update / ticks,
+            collect: self.total.collect / ticks,
+            send: self.total.send / ticks,
+            client_update: self.total.client_update / ticks,
+            receive: self.total.receive / ticks,
+            messages: self.total.messages / ticks as usize,
+            bytes: self.total.bytes / ticks as usize,
+        }
+    }
+}
+
+#[derive(Parser)]
+struct Cli {
+    /// Number of connected clients.
+    #[arg(short, long, default_value_t = 10)]
+    clients: usize,
+
+    /// Number of replicated entities.
+    #[arg(short, long, default_value_t = 1000)]
+    entities: usize,
+
+    /// Size of the replicated component in bytes.
+    #[arg(short = 's', long, default_value_t = 32)]
+    component_size: usize,
+
+    /// Probability for each entity to be mutated on a tick.
+    #[arg(short, long, default_value_t = 0.1)]
+    mutation_rate: f32,
+
+    /// Number of measured ticks after the initial replication.
+    #[arg(short, long, default_value_t = 100)]
+    ticks: u32,
+
+    /// Seed for mutations.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+#[derive(Component, Deref, DerefMut, Serialize, Deserialize, Clone)]
+struct Payload(Vec<u8>);
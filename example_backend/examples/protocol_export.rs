@@ -0,0 +1,35 @@
+//! Exports the protocol description as JSON for external tools.
+//!
+//! Run with `cargo run --example protocol_export > protocol.json`.
+//! In a real game, register the same plugins as the game itself to get the full protocol.
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, shared::protocol::description::ProtocolDescription};
+use serde::{Deserialize, Serialize};
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .replicate::<Player>()
+        .replicate::<Health>()
+        .add_client_event::<Attack>(Channel::Ordered)
+        .add_server_message::<Chat>(Channel::Ordered)
+        .finish();
+
+    let description = ProtocolDescription::new(app.world());
+    let json = serde_json::to_string_pretty(&description)
+        .expect("protocol description should be serializable");
+    println!("{json}");
+}
+
+#[derive(Component, Serialize, Deserialize)]
+struct Player;
+
+#[derive(Component, Serialize, Deserialize)]
+struct Health(u32);
+
+#[derive(Event, Serialize, Deserialize)]
+struct Attack;
+
+#[derive(Message, Serialize, Deserialize)]
+struct Chat(String);
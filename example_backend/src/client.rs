@@ -5,7 +5,7 @@ use std::{
 };
 
 use bevy::prelude::*;
-use bevy_replicon::prelude::*;
+use bevy_replicon::{prelude::*, shared::backend::channels::ServerChannelId};
 
 use super::{
     link_conditioner::{GlobalConditionerConfig, LinkConditioner},
@@ -77,7 +77,7 @@ fn receive_packets(
     }
 
     while let Some((channel_id, message)) = client.conditioner.pop(now) {
-        messages.insert_received(channel_id, message);
+        messages.insert_received(ServerChannelId::new(channel_id.into()), message);
     }
 }
 
@@ -87,7 +87,7 @@ fn send_packets(
     mut messages: ResMut<ClientMessages>,
 ) {
     for (channel_id, message) in messages.drain_sent() {
-        if let Err(e) = tcp::send_message(&mut client.stream, channel_id, &message) {
+        if let Err(e) = tcp::send_message(&mut client.stream, channel_id.get(), &message) {
             error!("disconnecting due message write error: {e}");
             commands.remove_resource::<ExampleClient>();
             return;
@@ -5,7 +5,10 @@ use std::{
 };
 
 use bevy::prelude::*;
-use bevy_replicon::{prelude::*, shared::backend::connected_client::NetworkId};
+use bevy_replicon::{
+    prelude::*,
+    shared::backend::{channels::ClientChannelId, connected_client::NetworkId},
+};
 
 use super::{
     link_conditioner::{ConditionerConfig, GlobalConditionerConfig, LinkConditioner},
@@ -116,7 +119,7 @@ fn receive_packets(
         }
 
         while let Some((channel_id, message)) = connection.conditioner.pop(now) {
-            messages.insert_received(client, channel_id, message)
+            messages.insert_received(client, ClientChannelId::new(channel_id.into()), message)
         }
     }
 }
@@ -131,7 +134,7 @@ fn send_packets(
         let mut connection = clients
             .get_mut(client)
             .expect("all connected clients should have streams");
-        if let Err(e) = tcp::send_message(&mut connection.stream, channel_id, &message) {
+        if let Err(e) = tcp::send_message(&mut connection.stream, channel_id.get(), &message) {
             commands.entity(client).despawn();
             error!("disconnecting client `{client}` due to error: {e}");
         }
@@ -0,0 +1,259 @@
+//! Derive macros for [bevy_replicon](https://docs.rs/bevy_replicon).
+//!
+//! Re-exported by the main crate, don't depend on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Fields, Member, Token, Type, parenthesized, parse_macro_input,
+    punctuated::Punctuated,
+};
+
+/// Implements `VisibilityFilter` for a component.
+#[proc_macro_derive(VisibilityFilter, attributes(visibility))]
+pub fn derive_visibility_filter(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    visibility_filter(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn visibility_filter(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut mode = None;
+    let mut scope = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("visibility"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("equal") || meta.path.is_ident("bitmask") {
+                if mode.is_some() {
+                    return Err(meta.error("visibility mode is already specified"));
+                }
+                mode = Some(if meta.path.is_ident("equal") {
+                    Mode::Equal
+                } else {
+                    Mode::Bitmask
+                });
+                Ok(())
+            } else if meta.path.is_ident("scope") {
+                if scope.is_some() {
+                    return Err(meta.error("visibility scope is already specified"));
+                }
+                let content;
+                parenthesized!(content in meta.input);
+                let types = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+                if types.is_empty() {
+                    return Err(meta.error("visibility scope can't be empty"));
+                }
+                scope = Some(types);
+                Ok(())
+            } else {
+                Err(meta.error("expected `equal`, `bitmask` or `scope`"))
+            }
+        })?;
+    }
+
+    let Some(mode) = mode else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "expected `#[visibility(equal)]` or `#[visibility(bitmask)]`",
+        ));
+    };
+
+    let body = match mode {
+        Mode::Equal => quote! { component.is_some_and(|component| self == component) },
+        Mode::Bitmask => {
+            if is_newtype(&input.data) {
+                quote! { component.is_some_and(|component| (self.0 & component.0) == self.0) }
+            } else {
+                quote! { component.is_some_and(|component| (*self & *component) == *self) }
+            }
+        }
+    };
+
+    let scope = match scope {
+        None => quote! { ::bevy_replicon::__private::Entity },
+        Some(types) if types.len() == 1 => {
+            let ty = &types[0];
+            if is_entity(ty) {
+                quote! { #ty }
+            } else {
+                quote! { ::bevy_replicon::prelude::SingleComponent<#ty> }
+            }
+        }
+        Some(types) => {
+            let types = types.iter();
+            quote! { (#(#types,)*) }
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::bevy_replicon::prelude::VisibilityFilter for #ident #ty_generics #where_clause {
+            type ClientComponent = Self;
+            type Scope = #scope;
+
+            fn is_visible(
+                &self,
+                _client: ::bevy_replicon::__private::Entity,
+                component: ::core::option::Option<&Self::ClientComponent>,
+            ) -> bool {
+                #body
+            }
+        }
+    })
+}
+
+/// Implements `PresenceBitmap` for a struct.
+#[proc_macro_derive(PresenceBitmap)]
+pub fn derive_presence_bitmap(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    presence_bitmap(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn presence_bitmap(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`PresenceBitmap` can only be derived for structs",
+        ));
+    };
+
+    let mut options = 0;
+    let mut mark_present = Vec::new();
+    let mut serialize = Vec::new();
+    let mut deserialize = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = field
+            .ident
+            .clone()
+            .map_or_else(|| Member::Unnamed(index.into()), Member::Named);
+
+        if is_option(&field.ty) {
+            let byte = Literal::usize_unsuffixed(options / 8);
+            let bit = Literal::u8_suffixed(1 << (options % 8));
+            options += 1;
+
+            mark_present.push(quote! {
+                if self.#member.is_some() {
+                    bitmap[#byte] |= #bit;
+                }
+            });
+            serialize.push(quote! {
+                if let ::core::option::Option::Some(value) = &self.#member {
+                    ::bevy_replicon::postcard_utils::to_extend_mut(value, writer)?;
+                }
+            });
+            deserialize.push(quote! {
+                #member: if bitmap[#byte] & #bit != 0 {
+                    ::core::option::Option::Some(::bevy_replicon::postcard_utils::from_buf(buf)?)
+                } else {
+                    ::core::option::Option::None
+                }
+            });
+        } else {
+            serialize.push(quote! {
+                ::bevy_replicon::postcard_utils::to_extend_mut(&self.#member, writer)?;
+            });
+            deserialize.push(quote! {
+                #member: ::bevy_replicon::postcard_utils::from_buf(buf)?
+            });
+        }
+    }
+
+    let (write_bitmap, read_bitmap) = if options == 0 {
+        (TokenStream2::new(), TokenStream2::new())
+    } else {
+        let len = Literal::usize_unsuffixed(options.div_ceil(8));
+        (
+            quote! {
+                let mut bitmap = [0u8; #len];
+                #(#mark_present)*
+                writer.extend(bitmap);
+            },
+            quote! {
+                if ::bevy_replicon::bytes::Buf::remaining(buf) < #len {
+                    return ::core::result::Result::Err(
+                        ::bevy_replicon::postcard::Error::DeserializeUnexpectedEnd,
+                    );
+                }
+                let mut bitmap = [0u8; #len];
+                ::bevy_replicon::bytes::Buf::copy_to_slice(buf, &mut bitmap);
+            },
+        )
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::bevy_replicon::prelude::PresenceBitmap for #ident #ty_generics #where_clause {
+            fn to_extend_mut<W: ::core::iter::Extend<u8>>(
+                &self,
+                writer: &mut W,
+            ) -> ::bevy_replicon::postcard::Result<()> {
+                #write_bitmap
+                #(#serialize)*
+                ::core::result::Result::Ok(())
+            }
+
+            fn from_buf(
+                buf: &mut ::bevy_replicon::bytes::Bytes,
+            ) -> ::bevy_replicon::postcard::Result<Self> {
+                #read_bitmap
+                ::core::result::Result::Ok(Self {
+                    #(#deserialize,)*
+                })
+            }
+        }
+    })
+}
+
+enum Mode {
+    /// Visible if the client has an equal component.
+    Equal,
+    /// Visible if the client has all bits of the entity.
+    Bitmask,
+}
+
+/// Returns `true` if the type is a tuple struct with a single field.
+fn is_newtype(data: &Data) -> bool {
+    let Data::Struct(data) = data else {
+        return false;
+    };
+
+    matches!(&data.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+}
+
+/// Returns `true` if the type is `Entity`, which hides the whole entity.
+fn is_entity(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    path.qself.is_none()
+        && path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Entity" && segment.arguments.is_empty())
+}
+
+/// Returns `true` if the type is `Option`, which is stored in the presence bitmap.
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    path.qself.is_none()
+        && path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option" && !segment.arguments.is_empty())
+}
@@ -1,34 +1,55 @@
 pub mod confirm_history;
+pub mod destructive_gate;
 #[cfg(feature = "client_diagnostics")]
 pub mod diagnostics;
+pub mod extrapolating;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+pub mod interpolating;
 pub mod message;
+pub mod replication_sink;
 pub mod server_mutate_ticks;
 
-use bevy::prelude::*;
+use core::{mem, time::Duration};
+
+use bevy::{
+    ecs::{component::Components, entity::EntityHashMap},
+    platform::{
+        collections::{HashSet, hash_map::Entry},
+        time::Instant,
+    },
+    prelude::*,
+};
 use bytes::{Buf, Bytes};
-use log::{Level, debug, error, log_enabled, trace};
+use log::{Level, debug, error, log_enabled, trace, warn};
 use postcard::experimental::max_size::MaxSize;
 
 use crate::{
     postcard_utils,
     prelude::*,
     shared::{
-        backend::channels::{ClientChannel, ServerChannel},
+        authorization::AuthRequest,
+        backend::channels::{ClientChannel, ServerChannel, ServerChannelId},
         replication::{
             deferred_entity::{DeferredEntity, EntityScratch},
+            entity_pool::EntityPool,
             message_flags::{MutateFlags, UpdateFlags},
             mutate_index::MutateIndex,
             receive_markers::{EntityMarkers, ReceiveMarkers},
             registry::{
-                ReplicationRegistry,
+                self, FnsId, ReplicationRegistry,
                 ctx::{BufferedSpawner, DespawnCtx, EntityBuffer, RemoveCtx, WriteCtx},
+                removal_id::RemovalId,
             },
-            signature::SignatureMap,
+            signature::{SignatureMap, SignatureRemovals, SignatureSettings},
         },
+        replication_error::{ErrorReporter, ReplicationErrorKind},
         server_entity_map::{EntityEntry, ServerEntityMap},
     },
 };
 use confirm_history::{ConfirmHistory, EntityReplicated};
+use destructive_gate::{DestructiveGates, DestructiveKind, PendingOp};
+use replication_sink::{ClientReplicationSink, SinkMode};
 use server_mutate_ticks::{MutateTickReceived, ServerMutateTicks};
 
 /// Client functionality and replication receiving.
@@ -44,6 +65,12 @@ impl Plugin for ClientPlugin {
             .init_resource::<ServerUpdateTick>()
             .init_resource::<ServerMutateTicks>()
             .init_resource::<BufferedMutations>()
+            .init_resource::<UnknownFns>()
+            .init_resource::<DestructiveGates>()
+            .init_resource::<ClientMessageTransforms>()
+            .init_resource::<UpdateApplyMode>()
+            .init_resource::<ReplicationBatch>()
+            .init_resource::<SignatureSettings>()
             .add_message::<EntityReplicated>()
             .add_message::<MutateTickReceived>()
             .configure_sets(
@@ -59,35 +86,84 @@ impl Plugin for ClientPlugin {
                 OnEnter(ClientState::Connected),
                 (ClientSystems::Receive, ClientSystems::Diagnostics).chain(),
             )
+            .configure_sets(
+                PreUpdate,
+                (
+                    ClientSystems::ApplyReplication,
+                    ClientSystems::EmitQueuedEvents,
+                )
+                    .chain()
+                    .in_set(ClientSystems::Receive),
+            )
+            .configure_sets(
+                OnEnter(ClientState::Connected),
+                (
+                    ClientSystems::ApplyReplication,
+                    ClientSystems::EmitQueuedEvents,
+                )
+                    .chain()
+                    .in_set(ClientSystems::Receive),
+            )
             .configure_sets(
                 PostUpdate,
                 (ClientSystems::Send, ClientSystems::SendPackets).chain(),
             )
             .add_observer(cleanup_storage)
             .add_observer(cleanup_entity_map)
+            .add_observer(rebind_signature)
+            .add_observer(receive_connection_seed)
             .add_systems(
                 PreUpdate,
-                receive_replication
-                    .in_set(ClientSystems::Receive)
+                (apply_ungated, receive_replication)
+                    .chain()
+                    .in_set(ClientSystems::ApplyReplication)
                     .run_if(in_state(ClientState::Connected)),
             )
             .add_systems(
                 OnEnter(ClientState::Connected),
-                receive_replication.in_set(ClientSystems::Receive),
+                receive_replication.in_set(ClientSystems::ApplyReplication),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    interpolating::mark_interpolating.run_if(resource_exists::<InterpolationDelay>),
+                    interpolating::finish_interpolating,
+                )
+                    .chain()
+                    .after(ClientSystems::Receive)
+                    .run_if(in_state(ClientState::Connected)),
+            )
+            .add_systems(
+                PreUpdate,
+                transform_received
+                    .after(ClientSystems::ReceivePackets)
+                    .before(ClientSystems::Receive)
+                    .run_if(not(in_state(ClientState::Disconnected))),
+            )
+            .add_systems(
+                PostUpdate,
+                transform_sent
+                    .after(ClientSystems::Send)
+                    .before(ClientSystems::SendPackets)
+                    .run_if(not(in_state(ClientState::Disconnected))),
             )
             .add_systems(
                 OnExit(ClientState::Connected),
-                reset.in_set(ClientSystems::Reset),
+                (reset, replication_sink::reset).in_set(ClientSystems::Reset),
             );
 
         let auth_method = *app.world().resource::<AuthMethod>();
         debug!("using authorization method `{auth_method:?}`");
-        if auth_method == AuthMethod::ProtocolCheck {
-            app.add_observer(log_protocol_error).add_systems(
+        if auth_method == AuthMethod::Authorizers {
+            app.add_systems(
                 OnEnter(ClientState::Connected),
-                send_protocol_hash.in_set(ClientSystems::SendHash),
+                send_auth_request.in_set(ClientSystems::SendHash),
             );
         }
+        if auth_method != AuthMethod::None {
+            app.add_observer(report_auth_failure)
+                .add_observer(log_protocol_error);
+        }
 
         if log_enabled!(Level::Debug) {
             app.add_systems(OnEnter(ClientState::Disconnected), || {
@@ -102,11 +178,76 @@ impl Plugin for ClientPlugin {
         app.world_mut()
             .resource_scope(|world, mut messages: Mut<ClientMessages>| {
                 let channels = world.resource::<RepliconChannels>();
-                messages.setup_server_channels(channels.server_channels().len());
+                messages.setup_channels(channels);
             });
     }
 }
 
+/// Reverts [`ClientMessageTransforms`] for received messages.
+///
+/// Runs regardless of the connection state because messages can arrive
+/// in the same frame the client transitions to [`ClientState::Connected`].
+fn transform_received(
+    mut messages: ResMut<ClientMessages>,
+    mut errors: ResMut<ErrorReporter>,
+    mut transforms: ResMut<ClientMessageTransforms>,
+) {
+    if transforms.is_empty() {
+        return;
+    }
+
+    messages.retain_received_mut(|channel_id, message| {
+        match transforms.inbound(mem::take(message)) {
+            Ok(transformed) => {
+                *message = transformed;
+                true
+            }
+            Err(e) => {
+                if errors.report(
+                    ReplicationErrorKind::Deserialize,
+                    Some(channel_id.get()),
+                    None,
+                    &e,
+                ) {
+                    error!("discarding message from server that failed to transform: {e}");
+                }
+                false
+            }
+        }
+    });
+}
+
+/// Applies [`ClientMessageTransforms`] to sent messages.
+fn transform_sent(
+    mut messages: ResMut<ClientMessages>,
+    mut errors: ResMut<ErrorReporter>,
+    mut transforms: ResMut<ClientMessageTransforms>,
+) {
+    if transforms.is_empty() {
+        return;
+    }
+
+    messages.retain_sent_mut(|(channel_id, message)| {
+        match transforms.outbound(mem::take(message)) {
+            Ok(transformed) => {
+                *message = transformed;
+                true
+            }
+            Err(e) => {
+                if errors.report(
+                    ReplicationErrorKind::Serialize,
+                    Some(channel_id.get()),
+                    None,
+                    &e,
+                ) {
+                    error!("discarding message for server that failed to transform: {e}");
+                }
+                false
+            }
+        }
+    });
+}
+
 /// Receives and applies replication messages from the server.
 ///
 /// Update messages are sent over the [`ServerChannel::Updates`] and are applied first to ensure valid state
@@ -126,9 +267,94 @@ impl Plugin for ClientPlugin {
 pub(super) fn receive_replication(
     world: &mut World,
     mut scratch: Local<EntityScratch>,
+    mut staged: Local<StagedEntities>,
     mut entity_markers: Local<EntityMarkers>,
     mut entity_buffer: Local<EntityBuffer>,
 ) {
+    let start = world
+        .get_resource_mut::<ReplicationApplyStats>()
+        .map(|mut stats| {
+            *stats = Default::default();
+            Instant::now()
+        });
+
+    if world.contains_resource::<ClientReplicationSink>() {
+        world.resource_scope(|world, mut sink: Mut<ClientReplicationSink>| {
+            sink.copy_messages(world)
+        });
+    }
+    for_each_target(world, |world| {
+        receive_scope(
+            world,
+            &mut scratch,
+            &mut staged,
+            &mut entity_markers,
+            &mut entity_buffer,
+            apply_replication,
+        )
+    });
+
+    if let Some(start) = start {
+        world.resource_mut::<ReplicationApplyStats>().apply_time = start.elapsed();
+    }
+}
+
+/// Applies operations deferred by [`DestructiveGates`] that are no longer gated.
+///
+/// Runs before receiving new messages to preserve the order.
+fn apply_ungated(
+    world: &mut World,
+    mut scratch: Local<EntityScratch>,
+    mut staged: Local<StagedEntities>,
+    mut entity_markers: Local<EntityMarkers>,
+    mut entity_buffer: Local<EntityBuffer>,
+) {
+    for_each_target(world, |world| {
+        // Avoid moving all receive resources out of the world when there is nothing to apply.
+        if !world.resource::<DestructiveGates>().has_pending() {
+            return;
+        }
+
+        receive_scope(
+            world,
+            &mut scratch,
+            &mut staged,
+            &mut entity_markers,
+            &mut entity_buffer,
+            |world, params, _, _| {
+                for (entity, ops) in params.gates.drain_ready(world) {
+                    apply_pending(world, params, entity, ops);
+                }
+            },
+        )
+    });
+}
+
+/// Calls `f` for each world into which replication is applied.
+///
+/// See [`ClientReplicationSink`] for details.
+fn for_each_target(world: &mut World, mut f: impl FnMut(&mut World)) {
+    let Some(mut sink) = world.remove_resource::<ClientReplicationSink>() else {
+        (f)(world);
+        return;
+    };
+
+    sink.scope(world, &mut f);
+    if sink.mode() == SinkMode::Both {
+        (f)(world);
+    }
+    world.insert_resource(sink);
+}
+
+/// Temporarily removes resources needed to apply replication, passes them to `f` and inserts them back.
+fn receive_scope<T>(
+    world: &mut World,
+    scratch: &mut EntityScratch,
+    staged: &mut StagedEntities,
+    entity_markers: &mut EntityMarkers,
+    entity_buffer: &mut EntityBuffer,
+    f: impl FnOnce(&mut World, &mut ReceiveParams, &mut ClientMessages, &mut BufferedMutations) -> T,
+) -> T {
     // Too many nested `resource_scope` break rustfmt.
     // Relevant issue to support multiple resources in a single scope: https://github.com/bevyengine/bevy/issues/23476
     let mut messages = world.remove_resource::<ClientMessages>().unwrap();
@@ -139,43 +365,65 @@ pub(super) fn receive_replication(
     let mut buffered_mutations = world.remove_resource::<BufferedMutations>().unwrap();
     let receive_markers = world.remove_resource::<ReceiveMarkers>().unwrap();
     let registry = world.remove_resource::<ReplicationRegistry>().unwrap();
+    let mut unknown_fns = world.remove_resource::<UnknownFns>().unwrap();
+    let mut gates = world.remove_resource::<DestructiveGates>().unwrap();
     let mut replicated = world
         .remove_resource::<Messages<EntityReplicated>>()
         .unwrap();
 
     let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let apply_mode = *world.resource::<UpdateApplyMode>();
     let mut stats = world.remove_resource::<ClientReplicationStats>();
+    let mut apply_stats = world.remove_resource::<ReplicationApplyStats>();
 
     let mut params = ReceiveParams {
-        scratch: &mut scratch,
-        entity_markers: &mut entity_markers,
-        entity_buffer: &mut entity_buffer,
+        scratch,
+        staged,
+        apply_mode,
+        entity_markers,
+        entity_buffer,
         entity_map: &mut entity_map,
         signature_map: &mut signature_map,
         storage: &mut storage,
         mutate_ticks: &mut mutate_ticks,
         replicated: &mut replicated,
+        applied: Vec::new(),
+        gates: &mut gates,
         stats: stats.as_mut(),
+        apply_stats: apply_stats.as_mut(),
         receive_markers: &receive_markers,
         registry: &registry,
+        unknown_fns: &mut unknown_fns,
         type_registry: &type_registry,
     };
 
-    apply_replication(world, &mut params, &mut messages, &mut buffered_mutations);
+    world.resource_mut::<ReplicationBatch>().active = true;
+    let result = (f)(world, &mut params, &mut messages, &mut buffered_mutations);
+    world.resource_mut::<ReplicationBatch>().active = false;
 
     if let Some(stats) = stats {
         world.insert_resource(stats);
     }
+    if let Some(apply_stats) = apply_stats {
+        world.insert_resource(apply_stats);
+    }
 
     world.insert_resource(messages);
     world.insert_resource(entity_map);
+    for entity in world.resource_mut::<SignatureRemovals>().drain(..) {
+        signature_map.remove(entity);
+    }
     world.insert_resource(signature_map);
     world.insert_resource(storage);
     world.insert_resource(mutate_ticks);
     world.insert_resource(buffered_mutations);
     world.insert_resource(receive_markers);
     world.insert_resource(registry);
+    world.insert_resource(unknown_fns);
+    world.insert_resource(gates);
     world.insert_resource(replicated);
+
+    result
 }
 
 // The storage resource may be unavailable while receiving replication.
@@ -187,41 +435,157 @@ fn cleanup_storage(remove: On<Remove, Remote>, mut storage: If<ResMut<Replicatio
 // The server can despawn an entity without sending a replication message,
 // so we need to manually remove the entity from the `ServerEntityMap`
 // when it is despawned on the client.
-fn cleanup_entity_map(despawn: On<Despawn, Remote>, mut entity_map: If<ResMut<ServerEntityMap>>) {
-    entity_map.remove_by_client(despawn.entity);
+// With `SignatureSettings::rebind_on_respawn`, the server entity is kept
+// for the next entity with the same signature.
+fn cleanup_entity_map(
+    despawn: On<Despawn, Remote>,
+    mut commands: Commands,
+    settings: Res<SignatureSettings>,
+    mut entity_map: If<ResMut<ServerEntityMap>>,
+    signature_map: Option<ResMut<SignatureMap>>,
+    signatures: Query<&Signature>,
+) {
+    let Some(server_entity) = entity_map.remove_by_client(despawn.entity) else {
+        return;
+    };
+
+    if !settings.rebind_on_respawn {
+        return;
+    }
+    let (Some(mut signature_map), Ok(signature)) = (signature_map, signatures.get(despawn.entity))
+    else {
+        return;
+    };
+
+    let hash = signature.hash();
+    if let Some(next) = signature_map.waiting(hash).next() {
+        // The hash will be passed to the waiting entity on removal.
+        debug!("rebinding `{server_entity}` to waiting `{next}` using hash 0x{hash:016x}");
+        entity_map.insert(server_entity, next);
+        commands.entity(next).insert(Remote);
+    } else {
+        signature_map.release(hash, server_entity);
+    }
+}
+
+/// Binds a server entity released by [`cleanup_entity_map`] to a respawned entity.
+fn rebind_signature(
+    add: On<Add, Signature>,
+    mut commands: Commands,
+    settings: Res<SignatureSettings>,
+    mut entity_map: If<ResMut<ServerEntityMap>>,
+    mut signature_map: If<ResMut<SignatureMap>>,
+    signatures: Query<&Signature>,
+) {
+    if !settings.rebind_on_respawn {
+        return;
+    }
+
+    let hash = signatures.get(add.entity).unwrap().hash();
+    if signature_map.get(hash) != Some(add.entity) {
+        return;
+    }
+    let Some(server_entity) = signature_map.take_released(hash) else {
+        return;
+    };
+    if entity_map.to_client().contains_key(&server_entity) {
+        return;
+    }
+
+    debug!(
+        "rebinding `{server_entity}` to respawned `{}` using hash 0x{hash:016x}",
+        add.entity
+    );
+    entity_map.insert(server_entity, add.entity);
+    commands.entity(add.entity).insert(Remote);
 }
 
 fn reset(
+    mut commands: Commands,
     mut messages: ResMut<ClientMessages>,
     mut stats: ResMut<ClientStats>,
     mut update_tick: ResMut<ServerUpdateTick>,
+    mut timeline: ResMut<TickTimeline>,
     mut entity_map: ResMut<ServerEntityMap>,
+    mut signature_map: ResMut<SignatureMap>,
     mut buffered_mutations: ResMut<BufferedMutations>,
+    mut unknown_fns: ResMut<UnknownFns>,
+    mut gates: ResMut<DestructiveGates>,
     mutate_ticks: Option<ResMut<ServerMutateTicks>>,
     replication_stats: Option<ResMut<ClientReplicationStats>>,
+    apply_stats: Option<ResMut<ReplicationApplyStats>>,
 ) {
+    commands.remove_resource::<ConnectionSeed>();
     messages.clear();
     *stats = Default::default();
     *update_tick = Default::default();
+    timeline.clear();
     entity_map.clear();
+    signature_map.clear_released();
     buffered_mutations.clear();
+    unknown_fns.clear();
+    gates.clear();
     if let Some(mut mutate_ticks) = mutate_ticks {
         mutate_ticks.clear();
     }
     if let Some(mut replication_stats) = replication_stats {
         *replication_stats = Default::default();
     }
+    if let Some(mut apply_stats) = apply_stats {
+        *apply_stats = Default::default();
+    }
 }
 
-fn send_protocol_hash(mut commands: Commands, protocol: Res<ProtocolHash>) {
-    debug!("sending `{:?}` to the server", *protocol);
-    commands.client_trigger(*protocol);
+fn receive_connection_seed(seed: On<ConnectionSeed>, mut commands: Commands) {
+    debug!("received `{:?}`", *seed);
+    commands.insert_resource(*seed);
 }
 
-fn log_protocol_error(_on: On<ProtocolMismatch>) {
-    error!(
-        "server reported protocol mismatch; make sure replication rules and events registration order match with the server"
-    );
+fn send_auth_request(
+    mut commands: Commands,
+    protocol: Res<ProtocolHash>,
+    breakdown: Res<ProtocolBreakdown>,
+    token: Option<Res<AuthToken>>,
+) {
+    debug!("sending authorization request with `{:?}`", *protocol);
+    commands.client_trigger(AuthRequest {
+        protocol: *protocol,
+        optional: breakdown.optional.clone(),
+        token: token.map(|token| token.0.clone()),
+    });
+}
+
+fn report_auth_failure(failed: On<AuthorizationFailed>, mut commands: Commands) {
+    match &failed.reason {
+        AuthDenial::ProtocolMismatch(server) => commands.trigger(ProtocolMismatch {
+            server: server.clone(),
+        }),
+        reason => error!("server denied authorization due to `{reason:?}`"),
+    }
+}
+
+fn log_protocol_error(mismatch: On<ProtocolMismatch>, breakdown: Res<ProtocolBreakdown>) {
+    let mut domains = breakdown.mismatched_domains(&mismatch.server).peekable();
+    if mismatch.server.registrations != breakdown.registrations {
+        error!(
+            "server reported protocol mismatch; make sure replication rules and events registration order match with the server"
+        );
+    } else if !breakdown.optional_prefix_of(&mismatch.server) {
+        error!(
+            "server reported protocol mismatch; make sure optional replication rules are a prefix of the server's"
+        );
+    } else if domains.peek().is_none() {
+        error!(
+            "server reported protocol mismatch; make sure domains are mixed in the same order as on the server"
+        );
+    }
+    for label in domains {
+        error!(
+            "server reported protocol mismatch for domain `{label}` (client: `{:?}`, server: `{:?}`)",
+            breakdown.domain(label),
+            mismatch.server.domain(label),
+        );
+    }
 }
 
 /// Reads all received messages and applies them.
@@ -233,13 +597,36 @@ fn apply_replication(
     messages: &mut ClientMessages,
     buffered_mutations: &mut BufferedMutations,
 ) {
+    let updates_count = messages.received_count(ServerChannel::Updates);
+    let mut update_ack = None;
     for mut message in messages.receive(ServerChannel::Updates) {
-        if let Err(e) = apply_update_message(world, params, &mut message) {
-            error!("unable to apply update message: {e}");
+        match apply_update_message(world, params, &mut message) {
+            Ok(Some(message_tick)) => update_ack = Some(message_tick),
+            Ok(None) => (),
+            Err(e) => {
+                if world.resource_mut::<ErrorReporter>().report(
+                    ReplicationErrorKind::Apply,
+                    Some(ServerChannel::Updates.into()),
+                    None,
+                    &e,
+                ) {
+                    error!("unable to apply update message: {e}");
+                }
 
-            // SAFETY: components in the scratch were pushed using this world.
-            unsafe { params.scratch.manual_drop(world.components()) };
-            params.entity_buffer.free(world);
+                // SAFETY: components in the scratch were pushed using this world.
+                unsafe { params.scratch.manual_drop(world.components()) };
+                // SAFETY: staged components were pushed using this world.
+                unsafe { params.staged.manual_drop(world.components()) };
+                params.entity_buffer.free(world);
+            }
+        }
+    }
+    if let Some(message_tick) = update_ack {
+        // Update messages are ordered, so it's enough to acknowledge only the last one.
+        let mut ack = Vec::with_capacity(RepliconTick::POSTCARD_MAX_SIZE);
+        match postcard_utils::to_extend_mut(&message_tick, &mut ack) {
+            Ok(()) => messages.send(ClientChannel::UpdateAcks, ack),
+            Err(e) => error!("unable to serialize update ack: {e}"),
         }
     }
 
@@ -249,27 +636,103 @@ fn apply_replication(
     // but skip outdated data per-entity by checking last received tick for it
     // (unless user requested history via marker).
     let update_tick = *world.resource::<ServerUpdateTick>();
-    let mutations_count = messages.received_count(ServerChannel::Mutations);
+    let channels = world.resource::<RepliconChannels>();
+    let mutation_channels = MutationChannel::ALL.map(|channel| channels.mutation_channel(channel));
+    let mutations_count: usize = mutation_channels
+        .into_iter()
+        .flatten()
+        .map(|channel_id| messages.received_count(channel_id))
+        .sum();
     if mutations_count != 0 {
         let mut acks = Vec::with_capacity(MutateIndex::POSTCARD_MAX_SIZE * mutations_count);
-        for message in messages.receive(ServerChannel::Mutations) {
-            if let Err(e) = buffer_mutate_message(params, buffered_mutations, message, &mut acks) {
-                error!("unable to buffer mutate message: {e}");
+        for (channel, channel_id) in MutationChannel::ALL.into_iter().zip(mutation_channels) {
+            let Some(channel_id) = channel_id else {
+                continue;
+            };
+            for message in messages.receive(channel_id) {
+                if let Err(e) =
+                    buffer_mutate_message(params, buffered_mutations, channel, message, &mut acks)
+                    && world.resource_mut::<ErrorReporter>().report(
+                        ReplicationErrorKind::Deserialize,
+                        Some(channel_id.get()),
+                        None,
+                        &e,
+                    )
+                {
+                    error!("unable to buffer mutate message: {e}");
+                }
             }
         }
         messages.send(ClientChannel::MutationAcks, acks);
     }
 
+    // Buffered mutations are sorted by tick in descending order.
+    let newest_tick = buffered_mutations
+        .0
+        .first()
+        .map(|mutate| mutate.message_tick)
+        .filter(|&tick| tick.is_newer(*update_tick))
+        .unwrap_or(*update_tick);
+    if updates_count + mutations_count != 0
+        && let Some(time) = world.get_resource::<Time>()
+    {
+        let elapsed = time.elapsed();
+        world
+            .resource_mut::<TickTimeline>()
+            .record(newest_tick, elapsed);
+    }
+
+    let playout_tick = world
+        .get_resource::<MutationJitterBuffer>()
+        .zip(world.get_resource::<Time>())
+        .map(|(&jitter, time)| {
+            jitter.playout_tick(world.resource::<TickTimeline>(), time.elapsed())
+        });
+
+    params.applied.clear();
+    let mut applied_tick = None;
     buffered_mutations.0.retain_mut(|mutate| {
         if mutate.update_tick.is_newer(*update_tick) {
             return true;
         }
+        match playout_tick {
+            Some(Some(tick)) if mutate.message_tick.is_newer(tick) => {
+                trace!(
+                    "holding mutate message for {:?} until {tick:?}",
+                    mutate.message_tick
+                );
+                return true;
+            }
+            Some(None) => {
+                trace!(
+                    "holding mutate message for {:?} until the tick rate is estimated",
+                    mutate.message_tick
+                );
+                return true;
+            }
+            _ => (),
+        }
+
+        // Messages are sorted by tick, so all messages for a tick are applied one after another.
+        if let Some(tick) = applied_tick
+            && tick != mutate.message_tick
+        {
+            trigger_mutations_applied(world, params, tick);
+        }
+        applied_tick = Some(mutate.message_tick);
 
         if let Err(e) = apply_mutate_message(world, params, mutate) {
-            error!(
-                "unable to apply mutate message for tick `{:?}`: {e}",
-                mutate.message_tick
-            );
+            if world.resource_mut::<ErrorReporter>().report(
+                ReplicationErrorKind::Apply,
+                mutation_channels[mutate.channel as usize].map(ServerChannelId::get),
+                None,
+                &e,
+            ) {
+                error!(
+                    "unable to apply mutate message for tick `{:?}`: {e}",
+                    mutate.message_tick
+                );
+            }
 
             // SAFETY: components in the scratch were pushed using this world.
             unsafe { params.scratch.manual_drop(world.components()) };
@@ -278,25 +741,62 @@ fn apply_replication(
 
         false
     });
+    if let Some(tick) = applied_tick {
+        trigger_mutations_applied(world, params, tick);
+    }
+
+    if let Some(apply_stats) = &mut params.apply_stats {
+        apply_stats.queued_mutations = buffered_mutations.0.len();
+    }
+}
+
+/// Triggers [`MutationsApplied`] for mutations applied for the given tick, if any.
+fn trigger_mutations_applied(world: &mut World, params: &mut ReceiveParams, tick: RepliconTick) {
+    if params.applied.is_empty() {
+        return;
+    }
+
+    world.trigger(MutationsApplied {
+        message_tick: tick,
+        entities: take_applied(params),
+    });
+}
+
+/// Takes entities that received data, without duplicates.
+fn take_applied(params: &mut ReceiveParams) -> Vec<Entity> {
+    let mut entities = mem::take(&mut params.applied);
+    entities.sort_unstable();
+    entities.dedup();
+    entities
 }
 
 /// Reads and applies an update message.
 ///
 /// For details see [`replication_messages`](crate::server::replication_messages).
+///
+/// Returns the message tick if the server requested an acknowledgment.
 fn apply_update_message(
     world: &mut World,
     params: &mut ReceiveParams,
     message: &mut Bytes,
-) -> Result<()> {
+) -> Result<Option<RepliconTick>> {
     if let Some(stats) = &mut params.stats {
         stats.messages += 1;
         stats.bytes += message.len();
     }
+    if let Some(apply_stats) = &mut params.apply_stats {
+        apply_stats.messages += 1;
+        apply_stats.bytes += message.len();
+    }
 
     let flags: UpdateFlags = postcard_utils::from_buf(message)?;
+    if flags.contains_unknown_bits() {
+        return Err(format!("received unknown update flags `{flags:?}`").into());
+    }
     let message_tick = postcard_utils::from_buf(message)?;
     trace!("applying update message with `{flags:?}` for {message_tick:?}");
     world.resource_mut::<ServerUpdateTick>().0 = message_tick;
+    params.applied.clear();
 
     let last_flag = flags.last();
     for (_, flag) in flags.iter_names() {
@@ -307,6 +807,7 @@ fn apply_update_message(
         };
 
         match flag {
+            UpdateFlags::ACK => (),
             UpdateFlags::USERDATA => {
                 process_userdata(world, message, message_tick)
                     .map_err(|e| format!("unable to process userdata: {e}"))?;
@@ -328,6 +829,9 @@ fn apply_update_message(
                 if let Some(stats) = &mut params.stats {
                     stats.despawns += len;
                 }
+                if let Some(apply_stats) = &mut params.apply_stats {
+                    apply_stats.entities_despawned += len;
+                }
             }
             UpdateFlags::REMOVALS => {
                 let len = apply_array(array_kind, message, |message| {
@@ -352,7 +856,13 @@ fn apply_update_message(
         }
     }
 
-    Ok(())
+    params.staged.flush(world);
+    world.trigger(UpdateApplied {
+        message_tick,
+        entities: take_applied(params),
+    });
+
+    Ok(flags.contains(UpdateFlags::ACK).then_some(message_tick))
 }
 
 /// Reads and buffers mutate message.
@@ -363,6 +873,7 @@ fn apply_update_message(
 fn buffer_mutate_message(
     params: &mut ReceiveParams,
     buffered_mutations: &mut BufferedMutations,
+    channel: MutationChannel,
     mut message: Bytes,
     acks: &mut Vec<u8>,
 ) -> Result<()> {
@@ -370,15 +881,23 @@ fn buffer_mutate_message(
         stats.messages += 1;
         stats.bytes += message.len();
     }
+    if let Some(apply_stats) = &mut params.apply_stats {
+        apply_stats.messages += 1;
+        apply_stats.bytes += message.len();
+    }
 
     let flags: MutateFlags = postcard_utils::from_buf(&mut message)?;
+    if flags.contains_unknown_bits() {
+        return Err(format!("received unknown mutate flags `{flags:?}`").into());
+    }
     let mutate_index: MutateIndex = postcard_utils::from_buf(&mut message)?;
     postcard_utils::to_extend_mut(&mutate_index, acks)?;
 
     let update_tick = postcard_utils::from_buf(&mut message)?;
     let message_tick = postcard_utils::from_buf(&mut message)?;
-    trace!("received mutate message for {message_tick:?}");
+    trace!("received mutate message for {message_tick:?} on `{channel:?}`");
     buffered_mutations.insert(BufferedMutate {
+        channel,
         flags,
         update_tick,
         message_tick,
@@ -411,7 +930,7 @@ fn apply_mutate_message(
             }
             MutateFlags::MUTATIONS => {
                 let len = apply_array(ArrayKind::Dynamic, &mut mutate.message, |message| {
-                    apply_mutations(world, params, message, mutate.message_tick)
+                    apply_mutations(world, params, message, mutate.channel, mutate.message_tick)
                 })
                 .map_err(|e| format!("unable to apply mutations: {e}"))?;
                 if let Some(stats) = &mut params.stats {
@@ -442,9 +961,37 @@ fn apply_entity_mapping(
         return Ok(());
     };
 
-    debug!("mapping `{server_entity}` to `{client_entity}` using hash 0x{hash:016x}");
+    let mut entity = world
+        .get_entity_mut(client_entity)
+        .map_err(|e| format!("unable to map `{server_entity}` using hash 0x{hash:016x}: {e}"))?;
+
+    // The server may despawn an entity and spawn another with the same signature,
+    // but mappings are applied before despawns, so the previous mapping needs to be replaced.
+    let previous = params
+        .entity_map
+        .to_server()
+        .get(&client_entity)
+        .copied()
+        .filter(|&previous| previous != server_entity);
+    if let Some(previous) = previous {
+        debug!(
+            "rebinding `{client_entity}` from `{previous}` to `{server_entity}` using hash 0x{hash:016x}"
+        );
+        params.entity_map.remove_by_client(client_entity);
+    } else {
+        debug!("mapping `{server_entity}` to `{client_entity}` using hash 0x{hash:016x}");
+    }
+
     params.entity_map.insert(server_entity, client_entity);
-    world.entity_mut(client_entity).insert(Remote);
+    entity.insert(Remote);
+
+    if let Some(previous) = previous {
+        world.trigger(SignatureRebound {
+            entity: client_entity,
+            previous,
+            server_entity,
+        });
+    }
 
     Ok(())
 }
@@ -460,21 +1007,42 @@ fn apply_despawn(
     // with the last replication message, but the server might not yet have received confirmation
     // from the client and could include the deletion in the this message.
     let server_entity = postcard_utils::entity_from_buf(message)?;
+    params.signature_map.forget_released(server_entity);
     if let Some(client_entity) = params.entity_map.server_entry(server_entity).remove() {
         // Requires manual removal since these resources are removed from the world and inaccessible to observers.
         params.signature_map.remove(client_entity);
         params.storage.entities.remove(&client_entity);
 
-        if let Ok(client_entity) = world.get_entity_mut(client_entity) {
-            trace!("applying despawn for `{}`", client_entity.id());
-            let ctx = DespawnCtx { message_tick };
-            (params.registry.despawn)(&ctx, client_entity);
+        if params
+            .gates
+            .should_defer(world, client_entity, DestructiveKind::Despawn)
+        {
+            params
+                .gates
+                .defer(client_entity, PendingOp::Despawn { message_tick });
+        } else {
+            despawn(world, params, client_entity, message_tick);
         }
     }
 
     Ok(())
 }
 
+fn despawn(
+    world: &mut World,
+    params: &ReceiveParams,
+    client_entity: Entity,
+    message_tick: RepliconTick,
+) {
+    if let Ok(client_entity) = world.get_entity_mut(client_entity) {
+        trace!("applying despawn for `{}`", client_entity.id());
+        if let Err(client_entity) = EntityPool::release(params.registry, client_entity) {
+            let ctx = DespawnCtx { message_tick };
+            (params.registry.despawn)(&ctx, client_entity);
+        }
+    }
+}
+
 /// Deserializes and applies component removals for an entity.
 fn apply_removals(
     world: &mut World,
@@ -492,13 +1060,19 @@ fn apply_removals(
         .get(&server_entity)
         .ok_or_else(|| format!("received removal for unknown server's `{server_entity}`"))?;
 
+    let deferred = params
+        .gates
+        .should_defer(world, client_entity, DestructiveKind::Removal);
+
+    // Stage removals to write them together with other changes for this entity
+    // from the same message. This way observers never see a partially updated entity.
     let Ok(mut client_entity) = world
         .get_entity_mut(client_entity)
-        .map(|entity| DeferredEntity::new(entity, params.scratch))
+        .map(|entity| params.staged.stage(entity))
     else {
         // Client could predict despawn.
         debug!("ignoring removals for despawned `{client_entity}`");
-        message.advance(data_size);
+        skip_data(message, data_size)?;
         return Ok(());
     };
 
@@ -506,22 +1080,63 @@ fn apply_removals(
         .entity_markers
         .read(params.receive_markers, &*client_entity);
 
-    confirm_tick(&mut client_entity, params.replicated, message_tick);
+    confirm_tick(
+        &mut client_entity,
+        params.replicated,
+        &mut params.applied,
+        message_tick,
+    );
 
-    let mut data = message.split_to(data_size);
-    let len = apply_array(ArrayKind::Dynamic, &mut data, |data| {
-        let fns_id = postcard_utils::from_buf(data)?;
-        let (_, component_id, fns) = params.registry.get(fns_id);
-        let mut ctx = RemoveCtx {
-            message_tick,
-            component_id,
-        };
-        trace!(
-            "applying removal for `{}` with `{fns_id:?}`",
-            client_entity.id()
+    let mut data = split_data(message, data_size)?;
+    if deferred {
+        let mut fns_ids = Vec::new();
+        apply_array(ArrayKind::Dynamic, &mut data, |data| {
+            let removal_id: RemovalId = postcard_utils::from_buf(data)?;
+            for fns_id in removal_id.fns_ids() {
+                if params.registry.try_get(fns_id).is_none() {
+                    // Components of a rule have consecutive IDs, so the rest are unknown too.
+                    params
+                        .unknown_fns
+                        .warn("removal", fns_id, client_entity.id());
+                    break;
+                }
+                fns_ids.push(fns_id);
+            }
+            Ok(())
+        })?;
+        params.gates.defer(
+            client_entity.id(),
+            PendingOp::Removal {
+                message_tick,
+                fns_ids,
+            },
         );
+        return Ok(());
+    }
 
-        fns.remove(&mut ctx, params.entity_markers, &mut client_entity);
+    let mut len = 0;
+    apply_array(ArrayKind::Dynamic, &mut data, |data| {
+        let removal_id: RemovalId = postcard_utils::from_buf(data)?;
+        for fns_id in removal_id.fns_ids() {
+            let Some((_, component_id, fns)) = params.registry.try_get(fns_id) else {
+                // Components of a rule have consecutive IDs, so the rest are unknown too.
+                params
+                    .unknown_fns
+                    .warn("removal", fns_id, client_entity.id());
+                break;
+            };
+            let mut ctx = RemoveCtx {
+                message_tick,
+                component_id,
+            };
+            trace!(
+                "applying removal for `{}` with `{fns_id:?}`",
+                client_entity.id()
+            );
+
+            fns.remove(&mut ctx, params.entity_markers, &mut client_entity);
+            len += 1;
+        }
 
         Ok(())
     })?;
@@ -529,12 +1144,56 @@ fn apply_removals(
     if let Some(stats) = &mut params.stats {
         stats.components_changed += len;
     }
-
-    client_entity.flush();
+    if let Some(apply_stats) = &mut params.apply_stats {
+        apply_stats.components_removed += len;
+    }
 
     Ok(())
 }
 
+/// Applies operations deferred by [`DestructiveGates`] for an entity.
+fn apply_pending(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    entity: Entity,
+    ops: Vec<PendingOp>,
+) {
+    for op in ops {
+        match op {
+            PendingOp::Despawn { message_tick } => despawn(world, params, entity, message_tick),
+            PendingOp::Removal {
+                message_tick,
+                fns_ids,
+            } => {
+                let Ok(mut client_entity) = world
+                    .get_entity_mut(entity)
+                    .map(|entity| DeferredEntity::new(entity, params.scratch))
+                else {
+                    debug!("ignoring deferred removals for despawned `{entity}`");
+                    continue;
+                };
+
+                params
+                    .entity_markers
+                    .read(params.receive_markers, &*client_entity);
+
+                for fns_id in fns_ids {
+                    let (_, component_id, fns) = params.registry.get(fns_id);
+                    let mut ctx = RemoveCtx {
+                        message_tick,
+                        component_id,
+                    };
+                    trace!("applying deferred removal for `{entity}` with `{fns_id:?}`");
+
+                    fns.remove(&mut ctx, params.entity_markers, &mut client_entity);
+                }
+
+                client_entity.flush();
+            }
+        }
+    }
+}
+
 /// Deserializes and applies component insertions and/or mutations for an entity.
 fn apply_changes(
     world: &mut World,
@@ -551,47 +1210,73 @@ fn apply_changes(
     let server_entity = postcard_utils::entity_from_buf(message)?;
     let data_size: usize = postcard_utils::from_buf(message)?;
 
+    if let Some(&client_entity) = params.entity_map.to_client().get(&server_entity)
+        && let Some(ops) = params.gates.take(client_entity)
+    {
+        debug!("applying deferred operations for `{client_entity}` before changes");
+        apply_pending(world, params, client_entity, ops);
+    }
+
     let world_cell = world.as_unsafe_world_cell();
     let entity_allocator = world_cell.entity_allocator();
     // SAFETY: used only to create `DeferredEntity`, which won't let mutably alias `EntityAllocator`.
     let world = unsafe { world_cell.world_mut() };
 
-    let mut client_entity = match params.entity_map.server_entry(server_entity) {
-        EntityEntry::Occupied(entry) => {
-            let Ok(client_entity) = world.get_entity_mut(entry.get()) else {
-                // Client could predict despawn.
-                debug!("ignoring changes for despawned `{}`", entry.get());
-                message.advance(data_size);
-                return Ok(());
-            };
-
-            let mut client_entity = DeferredEntity::new(client_entity, params.scratch);
-            if !client_entity.contains::<Remote>() {
-                // Even though the entity already exists, it could have been spawned during
-                // deserialization of another component and doesn't have the marker yet.
-                client_entity.insert(Remote);
-            }
-            client_entity
-        }
+    let client_entity = match params.entity_map.server_entry(server_entity) {
+        EntityEntry::Occupied(entry) => entry.get(),
         EntityEntry::Vacant(entry) => {
-            let mut client_entity = DeferredEntity::new(world.spawn_empty(), params.scratch);
-            client_entity.insert(Remote);
-            entry.insert(client_entity.id());
-            client_entity
+            if let Some(apply_stats) = &mut params.apply_stats {
+                apply_stats.entities_spawned += 1;
+            }
+            let entity = EntityPool::take(world, params.registry, message, data_size)
+                .unwrap_or_else(|| world.spawn_empty().id());
+            entry.insert(entity)
         }
     };
 
+    let Ok(client_entity) = world.get_entity_mut(client_entity) else {
+        // Client could predict despawn.
+        debug!("ignoring changes for despawned `{client_entity}`");
+        skip_data(message, data_size)?;
+        return Ok(());
+    };
+
+    let staged = params.apply_mode == UpdateApplyMode::Deferred
+        || params.staged.contains(client_entity.id());
+    let mut client_entity = if staged {
+        params.staged.stage(client_entity)
+    } else {
+        DeferredEntity::new(client_entity, params.scratch)
+    };
+    if !client_entity.contains::<Remote>() {
+        // Even if the entity already exists, it could have been spawned during
+        // deserialization of another component and doesn't have the marker yet.
+        client_entity.insert(Remote);
+    }
+
     params
         .entity_markers
         .read(params.receive_markers, &*client_entity);
 
-    confirm_tick(&mut client_entity, params.replicated, message_tick);
+    confirm_tick(
+        &mut client_entity,
+        params.replicated,
+        &mut params.applied,
+        message_tick,
+    );
 
-    let mut data = message.split_to(data_size);
+    let mut data = split_data(message, data_size)?;
     let len = apply_array(ArrayKind::Dynamic, &mut data, |data| {
         let spawner = BufferedSpawner::new(entity_allocator, params.entity_buffer);
         let fns_id = postcard_utils::from_buf(data)?;
-        let (_, component_id, fns) = params.registry.get(fns_id);
+        let mut component_data = split_component(params.registry, fns_id, data)?;
+        let Some((_, component_id, fns)) = params.registry.try_get(fns_id) else {
+            params
+                .unknown_fns
+                .warn("change", fns_id, client_entity.id());
+            return Ok(());
+        };
+        let data = component_data.as_mut().unwrap_or(data);
         let mut ctx = WriteCtx {
             entity: client_entity.id(),
             component_id,
@@ -607,6 +1292,14 @@ fn apply_changes(
             client_entity.id(),
         );
 
+        if let Some(apply_stats) = &mut params.apply_stats {
+            if client_entity.contains_id(component_id) {
+                apply_stats.components_mutated += 1;
+            } else {
+                apply_stats.components_inserted += 1;
+            }
+        }
+
         fns.write(&mut ctx, params.entity_markers, &mut client_entity, data)?;
 
         Ok(())
@@ -620,27 +1313,62 @@ fn apply_changes(
     params
         .entity_buffer
         .spawn(unsafe { client_entity.world_mut() });
-    client_entity.flush();
+    if !staged {
+        client_entity.flush();
+    }
 
     Ok(())
 }
 
+/// Splits off the next `data_size` bytes from the message.
+///
+/// Returns an error instead of panicking if the message is shorter.
+fn split_data(message: &mut Bytes, data_size: usize) -> Result<Bytes> {
+    if data_size > message.len() {
+        return Err(format!(
+            "data size ({data_size}) exceeds remaining message length ({})",
+            message.len()
+        )
+        .into());
+    }
+
+    Ok(message.split_to(data_size))
+}
+
+/// Splits off data of a single component if it's prefixed with its size.
+///
+/// Returns [`None`] if the component data isn't prefixed and needs to be read from the message directly.
+/// See [`ReplicationRegistry::is_size_prefixed`].
+fn split_component(
+    registry: &ReplicationRegistry,
+    fns_id: FnsId,
+    message: &mut Bytes,
+) -> Result<Option<Bytes>> {
+    if !registry.is_size_prefixed(fns_id) {
+        return Ok(None);
+    }
+
+    let size = registry::read_component_size(message)
+        .ok_or_else(|| format!("missing size for `{fns_id:?}` data"))?;
+    split_data(message, size).map(Some)
+}
+
+/// Like [`split_data`], but discards the data.
+fn skip_data(message: &mut Bytes, data_size: usize) -> Result<()> {
+    split_data(message, data_size)?;
+    Ok(())
+}
+
 fn process_userdata(
     world: &mut World,
     message: &mut Bytes,
     message_tick: RepliconTick,
 ) -> Result<()> {
     let len = postcard_utils::from_buf(message)?;
-    if len > message.len() {
-        return Err(format!(
-            "userdata length ({len}) exceeds remaining message length ({})",
-            message.len()
-        )
-        .into());
-    }
+    let bytes = split_data(message, len).map_err(|e| format!("invalid userdata: {e}"))?;
     world.trigger(UserdataReceived {
         message_tick,
-        bytes: message.split_to(len),
+        bytes,
     });
 
     Ok(())
@@ -684,10 +1412,11 @@ enum ArrayKind {
 fn confirm_tick(
     entity: &mut DeferredEntity,
     replicated: &mut Messages<EntityReplicated>,
+    applied: &mut Vec<Entity>,
     tick: RepliconTick,
 ) {
     if let Some(mut history) = entity.get_mut::<ConfirmHistory>() {
-        history.set_last_tick(tick);
+        history.set_update_tick(tick);
     } else {
         entity.insert(ConfirmHistory::new(tick));
     }
@@ -695,6 +1424,7 @@ fn confirm_tick(
         entity: entity.id(),
         tick,
     });
+    applied.push(entity.id());
 }
 
 fn confirm_mutate_tick(
@@ -718,6 +1448,7 @@ fn apply_mutations(
     world: &mut World,
     params: &mut ReceiveParams,
     message: &mut Bytes,
+    channel: MutationChannel,
     message_tick: RepliconTick,
 ) -> Result<()> {
     debug_assert!(
@@ -732,10 +1463,15 @@ fn apply_mutations(
     let Some(&client_entity) = params.entity_map.to_client().get(&server_entity) else {
         // Mutation could arrive after a despawn from update message.
         debug!("ignoring mutations received for unknown server's `{server_entity}`");
-        message.advance(data_size);
+        skip_data(message, data_size)?;
         return Ok(());
     };
 
+    if let Some(ops) = params.gates.take(client_entity) {
+        debug!("applying deferred operations for `{client_entity}` before mutations");
+        apply_pending(world, params, client_entity, ops);
+    }
+
     let world_cell = world.as_unsafe_world_cell();
     let entity_allocator = world_cell.entity_allocator();
     // SAFETY: used only to create `DeferredEntity`, which won't let mutably alias `EntityAllocator`.
@@ -747,7 +1483,7 @@ fn apply_mutations(
     else {
         // Client could predict despawn.
         debug!("ignoring mutations for despawned `{client_entity}`");
-        message.advance(data_size);
+        skip_data(message, data_size)?;
         return Ok(());
     };
 
@@ -763,13 +1499,15 @@ fn apply_mutations(
         .into());
     };
 
-    let new_tick = message_tick.is_newer(history.last_tick());
+    // Channels are acknowledged independently, so mutations could be outdated only
+    // relative to the data received for the same channel.
+    let new_tick = message_tick.is_newer(history.channel_tick(channel));
     if new_tick {
-        history.set_last_tick(message_tick);
+        history.set_channel_tick(channel, message_tick);
     } else {
         if !params.entity_markers.need_history() {
             trace!("ignoring outdated mutations for `{}`", client_entity.id());
-            message.advance(data_size);
+            skip_data(message, data_size)?;
             return Ok(());
         }
 
@@ -779,7 +1517,7 @@ fn apply_mutations(
                 "discarding {ago} ticks old mutations for `{}`",
                 client_entity.id()
             );
-            message.advance(data_size);
+            skip_data(message, data_size)?;
             return Ok(());
         }
 
@@ -789,12 +1527,22 @@ fn apply_mutations(
         entity: client_entity.id(),
         tick: message_tick,
     });
+    params.applied.push(client_entity.id());
 
-    let mut data = message.split_to(data_size);
+    let mut data = split_data(message, data_size)?;
     let len = apply_array(ArrayKind::Dynamic, &mut data, |data| {
         let spawner = BufferedSpawner::new(entity_allocator, params.entity_buffer);
         let fns_id = postcard_utils::from_buf(data)?;
-        let (_, component_id, fns) = params.registry.get(fns_id);
+        let mut component_data = split_component(params.registry, fns_id, data)?;
+        let Some((_, component_id, fns)) = params.registry.try_get(fns_id) else {
+            // Already warned when the component was inserted.
+            trace!(
+                "skipping mutation with unknown `{fns_id:?}` for `{}`",
+                client_entity.id()
+            );
+            return Ok(());
+        };
+        let data = component_data.as_mut().unwrap_or(data);
         let mut ctx = WriteCtx {
             entity: client_entity.id(),
             component_id,
@@ -828,6 +1576,9 @@ fn apply_mutations(
     if let Some(stats) = &mut params.stats {
         stats.components_changed += len;
     }
+    if let Some(apply_stats) = &mut params.apply_stats {
+        apply_stats.components_mutated += len;
+    }
 
     // SAFETY: only used to spawn entities.
     params
@@ -843,6 +1594,8 @@ fn apply_mutations(
 /// To avoid passing a lot of arguments into all receive functions.
 struct ReceiveParams<'a> {
     scratch: &'a mut EntityScratch,
+    staged: &'a mut StagedEntities,
+    apply_mode: UpdateApplyMode,
     entity_markers: &'a mut EntityMarkers,
     entity_buffer: &'a mut EntityBuffer,
     entity_map: &'a mut ServerEntityMap,
@@ -850,9 +1603,14 @@ struct ReceiveParams<'a> {
     storage: &'a mut ReplicationStorage,
     mutate_ticks: &'a mut ServerMutateTicks,
     replicated: &'a mut Messages<EntityReplicated>,
+    /// Entities that received data from the message being applied.
+    applied: Vec<Entity>,
+    gates: &'a mut DestructiveGates,
     stats: Option<&'a mut ClientReplicationStats>,
+    apply_stats: Option<&'a mut ReplicationApplyStats>,
     receive_markers: &'a ReceiveMarkers,
     registry: &'a ReplicationRegistry,
+    unknown_fns: &'a mut UnknownFns,
     type_registry: &'a AppTypeRegistry,
 }
 
@@ -869,11 +1627,29 @@ pub enum ClientSystems {
     ///
     /// Runs in [`PreUpdate`] and [`OnEnter`] for [`ClientState::Connected`] (to avoid 1 frame delay).
     Receive,
+    /// Systems that apply received replication to the world.
+    ///
+    /// Part of [`Self::Receive`] and always runs before [`Self::EmitQueuedEvents`], so
+    /// received messages and events can reference replicated entities.
+    ///
+    /// This ordering is a stable guarantee. Prediction crates can schedule rollback
+    /// between these sets or after [`Self::Receive`].
+    ///
+    /// Runs in [`PreUpdate`] and [`OnEnter`] for [`ClientState::Connected`] (to avoid 1 frame delay).
+    ApplyReplication,
+    /// Systems that emit received server messages and events, including the ones that were
+    /// queued until their tick was replicated.
+    ///
+    /// Part of [`Self::Receive`] and always runs after [`Self::ApplyReplication`].
+    /// User systems that read server messages should run after [`Self::Receive`].
+    ///
+    /// Runs in [`PreUpdate`] and [`OnEnter`] for [`ClientState::Connected`] (to avoid 1 frame delay).
+    EmitQueuedEvents,
     /// Systems that populate Bevy's [`Diagnostics`](bevy::diagnostic::Diagnostics).
     ///
     /// Runs in [`PreUpdate`] and [`OnEnter`] for [`ClientState::Connected`] (to avoid 1 frame delay).
     Diagnostics,
-    /// System that sends [`ProtocolHash`].
+    /// System that sends [`AuthRequest`] with [`ProtocolHash`].
     ///
     /// Runs in [`OnEnter`] for [`ClientState::Connected`].
     SendHash,
@@ -902,6 +1678,296 @@ pub enum ClientSystems {
 #[derive(Resource, Deref, Default, Reflect, Debug, Clone, Copy)]
 pub struct ServerUpdateTick(RepliconTick);
 
+/// Controls how entities from a single update message are written to the world.
+///
+/// Regardless of the mode, all removals, insertions and mutations for an entity from
+/// a single update message are written together: removals as a single bundle and then
+/// insertions as a single bundle. This way hooks and observers never see an entity with
+/// only part of its components from the message, which keeps required components and
+/// other invariants intact.
+///
+/// Can be changed at runtime.
+#[derive(Resource, Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateApplyMode {
+    /// Writes each entity as soon as its data is deserialized.
+    ///
+    /// Entities with removals are written after the whole message is deserialized,
+    /// since their insertions and mutations come later in the message.
+    ///
+    /// Observers may see references to entities from the same message
+    /// that haven't been written yet.
+    #[default]
+    Immediate,
+    /// Deserializes the whole message first and then writes all entities one after another.
+    ///
+    /// All entities from the message are spawned and mapped before any component is inserted,
+    /// so no mapping happens in between insertions. However, insertion observers still run
+    /// per entity. To react on a consistent set of entities, use [`UpdateApplied`].
+    Deferred,
+}
+
+/// Triggered after an update message is fully applied.
+///
+/// At this point all entities from the message are written, so observers can
+/// follow references between them.
+///
+/// See also [`UpdateApplyMode`] and [`MutationsApplied`].
+#[derive(Event, Debug, Clone)]
+pub struct UpdateApplied {
+    /// Tick of the applied message.
+    pub message_tick: RepliconTick,
+    /// Entities that received changes or removals from the message, in no particular order.
+    ///
+    /// Despawned entities are not included.
+    pub entities: Vec<Entity>,
+}
+
+/// Triggered after mutations for a tick are applied.
+///
+/// Mutations for a single tick may be split across multiple mutate messages, which are applied
+/// together. Unlike per-component observers, it allows reacting to all mutated entities in bulk,
+/// for example, to re-sync physics.
+///
+/// Not triggered if all mutations were outdated and skipped.
+///
+/// See also [`UpdateApplied`].
+#[derive(Event, Debug, Clone)]
+pub struct MutationsApplied {
+    /// Tick of the applied mutations.
+    pub message_tick: RepliconTick,
+    /// Entities that received mutations, in no particular order.
+    pub entities: Vec<Entity>,
+}
+
+/**
+Delays applying received mutate messages to smooth out bursts.
+
+When packets arrive clumped, mutations for several ticks are applied at once,
+followed by a gap. With this resource, mutate messages are held until the estimated
+current server tick from [`TickTimeline`] minus the delay reaches their tick, so they
+are released one tick at a time. Mutate messages are still applied only after
+[`ServerUpdateTick`] reaches their required update tick. Update messages are not delayed.
+
+The delay increases latency, so it should be slightly larger than the expected jitter.
+For [`Self::Ticks`], if the tick rate can't be estimated yet, the delay is counted from the latest
+received tick. [`Self::Time`] can't be converted into ticks without the tick rate, so all mutate
+messages are held until it's estimated, which requires messages for at least 2 ticks.
+
+The resource is not added by default and can be inserted or removed at runtime.
+
+# Examples
+
+```
+use core::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.insert_resource(MutationJitterBuffer::Time(Duration::from_millis(50)));
+```
+*/
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationJitterBuffer {
+    /// Holds mutations for the given number of server ticks.
+    Ticks(u32),
+    /// Holds mutations for the given duration, converted into ticks using [`TickTimeline`].
+    Time(Duration),
+}
+
+impl MutationJitterBuffer {
+    /// Returns the newest tick for which mutations can be applied at the given time.
+    ///
+    /// Returns [`None`] if all mutations should be held.
+    fn playout_tick(self, timeline: &TickTimeline, elapsed: Duration) -> Option<RepliconTick> {
+        let tick = timeline
+            .tick_at(elapsed)
+            .map(|(tick, _)| tick)
+            .or_else(|| timeline.latest().map(|(tick, _)| tick))?;
+        let delay = match self {
+            MutationJitterBuffer::Ticks(ticks) => ticks,
+            MutationJitterBuffer::Time(duration) => timeline
+                .duration_to_ticks(duration)
+                .map(|ticks| ticks.ceil() as u32)?,
+        };
+
+        Some(tick - delay)
+    }
+}
+
+/**
+Indicates whether received replication is currently being written to the world.
+
+Hooks and observers can read it to distinguish replication writes from game writes.
+Commands queued by them may also be applied while it's active.
+
+# Examples
+
+```
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_replicon::prelude::*;
+
+#[derive(Component)]
+#[component(on_insert = log_insertion)]
+struct Health(u32);
+
+fn log_insertion(world: DeferredWorld, ctx: HookContext) {
+    if world.resource::<ReplicationBatch>().is_active() {
+        info!("`{}` received health from the server", ctx.entity);
+    } else {
+        info!("`{}` got health locally", ctx.entity);
+    }
+}
+```
+*/
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct ReplicationBatch {
+    active: bool,
+}
+
+impl ReplicationBatch {
+    /// Returns `true` if replication is being written.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+/**
+Triggered when a client entity with [`Signature`] is mapped to a new server entity
+while still being mapped to another one.
+
+This happens when the server despawns an entity and spawns a new one with the same signature
+within the same tick. Instead of despawning the client entity and spawning it again, the client
+entity is rebound to the new server entity, and the despawn of the previous server entity is ignored.
+
+Components that were replicated from the previous server entity are kept, since the client
+can't know which of them the new server entity has. Observe this event to reset them if needed.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_observer(reset_health);
+
+fn reset_health(rebound: On<SignatureRebound>, mut commands: Commands) {
+    commands.entity(rebound.entity).remove::<Health>();
+}
+
+#[derive(Component)]
+struct Health(u32);
+```
+*/
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct SignatureRebound {
+    /// Client entity that was rebound.
+    pub entity: Entity,
+
+    /// Server entity that the client entity was previously mapped to.
+    pub previous: Entity,
+
+    /// Server entity that the client entity is now mapped to.
+    pub server_entity: Entity,
+}
+
+/// Entities with changes staged until the whole update message is deserialized.
+///
+/// Used for entities with removals and for all entities with [`UpdateApplyMode::Deferred`].
+#[derive(Default)]
+struct StagedEntities {
+    entities: Vec<Entity>,
+
+    /// Maps staged entities to their indices in [`Self::entities`].
+    indices: EntityHashMap<usize>,
+
+    /// Scratches for staged entities, reused between messages.
+    ///
+    /// Only the first `entities.len()` contain staged changes.
+    scratches: Vec<EntityScratch>,
+}
+
+impl StagedEntities {
+    /// Returns `true` if the entity has staged changes.
+    fn contains(&self, entity: Entity) -> bool {
+        self.indices.contains_key(&entity)
+    }
+
+    /// Wraps the entity to stage changes for it.
+    ///
+    /// If the entity was already staged, the previously staged changes are kept.
+    fn stage<'w>(&'w mut self, entity: EntityWorldMut<'w>) -> DeferredEntity<'w> {
+        match self.indices.entry(entity.id()) {
+            Entry::Occupied(index) => {
+                DeferredEntity::resume(entity, &mut self.scratches[*index.get()])
+            }
+            Entry::Vacant(entry) => {
+                let index = self.entities.len();
+                entry.insert(index);
+                self.entities.push(entity.id());
+                if index == self.scratches.len() {
+                    self.scratches.push(Default::default());
+                }
+                DeferredEntity::new(entity, &mut self.scratches[index])
+            }
+        }
+    }
+
+    /// Writes all staged changes to their entities.
+    fn flush(&mut self, world: &mut World) {
+        self.indices.clear();
+        for (entity, scratch) in self.entities.drain(..).zip(&mut self.scratches) {
+            if world.get_entity(entity).is_err() {
+                // Could be despawned by an observer for a previously written entity.
+                debug!("discarding staged changes for despawned `{entity}`");
+                // SAFETY: components in the scratch were pushed using this world.
+                unsafe { scratch.manual_drop(world.components()) };
+                continue;
+            }
+
+            trace!("writing staged changes for `{entity}`");
+            DeferredEntity::resume(world.entity_mut(entity), scratch).flush();
+        }
+    }
+
+    /// Drops all staged changes.
+    ///
+    /// # Safety
+    ///
+    /// `components` must come from the same world as the staged components.
+    unsafe fn manual_drop(&mut self, components: &Components) {
+        for scratch in &mut self.scratches[..self.entities.len()] {
+            unsafe { scratch.manual_drop(components) };
+        }
+        self.entities.clear();
+        self.indices.clear();
+    }
+}
+
+/// Functions IDs received from the server that aren't registered on the client.
+///
+/// Used to warn about each ID only once, since it will be received for every entity
+/// that has the component.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct UnknownFns(HashSet<FnsId>);
+
+impl UnknownFns {
+    /// Logs a warning about skipped data only for the first occurrence of the ID.
+    fn warn(&mut self, kind: &str, fns_id: FnsId, entity: Entity) {
+        if self.insert(fns_id) {
+            warn!(
+                "skipping {kind} with unknown `{fns_id:?}` for `{entity}`, further ones will be skipped silently"
+            );
+        } else {
+            trace!("skipping {kind} with unknown `{fns_id:?}` for `{entity}`");
+        }
+    }
+}
+
 /// Cached buffered mutate messages, used to synchronize mutations with update messages.
 #[derive(Resource, Default)]
 pub(crate) struct BufferedMutations(Vec<BufferedMutate>);
@@ -924,6 +1990,9 @@ impl BufferedMutations {
 ///
 /// See also [`crate::server::replication_messages`].
 pub(super) struct BufferedMutate {
+    /// Channel over which the message was received.
+    channel: MutationChannel,
+
     /// Flags for data in the message.
     flags: MutateFlags,
 
@@ -960,6 +2029,36 @@ pub struct ClientReplicationStats {
     pub bytes: usize,
 }
 
+/// Replication applied during the last run of [`ClientSystems::ApplyReplication`].
+///
+/// Unlike [`ClientReplicationStats`], which accumulates values over the whole session,
+/// this resource is reset on each run, so it can be used to find updates that cause hitches.
+///
+/// Statistic will be collected only if the resource is present.
+/// The resource is not added by default.
+#[derive(Resource, Default, Reflect, Debug, Clone, Copy)]
+pub struct ReplicationApplyStats {
+    /// Replication messages received.
+    pub messages: usize,
+    /// Replication bytes received in message payloads (without internal messaging plugin data).
+    pub bytes: usize,
+    /// Entities spawned by replication.
+    pub entities_spawned: usize,
+    /// Received entity despawns.
+    pub entities_despawned: usize,
+    /// Components inserted from update messages.
+    pub components_inserted: usize,
+    /// Components that were already present and were updated from update or mutate messages.
+    pub components_mutated: usize,
+    /// Received component removals.
+    pub components_removed: usize,
+    /// Mutate messages that are waiting for an update message with their tick
+    /// or held by [`MutationJitterBuffer`].
+    pub queued_mutations: usize,
+    /// Time spent receiving and applying replication.
+    pub apply_time: Duration,
+}
+
 /// Marker for entities spawned by replication.
 ///
 /// Automatically inserted for each newly received entity.
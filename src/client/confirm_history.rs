@@ -25,6 +25,12 @@ pub struct ConfirmHistory {
 
     /// The last received server tick for an entity.
     last_tick: RepliconTick,
+
+    /// The last tick for which mutations were received for each [`MutationChannel`].
+    ///
+    /// Channels are acknowledged independently, so outdated mutations are detected per channel.
+    /// Updated for all channels by update messages since they include mutations from all channels.
+    channel_ticks: [RepliconTick; MutationChannel::COUNT],
 }
 
 impl Debug for ConfirmHistory {
@@ -36,7 +42,11 @@ impl Debug for ConfirmHistory {
 impl ConfirmHistory {
     /// Creates a new instance with a single confirmed tick.
     pub fn new(last_tick: RepliconTick) -> Self {
-        Self { mask: 1, last_tick }
+        Self {
+            mask: 1,
+            last_tick,
+            channel_ticks: [last_tick; MutationChannel::COUNT],
+        }
     }
 
     /// Returns the last received tick for an entity.
@@ -44,6 +54,13 @@ impl ConfirmHistory {
         self.last_tick
     }
 
+    /// Returns the last tick for which mutations were received over the given channel.
+    ///
+    /// Update messages are considered to include mutations for all channels.
+    pub fn channel_tick(&self, channel: MutationChannel) -> RepliconTick {
+        self.channel_ticks[channel as usize]
+    }
+
     /// Returns a mask that represents the received ticks.
     pub fn mask(&self) -> u64 {
         self.mask
@@ -119,13 +136,33 @@ impl ConfirmHistory {
         self.mask |= 1 << ago;
     }
 
+    /// Confirms a tick received from an update message.
+    ///
+    /// Unlike [`Self::set_last_tick`], also updates ticks for all channels.
+    pub(super) fn set_update_tick(&mut self, tick: RepliconTick) {
+        self.set_last_tick(tick);
+        self.channel_ticks = [tick; MutationChannel::COUNT];
+    }
+
+    /// Confirms a tick received from a mutate message sent over the given channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `debug_assertions` are enabled and
+    /// `tick` is less then the last tick for this channel.
+    pub(super) fn set_channel_tick(&mut self, channel: MutationChannel, tick: RepliconTick) {
+        debug_assert!(tick.is_newer_or_eq(self.channel_tick(channel)));
+        self.confirm(tick);
+        self.channel_ticks[channel as usize] = tick;
+    }
+
     /// Sets the last received tick and shifts the mask.
     ///
     /// # Panics
     ///
     /// Panics if `debug_assertions` are enabled and
     /// `tick` is less then the last tick.
-    pub(super) fn set_last_tick(&mut self, tick: RepliconTick) {
+    fn set_last_tick(&mut self, tick: RepliconTick) {
         debug_assert!(tick.is_newer_or_eq(self.last_tick));
         let diff = tick - self.last_tick;
         self.mask = self.mask.wrapping_shl(diff);
@@ -293,4 +330,29 @@ mod tests {
         assert!(!history.contains(RepliconTick::new(3)));
         assert!(history.contains(RepliconTick::new(u32::MAX)));
     }
+
+    #[test]
+    fn channel_ticks() {
+        let mut history = ConfirmHistory::new(RepliconTick::new(1));
+        history.set_channel_tick(MutationChannel::Secondary, RepliconTick::new(3));
+        assert_eq!(history.last_tick(), RepliconTick::new(3));
+        assert_eq!(
+            history.channel_tick(MutationChannel::Primary),
+            RepliconTick::new(1)
+        );
+
+        history.set_channel_tick(MutationChannel::Primary, RepliconTick::new(2));
+        assert_eq!(history.last_tick(), RepliconTick::new(3));
+        assert_eq!(history.mask(), 0b111);
+
+        history.set_update_tick(RepliconTick::new(4));
+        assert_eq!(
+            history.channel_tick(MutationChannel::Primary),
+            RepliconTick::new(4)
+        );
+        assert_eq!(
+            history.channel_tick(MutationChannel::Secondary),
+            RepliconTick::new(4)
+        );
+    }
 }
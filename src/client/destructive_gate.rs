@@ -0,0 +1,241 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use bevy::{
+    ecs::{component::ComponentId, entity::EntityHashMap},
+    prelude::*,
+};
+use log::debug;
+
+use crate::{prelude::*, shared::replication::registry::FnsId};
+
+/// Gating of received despawns and removals on the client.
+pub trait AppDestructiveGateExt {
+    /**
+    Registers a function that can defer received despawns and removals for entities with marker `M`.
+
+    Before applying a despawn or component removal for an entity with `M`, the client calls `gate`.
+    If it returns [`GateDecision::Defer`], the operation is buffered and `gate` is called again
+    on each following frame until it returns [`GateDecision::Apply`] or the operation was deferred
+    for `max_frames` frames. Buffered operations are applied in the order they were received.
+
+    If the entity receives insertions or mutations while it has deferred operations, they are
+    applied first to preserve the order. The despawned entity is unmapped from
+    [`ServerEntityMap`](crate::shared::server_entity_map::ServerEntityMap) immediately,
+    so it won't receive any further updates while the despawn is deferred.
+
+    Deferred operations are discarded on disconnect.
+
+    While `gate` is called, resources used for receiving replication, such as
+    [`ServerEntityMap`](crate::shared::server_entity_map::ServerEntityMap),
+    are temporarily removed from the world.
+
+    Can be called multiple times with different markers. An operation is deferred
+    if any of the gates for markers present on the entity defers it.
+
+    # Examples
+
+    Don't despawn the entity the local player is interacting with.
+
+    ```
+    # use bevy::state::app::StatesPlugin;
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        client::destructive_gate::{AppDestructiveGateExt, DestructiveKind, GateDecision},
+        prelude::*,
+    };
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.init_resource::<Interaction>()
+        .set_destructive_gate::<Interactable>(keep_interacted, 60);
+
+    fn keep_interacted(world: &World, entity: Entity, _kind: DestructiveKind) -> GateDecision {
+        if **world.resource::<Interaction>() == Some(entity) {
+            GateDecision::Defer
+        } else {
+            GateDecision::Apply
+        }
+    }
+
+    /// Entity the local player is currently interacting with.
+    #[derive(Resource, Default, Deref)]
+    struct Interaction(Option<Entity>);
+
+    #[derive(Component)]
+    struct Interactable;
+    ```
+    */
+    fn set_destructive_gate<M: Component>(
+        &mut self,
+        gate: DestructiveGateFn,
+        max_frames: u32,
+    ) -> &mut Self;
+}
+
+impl AppDestructiveGateExt for App {
+    fn set_destructive_gate<M: Component>(
+        &mut self,
+        gate: DestructiveGateFn,
+        max_frames: u32,
+    ) -> &mut Self {
+        debug!(
+            "setting destructive gate for `{}` with max {max_frames} frames",
+            ShortName::of::<M>()
+        );
+        let marker_id = self.world_mut().register_component::<M>();
+        self.world_mut()
+            .resource_mut::<DestructiveGates>()
+            .gates
+            .push(Gate {
+                marker_id,
+                gate,
+                max_frames,
+            });
+        self
+    }
+}
+
+/// Signature of the function registered via [`AppDestructiveGateExt::set_destructive_gate`].
+pub type DestructiveGateFn = fn(&World, Entity, DestructiveKind) -> GateDecision;
+
+/// Kind of operation passed to [`DestructiveGateFn`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DestructiveKind {
+    /// Entity despawn.
+    ///
+    /// Also passed if the entity has deferred removals followed by a despawn.
+    Despawn,
+    /// Component removals.
+    Removal,
+}
+
+/// Decision returned from [`DestructiveGateFn`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GateDecision {
+    /// Apply the operation and all previously deferred operations for the entity.
+    Apply,
+    /// Postpone the operation to a later frame.
+    Defer,
+}
+
+/// Registered gates and deferred operations.
+///
+/// See [`AppDestructiveGateExt::set_destructive_gate`].
+#[derive(Resource, Default)]
+pub struct DestructiveGates {
+    gates: Vec<Gate>,
+    pending: EntityHashMap<PendingOps>,
+}
+
+impl DestructiveGates {
+    /// Returns `true` if the entity has deferred operations.
+    pub fn is_pending(&self, entity: Entity) -> bool {
+        self.pending.contains_key(&entity)
+    }
+
+    /// Returns `true` if any entity has deferred operations.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Returns `true` if an operation for the entity needs to be deferred.
+    ///
+    /// Operations are always deferred if the entity already has deferred operations
+    /// to preserve the order.
+    pub(crate) fn should_defer(
+        &self,
+        world: &World,
+        entity: Entity,
+        kind: DestructiveKind,
+    ) -> bool {
+        if self.gates.is_empty() {
+            return false;
+        }
+
+        self.is_pending(entity) || self.evaluate(world, entity, kind, 0)
+    }
+
+    pub(crate) fn defer(&mut self, entity: Entity, op: PendingOp) {
+        debug!("deferring `{op:?}` for `{entity}`");
+        self.pending.entry(entity).or_default().ops.push(op);
+    }
+
+    /// Removes deferred operations for the entity.
+    pub(crate) fn take(&mut self, entity: Entity) -> Option<Vec<PendingOp>> {
+        self.pending.remove(&entity).map(|pending| pending.ops)
+    }
+
+    /// Calls gates again for all entities with deferred operations.
+    ///
+    /// Returns operations that should be applied now.
+    pub(crate) fn drain_ready(&mut self, world: &World) -> Vec<(Entity, Vec<PendingOp>)> {
+        let mut ready = Vec::new();
+        let mut pending = mem::take(&mut self.pending);
+        pending.retain(|&entity, pending| {
+            pending.frames += 1;
+            let kind = if pending
+                .ops
+                .iter()
+                .any(|op| matches!(op, PendingOp::Despawn { .. }))
+            {
+                DestructiveKind::Despawn
+            } else {
+                DestructiveKind::Removal
+            };
+
+            if self.evaluate(world, entity, kind, pending.frames) {
+                return true;
+            }
+
+            ready.push((entity, mem::take(&mut pending.ops)));
+            false
+        });
+        self.pending = pending;
+
+        ready
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Returns `true` if any gate with a marker present on the entity defers the operation.
+    ///
+    /// Gates for which the operation was already deferred for `frames` or more are skipped.
+    fn evaluate(&self, world: &World, entity: Entity, kind: DestructiveKind, frames: u32) -> bool {
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            return false;
+        };
+
+        self.gates
+            .iter()
+            .filter(|gate| frames < gate.max_frames && entity_ref.contains_id(gate.marker_id))
+            .any(|gate| (gate.gate)(world, entity, kind) == GateDecision::Defer)
+    }
+}
+
+struct Gate {
+    marker_id: ComponentId,
+    gate: DestructiveGateFn,
+    max_frames: u32,
+}
+
+#[derive(Default)]
+struct PendingOps {
+    /// Number of frames the operations were deferred for.
+    frames: u32,
+    ops: Vec<PendingOp>,
+}
+
+/// Buffered operation from an update message.
+#[derive(Debug)]
+pub(crate) enum PendingOp {
+    Despawn {
+        message_tick: RepliconTick,
+    },
+    Removal {
+        message_tick: RepliconTick,
+        fns_ids: Vec<FnsId>,
+    },
+}
@@ -0,0 +1,205 @@
+use core::time::Duration;
+
+use bevy::{ecs::component::Mutable, prelude::*};
+use log::{debug, trace};
+
+use super::{
+    confirm_history::ConfirmHistory,
+    interpolating::{self, InterpolationDelay},
+};
+use crate::prelude::*;
+
+/// Dead reckoning for replicated components on the client.
+pub trait AppExtrapolationExt {
+    /**
+    Registers functions to extrapolate component `C` when its mutations arrive late.
+
+    When the latest received tick for an entity is older than the [`InterpolationDelay`],
+    for example due to packet loss or [lowered priority](crate::server::PriorityMap),
+    there is nothing to interpolate to. In this case the client calls `extrapolate`
+    each frame with the frame delta to advance the component locally.
+
+    Once the next authoritative value arrives, the client doesn't snap to it. Instead,
+    it calls `blend` each frame with the last extrapolated value, the received value and
+    the blend factor in the range `[0, 1)`, and writes the result into the component.
+    The factor grows over the interpolation delay, after which the received value
+    is written as is. If the entity falls behind again while blending, the received
+    value is extrapolated instead.
+
+    Does nothing without [`InterpolationDelay`]. The component is expected to be written
+    only by replication. Calling it again for the same component replaces the functions.
+
+    # Examples
+
+    ```
+    # use bevy::state::app::StatesPlugin;
+    use core::time::Duration;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{client::extrapolating::AppExtrapolationExt, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.insert_resource(InterpolationDelay::Time(Duration::from_millis(100)))
+        .replicate::<Movement>()
+        .extrapolate(advance_movement, blend_movement);
+
+    fn advance_movement(movement: &mut Movement, dt: Duration) {
+        movement.position += movement.velocity * dt.as_secs_f32();
+    }
+
+    fn blend_movement(from: &Movement, to: &Movement, factor: f32) -> Movement {
+        Movement {
+            position: from.position.lerp(to.position, factor),
+            velocity: to.velocity,
+        }
+    }
+
+    #[derive(Component, Deserialize, Serialize, Clone)]
+    struct Movement {
+        position: Vec2,
+        velocity: Vec2,
+    }
+    ```
+    */
+    fn extrapolate<C: Component<Mutability = Mutable> + Clone>(
+        &mut self,
+        extrapolate: ExtrapolateFn<C>,
+        blend: BlendFn<C>,
+    ) -> &mut Self;
+}
+
+impl AppExtrapolationExt for App {
+    fn extrapolate<C: Component<Mutability = Mutable> + Clone>(
+        &mut self,
+        extrapolate: ExtrapolateFn<C>,
+        blend: BlendFn<C>,
+    ) -> &mut Self {
+        debug!("registering extrapolation for `{}`", ShortName::of::<C>());
+        let fns = ExtrapolationFns { extrapolate, blend };
+        if self.world().contains_resource::<ExtrapolationFns<C>>() {
+            self.insert_resource(fns);
+        } else {
+            self.insert_resource(fns).add_systems(
+                PreUpdate,
+                extrapolate_component::<C>
+                    .after(ClientSystems::Receive)
+                    .run_if(in_state(ClientState::Connected))
+                    .run_if(resource_exists::<InterpolationDelay>),
+            );
+        }
+        self
+    }
+}
+
+/// Signature of the extrapolation function registered via [`AppExtrapolationExt::extrapolate`].
+///
+/// Advances the component by the given duration.
+pub type ExtrapolateFn<C> = fn(&mut C, Duration);
+
+/// Signature of the blend function registered via [`AppExtrapolationExt::extrapolate`].
+///
+/// Returns the value between the last extrapolated and the received value at the given factor.
+pub type BlendFn<C> = fn(&C, &C, f32) -> C;
+
+#[derive(Resource)]
+struct ExtrapolationFns<C> {
+    extrapolate: ExtrapolateFn<C>,
+    blend: BlendFn<C>,
+}
+
+/// Extrapolation state of component `C` for an entity.
+///
+/// Inserted once the component is extrapolated for the first time.
+#[derive(Component)]
+struct Extrapolation<C> {
+    /// Last extrapolated value if the component is currently extrapolated.
+    extrapolated: Option<C>,
+    blend: Option<Blend<C>>,
+}
+
+struct Blend<C> {
+    from: C,
+    to: C,
+    elapsed: Duration,
+}
+
+fn extrapolate_component<C: Component<Mutability = Mutable> + Clone>(
+    mut commands: Commands,
+    fns: Res<ExtrapolationFns<C>>,
+    delay: Res<InterpolationDelay>,
+    timeline: Res<TickTimeline>,
+    time: Res<Time>,
+    fixed_time: Res<Time<Fixed>>,
+    mut entities: Query<(
+        Entity,
+        Mut<C>,
+        Ref<ConfirmHistory>,
+        Option<&mut Extrapolation<C>>,
+    )>,
+) {
+    let Some(tick) = interpolating::estimated_tick(&timeline, time.elapsed()) else {
+        return;
+    };
+
+    let blend_duration = delay.duration(&timeline, fixed_time.timestep());
+    let delay = delay.ticks(&timeline, fixed_time.timestep());
+    let dt = time.delta();
+    for (entity, mut component, history, extrapolation) in &mut entities {
+        let stale = tick.is_newer(history.last_tick() + delay);
+        let Some(mut extrapolation) = extrapolation else {
+            if stale {
+                trace!(
+                    "extrapolating `{}` for `{entity}` received at `{:?}`",
+                    ShortName::of::<C>(),
+                    history.last_tick()
+                );
+                (fns.extrapolate)(&mut component, dt);
+                commands.entity(entity).insert(Extrapolation {
+                    extrapolated: Some(component.clone()),
+                    blend: None,
+                });
+            }
+            continue;
+        };
+
+        // Our own writes are not visible as changes on the next run,
+        // so a change here means that a new authoritative value was received.
+        if history.is_changed() && component.is_changed() {
+            if let Some(from) = extrapolation.extrapolated.take() {
+                trace!(
+                    "blending `{}` for `{entity}` to the received value",
+                    ShortName::of::<C>()
+                );
+                extrapolation.blend = Some(Blend {
+                    from,
+                    to: component.clone(),
+                    elapsed: Duration::ZERO,
+                });
+            } else if let Some(blend) = &mut extrapolation.blend {
+                blend.to = component.clone();
+            }
+        }
+
+        if stale {
+            if let Some(blend) = &mut extrapolation.blend {
+                (fns.extrapolate)(&mut blend.to, dt);
+            } else {
+                (fns.extrapolate)(&mut component, dt);
+                extrapolation.extrapolated = Some(component.clone());
+            }
+        }
+
+        if let Some(blend) = &mut extrapolation.blend {
+            blend.elapsed += dt;
+            if blend.elapsed >= blend_duration {
+                *component = blend.to.clone();
+                extrapolation.blend = None;
+            } else {
+                let factor = blend.elapsed.as_secs_f32() / blend_duration.as_secs_f32();
+                *component = (fns.blend)(&blend.from, &blend.to, factor);
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Entry points for fuzzing deserialization of replication messages.
+//!
+//! Available only with the `fuzzing` feature and intended for tools like `cargo-fuzz`.
+//! These functions aren't covered by semver guarantees.
+//!
+//! The world should belong to an app with [`RepliconPlugins`](crate::RepliconPlugins) after [`App::finish`].
+//! Any input is expected to produce either [`Ok`] or [`Err`], but never a panic.
+//!
+//! See [`replication_messages`](crate::server::replication_messages) for the wire format.
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy::{prelude::*, state::app::StatesPlugin};
+//! use bevy_replicon::{bytes::Bytes, client::fuzzing, prelude::*};
+//!
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+//!     .finish();
+//!
+//! let data: &[u8] = &[0xFF, 0xFF]; // Provided by the fuzzer.
+//! let mut message = Bytes::copy_from_slice(data);
+//! let _ = fuzzing::apply_update_message(app.world_mut(), &mut message);
+//! ```
+
+use core::mem;
+
+use bevy::prelude::*;
+use bytes::Bytes;
+
+use super::{BufferedMutations, StagedEntities};
+use crate::shared::{
+    backend::channels::MutationChannel,
+    replication::{
+        deferred_entity::EntityScratch, receive_markers::EntityMarkers, registry::ctx::EntityBuffer,
+    },
+};
+
+/// Deserializes and applies an update message received over
+/// [`ServerChannel::Updates`](crate::shared::backend::channels::ServerChannel::Updates).
+pub fn apply_update_message(world: &mut World, message: &mut Bytes) -> Result<()> {
+    let mut scratch = EntityScratch::default();
+    let mut staged = StagedEntities::default();
+    let mut entity_markers = EntityMarkers::from_world(world);
+    let mut entity_buffer = EntityBuffer::default();
+    super::receive_scope(
+        world,
+        &mut scratch,
+        &mut staged,
+        &mut entity_markers,
+        &mut entity_buffer,
+        |world, params, _, _| {
+            let result = super::apply_update_message(world, params, message);
+            if result.is_err() {
+                // SAFETY: components in the scratch were pushed using this world.
+                unsafe { params.scratch.manual_drop(world.components()) };
+                // SAFETY: staged components were pushed using this world.
+                unsafe { params.staged.manual_drop(world.components()) };
+                params.entity_buffer.free(world);
+            }
+
+            result
+        },
+    )
+}
+
+/// Deserializes and applies a mutate message received over
+/// [`ServerChannel::Mutations`](crate::shared::backend::channels::ServerChannel::Mutations).
+///
+/// Unlike regular receiving, the message is applied immediately without waiting for its update tick.
+/// Consumes the message.
+pub fn apply_mutate_message(world: &mut World, message: &mut Bytes) -> Result<()> {
+    let mut scratch = EntityScratch::default();
+    let mut staged = StagedEntities::default();
+    let mut entity_markers = EntityMarkers::from_world(world);
+    let mut entity_buffer = EntityBuffer::default();
+    super::receive_scope(
+        world,
+        &mut scratch,
+        &mut staged,
+        &mut entity_markers,
+        &mut entity_buffer,
+        |world, params, _, _| {
+            let mut buffered_mutations = BufferedMutations::default();
+            let mut acks = Vec::new();
+            super::buffer_mutate_message(
+                params,
+                &mut buffered_mutations,
+                MutationChannel::Primary,
+                mem::take(message),
+                &mut acks,
+            )?;
+
+            let mut mutate = buffered_mutations
+                .0
+                .pop()
+                .expect("message should be buffered");
+            let result = super::apply_mutate_message(world, params, &mut mutate);
+            if result.is_err() {
+                // SAFETY: components in the scratch were pushed using this world.
+                unsafe { params.scratch.manual_drop(world.components()) };
+                params.entity_buffer.free(world);
+            }
+
+            result
+        },
+    )
+}
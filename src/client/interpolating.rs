@@ -0,0 +1,143 @@
+use core::time::Duration;
+
+use bevy::prelude::*;
+use log::trace;
+
+use super::confirm_history::ConfirmHistory;
+use crate::prelude::*;
+
+/**
+Delay with which the game renders interpolated entities behind the latest received state.
+
+When present, newly replicated entities, including entities that entered the client's
+visibility, are marked with [`Interpolating`] until enough samples exist to interpolate them.
+Without it, the first mutations for such entities arrive before the interpolation buffer
+fills, which causes a visual snap. Games can hide or fade these entities instead.
+
+Replicon doesn't interpolate by itself, so the value should match the delay used by
+your interpolation.
+
+The resource is not added by default and can be inserted or removed at runtime.
+
+# Examples
+
+```
+use core::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.insert_resource(InterpolationDelay::Time(Duration::from_millis(100)))
+    .add_systems(Update, hide_interpolating);
+
+fn hide_interpolating(mut entities: Query<(&mut Visibility, Has<Interpolating>), With<Remote>>) {
+    for (mut visibility, interpolating) in &mut entities {
+        visibility.set_if_neq(if interpolating {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+}
+```
+*/
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpolationDelay {
+    /// Delays interpolation by the given number of server ticks.
+    Ticks(u32),
+    /// Delays interpolation by the given duration, converted into ticks using [`TickTimeline`].
+    ///
+    /// If the tick rate can't be estimated yet, the [`Time<Fixed>`] timestep is used since
+    /// the server increments its tick in [`FixedPostUpdate`] by default.
+    Time(Duration),
+}
+
+impl InterpolationDelay {
+    /// Returns the delay in ticks.
+    pub(super) fn ticks(self, timeline: &TickTimeline, timestep: Duration) -> u32 {
+        match self {
+            InterpolationDelay::Ticks(ticks) => ticks,
+            InterpolationDelay::Time(duration) => timeline
+                .duration_to_ticks(duration)
+                .map(|ticks| ticks.ceil() as u32)
+                .unwrap_or_else(|| {
+                    if timestep.is_zero() {
+                        0
+                    } else {
+                        duration.as_nanos().div_ceil(timestep.as_nanos()) as u32
+                    }
+                }),
+        }
+    }
+
+    /// Returns the delay as a duration.
+    pub(super) fn duration(self, timeline: &TickTimeline, timestep: Duration) -> Duration {
+        match self {
+            InterpolationDelay::Ticks(ticks) => timeline
+                .ticks_to_duration(ticks)
+                .unwrap_or(timestep * ticks),
+            InterpolationDelay::Time(duration) => duration,
+        }
+    }
+}
+
+/// Marks a newly replicated entity that doesn't have enough samples for interpolation yet.
+///
+/// Inserted on the client if [`InterpolationDelay`] is present and removed once
+/// the estimated server tick reaches [`Self::ready_at_tick`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interpolating {
+    /// Tick at which the interpolation delay covers the tick of the first received state.
+    pub ready_at_tick: RepliconTick,
+}
+
+pub(super) fn mark_interpolating(
+    mut commands: Commands,
+    delay: Res<InterpolationDelay>,
+    timeline: Res<TickTimeline>,
+    time: Res<Time>,
+    fixed_time: Res<Time<Fixed>>,
+    entities: Query<(Entity, &ConfirmHistory), Added<ConfirmHistory>>,
+) {
+    let Some(tick) = estimated_tick(&timeline, time.elapsed()) else {
+        return;
+    };
+
+    let delay = delay.ticks(&timeline, fixed_time.timestep());
+    for (entity, history) in &entities {
+        let ready_at_tick = history.last_tick() + delay;
+        if tick.is_older(ready_at_tick) {
+            trace!("marking `{entity}` as interpolating until `{ready_at_tick:?}`");
+            commands
+                .entity(entity)
+                .insert(Interpolating { ready_at_tick });
+        }
+    }
+}
+
+pub(super) fn finish_interpolating(
+    mut commands: Commands,
+    timeline: Res<TickTimeline>,
+    time: Res<Time>,
+    entities: Query<(Entity, &Interpolating)>,
+) {
+    let Some(tick) = estimated_tick(&timeline, time.elapsed()) else {
+        return;
+    };
+
+    for (entity, interpolating) in &entities {
+        if tick.is_newer_or_eq(interpolating.ready_at_tick) {
+            trace!("finishing interpolation for `{entity}`");
+            commands.entity(entity).remove::<Interpolating>();
+        }
+    }
+}
+
+/// Returns the estimated current server tick or the latest received tick if the tick rate is unknown.
+pub(super) fn estimated_tick(timeline: &TickTimeline, elapsed: Duration) -> Option<RepliconTick> {
+    timeline
+        .tick_at(elapsed)
+        .map(|(tick, _)| tick)
+        .or_else(|| timeline.latest().map(|(tick, _)| tick))
+}
@@ -11,6 +11,7 @@ use crate::{
             ctx::{ClientReceiveCtx, ClientSendCtx},
             registry::RemoteMessageRegistry,
         },
+        replication_error::ErrorReporter,
         server_entity_map::ServerEntityMap,
     },
 };
@@ -50,6 +51,7 @@ impl Plugin for ClientMessagePlugin {
                 ParamBuilder,
                 ParamBuilder,
                 ParamBuilder,
+                ParamBuilder,
             );
 
             let receive_fn = receive_builder
@@ -64,15 +66,12 @@ impl Plugin for ClientMessagePlugin {
             app.add_systems(
                 PreUpdate,
                 receive_fn
-                    .after(super::receive_replication)
                     .run_if(in_state(ClientState::Connected))
-                    .in_set(ClientSystems::Receive),
+                    .in_set(ClientSystems::EmitQueuedEvents),
             )
             .add_systems(
                 OnEnter(ClientState::Connected),
-                enter_receive_fn
-                    .after(super::receive_replication)
-                    .in_set(ClientSystems::Receive),
+                enter_receive_fn.in_set(ClientSystems::EmitQueuedEvents),
             );
         }
 
@@ -98,13 +97,15 @@ impl Plugin for ClientMessagePlugin {
 
             app.add_systems(
                 PreUpdate,
-                trigger_fn.after(receive).in_set(ClientSystems::Receive),
+                trigger_fn
+                    .after(receive)
+                    .in_set(ClientSystems::EmitQueuedEvents),
             )
             .add_systems(
                 OnEnter(ClientState::Connected),
                 enter_trigger_fn
                     .after(receive)
-                    .in_set(ClientSystems::Receive),
+                    .in_set(ClientSystems::EmitQueuedEvents),
             );
         }
 
@@ -125,6 +126,7 @@ impl Plugin for ClientMessagePlugin {
                 ParamBuilder,
                 ParamBuilder,
                 ParamBuilder,
+                ParamBuilder,
             )
                 .build_state(app.world_mut())
                 .build_system(send);
@@ -173,6 +175,7 @@ impl Plugin for ClientMessagePlugin {
                 ParamBuilder,
                 ParamBuilder,
                 ParamBuilder,
+                ParamBuilder,
             )
                 .build_state(app.world_mut())
                 .build_system(send_shared);
@@ -265,12 +268,14 @@ fn send(
     type_registry: Res<AppTypeRegistry>,
     entity_map: Res<ServerEntityMap>,
     registry: Res<RemoteMessageRegistry>,
+    mut errors: ResMut<ErrorReporter>,
 ) {
     let mut ctx = ClientSendCtx {
         storage: &mut storage,
         entity_map: &entity_map,
         type_registry: &type_registry,
         invalid_entities: Vec::new(),
+        errors: &mut errors,
     };
 
     for message in registry.iter_all_client() {
@@ -301,12 +306,14 @@ fn send_shared(
     type_registry: Res<AppTypeRegistry>,
     entity_map: Res<ServerEntityMap>,
     registry: Res<RemoteMessageRegistry>,
+    mut errors: ResMut<ErrorReporter>,
 ) {
     let mut ctx = ClientSendCtx {
         storage: &mut storage,
         entity_map: &entity_map,
         type_registry: &type_registry,
         invalid_entities: Vec::new(),
+        errors: &mut errors,
     };
 
     for message in registry.iter_all_shared() {
@@ -338,12 +345,14 @@ fn receive(
     entity_map: Res<ServerEntityMap>,
     message_registry: Res<RemoteMessageRegistry>,
     update_tick: Res<ServerUpdateTick>,
+    mut errors: ResMut<ErrorReporter>,
 ) {
     let mut ctx = ClientReceiveCtx {
         storage: &mut storage,
         type_registry: &type_registry,
         entity_map: &entity_map,
         invalid_entities: Vec::new(),
+        errors: &mut errors,
     };
 
     for message in message_registry.iter_all_server() {
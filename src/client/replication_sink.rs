@@ -0,0 +1,283 @@
+use alloc::boxed::Box;
+
+use bevy::prelude::*;
+use log::debug;
+
+use super::{
+    BufferedMutations, ClientReplicationStats, MutationJitterBuffer, ReplicationApplyStats,
+    ReplicationBatch, ServerUpdateTick, UpdateApplyMode,
+    confirm_history::EntityReplicated,
+    destructive_gate::DestructiveGates,
+    server_mutate_ticks::{MutateTickReceived, ServerMutateTicks},
+};
+use crate::{
+    prelude::*,
+    shared::{
+        backend::channels::ServerChannel,
+        replication::{
+            receive_markers::ReceiveMarkers,
+            registry::ReplicationRegistry,
+            signature::{SignatureMap, SignatureRemovals, SignatureSettings},
+        },
+        replication_error::ErrorReporter,
+        server_entity_map::ServerEntityMap,
+    },
+};
+
+/// Secondary world into which the client applies received replication.
+///
+/// See [`ClientReplicationSink`] for details.
+pub trait ReplicationSink: Send + Sync + 'static {
+    /// Returns the world to apply replication into.
+    fn world(&mut self) -> &mut World;
+}
+
+impl ReplicationSink for World {
+    fn world(&mut self) -> &mut World {
+        self
+    }
+}
+
+/**
+Applies received replication into a secondary world instead of or in addition to the main one.
+
+Useful for prediction crates that keep the authoritative state in a separate rollback world
+while the main world contains the presented or predicted state.
+
+The secondary world keeps its own [`ServerEntityMap`], [`ReplicationStorage`], [`DestructiveGates`]
+and other per-world state, which are initialized on the first receive.
+Replication rules, markers and the type registry are shared with the main world.
+
+Replication functions use component IDs from the main world, so all replicated components and
+[`ReceiveMarkers`] must be registered in the secondary world in the same order and with the same IDs.
+The easiest way to achieve this is to build it with the same plugins and registrations as the main app
+and take its world. Panics on the first receive if IDs mismatch.
+
+Observers that keep [`ServerEntityMap`] in sync with despawns are added by [`ClientPlugin`],
+so it should be included. The secondary world doesn't run any schedules by itself, so reading
+[`UpdateApplied`] or [`EntityReplicated`] is up to the owner of the world.
+
+With [`SinkMode::Secondary`], [`ServerUpdateTick`] and [`ServerMutateTicks`] are still updated in the
+main world, and server messages and events are received there too. But entities inside them are mapped
+with the main world's [`ServerEntityMap`], which stays empty. Use [`SinkMode::Both`] if they reference
+replicated entities.
+
+The resource isn't added by default and can be inserted or removed at runtime.
+
+# Examples
+
+```
+# use bevy::state::app::StatesPlugin;
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::replication_sink::{ClientReplicationSink, SinkMode},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+fn build_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .replicate::<Health>();
+    app
+}
+
+let mut app = build_app();
+
+// Build the secondary world with the same registrations.
+let mut rollback_app = build_app();
+let rollback_world = core::mem::take(rollback_app.world_mut());
+
+app.insert_resource(ClientReplicationSink::new(rollback_world, SinkMode::Both));
+
+#[derive(Component, Serialize, Deserialize)]
+struct Health(u32);
+```
+*/
+#[derive(Resource)]
+pub struct ClientReplicationSink {
+    sink: Box<dyn ReplicationSink>,
+    mode: SinkMode,
+
+    /// Whether component IDs were checked and the world was initialized.
+    initialized: bool,
+}
+
+impl ClientReplicationSink {
+    /// Creates a new instance that applies replication into the world returned by `sink`.
+    pub fn new(sink: impl ReplicationSink, mode: SinkMode) -> Self {
+        Self {
+            sink: Box::new(sink),
+            mode,
+            initialized: false,
+        }
+    }
+
+    /// Returns the world into which replication is applied.
+    pub fn world(&mut self) -> &mut World {
+        self.sink.world()
+    }
+
+    /// Returns the configured mode.
+    pub fn mode(&self) -> SinkMode {
+        self.mode
+    }
+
+    /// Copies received replication messages into the secondary world for [`SinkMode::Both`].
+    ///
+    /// With [`SinkMode::Secondary`], the secondary world receives them from the main world directly.
+    ///
+    /// Should be called once before applying replication.
+    pub(super) fn copy_messages(&mut self, world: &mut World) {
+        if self.mode == SinkMode::Secondary {
+            return;
+        }
+        self.init(world);
+
+        let sink_world = self.sink.world();
+        let mut sink_messages = sink_world.resource_mut::<ClientMessages>();
+        let messages = world.resource::<ClientMessages>();
+        let channels = world.resource::<RepliconChannels>();
+        let channel_ids = [Some(ServerChannel::Updates.into())]
+            .into_iter()
+            .chain(MutationChannel::ALL.map(|channel| channels.mutation_channel(channel)))
+            .flatten();
+        for channel_id in channel_ids {
+            for message in messages.iter_received(channel_id) {
+                sink_messages.insert_received(channel_id, message.clone());
+            }
+        }
+    }
+
+    /// Calls `f` with the secondary world, temporarily moving shared resources into it.
+    pub(super) fn scope(&mut self, world: &mut World, f: impl FnOnce(&mut World)) {
+        self.init(world);
+
+        let mode = self.mode;
+        let sink_world = self.sink.world();
+        sink_world
+            .resource_mut::<Messages<EntityReplicated>>()
+            .update();
+        sink_world
+            .resource_mut::<Messages<MutateTickReceived>>()
+            .update();
+
+        swap_shared(world, sink_world, mode);
+        (f)(sink_world);
+        swap_shared(world, sink_world, mode);
+
+        if mode == SinkMode::Both {
+            // The main world sends its own acknowledgments.
+            sink_world
+                .resource_mut::<ClientMessages>()
+                .drain_sent()
+                .for_each(drop);
+        }
+    }
+
+    /// Validates component IDs and initializes per-world resources on the first call.
+    fn init(&mut self, world: &mut World) {
+        if self.initialized {
+            return;
+        }
+        self.initialized = true;
+
+        let sink_world = self.sink.world();
+        let registry = world.resource::<ReplicationRegistry>();
+        let markers = world.resource::<ReceiveMarkers>();
+        for component_id in registry.component_ids().chain(markers.component_ids()) {
+            let type_id = world
+                .components()
+                .get_info(component_id)
+                .and_then(|info| info.type_id());
+            let sink_type_id = sink_world
+                .components()
+                .get_info(component_id)
+                .and_then(|info| info.type_id());
+            assert_eq!(
+                type_id, sink_type_id,
+                "component `{component_id:?}` should have the same ID in the secondary world"
+            );
+        }
+
+        debug!("initializing secondary world for replication");
+        let mut messages = ClientMessages::default();
+        messages.setup_channels(world.resource::<RepliconChannels>());
+        sink_world.insert_resource(messages);
+        sink_world.insert_resource(*world.resource::<SignatureSettings>());
+        sink_world.init_resource::<ServerEntityMap>();
+        sink_world.init_resource::<SignatureMap>();
+        sink_world.init_resource::<SignatureRemovals>();
+        sink_world.init_resource::<ReplicationStorage>();
+        sink_world.init_resource::<ServerMutateTicks>();
+        sink_world.init_resource::<ServerUpdateTick>();
+        sink_world.init_resource::<BufferedMutations>();
+        sink_world.init_resource::<DestructiveGates>();
+        sink_world.init_resource::<ReplicationBatch>();
+        sink_world.init_resource::<Messages<EntityReplicated>>();
+        sink_world.init_resource::<Messages<MutateTickReceived>>();
+    }
+}
+
+/// Where [`ClientReplicationSink`] applies received replication.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SinkMode {
+    /// Apply replication only into the secondary world.
+    #[default]
+    Secondary,
+    /// Apply replication into both the secondary and the main worlds.
+    Both,
+}
+
+/// Swaps resources that are shared between the main and the secondary worlds.
+///
+/// With [`SinkMode::Secondary`], the secondary world also receives messages, sends acknowledgments
+/// and tracks server ticks using the main world resources.
+fn swap_shared(world: &mut World, sink_world: &mut World, mode: SinkMode) {
+    swap_resource::<ReplicationRegistry>(world, sink_world);
+    swap_resource::<ReceiveMarkers>(world, sink_world);
+    swap_resource::<AppTypeRegistry>(world, sink_world);
+    swap_resource::<UpdateApplyMode>(world, sink_world);
+    swap_resource::<MutationJitterBuffer>(world, sink_world);
+    swap_resource::<ErrorReporter>(world, sink_world);
+    swap_resource::<TickTimeline>(world, sink_world);
+    swap_resource::<Time>(world, sink_world);
+    swap_resource::<ClientReplicationStats>(world, sink_world);
+    swap_resource::<ReplicationApplyStats>(world, sink_world);
+    if mode == SinkMode::Secondary {
+        swap_resource::<ClientMessages>(world, sink_world);
+        swap_resource::<ServerUpdateTick>(world, sink_world);
+        swap_resource::<ServerMutateTicks>(world, sink_world);
+        swap_resource::<Messages<MutateTickReceived>>(world, sink_world);
+    }
+}
+
+fn swap_resource<R: Resource>(a: &mut World, b: &mut World) {
+    let resource_a = a.remove_resource::<R>();
+    let resource_b = b.remove_resource::<R>();
+    if let Some(resource) = resource_a {
+        b.insert_resource(resource);
+    }
+    if let Some(resource) = resource_b {
+        a.insert_resource(resource);
+    }
+}
+
+/// Clears per-world state of the secondary world on disconnect.
+pub(super) fn reset(sink: Option<ResMut<ClientReplicationSink>>) {
+    let Some(mut sink) = sink else {
+        return;
+    };
+    if !sink.initialized {
+        return;
+    }
+
+    let world = sink.world();
+    world.resource_mut::<ClientMessages>().clear();
+    *world.resource_mut::<ServerUpdateTick>() = Default::default();
+    world.resource_mut::<ServerEntityMap>().clear();
+    world.resource_mut::<SignatureMap>().clear_released();
+    world.resource_mut::<BufferedMutations>().clear();
+    world.resource_mut::<DestructiveGates>().clear();
+    world.resource_mut::<ServerMutateTicks>().clear();
+}
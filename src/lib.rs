@@ -361,6 +361,10 @@ fn receive(ping: On<FromClient<Ping>>) {
 For events with entities inside use [`ClientEventAppExt::add_mapped_client_event`].
 Similar to messages, serialization can also be customized with [`ClientEventAppExt::add_client_event_with`].
 
+To trigger an entity event for many entities at once (for example, a command for all selected units),
+register it with [`ClientEventAppExt::add_client_targets_event`] and use [`ClientTriggerExt::client_trigger_targets`].
+Targets can be validated on the server with [`ClientEventAppExt::set_client_targets_filter`].
+
 If you need to react to the same message on both the client and the server (for example,
 to share logic between client-side prediction and authoritative server processing), use
 [`SharedMessageAppExt::add_shared_message`] or
@@ -561,6 +565,7 @@ or [`ServerEventAppExt::make_event_independent`]. Additionally, some components
 after replication starts. See the required components for [`AuthorizedClient`] for details.
 
 By default, this component is automatically inserted when the client and server [`ProtocolHash`] matches.
+Additional checks, like tokens or bans, can be added to [`Authorizers`].
 This behavior can be customized via [`RepliconSharedPlugin::auth_method`].
 
 ### Client visibility
@@ -569,6 +574,9 @@ You can control which parts of the world are visible to each client by using com
 This works similarly to collision layers in physics: you insert filters to both the client and gameplay entities.
 See [`AppVisibilityExt`] for API details.
 
+If you run multiple levels in a single server world, use [`AppVisibilityExt::add_level_visibility`]
+to replicate to each client only entities from its level.
+
 The server always sees the entire world, even in listen-server mode.
 
 ### Prioritization
@@ -579,7 +587,8 @@ how often mutations are sent for each entity on authorized clients. See its docu
 more details.
 
 In addition, [client visibility](#client-visibility) can be used to further reduce bandwidth by hiding entities
-that are irrelevant to a given client.
+that are irrelevant to a given client. To send distant entities with less precision instead,
+see [`AppRuleExt::replicate_lod`].
 
 ### Interpolation and/or client-side prediction
 
@@ -589,6 +598,11 @@ immediately. This might be fine depending on your type of game. However, sometim
 To make value updates look smooth, you can just to interpolate the received state. If the input delay doesn't matter
 for your type of game, it can be enough.
 
+If mutations for an entity arrive later than the interpolation delay, for example due to packet loss or
+[lowered priority](#prioritization), there is nothing to interpolate to. In this case you can extrapolate
+the state (dead reckoning): advance the component locally and blend towards the authoritative value once it arrives.
+See [`AppExtrapolationExt`](client::extrapolating::AppExtrapolationExt) for details.
+
 But if your game is fast-paced, waiting for server to receive your inputs and replicate the state back might
 might be unacceptable. The solution is to predict simulation on the client.
 
@@ -659,6 +673,8 @@ However, an entity might not have any mutations at a given tick. You can check t
 been received for this tick via [`ServerMutateTicks`](client::server_mutate_ticks::ServerMutateTicks). Since this requires including
 the number of mutate messages in each mutate message, you need to enable this by setting [`ServerPlugin::track_mutate_messages`].
 For convenience, we also emit the [`MutateTickReceived`](client::server_mutate_ticks::MutateTickReceived) to ergonomically react on it.
+On the server, the same setting enables [`MutateMessageStatus`](server::mutate_tracking::MutateMessageStatus) to observe
+which mutate messages were sent to each client and whether they were acknowledged.
 
 So, a tick for an entity is confirmed if one of the following is true:
 - [`ConfirmHistory`](client::confirm_history::ConfirmHistory) reports that the tick is received.
@@ -712,11 +728,28 @@ Alternatively you can configure `LogPlugin` from Bevy to make it permanent.
 
 For deserialization errors on client we use `error` level which should be visible by default.
 But on server we use `debug` for it to avoid flooding server logs with errors caused by clients.
+
+Errors on hot paths are also written as [`ReplicationError`] messages, which you can read to surface
+them in UI or telemetry. Both messages and logs for them are rate-limited per kind according to
+[`ReplicationErrorSettings`]. In production, you may want to disconnect clients that send invalid data
+instead of discarding it via [`ReplicationErrorSettings::client_policy`].
+
+Panics in replication functions, such as custom serialization or deserialization from
+[`RuleFns`](shared::replication::registry::rule_fns::RuleFns) or receive functions, crash the app
+by default. Enable the `catch_panics` feature to catch them instead. This requires `std` and
+unwinding panics. On the sending side, a component whose serialization panicked is logged and
+skipped as if it was vetoed, so replication of other components continues. On the receiving side,
+the panic is reported as a [`ReplicationErrorKind::Apply`] error and the message is discarded,
+like any other received data that can't be applied. Panics in deserialization of client messages
+are reported on the server as [`ReplicationErrorKind::Deserialize`] errors for the sending client,
+so [`ReplicationErrorSettings::client_policy`] applies to them too.
 */
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "catch_panics")]
+extern crate std;
 
 #[cfg(feature = "client")]
 pub mod client;
@@ -735,64 +768,135 @@ pub mod world_serialization;
 pub mod prelude {
     #[expect(deprecated, reason = "Re-export of deprecated aliases")]
     pub use super::{
-        RepliconPlugins,
+        RepliconPlugins, RepliconPluginsBuilder,
         shared::{
             AuthMethod, RepliconSharedPlugin,
+            applied_tick::{AppliedTickPlugin, ClientAppliedTick},
+            at_tick::AtTick,
+            authorization::{AuthDenial, AuthToken, AuthorizationFailed},
             backend::{
-                ClientState, ClientStats, ConnectedClientStats, DisconnectRequest, ServerState,
-                channels::{Channel, RepliconChannels},
+                BackendCapabilities, ClientState, ClientStats, ConnectedClientStats,
+                DisconnectRequest, ServerState,
+                channels::{Channel, MutationChannel, RepliconChannels},
                 client_messages::ClientMessages,
                 connected_client::ConnectedClient,
+                message_transforms::{
+                    ClientMessageTransforms, MessageTransform, MessageTransforms,
+                },
                 server_messages::ServerMessages,
             },
-            client_id::ClientId,
+            client_id::{ClientId, ClientRef},
+            connection_roster::{
+                ConnectionEntry, ConnectionPhase, ConnectionRosterPlugin, Moderator, ModeratorOnly,
+                ProtocolStatus,
+            },
+            connection_seed::{ConnectedClientSeed, ConnectionSeed},
+            leak_detection::{LeakDetectionPlugin, LeakKind, ReplicationLeak},
             message::{
-                client_event::{ClientEventAppExt, ClientTriggerExt},
-                client_message::{ClientMessageAppExt, FromClient},
+                client_event::{ClientEventAppExt, ClientTriggerExt, Relayed},
+                client_message::{ClientMessageAppExt, CoalescePolicy, FromClient},
+                entity_message::{EntityMessage, EntityMessageAppExt},
                 server_event::{ServerEventAppExt, ServerTriggerExt},
                 server_message::{SendMode, SendTargets, ServerMessageAppExt, ToClients},
                 shared_event::{SharedEventAppExt, SharedTriggerExt},
                 shared_message::{LocalOrRemote, Sender, SharedMessageAppExt},
             },
-            protocol::{ProtocolHash, ProtocolHasher, ProtocolMismatch},
+            protocol::{ProtocolBreakdown, ProtocolHash, ProtocolHasher, ProtocolMismatch},
+            raw_channel::{ClientRawChannel, RawChannelAppExt, RawChannels, ServerRawChannel},
             replication::{
                 Replicated,
+                crdt::CrdtMerge,
                 diff::{
                     CommandsDiffExt, Diffable, EntityCommandsDiffExt, EntityDiffExt, WorldDiffExt,
                     diff_index::DiffIndex,
                 },
+                entity_pool::{EntityPoolPlugin, Poolable},
+                keyframe::Keyframed,
+                possession::{
+                    AppPossessionExt, Possessed, PossessedBy, PossessionEnded, PossessionStarted,
+                },
+                presence_bitmap::PresenceBitmap,
                 receive_markers::AppMarkerExt,
                 registry::rule_fns::RuleFns,
-                rules::{AppRuleExt, component::ReplicationMode},
-                signature::Signature,
+                rules::{AppRuleExt, component::ReplicationMode, filter::ClientWith},
+                signature::{Signature, SignatureCollision, SignatureSettings},
+                state::{AppStateExt, ReplicatedState},
                 storage::{EntityStorageCtx, ReplicationStorage},
+                template::{AppTemplateExt, EntityTemplate, Template},
+                timer::{AppTimerExt, TimerFns},
+                topic::{AppTopicExt, Subscribe, TopicRegistry, Unsubscribe},
+                upload::{AppUploadExt, Upload, UploadRule},
                 visibility::{
                     AllExcept, ComponentScope, ComponentsScope, FilterScope, SingleComponent,
                     VisibilityFilter,
                 },
             },
+            replication_error::{
+                ClientErrorPolicy, ReplicationError, ReplicationErrorKind, ReplicationErrorSettings,
+            },
             replicon_tick::RepliconTick,
+            server_discovery::{PingServer, ServerDiscovered, ServerDiscoveryPlugin, ServerInfo},
+            tick_timeline::TickTimeline,
         },
     };
 
     #[cfg(feature = "client")]
     pub use super::client::{
-        ClientPlugin, ClientReplicationStats, ClientSystems, Remote, message::ClientMessagePlugin,
+        ClientPlugin, ClientReplicationStats, ClientSystems, MutationJitterBuffer,
+        MutationsApplied, Remote, ReplicationApplyStats, ReplicationBatch, SignatureRebound,
+        UpdateApplied, UpdateApplyMode,
+        interpolating::{Interpolating, InterpolationDelay},
+        message::ClientMessagePlugin,
     };
 
+    #[cfg(feature = "client")]
+    pub use super::shared::replication::entity_pool::EntityPool;
+
     #[cfg(feature = "server")]
     pub use super::server::{
-        AuthorizedClient, PriorityMap, ServerPlugin, ServerSystems, message::ServerMessagePlugin,
-        related_entities::SyncRelatedAppExt, visibility::AppVisibilityExt,
+        AuthorizedClient, InsertionStats, PriorityMap, RemovalStats, ReplicateOnceThenForget,
+        ReplicationSendStats, ServerPlugin, ServerSystems,
+        after_replication::SendAfterReplicationExt,
+        authorization::{Authorizers, ClientProtocol, DenyAuthorizationExt},
+        client_order::ClientOrder,
+        client_views::{ClientViews, ViewOf},
+        client_writes::{EntityCommandsEchoExt, EntityEchoExt},
+        despawn_after_ack::DespawnAfterAck,
+        despawn_on_disconnect::{DespawnOnDisconnect, DisconnectAction},
+        message::ServerMessagePlugin,
+        mutate_tracking::{MutateMessageStatus, MutateStatus, TrackMutateMessages},
+        pressure::{PressureKind, ReplicationPressure, ReplicationPressureSettings},
+        related_entities::SyncRelatedAppExt,
+        resend::{EntityCommandsResendExt, EntityResendExt},
+        visibility::{
+            AppVisibilityExt,
+            level::LevelTag,
+            topic::{InTopics, Subscriptions},
+        },
     };
 
     #[cfg(feature = "client_diagnostics")]
     pub use super::client::diagnostics::ClientDiagnosticsPlugin;
+
+    #[cfg(feature = "asset")]
+    pub use super::shared::replication::asset_handle::{
+        AppAssetHandleExt, AssetHandleComponent, AssetKey,
+    };
 }
 
 pub use bytes;
 pub use postcard;
 
+#[doc(hidden)]
+pub mod __private {
+    pub use bevy::ecs::entity::Entity;
+}
+
+#[cfg(feature = "server")]
+use core::time::Duration;
+
+#[cfg(feature = "server")]
+use bevy::ecs::schedule::ScheduleLabel;
 use bevy::{app::PluginGroupBuilder, prelude::*};
 use prelude::*;
 
@@ -805,16 +909,149 @@ use prelude::*;
 /// * [`ClientPlugin`] - with feature `client`.
 /// * [`ClientMessagePlugin`] - with feature `client`.
 /// * [`ClientDiagnosticsPlugin`] - with feature `client_diagnostics`.
+///
+/// Use [`Self::builder`] to configure the plugins.
 pub struct RepliconPlugins;
 
+impl RepliconPlugins {
+    /// Creates a builder to configure all plugins from the group in one place.
+    pub fn builder() -> RepliconPluginsBuilder {
+        Default::default()
+    }
+}
+
 impl PluginGroup for RepliconPlugins {
     fn build(self) -> PluginGroupBuilder {
-        let mut group = PluginGroupBuilder::start::<Self>();
-        group = group.add(RepliconSharedPlugin::default());
+        Self::builder().build()
+    }
+}
+
+/**
+Configuration for [`RepliconPlugins`].
+
+Covers options of all plugins from the group, so there is no need to combine
+[`PluginGroupBuilder::set`] calls for individual plugins. Options for disabled
+features are unavailable.
+
+Shared options need to match between the client and the server. The builder
+is [`Copy`], so the same configuration can be used for both apps.
+
+Runtime settings, such as [`ReplicationErrorSettings`](shared::replication_error::ReplicationErrorSettings),
+are resources and can be changed after the plugins are added.
+
+# Examples
+
+```
+use core::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::prelude::*;
+
+let replicon = RepliconPlugins::builder()
+    .with_auth_method(AuthMethod::Custom)
+    .with_tick_schedule(PostUpdate)
+    .with_mutations_timeout(Duration::from_secs(5))
+    .with_client_order(ClientOrder::RoundRobin);
+
+let mut server_app = App::new();
+server_app.add_plugins((MinimalPlugins, StatesPlugin, replicon));
+
+let mut client_app = App::new();
+client_app.add_plugins((MinimalPlugins, StatesPlugin, replicon));
+```
+
+# Panics
+
+Adding the plugins panics if the configuration is invalid.
+*/
+#[derive(Default, Clone, Copy)]
+pub struct RepliconPluginsBuilder {
+    shared: RepliconSharedPlugin,
+    #[cfg(feature = "server")]
+    server: ServerPlugin,
+}
+
+impl RepliconPluginsBuilder {
+    /// Sets [`RepliconSharedPlugin::auth_method`].
+    pub fn with_auth_method(mut self, auth_method: AuthMethod) -> Self {
+        self.shared.auth_method = auth_method;
+        self
+    }
+
+    /// Sets [`ServerPlugin::tick_schedule`].
+    #[cfg(feature = "server")]
+    pub fn with_tick_schedule(mut self, tick_schedule: impl ScheduleLabel) -> Self {
+        self.server.tick_schedule = Some(tick_schedule.intern());
+        self
+    }
+
+    /// Sets [`ServerPlugin::tick_schedule`] to `None` to increment
+    /// [`ServerTick`](server::server_tick::ServerTick) manually.
+    #[cfg(feature = "server")]
+    pub fn without_tick_schedule(mut self) -> Self {
+        self.server.tick_schedule = None;
+        self
+    }
+
+    /// Sets [`ServerPlugin::mutations_timeout`].
+    ///
+    /// Should be non-zero.
+    #[cfg(feature = "server")]
+    pub fn with_mutations_timeout(mut self, timeout: Duration) -> Self {
+        self.server.mutations_timeout = timeout;
+        self
+    }
+
+    /// Sets [`ServerPlugin::track_mutate_messages`].
+    #[cfg(feature = "server")]
+    pub fn with_mutate_messages_tracking(mut self, enable: bool) -> Self {
+        self.server.track_mutate_messages = enable;
+        self
+    }
+
+    /// Sets [`ServerPlugin::full_refresh_interval`].
+    ///
+    /// Should be non-zero.
+    #[cfg(feature = "server")]
+    pub fn with_full_refresh_interval(mut self, interval: Duration) -> Self {
+        self.server.full_refresh_interval = Some(interval);
+        self
+    }
+
+    /// Sets [`ServerPlugin::client_order`].
+    #[cfg(feature = "server")]
+    pub fn with_client_order(mut self, client_order: ClientOrder) -> Self {
+        self.server.client_order = client_order;
+        self
+    }
+
+    fn validate(&self) {
+        #[cfg(feature = "server")]
+        {
+            assert!(
+                !self.server.mutations_timeout.is_zero(),
+                "mutations timeout should be non-zero"
+            );
+            assert!(
+                self.server
+                    .full_refresh_interval
+                    .is_none_or(|interval| !interval.is_zero()),
+                "full refresh interval should be non-zero"
+            );
+        }
+    }
+}
+
+impl PluginGroup for RepliconPluginsBuilder {
+    fn build(self) -> PluginGroupBuilder {
+        self.validate();
+
+        let mut group = PluginGroupBuilder::start::<RepliconPlugins>();
+        group = group.add(self.shared);
 
         #[cfg(feature = "server")]
         {
-            group = group.add(ServerPlugin::default()).add(ServerMessagePlugin);
+            group = group.add(self.server).add(ServerMessagePlugin);
         }
 
         #[cfg(feature = "client")]
@@ -1,9 +1,43 @@
-//! Extensions for postcard to make streaming serialization and deserialiation more ergonomic.
+/*!
+Extensions for postcard to make streaming serialization and deserialiation more ergonomic.
 
-use alloc::slice;
+# Allocations on receive
+
+Received messages are stored as [`Bytes`] and split into slices without copying
+until they reach the deserialization functions. The only places where the payload
+is copied are:
+
+- Reassembly of messages split into fragments by
+  [`RepliconChannels::set_fragment_threshold`](crate::shared::backend::channels::RepliconChannels::set_fragment_threshold).
+- [`MessageTransform::inbound`](crate::shared::backend::message_transforms::MessageTransform::inbound),
+  which usually allocates a new buffer for the decoded message, for example on decryption
+  or decompression.
+- Deserialization of types that own their data, such as `Vec<u8>` or `String`.
+
+To avoid the latter for large binary payloads, store them as [`Bytes`] and use
+[`bytes_to_extend_mut`] and [`bytes_from_buf`] in custom serialization functions.
+The returned value will reference the received message instead of copying it,
+which keeps the whole message alive while the value exists. For small payloads
+that are stored for a long time, copying them into an owned type is usually cheaper.
+
+For short-lived values, [`from_buf`] also supports borrowed deserialization,
+like `&[u8]` or `&str`.
+
+# Compact encodings
+
+Postcard already encodes integers as varints, so small values take fewer bytes.
+For even more compact custom serialization functions, this module also provides:
+
+- [`BitWriter`] and [`BitReader`] to pack values that don't need whole bytes, such as bools.
+- [`Quantization`] to encode floats in a known range with fixed precision.
+- [`delta_to_extend_mut`] and [`delta_from_buf`] to encode integers relative to a base value,
+  like a keyframe.
+*/
+
+use alloc::{slice, vec::Vec};
 
 use bevy::prelude::*;
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 use postcard::{
     Deserializer, Serializer, de_flavors::Flavor as DeFlavor, ser_flavors::Flavor as SerFlavor,
 };
@@ -136,6 +170,70 @@ pub fn entity_from_buf<B: Buf>(buf: &mut B) -> postcard::Result<Entity> {
     compact_entity::deserialize(&mut deserializer)
 }
 
+/// Serializes bytes with a length prefix to an [`Extend`] writer.
+///
+/// Uses the same format as postcard uses for byte sequences, so the result can also be
+/// deserialized as `Vec<u8>`. Intended to be paired with [`bytes_from_buf`].
+pub fn bytes_to_extend_mut<W: Extend<u8>>(bytes: &[u8], writer: &mut W) -> postcard::Result<()> {
+    to_extend_mut(&bytes.len(), writer)?;
+    writer.extend(bytes.iter().copied());
+    Ok(())
+}
+
+/// Deserializes length-prefixed bytes as a slice of the buffer without copying.
+///
+/// Accepts the format of [`bytes_to_extend_mut`] or postcard-serialized `Vec<u8>`.
+///
+/// The returned value shares the allocation with `buf`, so the whole received message
+/// stays in memory until all slices of it are dropped. Use `Vec<u8>` instead for small
+/// payloads that outlive the message.
+///
+/// # Examples
+///
+/// Replicating a component with a large payload:
+///
+/// ```
+/// use bevy::{prelude::*, state::app::StatesPlugin};
+/// use bevy_replicon::{
+///     bytes::Bytes,
+///     postcard_utils,
+///     prelude::*,
+///     shared::replication::registry::{
+///         ctx::{SerializeCtx, WriteCtx},
+///         rule_fns::RuleFns,
+///     },
+/// };
+///
+/// # let mut app = App::new();
+/// # app.add_plugins((StatesPlugin, RepliconPlugins));
+/// app.replicate_with(RuleFns::new(serialize_voice, deserialize_voice));
+///
+/// fn serialize_voice(
+///     _ctx: &mut SerializeCtx,
+///     voice: &VoiceChunk,
+///     message: &mut Vec<u8>,
+/// ) -> Result<()> {
+///     postcard_utils::bytes_to_extend_mut(&voice.0, message)?;
+///     Ok(())
+/// }
+///
+/// fn deserialize_voice(_ctx: &mut WriteCtx, message: &mut Bytes) -> Result<VoiceChunk> {
+///     let data = postcard_utils::bytes_from_buf(message)?;
+///     Ok(VoiceChunk(data))
+/// }
+///
+/// #[derive(Component)]
+/// struct VoiceChunk(Bytes);
+/// ```
+pub fn bytes_from_buf(buf: &mut Bytes) -> postcard::Result<Bytes> {
+    let len: usize = from_buf(buf)?;
+    if buf.len() < len {
+        return Err(postcard::Error::DeserializeUnexpectedEnd);
+    }
+
+    Ok(buf.split_to(len))
+}
+
 /// A deserialization flavor for a borrowed buffer.
 ///
 /// Unlike [`Slice`](postcard::de_flavors::Slice), deserialization advances buffer's cursor.
@@ -210,6 +308,299 @@ impl<'a, T: Buf> DeFlavor<'a> for BufFlavor<'a, T> {
     }
 }
 
+/// Serializes the difference between `value` and `base` to an [`Extend`] writer.
+///
+/// The difference is encoded as a zigzag varint, so values close to the base take
+/// a single byte regardless of the sign. Intended to be paired with [`delta_from_buf`].
+///
+/// # Examples
+///
+/// ```
+/// use bevy_replicon::{bytes::Bytes, postcard_utils};
+///
+/// let mut message = Vec::new();
+/// postcard_utils::delta_to_extend_mut(1_000_005, 1_000_000, &mut message).unwrap();
+/// assert_eq!(message.len(), 1);
+///
+/// let mut message = Bytes::from(message);
+/// let value = postcard_utils::delta_from_buf(1_000_000, &mut message).unwrap();
+/// assert_eq!(value, 1_000_005);
+/// ```
+pub fn delta_to_extend_mut<W: Extend<u8>>(
+    value: i64,
+    base: i64,
+    writer: &mut W,
+) -> postcard::Result<()> {
+    to_extend_mut(&value.wrapping_sub(base), writer)
+}
+
+/// Deserializes a value encoded with [`delta_to_extend_mut`] relative to `base`.
+pub fn delta_from_buf<B: Buf>(base: i64, buf: &mut B) -> postcard::Result<i64> {
+    let delta: i64 = from_buf(buf)?;
+    Ok(base.wrapping_add(delta))
+}
+
+/// Maps a signed integer to an unsigned one so that values close to zero stay small.
+///
+/// Useful to write signed values with [`BitWriter::write_bits`].
+/// See also [`zigzag_decode`].
+pub fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Reverses [`zigzag_encode`].
+pub fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Packs values into bits instead of whole bytes.
+///
+/// Bits are accumulated in memory and written with [`Self::to_extend_mut`]
+/// as length-prefixed bytes, so they can be mixed with regular postcard values.
+/// Intended to be paired with [`BitReader`].
+///
+/// # Examples
+///
+/// Replicating a component with bitpacked fields:
+///
+/// ```
+/// use bevy::{prelude::*, state::app::StatesPlugin};
+/// use bevy_replicon::{
+///     bytes::Bytes,
+///     postcard_utils::{BitReader, BitWriter, Quantization},
+///     prelude::*,
+///     shared::replication::registry::{
+///         ctx::{SerializeCtx, WriteCtx},
+///         rule_fns::RuleFns,
+///     },
+/// };
+///
+/// # let mut app = App::new();
+/// # app.add_plugins((StatesPlugin, RepliconPlugins));
+/// app.replicate_with(RuleFns::new(serialize_unit, deserialize_unit));
+///
+/// const HEALTH: Quantization = Quantization::new(0.0, 100.0, 7);
+///
+/// fn serialize_unit(_ctx: &mut SerializeCtx, unit: &Unit, message: &mut Vec<u8>) -> Result<()> {
+///     let mut writer = BitWriter::default();
+///     writer.write_bool(unit.stunned);
+///     writer.write_bool(unit.burning);
+///     writer.write_quantized(unit.health, HEALTH);
+///     writer.to_extend_mut(message)?;
+///     Ok(())
+/// }
+///
+/// fn deserialize_unit(_ctx: &mut WriteCtx, message: &mut Bytes) -> Result<Unit> {
+///     let mut reader = BitReader::from_buf(message)?;
+///     Ok(Unit {
+///         stunned: reader.read_bool()?,
+///         burning: reader.read_bool()?,
+///         health: reader.read_quantized(HEALTH)?,
+///     })
+/// }
+///
+/// #[derive(Component)]
+/// struct Unit {
+///     stunned: bool,
+///     burning: bool,
+///     health: f32,
+/// }
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+
+    /// Number of written bits.
+    len: usize,
+}
+
+impl BitWriter {
+    /// Writes a single bit.
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_bits(value as u64, 1);
+    }
+
+    /// Writes the lowest `bits` bits of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than 64.
+    pub fn write_bits(&mut self, mut value: u64, mut bits: u32) {
+        assert!(bits <= u64::BITS, "can't write more than 64 bits at once");
+        if bits < u64::BITS {
+            value &= (1 << bits) - 1;
+        }
+
+        while bits > 0 {
+            let offset = (self.len % 8) as u32;
+            if offset == 0 {
+                self.bytes.push(0);
+            }
+
+            let count = bits.min(8 - offset);
+            let last = self.bytes.last_mut().expect("byte should be pushed");
+            *last |= ((value & ((1 << count) - 1)) as u8) << offset;
+            value >>= count;
+            bits -= count;
+            self.len += count as usize;
+        }
+    }
+
+    /// Writes a float quantized with the given parameters.
+    pub fn write_quantized(&mut self, value: f32, quantization: Quantization) {
+        self.write_bits(quantization.quantize(value), quantization.bits);
+    }
+
+    /// Returns the number of written bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bits were written.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes accumulated bits to an [`Extend`] writer, padded to whole bytes.
+    ///
+    /// Uses the format of [`bytes_to_extend_mut`].
+    pub fn to_extend_mut<W: Extend<u8>>(&self, writer: &mut W) -> postcard::Result<()> {
+        bytes_to_extend_mut(&self.bytes, writer)
+    }
+}
+
+/// Reads values packed by [`BitWriter`].
+///
+/// See [`BitWriter`] for an example.
+#[derive(Debug, Clone)]
+pub struct BitReader {
+    bytes: Bytes,
+
+    /// Number of read bits.
+    position: usize,
+}
+
+impl BitReader {
+    /// Takes bytes written by [`BitWriter::to_extend_mut`] from a buffer without copying.
+    pub fn from_buf(buf: &mut Bytes) -> postcard::Result<Self> {
+        Ok(Self {
+            bytes: bytes_from_buf(buf)?,
+            position: 0,
+        })
+    }
+
+    /// Reads a single bit.
+    pub fn read_bool(&mut self) -> postcard::Result<bool> {
+        self.read_bits(1).map(|value| value != 0)
+    }
+
+    /// Reads `bits` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than 64.
+    pub fn read_bits(&mut self, bits: u32) -> postcard::Result<u64> {
+        assert!(bits <= u64::BITS, "can't read more than 64 bits at once");
+        if self.position + bits as usize > self.bytes.len() * 8 {
+            return Err(postcard::Error::DeserializeUnexpectedEnd);
+        }
+
+        let mut value = 0;
+        let mut read = 0;
+        while read < bits {
+            let byte = self.bytes[self.position / 8];
+            let offset = (self.position % 8) as u32;
+            let count = (bits - read).min(8 - offset);
+            let chunk = (byte >> offset) as u64 & ((1 << count) - 1);
+            value |= chunk << read;
+            read += count;
+            self.position += count as usize;
+        }
+
+        Ok(value)
+    }
+
+    /// Reads a float quantized with the given parameters.
+    pub fn read_quantized(&mut self, quantization: Quantization) -> postcard::Result<f32> {
+        self.read_bits(quantization.bits)
+            .map(|value| quantization.dequantize(value))
+    }
+
+    /// Returns the number of bits left, including padding.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+}
+
+/// Parameters to encode a float in a known range as an integer with fixed precision.
+///
+/// Values outside the range are clamped. Use it with [`BitWriter::write_quantized`] or
+/// serialize the result of [`Self::quantize`] directly to get a varint.
+///
+/// See [`BitWriter`] for an example.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantization {
+    /// Minimum encoded value.
+    pub min: f32,
+
+    /// Maximum encoded value.
+    pub max: f32,
+
+    /// Number of bits for the encoded value, from 1 to 32.
+    pub bits: u32,
+}
+
+impl Quantization {
+    /// Creates a new instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not less than `max` or `bits` is not in the range from 1 to 32.
+    pub const fn new(min: f32, max: f32, bits: u32) -> Self {
+        assert!(min < max, "minimum should be less than maximum");
+        assert!(matches!(bits, 1..=32), "bits should be from 1 to 32");
+        Self { min, max, bits }
+    }
+
+    /// Creates a new instance with the smallest number of bits that provides at least `precision`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not less than `max`, `precision` is not positive
+    /// or more than 32 bits are required.
+    pub fn with_precision(min: f32, max: f32, precision: f32) -> Self {
+        assert!(precision > 0.0, "precision should be positive");
+        let steps = ((max - min) as f64 / precision as f64) as u64 + 1;
+        let bits = (u64::BITS - steps.leading_zeros()).max(1);
+        Self::new(min, max, bits)
+    }
+
+    /// Returns the maximum difference between the original and decoded value.
+    pub fn precision(self) -> f32 {
+        ((self.max - self.min) as f64 / self.max_step() as f64 / 2.0) as f32
+    }
+
+    /// Encodes a value as an integer that fits into [`Self::bits`].
+    pub fn quantize(self, value: f32) -> u64 {
+        let range = (self.max - self.min) as f64;
+        let normalized = ((value - self.min) as f64 / range).clamp(0.0, 1.0);
+        (normalized * self.max_step() as f64 + 0.5) as u64
+    }
+
+    /// Decodes a value encoded with [`Self::quantize`].
+    ///
+    /// Values that don't fit into [`Self::bits`] are clamped.
+    pub fn dequantize(self, value: u64) -> f32 {
+        let max_step = self.max_step();
+        let normalized = value.min(max_step) as f64 / max_step as f64;
+        (self.min as f64 + normalized * (self.max - self.min) as f64) as f32
+    }
+
+    fn max_step(self) -> u64 {
+        (1 << self.bits) - 1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bevy::ecs::entity::{EntityGeneration, EntityIndex};
@@ -240,4 +631,122 @@ mod tests {
         let entity = entity_from_buf(&mut &*buffer).unwrap();
         assert_eq!(entity, expected_entity);
     }
+
+    #[test]
+    fn bytes() {
+        let mut buffer = Vec::new();
+        bytes_to_extend_mut(&[1, 2, 3], &mut buffer).unwrap();
+        to_extend_mut(&4u8, &mut buffer).unwrap();
+
+        let mut message = Bytes::from(buffer);
+        let start = message.as_ptr();
+        let bytes = bytes_from_buf(&mut message).unwrap();
+        assert_eq!(bytes, [1, 2, 3].as_slice());
+        assert_eq!(
+            bytes.as_ptr(),
+            start.wrapping_add(1),
+            "should reference the message"
+        );
+        assert_eq!(message, [4].as_slice());
+    }
+
+    #[test]
+    fn bytes_as_vec() {
+        let mut buffer = Vec::new();
+        to_extend_mut(&vec![1u8, 2], &mut buffer).unwrap();
+        let mut message = Bytes::from(buffer.clone());
+        assert_eq!(bytes_from_buf(&mut message).unwrap(), [1, 2].as_slice());
+
+        buffer.clear();
+        bytes_to_extend_mut(&[1, 2], &mut buffer).unwrap();
+        let vec: Vec<u8> = from_buf(&mut &*buffer).unwrap();
+        assert_eq!(vec, [1, 2]);
+    }
+
+    #[test]
+    fn bytes_unexpected_end() {
+        let mut message = Bytes::from_static(&[3, 1, 2]);
+        assert!(bytes_from_buf(&mut message).is_err());
+    }
+
+    #[test]
+    fn delta() {
+        let mut buffer = Vec::new();
+        delta_to_extend_mut(95, 100, &mut buffer).unwrap();
+        delta_to_extend_mut(i64::MIN, i64::MAX, &mut buffer).unwrap();
+        assert_eq!(buffer[0], 9, "should be encoded as a zigzag varint");
+
+        let mut message = Bytes::from(buffer);
+        assert_eq!(delta_from_buf(100, &mut message).unwrap(), 95);
+        assert_eq!(delta_from_buf(i64::MAX, &mut message).unwrap(), i64::MIN);
+        assert!(message.is_empty());
+    }
+
+    #[test]
+    fn zigzag() {
+        for value in [0, 1, -1, 2, -2, i64::MAX, i64::MIN] {
+            assert_eq!(zigzag_decode(zigzag_encode(value)), value);
+        }
+        assert_eq!(zigzag_encode(-1), 1);
+        assert_eq!(zigzag_encode(1), 2);
+    }
+
+    #[test]
+    fn bits() {
+        let mut writer = BitWriter::default();
+        writer.write_bool(true);
+        writer.write_bool(false);
+        writer.write_bits(0b101, 3);
+        writer.write_bits(u64::MAX, 64);
+        writer.write_bits(0xFF, 4); // Only the lowest bits should be written.
+        writer.write_bits(zigzag_encode(-3), 4);
+        assert_eq!(writer.len(), 77);
+
+        let mut buffer = Vec::new();
+        writer.to_extend_mut(&mut buffer).unwrap();
+        to_extend_mut(&4u8, &mut buffer).unwrap();
+        assert_eq!(buffer.len(), 1 + 10 + 1);
+
+        let mut message = Bytes::from(buffer);
+        let mut reader = BitReader::from_buf(&mut message).unwrap();
+        assert!(reader.read_bool().unwrap());
+        assert!(!reader.read_bool().unwrap());
+        assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+        assert_eq!(reader.read_bits(64).unwrap(), u64::MAX);
+        assert_eq!(reader.read_bits(4).unwrap(), 0xF);
+        assert_eq!(zigzag_decode(reader.read_bits(4).unwrap()), -3);
+        assert_eq!(reader.remaining(), 3);
+        assert!(reader.read_bits(4).is_err());
+
+        let value: u8 = from_buf(&mut message).unwrap();
+        assert_eq!(value, 4);
+    }
+
+    #[test]
+    fn quantization() {
+        let quantization = Quantization::new(-10.0, 10.0, 12);
+        for value in [-10.0, -3.3, 0.001, 1.0, 7.5, 10.0] {
+            let decoded = quantization.dequantize(quantization.quantize(value));
+            assert!((decoded - value).abs() <= quantization.precision());
+        }
+        assert_eq!(quantization.dequantize(quantization.quantize(-20.0)), -10.0);
+        assert_eq!(quantization.dequantize(quantization.quantize(20.0)), 10.0);
+
+        let mut writer = BitWriter::default();
+        writer.write_quantized(1.5, quantization);
+        let mut buffer = Vec::new();
+        writer.to_extend_mut(&mut buffer).unwrap();
+
+        let mut message = Bytes::from(buffer);
+        let mut reader = BitReader::from_buf(&mut message).unwrap();
+        let decoded = reader.read_quantized(quantization).unwrap();
+        assert!((decoded - 1.5).abs() <= quantization.precision());
+    }
+
+    #[test]
+    fn quantization_precision() {
+        let quantization = Quantization::with_precision(0.0, 10.0, 0.01);
+        assert_eq!(quantization.bits, 10);
+        assert!(quantization.precision() <= 0.01);
+    }
 }
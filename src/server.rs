@@ -1,62 +1,97 @@
+pub mod after_replication;
+pub mod authorization;
+pub mod census;
+pub mod changed_this_tick;
+pub mod client_order;
+pub mod client_views;
+pub mod client_writes;
+pub mod despawn_after_ack;
+pub mod despawn_on_disconnect;
+pub mod dry_run;
+pub mod host_migration;
 pub mod message;
+pub mod message_sender;
+pub mod mutate_tracking;
+pub mod pressure;
 pub mod related_entities;
 pub(super) mod removal_buffer;
 pub mod replicated_archetypes;
-pub(super) mod replication_messages;
+pub mod replication_messages;
 mod replication_query;
+pub mod resend;
+pub mod rule_stats;
+mod serialization_cache;
 pub mod server_tick;
+pub mod snapshot;
 pub mod visibility;
 
-use core::time::Duration;
+use alloc::vec::Vec;
+use core::{mem, time::Duration};
 
 use bevy::{
     ecs::{
         archetype::Archetypes,
         change_detection::{CheckChangeTicks, Tick},
-        entity::{Entities, EntityHash, EntityHashMap},
+        entity::{Entities, EntityHashMap},
         intern::Interned,
         schedule::ScheduleLabel,
         system::SystemChangeTick,
     },
-    platform::collections::{HashSet, hash_map::Entry},
+    platform::{
+        collections::{HashMap, HashSet},
+        time::Instant,
+    },
     prelude::*,
     time::common_conditions::on_timer,
 };
 use bytes::Buf;
-use log::{Level, debug, log_enabled, trace, warn};
+use log::{Level, debug, error, log_enabled, trace, warn};
 
 use crate::{
     postcard_utils,
     prelude::*,
     server::{
+        authorization::{Authorizers, BannedClients},
+        census::ReplicationCensus,
+        changed_this_tick::ChangedThisTick,
+        client_order::ClientOrder,
+        despawn_on_disconnect::PendingDisconnects,
+        dry_run::DryRunClient,
+        pressure::{PriorityScale, ReplicationPressure, ReplicationPressureSettings},
         replicated_archetypes::ReplicatedArchetypes,
         replication_messages::{mutations::MutationsSplit, serialized_data::ErasedComponent},
+        rule_stats::RuleStats,
         visibility::registry::FilterRegistry,
     },
     shared::{
         backend::channels::ClientChannel,
+        leak_detection::DespawnHistory,
         message::server_message::message_buffer::MessageBuffer,
         replication::{
             client_ticks::{ClientTicks, EntityTicks},
             registry::{
-                ComponentIndex, ReplicationRegistry, component_mask::ComponentMask,
-                ctx::SerializeCtx,
+                ComponentIndex, ReplicationRegistry, ctx::SerializeCtx, removal_id::RemovalId,
             },
             rules::ReplicationRules,
             storage::ReplicationStorage,
+            template::EntityTemplates,
             visibility::VisibilityScope,
         },
+        replication_error::{ErrorReporter, ReplicationErrorKind},
     },
 };
+use client_views::ViewOf;
 use related_entities::RelatedEntities;
 use removal_buffer::RemovalBuffer;
 use replication_messages::{
     mutations::Mutations, serialized_data::SerializedData, updates::Updates,
 };
 use replication_query::ReplicationQuery;
+use serialization_cache::SerializationCache;
 use server_tick::ServerTick;
 use visibility::client_visibility::ClientVisibility;
 
+#[derive(Clone, Copy)]
 pub struct ServerPlugin {
     /// Schedule in which [`ServerTick`] is incremented.
     ///
@@ -104,6 +139,23 @@ pub struct ServerPlugin {
     /// entity value didn't change on a tick if all updates were received and
     /// [`ConfirmHistory`](crate::client::confirm_history::ConfirmHistory) don't have this tick confirmed.
     pub track_mutate_messages: bool,
+
+    /// How often all components are re-sent to all clients regardless of their changes.
+    ///
+    /// Heals silent divergence, for example, caused by custom in-place deserialization functions.
+    /// Affects only components with [`ReplicationMode::OnChange`]. They are re-sent as insertions
+    /// in update messages, so a refresh costs about as much as replicating the world to a newly
+    /// connected client. Use [`AppRuleExt::without_full_refresh`] to exclude huge components.
+    ///
+    /// By default set to `None`. A refresh for a single client can also be requested
+    /// with [`EntityResendExt::resend_all`](resend::EntityResendExt::resend_all).
+    pub full_refresh_interval: Option<Duration>,
+
+    /// Order in which clients receive replication messages each tick.
+    ///
+    /// Matters only for backends that don't send all messages at once.
+    /// By default set to [`ClientOrder::Fixed`].
+    pub client_order: ClientOrder,
 }
 
 impl ServerPlugin {
@@ -113,6 +165,8 @@ impl ServerPlugin {
             tick_schedule: Some(tick_schedule.intern()),
             mutations_timeout: Duration::from_secs(10),
             track_mutate_messages: false,
+            full_refresh_interval: None,
+            client_order: Default::default(),
         }
     }
 }
@@ -139,8 +193,19 @@ impl Plugin for ServerPlugin {
             .init_resource::<MessageBuffer>()
             .init_resource::<RelatedEntities>()
             .init_resource::<FilterRegistry>()
+            .init_resource::<ReplicationPressureSettings>()
+            .init_resource::<PriorityScale>()
+            .init_resource::<RulePriorities>()
+            .init_resource::<LodBands>()
+            .init_resource::<SerializationCache>()
+            .init_resource::<PendingDisconnects>()
+            .init_resource::<Authorizers>()
+            .init_resource::<BannedClients>()
+            .add_message::<ReplicationPressure>()
+            .add_message::<MutateMessageStatus>()
             .register_required_components::<Replicated, TicksTracked>()
             .insert_resource(TrackMutateMessages(self.track_mutate_messages))
+            .insert_resource(self.client_order)
             .configure_sets(
                 PreUpdate,
                 (ServerSystems::ReceivePackets, ServerSystems::Receive).chain(),
@@ -156,10 +221,15 @@ impl Plugin for ServerPlugin {
             )
             .add_observer(handle_connect)
             .add_observer(handle_disconnect)
+            .add_observer(send_connection_seed)
             .add_observer(check_mutation_ticks)
             .add_observer(buffer_despawn)
             .add_observer(cleanup_unreplicated)
             .add_observer(cleanup_storage)
+            .add_observer(cleanup_serialization_cache)
+            .add_observer(cleanup_lod_entity)
+            .add_observer(cleanup_lod_client)
+            .add_observer(despawn_on_disconnect::mark_pending)
             .add_systems(
                 PreUpdate,
                 (
@@ -170,21 +240,60 @@ impl Plugin for ServerPlugin {
                     .in_set(ServerSystems::Receive)
                     .run_if(in_state(ServerState::Running)),
             )
+            .add_systems(
+                PreUpdate,
+                (
+                    despawn_on_disconnect::apply_pending,
+                    despawn_after_ack::despawn_acked,
+                )
+                    .after(ServerSystems::Receive)
+                    .run_if(in_state(ServerState::Running)),
+            )
+            .add_systems(
+                PreUpdate,
+                (report_invalid_channels, transform_received)
+                    .chain()
+                    .after(ServerSystems::ReceivePackets)
+                    .before(ServerSystems::Receive)
+                    .run_if(in_state(ServerState::Running)),
+            )
+            .add_systems(
+                PreUpdate,
+                client_order::record_served
+                    .after(ServerSystems::ReceivePackets)
+                    .before(ServerSystems::Receive)
+                    .run_if(resource_equals(ClientOrder::LongestWaiting))
+                    .run_if(in_state(ServerState::Running)),
+            )
             .add_systems(OnExit(ServerState::Running), reset)
             .add_systems(
                 PostUpdate,
                 (
+                    record_tick,
                     prepare_messages,
+                    client_views::merge_views,
+                    despawn_after_ack::hide,
                     collect_mappings,
                     collect_despawns,
                     collect_removals,
+                    evaluate_rule_priorities,
+                    evaluate_lod_bands,
                     collect_changes,
+                    pressure::check_pressure,
                     send_messages,
                 )
                     .chain()
                     .run_if(resource_changed::<ServerTick>)
                     .in_set(ServerSystems::Send)
                     .run_if(in_state(ServerState::Running)),
+            )
+            .add_systems(
+                PostUpdate,
+                (transform_sent, dry_run::discard_messages)
+                    .chain()
+                    .after(ServerSystems::Send)
+                    .before(ServerSystems::SendPackets)
+                    .run_if(in_state(ServerState::Running)),
             );
 
         if let Some(tick_schedule) = self.tick_schedule {
@@ -197,11 +306,22 @@ impl Plugin for ServerPlugin {
             );
         }
 
+        if let Some(interval) = self.full_refresh_interval {
+            debug!("using full refresh interval `{interval:?}`");
+            app.add_systems(
+                PreUpdate,
+                request_full_refresh
+                    .run_if(on_timer(interval))
+                    .after(ServerSystems::Receive)
+                    .run_if(in_state(ServerState::Running)),
+            );
+        }
+
         let auth_method = app.world().resource::<AuthMethod>();
         debug!("using authorization method `{auth_method:?}`");
         match auth_method {
-            AuthMethod::ProtocolCheck => {
-                app.add_observer(check_protocol);
+            AuthMethod::Authorizers => {
+                app.add_observer(authorization::authorize);
             }
             AuthMethod::None => {
                 app.register_required_components::<ConnectedClient, AuthorizedClient>();
@@ -237,7 +357,7 @@ impl Plugin for ServerPlugin {
         app.world_mut()
             .resource_scope(|world, mut messages: Mut<ServerMessages>| {
                 let channels = world.resource::<RepliconChannels>();
-                messages.setup_client_channels(channels.client_channels().len());
+                messages.setup_channels(channels);
             });
     }
 }
@@ -252,30 +372,44 @@ fn handle_disconnect(remove: On<Remove, ConnectedClient>, mut messages: ResMut<S
     messages.remove_client(remove.entity);
 }
 
-fn check_protocol(
-    client_protocol: On<FromClient<ProtocolHash>>,
+fn send_connection_seed(
+    add: On<Add, AuthorizedClient>,
     mut commands: Commands,
-    mut disconnects: MessageWriter<DisconnectRequest>,
-    protocol: Res<ProtocolHash>,
+    seeds: Query<&ConnectedClientSeed>,
 ) {
-    let client = client_protocol
-        .client_id
-        .entity()
-        .expect("protocol hash sent only from clients");
-
-    if **client_protocol == *protocol {
-        debug!("marking client `{client}` as authorized");
-        commands.entity(client).insert(AuthorizedClient);
-    } else {
-        debug!(
-            "disconnecting client `{client}` due to protocol mismatch (client: `{:?}`, server: `{:?}`)",
-            **client_protocol, *protocol
-        );
-        commands.server_trigger(ToClients {
-            targets: SendTargets::Single(client_protocol.client_id),
-            message: ProtocolMismatch,
-        });
-        disconnects.write(DisconnectRequest { client });
+    let seed = match seeds.get(add.entity) {
+        Ok(&seed) => *seed,
+        #[cfg(feature = "getrandom")]
+        Err(_) => match ConnectionSeed::random() {
+            Ok(seed) => {
+                commands
+                    .entity(add.entity)
+                    .insert(ConnectedClientSeed(seed));
+                seed
+            }
+            Err(e) => {
+                error!("unable to generate seed for client `{}`: {e}", add.entity);
+                return;
+            }
+        },
+        #[cfg(not(feature = "getrandom"))]
+        Err(_) => {
+            debug!("client `{}` has no seed to send", add.entity);
+            return;
+        }
+    };
+
+    debug!("sending `{seed:?}` to client `{}`", add.entity);
+    commands.server_trigger(ToClients {
+        targets: SendTargets::Single(add.entity.into()),
+        message: seed,
+    });
+}
+
+fn request_full_refresh(mut clients: Query<&mut ClientTicks>) {
+    debug!("requesting full refresh for all clients");
+    for mut ticks in &mut clients {
+        ticks.full_refresh = true;
     }
 }
 
@@ -286,7 +420,9 @@ fn check_mutation_ticks(check: On<CheckChangeTicks>, mut clients: Query<&mut Cli
     );
     for mut ticks in &mut clients {
         for entity_ticks in ticks.entities.values_mut() {
-            entity_ticks.system_tick.check_tick(*check);
+            for channel_ticks in entity_ticks.channels_mut() {
+                channel_ticks.system_tick.check_tick(*check);
+            }
         }
     }
 }
@@ -304,7 +440,6 @@ fn buffer_removals(
     state: Res<State<ServerState>>,
     mut replicated_archetypes: ResMut<ReplicatedArchetypes>,
     rules: Res<ReplicationRules>,
-    registry: Option<Res<ReplicationRegistry>>,
     mut removals: ResMut<RemovalBuffer>,
 ) {
     if *state != ServerState::Running {
@@ -314,14 +449,10 @@ fn buffer_removals(
     let components = remove.trigger().components;
     if components.contains(&replicated_archetypes.marker_id()) {
         trace!("ignoring removals for despawned `{}`", remove.entity);
+        removals.remove_sent(remove.entity);
         return;
     }
 
-    // Observers can't use run conditions. We return early on the client, but system parameters
-    // are validated before the observer runs. Because of this, the registry may not be present
-    // in the world during replication receive, so it needs to be optional.
-    let registry = registry.expect("registry should always exist on the server");
-
     replicated_archetypes.update(archetypes, &rules);
     let location = entities.get_spawned(remove.entity).unwrap();
     let Some(archetype) = replicated_archetypes.get(location.archetype_id) else {
@@ -333,7 +464,7 @@ fn buffer_removals(
         return;
     };
 
-    removals.insert(remove.entity, components, archetype, &registry);
+    removals.insert(remove.entity, components, archetype, &rules);
 }
 
 fn buffer_despawn(
@@ -356,23 +487,142 @@ fn cleanup_unreplicated(
     if *state == ServerState::Running && !replicated.contains(despawn.entity) {
         trace!("cleaning up ticks for despawned `{}`", despawn.entity);
         for mut ticks in &mut clients {
-            ticks.entities.remove(&despawn.entity);
+            ticks.entities.remove(despawn.entity);
+            ticks.forgotten.remove(&despawn.entity);
         }
     }
 }
 
 fn cleanup_acks(
     mutations_timeout: Duration,
-) -> impl FnMut(Query<&mut ClientTicks>, Res<Time<Real>>) {
-    move |mut clients: Query<&mut ClientTicks>, time: Res<Time<Real>>| {
+) -> impl FnMut(
+    Query<(Entity, &mut ClientTicks)>,
+    Res<Time<Real>>,
+    Res<TrackMutateMessages>,
+    MessageWriter<MutateMessageStatus>,
+) {
+    move |mut clients: Query<(Entity, &mut ClientTicks)>,
+          time: Res<Time<Real>>,
+          track_mutate_messages: Res<TrackMutateMessages>,
+          mut statuses: MessageWriter<MutateMessageStatus>| {
         let min_timestamp = time.elapsed().saturating_sub(mutations_timeout);
-        for mut ticks in &mut clients {
-            ticks.cleanup_older_mutations(min_timestamp);
+        for (client, mut ticks) in &mut clients {
+            ticks.cleanup_older_mutations(min_timestamp, |index, server_tick| {
+                if **track_mutate_messages {
+                    statuses.write(MutateMessageStatus {
+                        client,
+                        index,
+                        server_tick,
+                        status: MutateStatus::Expired,
+                    });
+                }
+            });
         }
     }
 }
 
-fn receive_acks(mut messages: ResMut<ServerMessages>, mut clients: Query<&mut ClientTicks>) {
+/// Reports clients that sent messages over unknown channels.
+fn report_invalid_channels(
+    mut messages: ResMut<ServerMessages>,
+    mut errors: ResMut<ErrorReporter>,
+) {
+    for (client, channel_id) in messages.drain_invalid_channels() {
+        if errors.report(
+            ReplicationErrorKind::Deserialize,
+            Some(channel_id.get()),
+            Some(client),
+            format_args!("unknown channel {channel_id}"),
+        ) {
+            error!("discarding message from client `{client}` over unknown channel {channel_id}");
+        }
+    }
+}
+
+/// Reverts [`MessageTransforms`] for received messages.
+///
+/// Runs before any other system that reads messages.
+fn transform_received(
+    mut messages: ResMut<ServerMessages>,
+    mut errors: ResMut<ErrorReporter>,
+    mut clients: Query<&mut MessageTransforms>,
+) {
+    if clients.is_empty() {
+        return;
+    }
+
+    messages.retain_received_mut(|channel_id, (client, message)| {
+        let Ok(mut transforms) = clients.get_mut(*client) else {
+            return true;
+        };
+
+        match transforms.inbound(mem::take(message)) {
+            Ok(transformed) => {
+                *message = transformed;
+                true
+            }
+            Err(e) => {
+                if errors.report(
+                    ReplicationErrorKind::Deserialize,
+                    Some(channel_id.get()),
+                    Some(*client),
+                    &e,
+                ) {
+                    error!(
+                        "discarding message from client `{client}` that failed to transform: {e}"
+                    );
+                }
+                false
+            }
+        }
+    });
+}
+
+/// Applies [`MessageTransforms`] to sent messages.
+///
+/// Runs after all messages are written, including split mutations.
+fn transform_sent(
+    mut messages: ResMut<ServerMessages>,
+    mut errors: ResMut<ErrorReporter>,
+    mut clients: Query<&mut MessageTransforms>,
+) {
+    if clients.is_empty() {
+        return;
+    }
+
+    messages.retain_sent_mut(|(client, channel_id, message)| {
+        let Ok(mut transforms) = clients.get_mut(*client) else {
+            return true;
+        };
+
+        match transforms.outbound(mem::take(message)) {
+            Ok(transformed) => {
+                *message = transformed;
+                true
+            }
+            Err(e) => {
+                if errors.report(
+                    ReplicationErrorKind::Serialize,
+                    Some(channel_id.get()),
+                    Some(*client),
+                    &e,
+                ) {
+                    error!(
+                        "discarding message for client `{client}` that failed to transform: {e}"
+                    );
+                }
+                false
+            }
+        }
+    });
+}
+
+fn receive_acks(
+    mut messages: ResMut<ServerMessages>,
+    mut errors: ResMut<ErrorReporter>,
+    mut statuses: MessageWriter<MutateMessageStatus>,
+    track_mutate_messages: Res<TrackMutateMessages>,
+    mut clients: Query<&mut ClientTicks>,
+) {
     for (client, mut message) in messages.receive(ClientChannel::MutationAcks) {
         let Ok(mut ticks) = clients.get_mut(client) else {
             debug!("ignoring acks for disconnected client `{client}`");
@@ -381,16 +631,57 @@ fn receive_acks(mut messages: ResMut<ServerMessages>, mut clients: Query<&mut Cl
         while message.has_remaining() {
             match postcard_utils::from_buf(&mut message) {
                 Ok(mutate_index) => {
-                    ticks.ack_mutate_message(client, mutate_index);
+                    if let Some(server_tick) = ticks.ack_mutate_message(client, mutate_index)
+                        && **track_mutate_messages
+                    {
+                        statuses.write(MutateMessageStatus {
+                            client,
+                            index: mutate_index,
+                            server_tick,
+                            status: MutateStatus::Acknowledged,
+                        });
+                    }
                 }
                 Err(e) => {
-                    debug!("unable to deserialize mutate index from client `{client}`: {e}")
+                    if errors.report(
+                        ReplicationErrorKind::Deserialize,
+                        Some(ClientChannel::MutationAcks.into()),
+                        Some(client),
+                        &e,
+                    ) {
+                        debug!("unable to deserialize mutate index from client `{client}`: {e}")
+                    }
+                }
+            }
+        }
+    }
+
+    for (client, mut message) in messages.receive(ClientChannel::UpdateAcks) {
+        let Ok(mut ticks) = clients.get_mut(client) else {
+            debug!("ignoring update ack for disconnected client `{client}`");
+            continue;
+        };
+        match postcard_utils::from_buf(&mut message) {
+            Ok(message_tick) => ticks.ack_update_message(client, message_tick),
+            Err(e) => {
+                if errors.report(
+                    ReplicationErrorKind::Deserialize,
+                    Some(ClientChannel::UpdateAcks.into()),
+                    Some(client),
+                    &e,
+                ) {
+                    debug!("unable to deserialize update ack from client `{client}`: {e}")
                 }
             }
         }
     }
 }
 
+fn record_tick(server_tick: Res<ServerTick>, time: Res<Time>, mut timeline: ResMut<TickTimeline>) {
+    trace!("recording `{:?}` at {:?}", **server_tick, time.elapsed());
+    timeline.record(**server_tick, time.elapsed());
+}
+
 fn prepare_messages(
     change_tick: SystemChangeTick,
     mut related_entities: ResMut<RelatedEntities>,
@@ -464,12 +755,14 @@ fn should_send_mapping(
     }
 
     // Check if the client already received the entity.
-    !ticks.entities.contains_key(&entity)
+    !ticks.entities.contains_key(entity)
 }
 
 /// Collect entity despawns from this tick into update messages.
 fn collect_despawns(
     registry: Res<FilterRegistry>,
+    time: Res<Time<Real>>,
+    server_tick: Res<ServerTick>,
     mut serialized: ResMut<SerializedData>,
     mut despawn_buffer: ResMut<DespawnBuffer>,
     mut clients: Query<(
@@ -478,33 +771,53 @@ fn collect_despawns(
         &mut ClientTicks,
         &mut PriorityMap,
         &mut ClientVisibility,
+        Option<&mut DespawnHistory>,
     )>,
+    mut views: Query<
+        (&mut PriorityMap, &mut ClientVisibility),
+        (With<ViewOf>, Without<ClientTicks>),
+    >,
 ) -> Result<()> {
     for entity in despawn_buffer.drain(..) {
         let entity_range = serialized.write_entity(entity)?;
-        for (client, mut message, mut ticks, mut priority, mut visibility) in &mut clients {
-            if ticks.entities.remove(&entity).is_some() {
+        for (client, mut message, mut ticks, mut priority, mut visibility, history) in &mut clients
+        {
+            let forgotten = ticks.forgotten.remove(&entity);
+            if ticks.entities.remove(entity).is_some() || forgotten {
                 // Write despawn only if the entity was previously sent because
                 // spawn and despawn could happen during the same tick.
                 trace!("writing despawn for `{entity}` for client `{client}`");
                 message.add_despawn(entity_range.clone());
+                if let Some(mut history) = history {
+                    history.insert(entity, **server_tick, time.elapsed());
+                    message.request_ack();
+                }
             }
             visibility.remove_despawned(entity);
             priority.remove(&entity);
         }
+        for (mut priority, mut visibility) in &mut views {
+            visibility.remove_despawned(entity);
+            priority.remove(&entity);
+        }
     }
 
-    for (client, mut message, mut ticks, mut priority, visibility) in clients {
+    for (client, mut message, mut ticks, mut priority, visibility, mut history) in clients {
         for (entity, filter_mask) in visibility.iter_lost() {
             // Skip visibility changes that hide only components.
             if !filter_mask.is_hidden(&registry) {
                 continue;
             }
 
-            if ticks.entities.remove(&entity).is_some() {
+            let forgotten = ticks.forgotten.remove(&entity);
+            if ticks.entities.remove(entity).is_some() || forgotten {
                 trace!("writing visibility lost for `{entity}` for client `{client}`");
                 let entity_range = serialized.write_entity(entity)?;
                 message.add_despawn(entity_range);
+                if let Some(history) = &mut history {
+                    history.insert(entity, **server_tick, time.elapsed());
+                    message.request_ack();
+                }
             }
             priority.remove(&entity);
         }
@@ -519,12 +832,15 @@ fn collect_despawns(
 fn collect_removals(
     archetypes: &Archetypes,
     entities: &Entities,
-    removal_buffer: Res<RemovalBuffer>,
+    mut removal_buffer: ResMut<RemovalBuffer>,
     rules: Res<ReplicationRules>,
     registry: Res<ReplicationRegistry>,
     filter_registry: Res<FilterRegistry>,
+    server_tick: Res<ServerTick>,
     mut replicated_archetypes: ResMut<ReplicatedArchetypes>,
     mut serialized: ResMut<SerializedData>,
+    mut changed: Option<ResMut<ChangedThisTick>>,
+    mut stats: Option<ResMut<RemovalStats>>,
     mut lost_buffer: Local<Vec<ComponentIndex>>,
     mut clients: Query<(
         Entity,
@@ -535,44 +851,113 @@ fn collect_removals(
 ) -> Result<()> {
     replicated_archetypes.update(archetypes, &rules);
 
+    if let Some(changed) = &mut changed {
+        changed.start(**server_tick);
+    }
+
     for (&entity, remove_ids) in removal_buffer.iter() {
+        if let Some(stats) = &mut stats {
+            stats.removals += remove_ids.len();
+        }
+
+        // Skip the entity without visiting clients if none of the removed components were ever sent.
+        if !remove_ids
+            .iter()
+            .any(|&removal_id| removal_buffer.any_sent(entity, removal_id, &registry))
+        {
+            trace!("skipping removals for `{entity}` with components that weren't sent");
+            if let Some(stats) = &mut stats {
+                stats.skipped += remove_ids.len();
+            }
+            continue;
+        }
+
         let mut entity_range = None;
         for (_, mut message, _, _) in &mut clients {
             message.start_entity_removals();
         }
 
-        for &(component_index, fns_id) in remove_ids {
-            let mut fns_id_range = None;
+        for &removal_id in remove_ids {
+            if !removal_buffer.any_sent(entity, removal_id, &registry) {
+                trace!("skipping `{removal_id:?}` removal for `{entity}` that wasn't sent");
+                if let Some(stats) = &mut stats {
+                    stats.skipped += 1;
+                }
+                continue;
+            }
+
+            let mut removal_range = None;
             for (client, mut message, mut ticks, _) in &mut clients {
+                if let Some(stats) = &mut stats {
+                    stats.client_checks += 1;
+                }
+
                 // Only send removals for components that were previously sent.
                 // If the entity was despawned or lost visibility, it was removed
                 // from ticks earlier during despawn collection.
-                let Some(entity_ticks) = ticks.entities.get_mut(&entity) else {
+                let Some(entity_ticks) = ticks.entities.get_mut(entity) else {
                     continue;
                 };
-                if !entity_ticks.components.contains(component_index) {
+
+                let received = removal_id
+                    .fns_ids()
+                    .filter(|&fns_id| {
+                        let (component_index, ..) = registry.get(fns_id);
+                        entity_ticks.components.contains(component_index)
+                    })
+                    .count();
+                if received == 0 {
                     continue;
                 }
 
-                trace!("writing `{fns_id:?}` removal for `{entity}` for client `{client}`");
                 if !message.removals_entity_added() {
                     let entity_range = serialized.write_cached_entity(&mut entity_range, entity)?;
                     message.add_removals_entity(entity_range);
                 }
-                let fns_id_range = serialized.write_cached_fns_id(&mut fns_id_range, fns_id)?;
-                message.add_removal(fns_id_range);
-                entity_ticks.remove_component(component_index);
+
+                if received == removal_id.fns_ids().count() {
+                    trace!("writing `{removal_id:?}` removal for `{entity}` for client `{client}`");
+                    let removal_range =
+                        serialized.write_cached_removal_id(&mut removal_range, removal_id)?;
+                    message.add_removal(removal_range);
+                    for fns_id in removal_id.fns_ids() {
+                        let (component_index, ..) = registry.get(fns_id);
+                        entity_ticks.remove_component(component_index);
+                    }
+                } else {
+                    // The client received only some components of the bundle,
+                    // for example, due to component visibility.
+                    for fns_id in removal_id.fns_ids() {
+                        let (component_index, ..) = registry.get(fns_id);
+                        if !entity_ticks.components.contains(component_index) {
+                            continue;
+                        }
+
+                        trace!("writing `{fns_id:?}` removal for `{entity}` for client `{client}`");
+                        let removal_range =
+                            serialized.write_removal_id(RemovalId::Component(fns_id))?;
+                        message.add_removal(removal_range);
+                        entity_ticks.remove_component(component_index);
+                    }
+                }
+
+                if let Some(changed) = &mut changed {
+                    changed.entry(entity).removed = true;
+                }
             }
         }
     }
 
+    // Components can be inserted again on this tick, so they need to be marked as sent again.
+    removal_buffer.unmark_removed(&registry);
+
     for (client, mut message, mut ticks, mut visibility) in &mut clients {
         for (entity, filter_mask) in visibility.drain_lost() {
             if filter_mask.is_hidden(&filter_registry) {
                 // Was processed earlier during collecting despawns.
                 continue;
             }
-            let Some(entity_ticks) = ticks.entities.get_mut(&entity) else {
+            let Some(entity_ticks) = ticks.entities.get_mut(entity) else {
                 // The client didn't see this entity.
                 continue;
             };
@@ -611,8 +996,9 @@ fn collect_removals(
                     let entity_range = serialized.write_cached_entity(&mut entity_range, entity)?;
                     message.add_removals_entity(entity_range);
                 }
-                let fns_id_range = serialized.write_fns_id(rule.fns_id)?;
-                message.add_removal(fns_id_range);
+                let removal_range =
+                    serialized.write_removal_id(RemovalId::Component(rule.fns_id))?;
+                message.add_removal(removal_range);
                 entity_ticks.remove_component(component_index);
 
                 Ok(())
@@ -650,9 +1036,109 @@ fn collect_removals(
     Ok(())
 }
 
+/// Evaluates priority functions from replication rules for all replicated entities.
+///
+/// See [`AppRuleExt::with_priority_fn`].
+fn evaluate_rule_priorities(world: &mut World) {
+    world.resource_scope(|world, mut priorities: Mut<RulePriorities>| {
+        priorities.clear();
+
+        let rules = world.resource::<ReplicationRules>();
+        if rules.iter().all(|rule| rule.priority_fn.is_none()) {
+            return;
+        }
+
+        let marker_id = world
+            .component_id::<Replicated>()
+            .expect("marker should be registered");
+        for archetype in world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(marker_id))
+        {
+            for rule in rules.iter().filter(|rule| rule.matches(archetype)) {
+                let Some(priority_fn) = rule.priority_fn else {
+                    continue;
+                };
+
+                for entity in archetype.entities() {
+                    let priority = (priority_fn)(world.entity(entity.id()));
+                    *priorities.entry(entity.id()).or_insert(1.0) *= priority;
+                }
+            }
+        }
+    });
+}
+
+fn evaluate_lod_bands(world: &mut World, clients: &mut QueryState<Entity, With<AuthorizedClient>>) {
+    let last_run = world.last_change_tick();
+    let this_run = world.read_change_tick();
+    world.resource_scope(|world, mut lod_bands: Mut<LodBands>| {
+        lod_bands.changed.clear();
+
+        let rules = world.resource::<ReplicationRules>();
+        if rules.iter().all(|rule| rule.lod.is_none()) {
+            return;
+        }
+
+        let registry = world.resource::<ReplicationRegistry>();
+        let marker_id = world
+            .component_id::<Replicated>()
+            .expect("marker should be registered");
+        let mut interested_clients = Vec::new();
+        let mut changed_clients = Vec::new();
+        for (rule_index, rule) in rules.iter().enumerate() {
+            let Some(lod) = &rule.lod else {
+                continue;
+            };
+
+            interested_clients.clear();
+            changed_clients.clear();
+            for client in clients.iter(world) {
+                let Some(ticks) = world.entity(client).get_change_ticks_by_id(lod.interest_id)
+                else {
+                    continue;
+                };
+                interested_clients.push(client);
+                if ticks.is_changed(last_run, this_run) {
+                    changed_clients.push(client);
+                }
+            }
+
+            let (_, component_id, _) = registry.get(lod.bands[0].fns_id);
+            for archetype in world
+                .archetypes()
+                .iter()
+                .filter(|archetype| archetype.contains(marker_id) && rule.matches(archetype))
+            {
+                for entity in archetype.entities() {
+                    let entity_ref = world.entity(entity.id());
+                    let entity_changed = [component_id, marker_id].into_iter().any(|id| {
+                        entity_ref
+                            .get_change_ticks_by_id(id)
+                            .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+                    });
+
+                    // Unchanged entities need to be re-evaluated only for clients with changed interest.
+                    let clients = if entity_changed {
+                        &interested_clients
+                    } else {
+                        &changed_clients
+                    };
+                    for &client in clients {
+                        let distance = (lod.distance_fn)(world.entity(client), entity_ref);
+                        lod_bands.update(client, entity.id(), rule_index, lod.band(distance));
+                    }
+                }
+            }
+        }
+    });
+}
+
 /// Collects component changes from this tick into update and mutate messages since the last entity tick.
 fn collect_changes(
     archetypes: &Archetypes,
+    entities: &Entities,
     query: ReplicationQuery,
     server_tick: Res<ServerTick>,
     change_tick: Res<ServerChangeTick>,
@@ -665,6 +1151,29 @@ fn collect_changes(
     mut replicated_archetypes: ResMut<ReplicatedArchetypes>,
     mut serialized: ResMut<SerializedData>,
     mut removal_buffer: ResMut<RemovalBuffer>,
+    (
+        mut census,
+        mut changed,
+        mut insertion_stats,
+        priority_scale,
+        rule_priorities,
+        lod_bands,
+        mut serialization_cache,
+        templates,
+        system_ticks,
+        mut send_stats,
+    ): (
+        Option<ResMut<ReplicationCensus>>,
+        Option<ResMut<ChangedThisTick>>,
+        Option<ResMut<InsertionStats>>,
+        Res<PriorityScale>,
+        Res<RulePriorities>,
+        Res<LodBands>,
+        ResMut<SerializationCache>,
+        Option<Res<EntityTemplates>>,
+        SystemChangeTick,
+        Option<ResMut<ReplicationSendStats>>,
+    ),
     mut clients: Query<(
         Entity,
         &mut Updates,
@@ -672,22 +1181,56 @@ fn collect_changes(
         &mut ClientTicks,
         &mut PriorityMap,
         &mut ClientVisibility,
+        Has<RuleStats>,
+        &ConnectedClient,
     )>,
 ) -> Result<()> {
+    let start = send_stats.is_some().then(Instant::now);
     replicated_archetypes.update(archetypes, &rules);
 
-    for replicated_archetype in replicated_archetypes.iter() {
+    if let Some(census) = &mut census {
+        census.start(**server_tick);
+    }
+
+    // Clients and components with insertion boost inserted for the current entity.
+    let mut boosted_insertions = Vec::new();
+    // Cached component and insertion ranges for each band of the current LOD component.
+    let mut band_ranges = Vec::new();
+    for (archetype_index, replicated_archetype) in replicated_archetypes.iter().enumerate() {
         // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
         let archetype = unsafe { archetypes.get(replicated_archetype.id).unwrap_unchecked() };
 
-        for entity in archetype.entities() {
+        let template_storage = templates.as_ref().and_then(|templates| {
+            archetype
+                .get_storage_type(templates.component_id())
+                .map(|storage| (&**templates, storage))
+        });
+
+        let archetype_start = serialized.len();
+        for (row, entity) in archetype.entities().iter().enumerate() {
             let mut entity_range = None;
             for (_, mut updates, mut mutations, ..) in &mut clients {
                 updates.start_entity_changes();
                 mutations.start_entity();
             }
+            boosted_insertions.clear();
 
-            for &(rule, storage) in &replicated_archetype.components {
+            let template = template_storage.map(|(templates, storage)| {
+                // SAFETY: `Template` is replicated and its storage was obtained from this archetype.
+                let (ptr, _) = unsafe {
+                    query.get_component_unchecked(
+                        entity,
+                        archetype.table_id(),
+                        storage,
+                        templates.component_id(),
+                    )
+                };
+                // SAFETY: the pointer was obtained for `Template`.
+                let template = unsafe { *ptr.deref::<Template>() };
+                (templates, template)
+            });
+
+            for &(rule, storage, rule_index) in &replicated_archetype.components {
                 let (component_index, component_id, fns) = registry.get(rule.fns_id);
 
                 // SAFETY: component and storage were obtained from this archetype.
@@ -701,7 +1244,8 @@ fn collect_changes(
                 };
 
                 // SAFETY: `fns` and `ptr` were created for the same component type.
-                let mut component = unsafe { ErasedComponent::new(fns, ptr, rule.fns_id) };
+                let mut component =
+                    unsafe { ErasedComponent::new(&registry, fns, ptr, rule.fns_id) };
 
                 let mut ctx = SerializeCtx {
                     entity: entity.id(),
@@ -709,95 +1253,285 @@ fn collect_changes(
                     last_changed: ticks.changed,
                     server_tick: **server_tick,
                     diff_cursor: None,
+                    keyframe_interval: rule.keyframe_interval,
+                    insertion: false,
                     type_registry: &type_registry,
                     storage: &mut replication_storage,
                 };
 
+                let client_filters = replicated_archetype.client_filters(component_id);
                 let mut component_range = None;
-                for (client, mut updates, mut mutations, client_ticks, priority, visibility) in
-                    &mut clients
+                let mut insertion_range = None;
+                let lod = rules[rule_index].lod.as_ref();
+                band_ranges.clear();
+                if let Some(lod) = lod {
+                    band_ranges.resize(lod.bands.len(), (None, None));
+                }
+                // Keyframed components are serialized differently each tick.
+                let cached = rule.cached && rule.keyframe_interval == 0;
+                for (
+                    client,
+                    mut updates,
+                    mut mutations,
+                    mut client_ticks,
+                    priority,
+                    visibility,
+                    has_stats,
+                    _,
+                ) in &mut clients
                 {
-                    if visibility
-                        .get(entity.id())
-                        .is_component_hidden(&filter_registry, component_index)
+                    if client_ticks.forgotten.contains(&entity.id())
+                        || visibility
+                            .get(entity.id())
+                            .is_component_hidden(&filter_registry, component_index)
                     {
                         continue;
                     }
 
-                    if let Some(entity_ticks) = client_ticks.entities.get(&entity.id())
+                    if let Some(filters) = client_filters {
+                        let location = entities
+                            .get_spawned(client)
+                            .expect("client entities should be spawned");
+                        let client_archetype = &archetypes[location.archetype_id];
+                        if !filters
+                            .iter()
+                            .all(|filter| filter.matches_client(archetype, client_archetype))
+                        {
+                            continue;
+                        }
+                    }
+
+                    let mut band_component;
+                    let (component, component_range, insertion_range) = match lod {
+                        Some(lod) => {
+                            let Some(band) = lod_bands.get(client, entity.id(), rule_index) else {
+                                trace!(
+                                    "skipping `{:?}` for `{}` beyond LOD bands for client `{client}`",
+                                    rule.fns_id,
+                                    entity.id(),
+                                );
+                                continue;
+                            };
+
+                            let fns_id = lod.bands[band].fns_id;
+                            let (_, _, fns) = registry.get(fns_id);
+                            // SAFETY: all bands were registered for the same component.
+                            band_component =
+                                unsafe { ErasedComponent::new(&registry, fns, ptr, fns_id) };
+                            let (component_range, insertion_range) = &mut band_ranges[band];
+                            (&mut band_component, component_range, insertion_range)
+                        }
+                        None => (&mut component, &mut component_range, &mut insertion_range),
+                    };
+
+                    // The functions of the new band may serialize the component differently.
+                    let band_changed = rule.mode != ReplicationMode::Once
+                        && lod.is_some()
+                        && lod_bands.is_changed(client, entity.id(), rule_index);
+                    let refresh = band_changed
+                        || client_ticks.full_refresh
+                            && rule.full_refresh
+                            && rule.mode == ReplicationMode::OnChange;
+                    let mut entity_ticks =
+                        client_ticks
+                            .entities
+                            .get_mut_at(replicated_archetype.id, row, entity.id());
+                    if refresh && let Some(entity_ticks) = &mut entity_ticks {
+                        // Reset the component state like on resend to send it as an insertion.
+                        entity_ticks.remove_component(component_index);
+                    }
+
+                    if let Some(entity_ticks) = entity_ticks
                         && entity_ticks.components.contains(component_index)
                     {
-                        let base_priority = priority.get(&entity.id()).copied().unwrap_or(1.0);
-
-                        let tick_diff = **server_tick - entity_ticks.server_tick;
+                        let base_priority = priority.get(&entity.id()).copied().unwrap_or(1.0)
+                            * rule_priorities.get(&entity.id()).copied().unwrap_or(1.0)
+                            * **priority_scale
+                            * rule.insertion_boost.map_or(1.0, |boost| {
+                                entity_ticks.insertion_boost(component_index, **server_tick, boost)
+                            });
+
+                        // Each channel accumulates priority until its own acknowledgment.
+                        let channel_ticks = *entity_ticks.channel(rule.channel);
+                        let tick_diff = **server_tick - channel_ticks.server_tick;
+                        let last_sent =
+                            entity_ticks
+                                .last_refresh(component_index)
+                                .unwrap_or_else(|| {
+                                    channel_ticks.server_tick
+                                        - rule.mode.refresh_offset(entity.id())
+                                });
                         if rule.mode != ReplicationMode::Once
                             && base_priority * tick_diff as f32 >= 1.0
-                            && ticks.is_changed(entity_ticks.system_tick, **change_tick)
+                            && (ticks.is_changed(channel_ticks.system_tick, **change_tick)
+                                || rule.mode.should_refresh(last_sent, **server_tick))
+                            && (rule.echo_suppression == 0
+                                || !entity_ticks.is_echo_suppressed(
+                                    component_index,
+                                    **server_tick,
+                                    rule.echo_suppression,
+                                ))
                         {
                             trace!(
-                                "writing `{:?}` mutation for `{}` for client `{client}`",
+                                "writing `{:?}` mutation for `{}` on `{:?}` for client `{client}`",
                                 rule.fns_id,
                                 entity.id(),
+                                rule.channel,
                             );
 
-                            if !mutations.entity_added() {
-                                let graph_index = related_entities.graph_index(entity.id());
-                                let entity_range = serialized
-                                    .write_cached_entity(&mut entity_range, entity.id())?;
-                                mutations.add_entity(entity.id(), graph_index, entity_range);
-                            }
-
                             let diff_cursor = entity_ticks.diff_cursor(component_index);
-                            let component_range = if diff_cursor.is_none() {
+                            ctx.insertion = false;
+                            let (component_range, diff_cursor) = if diff_cursor.is_none() {
                                 // Cache only full component snapshots.
-                                serialized.write_cached_component(
+                                let range = serialized.write_cached_component(
                                     &mut ctx,
-                                    &mut component_range,
-                                    &mut component,
-                                )?
+                                    component_range,
+                                    component,
+                                    cached.then_some(&mut *serialization_cache),
+                                )?;
+                                (range, None)
                             } else {
                                 ctx.diff_cursor = diff_cursor;
-                                let range = serialized.write_component(&mut ctx, &mut component)?;
-                                if let Some(cursor) = ctx.diff_cursor.take() {
-                                    mutations.add_diff_cursor(component_index, cursor);
-                                }
-                                range
+                                let range = serialized.write_component(&mut ctx, component)?;
+                                (range, ctx.diff_cursor.take())
                             };
+                            if component_range.is_empty() {
+                                trace!("skipping vetoed `{:?}` mutation", rule.fns_id);
+                                continue;
+                            }
+                            if matches!(rule.mode, ReplicationMode::Refresh { .. }) {
+                                entity_ticks.refresh_component(component_index, **server_tick);
+                            }
+
+                            let mutations = mutations.channel_mut(rule.channel);
+                            if !mutations.entity_added() {
+                                let graph_index = related_entities.graph_index(entity.id());
+                                let entity_range = serialized
+                                    .write_cached_entity(&mut entity_range, entity.id())?;
+                                mutations.add_entity(entity.id(), graph_index, entity_range);
+                            }
+                            if let Some(cursor) = diff_cursor {
+                                mutations.add_diff_cursor(component_index, cursor);
+                            }
+                            if has_stats {
+                                mutations.add_rule_bytes(rule_index, component_range.len());
+                            }
                             mutations.add_component(component_range);
+                            if let Some(changed) = &mut changed {
+                                changed.entry(entity.id()).mutated = true;
+                            }
                         }
+                    } else if !refresh
+                        && template.is_some_and(|(templates, template)| {
+                            // SAFETY: the pointer was obtained for the component with this ID.
+                            unsafe { templates.matches(template, component_id, ptr) }
+                        })
+                    {
+                        // Will be instantiated by the client from the template.
+                        trace!(
+                            "skipping `{:?}` insertion for `{}` for client `{client}` that matches its template",
+                            rule.fns_id,
+                            entity.id(),
+                        );
                     } else {
+                        // The client doesn't have the component, for example, after gaining visibility.
+                        // The insertion always contains the current value, so mutations from this tick
+                        // are folded into it and never written separately.
                         trace!(
                             "writing `{:?}` insertion for `{}` for client `{client}`",
                             rule.fns_id,
                             entity.id(),
                         );
 
+                        // Mutations of keyframed components may be serialized as deltas,
+                        // which can't be inserted, so they're cached separately.
+                        ctx.insertion = true;
+                        let cached_range = if rule.keyframe_interval == 0 {
+                            component_range
+                        } else {
+                            insertion_range
+                        };
+                        let component_range = serialized.write_cached_component(
+                            &mut ctx,
+                            cached_range,
+                            component,
+                            cached.then_some(&mut *serialization_cache),
+                        )?;
+                        if component_range.is_empty() {
+                            trace!("skipping vetoed `{:?}` insertion", rule.fns_id);
+                            continue;
+                        }
+
                         if !updates.changed_entity_added() {
                             let entity_range =
                                 serialized.write_cached_entity(&mut entity_range, entity.id())?;
                             updates.add_changed_entity(entity_range);
                         }
-                        let component_range = serialized.write_cached_component(
-                            &mut ctx,
-                            &mut component_range,
-                            &mut component,
-                        )?;
+                        if has_stats {
+                            updates.add_rule_bytes(rule_index, component_range.len());
+                        }
                         updates.add_inserted_component(component_range, component_index);
+                        removal_buffer.mark_sent(entity.id(), component_index);
+                        if let Some(stats) = &mut insertion_stats {
+                            stats.insertions += 1;
+                            let last_run = system_ticks.last_run();
+                            if ticks.is_changed(last_run, **change_tick)
+                                && !ticks.is_added(last_run, **change_tick)
+                            {
+                                stats.folded_mutations += 1;
+                            }
+                        }
+                        if rule.insertion_boost.is_some() {
+                            boosted_insertions.push((client, component_index));
+                        }
+                        if let Some(changed) = &mut changed {
+                            changed.entry(entity.id()).inserted = true;
+                        }
                     }
                 }
             }
 
-            for (client, mut updates, mut mutations, mut ticks, _, visibility) in &mut clients {
-                if visibility.get(entity.id()).is_hidden(&filter_registry) {
+            for (client, mut updates, mut mutations, mut ticks, _, visibility, _, connected) in
+                &mut clients
+            {
+                if ticks.forgotten.contains(&entity.id())
+                    || visibility.get(entity.id()).is_hidden(&filter_registry)
+                {
                     continue;
                 }
 
-                let entity_ticks = ticks.entities.entry(entity.id());
-                let new_for_client = matches!(entity_ticks, Entry::Vacant(_));
-                if new_for_client
+                let mut entity_ticks =
+                    ticks
+                        .entities
+                        .get_mut_at(replicated_archetype.id, row, entity.id());
+                let new_for_client = entity_ticks.is_none();
+                // Mutations of entities that will be forgotten are sent reliably,
+                // so acknowledgment of the update message covers all their changes.
+                let mut merge_mutations = new_for_client
+                    || (replicated_archetype.forget && mutations.entity_added())
                     || updates.changed_entity_added()
-                    || removal_buffer.contains_key(&entity.id())
-                {
+                    || removal_buffer.contains_key(&entity.id());
+                if !merge_mutations && mutations.entity_exceeds(connected.max_size)? {
+                    // Mutate messages are split only per entity, so the message would exceed the MTU
+                    // and be lost entirely if any of its packets is lost. Update messages are reliable.
+                    if entity_ticks
+                        .as_deref_mut()
+                        .is_some_and(EntityTicks::mark_oversized)
+                    {
+                        warn!(
+                            "mutations for `{}` exceed max size of {} bytes for client `{client}`, sending them as updates instead",
+                            entity.id(),
+                            connected.max_size,
+                        );
+                    } else {
+                        trace!(
+                            "sending oversized mutations for `{}` as an update for client `{client}`",
+                            entity.id()
+                        );
+                    }
+                    merge_mutations = true;
+                }
+                if merge_mutations {
                     // If there is any insertion, removal, or it's a new entity for a client, include all mutations
                     // into update message and bump the last acknowledged tick to keep entity updates atomic.
                     if mutations.entity_added() {
@@ -808,12 +1542,25 @@ fn collect_changes(
                         updates.take_added_entity(&mut mutations);
                     }
 
-                    update_ticks(
-                        entity_ticks,
-                        **change_tick,
-                        **server_tick,
-                        updates.take_changed_components(),
-                    );
+                    let components = updates.take_changed_components();
+                    let entity_ticks = match entity_ticks {
+                        Some(entity_ticks) => {
+                            entity_ticks.set_all(**server_tick, **change_tick);
+                            entity_ticks.components |= &components;
+                            entity_ticks
+                        }
+                        None => ticks.entities.insert(
+                            entity.id(),
+                            EntityTicks::new(**server_tick, **change_tick, components),
+                        ),
+                    };
+
+                    for &(_, component_index) in boosted_insertions
+                        .iter()
+                        .filter(|&&(boosted_client, _)| boosted_client == client)
+                    {
+                        entity_ticks.insert_component(component_index, **server_tick);
+                    }
                 }
 
                 if new_for_client && !updates.changed_entity_added() {
@@ -824,54 +1571,94 @@ fn collect_changes(
                         serialized.write_cached_entity(&mut entity_range, entity.id())?;
                     updates.add_changed_entity(entity_range);
                 }
+
+                if replicated_archetype.forget && updates.changed_entity_added() {
+                    trace!(
+                        "requesting ack to forget `{}` for client `{client}`",
+                        entity.id()
+                    );
+                    ticks.forget_after_ack(entity.id(), **server_tick);
+                    updates.request_ack();
+                }
             }
         }
+
+        if let Some(census) = &mut census {
+            census.record(
+                archetype_index,
+                replicated_archetype,
+                archetype.entities().len(),
+                serialized.len() - archetype_start,
+            );
+        }
     }
 
-    removal_buffer.clear();
+    for (client, _, _, mut ticks, _, _, _, _) in &mut clients {
+        if ticks.full_refresh {
+            trace!("finishing full refresh for client `{client}`");
+            ticks.full_refresh = false;
+        }
+    }
 
-    Ok(())
-}
+    removal_buffer.clear();
 
-fn update_ticks(
-    entity_ticks: Entry<Entity, EntityTicks, EntityHash>,
-    system_tick: Tick,
-    server_tick: RepliconTick,
-    components: ComponentMask,
-) {
-    match entity_ticks {
-        Entry::Occupied(entry) => {
-            let entity_ticks = entry.into_mut();
-            entity_ticks.system_tick = system_tick;
-            entity_ticks.server_tick = server_tick;
-            entity_ticks.components |= &components;
-        }
-        Entry::Vacant(entry) => {
-            entry.insert(EntityTicks::new(server_tick, system_tick, components));
-        }
+    if let (Some(stats), Some(start)) = (&mut send_stats, start) {
+        stats.collect_time = start.elapsed();
     }
+
+    Ok(())
 }
 
 /// Sends previously constructed [`Updates`] and [`Mutations`].
+///
+/// Clients are iterated in the order from [`ClientOrder`].
 fn send_messages(
     mut split_buffer: Local<Vec<MutationsSplit>>,
+    mut order_buffer: Local<Vec<(Entity, Duration)>>,
+    mut rotation: Local<usize>,
+    client_order: Res<ClientOrder>,
     time: Res<Time<Real>>,
     server_tick: Res<ServerTick>,
     change_tick: Res<ServerChangeTick>,
     track_mutate_messages: Res<TrackMutateMessages>,
     userdata: Res<ReplicationUserdata>,
+    channels: Res<RepliconChannels>,
     mut serialized: ResMut<SerializedData>,
     mut messages: ResMut<ServerMessages>,
+    mut statuses: MessageWriter<MutateMessageStatus>,
+    mut send_stats: Option<ResMut<ReplicationSendStats>>,
     mut clients: Query<(
         Entity,
         &mut Updates,
         &mut Mutations,
         &ConnectedClient,
         &mut ClientTicks,
+        Option<&mut RuleStats>,
+        Has<DryRunClient>,
     )>,
 ) -> Result<()> {
+    let start = send_stats.is_some().then(Instant::now);
+    let mutation_channels = MutationChannel::ALL.map(|channel| channels.mutation_channel(channel));
+    let reliable_mutations = mutation_channels.map(|id| {
+        id.and_then(|id| channels.server_channel(id))
+            .is_some_and(|info| info.kind != Channel::Unreliable)
+    });
+    order_buffer.extend(
+        clients
+            .iter()
+            .map(|(client, .., ticks, _, _)| (client, ticks.served_at)),
+    );
+    client_order.sort(&mut order_buffer, &mut rotation);
+
     let mut server_tick_range = None;
-    for (client, updates, mut mutations, connected, mut ticks) in &mut clients {
+    for &(client, _) in &*order_buffer {
+        let (_, updates, mut mutations, connected, mut ticks, mut stats, dry_run) = clients
+            .get_mut(client)
+            .expect("client should be collected from the same query");
+        if let Some(stats) = &mut stats {
+            stats.start(**server_tick);
+        }
+
         if !updates.is_empty() {
             ticks.update_tick = **server_tick;
             let server_tick_range =
@@ -883,6 +1670,7 @@ fn send_messages(
                 &serialized,
                 &userdata,
                 server_tick_range,
+                stats.as_deref_mut(),
             )?;
         }
 
@@ -890,25 +1678,60 @@ fn send_messages(
             let server_tick_range =
                 serialized.write_cached_tick(&mut server_tick_range, **server_tick)?;
 
-            mutations.send(
+            // Messages for dry-run clients are discarded, so there is nobody to acknowledge them.
+            let reliable_mutations = if dry_run {
+                [true; MutationChannel::COUNT]
+            } else {
+                reliable_mutations
+            };
+            let messages_count = mutations.send(
                 &mut messages,
                 client,
                 &mut ticks,
                 &mut split_buffer,
                 &serialized,
                 **track_mutate_messages,
+                mutation_channels,
+                reliable_mutations,
                 &userdata,
                 server_tick_range,
                 **server_tick,
                 **change_tick,
                 time.elapsed(),
                 connected.max_size,
+                stats.as_deref_mut(),
             )?;
+
+            if **track_mutate_messages {
+                for split in &*split_buffer {
+                    let index = split.mutate_index();
+                    statuses.write(MutateMessageStatus {
+                        client,
+                        index,
+                        server_tick: **server_tick,
+                        status: MutateStatus::Sent { messages_count },
+                    });
+                    if reliable_mutations[split.channel() as usize] {
+                        statuses.write(MutateMessageStatus {
+                            client,
+                            index,
+                            server_tick: **server_tick,
+                            status: MutateStatus::Acknowledged,
+                        });
+                    }
+                }
+            }
+            split_buffer.clear();
         }
     }
 
+    order_buffer.clear();
     serialized.clear();
 
+    if let (Some(stats), Some(start)) = (&mut send_stats, start) {
+        stats.send_time = start.elapsed();
+    }
+
     Ok(())
 }
 
@@ -919,18 +1742,49 @@ fn cleanup_storage(remove: On<Remove, Replicated>, mut storage: If<ResMut<Replic
     storage.entities.remove(&remove.entity);
 }
 
+fn cleanup_serialization_cache(
+    remove: On<Remove, Replicated>,
+    mut serialization_cache: ResMut<SerializationCache>,
+) {
+    serialization_cache.remove(&remove.entity);
+}
+
+fn cleanup_lod_entity(remove: On<Remove, Replicated>, mut lod_bands: ResMut<LodBands>) {
+    lod_bands.remove_entity(remove.entity);
+}
+
+fn cleanup_lod_client(remove: On<Remove, AuthorizedClient>, mut lod_bands: ResMut<LodBands>) {
+    lod_bands.remove_client(remove.entity);
+}
+
 fn reset(
     mut commands: Commands,
     mut messages: ResMut<ServerMessages>,
     mut server_tick: ResMut<ServerTick>,
+    mut timeline: ResMut<TickTimeline>,
     mut related_entities: ResMut<RelatedEntities>,
     clients: Query<Entity, With<ConnectedClient>>,
     mut message_buffer: ResMut<MessageBuffer>,
+    mut priority_scale: ResMut<PriorityScale>,
+    mut serialization_cache: ResMut<SerializationCache>,
+    mut pending_disconnects: ResMut<PendingDisconnects>,
+    mut removal_buffer: ResMut<RemovalBuffer>,
+    mut lod_bands: ResMut<LodBands>,
+    changed: Option<ResMut<ChangedThisTick>>,
 ) {
     messages.clear();
     *server_tick = Default::default();
+    timeline.clear();
     message_buffer.clear();
     related_entities.clear();
+    *priority_scale = Default::default();
+    serialization_cache.clear();
+    pending_disconnects.clear();
+    removal_buffer.reset();
+    lod_bands.clear();
+    if let Some(mut changed) = changed {
+        changed.clear();
+    }
     for client in &clients {
         commands.entity(client).despawn();
     }
@@ -1011,12 +1865,118 @@ pub struct AuthorizedClient;
 /// no more often than once every 2 ticks. With the default priority of 1.0,
 /// all unacknowledged mutations will be sent every tick.
 ///
+/// The priority is additionally multiplied by values from [`AppRuleExt::with_priority_fn`].
+/// Under pressure, all priorities are also scaled by
+/// [`ReplicationPressureSettings::degraded_priority`].
+///
 /// All of this only affects mutations. For any component insertion or removal, the changes
 /// will be sent using [`ServerChannel::Updates`](crate::shared::backend::channels::ServerChannel::Updates).
 /// See its documentation for more details.
 #[derive(Component, Reflect, Deref, DerefMut, Default, Debug, Clone)]
 pub struct PriorityMap(EntityHashMap<f32>);
 
+/// Bands from [`AppRuleExt::replicate_lod`] for each client.
+///
+/// Updated incrementally by re-evaluating only changed entities and clients.
+#[derive(Resource, Default)]
+struct LodBands {
+    /// Maps an entity to its bands for each client and the index of its LOD rule.
+    ///
+    /// Contains only pairs within any band.
+    bands: EntityHashMap<HashMap<(Entity, usize), usize>>,
+
+    /// Clients, entities and rule indices whose band changed on this tick.
+    changed: HashSet<(Entity, Entity, usize)>,
+}
+
+impl LodBands {
+    fn get(&self, client: Entity, entity: Entity, rule_index: usize) -> Option<usize> {
+        self.bands
+            .get(&entity)
+            .and_then(|bands| bands.get(&(client, rule_index)))
+            .copied()
+    }
+
+    fn is_changed(&self, client: Entity, entity: Entity, rule_index: usize) -> bool {
+        self.changed.contains(&(client, entity, rule_index))
+    }
+
+    fn update(&mut self, client: Entity, entity: Entity, rule_index: usize, band: Option<usize>) {
+        let bands = self.bands.entry(entity).or_default();
+        let old_band = match band {
+            Some(band) => bands.insert((client, rule_index), band),
+            None => bands.remove(&(client, rule_index)),
+        };
+        if band.is_some() && old_band != band {
+            trace!("changing LOD band for `{entity}` to {band:?} for client `{client}`");
+            self.changed.insert((client, entity, rule_index));
+        }
+    }
+
+    fn remove_entity(&mut self, entity: Entity) {
+        self.bands.remove(&entity);
+    }
+
+    fn remove_client(&mut self, client: Entity) {
+        for bands in self.bands.values_mut() {
+            bands.retain(|&(band_client, _), _| band_client != client);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.bands.clear();
+        self.changed.clear();
+    }
+}
+
+/// Priority multipliers from [`AppRuleExt::with_priority_fn`] evaluated for the current tick.
+///
+/// Contains only entities matched by rules with priority functions.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct RulePriorities(EntityHashMap<f32>);
+
+/// Map for per-entity buffers that are iterated while writing messages.
+///
+/// With the `deterministic` feature, it's ordered by entity to make the layout
+/// of messages independent of the hash map iteration order.
+#[cfg(not(feature = "deterministic"))]
+pub(crate) type WriteOrderMap<V> = EntityHashMap<V>;
+#[cfg(feature = "deterministic")]
+pub(crate) type WriteOrderMap<V> = alloc::collections::BTreeMap<Entity, V>;
+
+/// Marker for entities that stop being tracked for a client after it receives them.
+/// Once all replicated components of an entity with [`Replicated`] are acknowledged by a client,
+/// the server frees the per-client tracking data for it and skips the entity for this client
+/// during replication. The entity stays alive on the client.
+///
+/// Until then, mutations of the entity are sent reliably inside update messages, which
+/// request acknowledgment. Only the entity ID is kept afterward to replicate its despawn
+/// or visibility loss.
+///
+/// Useful for static content streamed to clients, such as decorations, that shouldn't
+/// occupy per-client tracking memory forever.
+///
+/// After the entity is forgotten for a client, component insertions, mutations and removals
+/// are no longer replicated to it. Despawns and visibility loss are still replicated
+/// as despawns, after which the entity will be sent as new if it becomes visible again.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// fn spawn_decorations(mut commands: Commands) {
+///     commands.spawn((Replicated, ReplicateOnceThenForget, Tree));
+/// }
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Tree;
+/// ```
+#[derive(Component, Reflect, Default, Debug, Clone, Copy)]
+pub struct ReplicateOnceThenForget;
+
 /// Marker for entities stored in [`ClientTicks`].
 ///
 /// Marked as required for [`Replicated`] by [`ServerPlugin`].
@@ -1029,10 +1989,6 @@ pub struct PriorityMap(EntityHashMap<f32>);
 #[derive(Component, Default)]
 struct TicksTracked;
 
-/// Value of the [`ServerPlugin::track_mutate_messages`].
-#[derive(Resource, Deref, Default, Debug, Clone, Copy)]
-struct TrackMutateMessages(bool);
-
 /// User-defined bytes appended to outgoing replication messages.
 ///
 /// When this resource is non-empty, its contents are sent with every replication
@@ -1045,3 +2001,55 @@ struct TrackMutateMessages(bool);
 /// The bytes are not cleared after being sent.
 #[derive(Resource, Deref, DerefMut, Default)]
 pub struct ReplicationUserdata(pub Vec<u8>);
+
+/// Component removals processed by the server.
+///
+/// Removals of components that were never sent to any client for their entity
+/// are skipped without visiting clients. For example, removals of short-lived
+/// projectile components that were inserted and removed before being replicated.
+///
+/// Accumulates values over the whole session.
+///
+/// Statistic will be collected only if the resource is present.
+/// The resource is not added by default.
+#[derive(Resource, Default, Reflect, Debug, Clone, Copy)]
+pub struct RemovalStats {
+    /// Buffered removals of replicated components.
+    pub removals: usize,
+    /// Removals skipped because their components were never sent to any client for the entity.
+    pub skipped: usize,
+    /// Number of times a removal was checked against a client.
+    pub client_checks: usize,
+}
+
+/// Time spent on replication phases on the server during the last tick.
+///
+/// Statistic will be collected only if the resource is present.
+/// The resource is not added by default.
+#[derive(Resource, Default, Reflect, Debug, Clone, Copy)]
+pub struct ReplicationSendStats {
+    /// Time spent collecting changes into messages for all clients.
+    pub collect_time: Duration,
+    /// Time spent splitting and writing messages to [`ServerMessages`].
+    pub send_time: Duration,
+}
+
+/// Component insertions sent by the server.
+///
+/// A component is sent as an insertion whenever the client doesn't have it yet, for example,
+/// for a newly connected client or after gaining visibility. If the component was also mutated
+/// since the last tick, the insertion contains the mutated value and no separate mutation
+/// is sent. Such insertions are counted in [`Self::folded_mutations`], which is useful for
+/// bandwidth accounting since they replace mutations that would otherwise be sent.
+///
+/// Accumulates values over the whole session.
+///
+/// Statistic will be collected only if the resource is present.
+/// The resource is not added by default.
+#[derive(Resource, Default, Reflect, Debug, Clone, Copy)]
+pub struct InsertionStats {
+    /// Component insertions written for clients.
+    pub insertions: usize,
+    /// Insertions of components that were also mutated since the last tick.
+    pub folded_mutations: usize,
+}
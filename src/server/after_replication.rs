@@ -0,0 +1,205 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::mem;
+
+use bevy::prelude::*;
+use log::{debug, trace};
+
+use super::{replication_messages::updates::Updates, server_tick::ServerTick};
+use crate::{prelude::*, shared::replication::client_ticks::ClientTicks};
+
+/// Extension trait to send server messages only after an entity is replicated.
+pub trait SendAfterReplicationExt {
+    /**
+    Like writing [`ToClients<M>`], but holds the message for each recipient until
+    the client acknowledges the update message in which `entity` was replicated to it.
+
+    Regular server messages are only guaranteed to be received after all updates
+    from the tick in which they were sent, which isn't enough if the entity becomes
+    visible to the client later, for example, due to visibility or priority. With this
+    method, the message is guaranteed to be received after the entity is spawned on the client.
+
+    The message is sent independently to each client once it acknowledges the entity, so
+    clients may receive it on different ticks. It costs at least one round trip even if the
+    entity was already replicated. Messages for clients that disconnect or for entities
+    that are despawned are discarded. If the entity never becomes visible to a client,
+    the message is held until one of these happens.
+
+    If [`ClientId::Server`] is a recipient, the message is written locally right away.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    fn spawn_boss(mut commands: Commands) {
+        let boss = commands.spawn((Replicated, Boss)).id();
+        commands.send_after_replication(
+            boss,
+            ToClients {
+                targets: SendTargets::All,
+                message: BossIntro { boss },
+            },
+        );
+    }
+
+    #[derive(Component, Serialize, Deserialize)]
+    struct Boss;
+
+    #[derive(Message, Serialize, Deserialize, Clone, Copy)]
+    struct BossIntro {
+        boss: Entity,
+    }
+    ```
+    */
+    fn send_after_replication<M: Message + Clone>(&mut self, entity: Entity, message: ToClients<M>);
+}
+
+impl SendAfterReplicationExt for World {
+    fn send_after_replication<M: Message + Clone>(
+        &mut self,
+        entity: Entity,
+        message: ToClients<M>,
+    ) {
+        let ToClients { targets, message } = message;
+        let local = match targets {
+            SendTargets::All => true,
+            SendTargets::AllExcept(client_id) => client_id != ClientId::Server,
+            SendTargets::Single(client_id) => client_id == ClientId::Server,
+        };
+        if local {
+            self.write_message(ToClients {
+                targets: SendTargets::SERVER_ONLY,
+                message: message.clone(),
+            });
+        }
+
+        let clients: Vec<_> = self
+            .query_filtered::<Entity, With<ClientTicks>>()
+            .iter(self)
+            .filter(|&client| match targets {
+                SendTargets::All => true,
+                SendTargets::AllExcept(client_id) => client_id != client.into(),
+                SendTargets::Single(client_id) => client_id == client.into(),
+            })
+            .collect();
+
+        debug!(
+            "holding message `{}` for {} clients until `{entity}` is replicated",
+            ShortName::of::<M>(),
+            clients.len()
+        );
+        let mut pending = self.resource_mut::<PendingMessages>();
+        for client in clients {
+            let message = message.clone();
+            pending.push(PendingMessage {
+                client,
+                entity,
+                ack_tick: None,
+                write: Box::new(move |world| {
+                    world.write_message(ToClients {
+                        targets: SendTargets::Single(client.into()),
+                        message,
+                    });
+                }),
+            });
+        }
+    }
+}
+
+impl SendAfterReplicationExt for Commands<'_, '_> {
+    fn send_after_replication<M: Message + Clone>(
+        &mut self,
+        entity: Entity,
+        message: ToClients<M>,
+    ) {
+        self.queue(move |world: &mut World| world.send_after_replication(entity, message));
+    }
+}
+
+/// Messages held by [`SendAfterReplicationExt::send_after_replication`].
+#[derive(Resource, Default, Deref, DerefMut)]
+pub(super) struct PendingMessages(Vec<PendingMessage>);
+
+struct PendingMessage {
+    client: Entity,
+    entity: Entity,
+
+    /// Tick of the update message whose acknowledgment is awaited.
+    ///
+    /// Assigned once the entity is replicated to the client.
+    ack_tick: Option<RepliconTick>,
+
+    write: Box<dyn FnOnce(&mut World) + Send + Sync>,
+}
+
+/// Requests acknowledgments from clients to which entities of pending messages were replicated.
+///
+/// Should run after changes are collected and before messages are sent.
+pub(super) fn request_acks(
+    server_tick: Res<ServerTick>,
+    mut pending: ResMut<PendingMessages>,
+    mut clients: Query<(&mut Updates, &ClientTicks)>,
+) {
+    for message in pending
+        .iter_mut()
+        .filter(|message| message.ack_tick.is_none())
+    {
+        let Ok((mut updates, ticks)) = clients.get_mut(message.client) else {
+            continue;
+        };
+
+        if ticks.entities.contains_key(message.entity) || ticks.forgotten.contains(&message.entity)
+        {
+            trace!(
+                "requesting ack of `{}` from client `{}`",
+                message.entity, message.client
+            );
+            message.ack_tick = Some(**server_tick);
+            updates.request_ack();
+        }
+    }
+}
+
+/// Writes pending messages whose entities were acknowledged by their clients.
+pub(super) fn write_acked(world: &mut World) {
+    let pending = mem::take(&mut **world.resource_mut::<PendingMessages>());
+    let mut waiting = Vec::with_capacity(pending.len());
+    for message in pending {
+        if world.get_entity(message.entity).is_err() {
+            debug!(
+                "discarding message for client `{}` because `{}` was despawned",
+                message.client, message.entity
+            );
+            continue;
+        }
+
+        let Some(ticks) = world.get::<ClientTicks>(message.client) else {
+            debug!(
+                "discarding message for `{}` because client `{}` disconnected",
+                message.entity, message.client
+            );
+            continue;
+        };
+
+        let acked = message.ack_tick.is_some_and(|tick| {
+            ticks
+                .acked_update_tick
+                .is_some_and(|acked_tick| !acked_tick.is_older(tick))
+        });
+        if acked {
+            trace!(
+                "writing message for client `{}` after `{}` was acknowledged",
+                message.client, message.entity
+            );
+            (message.write)(world);
+        } else {
+            waiting.push(message);
+        }
+    }
+
+    let mut pending = world.resource_mut::<PendingMessages>();
+    waiting.append(&mut pending);
+    **pending = waiting;
+}
@@ -0,0 +1,314 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use bevy::{platform::collections::HashSet, prelude::*};
+use log::debug;
+
+use crate::{
+    prelude::*,
+    shared::{
+        authorization::{AuthDenial, AuthRequest, AuthorizationFailed},
+        backend::connected_client::NetworkId,
+    },
+};
+
+/**
+A single check that a client passes before it receives [`AuthorizedClient`].
+
+Implemented for closures with the same signature as [`Self::authorize`],
+so simple checks can be added without defining a type.
+
+See also [`Authorizers`].
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::authorization::{Authorizers, BanCheck},
+    shared::authorization::{AuthDenial, AuthRequest},
+};
+
+# let mut app = App::new();
+app.insert_resource(
+    Authorizers::default()
+        .with(BanCheck)
+        .with(|world: &World, _client: Entity, _request: &AuthRequest| {
+            let lobby = world.resource::<Lobby>();
+            if lobby.players >= lobby.capacity {
+                return Err(AuthDenial::Custom("lobby is full".into()));
+            }
+            Ok(())
+        }),
+);
+
+#[derive(Resource)]
+struct Lobby {
+    players: usize,
+    capacity: usize,
+}
+```
+*/
+pub trait Authorizer: Send + Sync + 'static {
+    /// Checks the request from the client.
+    ///
+    /// Returns the reason if the client should be denied.
+    fn authorize(
+        &mut self,
+        world: &World,
+        client: Entity,
+        request: &AuthRequest,
+    ) -> Result<(), AuthDenial>;
+}
+
+impl<F> Authorizer for F
+where
+    F: FnMut(&World, Entity, &AuthRequest) -> Result<(), AuthDenial> + Send + Sync + 'static,
+{
+    fn authorize(
+        &mut self,
+        world: &World,
+        client: Entity,
+        request: &AuthRequest,
+    ) -> Result<(), AuthDenial> {
+        (self)(world, client, request)
+    }
+}
+
+/// Checks that decide whether a client receives [`AuthorizedClient`].
+///
+/// Used when [`RepliconSharedPlugin::auth_method`] is set to [`AuthMethod::Authorizers`].
+/// On [`AuthRequest`] from a client, authorizers are evaluated in order. The first one
+/// that returns an error vetoes the authorization: the client receives [`AuthorizationFailed`]
+/// with the reason and gets disconnected. Authorizers after it aren't evaluated. If all
+/// authorizers pass, [`AuthorizedClient`] is inserted.
+///
+/// Contains only [`ProtocolCheck`] by default. Use [`Self::empty`] to start from an empty stack.
+///
+/// Inserted as resource by [`ServerPlugin`] and can be replaced or modified at runtime.
+///
+/// See also [`Authorizer`].
+#[derive(Resource)]
+pub struct Authorizers(Vec<Box<dyn Authorizer>>);
+
+impl Authorizers {
+    /// Creates an empty stack that authorizes all clients.
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Adds an authorizer to the end and returns itself.
+    #[must_use]
+    pub fn with(mut self, authorizer: impl Authorizer) -> Self {
+        self.push(authorizer);
+        self
+    }
+
+    /// Adds an authorizer to the end.
+    pub fn push(&mut self, authorizer: impl Authorizer) {
+        self.0.push(Box::new(authorizer));
+    }
+
+    /// Inserts an authorizer at `index`, shifting all authorizers after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than [`Self::len`].
+    pub fn insert(&mut self, index: usize, authorizer: impl Authorizer) {
+        self.0.insert(index, Box::new(authorizer));
+    }
+
+    /// Removes all authorizers.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns the number of authorizers.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no authorizers.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Evaluates authorizers in order until the first denial.
+    fn authorize(
+        &mut self,
+        world: &World,
+        client: Entity,
+        request: &AuthRequest,
+    ) -> Result<(), AuthDenial> {
+        for authorizer in &mut self.0 {
+            authorizer.authorize(world, client, request)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Authorizers {
+    fn default() -> Self {
+        Self(vec![Box::new(ProtocolCheck)])
+    }
+}
+
+/// Denies clients whose [`ProtocolHash`] differs from the server's.
+///
+/// Also denies clients whose optional replication rules aren't a prefix of the server's.
+/// See [`ProtocolBreakdown::optional`].
+///
+/// Included in [`Authorizers`] by default.
+pub struct ProtocolCheck;
+
+impl Authorizer for ProtocolCheck {
+    fn authorize(
+        &mut self,
+        world: &World,
+        client: Entity,
+        request: &AuthRequest,
+    ) -> Result<(), AuthDenial> {
+        let protocol = *world.resource::<ProtocolHash>();
+        if request.protocol != protocol {
+            debug!(
+                "protocol mismatch for client `{client}` (client: `{:?}`, server: `{protocol:?}`)",
+                request.protocol
+            );
+            let breakdown = world.resource::<ProtocolBreakdown>().clone();
+            return Err(AuthDenial::ProtocolMismatch(breakdown));
+        }
+
+        let breakdown = world.resource::<ProtocolBreakdown>();
+        if !breakdown.optional.starts_with(&request.optional) {
+            debug!(
+                "optional rules mismatch for client `{client}` (client: `{:?}`, server: `{:?}`)",
+                request.optional, breakdown.optional
+            );
+            return Err(AuthDenial::ProtocolMismatch(breakdown.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Denies clients whose [`AuthToken`](crate::shared::authorization::AuthToken)
+/// is missing or doesn't pass validation.
+pub struct TokenCheck(fn(&[u8]) -> bool);
+
+impl TokenCheck {
+    /// Creates a new instance that validates tokens with `validate`.
+    pub fn new(validate: fn(&[u8]) -> bool) -> Self {
+        Self(validate)
+    }
+}
+
+impl Authorizer for TokenCheck {
+    fn authorize(
+        &mut self,
+        _world: &World,
+        client: Entity,
+        request: &AuthRequest,
+    ) -> Result<(), AuthDenial> {
+        match &request.token {
+            Some(token) if (self.0)(token) => Ok(()),
+            _ => {
+                debug!("invalid token from client `{client}`");
+                Err(AuthDenial::InvalidToken)
+            }
+        }
+    }
+}
+
+/// Denies clients whose [`NetworkId`] is in [`BannedClients`].
+///
+/// Clients without [`NetworkId`] are not affected.
+pub struct BanCheck;
+
+impl Authorizer for BanCheck {
+    fn authorize(
+        &mut self,
+        world: &World,
+        client: Entity,
+        _request: &AuthRequest,
+    ) -> Result<(), AuthDenial> {
+        let Some(&network_id) = world.get::<NetworkId>(client) else {
+            return Ok(());
+        };
+
+        if world.resource::<BannedClients>().contains(&network_id) {
+            debug!("client `{client}` with `{network_id:?}` is banned");
+            return Err(AuthDenial::Banned);
+        }
+
+        Ok(())
+    }
+}
+
+/// Protocol that the client sent in [`AuthRequest`].
+///
+/// Inserted on the client entity before [`Authorizers`] are evaluated,
+/// so it's available even for clients that end up denied.
+#[derive(Component, Deref, Debug, Clone, Copy)]
+pub struct ClientProtocol(pub ProtocolHash);
+
+/// Clients denied by [`BanCheck`].
+///
+/// Inserted as resource by [`ServerPlugin`]. Banning doesn't affect
+/// already authorized clients, disconnect them with [`DisconnectRequest`].
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct BannedClients(HashSet<NetworkId>);
+
+/// Extension trait to deny authorization with a reason.
+pub trait DenyAuthorizationExt {
+    /// Sends [`AuthorizationFailed`] with `reason` to the client and disconnects it.
+    ///
+    /// Useful with [`AuthMethod::Custom`] to use the same failure messaging as [`Authorizers`].
+    fn deny_authorization(&mut self, client: Entity, reason: AuthDenial);
+}
+
+impl DenyAuthorizationExt for World {
+    fn deny_authorization(&mut self, client: Entity, reason: AuthDenial) {
+        debug!("denying authorization for client `{client}` due to `{reason:?}`");
+        self.server_trigger(ToClients {
+            targets: SendTargets::Single(client.into()),
+            message: AuthorizationFailed { reason },
+        });
+        self.write_message(DisconnectRequest { client });
+    }
+}
+
+impl DenyAuthorizationExt for Commands<'_, '_> {
+    fn deny_authorization(&mut self, client: Entity, reason: AuthDenial) {
+        self.queue(move |world: &mut World| world.deny_authorization(client, reason));
+    }
+}
+
+pub(super) fn authorize(request: On<FromClient<AuthRequest>>, mut commands: Commands) {
+    let Some(client) = request.client_id.entity() else {
+        debug!("ignoring authorization request sent from the server itself");
+        return;
+    };
+
+    let request = request.message.clone();
+    commands.queue(move |world: &mut World| {
+        if world.get_entity(client).is_err() {
+            debug!("ignoring authorization request from disconnected client `{client}`");
+            return;
+        }
+
+        world
+            .entity_mut(client)
+            .insert(ClientProtocol(request.protocol));
+        let result = world.resource_scope(|world, mut authorizers: Mut<Authorizers>| {
+            authorizers.authorize(world, client, &request)
+        });
+        match result {
+            Ok(()) => {
+                debug!("marking client `{client}` as authorized");
+                world.entity_mut(client).insert(AuthorizedClient);
+            }
+            Err(reason) => world.deny_authorization(client, reason),
+        }
+    });
+}
@@ -0,0 +1,210 @@
+//! Statistics about replicated archetypes.
+//!
+//! Useful for capacity planning and to identify which content dominates bandwidth at scale.
+//! See [`CensusPlugin`] for details.
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use bevy::{ecs::archetype::ArchetypeId, prelude::*, time::common_conditions::on_timer};
+use log::info;
+
+use super::{ServerSystems, replicated_archetypes::ReplicatedArchetype};
+use crate::prelude::*;
+
+/// Collects [`ReplicationCensus`] on each server tick.
+///
+/// Optionally logs the census every [`Self::log_interval`].
+///
+/// # Examples
+///
+/// Log the census every 10 seconds.
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use bevy::{prelude::*, state::app::StatesPlugin};
+/// use bevy_replicon::{prelude::*, server::census::CensusPlugin};
+///
+/// # let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     StatesPlugin,
+///     RepliconPlugins,
+///     CensusPlugin::default().with_log_interval(Duration::from_secs(10)),
+/// ));
+/// ```
+#[derive(Default)]
+pub struct CensusPlugin {
+    /// How often to log the census.
+    ///
+    /// Logging is disabled if set to [`None`].
+    pub log_interval: Option<Duration>,
+}
+
+impl CensusPlugin {
+    /// Sets [`Self::log_interval`].
+    #[must_use]
+    pub fn with_log_interval(mut self, interval: Duration) -> Self {
+        self.log_interval = Some(interval);
+        self
+    }
+}
+
+impl Plugin for CensusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplicationCensus>()
+            .add_systems(OnExit(ServerState::Running), reset);
+
+        if let Some(interval) = self.log_interval {
+            app.add_systems(
+                PostUpdate,
+                log_census
+                    .after(ServerSystems::Send)
+                    .run_if(on_timer(interval))
+                    .run_if(in_state(ServerState::Running)),
+            );
+        }
+    }
+}
+
+fn log_census(census: Res<ReplicationCensus>) {
+    info!(
+        "replicating {} entities in {} archetypes with {} bytes at `{:?}`",
+        census.entities(),
+        census.archetypes.len(),
+        census.bytes(),
+        census.tick,
+    );
+    for archetype in census.iter().filter(|archetype| archetype.entities > 0) {
+        info!(
+            "`{:?}`: {} entities, {} components, {} bytes, rules {:?}",
+            archetype.id,
+            archetype.entities,
+            archetype.components,
+            archetype.bytes,
+            archetype.rules,
+        );
+    }
+}
+
+fn reset(mut census: ResMut<ReplicationCensus>) {
+    census.tick = Default::default();
+    census.archetypes.clear();
+}
+
+/// Statistics about replicated archetypes for the last server tick.
+///
+/// Updated in [`ServerSystems::Send`] only if the resource is present,
+/// which is done by [`CensusPlugin`]. Cleared when the server stops.
+///
+/// Entities are counted regardless of their visibility, while bytes include only
+/// data serialized for connected clients. Cached data shared between clients is counted once.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct ReplicationCensus {
+    tick: RepliconTick,
+    archetypes: Vec<ArchetypeCensus>,
+}
+
+impl ReplicationCensus {
+    /// Returns the server tick at which the census was collected.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns statistics for the given archetype.
+    ///
+    /// Performs a linear search.
+    pub fn get(&self, id: ArchetypeId) -> Option<&ArchetypeCensus> {
+        self.archetypes.iter().find(|archetype| archetype.id == id)
+    }
+
+    /// Returns an iterator over statistics for all replicated archetypes.
+    pub fn iter(&self) -> impl Iterator<Item = &ArchetypeCensus> {
+        self.archetypes.iter()
+    }
+
+    /// Returns the total number of replicated entities.
+    pub fn entities(&self) -> usize {
+        self.archetypes
+            .iter()
+            .map(|archetype| archetype.entities)
+            .sum()
+    }
+
+    /// Returns the total number of bytes serialized for entity changes.
+    pub fn bytes(&self) -> usize {
+        self.archetypes
+            .iter()
+            .map(|archetype| archetype.bytes)
+            .sum()
+    }
+
+    /// Returns the average number of replicated components per entity.
+    pub fn average_components(&self) -> f32 {
+        let entities = self.entities();
+        if entities == 0 {
+            return 0.0;
+        }
+
+        let components: usize = self
+            .archetypes
+            .iter()
+            .map(|archetype| archetype.components * archetype.entities)
+            .sum();
+
+        components as f32 / entities as f32
+    }
+
+    pub(super) fn start(&mut self, tick: RepliconTick) {
+        self.tick = tick;
+    }
+
+    /// Updates statistics for the archetype at `index` in the replicated archetypes list.
+    ///
+    /// Entries are kept between ticks since the list only grows,
+    /// so matched rules are copied into the existing allocation.
+    pub(super) fn record(
+        &mut self,
+        index: usize,
+        replicated_archetype: &ReplicatedArchetype,
+        entities: usize,
+        bytes: usize,
+    ) {
+        let Some(census) = self.archetypes.get_mut(index) else {
+            self.archetypes.push(ArchetypeCensus {
+                id: replicated_archetype.id,
+                entities,
+                components: replicated_archetype.components.len(),
+                bytes,
+                rules: replicated_archetype.rules.clone(),
+            });
+            return;
+        };
+
+        census.id = replicated_archetype.id;
+        census.entities = entities;
+        census.components = replicated_archetype.components.len();
+        census.bytes = bytes;
+        census.rules.clone_from(&replicated_archetype.rules);
+    }
+}
+
+/// Statistics for a single replicated archetype inside [`ReplicationCensus`].
+#[derive(Debug, Clone)]
+pub struct ArchetypeCensus {
+    /// Associated archetype ID.
+    pub id: ArchetypeId,
+
+    /// Number of entities in the archetype.
+    pub entities: usize,
+
+    /// Number of replicated components for each entity.
+    pub components: usize,
+
+    /// Number of bytes serialized for entities of the archetype in this tick.
+    pub bytes: usize,
+
+    /// Indices of matched rules in [`ReplicationRules`](crate::shared::replication::rules::ReplicationRules).
+    pub rules: Vec<usize>,
+}
@@ -0,0 +1,106 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+use crate::prelude::*;
+
+/**
+Entities with changes that were written for at least one client during the last server tick.
+
+Updated in [`ServerSystems::Send`] only if the resource is present.
+The resource is not added by default. Cleared when the server stops.
+
+Allows other server systems to reuse the change set that replication already computed
+instead of running their own change detection. Changes are reported from the perspective
+of clients, so an entity that becomes visible to a client will have all its components
+reported as inserted, and changes hidden from all clients won't be reported.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::{changed_this_tick::ChangedThisTick, server_tick::ServerTick},
+};
+
+# let mut app = App::new();
+app.init_resource::<ChangedThisTick>().add_systems(
+    PostUpdate,
+    log_changes
+        .after(ServerSystems::Send)
+        .run_if(resource_changed::<ServerTick>),
+);
+
+fn log_changes(changed: Res<ChangedThisTick>) {
+    for (entity, changes) in changed.iter() {
+        if changes.removed {
+            info!("`{entity}` lost some components at `{:?}`", changed.tick());
+        }
+    }
+}
+```
+*/
+#[derive(Resource, Default, Debug, Clone)]
+pub struct ChangedThisTick {
+    tick: RepliconTick,
+    entities: EntityHashMap<EntityChanges>,
+}
+
+impl ChangedThisTick {
+    /// Returns the server tick at which the changes were collected.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns changes for the given entity.
+    pub fn get(&self, entity: Entity) -> Option<EntityChanges> {
+        self.entities.get(&entity).copied()
+    }
+
+    /// Returns `true` if the entity has any changes.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains_key(&entity)
+    }
+
+    /// Returns an iterator over changed entities.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, EntityChanges)> {
+        self.entities
+            .iter()
+            .map(|(&entity, &changes)| (entity, changes))
+    }
+
+    /// Returns the number of changed entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub(super) fn start(&mut self, tick: RepliconTick) {
+        self.tick = tick;
+        self.entities.clear();
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.start(Default::default());
+    }
+
+    pub(super) fn entry(&mut self, entity: Entity) -> &mut EntityChanges {
+        self.entities.entry(entity).or_default()
+    }
+}
+
+/// Kinds of changes for an entity inside [`ChangedThisTick`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityChanges {
+    /// At least one component was inserted.
+    pub inserted: bool,
+
+    /// At least one component was mutated.
+    pub mutated: bool,
+
+    /// At least one component was removed.
+    pub removed: bool,
+}
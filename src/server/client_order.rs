@@ -0,0 +1,130 @@
+use core::time::Duration;
+
+use bevy::{ecs::entity::hash_set::EntityHashSet, prelude::*};
+
+use crate::{prelude::*, shared::replication::client_ticks::ClientTicks};
+
+/**
+Order in which clients receive replication messages each tick.
+
+Messages are written to [`ServerMessages`] client by client. Backends usually send all
+of them at once, so the order doesn't matter. But if the backend has a send budget and
+takes only a part of the messages each tick (for example, with [`ServerMessages::retain_sent`]),
+clients that always come last may starve. Other policies distribute the budget between clients.
+
+The value of [`ServerPlugin::client_order`]. Inserted as a resource and can be changed at runtime.
+
+# Examples
+
+```
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    StatesPlugin,
+    RepliconPlugins.set(ServerPlugin {
+        client_order: ClientOrder::LongestWaiting,
+        ..Default::default()
+    }),
+));
+```
+*/
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientOrder {
+    /// Iterate clients in query order.
+    ///
+    /// The order is stable between ticks, so the same clients always come first.
+    #[default]
+    Fixed,
+
+    /// Start from the next client each tick.
+    ///
+    /// Each client periodically comes first regardless of how much the backend sends.
+    RoundRobin,
+
+    /// Clients that waited longest since the backend took all their messages come first.
+    ///
+    /// A client counts as fully served when none of its messages are left in [`ServerMessages`]
+    /// at the start of the frame. Clients that were served at the same time keep query order.
+    LongestWaiting,
+}
+
+impl ClientOrder {
+    /// Reorders clients with the time they were last fully served according to the policy.
+    ///
+    /// `rotation` is advanced for [`Self::RoundRobin`].
+    pub(super) fn sort(self, clients: &mut [(Entity, Duration)], rotation: &mut usize) {
+        match self {
+            ClientOrder::Fixed => (),
+            ClientOrder::RoundRobin => {
+                if !clients.is_empty() {
+                    clients.rotate_left(*rotation % clients.len());
+                    *rotation = rotation.wrapping_add(1);
+                }
+            }
+            ClientOrder::LongestWaiting => {
+                // Stable sort to keep query order for clients served at the same time.
+                clients.sort_by_key(|&(_, served_at)| served_at);
+            }
+        }
+    }
+}
+
+/// Updates the time when the backend took all messages for each client.
+///
+/// Runs before new messages are written, so only messages left from the previous frame are considered.
+pub(super) fn record_served(
+    mut pending: Local<EntityHashSet>,
+    time: Res<Time<Real>>,
+    messages: Res<ServerMessages>,
+    mut clients: Query<(Entity, &mut ClientTicks)>,
+) {
+    pending.extend(messages.iter_sent().map(|(client, ..)| client));
+    for (client, mut ticks) in &mut clients {
+        if !pending.contains(&client) {
+            ticks.served_at = time.elapsed();
+        }
+    }
+    pending.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_robin() {
+        let mut rotation = 0;
+        let mut clients = CLIENTS;
+        ClientOrder::RoundRobin.sort(&mut clients, &mut rotation);
+        assert_eq!(clients, CLIENTS);
+
+        let mut clients = CLIENTS;
+        ClientOrder::RoundRobin.sort(&mut clients, &mut rotation);
+        assert_eq!(clients, [CLIENTS[1], CLIENTS[2], CLIENTS[0]]);
+
+        let mut clients = CLIENTS;
+        ClientOrder::RoundRobin.sort(&mut clients, &mut rotation);
+        assert_eq!(clients, [CLIENTS[2], CLIENTS[0], CLIENTS[1]]);
+
+        ClientOrder::RoundRobin.sort(&mut [], &mut rotation);
+        assert_eq!(rotation, 3, "shouldn't advance without clients");
+    }
+
+    #[test]
+    fn longest_waiting() {
+        let mut rotation = 0;
+        let mut clients = CLIENTS;
+        ClientOrder::LongestWaiting.sort(&mut clients, &mut rotation);
+        assert_eq!(clients, [CLIENTS[1], CLIENTS[0], CLIENTS[2]]);
+        assert_eq!(rotation, 0);
+    }
+
+    const CLIENTS: [(Entity, Duration); 3] = [
+        (Entity::from_raw_u32(1).unwrap(), Duration::from_secs(2)),
+        (Entity::from_raw_u32(2).unwrap(), Duration::from_secs(1)),
+        (Entity::from_raw_u32(3).unwrap(), Duration::from_secs(2)),
+    ];
+}
@@ -0,0 +1,97 @@
+use alloc::vec::Vec;
+
+use bevy::prelude::*;
+
+use super::{PriorityMap, visibility::client_visibility::ClientVisibility};
+use crate::shared::replication::client_ticks::ClientTicks;
+
+/**
+Makes an entity an additional view of an authorized client.
+
+Each view has its own [`ClientVisibility`] and [`PriorityMap`], so it can have different
+interest rules, for example, the main camera view and a map view with a coarser level of detail.
+Visibility filters are evaluated for view entities the same way as for clients, so the
+[`VisibilityFilter::ClientComponent`](crate::prelude::VisibilityFilter::ClientComponent)
+needs to be inserted on the view. [`LevelTag`](super::visibility::level::LevelTag)
+on a view assigns the view to a level. Note that the view entity is passed as the client to
+[`VisibilityFilter::is_visible`](crate::prelude::VisibilityFilter::is_visible).
+
+All views of a client are merged into a single message stream right before replication
+in [`ServerSystems::Send`](super::ServerSystems::Send):
+- Data is visible to the client if it's visible in any of its views.
+- Each entity uses the highest priority from all views.
+
+Entities visible in multiple views are sent only once since acknowledgment ticks are
+tracked per connection.
+
+While a client has views, its own [`ClientVisibility`] and [`PriorityMap`] are overwritten by
+the merged values, so filter components and priorities should be set on views instead.
+After the last view is removed, the client keeps the last merged values.
+
+Views are despawned together with their client.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_observer(spawn_views);
+
+fn spawn_views(add: On<Add, AuthorizedClient>, mut commands: Commands) {
+    // Entities from the current level.
+    commands.spawn((ViewOf(add.entity), LevelTag(0)));
+    // Markers from all levels for the map.
+    commands.spawn((ViewOf(add.entity), MapMarker));
+}
+
+#[derive(Component)]
+struct MapMarker;
+```
+*/
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Deref)]
+#[relationship(relationship_target = ClientViews)]
+#[require(ClientVisibility, PriorityMap)]
+pub struct ViewOf(pub Entity);
+
+/// All views of a client.
+///
+/// See [`ViewOf`] for details.
+#[derive(Component, Debug, Deref)]
+#[relationship_target(relationship = ViewOf, linked_spawn)]
+pub struct ClientViews(Vec<Entity>);
+
+/// Merges visibility and priorities of all views into their clients.
+pub(super) fn merge_views(
+    mut buffer: Local<Vec<Entity>>,
+    mut clients: Query<(&ClientViews, &mut ClientVisibility, &mut PriorityMap), With<ClientTicks>>,
+    mut views: Query<(&mut ClientVisibility, &PriorityMap), (With<ViewOf>, Without<ClientTicks>)>,
+) {
+    for (client_views, mut visibility, mut priority) in &mut clients {
+        let view_visibilities: Vec<_> = views
+            .iter_many(client_views.iter())
+            .map(|(visibility, _)| visibility)
+            .collect();
+        visibility.merge(&view_visibilities);
+
+        buffer.extend(
+            views
+                .iter_many(client_views.iter())
+                .flat_map(|(_, priority)| priority.keys()),
+        );
+        priority.clear();
+        for entity in buffer.drain(..) {
+            let max = views
+                .iter_many(client_views.iter())
+                .map(|(_, priority)| priority.get(&entity).copied().unwrap_or(1.0))
+                .fold(f32::MIN, f32::max);
+            priority.insert(entity, max);
+        }
+    }
+
+    // Views aren't processed during replication.
+    for (mut visibility, _) in &mut views {
+        visibility.clear_lost();
+    }
+}
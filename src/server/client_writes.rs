@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use log::{trace, warn};
+
+use super::server_tick::ServerTick;
+use crate::shared::replication::{client_ticks::ClientTicks, registry::ReplicationRegistry};
+
+/// Extension trait for [`EntityWorldMut`] to mark accepted client writes.
+///
+/// See also [`EntityCommandsEchoExt`].
+pub trait EntityEchoExt {
+    /**
+    Marks that the server accepted a write of `C` on this entity from a client.
+
+    Mutations of `C` won't be sent back to this client for
+    [`ComponentRule::echo_suppression`](crate::shared::replication::rules::component::ComponentRule::echo_suppression)
+    ticks. Should be called right after applying the client's value.
+
+    See [`AppRuleExt::with_echo_suppression`](crate::prelude::AppRuleExt::with_echo_suppression)
+    for details.
+
+    Ignored if the entity wasn't replicated to the client yet.
+    */
+    fn accept_client_write<C: Component>(&mut self, client: Entity) -> &mut Self;
+}
+
+impl EntityEchoExt for EntityWorldMut<'_> {
+    fn accept_client_write<C: Component>(&mut self, client: Entity) -> &mut Self {
+        let entity = self.id();
+        self.world_scope(|world| {
+            let Some(component_index) = world.component_id::<C>().and_then(|component_id| {
+                world
+                    .resource::<ReplicationRegistry>()
+                    .component_index(component_id)
+            }) else {
+                warn!(
+                    "ignoring accepted write of non-replicated `{}` on `{entity}`",
+                    ShortName::of::<C>()
+                );
+                return;
+            };
+
+            let server_tick = **world.resource::<ServerTick>();
+            if let Some(mut ticks) = world.get_mut::<ClientTicks>(client) {
+                trace!(
+                    "accepting write of `{}` on `{entity}` from client `{client}`",
+                    ShortName::of::<C>()
+                );
+                ticks.accept_write(entity, component_index, server_tick);
+            }
+        });
+
+        self
+    }
+}
+
+/// Extension trait for [`EntityCommands`] to mark accepted client writes.
+///
+/// See also [`EntityEchoExt`].
+pub trait EntityCommandsEchoExt {
+    /// Queues marking of an accepted write of `C` on this entity from a client.
+    ///
+    /// See [`EntityEchoExt::accept_client_write`] for details.
+    fn accept_client_write<C: Component>(&mut self, client: Entity) -> &mut Self;
+}
+
+impl EntityCommandsEchoExt for EntityCommands<'_> {
+    fn accept_client_write<C: Component>(&mut self, client: Entity) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.accept_client_write::<C>(client);
+        })
+    }
+}
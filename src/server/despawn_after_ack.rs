@@ -0,0 +1,171 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use bevy::prelude::*;
+use log::{debug, trace};
+
+use super::{DespawnBuffer, replication_messages::updates::Updates, server_tick::ServerTick};
+use crate::{prelude::*, shared::replication::client_ticks::ClientTicks};
+
+/**
+Hides an entity from clients immediately, but despawns it on the server only after
+the despawn is acknowledged.
+
+On the next server tick after insertion, the despawn is sent to clients and [`Replicated`]
+is removed from the entity. The entity itself stays on the server until all clients
+that had it acknowledge the update message with the despawn or [`Self::timeout`] passes.
+Acknowledgments are requested only from clients that had the entity, so this has no
+overhead for other update messages.
+
+Useful when the entity is still referenced by in-flight messages, or to guarantee
+that clients have seen the despawn before the server runs its own teardown logic,
+for example, in a [`Despawn`] observer.
+
+Has no effect on entities without [`Replicated`].
+
+# Examples
+
+```
+use core::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+fn destroy_projectiles(mut commands: Commands, projectiles: Query<(Entity, &Projectile)>) {
+    for (entity, projectile) in &projectiles {
+        if projectile.hit {
+            commands
+                .entity(entity)
+                .insert(DespawnAfterAck::new(Duration::from_secs(5)));
+        }
+    }
+}
+
+#[derive(Component)]
+struct Projectile {
+    hit: bool,
+}
+```
+*/
+#[derive(Component, Debug, Clone)]
+pub struct DespawnAfterAck {
+    /// Clients whose acknowledgment is required.
+    ///
+    /// If empty, acknowledgment from all clients is required.
+    pub clients: Vec<Entity>,
+
+    /// Maximum time to wait for acknowledgments after hiding the entity.
+    ///
+    /// Needed because a client may disconnect or never respond.
+    pub timeout: Duration,
+
+    /// Clients that haven't acknowledged the despawn yet with the tick of the update message.
+    pending: Vec<(Entity, RepliconTick)>,
+
+    /// Time after which the entity will be despawned regardless of acknowledgments.
+    ///
+    /// Set when the entity is hidden.
+    deadline: Option<Duration>,
+}
+
+impl DespawnAfterAck {
+    /// Creates a new instance that waits for all clients with the given timeout.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            clients: Default::default(),
+            timeout,
+            pending: Default::default(),
+            deadline: None,
+        }
+    }
+
+    /// Sets [`Self::clients`].
+    #[must_use]
+    pub fn with_clients(mut self, clients: impl IntoIterator<Item = Entity>) -> Self {
+        self.clients = clients.into_iter().collect();
+        self
+    }
+
+    /// Returns `true` if the entity was hidden from clients and waits for acknowledgments.
+    pub fn is_hidden(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    /// Returns an iterator over clients that haven't acknowledged the despawn yet.
+    pub fn pending(&self) -> impl Iterator<Item = Entity> {
+        self.pending.iter().map(|&(client, _)| client)
+    }
+}
+
+impl Default for DespawnAfterAck {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+/// Sends despawns for newly marked entities and requests acknowledgments.
+///
+/// Should run after [`Updates`] are cleared and before despawns are collected.
+pub(super) fn hide(
+    mut commands: Commands,
+    time: Res<Time>,
+    server_tick: Res<ServerTick>,
+    mut despawn_buffer: ResMut<DespawnBuffer>,
+    entities: Query<(Entity, &mut DespawnAfterAck), (Added<DespawnAfterAck>, With<Replicated>)>,
+    mut clients: Query<(Entity, &mut Updates, &ClientTicks)>,
+) {
+    for (entity, mut despawn) in entities {
+        for (client, mut updates, ticks) in &mut clients {
+            if !despawn.clients.is_empty() && !despawn.clients.contains(&client) {
+                continue;
+            }
+
+            if ticks.entities.contains_key(entity) || ticks.forgotten.contains(&entity) {
+                trace!("requesting despawn ack of `{entity}` from client `{client}`");
+                despawn.pending.push((client, **server_tick));
+                updates.request_ack();
+            }
+        }
+
+        debug!(
+            "hiding `{entity}` until acknowledged by {} clients",
+            despawn.pending.len()
+        );
+        despawn.deadline = Some(time.elapsed() + despawn.timeout);
+        despawn_buffer.push(entity);
+        commands.entity(entity).remove::<Replicated>();
+    }
+}
+
+/// Despawns hidden entities whose despawn was acknowledged or timed out.
+pub(super) fn despawn_acked(
+    mut commands: Commands,
+    time: Res<Time>,
+    entities: Query<(Entity, &mut DespawnAfterAck)>,
+    clients: Query<&ClientTicks>,
+) {
+    for (entity, mut despawn) in entities {
+        let Some(deadline) = despawn.deadline else {
+            continue;
+        };
+
+        despawn.pending.retain(|&(client, tick)| {
+            clients.get(client).is_ok_and(|ticks| {
+                ticks
+                    .acked_update_tick
+                    .is_none_or(|acked_tick| acked_tick.is_older(tick))
+            })
+        });
+
+        if despawn.pending.is_empty() {
+            debug!("despawning acknowledged `{entity}`");
+            commands.entity(entity).despawn();
+        } else if time.elapsed() >= deadline {
+            debug!(
+                "despawning `{entity}` after timeout with {} pending clients",
+                despawn.pending.len()
+            );
+            commands.entity(entity).despawn();
+        }
+    }
+}
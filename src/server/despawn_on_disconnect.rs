@@ -0,0 +1,174 @@
+use core::time::Duration;
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use log::debug;
+
+use crate::{
+    prelude::*,
+    shared::backend::connected_client::{NetworkId, NetworkIdMap},
+};
+
+/**
+Despawns or neutralizes an entity after its owner disconnects.
+
+The owner is referenced by [`ClientRef`], so it requires a messaging backend that provides
+[`NetworkId`]. This allows the owner to reconnect within [`Self::with_grace_period`]
+to keep the entity, for example, to let a player take control of their character again.
+
+# Examples
+
+```
+use core::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, shared::backend::connected_client::NetworkId};
+
+fn spawn_ship(add: On<Add, AuthorizedClient>, mut commands: Commands, clients: Query<&NetworkId>) {
+    if let Ok(&network_id) = clients.get(add.entity) {
+        commands.spawn((
+            Replicated,
+            Ship,
+            Controlled,
+            DespawnOnDisconnect::new(network_id)
+                .with_grace_period(Duration::from_secs(30))
+                .with_action(DisconnectAction::Neutralize(|entity| {
+                    // Leave the ship drifting instead of despawning.
+                    entity.remove::<Controlled>();
+                })),
+        ));
+    }
+}
+
+#[derive(Component)]
+struct Ship;
+
+#[derive(Component)]
+struct Controlled;
+```
+*/
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DespawnOnDisconnect {
+    owner: ClientRef,
+    grace_period: Duration,
+    action: DisconnectAction,
+}
+
+impl DespawnOnDisconnect {
+    /// Creates a new instance that despawns the entity immediately after the owner disconnects.
+    pub fn new(owner: impl Into<ClientRef>) -> Self {
+        Self {
+            owner: owner.into(),
+            grace_period: Duration::ZERO,
+            action: DisconnectAction::Despawn,
+        }
+    }
+
+    /// Sets how long to wait for the owner to reconnect before applying the action.
+    #[must_use]
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Sets what to do with the entity after the grace period.
+    #[must_use]
+    pub fn with_action(mut self, action: DisconnectAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Returns the owner of the entity.
+    pub fn owner(&self) -> ClientRef {
+        self.owner
+    }
+
+    /// Returns the configured grace period.
+    pub fn grace_period(&self) -> Duration {
+        self.grace_period
+    }
+
+    /// Returns the configured action.
+    pub fn action(&self) -> DisconnectAction {
+        self.action
+    }
+}
+
+/// What to do with an entity with [`DespawnOnDisconnect`] after its owner disconnects.
+#[derive(Debug, Clone, Copy)]
+pub enum DisconnectAction {
+    /// Despawn the entity.
+    Despawn,
+    /// Call the function to remove control components and keep the entity.
+    ///
+    /// [`DespawnOnDisconnect`] is also removed from the entity.
+    Neutralize(fn(&mut EntityCommands)),
+}
+
+/// Disconnect times of clients that own entities with [`DespawnOnDisconnect`].
+#[derive(Resource, Default, Deref, DerefMut)]
+pub(super) struct PendingDisconnects(HashMap<NetworkId, Duration>);
+
+pub(super) fn mark_pending(
+    remove: On<Remove, ConnectedClient>,
+    time: Res<Time>,
+    mut pending: ResMut<PendingDisconnects>,
+    clients: Query<&NetworkId>,
+) {
+    if let Ok(&network_id) = clients.get(remove.entity) {
+        pending.insert(network_id, time.elapsed());
+    }
+}
+
+pub(super) fn apply_pending(
+    mut commands: Commands,
+    mut waiting: Local<HashSet<NetworkId>>,
+    time: Res<Time>,
+    network_map: Res<NetworkIdMap>,
+    mut pending: ResMut<PendingDisconnects>,
+    entities: Query<(Entity, &DespawnOnDisconnect)>,
+) {
+    if pending.is_empty() {
+        return;
+    }
+
+    pending.retain(|network_id, _| {
+        let reconnected = network_map.contains_key(network_id);
+        if reconnected {
+            debug!("`{network_id:?}` reconnected, keeping its entities");
+        }
+        !reconnected
+    });
+
+    for (entity, despawn) in &entities {
+        let Some(network_id) = despawn.owner.network_id() else {
+            continue;
+        };
+        let Some(&disconnected_at) = pending.get(&network_id) else {
+            continue;
+        };
+
+        if time.elapsed().saturating_sub(disconnected_at) < despawn.grace_period {
+            waiting.insert(network_id);
+            continue;
+        }
+
+        let mut entity = commands.entity(entity);
+        match despawn.action {
+            DisconnectAction::Despawn => {
+                debug!("despawning `{}` owned by `{network_id:?}`", entity.id());
+                entity.despawn();
+            }
+            DisconnectAction::Neutralize(neutralize) => {
+                debug!("neutralizing `{}` owned by `{network_id:?}`", entity.id());
+                entity.remove::<DespawnOnDisconnect>();
+                (neutralize)(&mut entity);
+            }
+        }
+    }
+
+    pending.retain(|network_id, _| waiting.contains(network_id));
+    waiting.clear();
+}
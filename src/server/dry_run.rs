@@ -0,0 +1,89 @@
+//! Measuring replication cost without real clients.
+//!
+//! See [`DryRunClient`] for details.
+
+use bevy::prelude::*;
+use log::trace;
+
+use crate::prelude::*;
+
+/**
+Marker for a synthetic client whose messages are discarded instead of being sent.
+
+The server runs the entire collection pipeline for such clients, but all messages
+for them are dropped in [`ServerSystems::Send`] before they reach the messaging backend.
+Sent bytes and messages are recorded in [`DryRunStats`]. Mutations are considered
+acknowledged as soon as they are sent, as if they were sent over a reliable channel.
+
+Useful in CI or load tests to measure the replication cost of content changes without
+running real clients. The client entity needs to be spawned manually with [`ConnectedClient`].
+Because no backend is involved, the server also needs to be started manually by setting
+[`ServerState::Running`]. Visibility can be configured as for regular clients, for example,
+via [`ClientVisibility`](super::visibility::client_visibility::ClientVisibility).
+For per-rule stats, insert [`RuleStats`](super::rule_stats::RuleStats).
+
+# Examples
+
+```
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    server::dry_run::{DryRunClient, DryRunStats},
+};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+    .add_systems(Startup, spawn_clients)
+    .add_systems(Last, report_cost);
+
+fn spawn_clients(mut commands: Commands, mut state: ResMut<NextState<ServerState>>) {
+    state.set(ServerState::Running);
+    for _ in 0..10 {
+        commands.spawn((ConnectedClient { max_size: 1200 }, DryRunClient));
+    }
+}
+
+fn report_cost(clients: Query<&DryRunStats>) {
+    let bytes: usize = clients.iter().map(|stats| stats.bytes).sum();
+    info!("replication would send {bytes} bytes in total");
+}
+```
+*/
+#[derive(Component, Default, Debug, Clone, Copy)]
+#[require(AuthorizedClient, DryRunStats)]
+pub struct DryRunClient;
+
+/// Accumulated totals of discarded messages for a [`DryRunClient`].
+///
+/// Includes both replication and server messages.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct DryRunStats {
+    /// Number of bytes that would have been sent.
+    pub bytes: usize,
+
+    /// Number of messages that would have been sent.
+    pub messages: usize,
+}
+
+pub(super) fn discard_messages(
+    mut messages: ResMut<ServerMessages>,
+    mut clients: Query<&mut DryRunStats, With<DryRunClient>>,
+) {
+    if clients.is_empty() {
+        return;
+    }
+
+    messages.retain_sent(|(client, channel_id, message)| {
+        let Ok(mut stats) = clients.get_mut(*client) else {
+            return true;
+        };
+
+        trace!(
+            "discarding {} bytes over channel {channel_id} for dry-run client `{client}`",
+            message.len()
+        );
+        stats.bytes += message.len();
+        stats.messages += 1;
+        false
+    });
+}
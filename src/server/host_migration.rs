@@ -0,0 +1,363 @@
+//! Scaffolding for transferring server authority to another instance.
+//!
+//! The general flow looks like this:
+//!
+//! 1. The current host captures its state using [`HostSnapshot::capture`] and sends it
+//!    to a designated client using any transport (for example, a server event).
+//! 2. The designated client starts a server and calls [`HostSnapshot::restore`]. It spawns
+//!    all entities from the snapshot (or reuses the ones it already has), writes their components
+//!    using the registered replication functions and resumes [`ServerTick`] from the snapshot.
+//! 3. The new host distributes the returned entity mapping to other clients. They reconnect
+//!    and, while in [`ClientState::Connecting`], insert the mapping into [`ServerEntityMap`]
+//!    using [`ServerEntityMap::insert`] to keep their existing entities instead of receiving
+//!    duplicates.
+//!
+//! This is a best-effort framework. Entities that aren't replicated, resources and
+//! game-specific state need to be transferred manually.
+
+use alloc::vec::Vec;
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use bytes::Bytes;
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+
+use super::{replicated_archetypes::ReplicatedArchetypes, server_tick::ServerTick};
+use crate::{
+    prelude::*,
+    shared::{
+        backend::connected_client::NetworkId,
+        replication::{
+            client_ticks::ClientTicks,
+            deferred_entity::{DeferredEntity, EntityScratch},
+            receive_markers::{EntityMarkers, ReceiveMarkers},
+            registry::{
+                FnsId, ReplicationRegistry,
+                ctx::{BufferedSpawner, EntityBuffer, SerializeCtx, WriteCtx},
+            },
+            rules::ReplicationRules,
+            storage::ReplicationStorage,
+        },
+        server_entity_map::ServerEntityMap,
+    },
+};
+
+/// Serialized authoritative server state.
+///
+/// Components are serialized using the registered replication functions,
+/// so the receiving instance must have the same replication rules registered.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct HostSnapshot {
+    /// Server tick at the moment of capture.
+    pub server_tick: RepliconTick,
+
+    /// All entities with [`Replicated`] and their components.
+    pub entities: Vec<SnapshotEntity>,
+
+    /// Connected clients with assigned [`NetworkId`].
+    pub clients: Vec<SnapshotClient>,
+}
+
+impl HostSnapshot {
+    /// Captures all replicated entities, the current [`ServerTick`] and connected clients.
+    ///
+    /// Should be called on the current host. Components are serialized without diffs,
+    /// like for a newly connected client.
+    pub fn capture(world: &mut World) -> Result<Self> {
+        world.resource_scope(
+            |world, mut replicated_archetypes: Mut<ReplicatedArchetypes>| {
+                world.resource_scope(|world, mut storage: Mut<ReplicationStorage>| {
+                    Self::capture_with(world, &mut replicated_archetypes, &mut storage)
+                })
+            },
+        )
+    }
+
+    fn capture_with(
+        world: &World,
+        replicated_archetypes: &mut ReplicatedArchetypes,
+        storage: &mut ReplicationStorage,
+    ) -> Result<Self> {
+        let registry = world.resource::<ReplicationRegistry>();
+        let rules = world.resource::<ReplicationRules>();
+        let type_registry = world.resource::<AppTypeRegistry>();
+        let server_tick = **world.resource::<ServerTick>();
+
+        replicated_archetypes.update(world.archetypes(), rules);
+
+        let mut entities = Vec::new();
+        for replicated_archetype in replicated_archetypes.iter() {
+            // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
+            let archetype = unsafe {
+                world
+                    .archetypes()
+                    .get(replicated_archetype.id)
+                    .unwrap_unchecked()
+            };
+
+            for entity in archetype.entities() {
+                let entity = world.entity(entity.id());
+                let mut components = Vec::with_capacity(replicated_archetype.components.len());
+                for &(rule, ..) in &replicated_archetype.components {
+                    let (_, component_id, fns) = registry.get(rule.fns_id);
+                    let ptr = entity
+                        .get_by_id(component_id)
+                        .expect("archetype should match the rule");
+                    let ticks = entity
+                        .get_change_ticks_by_id(component_id)
+                        .expect("archetype should match the rule");
+
+                    let mut ctx = SerializeCtx {
+                        entity: entity.id(),
+                        component_id,
+                        last_changed: ticks.changed,
+                        server_tick,
+                        diff_cursor: None,
+                        keyframe_interval: 0,
+                        insertion: true,
+                        storage,
+                        type_registry,
+                    };
+
+                    let mut data = Vec::new();
+                    // SAFETY: `ptr` obtained for the component ID associated with `fns`.
+                    if !unsafe { fns.serialize(&mut ctx, ptr, &mut data)? } {
+                        trace!("skipping vetoed `{:?}` for `{}`", rule.fns_id, entity.id());
+                        continue;
+                    }
+                    trace!("capturing `{:?}` for `{}`", rule.fns_id, entity.id());
+                    components.push((rule.fns_id, data));
+                }
+
+                entities.push(SnapshotEntity {
+                    entity: entity.id(),
+                    components,
+                });
+            }
+        }
+
+        let mut clients = Vec::new();
+        let mut query = world.try_query::<(&NetworkId, &ClientTicks)>();
+        if let Some(query) = &mut query {
+            for (&network_id, ticks) in query.iter(world) {
+                clients.push(SnapshotClient {
+                    network_id,
+                    entities: ticks
+                        .entities
+                        .keys()
+                        .chain(&ticks.forgotten)
+                        .copied()
+                        .collect(),
+                });
+            }
+        }
+
+        debug!(
+            "captured {} entities and {} clients at `{server_tick:?}`",
+            entities.len(),
+            clients.len()
+        );
+
+        Ok(Self {
+            server_tick,
+            entities,
+            clients,
+        })
+    }
+
+    /// Restores the snapshot on a new host.
+    ///
+    /// `existing` maps snapshot entities to entities that should be reused instead of spawning
+    /// new ones. For example, the designated client can pass a copy of [`ServerEntityMap::to_client`]
+    /// taken before disconnecting from the previous host. Reused entities will have [`Remote`]
+    /// removed.
+    ///
+    /// Inserts [`Replicated`] on all restored entities and sets [`ServerTick`] to the captured value.
+    /// Entities referenced inside components are mapped.
+    ///
+    /// Returns the mapping from snapshot entities to entities on this instance.
+    /// It should be sent to other clients to resume their sessions.
+    pub fn restore(
+        &self,
+        world: &mut World,
+        existing: &EntityHashMap<Entity>,
+    ) -> Result<EntityHashMap<Entity>> {
+        // Check ahead of time to avoid leaving the world half-restored.
+        ensure_resource::<ReplicationStorage>(world)?;
+        ensure_resource::<ReceiveMarkers>(world)?;
+        ensure_resource::<ReplicationRegistry>(world)?;
+        ensure_resource::<AppTypeRegistry>(world)?;
+        ensure_resource::<ServerTick>(world)?;
+
+        let mut entity_map = ServerEntityMap::default();
+        for snapshot_entity in &self.entities {
+            let entity = existing
+                .get(&snapshot_entity.entity)
+                .copied()
+                .filter(|&entity| world.get_entity(entity).is_ok())
+                .unwrap_or_else(|| world.spawn_empty().id());
+            entity_map.insert(snapshot_entity.entity, entity);
+        }
+
+        let mut storage = take_resource::<ReplicationStorage>(world)?;
+        let receive_markers = take_resource::<ReceiveMarkers>(world)?;
+        let registry = take_resource::<ReplicationRegistry>(world)?;
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+
+        let mut scratch = EntityScratch::default();
+        let mut entity_markers = EntityMarkers::from_world(world);
+        let mut entity_buffer = EntityBuffer::default();
+        let mut result = Ok(());
+        for snapshot_entity in &self.entities {
+            let entity = *entity_map
+                .to_client()
+                .get(&snapshot_entity.entity)
+                .expect("all entities should be mapped ahead of time");
+
+            result = restore_entity(
+                world,
+                entity,
+                snapshot_entity,
+                self.server_tick,
+                &mut RestoreParams {
+                    scratch: &mut scratch,
+                    entity_markers: &mut entity_markers,
+                    entity_buffer: &mut entity_buffer,
+                    entity_map: &mut entity_map,
+                    storage: &mut storage,
+                    receive_markers: &receive_markers,
+                    registry: &registry,
+                    type_registry: &type_registry,
+                },
+            );
+
+            if result.is_err() {
+                // SAFETY: components in the scratch were pushed using this world.
+                unsafe { scratch.manual_drop(world.components()) };
+                entity_buffer.free(world);
+                break;
+            }
+        }
+
+        world.insert_resource(storage);
+        world.insert_resource(receive_markers);
+        world.insert_resource(registry);
+        result?;
+
+        let mut server_tick = world.resource_mut::<ServerTick>();
+        let diff = self.server_tick - **server_tick;
+        server_tick.increment_by(diff);
+
+        debug!(
+            "restored {} entities at `{:?}`",
+            self.entities.len(),
+            self.server_tick
+        );
+
+        Ok(entity_map.to_client().clone())
+    }
+}
+
+/// Returns an error if `R` is missing.
+fn ensure_resource<R: Resource>(world: &World) -> Result<()> {
+    if !world.contains_resource::<R>() {
+        return Err(missing_resource::<R>());
+    }
+
+    Ok(())
+}
+
+fn take_resource<R: Resource>(world: &mut World) -> Result<R> {
+    world
+        .remove_resource::<R>()
+        .ok_or_else(missing_resource::<R>)
+}
+
+fn missing_resource<R: Resource>() -> BevyError {
+    format!(
+        "missing resource `{}`, make sure that replication plugins are added",
+        ShortName::of::<R>()
+    )
+    .into()
+}
+
+/// Writes all components from the snapshot into the entity.
+fn restore_entity(
+    world: &mut World,
+    entity: Entity,
+    snapshot_entity: &SnapshotEntity,
+    server_tick: RepliconTick,
+    params: &mut RestoreParams,
+) -> Result<()> {
+    let world_cell = world.as_unsafe_world_cell();
+    let entity_allocator = world_cell.entity_allocator();
+    // SAFETY: used only to create `DeferredEntity`, which won't let mutably alias `EntityAllocator`.
+    let world = unsafe { world_cell.world_mut() };
+
+    let mut entity = DeferredEntity::new(world.entity_mut(entity), params.scratch);
+    entity.insert(Replicated);
+    #[cfg(feature = "client")]
+    entity.remove::<Remote>();
+
+    params.entity_markers.read(params.receive_markers, &*entity);
+
+    for (fns_id, data) in &snapshot_entity.components {
+        let spawner = BufferedSpawner::new(entity_allocator, params.entity_buffer);
+        let (_, component_id, fns) = params
+            .registry
+            .try_get(*fns_id)
+            .ok_or_else(|| format!("snapshot contains unknown `{fns_id:?}`"))?;
+        let mut ctx = WriteCtx {
+            entity: entity.id(),
+            component_id,
+            message_tick: server_tick,
+            entity_map: params.entity_map,
+            storage: params.storage,
+            type_registry: params.type_registry,
+            spawner,
+            ignore_mapping: false,
+        };
+        trace!("restoring `{fns_id:?}` for `{}`", entity.id());
+
+        let mut data = Bytes::copy_from_slice(data);
+        fns.write(&mut ctx, params.entity_markers, &mut entity, &mut data)?;
+    }
+
+    // SAFETY: only used to spawn entities.
+    params.entity_buffer.spawn(unsafe { entity.world_mut() });
+    entity.flush();
+
+    Ok(())
+}
+
+/// Borrowed resources needed for [`restore_entity`].
+struct RestoreParams<'a> {
+    scratch: &'a mut EntityScratch,
+    entity_markers: &'a mut EntityMarkers,
+    entity_buffer: &'a mut EntityBuffer,
+    entity_map: &'a mut ServerEntityMap,
+    storage: &'a mut ReplicationStorage,
+    receive_markers: &'a ReceiveMarkers,
+    registry: &'a ReplicationRegistry,
+    type_registry: &'a AppTypeRegistry,
+}
+
+/// A replicated entity inside [`HostSnapshot`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnapshotEntity {
+    /// Entity on the host that captured the snapshot.
+    pub entity: Entity,
+
+    /// Serialized components with the IDs of functions that were used for serialization.
+    pub components: Vec<(FnsId, Vec<u8>)>,
+}
+
+/// A connected client inside [`HostSnapshot`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnapshotClient {
+    /// Persistent ID of the client that can be used to recognize it after reconnection.
+    pub network_id: NetworkId,
+
+    /// Entities that were visible to the client and acknowledged at least once.
+    pub entities: Vec<Entity>,
+}
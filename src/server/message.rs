@@ -3,7 +3,11 @@ use bevy::{
     prelude::*,
 };
 
-use super::server_tick::ServerTick;
+use super::{
+    after_replication::{self, PendingMessages},
+    message_sender::{self, ServerMessageSender},
+    server_tick::ServerTick,
+};
 use crate::{
     prelude::*,
     shared::{
@@ -13,6 +17,7 @@ use crate::{
             server_message::message_buffer::MessageBuffer,
         },
         replication::client_ticks::ClientTicks,
+        replication_error::ErrorReporter,
     },
 };
 
@@ -23,7 +28,28 @@ use crate::{
 pub struct ServerMessagePlugin;
 
 impl Plugin for ServerMessagePlugin {
-    fn build(&self, _app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServerMessageSender>()
+            .init_resource::<PendingMessages>()
+            .add_systems(
+                PostUpdate,
+                (
+                    message_sender::write_queued,
+                    after_replication::write_acked.run_if(in_state(ServerState::Running)),
+                )
+                    .before(super::prepare_messages)
+                    .in_set(ServerSystems::Send),
+            )
+            .add_systems(
+                PostUpdate,
+                after_replication::request_acks
+                    .after(super::collect_changes)
+                    .before(super::send_messages)
+                    .run_if(resource_changed::<ServerTick>)
+                    .in_set(ServerSystems::Send)
+                    .run_if(in_state(ServerState::Running)),
+            );
+    }
 
     fn finish(&self, app: &mut App) {
         // Construct systems dynamically after all plugins initialization
@@ -44,6 +70,7 @@ impl Plugin for ServerMessagePlugin {
                 ParamBuilder,
                 ParamBuilder,
                 ParamBuilder,
+                ParamBuilder,
             )
                 .build_state(app.world_mut())
                 .build_system(receive);
@@ -89,6 +116,7 @@ impl Plugin for ServerMessagePlugin {
                 ParamBuilder,
                 ParamBuilder,
                 ParamBuilder,
+                ParamBuilder,
             )
                 .build_state(app.world_mut())
                 .build_system(receive_shared);
@@ -223,10 +251,12 @@ fn receive(
     mut storage: ResMut<ReplicationStorage>,
     type_registry: Res<AppTypeRegistry>,
     message_registry: Res<RemoteMessageRegistry>,
+    mut errors: ResMut<ErrorReporter>,
 ) {
     let mut ctx = ServerReceiveCtx {
         storage: &mut storage,
         type_registry: &type_registry,
+        errors: &mut errors,
     };
 
     for message in message_registry.iter_all_client() {
@@ -245,10 +275,12 @@ fn receive_shared(
     mut storage: ResMut<ReplicationStorage>,
     type_registry: Res<AppTypeRegistry>,
     message_registry: Res<RemoteMessageRegistry>,
+    mut errors: ResMut<ErrorReporter>,
 ) {
     let mut ctx = ServerReceiveCtx {
         storage: &mut storage,
         type_registry: &type_registry,
+        errors: &mut errors,
     };
 
     for message in message_registry.iter_all_shared() {
@@ -0,0 +1,85 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::mem;
+
+use bevy::{
+    platform::sync::{Arc, Mutex, PoisonError},
+    prelude::*,
+};
+use log::debug;
+
+use crate::prelude::*;
+
+/// Thread-safe handle to send server messages and events from outside of the ECS.
+///
+/// Can be cloned into async tasks or threads, for example for matchmaking or database IO.
+/// Queued messages are written at the beginning of [`ServerSystems::Send`]
+/// and then sent like any other [`ToClients`] message.
+///
+/// Messages and events need to be registered as usual.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::{prelude::*, tasks::IoTaskPool};
+/// use bevy_replicon::{prelude::*, server::message_sender::ServerMessageSender};
+/// use serde::{Deserialize, Serialize};
+///
+/// fn load_profile(add: On<Add, AuthorizedClient>, sender: Res<ServerMessageSender>) {
+///     let sender = sender.clone();
+///     let client = add.entity;
+///     IoTaskPool::get()
+///         .spawn(async move {
+///             // Imagine some database IO here.
+///             sender.send(ToClients {
+///                 targets: SendTargets::Single(client.into()),
+///                 message: Profile { rating: 1500 },
+///             });
+///         })
+///         .detach();
+/// }
+///
+/// #[derive(Message, Serialize, Deserialize)]
+/// struct Profile {
+///     rating: u32,
+/// }
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct ServerMessageSender(Arc<Mutex<Vec<QueuedMessage>>>);
+
+impl ServerMessageSender {
+    /// Queues a message to be written as [`ToClients<M>`].
+    pub fn send<M: Message>(&self, message: ToClients<M>) {
+        self.push(Box::new(move |world| {
+            world.write_message(message);
+        }));
+    }
+
+    /// Queues an event to be triggered via [`ServerTriggerExt::server_trigger`].
+    pub fn trigger<E: Event>(&self, event: ToClients<E>) {
+        self.push(Box::new(move |world| world.server_trigger(event)));
+    }
+
+    fn push(&self, message: QueuedMessage) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(message);
+    }
+
+    fn take(&self) -> Vec<QueuedMessage> {
+        mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+type QueuedMessage = Box<dyn FnOnce(&mut World) + Send>;
+
+/// Writes all messages queued via [`ServerMessageSender`].
+pub(super) fn write_queued(world: &mut World) {
+    let queued = world.resource::<ServerMessageSender>().take();
+    if !queued.is_empty() {
+        debug!("writing {} queued messages", queued.len());
+        for write in queued {
+            (write)(world);
+        }
+    }
+}
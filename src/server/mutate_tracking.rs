@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+
+use crate::{prelude::*, shared::replication::mutate_index::MutateIndex};
+
+/// Value of the [`ServerPlugin::track_mutate_messages`].
+///
+/// Inserted by [`ServerPlugin`] and can't be changed after the app is built,
+/// since clients need the same value to interpret mutate messages.
+#[derive(Resource, Deref, Default, Debug, Clone, Copy)]
+pub struct TrackMutateMessages(pub(super) bool);
+
+/**
+Status change of a mutate message sent to a client.
+
+Emitted only if [`ServerPlugin::track_mutate_messages`] is enabled. Intended for tools like
+deterministic replay recorders or network graphs to reconstruct which mutations were in flight.
+
+This is a stable API with the following guarantees:
+- For each client and server tick, [`MutateStatus::Sent`] is emitted for every mutate message
+  in the order of their indices. Since tracking is enabled, at least one message is sent
+  to each connected client every tick, even if there are no mutations.
+- After that, at most one [`MutateStatus::Acknowledged`] or [`MutateStatus::Expired`] is
+  emitted for each message. If the client disconnects, the pending messages are discarded
+  without a status.
+- If a [mutation channel](crate::shared::backend::channels::RepliconChannels::mutation_channel)
+  is reliable, messages sent over it are acknowledged right after sending.
+- Messages from all mutation channels share indices and are counted together.
+
+Indices wrap around, so they should be matched only against recently sent messages.
+
+# Examples
+
+```
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    StatesPlugin,
+    RepliconPlugins.set(ServerPlugin {
+        track_mutate_messages: true,
+        ..Default::default()
+    }),
+))
+.add_systems(Update, count_in_flight);
+
+fn count_in_flight(mut statuses: MessageReader<MutateMessageStatus>, mut in_flight: Local<usize>) {
+    for status in statuses.read() {
+        match status.status {
+            MutateStatus::Sent { .. } => *in_flight += 1,
+            MutateStatus::Acknowledged | MutateStatus::Expired => {
+                *in_flight = in_flight.saturating_sub(1)
+            }
+        }
+    }
+}
+```
+*/
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MutateMessageStatus {
+    /// Client entity to which the message was sent.
+    pub client: Entity,
+
+    /// Index of the mutate message.
+    pub index: MutateIndex,
+
+    /// Server tick for which the message was sent.
+    pub server_tick: RepliconTick,
+
+    /// What happened to the message.
+    pub status: MutateStatus,
+}
+
+/// Status of a mutate message.
+///
+/// See [`MutateMessageStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutateStatus {
+    /// The message was sent to the backend.
+    Sent {
+        /// Number of mutate messages sent to this client for the tick.
+        ///
+        /// Matches the count that the client uses for
+        /// [`ServerMutateTicks`](crate::client::server_mutate_ticks::ServerMutateTicks).
+        messages_count: usize,
+    },
+    /// The client confirmed receiving the message.
+    Acknowledged,
+    /// The message wasn't acknowledged within [`ServerPlugin::mutations_timeout`]
+    /// and is considered lost.
+    ///
+    /// Its mutations are still re-sent in the next messages until a newer message
+    /// with them is acknowledged.
+    Expired,
+}
@@ -0,0 +1,169 @@
+//! Early warnings about excessive replication load.
+//!
+//! See [`ReplicationPressureSettings`] for details.
+
+use bevy::prelude::*;
+use log::{debug, warn};
+
+use super::replication_messages::serialized_data::SerializedData;
+use crate::shared::{
+    message::server_message::message_buffer::MessageBuffer, replication::client_ticks::ClientTicks,
+};
+
+/// Soft limits for replication on the server.
+///
+/// Checked on each server tick after all changes are collected, but before they are sent.
+/// For each exceeded limit, a [`ReplicationPressure`] message is written.
+///
+/// While any limit is exceeded, all priorities from [`PriorityMap`](super::PriorityMap)
+/// are multiplied by [`Self::degraded_priority`].
+///
+/// All limits are disabled by default. Can be modified at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::{prelude::*, state::app::StatesPlugin};
+/// use bevy_replicon::prelude::*;
+///
+/// # let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+///     .insert_resource(ReplicationPressureSettings {
+///         max_serialized_size: Some(4 * 1024 * 1024),
+///         max_pending_mutations: Some(256),
+///         degraded_priority: 0.5,
+///         ..Default::default()
+///     })
+///     .add_systems(Update, report_pressure);
+///
+/// fn report_pressure(mut pressure: MessageReader<ReplicationPressure>) {
+///     for pressure in pressure.read() {
+///         error!("{pressure:?}");
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ReplicationPressureSettings {
+    /// Maximum number of bytes serialized for all clients in a single tick.
+    pub max_serialized_size: Option<usize>,
+
+    /// Maximum number of unacknowledged mutate messages for a single client.
+    ///
+    /// Messages are counted until acknowledged or discarded after
+    /// [`ServerPlugin::mutations_timeout`](super::ServerPlugin::mutations_timeout).
+    pub max_pending_mutations: Option<usize>,
+
+    /// Maximum number of server messages buffered until the next tick.
+    ///
+    /// Messages are buffered when they are written between server ticks.
+    pub max_buffered_messages: Option<usize>,
+
+    /// Multiplier for all priorities while any limit is exceeded.
+    ///
+    /// For example, with 0.5 mutations will be sent no more often than once every 2 ticks.
+    ///
+    /// By default set to 1.0, which disables the degradation.
+    pub degraded_priority: f32,
+}
+
+impl Default for ReplicationPressureSettings {
+    fn default() -> Self {
+        Self {
+            max_serialized_size: None,
+            max_pending_mutations: None,
+            max_buffered_messages: None,
+            degraded_priority: 1.0,
+        }
+    }
+}
+
+/// A limit from [`ReplicationPressureSettings`] that was exceeded during a server tick.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ReplicationPressure {
+    /// Exceeded limit.
+    pub kind: PressureKind,
+
+    /// Measured value.
+    pub value: usize,
+
+    /// Configured limit.
+    pub limit: usize,
+}
+
+/// Category of [`ReplicationPressure`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PressureKind {
+    /// [`ReplicationPressureSettings::max_serialized_size`].
+    SerializedSize,
+    /// [`ReplicationPressureSettings::max_pending_mutations`] for the client entity.
+    PendingMutations(Entity),
+    /// [`ReplicationPressureSettings::max_buffered_messages`].
+    BufferedMessages,
+}
+
+/// Multiplier for all priorities, derived from [`ReplicationPressureSettings::degraded_priority`].
+#[derive(Resource, Deref)]
+pub(super) struct PriorityScale(f32);
+
+impl Default for PriorityScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+pub(super) fn check_pressure(
+    settings: Res<ReplicationPressureSettings>,
+    serialized: Res<SerializedData>,
+    message_buffer: Res<MessageBuffer>,
+    mut priority_scale: ResMut<PriorityScale>,
+    mut pressure: MessageWriter<ReplicationPressure>,
+    mut under_pressure: Local<bool>,
+    clients: Query<(Entity, &ClientTicks)>,
+) {
+    let mut exceeded = false;
+    let mut check = |kind, value, limit: Option<usize>| {
+        if let Some(limit) = limit
+            && value > limit
+        {
+            debug!("`{kind:?}` exceeded the limit with {value} of {limit}");
+            pressure.write(ReplicationPressure { kind, value, limit });
+            exceeded = true;
+        }
+    };
+
+    check(
+        PressureKind::SerializedSize,
+        serialized.len(),
+        settings.max_serialized_size,
+    );
+    check(
+        PressureKind::BufferedMessages,
+        message_buffer.len(),
+        settings.max_buffered_messages,
+    );
+    for (client, ticks) in &clients {
+        check(
+            PressureKind::PendingMutations(client),
+            ticks.pending_mutations(),
+            settings.max_pending_mutations,
+        );
+    }
+
+    if *under_pressure != exceeded {
+        *under_pressure = exceeded;
+        if exceeded {
+            warn!(
+                "replication is under pressure, scaling priorities by {}",
+                settings.degraded_priority
+            );
+        } else {
+            debug!("replication pressure is relieved");
+        }
+    }
+
+    priority_scale.0 = if exceeded {
+        settings.degraded_priority
+    } else {
+        1.0
+    };
+}
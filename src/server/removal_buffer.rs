@@ -1,13 +1,19 @@
 use bevy::{
-    ecs::{component::ComponentId, entity::hash_map::EntityHashMap},
+    ecs::{component::ComponentId, entity::EntityHashMap},
     prelude::*,
 };
 use log::trace;
 use smallvec::SmallVec;
 
 use crate::{
-    server::replicated_archetypes::ReplicatedArchetype,
-    shared::replication::registry::{ComponentIndex, FnsId, ReplicationRegistry},
+    server::{WriteOrderMap, replicated_archetypes::ReplicatedArchetype},
+    shared::replication::{
+        registry::{
+            ComponentIndex, ReplicationRegistry, component_mask::ComponentMask,
+            removal_id::RemovalId,
+        },
+        rules::ReplicationRules,
+    },
 };
 
 /// Buffer with removed components for the current tick.
@@ -15,37 +21,125 @@ use crate::{
 pub(super) struct RemovalBuffer {
     /// Component removals grouped by entity.
     #[deref]
-    removals: EntityHashMap<SmallVec<[(ComponentIndex, FnsId); 4]>>,
+    removals: WriteOrderMap<SmallVec<[RemovalId; 4]>>,
+
+    /// Components of each entity that were sent to at least one client.
+    ///
+    /// Removals of other components can be skipped without checking each client.
+    /// Components are unmarked once their removals are collected
+    /// and entities are removed when they lose [`Replicated`].
+    sent: EntityHashMap<ComponentMask>,
 }
 
 impl RemovalBuffer {
+    /// Buffers removals of replicated components.
+    ///
+    /// If all components of a rule are removed together, they are buffered as a single bundle.
     pub(super) fn insert(
         &mut self,
         entity: Entity,
         components: &[ComponentId],
         archetype: &ReplicatedArchetype,
-        registry: &ReplicationRegistry,
+        rules: &ReplicationRules,
     ) {
         let entity_removals = self.removals.entry(entity).or_default();
 
-        for &id in components {
+        let mut bundled = SmallVec::<[ComponentId; 4]>::new();
+        for &index in &archetype.rules {
+            let rule = &rules[index];
+            let removed = rule.components.iter().all(|component| {
+                components.contains(&component.id)
+                    && archetype
+                        .find_rule(component.id)
+                        .is_some_and(|selected| selected.fns_id == component.fns_id)
+            });
+            if !removed {
+                continue;
+            }
+
+            let Some(removal_id) =
+                RemovalId::bundle(rule.components.iter().map(|component| component.fns_id))
+            else {
+                continue;
+            };
+
+            trace!("buffering `{removal_id:?}` removal for `{entity}`");
+            entity_removals.push(removal_id);
+            bundled.extend(rule.components.iter().map(|component| component.id));
+        }
+
+        for &id in components.iter().filter(|id| !bundled.contains(id)) {
             let Some(rule) = archetype.find_rule(id) else {
                 trace!("skipping non-replicated `{id:?}` removal for `{entity}`");
                 continue;
             };
 
-            let (component_index, ..) = registry.get(rule.fns_id);
             trace!("buffering `{:?}` removal for `{entity}`", rule.fns_id);
-            entity_removals.push((component_index, rule.fns_id));
+            entity_removals.push(RemovalId::Component(rule.fns_id));
         }
     }
 
+    /// Marks a component of an entity as sent to a client.
+    pub(super) fn mark_sent(&mut self, entity: Entity, component_index: ComponentIndex) {
+        self.sent.entry(entity).or_default().insert(component_index);
+    }
+
+    /// Returns `true` if any component of the removal was sent to a client for the entity.
+    pub(super) fn any_sent(
+        &self,
+        entity: Entity,
+        removal_id: RemovalId,
+        registry: &ReplicationRegistry,
+    ) -> bool {
+        let Some(sent) = self.sent.get(&entity) else {
+            return false;
+        };
+
+        removal_id.fns_ids().any(|fns_id| {
+            let (component_index, ..) = registry.get(fns_id);
+            sent.contains(component_index)
+        })
+    }
+
+    /// Unmarks all buffered removals as sent.
+    ///
+    /// Should be called after the removals are collected.
+    pub(super) fn unmark_removed(&mut self, registry: &ReplicationRegistry) {
+        for (entity, removal_ids) in self.removals.iter() {
+            let Some(sent) = self.sent.get_mut(entity) else {
+                continue;
+            };
+
+            for fns_id in removal_ids
+                .iter()
+                .flat_map(|removal_id| removal_id.fns_ids())
+            {
+                let (component_index, ..) = registry.get(fns_id);
+                sent.remove(component_index);
+            }
+            if sent.is_empty() {
+                self.sent.remove(entity);
+            }
+        }
+    }
+
+    /// Removes sent components of an entity that is no longer replicated.
+    pub(super) fn remove_sent(&mut self, entity: Entity) {
+        self.sent.remove(&entity);
+    }
+
     /// Clears all removals.
     ///
     /// Keeps the allocated memory for reuse.
     pub(super) fn clear(&mut self) {
         self.removals.clear();
     }
+
+    /// Clears all removals and sent components.
+    pub(super) fn reset(&mut self) {
+        self.removals.clear();
+        self.sent.clear();
+    }
 }
 
 #[cfg(test)]
@@ -116,7 +210,10 @@ mod tests {
         assert_eq!(removal_buffer.len(), 1);
 
         let removal_ids = removal_buffer.get(&entity).unwrap();
-        assert_eq!(removal_ids.len(), 2);
+        assert!(
+            matches!(removal_ids.as_slice(), [RemovalId::Bundle { len: 2, .. }]),
+            "removal of the whole bundle should be a single record"
+        );
     }
 
     #[test]
@@ -163,7 +260,10 @@ mod tests {
         assert_eq!(removal_buffer.len(), 1);
 
         let removal_ids = removal_buffer.get(&entity).unwrap();
-        assert_eq!(removal_ids.len(), 2);
+        assert!(
+            matches!(removal_ids.as_slice(), [RemovalId::Bundle { len: 2, .. }]),
+            "removal of the whole bundle should be a single record"
+        );
     }
 
     #[test]
@@ -185,7 +285,9 @@ mod tests {
         assert_eq!(removal_buffer.len(), 1);
 
         let removal_ids = removal_buffer.get(&entity).unwrap();
-        let [(_, fns_id)] = removal_ids.as_slice().try_into().unwrap();
+        let &[RemovalId::Component(fns_id)] = removal_ids.as_slice() else {
+            panic!("removal should contain a single component");
+        };
 
         let rules = app.world().resource::<ReplicationRules>();
         let bundle_rule = rules.iter().find(|r| r.components.len() == 2).unwrap();
@@ -216,6 +318,32 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "deterministic")]
+    fn ordered() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .replicate::<A>()
+            .finish();
+
+        app.world_mut()
+            .resource_mut::<NextState<ServerState>>()
+            .set(ServerState::Running);
+        app.update();
+
+        let mut entities: Vec<_> = (0..10)
+            .map(|_| app.world_mut().spawn((Replicated, A)).id())
+            .collect();
+        for &entity in entities.iter().rev() {
+            app.world_mut().entity_mut(entity).remove::<A>();
+        }
+
+        let removal_buffer = app.world().resource::<RemovalBuffer>();
+        let removals: Vec<_> = removal_buffer.keys().copied().collect();
+        entities.sort();
+        assert_eq!(removals, entities, "removals should be ordered by entity");
+    }
+
     #[derive(Component, Serialize, Deserialize)]
     struct A;
 
@@ -2,7 +2,7 @@ use core::mem;
 
 use bevy::{
     ecs::{
-        archetype::{ArchetypeGeneration, ArchetypeId, Archetypes},
+        archetype::{Archetype, ArchetypeGeneration, ArchetypeId, Archetypes},
         component::{ComponentId, StorageType},
     },
     platform::collections::HashMap,
@@ -12,7 +12,7 @@ use log::trace;
 
 use crate::{
     prelude::*,
-    shared::replication::rules::{ReplicationRules, component::ComponentRule},
+    shared::replication::rules::{ReplicationRules, component::ComponentRule, filter::FilterRule},
 };
 
 #[derive(Resource)]
@@ -20,9 +20,17 @@ pub(super) struct ReplicatedArchetypes {
     /// ID of [`Replicated`] component.
     marker_id: ComponentId,
 
+    /// ID of [`ReplicateOnceThenForget`] component.
+    forget_id: ComponentId,
+
     /// Highest processed archetype ID.
     generation: ArchetypeGeneration,
 
+    /// Enabled state of each rule from [`ReplicationRules`] at the last update.
+    ///
+    /// Used to detect toggled rules.
+    enabled_rules: Vec<bool>,
+
     /// Maps a Bevy archetype ID to an index in [`Self::list`].
     ids_map: HashMap<ArchetypeId, usize>,
 
@@ -32,38 +40,56 @@ pub(super) struct ReplicatedArchetypes {
 
 impl ReplicatedArchetypes {
     pub(super) fn update(&mut self, archetypes: &Archetypes, rules: &ReplicationRules) {
-        let old_generation = mem::replace(&mut self.generation, archetypes.generation());
+        self.invalidate_toggled(archetypes, rules);
 
+        let old_generation = mem::replace(&mut self.generation, archetypes.generation());
         for archetype in archetypes[old_generation..]
             .iter()
             .filter(|archetype| archetype.contains(self.marker_id))
         {
             trace!("marking `{:?}` as replicated", archetype.id());
-            let mut replicated_archetype = ReplicatedArchetype::new(archetype.id());
-            for rule in rules.iter().filter(|rule| rule.matches(archetype)) {
-                for &component in &rule.components {
-                    // Since rules are sorted by priority,
-                    // we are inserting only new components that aren't present.
-                    if replicated_archetype
-                        .components
-                        .iter()
-                        .any(|(existing, _)| existing.id == component.id)
-                    {
-                        continue;
-                    }
-
-                    // SAFETY: archetype matches the rule, so the component is present.
-                    let storage =
-                        unsafe { archetype.get_storage_type(component.id).unwrap_unchecked() };
-                    replicated_archetype.components.push((component, storage));
-                }
-            }
-
+            let replicated_archetype = ReplicatedArchetype::new(archetype, self.forget_id, rules);
             self.ids_map.insert(archetype.id(), self.list.len());
             self.list.push(replicated_archetype);
         }
     }
 
+    /// Re-evaluates cached archetypes affected by rules toggled since the last update.
+    fn invalidate_toggled(&mut self, archetypes: &Archetypes, rules: &ReplicationRules) {
+        if self.enabled_rules.len() != rules.len() {
+            // Rules can only be registered before the app starts,
+            // so this happens only on the first update.
+            self.enabled_rules = rules.iter().map(|rule| rule.enabled).collect();
+            return;
+        }
+
+        let toggled: Vec<_> = rules
+            .iter()
+            .zip(&mut self.enabled_rules)
+            .enumerate()
+            .filter(|(_, (rule, enabled))| rule.enabled != **enabled)
+            .map(|(index, (rule, enabled))| {
+                *enabled = rule.enabled;
+                index
+            })
+            .collect();
+        if toggled.is_empty() {
+            return;
+        }
+
+        for replicated_archetype in &mut self.list {
+            // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
+            let archetype = unsafe { archetypes.get(replicated_archetype.id).unwrap_unchecked() };
+            let affected = toggled.iter().any(|&index| {
+                replicated_archetype.rules.contains(&index) || rules[index].matches(archetype)
+            });
+            if affected {
+                trace!("re-evaluating `{:?}` after rules toggling", archetype.id());
+                *replicated_archetype = ReplicatedArchetype::new(archetype, self.forget_id, rules);
+            }
+        }
+    }
+
     pub(super) fn marker_id(&self) -> ComponentId {
         self.marker_id
     }
@@ -82,7 +108,9 @@ impl FromWorld for ReplicatedArchetypes {
     fn from_world(world: &mut World) -> Self {
         Self {
             marker_id: world.register_component::<Replicated>(),
+            forget_id: world.register_component::<ReplicateOnceThenForget>(),
             generation: ArchetypeGeneration::initial(),
+            enabled_rules: Default::default(),
             ids_map: Default::default(),
             list: Default::default(),
         }
@@ -94,20 +122,74 @@ pub(super) struct ReplicatedArchetype {
     /// Associated archetype ID.
     pub(super) id: ArchetypeId,
 
-    /// Components marked as replicated.
-    pub(super) components: Vec<(ComponentRule, StorageType)>,
+    /// Components marked as replicated with indices of rules they were selected from.
+    pub(super) components: Vec<(ComponentRule, StorageType, usize)>,
+
+    /// Indices of matched rules in [`ReplicationRules`].
+    pub(super) rules: Vec<usize>,
+
+    /// Whether the archetype contains [`ReplicateOnceThenForget`].
+    pub(super) forget: bool,
+
+    /// Filters of rules with client filters for components selected from them.
+    ///
+    /// See [`ClientWith`].
+    client_filters: Vec<(ComponentId, Vec<FilterRule>)>,
 }
 
 impl ReplicatedArchetype {
-    fn new(id: ArchetypeId) -> Self {
-        Self {
-            id,
+    fn new(archetype: &Archetype, forget_id: ComponentId, rules: &ReplicationRules) -> Self {
+        let mut replicated_archetype = Self {
+            id: archetype.id(),
             components: Default::default(),
+            rules: Default::default(),
+            forget: archetype.contains(forget_id),
+            client_filters: Default::default(),
+        };
+
+        for (index, rule) in rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.matches(archetype))
+        {
+            replicated_archetype.rules.push(index);
+            for &component in &rule.components {
+                // Since rules are sorted by priority,
+                // we are inserting only new components that aren't present.
+                if replicated_archetype
+                    .components
+                    .iter()
+                    .any(|(existing, ..)| existing.id == component.id)
+                {
+                    continue;
+                }
+
+                // SAFETY: archetype matches the rule, so the component is present.
+                let storage =
+                    unsafe { archetype.get_storage_type(component.id).unwrap_unchecked() };
+                replicated_archetype
+                    .components
+                    .push((component, storage, index));
+                if rule.filters.iter().any(FilterRule::is_client) {
+                    replicated_archetype
+                        .client_filters
+                        .push((component.id, rule.filters.clone()));
+                }
+            }
         }
+
+        replicated_archetype
+    }
+
+    /// Returns filters that need to be checked for each client before sending the component.
+    pub(super) fn client_filters(&self, id: ComponentId) -> Option<&[FilterRule]> {
+        self.client_filters
+            .iter()
+            .find_map(|(component_id, filters)| (*component_id == id).then_some(&**filters))
     }
 
     pub(super) fn find_rule(&self, id: ComponentId) -> Option<&ComponentRule> {
-        self.components.iter().map(|(r, _)| r).find(|r| r.id == id)
+        self.components.iter().map(|(r, ..)| r).find(|r| r.id == id)
     }
 }
 
@@ -259,6 +341,49 @@ mod tests {
         assert_eq!(archetype.components.len(), 3);
     }
 
+    #[test]
+    fn toggling() {
+        let mut app = App::new();
+        app.init_resource::<ReplicatedArchetypes>()
+            .init_resource::<ReplicationRules>()
+            .init_resource::<ProtocolHasher>()
+            .init_resource::<ReplicationRegistry>()
+            .replicate::<A>()
+            .replicate::<B>();
+
+        app.world_mut().spawn((Replicated, A));
+        app.world_mut().spawn((Replicated, B));
+        update_archetypes(&mut app);
+
+        app.world_mut()
+            .resource_mut::<ReplicationRules>()
+            .set_enabled(0, false);
+        update_archetypes(&mut app);
+
+        let archetypes = app.world().resource::<ReplicatedArchetypes>();
+        assert_eq!(archetypes.list.len(), 2);
+        assert_eq!(
+            archetypes
+                .iter()
+                .filter(|archetype| archetype.components.is_empty())
+                .count(),
+            1,
+            "only archetype with the disabled rule should be affected"
+        );
+
+        app.world_mut()
+            .resource_mut::<ReplicationRules>()
+            .set_enabled(0, true);
+        update_archetypes(&mut app);
+
+        let archetypes = app.world().resource::<ReplicatedArchetypes>();
+        assert!(
+            archetypes
+                .iter()
+                .all(|archetype| archetype.components.len() == 1)
+        );
+    }
+
     fn update_archetypes(app: &mut App) {
         app.world_mut()
             .resource_scope(|world, mut archetypes: Mut<ReplicatedArchetypes>| {
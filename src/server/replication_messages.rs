@@ -1,3 +1,78 @@
+/*!
+Serialization of replication messages.
+
+All values are serialized with [`postcard`]. Integers use variable-length encoding (varint) unless
+stated otherwise, and entities use [`compact_entity`](crate::compact_entity) encoding.
+
+# Update message
+
+Sent over [`ServerChannel::Updates`](crate::shared::backend::channels::ServerChannel::Updates).
+Contains mappings, despawns, removals and insertions that happened in a tick.
+
+| Field       | Encoding                                              | Present if                 |
+|-------------|-------------------------------------------------------|----------------------------|
+| Flags       | `u8` bitset of the sections below, in order           | Always                     |
+| Server tick | [`RepliconTick`](crate::prelude::RepliconTick)       | Always                     |
+| Userdata    | length followed by raw bytes                          | Bit `0b00000010` is set    |
+| Mappings    | array of entity followed by `u64` little-endian hash  | Bit `0b00000100` is set    |
+| Despawns    | array of entities                                     | Bit `0b00001000` is set    |
+| Removals    | array of entity removals                              | Bit `0b00010000` is set    |
+| Changes     | array of entity changes                               | Bit `0b00100000` is set    |
+
+Arrays are prefixed with their number of elements, except for the last present section,
+which has no length and occupies all remaining bytes.
+
+Bit `0b00000001` has no data and requests the client to acknowledge the message.
+
+Entity removals consist of an entity, the size of the following data in bytes and
+an array of removal records without length that occupies this data. Each record is a
+[`FnsId`](crate::shared::replication::registry::FnsId) shifted left by 1 bit. If the lowest
+bit is set, the record removes a bundle of components with consecutive IDs starting from it,
+and the number of components follows.
+
+Entity changes consist of an entity, the size of the following data in bytes and
+an array of components without length that occupies this data. Each component is
+a [`FnsId`](crate::shared::replication::registry::FnsId) followed by the data written
+by the serialization function registered for it. Data of components from
+[optional rules](crate::shared::replication::rules::AppRuleExt::replicate_optional), or of all
+components with [`AppRuleExt::prefix_component_sizes`](crate::shared::replication::rules::AppRuleExt::prefix_component_sizes),
+is prefixed with its size in bytes as `u32` little-endian.
+
+# Mutate message
+
+Sent over the channel of the [`MutationChannel`](crate::shared::backend::channels::MutationChannel)
+of the mutated components, see [`RepliconChannels::mutation_channel`](crate::shared::backend::channels::RepliconChannels::mutation_channel).
+Contains component mutations since the last acknowledged tick for each entity. Large messages are split
+between entities, so each message can be applied independently.
+
+| Field          | Encoding                                            | Present if                 |
+|----------------|-----------------------------------------------------|----------------------------|
+| Flags          | `u8` bitset of the sections below, in order         | Always                     |
+| Mutate index   | `u16` little-endian                                 | Always                     |
+| Update tick    | [`RepliconTick`](crate::prelude::RepliconTick) of the required update message | Always                     |
+| Server tick    | [`RepliconTick`](crate::prelude::RepliconTick)      | Always                     |
+| Userdata       | length followed by raw bytes                        | Bit `0b00000001` is set    |
+| Messages count | number of mutate messages sent for this tick        | Bit `0b00000010` is set    |
+| Mutations      | entity changes until the end of the message         | Bit `0b00000100` is set    |
+
+# Acknowledgments
+
+Sent by clients over [`ClientChannel::MutationAcks`](crate::shared::backend::channels::ClientChannel::MutationAcks).
+Contains mutate indices from received mutate messages until the end of the message.
+
+Update messages that request acknowledgment are acknowledged over
+[`ClientChannel::UpdateAcks`](crate::shared::backend::channels::ClientChannel::UpdateAcks)
+with the [`RepliconTick`](crate::prelude::RepliconTick) of the last applied update message.
+
+# Validation
+
+Messages with unknown flags or sizes that exceed the remaining data are rejected.
+Clients deserialize sizes into `usize`, so sizes that don't fit the client's pointer width are also rejected.
+
+Components and removals with unregistered functions IDs are skipped with a warning to support
+[optional rules](crate::shared::replication::rules::AppRuleExt::replicate_optional).
+*/
+
 mod entity_ranges;
 pub(super) mod mutations;
 pub(super) mod serialized_data;
@@ -17,6 +17,11 @@ pub(super) struct EntityRanges {
     pub(super) entity: Range<usize>,
     // Entities usually don't have a lot of changed components at the same time.
     pub(super) data: SmallVec<[Range<usize>; 4]>,
+
+    /// Indices of rules with sizes of their components from [`Self::data`].
+    ///
+    /// Filled only for clients with [`RuleStats`](crate::server::rule_stats::RuleStats).
+    pub(super) rule_bytes: Vec<(usize, usize)>,
 }
 
 impl EntityRanges {
@@ -45,5 +50,6 @@ impl EntityRanges {
 
     pub(super) fn extend(&mut self, other: &Self) {
         self.data.extend(other.data.iter().cloned());
+        self.rule_bytes.extend_from_slice(&other.rule_bytes);
     }
 }
@@ -8,9 +8,9 @@ use super::{entity_ranges::EntityRanges, serialized_data::SerializedData};
 use crate::{
     postcard_utils,
     prelude::*,
-    server::ReplicationUserdata,
+    server::{ReplicationUserdata, rule_stats::RuleStats},
     shared::{
-        backend::channels::ServerChannel,
+        backend::channels::ServerChannelId,
         replication::{
             client_ticks::{ClientTicks, DiffCursors, MutateInfo, MutatedEntityInfo},
             message_flags::MutateFlags,
@@ -28,6 +28,225 @@ use crate::{
 /// Can be packed into messages using [`Self::send`].
 #[derive(Component, Default)]
 pub(crate) struct Mutations {
+    /// Mutations for each [`MutationChannel`].
+    channels: [ChannelMutations; MutationChannel::COUNT],
+}
+
+impl Mutations {
+    /// Updates internal state to start writing mutated components for an entity.
+    ///
+    /// Entities and their data written lazily during the iteration.
+    /// See [`ChannelMutations::add_entity`] and [`ChannelMutations::add_component`].
+    pub(crate) fn start_entity(&mut self) {
+        for mutations in &mut self.channels {
+            mutations.entity_location = None;
+        }
+    }
+
+    /// Returns `true` if [`ChannelMutations::add_entity`] were called on any channel
+    /// since the last call of [`Self::start_entity`].
+    pub(crate) fn entity_added(&self) -> bool {
+        self.channels.iter().any(ChannelMutations::entity_added)
+    }
+
+    /// Returns mutations for the given channel.
+    pub(crate) fn channel_mut(&mut self, channel: MutationChannel) -> &mut ChannelMutations {
+        &mut self.channels[channel as usize]
+    }
+
+    /// Removes last added entity from [`ChannelMutations::add_entity`] on the first channel
+    /// that has it and returns it.
+    ///
+    /// Should be called until it returns [`None`] to take the entity from all channels.
+    pub(super) fn pop(&mut self) -> Option<EntityMutations> {
+        self.channels.iter_mut().find_map(ChannelMutations::pop)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.channels.iter().all(ChannelMutations::is_empty)
+    }
+
+    /// Returns `true` if mutations of the last added entity on any channel don't fit into `max_size`.
+    ///
+    /// Messages are split only per entity, so such mutations will always be sent in a message
+    /// that exceeds `max_size` and will be lost if any of its packets is lost.
+    pub(crate) fn entity_exceeds(&self, max_size: usize) -> Result<bool> {
+        for mutations in &self.channels {
+            if let Some(entity_mutations) = mutations.last_entity()
+                && entity_mutations.ranges.size()? > max_size
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Packs mutations into messages.
+    ///
+    /// Contains update tick, current tick, mutate index and component mutations since
+    /// the last acknowledged tick for each entity.
+    ///
+    /// Cannot be applied on the client until the update message matching this message's update tick
+    /// has been applied to the client world.
+    /// The message will be manually split into packets up to max size, and each packet will be applied
+    /// independently on the client.
+    /// Message splits only happen per-entity to avoid weird behavior from partial entity mutations.
+    /// Entities whose mutations don't fit into `max_size` are moved into update messages
+    /// during collection, see [`Self::entity_exceeds`].
+    ///
+    /// Mutations of each [`MutationChannel`] are packed into separate messages and sent over the corresponding
+    /// channel. If the message gets lost, we try to resend it manually, using the last up-to-date mutations
+    /// to avoid re-sending old values.
+    ///
+    /// `channel_ids` contain IDs of server channels for each mutation channel.
+    /// If the channel is `reliable`, messages are acknowledged immediately since they will be delivered anyway.
+    ///
+    /// Splits for all sent messages are left in `split_buffer`. Returns the number of sent messages.
+    pub(crate) fn send(
+        &mut self,
+        messages: &mut ServerMessages,
+        client: Entity,
+        ticks: &mut ClientTicks,
+        split_buffer: &mut Vec<MutationsSplit>,
+        serialized: &SerializedData,
+        track_mutate_messages: bool,
+        channel_ids: [Option<ServerChannelId>; MutationChannel::COUNT],
+        reliable: [bool; MutationChannel::COUNT],
+        userdata: &ReplicationUserdata,
+        server_tick_range: Range<usize>,
+        server_tick: RepliconTick,
+        system_tick: Tick,
+        timestamp: Duration,
+        max_size: usize,
+        mut stats: Option<&mut RuleStats>,
+    ) -> Result<usize> {
+        const MESSAGES_COUNT_MAX_SIZE: usize = usize::POSTCARD_MAX_SIZE;
+        let mut tick_buffer = [0; RepliconTick::POSTCARD_MAX_SIZE];
+        let update_tick = postcard::to_slice(&ticks.update_tick, &mut tick_buffer)?;
+        let mut base_header_size =
+            size_of::<MutateFlags>() + update_tick.len() + server_tick_range.len();
+        if !userdata.is_empty() {
+            base_header_size += serialized_size(&userdata.len())? + userdata.len();
+        }
+        if track_mutate_messages {
+            // We don't know the number of messages ahead of time, so we assume the maximum
+            // possible size during the splits calculation to avoid exceeding MTU.
+            base_header_size += MESSAGES_COUNT_MAX_SIZE;
+        }
+
+        let new_info = |channel| MutateInfo {
+            channel,
+            server_tick,
+            system_tick,
+            timestamp,
+            entities: Default::default(),
+        };
+        for channel in MutationChannel::ALL {
+            self.channel_mut(channel).split(
+                ticks,
+                split_buffer,
+                new_info(channel),
+                base_header_size,
+                max_size,
+            )?;
+        }
+        if split_buffer.is_empty() && track_mutate_messages {
+            // Create an empty message if tracking mutate messages is enabled.
+            let mutate_index = ticks.next_mutate_index();
+            split_buffer.push(MutationsSplit {
+                channel: MutationChannel::Primary,
+                mutate_index,
+                message_size: base_header_size + serialized_size(&mutate_index)?,
+                chunks_range: Default::default(),
+            });
+            ticks.register_mutate_message(mutate_index, new_info(MutationChannel::Primary));
+        }
+
+        if split_buffer.len() > 1 {
+            trace!(
+                "splitting into {} messages for client `{client}`",
+                split_buffer.len()
+            );
+        }
+
+        let mut base_flags = MutateFlags::default();
+        if track_mutate_messages {
+            base_flags |= MutateFlags::MESSAGES_COUNT;
+        }
+        if !userdata.is_empty() {
+            base_flags |= MutateFlags::USERDATA;
+        }
+
+        for split in &*split_buffer {
+            let mut message_size = split.message_size;
+            if track_mutate_messages {
+                // Update message counter size based on actual value.
+                message_size -= MESSAGES_COUNT_MAX_SIZE - serialized_size(&split_buffer.len())?;
+            }
+            let mut message = Vec::with_capacity(message_size);
+
+            let flags = if split.chunks_range.is_empty() {
+                base_flags
+            } else {
+                base_flags | MutateFlags::MUTATIONS
+            };
+
+            postcard_utils::to_extend_mut(&flags, &mut message)?;
+            postcard_utils::to_extend_mut(&split.mutate_index, &mut message)?;
+            message.extend_from_slice(update_tick);
+            message.extend_from_slice(&serialized[server_tick_range.clone()]);
+            if !userdata.is_empty() {
+                postcard_utils::to_extend_mut(&userdata.len(), &mut message)?;
+                message.extend_from_slice(userdata);
+            }
+            if track_mutate_messages {
+                postcard_utils::to_extend_mut(&split_buffer.len(), &mut message)?;
+            }
+            if let Some(stats) = &mut stats {
+                stats.start_message();
+            }
+            let chunks = self.channel_mut(split.channel).chunks();
+            for mutations in chunks.iter_flatten(split.chunks_range.clone()) {
+                message.extend_from_slice(&serialized[mutations.ranges.entity.clone()]);
+                postcard_utils::to_extend_mut(&mutations.ranges.data_size(), &mut message)?;
+                for component in &mutations.ranges.data {
+                    message.extend_from_slice(&serialized[component.clone()]);
+                }
+                if let Some(stats) = &mut stats {
+                    for &(rule, bytes) in &mutations.ranges.rule_bytes {
+                        stats.add(rule, bytes);
+                    }
+                }
+            }
+
+            debug_assert_eq!(message.len(), message_size);
+
+            let channel_id = channel_ids[split.channel as usize]
+                .expect("mutations should be written only for created channels");
+            messages.send(client, channel_id, message);
+            if reliable[split.channel as usize] {
+                ticks.ack_mutate_message(client, split.mutate_index);
+            }
+        }
+
+        Ok(split_buffer.len())
+    }
+
+    /// Clears all entity mutations and updates size of [`ChannelMutations::related`]
+    /// to split related entities by graph index.
+    ///
+    /// Keeps allocated memory for reuse.
+    pub(crate) fn reset(&mut self, graphs_count: usize) {
+        for mutations in &mut self.channels {
+            mutations.reset(graphs_count);
+        }
+    }
+}
+
+/// Component mutations for a single [`MutationChannel`].
+#[derive(Default)]
+pub(crate) struct ChannelMutations {
     /// Entities that are related to each other and should be replicated in sync.
     ///
     /// Like [`Self::standalone`], but grouped into arrays based on their relation graph indices.
@@ -39,22 +258,14 @@ pub(crate) struct Mutations {
     /// These mutation are not related to any others and can be replicated independently.
     standalone: Vec<EntityMutations>,
 
-    /// Location of the last written entity since the last call of [`Self::start_entity_mutations`].
+    /// Location of the last written entity since the last call of [`Mutations::start_entity`].
     entity_location: Option<EntityLocation>,
 }
 
-impl Mutations {
-    /// Updates internal state to start writing mutated components for an entity.
-    ///
-    /// Entities and their data written lazily during the iteration.
-    /// See [`Self::add_entity`] and [`Self::add_component`].
-    pub(crate) fn start_entity(&mut self) {
-        self.entity_location = None;
-    }
-
+impl ChannelMutations {
     /// Returns `true` if [`Self::add_entity`] were called since the last
-    /// call of [`Self::start_entity`].
-    pub(crate) fn entity_added(&mut self) -> bool {
+    /// call of [`Mutations::start_entity`].
+    pub(crate) fn entity_added(&self) -> bool {
         self.entity_location.is_some()
     }
 
@@ -70,6 +281,7 @@ impl Mutations {
             ranges: EntityRanges {
                 entity: entity_range,
                 data: Default::default(),
+                rule_bytes: Default::default(),
             },
             components: Default::default(),
             diff_cursors: Default::default(),
@@ -100,6 +312,19 @@ impl Mutations {
         mutations.ranges.add_data(component);
     }
 
+    /// Attributes the size of the next component from [`Self::add_component`] to a rule.
+    pub(crate) fn add_rule_bytes(&mut self, rule: usize, bytes: usize) {
+        let mutations = self
+            .entity_location
+            .and_then(|location| match location {
+                EntityLocation::Related { index } => self.related[index].last_mut(),
+                EntityLocation::Standalone => self.standalone.last_mut(),
+            })
+            .expect("entity should be written before adding components");
+
+        mutations.ranges.rule_bytes.push((rule, bytes));
+    }
+
     /// Adds the diff cursor serialized for component.
     pub(crate) fn add_diff_cursor(&mut self, component: ComponentIndex, cursor: DiffIndex) {
         let mutations = self
@@ -113,8 +338,16 @@ impl Mutations {
         mutations.diff_cursors.push((component, cursor));
     }
 
+    /// Returns last added entity from [`Self::add_entity`].
+    fn last_entity(&self) -> Option<&EntityMutations> {
+        self.entity_location.and_then(|location| match location {
+            EntityLocation::Related { index } => self.related[index].last(),
+            EntityLocation::Standalone => self.standalone.last(),
+        })
+    }
+
     /// Removes last added entity from [`Self::add_entity`] and returns it.
-    pub(super) fn pop(&mut self) -> Option<EntityMutations> {
+    fn pop(&mut self) -> Option<EntityMutations> {
         self.entity_location
             .take()
             .and_then(|location| match location {
@@ -123,60 +356,30 @@ impl Mutations {
             })
     }
 
-    pub(crate) fn is_empty(&self) -> bool {
+    fn is_empty(&self) -> bool {
         self.standalone.is_empty() && self.related.is_empty()
     }
 
-    /// Packs mutations into messages.
+    /// Splits mutations into messages that fit into `max_size` and registers them in `ticks`.
     ///
-    /// Contains update tick, current tick, mutate index and component mutations since
-    /// the last acknowledged tick for each entity.
-    ///
-    /// Cannot be applied on the client until the update message matching this message's update tick
-    /// has been applied to the client world.
-    /// The message will be manually split into packets up to max size, and each packet will be applied
-    /// independently on the client.
-    /// Message splits only happen per-entity to avoid weird behavior from partial entity mutations.
-    ///
-    /// Sent over the [`ServerChannel::Mutations`] channel. If the message gets lost, we try to resend it manually,
-    /// using the last up-to-date mutations to avoid re-sending old values.
-    pub(crate) fn send(
+    /// Each message gets its own mutate index and a copy of `info` with entities
+    /// that it contains. Pushes the splits into `split_buffer`.
+    fn split(
         &mut self,
-        messages: &mut ServerMessages,
-        client: Entity,
         ticks: &mut ClientTicks,
         split_buffer: &mut Vec<MutationsSplit>,
-        serialized: &SerializedData,
-        track_mutate_messages: bool,
-        userdata: &ReplicationUserdata,
-        server_tick_range: Range<usize>,
-        server_tick: RepliconTick,
-        system_tick: Tick,
-        timestamp: Duration,
+        info: MutateInfo,
+        base_header_size: usize,
         max_size: usize,
-    ) -> Result<usize> {
-        const MESSAGES_COUNT_MAX_SIZE: usize = usize::POSTCARD_MAX_SIZE;
-        let mut tick_buffer = [0; RepliconTick::POSTCARD_MAX_SIZE];
-        let update_tick = postcard::to_slice(&ticks.update_tick, &mut tick_buffer)?;
-        let mut base_header_size =
-            size_of::<MutateFlags>() + update_tick.len() + server_tick_range.len();
-        if !userdata.is_empty() {
-            base_header_size += serialized_size(&userdata.len())? + userdata.len();
-        }
-        if track_mutate_messages {
-            // We don't know the number of messages ahead of time, so we assume the maximum
-            // possible size during the splits calculation to avoid exceeding MTU.
-            base_header_size += MESSAGES_COUNT_MAX_SIZE;
+    ) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
         }
 
-        let mut mutate_info = MutateInfo {
-            server_tick,
-            system_tick,
-            timestamp,
-            entities: Default::default(),
-        };
+        let channel = info.channel;
+        let mut mutate_info = info;
         let mut mutate_index = ticks.next_mutate_index();
-        let mut chunks = EntityChunks::new(&mut self.related, &mut self.standalone);
+        let mut chunks = self.chunks();
         let mut header_size = base_header_size + serialized_size(&mutate_index)?;
         let mut body_size = 0;
         let mut chunks_range = Range::<usize>::default();
@@ -192,19 +395,21 @@ impl Mutations {
                 && !can_pack(header_size + mutations_size, body_size, max_size)
             {
                 split_buffer.push(MutationsSplit {
+                    channel,
                     mutate_index,
                     message_size: body_size + header_size,
                     chunks_range: chunks_range.clone(),
                 });
 
-                ticks.register_mutate_message(mutate_index, mutate_info);
+                let entities = mem::take(&mut mutate_info.entities);
+                ticks.register_mutate_message(
+                    mutate_index,
+                    MutateInfo {
+                        entities,
+                        ..mutate_info
+                    },
+                );
                 mutate_index = ticks.next_mutate_index();
-                mutate_info = MutateInfo {
-                    server_tick,
-                    system_tick,
-                    timestamp,
-                    entities: Default::default(),
-                };
                 chunks_range.start = chunks_range.end;
                 header_size = base_header_size + serialized_size(&mutate_index)?; // Recalculate since the mutate index changed.
                 body_size = 0;
@@ -220,80 +425,29 @@ impl Mutations {
             chunks_range.end += 1;
             body_size += mutations_size;
         }
-        if !chunks_range.is_empty() || track_mutate_messages {
-            // When the loop ends, pack all leftovers into a message.
-            // Or create an empty message if tracking mutate messages is enabled.
-            split_buffer.push(MutationsSplit {
-                mutate_index,
-                message_size: body_size + header_size,
-                chunks_range,
-            });
-            ticks.register_mutate_message(mutate_index, mutate_info);
-        }
-
-        if split_buffer.len() > 1 {
-            trace!(
-                "splitting into {} messages for client `{client}`",
-                split_buffer.len()
-            );
-        }
-
-        let mut base_flags = MutateFlags::default();
-        if track_mutate_messages {
-            base_flags |= MutateFlags::MESSAGES_COUNT;
-        }
-        if !userdata.is_empty() {
-            base_flags |= MutateFlags::USERDATA;
-        }
 
-        for split in &*split_buffer {
-            let mut message_size = split.message_size;
-            if track_mutate_messages {
-                // Update message counter size based on actual value.
-                message_size -= MESSAGES_COUNT_MAX_SIZE - serialized_size(&split_buffer.len())?;
-            }
-            let mut message = Vec::with_capacity(message_size);
+        // When the loop ends, pack all leftovers into a message.
+        split_buffer.push(MutationsSplit {
+            channel,
+            mutate_index,
+            message_size: body_size + header_size,
+            chunks_range,
+        });
+        ticks.register_mutate_message(mutate_index, mutate_info);
 
-            let flags = if split.chunks_range.is_empty() {
-                base_flags
-            } else {
-                base_flags | MutateFlags::MUTATIONS
-            };
-
-            postcard_utils::to_extend_mut(&flags, &mut message)?;
-            postcard_utils::to_extend_mut(&split.mutate_index, &mut message)?;
-            message.extend_from_slice(update_tick);
-            message.extend_from_slice(&serialized[server_tick_range.clone()]);
-            if !userdata.is_empty() {
-                postcard_utils::to_extend_mut(&userdata.len(), &mut message)?;
-                message.extend_from_slice(userdata);
-            }
-            if track_mutate_messages {
-                postcard_utils::to_extend_mut(&split_buffer.len(), &mut message)?;
-            }
-            for mutations in chunks.iter_flatten(split.chunks_range.clone()) {
-                message.extend_from_slice(&serialized[mutations.ranges.entity.clone()]);
-                postcard_utils::to_extend_mut(&mutations.ranges.data_size(), &mut message)?;
-                for component in &mutations.ranges.data {
-                    message.extend_from_slice(&serialized[component.clone()]);
-                }
-            }
-
-            debug_assert_eq!(message.len(), message_size);
-
-            messages.send(client, ServerChannel::Mutations, message);
-        }
+        Ok(())
+    }
 
-        let len = split_buffer.len();
-        split_buffer.clear();
-        Ok(len)
+    /// Treats related and standalone entity mutations as a single continuous buffer.
+    fn chunks(&mut self) -> EntityChunks<'_> {
+        EntityChunks::new(&mut self.related, &mut self.standalone)
     }
 
     /// Clears all entity mutations and updates size of [`Self::related`]
     /// to split related entities by graph index.
     ///
     /// Keeps allocated memory for reuse.
-    pub(crate) fn reset(&mut self, graphs_count: usize) {
+    fn reset(&mut self, graphs_count: usize) {
         let old_len = self.related.len();
         self.related.resize_with(graphs_count, Default::default);
         for entities in &mut self.related[..old_len.min(graphs_count)] {
@@ -303,7 +457,7 @@ impl Mutations {
     }
 }
 
-/// Mutations data for [`Mutations::related`] and [`Mutations::standalone`].
+/// Mutations data for [`ChannelMutations::related`] and [`ChannelMutations::standalone`].
 pub(crate) struct EntityMutations {
     /// Associated entity.
     ///
@@ -391,12 +545,23 @@ impl<'a> EntityChunks<'a> {
 ///
 /// We split mutations into messages first in order to know their count in advance.
 pub(crate) struct MutationsSplit {
+    channel: MutationChannel,
     mutate_index: MutateIndex,
     message_size: usize,
     /// Indices in [`EntityChunks`].
     chunks_range: Range<usize>,
 }
 
+impl MutationsSplit {
+    pub(crate) fn channel(&self) -> MutationChannel {
+        self.channel
+    }
+
+    pub(crate) fn mutate_index(&self) -> MutateIndex {
+        self.mutate_index
+    }
+}
+
 /// Returns `true` if the additional data fits within the remaining space
 /// of the current packet tail.
 ///
@@ -411,6 +576,7 @@ fn can_pack(message_size: usize, add: usize, mtu: usize) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::backend::channels::ServerChannel;
 
     const MAX_SIZE: usize = 1200;
 
@@ -499,12 +665,15 @@ mod tests {
                 &mut Default::default(),
                 &serialized,
                 track_mutate_messages,
+                [Some(ServerChannel::Mutations.into()), None],
+                Default::default(),
                 &Default::default(),
                 Default::default(),
                 Default::default(),
                 Default::default(),
                 Default::default(),
                 MAX_SIZE,
+                None,
             )
             .unwrap()
     }
@@ -525,6 +694,7 @@ mod tests {
 
         let entity_size = start + 4;
         mutations.start_entity();
+        let mutations = mutations.channel_mut(MutationChannel::Primary);
         mutations.add_entity(Entity::PLACEHOLDER, graph_index, start..entity_size);
         mutations.add_component(entity_size..serialized.len());
     }
@@ -5,7 +5,10 @@ use bevy::{prelude::*, ptr::Ptr};
 use crate::{
     postcard_utils,
     prelude::*,
-    shared::replication::registry::{FnsId, ctx::SerializeCtx, serde_fns::SerdeFns},
+    server::serialization_cache::SerializationCache,
+    shared::replication::registry::{
+        FnsId, ReplicationRegistry, ctx::SerializeCtx, removal_id::RemovalId, serde_fns::SerdeFns,
+    },
 };
 
 /// Single continuous buffer that stores serialized data for messages.
@@ -31,14 +34,17 @@ impl SerializedData {
         })
     }
 
+    /// If `cache` is set, serialized bytes are also reused across ticks.
     pub(crate) fn write_cached_component(
         &mut self,
         ctx: &mut SerializeCtx,
         cached_range: &mut Option<Range<usize>>,
         component: &mut ErasedComponent,
+        cache: Option<&mut SerializationCache>,
     ) -> Result<Range<usize>> {
-        self.write_cached(cached_range, |serialized| {
-            serialized.write_component(ctx, component)
+        self.write_cached(cached_range, |serialized| match cache {
+            Some(cache) => cache.write_component(serialized, ctx, component),
+            None => serialized.write_component(ctx, component),
         })
     }
 
@@ -50,12 +56,14 @@ impl SerializedData {
         self.write_cached(cached_range, |serialized| serialized.write_entity(entity))
     }
 
-    pub(crate) fn write_cached_fns_id(
+    pub(crate) fn write_cached_removal_id(
         &mut self,
         cached_range: &mut Option<Range<usize>>,
-        fns_id: FnsId,
+        removal_id: RemovalId,
     ) -> Result<Range<usize>> {
-        self.write_cached(cached_range, |serialized| serialized.write_fns_id(fns_id))
+        self.write_cached(cached_range, |serialized| {
+            serialized.write_removal_id(removal_id)
+        })
     }
 
     pub(crate) fn write_cached_tick(
@@ -85,17 +93,41 @@ impl SerializedData {
         Ok(range)
     }
 
+    /// Returns an empty range if sending was vetoed by
+    /// [`RuleFns::with_outbound`](crate::shared::replication::registry::rule_fns::RuleFns::with_outbound).
     pub(crate) fn write_component(
         &mut self,
         ctx: &mut SerializeCtx,
         component: &mut ErasedComponent,
     ) -> Result<Range<usize>> {
         self.write_with(|bytes| {
+            let id_start = bytes.len();
             postcard_utils::to_extend_mut(&component.fns_id, bytes)?;
 
+            // The size is known only after serialization,
+            // so reserve a fixed-width slot for it and fill it afterward.
+            let size_start = bytes.len();
+            if component.size_prefixed {
+                bytes.extend([0; size_of::<u32>()]);
+            }
+
+            let start = bytes.len();
             // SAFETY: `fns` and `ptr` were created for the same component type.
-            unsafe {
-                component.fns.serialize(ctx, component.ptr, bytes)?;
+            let written = unsafe { component.fns.serialize(ctx, component.ptr, bytes)? };
+            if !written {
+                bytes.truncate(id_start);
+                return Ok(());
+            }
+
+            if component.size_prefixed {
+                let size = u32::try_from(bytes.len() - start).map_err(|_| {
+                    format!(
+                        "size of `{:?}` data ({}) doesn't fit into the prefix",
+                        component.fns_id,
+                        bytes.len() - start
+                    )
+                })?;
+                bytes[size_start..start].copy_from_slice(&size.to_le_bytes());
             }
 
             Ok(())
@@ -117,9 +149,9 @@ impl SerializedData {
         })
     }
 
-    pub(crate) fn write_fns_id(&mut self, fns_id: FnsId) -> Result<Range<usize>> {
+    pub(crate) fn write_removal_id(&mut self, removal_id: RemovalId) -> Result<Range<usize>> {
         self.write_with(|bytes| {
-            postcard_utils::to_extend_mut(&fns_id, bytes)?;
+            postcard_utils::to_extend_mut(&removal_id, bytes)?;
             Ok(())
         })
     }
@@ -152,6 +184,7 @@ pub(crate) struct ErasedComponent<'a> {
     fns: SerdeFns<'a>,
     ptr: Ptr<'a>,
     fns_id: FnsId,
+    size_prefixed: bool,
 }
 
 impl<'a> ErasedComponent<'a> {
@@ -160,7 +193,21 @@ impl<'a> ErasedComponent<'a> {
     /// # Safety
     ///
     /// The caller must ensure that `fns` and `ptr` was created for the same type.
-    pub(crate) unsafe fn new(fns: SerdeFns<'a>, ptr: Ptr<'a>, fns_id: FnsId) -> Self {
-        Self { fns, ptr, fns_id }
+    pub(crate) unsafe fn new(
+        registry: &ReplicationRegistry,
+        fns: SerdeFns<'a>,
+        ptr: Ptr<'a>,
+        fns_id: FnsId,
+    ) -> Self {
+        Self {
+            fns,
+            ptr,
+            fns_id,
+            size_prefixed: registry.is_size_prefixed(fns_id),
+        }
+    }
+
+    pub(crate) fn fns_id(&self) -> FnsId {
+        self.fns_id
     }
 }
@@ -7,7 +7,7 @@ use super::{entity_ranges::EntityRanges, mutations::Mutations, serialized_data::
 use crate::{
     postcard_utils,
     prelude::*,
-    server::ReplicationUserdata,
+    server::{ReplicationUserdata, rule_stats::RuleStats},
     shared::{
         backend::channels::ServerChannel,
         replication::{
@@ -74,6 +74,9 @@ pub(crate) struct Updates {
     /// Indicates that an entity has been written since the
     /// last call of [`Self::start_entity_changes`].
     changed_entity_added: bool,
+
+    /// Indicates that the client should acknowledge the message.
+    ack: bool,
 }
 
 impl Updates {
@@ -120,19 +123,20 @@ impl Updates {
         self.removals.push(EntityRanges {
             entity,
             data: Default::default(),
+            rule_bytes: Default::default(),
         });
         self.removals_entity_added = true;
     }
 
     /// Adds a chunk with removal to the last added entity from [`Self::add_removals_entity`].
-    pub(crate) fn add_removal(&mut self, fns_id: Range<usize>) {
+    pub(crate) fn add_removal(&mut self, removal_id: Range<usize>) {
         debug_assert!(self.removals_entity_added);
         let removals = self
             .removals
             .last_mut()
             .expect("entity should be written before adding removals");
 
-        removals.add_data(fns_id);
+        removals.add_data(removal_id);
     }
 
     /// Updates internal state to start writing changed components for an entity.
@@ -158,6 +162,7 @@ impl Updates {
         self.changes.push(EntityRanges {
             entity,
             data: Default::default(),
+            rule_bytes: Default::default(),
         });
         self.changed_entity_added = true;
     }
@@ -178,17 +183,30 @@ impl Updates {
         self.changed_components.insert(index);
     }
 
+    /// Attributes the size of the next component from [`Self::add_inserted_component`] to a rule.
+    pub(crate) fn add_rule_bytes(&mut self, rule: usize, bytes: usize) {
+        let changes = self
+            .changes
+            .last_mut()
+            .expect("entity should be written before adding insertions");
+
+        changes.rule_bytes.push((rule, bytes));
+    }
+
     /// Takes last mutated entity with its component chunks from the mutate message.
+    ///
+    /// Merges chunks from all mutation channels.
     pub(crate) fn take_added_entity(&mut self, mutations: &mut Mutations) {
         debug_assert!(mutations.entity_added());
-        let entity_mutations = mutations.pop().expect("entity should be written");
-
-        if !self.changed_entity_added {
-            self.changes.push(entity_mutations.ranges);
-        } else {
-            let changes = self.changes.last_mut().expect("entity should be written");
-            debug_assert_eq!(entity_mutations.ranges.entity, changes.entity);
-            changes.extend(&entity_mutations.ranges);
+        while let Some(entity_mutations) = mutations.pop() {
+            if !self.changed_entity_added {
+                self.changes.push(entity_mutations.ranges);
+                self.changed_entity_added = true;
+            } else {
+                let changes = self.changes.last_mut().expect("entity should be written");
+                debug_assert_eq!(entity_mutations.ranges.entity, changes.entity);
+                changes.extend(&entity_mutations.ranges);
+            }
             self.changed_components |= &entity_mutations.components;
         }
     }
@@ -198,11 +216,19 @@ impl Updates {
         mem::take(&mut self.changed_components)
     }
 
+    /// Requests the client to acknowledge the message.
+    ///
+    /// See [`ClientChannel::UpdateAcks`](crate::shared::backend::channels::ClientChannel::UpdateAcks).
+    pub(crate) fn request_ack(&mut self) {
+        self.ack = true;
+    }
+
     pub(crate) fn is_empty(&self) -> bool {
         self.changes.is_empty()
             && self.despawns.is_empty()
             && self.removals.is_empty()
             && self.mappings.is_empty()
+            && !self.ack
     }
 
     /// Packs updates into a message.
@@ -217,7 +243,7 @@ impl Updates {
     /// To know how much data array takes, we serialize it's length. We use `usize`,
     /// but we use variable integer encoding, so they are correctly deserialized even
     /// on a client with a different pointer size. However, if the server sends a value
-    /// larger than what a client can fit into `usize` (which is very unlikely), the client will panic.
+    /// larger than what a client can fit into `usize` (which is very unlikely), the client will reject the message.
     /// This is expected, as the client can't have an array of such a size anyway.
     ///
     /// Additionally, we don't serialize the size for the last array and
@@ -229,6 +255,7 @@ impl Updates {
         serialized: &SerializedData,
         userdata: &ReplicationUserdata,
         server_tick_range: Range<usize>,
+        stats: Option<&mut RuleStats>,
     ) -> Result<()> {
         let flags = self.flags(userdata);
         let last_flag = flags.last();
@@ -237,6 +264,7 @@ impl Updates {
         let mut message_size = size_of::<UpdateFlags>() + server_tick_range.len();
         for (_, flag) in flags.iter_names() {
             match flag {
+                UpdateFlags::ACK => (),
                 UpdateFlags::USERDATA => {
                     message_size += serialized_size(&userdata.len())? + userdata.len();
                 }
@@ -279,6 +307,7 @@ impl Updates {
         message.extend_from_slice(&serialized[server_tick_range]);
         for (_, flag) in flags.iter_names() {
             match flag {
+                UpdateFlags::ACK => (),
                 UpdateFlags::USERDATA => {
                     postcard_utils::to_extend_mut(&userdata.len(), &mut message)?;
                     message.extend_from_slice(userdata);
@@ -306,8 +335,8 @@ impl Updates {
                     for removals in &self.removals {
                         message.extend_from_slice(&serialized[removals.entity.clone()]);
                         postcard_utils::to_extend_mut(&removals.data_size(), &mut message)?;
-                        for fns_id in &removals.data {
-                            message.extend_from_slice(&serialized[fns_id.clone()]);
+                        for removal_id in &removals.data {
+                            message.extend_from_slice(&serialized[removal_id.clone()]);
                         }
                     }
                 }
@@ -329,6 +358,15 @@ impl Updates {
 
         messages.send(client, ServerChannel::Updates, message);
 
+        if let Some(stats) = stats {
+            stats.start_message();
+            for changes in &self.changes {
+                for &(rule, bytes) in &changes.rule_bytes {
+                    stats.add(rule, bytes);
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -350,6 +388,9 @@ impl Updates {
         if !userdata.is_empty() {
             flags |= UpdateFlags::USERDATA;
         }
+        if self.ack {
+            flags |= UpdateFlags::ACK;
+        }
 
         flags
     }
@@ -364,5 +405,6 @@ impl Updates {
         self.despawns_len = 0;
         self.changes.clear();
         self.removals.clear();
+        self.ack = false;
     }
 }
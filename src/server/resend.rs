@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use log::{trace, warn};
+
+use crate::shared::replication::{client_ticks::ClientTicks, registry::ReplicationRegistry};
+
+/// Extension trait for [`EntityWorldMut`] to force-resend components to a client.
+///
+/// See also [`EntityCommandsResendExt`].
+pub trait EntityResendExt {
+    /**
+    Resends the current value of `C` on `entity` to this client on the next server tick,
+    even if it wasn't changed.
+
+    The component is sent as an insertion, so the client replaces its value entirely.
+    Useful when the client is known to have corrupted or intentionally discarded its state,
+    for example, after a client-side hot-reload of a subsystem.
+
+    Should be called on a client entity. Ignored if the entity wasn't replicated
+    to the client yet or if the client didn't receive `C` on it.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    fn resend_inventory(
+        mut commands: Commands,
+        clients: Query<Entity, With<AuthorizedClient>>,
+        inventories: Query<Entity, With<Inventory>>,
+    ) {
+        for client in &clients {
+            for inventory in &inventories {
+                commands.entity(client).resend::<Inventory>(inventory);
+            }
+        }
+    }
+
+    #[derive(Component, Serialize, Deserialize)]
+    struct Inventory(Vec<u32>);
+    ```
+    */
+    fn resend<C: Component>(&mut self, entity: Entity) -> &mut Self;
+
+    /// Resends all components with [`ReplicationMode::OnChange`](crate::shared::replication::rules::component::ReplicationMode::OnChange)
+    /// on all entities visible to this client on the next server tick, even if they weren't changed.
+    ///
+    /// Like [`Self::resend`], but for the whole replicated state. Components excluded with
+    /// [`AppRuleExt::without_full_refresh`](crate::shared::replication::rules::AppRuleExt::without_full_refresh)
+    /// are skipped.
+    ///
+    /// Should be called on a client entity.
+    ///
+    /// See also [`ServerPlugin::full_refresh_interval`](crate::server::ServerPlugin::full_refresh_interval).
+    fn resend_all(&mut self) -> &mut Self;
+}
+
+impl EntityResendExt for EntityWorldMut<'_> {
+    fn resend<C: Component>(&mut self, entity: Entity) -> &mut Self {
+        let client = self.id();
+        let Some(component_index) = self.world().component_id::<C>().and_then(|component_id| {
+            self.world()
+                .resource::<ReplicationRegistry>()
+                .component_index(component_id)
+        }) else {
+            warn!(
+                "ignoring resend of non-replicated `{}` on `{entity}`",
+                ShortName::of::<C>()
+            );
+            return self;
+        };
+
+        if let Some(mut ticks) = self.get_mut::<ClientTicks>() {
+            trace!(
+                "resending `{}` on `{entity}` to client `{client}`",
+                ShortName::of::<C>()
+            );
+            ticks.resend(entity, component_index);
+        }
+
+        self
+    }
+
+    fn resend_all(&mut self) -> &mut Self {
+        let client = self.id();
+        if let Some(mut ticks) = self.get_mut::<ClientTicks>() {
+            trace!("requesting full refresh for client `{client}`");
+            ticks.full_refresh = true;
+        }
+
+        self
+    }
+}
+
+/// Extension trait for [`EntityCommands`] to force-resend components to a client.
+///
+/// See also [`EntityResendExt`].
+pub trait EntityCommandsResendExt {
+    /// Queues a resend of `C` on `entity` to this client.
+    ///
+    /// See [`EntityResendExt::resend`] for details.
+    fn resend<C: Component>(&mut self, entity: Entity) -> &mut Self;
+
+    /// Queues a resend of all components to this client.
+    ///
+    /// See [`EntityResendExt::resend_all`] for details.
+    fn resend_all(&mut self) -> &mut Self;
+}
+
+impl EntityCommandsResendExt for EntityCommands<'_> {
+    fn resend<C: Component>(&mut self, entity: Entity) -> &mut Self {
+        self.queue(move |mut client: EntityWorldMut| {
+            client.resend::<C>(entity);
+        })
+    }
+    fn resend_all(&mut self) -> &mut Self {
+        self.queue(|mut client: EntityWorldMut| {
+            client.resend_all();
+        })
+    }
+}
@@ -0,0 +1,218 @@
+//! Attribution of sent replication data to replication rules.
+//!
+//! Useful to find out which components dominate bandwidth when it spikes.
+//! See [`RuleStatsPlugin`] for details.
+
+use alloc::{string::ToString, vec::Vec};
+use core::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use log::info;
+
+use super::ServerSystems;
+use crate::{prelude::*, shared::replication::rules::ReplicationRules};
+
+/// Adds [`RuleStats`] to all authorized clients.
+///
+/// Optionally logs the stats every [`Self::log_interval`].
+///
+/// # Examples
+///
+/// Log the stats every 10 seconds.
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use bevy::{prelude::*, state::app::StatesPlugin};
+/// use bevy_replicon::{prelude::*, server::rule_stats::RuleStatsPlugin};
+///
+/// # let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     StatesPlugin,
+///     RepliconPlugins,
+///     RuleStatsPlugin::default().with_log_interval(Duration::from_secs(10)),
+/// ));
+/// ```
+#[derive(Default)]
+pub struct RuleStatsPlugin {
+    /// How often to log the stats.
+    ///
+    /// Logging is disabled if set to [`None`].
+    pub log_interval: Option<Duration>,
+}
+
+impl RuleStatsPlugin {
+    /// Sets [`Self::log_interval`].
+    #[must_use]
+    pub fn with_log_interval(mut self, interval: Duration) -> Self {
+        self.log_interval = Some(interval);
+        self
+    }
+}
+
+impl Plugin for RuleStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_required_components::<AuthorizedClient, RuleStats>();
+
+        if let Some(interval) = self.log_interval {
+            app.add_systems(
+                PostUpdate,
+                log_stats
+                    .after(ServerSystems::Send)
+                    .run_if(on_timer(interval))
+                    .run_if(in_state(ServerState::Running)),
+            );
+        }
+    }
+}
+
+fn log_stats(world: &World, clients: Query<(Entity, &RuleStats)>) {
+    let rules = world.resource::<ReplicationRules>();
+    for (client, stats) in &clients {
+        info!(
+            "sent {} bytes for client `{client}` at `{:?}`",
+            stats.bytes(),
+            stats.tick,
+        );
+        for (index, stat) in stats.iter() {
+            let names: Vec<_> = rules[index]
+                .components
+                .iter()
+                .filter_map(|component| world.components().get_name(component.id))
+                .map(|name| name.shortname().to_string())
+                .collect();
+            info!(
+                "rule {index} {names:?}: {} bytes, {} components, {} messages",
+                stat.bytes, stat.components, stat.messages,
+            );
+        }
+    }
+}
+
+/**
+Bytes and messages sent to a client for each replication rule during the last server tick.
+
+Updated in [`ServerSystems::Send`] for clients that have this component.
+Can be inserted manually or automatically for all authorized clients using [`RuleStatsPlugin`].
+
+Rules are identified by their indices in [`ReplicationRules`]. Only component insertions
+and mutations are attributed, since other data, such as entity IDs and removals, can't be
+associated with a single rule. Each client is counted separately, even if the serialized data
+is shared between them.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::{rule_stats::RuleStats, server_tick::ServerTick},
+    shared::replication::rules::ReplicationRules,
+};
+
+fn find_heaviest(
+    rules: Res<ReplicationRules>,
+    clients: Query<(Entity, &RuleStats)>,
+) {
+    for (client, stats) in &clients {
+        if let Some((index, stat)) = stats.iter().max_by_key(|(_, stat)| stat.bytes) {
+            let rule = &rules[index];
+            info!(
+                "rule with {} components took {} bytes for `{client}`",
+                rule.components.len(),
+                stat.bytes,
+            );
+        }
+    }
+}
+
+# let mut app = App::new();
+app.add_systems(
+    PostUpdate,
+    find_heaviest
+        .after(ServerSystems::Send)
+        .run_if(resource_changed::<ServerTick>),
+);
+```
+*/
+#[derive(Component, Default, Debug, Clone)]
+pub struct RuleStats {
+    tick: RepliconTick,
+
+    /// Stats indexed by rule index.
+    rules: Vec<RuleStat>,
+
+    /// Incremented for each sent message to count messages for rules only once.
+    message: usize,
+}
+
+impl RuleStats {
+    /// Returns the server tick at which the stats were collected.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns stats for the rule with the given index.
+    ///
+    /// Returns [`None`] if nothing was sent for this rule.
+    pub fn get(&self, rule: usize) -> Option<&RuleStat> {
+        self.rules.get(rule).filter(|stat| stat.components != 0)
+    }
+
+    /// Returns an iterator over rule indices and their stats.
+    ///
+    /// Includes only rules for which something was sent.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &RuleStat)> {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|(_, stat)| stat.components != 0)
+    }
+
+    /// Returns the total number of attributed bytes.
+    pub fn bytes(&self) -> usize {
+        self.rules.iter().map(|stat| stat.bytes).sum()
+    }
+
+    pub(super) fn start(&mut self, tick: RepliconTick) {
+        self.tick = tick;
+        self.rules.clear();
+        self.message = 0;
+    }
+
+    /// Marks the start of a new message.
+    pub(super) fn start_message(&mut self) {
+        self.message += 1;
+    }
+
+    /// Attributes a component with the given size to the rule in the current message.
+    pub(super) fn add(&mut self, rule: usize, bytes: usize) {
+        if rule >= self.rules.len() {
+            self.rules.resize_with(rule + 1, Default::default);
+        }
+
+        let stat = &mut self.rules[rule];
+        stat.bytes += bytes;
+        stat.components += 1;
+        if stat.last_message != self.message {
+            stat.last_message = self.message;
+            stat.messages += 1;
+        }
+    }
+}
+
+/// Stats for a single rule inside [`RuleStats`].
+#[derive(Default, Debug, Clone, Copy)]
+pub struct RuleStat {
+    /// Number of serialized bytes of components.
+    pub bytes: usize,
+
+    /// Number of sent component insertions and mutations.
+    pub components: usize,
+
+    /// Number of messages that contain components from the rule.
+    pub messages: usize,
+
+    last_message: usize,
+}
@@ -0,0 +1,69 @@
+use core::ops::Range;
+
+use bevy::{
+    ecs::{change_detection::Tick, entity::hash_map::EntityHashMap},
+    prelude::*,
+};
+use log::trace;
+
+use super::replication_messages::serialized_data::{ErasedComponent, SerializedData};
+use crate::shared::replication::registry::{FnsId, ctx::SerializeCtx};
+
+/// Serialized components from previous ticks for rules with enabled cache.
+///
+/// [`SerializedData`] is cleared after each tick, so this cache allows skipping serialization
+/// for components that weren't changed since the last time they were serialized.
+///
+/// See [`AppRuleExt::with_serialization_cache`](crate::prelude::AppRuleExt::with_serialization_cache).
+#[derive(Resource, Deref, DerefMut, Default)]
+pub(super) struct SerializationCache(EntityHashMap<Vec<CachedComponent>>);
+
+impl SerializationCache {
+    /// Copies previously serialized bytes if the component wasn't changed since,
+    /// or writes the component and caches its bytes.
+    pub(super) fn write_component(
+        &mut self,
+        serialized: &mut SerializedData,
+        ctx: &mut SerializeCtx,
+        component: &mut ErasedComponent,
+    ) -> Result<Range<usize>> {
+        let components = self.0.entry(ctx.entity).or_default();
+        let index = components
+            .iter()
+            .position(|cached| cached.fns_id == component.fns_id());
+
+        if let Some(index) = index {
+            let cached = &components[index];
+            if cached.changed == ctx.last_changed {
+                trace!("reusing cached `{:?}` for `{}`", cached.fns_id, ctx.entity);
+                let start = serialized.len();
+                serialized.extend_from_slice(&cached.bytes);
+                return Ok(start..serialized.len());
+            }
+        }
+
+        let range = serialized.write_component(ctx, component)?;
+        let bytes = &serialized[range.clone()];
+        if let Some(index) = index {
+            let cached = &mut components[index];
+            cached.changed = ctx.last_changed;
+            cached.bytes.clear();
+            cached.bytes.extend_from_slice(bytes);
+        } else {
+            components.push(CachedComponent {
+                fns_id: component.fns_id(),
+                changed: ctx.last_changed,
+                bytes: bytes.to_vec(),
+            });
+        }
+
+        Ok(range)
+    }
+}
+
+/// Serialized component with the tick at which it was changed.
+pub(super) struct CachedComponent {
+    fns_id: FnsId,
+    changed: Tick,
+    bytes: Vec<u8>,
+}
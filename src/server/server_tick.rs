@@ -19,6 +19,7 @@ use crate::prelude::*;
 ///
 /// See [`ServerUpdateTick`](crate::client::ServerUpdateTick) for tracking the last received
 /// tick on clients.
+/// To convert ticks into time, use [`TickTimeline`].
 #[derive(Resource, Deref, Default, Serialize, Deserialize, Reflect, Debug, Clone, Copy)]
 pub struct ServerTick(RepliconTick);
 
@@ -0,0 +1,369 @@
+//! Periodic capturing of the replicated state on the server.
+//!
+//! Useful for killcam replays, server-side analytics or inspecting the state at specific ticks.
+//! See [`SnapshotPlugin`] for details.
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{any, marker::PhantomData, mem, ops::Range};
+
+use bevy::{
+    ecs::{archetype::Archetypes, query::QueryFilter},
+    prelude::*,
+};
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ServerSystems,
+    replicated_archetypes::ReplicatedArchetypes,
+    replication_messages::serialized_data::{ErasedComponent, SerializedData},
+    replication_query::ReplicationQuery,
+    server_tick::ServerTick,
+};
+use crate::{
+    prelude::*,
+    shared::replication::{
+        registry::{ReplicationRegistry, ctx::SerializeCtx},
+        rules::ReplicationRules,
+    },
+};
+
+/// Captures replicated entities matching `F` into [`SnapshotHistory<F>`] every
+/// [`Self::interval`] server ticks.
+///
+/// Only replicated components of entities with [`Replicated`] are captured.
+/// The capture happens in [`ServerSystems::Send`] when the state is [`ServerState::Running`].
+///
+/// Multiple plugins with different filters can be added.
+///
+/// # Examples
+///
+/// Capture all players every 30 ticks and keep the last 10 snapshots.
+///
+/// ```
+/// use bevy::{prelude::*, state::app::StatesPlugin};
+/// use bevy_replicon::{
+///     prelude::*,
+///     server::snapshot::{SnapshotHistory, SnapshotPlugin},
+/// };
+/// use serde::{Deserialize, Serialize};
+///
+/// # let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+///     .replicate::<Player>()
+///     .add_plugins(SnapshotPlugin::<With<Player>>::new(30).with_history_len(10))
+///     .add_systems(Update, inspect_snapshots);
+///
+/// fn inspect_snapshots(history: Res<SnapshotHistory<With<Player>>>) {
+///     if history.is_changed()
+///         && let Some(snapshot) = history.latest()
+///     {
+///         info!(
+///             "captured {} players at `{:?}`",
+///             snapshot.len(),
+///             snapshot.tick()
+///         );
+///     }
+/// }
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Player;
+/// ```
+pub struct SnapshotPlugin<F: QueryFilter = ()> {
+    /// How often to capture a snapshot in server ticks.
+    ///
+    /// Should be greater than 0.
+    pub interval: u32,
+
+    /// Maximum number of snapshots stored in [`SnapshotHistory<F>`].
+    ///
+    /// The oldest snapshot is removed when the limit is reached.
+    pub history_len: usize,
+
+    marker: PhantomData<fn() -> F>,
+}
+
+impl<F: QueryFilter> SnapshotPlugin<F> {
+    /// Creates a plugin with the given [`Self::interval`] that keeps only the latest snapshot.
+    pub fn new(interval: u32) -> Self {
+        Self {
+            interval,
+            history_len: 1,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets [`Self::history_len`].
+    #[must_use]
+    pub fn with_history_len(mut self, history_len: usize) -> Self {
+        self.history_len = history_len;
+        self
+    }
+}
+
+impl<F: QueryFilter + 'static> Plugin for SnapshotPlugin<F> {
+    fn build(&self, app: &mut App) {
+        assert!(self.interval > 0, "snapshot interval should be positive");
+        assert!(self.history_len > 0, "snapshot history shouldn't be empty");
+
+        debug!(
+            "capturing snapshots for `{}` every {} ticks",
+            any::type_name::<F>(),
+            self.interval
+        );
+
+        app.insert_resource(SnapshotHistory::<F> {
+            interval: self.interval,
+            max_len: self.history_len,
+            last_tick: None,
+            snapshots: Default::default(),
+            marker: PhantomData,
+        })
+        .add_systems(
+            PostUpdate,
+            capture::<F>
+                .run_if(resource_changed::<ServerTick>)
+                .in_set(ServerSystems::Send)
+                .run_if(in_state(ServerState::Running)),
+        )
+        .add_systems(OnExit(ServerState::Running), reset::<F>);
+    }
+}
+
+fn capture<F: QueryFilter + 'static>(
+    archetypes: &Archetypes,
+    query: ReplicationQuery,
+    filter: Query<(), (With<Replicated>, F)>,
+    server_tick: Res<ServerTick>,
+    registry: Res<ReplicationRegistry>,
+    type_registry: Res<AppTypeRegistry>,
+    rules: Res<ReplicationRules>,
+    mut replication_storage: ResMut<ReplicationStorage>,
+    mut replicated_archetypes: ResMut<ReplicatedArchetypes>,
+    mut history: ResMut<SnapshotHistory<F>>,
+) -> Result<()> {
+    if let Some(last_tick) = history.last_tick
+        && **server_tick - last_tick < history.interval
+    {
+        return Ok(());
+    }
+
+    replicated_archetypes.update(archetypes, &rules);
+
+    let mut serialized = SerializedData::default();
+    let mut entities = Vec::new();
+    for replicated_archetype in replicated_archetypes.iter() {
+        // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
+        let archetype = unsafe { archetypes.get(replicated_archetype.id).unwrap_unchecked() };
+
+        // Archetypal filters match either all entities of an archetype or none of them.
+        let archetype_entities = archetype.entities();
+        if F::IS_ARCHETYPAL
+            && archetype_entities
+                .first()
+                .is_none_or(|entity| !filter.contains(entity.id()))
+        {
+            continue;
+        }
+
+        for entity in archetype_entities
+            .iter()
+            .filter(|entity| F::IS_ARCHETYPAL || filter.contains(entity.id()))
+        {
+            let start = serialized.len();
+            for &(rule, storage, _) in &replicated_archetype.components {
+                let (_, component_id, fns) = registry.get(rule.fns_id);
+
+                // SAFETY: component and storage were obtained from this archetype.
+                let (ptr, ticks) = unsafe {
+                    query.get_component_unchecked(
+                        entity,
+                        archetype.table_id(),
+                        storage,
+                        component_id,
+                    )
+                };
+
+                // SAFETY: `fns` and `ptr` were created for the same component type.
+                let mut component =
+                    unsafe { ErasedComponent::new(&registry, fns, ptr, rule.fns_id) };
+
+                let mut ctx = SerializeCtx {
+                    entity: entity.id(),
+                    component_id,
+                    last_changed: ticks.changed,
+                    server_tick: **server_tick,
+                    diff_cursor: None,
+                    keyframe_interval: 0,
+                    insertion: true,
+                    type_registry: &type_registry,
+                    storage: &mut replication_storage,
+                };
+
+                trace!("capturing `{:?}` for `{}`", rule.fns_id, entity.id());
+                serialized.write_component(&mut ctx, &mut component)?;
+            }
+
+            entities.push((entity.id(), start..serialized.len()));
+        }
+    }
+
+    debug!(
+        "captured {} entities for `{}` at `{:?}`",
+        entities.len(),
+        any::type_name::<F>(),
+        **server_tick
+    );
+
+    history.last_tick = Some(**server_tick);
+    history.push(Snapshot {
+        tick: **server_tick,
+        entities,
+        data: mem::take(&mut *serialized),
+    });
+
+    Ok(())
+}
+
+fn reset<F: QueryFilter + 'static>(mut history: ResMut<SnapshotHistory<F>>) {
+    history.last_tick = None;
+    history.snapshots.clear();
+}
+
+/// Snapshots captured by [`SnapshotPlugin<F>`], from oldest to newest.
+///
+/// Cleared when the server stops.
+#[derive(Resource)]
+pub struct SnapshotHistory<F: QueryFilter = ()> {
+    interval: u32,
+    max_len: usize,
+    last_tick: Option<RepliconTick>,
+    snapshots: VecDeque<Snapshot>,
+    marker: PhantomData<fn() -> F>,
+}
+
+impl<F: QueryFilter> SnapshotHistory<F> {
+    /// Returns the most recent snapshot.
+    pub fn latest(&self) -> Option<&Snapshot> {
+        self.snapshots.back()
+    }
+
+    /// Returns a snapshot captured at the given tick.
+    pub fn get(&self, tick: RepliconTick) -> Option<&Snapshot> {
+        self.snapshots.iter().find(|snapshot| snapshot.tick == tick)
+    }
+
+    /// Returns an iterator over stored snapshots, from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Snapshot> {
+        self.snapshots.iter()
+    }
+
+    /// Removes all stored snapshots, returning them from oldest to newest.
+    ///
+    /// Useful to persist snapshots without keeping them in memory.
+    pub fn drain(&mut self) -> impl Iterator<Item = Snapshot> {
+        self.snapshots.drain(..)
+    }
+
+    /// Returns the number of stored snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns `true` if no snapshots were captured yet.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    fn push(&mut self, snapshot: Snapshot) {
+        if self.snapshots.len() == self.max_len {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+}
+
+/// Serialized state of entities at a specific server tick.
+///
+/// Components are serialized using the registered replication functions in the same format
+/// as in replication messages: [`FnsId`](crate::shared::replication::registry::FnsId) followed by the component data,
+/// which is prefixed with its size only for optional rules or with
+/// [`AppRuleExt::prefix_component_sizes`](crate::shared::replication::rules::AppRuleExt::prefix_component_sizes).
+/// Mutations are never serialized as diffs.
+///
+/// Can be serialized to persist it. Deserialization fails if entity data points outside
+/// the serialized components.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(try_from = "UncheckedSnapshot")]
+pub struct Snapshot {
+    tick: RepliconTick,
+    entities: Vec<(Entity, Range<usize>)>,
+    data: Vec<u8>,
+}
+
+impl Snapshot {
+    /// Returns the server tick at which the snapshot was captured.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns serialized components of an entity.
+    ///
+    /// Performs a linear search.
+    pub fn get(&self, entity: Entity) -> Option<&[u8]> {
+        self.entities
+            .iter()
+            .find(|&&(captured, _)| captured == entity)
+            .map(|(_, range)| &self.data[range.clone()])
+    }
+
+    /// Returns an iterator over captured entities and their serialized components.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &[u8])> {
+        self.entities
+            .iter()
+            .map(|(entity, range)| (*entity, &self.data[range.clone()]))
+    }
+
+    /// Returns the number of captured entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entities were captured.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns the size of all serialized components in bytes.
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// Deserialized [`Snapshot`] whose entity ranges weren't validated yet.
+#[derive(Deserialize)]
+struct UncheckedSnapshot {
+    tick: RepliconTick,
+    entities: Vec<(Entity, Range<usize>)>,
+    data: Vec<u8>,
+}
+
+impl TryFrom<UncheckedSnapshot> for Snapshot {
+    type Error = &'static str;
+
+    fn try_from(value: UncheckedSnapshot) -> Result<Self, Self::Error> {
+        if value
+            .entities
+            .iter()
+            .any(|(_, range)| range.start > range.end || range.end > value.data.len())
+        {
+            return Err("snapshot contains entity data outside the serialized components");
+        }
+
+        Ok(Self {
+            tick: value.tick,
+            entities: value.entities,
+            data: value.data,
+        })
+    }
+}
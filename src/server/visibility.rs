@@ -1,6 +1,10 @@
 pub mod client_visibility;
+pub mod debug;
 pub mod filters_mask;
+pub mod level;
+pub mod manual;
 pub mod registry;
+pub mod topic;
 
 use bevy::{ecs::entity_disabling::Disabled, prelude::*};
 use log::debug;
@@ -10,6 +14,8 @@ use crate::shared::replication::{
     visibility::{FilterScope, VisibilityFilter},
 };
 use client_visibility::ClientVisibility;
+use level::{LevelIndex, LevelTag};
+use manual::ManualVisibility;
 use registry::FilterRegistry;
 
 /// Remote visibility functions for [`App`].
@@ -85,6 +91,92 @@ pub trait AppVisibilityExt {
     ```
     */
     fn add_visibility_filter<F: VisibilityFilter>(&mut self) -> &mut Self;
+
+    /**
+    Enables partitioning of replicated entities by [`LevelTag`](level::LevelTag).
+
+    Intended for servers that run multiple levels in a single world. Each client receives
+    only entities from its assigned level, plus all entities without a tag.
+
+    Unlike a regular [`VisibilityFilter`], entities are indexed by their level in [`LevelIndex`].
+    So when a client switches levels, only entities from the previous and the new level are
+    evaluated instead of all entities with the filter. Entities from the previous level are
+    despawned on the client, and entities from the new level are spawned.
+
+    Occupies one visibility bit, see [`Self::add_visibility_filter`] for the limit.
+
+    # Panics
+
+    Panics if called more than once.
+
+    # Examples
+
+    ```
+    # use bevy::state::app::StatesPlugin;
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    # app.add_plugins((StatesPlugin, RepliconPlugins));
+    app.add_level_visibility()
+        .add_observer(spawn_player);
+
+    fn spawn_player(add: On<Add, AuthorizedClient>, mut commands: Commands) {
+        // Start in the lobby, the client will receive only entities from it.
+        commands.entity(add.entity).insert(LevelTag(0));
+    }
+
+    fn load_dungeon(mut commands: Commands) {
+        commands.spawn((Replicated, LevelTag(1), Transform::default()));
+    }
+    ```
+    */
+    fn add_level_visibility(&mut self) -> &mut Self;
+
+    /**
+    Enables imperative per-entity visibility with [`ClientVisibility::set_visible`].
+
+    Intended for code written before filters, when visibility was controlled by a blacklist
+    on each client. Entities are visible by default and can be hidden for specific clients.
+    Internally, it's a regular filter with [`ManualVisibility`] as the bit, so it can be combined
+    with other filters.
+
+    For new code, prefer [`VisibilityFilter`] components, which are evaluated automatically.
+
+    Occupies one visibility bit, see [`Self::add_visibility_filter`] for the limit.
+
+    # Panics
+
+    Panics if called more than once.
+
+    # Examples
+
+    ```
+    # use bevy::state::app::StatesPlugin;
+    use bevy::prelude::*;
+    use bevy_replicon::{prelude::*, server::visibility::client_visibility::ClientVisibility};
+
+    # let mut app = App::new();
+    # app.add_plugins((StatesPlugin, RepliconPlugins));
+    app.add_manual_visibility()
+        .add_systems(Update, update_stealth);
+
+    fn update_stealth(
+        mut clients: Query<&mut ClientVisibility, With<AuthorizedClient>>,
+        players: Query<(Entity, &Stealth), Changed<Stealth>>,
+    ) {
+        for mut visibility in &mut clients {
+            for (player, stealth) in &players {
+                visibility.set_visible(player, !**stealth);
+            }
+        }
+    }
+
+    #[derive(Component, Deref)]
+    struct Stealth(bool);
+    ```
+    */
+    fn add_manual_visibility(&mut self) -> &mut Self;
 }
 
 impl AppVisibilityExt for App {
@@ -104,6 +196,54 @@ impl AppVisibilityExt for App {
             .add_observer(on_remove::<F>)
             .add_observer(on_client_remove::<F>)
     }
+
+    fn add_level_visibility(&mut self) -> &mut Self {
+        debug!("adding level visibility");
+
+        if self.world().contains_resource::<LevelIndex>() {
+            panic!("level visibility can't be added more than once");
+        }
+
+        let bit =
+            self.world_mut()
+                .resource_scope(|world, mut filter_registry: Mut<FilterRegistry>| {
+                    world.resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                        filter_registry.register_named_scope::<Entity>(
+                            world,
+                            &mut registry,
+                            ShortName::of::<LevelTag>(),
+                        )
+                    })
+                });
+
+        self.insert_resource(LevelIndex::new(bit))
+            .add_observer(level::init_client)
+            .add_observer(level::on_insert)
+            .add_observer(level::on_replace)
+    }
+
+    fn add_manual_visibility(&mut self) -> &mut Self {
+        debug!("adding manual visibility");
+
+        if self.world().contains_resource::<ManualVisibility>() {
+            panic!("manual visibility can't be added more than once");
+        }
+
+        let bit =
+            self.world_mut()
+                .resource_scope(|world, mut filter_registry: Mut<FilterRegistry>| {
+                    world.resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                        filter_registry.register_named_scope::<Entity>(
+                            world,
+                            &mut registry,
+                            ShortName::of::<ManualVisibility>(),
+                        )
+                    })
+                });
+
+        self.insert_resource(ManualVisibility::new(bit))
+            .add_observer(manual::init_client)
+    }
 }
 
 fn update_for_new_clients<F: VisibilityFilter>(
@@ -218,9 +358,13 @@ fn on_client_remove<F: VisibilityFilter>(
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
+
+    use bevy::ecs::system::SystemState;
     use test_log::test;
 
-    use super::*;
+    use super::{debug::VisibilityDebug, *};
+    use crate::prelude::*;
 
     #[test]
     fn after_clients() {
@@ -394,6 +538,155 @@ mod tests {
         assert!(!visibility2.get(entity).is_hidden(registry));
     }
 
+    #[test]
+    fn levels() {
+        let mut app = App::new();
+        app.init_resource::<FilterRegistry>()
+            .init_resource::<ReplicationRegistry>()
+            .add_level_visibility();
+
+        let entity1 = app.world_mut().spawn(LevelTag(0)).id();
+        let client1 = app
+            .world_mut()
+            .spawn((
+                ConnectedClient { max_size: 1200 },
+                ClientVisibility::default(),
+                LevelTag(0),
+            ))
+            .id();
+        let client2 = app
+            .world_mut()
+            .spawn((
+                ConnectedClient { max_size: 1200 },
+                ClientVisibility::default(),
+            ))
+            .id();
+        let entity2 = app.world_mut().spawn(LevelTag(1)).id();
+        let entity3 = app.world_mut().spawn_empty().id();
+
+        let registry = app.world().resource::<FilterRegistry>();
+        let visibility1 = app.world().get::<ClientVisibility>(client1).unwrap();
+        assert!(!visibility1.get(entity1).is_hidden(registry));
+        assert!(visibility1.get(entity2).is_hidden(registry));
+        assert!(!visibility1.get(entity3).is_hidden(registry));
+
+        let visibility2 = app.world().get::<ClientVisibility>(client2).unwrap();
+        assert!(visibility2.get(entity1).is_hidden(registry));
+        assert!(visibility2.get(entity2).is_hidden(registry));
+        assert!(!visibility2.get(entity3).is_hidden(registry));
+
+        // Move the first client to another level.
+        app.world_mut().entity_mut(client1).insert(LevelTag(1));
+
+        let registry = app.world().resource::<FilterRegistry>();
+        let visibility1 = app.world().get::<ClientVisibility>(client1).unwrap();
+        assert!(visibility1.get(entity1).is_hidden(registry));
+        assert!(!visibility1.get(entity2).is_hidden(registry));
+        assert!(!visibility1.get(entity3).is_hidden(registry));
+
+        // Move entities between levels.
+        app.world_mut().entity_mut(entity1).insert(LevelTag(1));
+        app.world_mut().entity_mut(entity2).remove::<LevelTag>();
+        app.world_mut().entity_mut(entity3).insert(LevelTag(0));
+
+        let registry = app.world().resource::<FilterRegistry>();
+        let visibility1 = app.world().get::<ClientVisibility>(client1).unwrap();
+        assert!(!visibility1.get(entity1).is_hidden(registry));
+        assert!(!visibility1.get(entity2).is_hidden(registry));
+        assert!(visibility1.get(entity3).is_hidden(registry));
+
+        let visibility2 = app.world().get::<ClientVisibility>(client2).unwrap();
+        assert!(visibility2.get(entity1).is_hidden(registry));
+        assert!(!visibility2.get(entity2).is_hidden(registry));
+        assert!(visibility2.get(entity3).is_hidden(registry));
+
+        let index = app.world().resource::<LevelIndex>();
+        assert_eq!(index.entities(LevelTag(0)).collect::<Vec<_>>(), [entity3]);
+        assert_eq!(index.entities(LevelTag(1)).collect::<Vec<_>>(), [entity1]);
+    }
+
+    #[test]
+    fn manual() {
+        let mut app = App::new();
+        app.init_resource::<FilterRegistry>()
+            .init_resource::<ReplicationRegistry>()
+            .add_manual_visibility();
+
+        let client = app.world_mut().spawn(ClientVisibility::default()).id();
+        let entity = app.world_mut().spawn_empty().id();
+
+        let registry = app.world().resource::<FilterRegistry>();
+        let visibility = app.world().get::<ClientVisibility>(client).unwrap();
+        assert!(!visibility.get(entity).is_hidden(registry));
+
+        app.world_mut()
+            .get_mut::<ClientVisibility>(client)
+            .unwrap()
+            .set_visible(entity, false);
+
+        let registry = app.world().resource::<FilterRegistry>();
+        let visibility = app.world().get::<ClientVisibility>(client).unwrap();
+        assert!(visibility.get(entity).is_hidden(registry));
+
+        app.world_mut()
+            .get_mut::<ClientVisibility>(client)
+            .unwrap()
+            .set_visible(entity, true);
+
+        let registry = app.world().resource::<FilterRegistry>();
+        let visibility = app.world().get::<ClientVisibility>(client).unwrap();
+        assert!(!visibility.get(entity).is_hidden(registry));
+    }
+
+    #[test]
+    #[should_panic]
+    fn manual_disabled() {
+        let mut visibility = ClientVisibility::default();
+        visibility.set_visible(Entity::PLACEHOLDER, false);
+    }
+
+    #[test]
+    fn debug() {
+        let mut app = App::new();
+        app.init_resource::<FilterRegistry>()
+            .init_resource::<ReplicationRegistry>()
+            .add_visibility_filter::<SelfFilter>()
+            .add_visibility_filter::<EntityFilter>();
+
+        let client = app
+            .world_mut()
+            .spawn((ClientVisibility::default(), SelfFilter))
+            .id();
+        let visible = app.world_mut().spawn((Replicated, SelfFilter)).id();
+        let hidden = app
+            .world_mut()
+            .spawn((Replicated, SelfFilter, EntityFilter))
+            .id();
+        app.world_mut().spawn(EntityFilter); // Not replicated.
+
+        let mut state = SystemState::<VisibilityDebug>::new(app.world_mut());
+        let visibility_debug = state.get(app.world());
+        let mut infos: Vec<_> = visibility_debug.for_client(client).unwrap().collect();
+        infos.sort_by_key(|info| info.entity());
+        assert_eq!(infos.len(), 2);
+
+        let visible_info = infos[0];
+        assert_eq!(visible_info.entity(), visible);
+        assert!(visible_info.is_visible());
+        assert_eq!(visible_info.hidden_by().count(), 0);
+
+        let hidden_info = infos[1];
+        assert_eq!(hidden_info.entity(), hidden);
+        assert!(!hidden_info.is_visible());
+        let names: Vec<_> = hidden_info
+            .hidden_by()
+            .map(|filter| filter.name.to_string())
+            .collect();
+        assert_eq!(names, ["EntityFilter"]);
+
+        assert!(visibility_debug.for_client(visible).is_err());
+    }
+
     #[derive(Component)]
     #[component(immutable)]
     struct SelfFilter;
@@ -1,6 +1,9 @@
+use alloc::vec::Vec;
+
 use bevy::{ecs::entity::EntityHashMap, platform::collections::hash_map::Entry, prelude::*};
 
 use super::filters_mask::{FilterBit, FiltersMask};
+use crate::server::WriteOrderMap;
 
 /// Visibility as masks for a client.
 ///
@@ -20,7 +23,11 @@ pub struct ClientVisibility {
     ///
     /// Stored redundantly to quickly iterate only over entities
     /// with newly hidden data.
-    lost: EntityHashMap<FiltersMask>,
+    lost: WriteOrderMap<FiltersMask>,
+
+    /// Bit from [`ManualVisibility`](super::manual::ManualVisibility) if it's enabled.
+    #[reflect(ignore)]
+    pub(super) manual_bit: Option<FilterBit>,
 }
 
 impl ClientVisibility {
@@ -30,10 +37,17 @@ impl ClientVisibility {
     }
 
     /// Clears all entities that lost any visibility during this tick, returning them as an iterator.
+    #[cfg(not(feature = "deterministic"))]
     pub(crate) fn drain_lost(&mut self) -> impl Iterator<Item = (Entity, FiltersMask)> {
         self.lost.drain()
     }
 
+    /// Clears all entities that lost any visibility during this tick, returning them as an iterator.
+    #[cfg(feature = "deterministic")]
+    pub(crate) fn drain_lost(&mut self) -> impl Iterator<Item = (Entity, FiltersMask)> {
+        core::mem::take(&mut self.lost).into_iter()
+    }
+
     /// Removes a despawned entity tracked by this client.
     ///
     /// Since observers can't be ordered, we can't distinguish between
@@ -152,10 +166,10 @@ impl ClientVisibility {
                     mask.remove();
                 }
 
-                if let Entry::Occupied(mut lost_mask) = self.lost.entry(entity) {
-                    lost_mask.get_mut().remove(bit);
-                    if lost_mask.get().is_empty() {
-                        lost_mask.remove();
+                if let Some(lost_mask) = self.lost.get_mut(&entity) {
+                    lost_mask.remove(bit);
+                    if lost_mask.is_empty() {
+                        self.lost.remove(&entity);
                     }
                 }
             }
@@ -168,10 +182,64 @@ impl ClientVisibility {
         }
     }
 
+    /// Shows or hides an entity for the client.
+    ///
+    /// Entities are visible by default, so hidden entities need to be explicitly shown again.
+    /// Other filters still apply, so the entity is replicated only if they allow it too.
+    ///
+    /// Works only after [`AppVisibilityExt::add_manual_visibility`](super::AppVisibilityExt::add_manual_visibility),
+    /// see it for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if manual visibility isn't enabled.
+    pub fn set_visible(&mut self, entity: Entity, visible: bool) {
+        let bit = self
+            .manual_bit
+            .expect("manual visibility should be enabled with `add_manual_visibility`");
+        self.set(entity, bit, visible);
+    }
+
     /// Returns bits for all filters that affect visibility of the given entity.
     pub(crate) fn get(&self, entity: Entity) -> FiltersMask {
         self.hidden.get(&entity).copied().unwrap_or_default()
     }
+
+    /// Replaces visibility with data that is hidden in all given views.
+    ///
+    /// Used for clients with [`ClientViews`](crate::server::client_views::ClientViews).
+    pub(crate) fn merge(&mut self, views: &[&ClientVisibility]) {
+        let Some((first, others)) = views.split_first() else {
+            return;
+        };
+
+        // Only entities hidden for the client or in all views can change their masks.
+        let entities: Vec<_> = self
+            .hidden
+            .keys()
+            .chain(first.hidden.keys())
+            .copied()
+            .collect();
+        for entity in entities {
+            let mask = others.iter().fold(first.get(entity), |mask, view| {
+                mask.intersection(view.get(entity))
+            });
+            let current = self.get(entity);
+            for bit in current.iter().filter(|&bit| !mask.contains(bit)) {
+                self.set(entity, bit, true);
+            }
+            for bit in mask.iter().filter(|&bit| !current.contains(bit)) {
+                self.set(entity, bit, false);
+            }
+        }
+    }
+
+    /// Clears all entities that lost any visibility during this tick.
+    ///
+    /// Used for views since they aren't drained during replication.
+    pub(crate) fn clear_lost(&mut self) {
+        self.lost.clear();
+    }
 }
 
 #[cfg(test)]
@@ -209,6 +277,29 @@ mod tests {
         assert!(!visibility.lost.contains_key(&Entity::PLACEHOLDER));
     }
 
+    #[test]
+    fn merge() {
+        let entity1 = Entity::from_raw_u32(1).unwrap();
+        let entity2 = Entity::from_raw_u32(2).unwrap();
+
+        let mut view1 = ClientVisibility::default();
+        view1.set(entity1, FilterBit::new(0), false);
+        view1.set(entity2, FilterBit::new(0), false);
+        view1.set(entity2, FilterBit::new(1), false);
+
+        let mut view2 = ClientVisibility::default();
+        view2.set(entity2, FilterBit::new(1), false);
+
+        let mut visibility = ClientVisibility::default();
+        visibility.set(entity1, FilterBit::new(2), false);
+        visibility.merge(&[&view1, &view2]);
+        assert!(visibility.get(entity1).is_empty());
+        assert!(!visibility.lost.contains_key(&entity1));
+        assert!(!visibility.get(entity2).contains(FilterBit::new(0)));
+        assert!(visibility.get(entity2).contains(FilterBit::new(1)));
+        assert!(visibility.lost.contains_key(&entity2));
+    }
+
     #[test]
     fn already_visible() {
         let mut visibility = ClientVisibility::default();
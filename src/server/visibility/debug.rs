@@ -0,0 +1,114 @@
+use bevy::{
+    ecs::{query::QueryEntityError, system::SystemParam},
+    prelude::*,
+};
+
+use super::{
+    client_visibility::ClientVisibility,
+    filters_mask::{FilterBit, FiltersMask},
+    registry::FilterRegistry,
+};
+use crate::shared::replication::visibility::VisibilityScope;
+
+/**
+Provides visibility of replicated entities for clients.
+
+Intended for debugging interest management, for example, to draw an overlay
+with entities that are hidden from a specific client.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::visibility::debug::VisibilityDebug};
+
+fn log_hidden(visibility_debug: VisibilityDebug, clients: Query<Entity, With<AuthorizedClient>>) {
+    for client in &clients {
+        let Ok(entities) = visibility_debug.for_client(client) else {
+            continue;
+        };
+
+        for info in entities.filter(|info| !info.is_visible()) {
+            let names: Vec<_> = info.hidden_by().map(|filter| filter.name).collect();
+            info!("`{}` is hidden from `{client}` by {names:?}", info.entity());
+        }
+    }
+}
+```
+*/
+#[derive(SystemParam)]
+pub struct VisibilityDebug<'w, 's> {
+    registry: Res<'w, FilterRegistry>,
+    clients: Query<'w, 's, &'static ClientVisibility>,
+    entities: Query<'w, 's, Entity, With<Replicated>>,
+}
+
+impl VisibilityDebug<'_, '_> {
+    /// Returns visibility of every replicated entity for the given client.
+    ///
+    /// Returns an error if the entity is not an authorized client.
+    pub fn for_client(
+        &self,
+        client: Entity,
+    ) -> Result<impl Iterator<Item = VisibilityInfo<'_>>, QueryEntityError> {
+        let visibility = self.clients.get(client)?;
+        let iter = self.entities.iter().map(move |entity| VisibilityInfo {
+            entity,
+            mask: visibility.get(entity),
+            registry: &self.registry,
+        });
+
+        Ok(iter)
+    }
+}
+
+/// Visibility of a replicated entity for a client.
+///
+/// See [`VisibilityDebug::for_client`].
+#[derive(Clone, Copy)]
+pub struct VisibilityInfo<'a> {
+    entity: Entity,
+    mask: FiltersMask,
+    registry: &'a FilterRegistry,
+}
+
+impl<'a> VisibilityInfo<'a> {
+    /// Returns the replicated entity.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns `true` if the entity is replicated to the client.
+    ///
+    /// Some of its components could still be hidden, see [`Self::hidden_by`].
+    pub fn is_visible(&self) -> bool {
+        !self.mask.is_hidden(self.registry)
+    }
+
+    /// Returns filters that hide the entity or some of its components.
+    ///
+    /// Empty if nothing is hidden.
+    pub fn hidden_by(&self) -> impl Iterator<Item = HidingFilter<'a>> + use<'a> {
+        let registry = self.registry;
+        self.mask.iter().map(|bit| HidingFilter {
+            bit,
+            name: registry.name(bit),
+            scope: registry.scope(bit),
+        })
+    }
+}
+
+/// Filter that hides data of an entity for a client.
+///
+/// See [`VisibilityInfo::hidden_by`].
+#[derive(Clone, Copy)]
+pub struct HidingFilter<'a> {
+    /// Bit of the filter.
+    pub bit: FilterBit,
+
+    /// Name of the filter, see [`FilterRegistry::name`].
+    pub name: ShortName<'static>,
+
+    /// Data that the filter hides.
+    pub scope: &'a VisibilityScope,
+}
@@ -28,8 +28,13 @@ impl FiltersMask {
         self.0 == 0
     }
 
+    /// Returns bits that are set in both masks.
+    pub(super) fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
     /// Returns an iterator over all set bits, in ascending bit order.
-    fn iter(self) -> impl Iterator<Item = FilterBit> {
+    pub(super) fn iter(self) -> impl Iterator<Item = FilterBit> {
         let mut mask = self.0;
         iter::from_fn(move || {
             if mask == 0 {
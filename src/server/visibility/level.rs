@@ -0,0 +1,143 @@
+use bevy::{
+    ecs::{entity::EntityHashSet, entity_disabling::Disabled},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use log::debug;
+
+use super::{client_visibility::ClientVisibility, filters_mask::FilterBit};
+use crate::{prelude::*, server::client_views::ViewOf};
+
+/// Assigns an entity or a client to a level.
+///
+/// Works only after [`AppVisibilityExt::add_level_visibility`](super::AppVisibilityExt::add_level_visibility).
+///
+/// When inserted on a replicated entity, the entity will be visible only to clients with the same
+/// tag. Entities without this component are visible to all clients.
+///
+/// When inserted on a client entity, the client will receive entities from the given level.
+/// Clients without this component receive only entities without a level. The same applies
+/// to views from [`ViewOf`].
+///
+/// Re-inserting the component with a different value moves the entity or the client to another level.
+/// For clients, all entities from the previous level are despawned and entities from the new level
+/// are spawned.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, Deref)]
+#[component(immutable)]
+pub struct LevelTag(pub u32);
+
+/// Index of entities with [`LevelTag`] on the server.
+///
+/// Used to update visibility only for entities from affected levels when a client
+/// switches levels instead of evaluating all entities.
+///
+/// Inserted by [`AppVisibilityExt::add_level_visibility`](super::AppVisibilityExt::add_level_visibility).
+#[derive(Resource)]
+pub struct LevelIndex {
+    bit: FilterBit,
+    levels: HashMap<LevelTag, EntityHashSet>,
+}
+
+impl LevelIndex {
+    pub(super) fn new(bit: FilterBit) -> Self {
+        Self {
+            bit,
+            levels: Default::default(),
+        }
+    }
+
+    /// Returns all entities assigned to the given level.
+    pub fn entities(&self, level: LevelTag) -> impl Iterator<Item = Entity> + '_ {
+        self.levels.get(&level).into_iter().flatten().copied()
+    }
+}
+
+pub(super) fn on_insert(
+    insert: On<Insert, LevelTag>,
+    mut index: ResMut<LevelIndex>,
+    levels: Query<&LevelTag, Allow<Disabled>>,
+    viewers: Query<(), Or<(With<ConnectedClient>, With<ViewOf>)>>,
+    mut clients: Query<(Entity, Option<&LevelTag>, &mut ClientVisibility)>,
+) {
+    let level = *levels.get(insert.entity).unwrap();
+    let bit = index.bit;
+    if viewers.contains(insert.entity) {
+        let Ok((_, _, mut visibility)) = clients.get_mut(insert.entity) else {
+            // Will be evaluated on authorization.
+            return;
+        };
+
+        debug!("moving client `{}` to `{level:?}`", insert.entity);
+        for &entity in index.levels.get(&level).into_iter().flatten() {
+            visibility.set(entity, bit, true);
+        }
+    } else {
+        debug!("adding `{}` to `{level:?}`", insert.entity);
+        index.levels.entry(level).or_default().insert(insert.entity);
+        for (client, client_level, mut visibility) in &mut clients {
+            let visible = client_level == Some(&level);
+            debug!(
+                "evaluating `{level:?}` of entity `{}` for client `{client}` to `{visible}`",
+                insert.entity
+            );
+            visibility.set(insert.entity, bit, visible);
+        }
+    }
+}
+
+pub(super) fn on_replace(
+    replace: On<Replace, LevelTag>,
+    mut index: ResMut<LevelIndex>,
+    levels: Query<&LevelTag, Allow<Disabled>>,
+    viewers: Query<(), Or<(With<ConnectedClient>, With<ViewOf>)>>,
+    mut clients: Query<&mut ClientVisibility>,
+) {
+    let level = *levels.get(replace.entity).unwrap();
+    let bit = index.bit;
+    if viewers.contains(replace.entity) {
+        let Ok(mut visibility) = clients.get_mut(replace.entity) else {
+            return;
+        };
+
+        debug!("removing client `{}` from `{level:?}`", replace.entity);
+        for &entity in index.levels.get(&level).into_iter().flatten() {
+            visibility.set(entity, bit, false);
+        }
+    } else {
+        debug!("removing `{}` from `{level:?}`", replace.entity);
+        if let Some(entities) = index.levels.get_mut(&level) {
+            entities.remove(&replace.entity);
+            if entities.is_empty() {
+                index.levels.remove(&level);
+            }
+        }
+
+        // Will be hidden again if the entity is moved to another level.
+        for mut visibility in &mut clients {
+            visibility.set(replace.entity, bit, true);
+        }
+    }
+}
+
+pub(super) fn init_client(
+    insert: On<Insert, ClientVisibility>,
+    index: Res<LevelIndex>,
+    mut clients: Query<(Option<&LevelTag>, &mut ClientVisibility)>,
+) {
+    let Ok((client_level, mut visibility)) = clients.get_mut(insert.entity) else {
+        return;
+    };
+
+    for (level, entities) in &index.levels {
+        let visible = client_level == Some(level);
+        debug!(
+            "evaluating `{level:?}` for new client `{}` to `{visible}`",
+            insert.entity
+        );
+        if !visible {
+            for &entity in entities {
+                visibility.set(entity, index.bit, false);
+            }
+        }
+    }
+}
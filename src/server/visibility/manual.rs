@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use super::{client_visibility::ClientVisibility, filters_mask::FilterBit};
+
+/// Filter bit for visibility controlled by [`ClientVisibility::set_visible`].
+///
+/// Inserted by [`AppVisibilityExt::add_manual_visibility`](super::AppVisibilityExt::add_manual_visibility).
+#[derive(Resource, Deref, Debug, Clone, Copy)]
+pub struct ManualVisibility(FilterBit);
+
+impl ManualVisibility {
+    pub(super) fn new(bit: FilterBit) -> Self {
+        Self(bit)
+    }
+}
+
+pub(super) fn init_client(
+    insert: On<Insert, ClientVisibility>,
+    manual: Res<ManualVisibility>,
+    mut clients: Query<&mut ClientVisibility>,
+) {
+    if let Ok(mut visibility) = clients.get_mut(insert.entity) {
+        visibility.manual_bit = Some(**manual);
+    }
+}
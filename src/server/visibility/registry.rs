@@ -20,6 +20,7 @@ use crate::{
 pub struct FilterRegistry {
     bits: TypeIdMap<FilterBit>,
     scopes: Vec<VisibilityScope>,
+    names: Vec<ShortName<'static>>,
 }
 
 impl FilterRegistry {
@@ -28,7 +29,7 @@ impl FilterRegistry {
         world: &mut World,
         registry: &mut ReplicationRegistry,
     ) {
-        let bit = self.register_scope::<F::Scope>(world, registry);
+        let bit = self.register_named_scope::<F::Scope>(world, registry, ShortName::of::<F>());
         if self.bits.insert_type::<F>(bit).is_some() {
             panic!(
                 "`{}` can't be registered more than once",
@@ -50,6 +51,16 @@ impl FilterRegistry {
         &mut self,
         world: &mut World,
         registry: &mut ReplicationRegistry,
+    ) -> FilterBit {
+        self.register_named_scope::<S>(world, registry, ShortName::of::<S>())
+    }
+
+    /// Like [`Self::register_scope`], but uses a custom name for [`Self::name`].
+    pub(crate) fn register_named_scope<S: FilterScope>(
+        &mut self,
+        world: &mut World,
+        registry: &mut ReplicationRegistry,
+        name: ShortName<'static>,
     ) -> FilterBit {
         if self.scopes.len() >= u32::BITS as usize {
             panic!("number of visibility scopes can't exceed {}", u32::BITS);
@@ -58,6 +69,7 @@ impl FilterRegistry {
         let bit = FilterBit::new(self.scopes.len() as u8);
         let scope = S::visibility_scope(world, registry);
         self.scopes.push(scope);
+        self.names.push(name);
         bit
     }
 
@@ -75,6 +87,17 @@ impl FilterRegistry {
             .get(*bit as usize)
             .unwrap_or_else(|| panic!("scope for `{bit:?}` should've been registered"))
     }
+
+    /// Returns the name of the filter that occupies the bit.
+    ///
+    /// For filters registered via [`AppVisibilityExt`](super::AppVisibilityExt), this is the filter type.
+    /// For bits registered via [`Self::register_scope`], this is the scope type.
+    pub fn name(&self, bit: FilterBit) -> ShortName<'static> {
+        *self
+            .names
+            .get(*bit as usize)
+            .unwrap_or_else(|| panic!("name for `{bit:?}` should've been registered"))
+    }
 }
 
 #[cfg(test)]
@@ -110,6 +133,18 @@ mod tests {
             filter_registry.scope(multi_component_bit),
             VisibilityScope::Components(_)
         ));
+
+        assert_eq!(
+            filter_registry.name(entity_bit).to_string(),
+            "EntityVisibility"
+        );
+        assert_eq!(
+            filter_registry.name(component_bit).to_string(),
+            "ComponentVisibility"
+        );
+
+        let scope_bit = filter_registry.register_scope::<(A, B)>(&mut world, &mut registry);
+        assert_eq!(filter_registry.name(scope_bit).to_string(), "(A, B)");
     }
 
     #[test]
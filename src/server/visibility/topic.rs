@@ -0,0 +1,252 @@
+use alloc::{borrow::Cow, vec, vec::Vec};
+
+use bevy::{
+    ecs::{entity::EntityHashSet, entity_disabling::Disabled},
+    prelude::*,
+};
+use log::{debug, warn};
+
+use super::{client_visibility::ClientVisibility, filters_mask::FilterBit};
+use crate::{
+    prelude::*,
+    server::client_views::{ClientViews, ViewOf},
+    shared::replication::topic::{Subscribe, TopicRegistry, Unsubscribe},
+};
+
+/// Topics of a replicated entity.
+///
+/// Works only for topics registered via [`AppTopicExt`].
+///
+/// The entity's scope of each topic is visible only to clients subscribed to it.
+/// Re-inserting the component with different topics moves the entity between topics.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+#[component(immutable)]
+pub struct InTopics(Vec<&'static str>);
+
+impl InTopics {
+    /// Creates a new instance with the given topic names.
+    pub fn new(topics: impl IntoIterator<Item = &'static str>) -> Self {
+        Self(topics.into_iter().collect())
+    }
+
+    /// Returns an iterator over topic names.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+/// Topics to which a client is subscribed.
+///
+/// Inserted on the client entity after the first accepted [`Subscribe`].
+/// Also applies to views of the client from [`ViewOf`].
+#[derive(Component, Default, Debug, Clone)]
+pub struct Subscriptions(Vec<&'static str>);
+
+impl Subscriptions {
+    /// Returns `true` if the client is subscribed to the topic.
+    pub fn contains(&self, topic: &str) -> bool {
+        self.0.contains(&topic)
+    }
+
+    /// Returns an iterator over topic names.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+/// Index of entities with [`InTopics`] on the server.
+///
+/// Used to update visibility only for entities from the affected topic when a client
+/// subscribes or unsubscribes instead of evaluating all entities.
+///
+/// Initialized by [`AppTopicExt`].
+#[derive(Resource, Default)]
+pub struct TopicIndex {
+    /// Topics in the registration order, same as in [`TopicRegistry`].
+    topics: Vec<TopicEntities>,
+}
+
+impl TopicIndex {
+    pub(crate) fn register(&mut self, bit: FilterBit, validate: fn(EntityRef) -> bool) {
+        self.topics.push(TopicEntities {
+            bit,
+            validate,
+            entities: Default::default(),
+        });
+    }
+
+    /// Returns all entities in a topic with the given registration index.
+    pub fn entities(&self, index: usize) -> impl Iterator<Item = Entity> + '_ {
+        self.topics
+            .get(index)
+            .into_iter()
+            .flat_map(|topic| topic.entities.iter().copied())
+    }
+}
+
+struct TopicEntities {
+    bit: FilterBit,
+    validate: fn(EntityRef) -> bool,
+    entities: EntityHashSet,
+}
+
+pub(crate) fn add_observers(app: &mut App) {
+    app.add_observer(on_insert)
+        .add_observer(on_replace)
+        .add_observer(init_client)
+        .add_observer(subscribe)
+        .add_observer(unsubscribe);
+}
+
+fn on_insert(
+    insert: On<Insert, InTopics>,
+    registry: Res<TopicRegistry>,
+    mut index: ResMut<TopicIndex>,
+    entities: Query<&InTopics, Allow<Disabled>>,
+    subscriptions: Query<&Subscriptions>,
+    mut clients: Query<(Entity, Option<&ViewOf>, &mut ClientVisibility)>,
+) {
+    let in_topics = entities.get(insert.entity).unwrap();
+    for topic in in_topics.iter() {
+        let Some(topic_index) = registry.index(topic) else {
+            warn!(
+                "ignoring unregistered topic `{topic}` for `{}`",
+                insert.entity
+            );
+            continue;
+        };
+
+        debug!("adding `{}` to topic `{topic}`", insert.entity);
+        let entry = &mut index.topics[topic_index];
+        entry.entities.insert(insert.entity);
+        for (client, view_of, mut visibility) in &mut clients {
+            let visible = is_subscribed(&subscriptions, client, view_of, topic);
+            visibility.set(insert.entity, entry.bit, visible);
+        }
+    }
+}
+
+fn on_replace(
+    replace: On<Replace, InTopics>,
+    registry: Res<TopicRegistry>,
+    mut index: ResMut<TopicIndex>,
+    entities: Query<&InTopics, Allow<Disabled>>,
+    mut clients: Query<&mut ClientVisibility>,
+) {
+    let in_topics = entities.get(replace.entity).unwrap();
+    for topic in in_topics.iter() {
+        let Some(topic_index) = registry.index(topic) else {
+            continue;
+        };
+
+        debug!("removing `{}` from topic `{topic}`", replace.entity);
+        let entry = &mut index.topics[topic_index];
+        entry.entities.remove(&replace.entity);
+
+        // Will be hidden again if the entity is added to the topic.
+        for mut visibility in &mut clients {
+            visibility.set(replace.entity, entry.bit, true);
+        }
+    }
+}
+
+fn init_client(
+    insert: On<Insert, ClientVisibility>,
+    registry: Res<TopicRegistry>,
+    index: Res<TopicIndex>,
+    subscriptions: Query<&Subscriptions>,
+    mut clients: Query<(Option<&ViewOf>, &mut ClientVisibility)>,
+) {
+    let Ok((view_of, mut visibility)) = clients.get_mut(insert.entity) else {
+        return;
+    };
+
+    for (topic, entry) in registry.iter().zip(&index.topics) {
+        let visible = is_subscribed(&subscriptions, insert.entity, view_of, topic);
+        if !visible {
+            for &entity in &entry.entities {
+                visibility.set(entity, entry.bit, false);
+            }
+        }
+    }
+}
+
+fn is_subscribed(
+    subscriptions: &Query<&Subscriptions>,
+    client: Entity,
+    view_of: Option<&ViewOf>,
+    topic: &str,
+) -> bool {
+    let client = view_of.map_or(client, |view_of| **view_of);
+    subscriptions
+        .get(client)
+        .is_ok_and(|subscriptions| subscriptions.contains(topic))
+}
+
+fn subscribe(subscribe: On<FromClient<Subscribe>>, mut commands: Commands) {
+    if let Some(client) = subscribe.client_id.entity() {
+        let topic = subscribe.topic.clone();
+        commands.queue(move |world: &mut World| set_subscribed(world, client, topic, true));
+    }
+}
+
+fn unsubscribe(unsubscribe: On<FromClient<Unsubscribe>>, mut commands: Commands) {
+    if let Some(client) = unsubscribe.client_id.entity() {
+        let topic = unsubscribe.topic.clone();
+        commands.queue(move |world: &mut World| set_subscribed(world, client, topic, false));
+    }
+}
+
+fn set_subscribed(world: &mut World, client: Entity, topic: Cow<str>, subscribed: bool) {
+    let registry = world.resource::<TopicRegistry>();
+    let Some((topic_index, topic)) = registry.iter().enumerate().find(|(_, name)| *name == topic)
+    else {
+        debug!("ignoring request for unregistered topic `{topic}` from client `{client}`");
+        return;
+    };
+
+    world.resource_scope(|world, index: Mut<TopicIndex>| {
+        let entry = &index.topics[topic_index];
+        let Ok(mut client_entity) = world.get_entity_mut(client) else {
+            debug!("ignoring request for topic `{topic}` from disconnected client `{client}`");
+            return;
+        };
+
+        if subscribed && !(entry.validate)(client_entity.as_readonly()) {
+            debug!("rejecting subscription to topic `{topic}` from client `{client}`");
+            return;
+        }
+
+        match client_entity.get_mut::<Subscriptions>() {
+            Some(mut subscriptions) => {
+                if subscriptions.contains(topic) == subscribed {
+                    return;
+                }
+                if subscribed {
+                    subscriptions.0.push(topic);
+                } else {
+                    subscriptions.0.retain(|&name| name != topic);
+                }
+            }
+            None => {
+                if !subscribed {
+                    return;
+                }
+                client_entity.insert(Subscriptions(vec![topic]));
+            }
+        }
+
+        debug!("setting subscription to topic `{topic}` for client `{client}` to `{subscribed}`");
+        let views = client_entity
+            .get::<ClientViews>()
+            .map(|views| views.to_vec())
+            .unwrap_or_default();
+        for viewer in views.into_iter().chain([client]) {
+            if let Some(mut visibility) = world.get_mut::<ClientVisibility>(viewer) {
+                for &entity in &entry.entities {
+                    visibility.set(entity, entry.bit, subscribed);
+                }
+            }
+        }
+    });
+}
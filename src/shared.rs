@@ -1,23 +1,39 @@
+pub mod applied_tick;
+pub mod at_tick;
+pub mod authorization;
 pub mod backend;
 pub mod client_id;
+pub mod connection_roster;
+pub mod connection_seed;
+pub mod leak_detection;
 pub mod message;
 pub mod protocol;
+pub mod raw_channel;
 pub mod replication;
+pub mod replication_error;
 pub mod replicon_tick;
+pub mod server_discovery;
 pub mod server_entity_map;
+pub mod tick_timeline;
 
 use bevy::prelude::*;
 
 use crate::prelude::*;
+use authorization::{AuthRequest, AuthorizationFailed};
 use backend::connected_client::NetworkIdMap;
+use connection_seed::ConnectionSeed;
 use message::registry::RemoteMessageRegistry;
 use replication::{
-    receive_markers::ReceiveMarkers, registry::ReplicationRegistry, rules::ReplicationRules,
-    signature::SignatureMap,
+    receive_markers::ReceiveMarkers,
+    registry::ReplicationRegistry,
+    rules::ReplicationRules,
+    signature::{SignatureMap, SignatureRemovals},
 };
+use replication_error::{ErrorReporter, ReplicationError, ReplicationErrorSettings};
+use tick_timeline::TickTimeline;
 
 /// Initializes types, resources and events needed for both client and server.
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct RepliconSharedPlugin {
     /**
     Configures the authorization process.
@@ -25,8 +41,8 @@ pub struct RepliconSharedPlugin {
     # Examples
 
     Custom authorization to set a player name before starting replication.
-    We re-use [`ProtocolMismatch`], which is registered only with
-    [`AuthMethod::ProtocolCheck`], but it could be any event.
+    Denials are sent with [`DenyAuthorizationExt::deny_authorization`](crate::server::authorization::DenyAuthorizationExt::deny_authorization),
+    so the client receives the standard [`AuthorizationFailed`].
 
     ```
     use bevy::{prelude::*, state::app::StatesPlugin};
@@ -42,8 +58,6 @@ pub struct RepliconSharedPlugin {
         }),
     ))
     .add_client_event::<ClientInfo>(Channel::Ordered)
-    .add_server_event::<ProtocolMismatch>(Channel::Unreliable)
-    .make_event_independent::<ProtocolMismatch>() // Let the client receive it without replication.
     .add_observer(start_game)
     .add_systems(OnEnter(ClientState::Connected), send_info);
 
@@ -60,8 +74,8 @@ pub struct RepliconSharedPlugin {
     fn start_game(
         client_info: On<FromClient<ClientInfo>>,
         mut commands: Commands,
-        mut disconnects: MessageWriter<DisconnectRequest>,
         protocol: Res<ProtocolHash>,
+        breakdown: Res<ProtocolBreakdown>,
     ) {
         let client = client_info
             .client_id
@@ -71,13 +85,9 @@ pub struct RepliconSharedPlugin {
         // Since we are using custom authorization,
         // we need to verify the protocol manually.
         if client_info.protocol != *protocol {
-            // Notify the client about the problem. No delivery
-            // guarantee, since we disconnect after sending.
-            commands.server_trigger(ToClients {
-                targets: SendTargets::Single(client_info.client_id),
-                message: ProtocolMismatch,
-            });
-            disconnects.write(DisconnectRequest { client });
+            // Notify the client about the problem and disconnect it.
+            commands.deny_authorization(client, AuthDenial::ProtocolMismatch(breakdown.clone()));
+            return;
         }
 
         // Validate player name, run the necessary game logic...
@@ -104,19 +114,31 @@ impl Plugin for RepliconSharedPlugin {
             .init_resource::<ProtocolHasher>()
             .init_resource::<NetworkIdMap>()
             .init_resource::<RepliconChannels>()
+            .init_resource::<RawChannels>()
+            .init_resource::<BackendCapabilities>()
             .init_resource::<ReplicationRegistry>()
             .init_resource::<ReplicationRules>()
             .init_resource::<ReplicationStorage>()
             .init_resource::<SignatureMap>()
+            .init_resource::<SignatureRemovals>()
             .init_resource::<ReceiveMarkers>()
             .init_resource::<RemoteMessageRegistry>()
+            .init_resource::<ReplicationErrorSettings>()
+            .init_resource::<ErrorReporter>()
+            .init_resource::<TickTimeline>()
             .insert_resource(self.auth_method)
-            .add_message::<DisconnectRequest>();
-
-        if self.auth_method == AuthMethod::ProtocolCheck {
-            app.add_client_event::<ProtocolHash>(Channel::Ordered)
-                .add_server_event::<ProtocolMismatch>(Channel::Unreliable)
-                .make_event_independent::<ProtocolMismatch>();
+            .add_message::<DisconnectRequest>()
+            .add_message::<ReplicationError>()
+            .add_server_event::<ConnectionSeed>(Channel::Ordered)
+            .make_event_independent::<ConnectionSeed>()
+            .add_systems(Last, replication_error::write_errors);
+
+        if self.auth_method == AuthMethod::Authorizers {
+            app.add_client_event::<AuthRequest>(Channel::Ordered);
+        }
+        if self.auth_method != AuthMethod::None {
+            app.add_server_event::<AuthorizationFailed>(Channel::Unreliable)
+                .make_event_independent::<AuthorizationFailed>();
         }
     }
 
@@ -126,7 +148,15 @@ impl Plugin for RepliconSharedPlugin {
             .remove_resource::<ProtocolHasher>()
             .expect("protocol hasher should be initialized at the plugin build");
 
+        app.world_mut().insert_resource(protocol_hasher.breakdown());
+        app.world_mut()
+            .insert_resource(protocol_hasher.registrations());
         app.world_mut().insert_resource(protocol_hasher.finish());
+
+        let capabilities = *app.world().resource::<BackendCapabilities>();
+        app.world_mut()
+            .resource_mut::<RepliconChannels>()
+            .apply_capabilities(capabilities);
     }
 }
 
@@ -135,13 +165,16 @@ impl Plugin for RepliconSharedPlugin {
 /// Can be set via [`RepliconSharedPlugin::auth_method`].
 #[derive(Resource, Default, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum AuthMethod {
-    /// Wait for receiving [`ProtocolHash`] event from the client.
+    /// Wait for receiving [`AuthRequest`] event from the client and check it with
+    /// [`Authorizers`](crate::server::authorization::Authorizers).
+    ///
+    /// - If any authorizer denies the request, the client will be notified with
+    ///   an [`AuthorizationFailed`] event and disconnected.
+    /// - If all authorizers pass, the [`AuthorizedClient`] component will be inserted.
     ///
-    /// - If the hash differs from the server's, the client will be notified with
-    ///   a [`ProtocolMismatch`] event and disconnected.
-    /// - If the hash matches, the [`AuthorizedClient`] component will be inserted.
+    /// By default only the protocol is checked.
     #[default]
-    ProtocolCheck,
+    Authorizers,
 
     /// Consider all connected clients immediately authorized.
     ///
@@ -153,5 +186,7 @@ pub enum AuthMethod {
     /// Disable automatic insertion.
     ///
     /// The user is responsible for manually inserting [`AuthorizedClient`] on the server.
+    /// [`AuthorizationFailed`] is still registered and can be sent with
+    /// [`DenyAuthorizationExt::deny_authorization`](crate::server::authorization::DenyAuthorizationExt::deny_authorization).
     Custom,
 }
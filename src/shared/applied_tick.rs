@@ -0,0 +1,134 @@
+//! Application-level acknowledgments of applied update messages.
+//!
+//! See [`AppliedTickPlugin`] for details.
+
+use bevy::prelude::*;
+#[cfg(any(feature = "client", feature = "server"))]
+use log::trace;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+use crate::client::ServerUpdateTick;
+use crate::prelude::*;
+
+/**
+Reports the last update tick applied by each client to the server.
+
+Transport acknowledgments only confirm that a message was delivered. With this plugin,
+the client sends [`ServerUpdateTick`](crate::client::ServerUpdateTick) to the server at the
+end of each frame in which it changed, after all received update messages were written to
+the world. The server stores it in [`ClientAppliedTick`] on the client entity.
+
+Useful for server logic that must know that the client actually has the state, for example,
+before starting a match countdown. Compare it with
+[`ClientTicks::update_tick`](crate::shared::replication::client_ticks::ClientTicks::update_tick)
+to check if the client applied all updates sent to it.
+
+Destructive changes deferred by [`DestructiveGates`](crate::client::destructive_gate::DestructiveGates)
+are considered applied.
+
+Not included in [`RepliconPlugins`] and needs to be added manually on both client and server.
+
+# Examples
+
+```
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, shared::replication::client_ticks::ClientTicks};
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    StatesPlugin,
+    RepliconPlugins,
+    AppliedTickPlugin,
+))
+.add_systems(Update, start_countdown);
+
+fn start_countdown(clients: Query<(&ClientAppliedTick, &ClientTicks)>) {
+    if clients
+        .iter()
+        .all(|(applied_tick, ticks)| applied_tick.contains(ticks.update_tick()))
+    {
+        info!("all clients are ready");
+    }
+}
+```
+*/
+pub struct AppliedTickPlugin;
+
+impl Plugin for AppliedTickPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_message::<AppliedTick>(Channel::Ordered);
+
+        #[cfg(feature = "server")]
+        app.register_required_components::<AuthorizedClient, ClientAppliedTick>()
+            .add_systems(
+                PreUpdate,
+                receive_applied_ticks
+                    .after(ServerSystems::Receive)
+                    .run_if(in_state(ServerState::Running)),
+            );
+
+        #[cfg(feature = "client")]
+        app.add_systems(
+            PostUpdate,
+            send_applied_tick
+                .before(ClientSystems::Send)
+                .run_if(in_state(ClientState::Connected))
+                .run_if(resource_changed::<ServerUpdateTick>),
+        );
+    }
+}
+
+#[cfg(feature = "server")]
+fn receive_applied_ticks(
+    mut applied_ticks: MessageReader<FromClient<AppliedTick>>,
+    mut clients: Query<&mut ClientAppliedTick>,
+) {
+    for applied_tick in applied_ticks.read() {
+        let Some(client) = applied_tick.client_id.entity() else {
+            continue;
+        };
+        let Ok(mut client_tick) = clients.get_mut(client) else {
+            continue;
+        };
+
+        let tick = *applied_tick.message;
+        if client_tick.contains(tick) {
+            trace!("ignoring outdated `{tick:?}` applied by client `{client}`");
+            continue;
+        }
+
+        trace!("client `{client}` applied `{tick:?}`");
+        client_tick.0 = Some(tick);
+    }
+}
+
+#[cfg(feature = "client")]
+fn send_applied_tick(
+    mut applied_ticks: MessageWriter<AppliedTick>,
+    update_tick: Res<ServerUpdateTick>,
+) {
+    trace!("sending applied `{:?}`", **update_tick);
+    applied_ticks.write(AppliedTick(**update_tick));
+}
+
+/// The last update tick that the client applied.
+///
+/// [`None`] if the client didn't report any tick yet.
+///
+/// Present on authorized clients on the server if [`AppliedTickPlugin`] is added.
+#[derive(Component, Deref, Default, Debug, Clone, Copy)]
+pub struct ClientAppliedTick(Option<RepliconTick>);
+
+impl ClientAppliedTick {
+    /// Returns `true` if the client applied the update message for `tick` or any newer one.
+    pub fn contains(self, tick: RepliconTick) -> bool {
+        self.0
+            .is_some_and(|applied_tick| applied_tick.is_newer_or_eq(tick))
+    }
+}
+
+/// Update tick applied by the client.
+#[derive(Message, Deref, Serialize, Deserialize, Clone, Copy)]
+struct AppliedTick(RepliconTick);
@@ -0,0 +1,148 @@
+use core::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// A value sampled at a specific server tick.
+///
+/// Intended to be used inside networked messages and components, so crates for prediction
+/// or interpolation can exchange timestamped data in the same format.
+///
+/// Use [`Self::stamp`] to create it with the estimated current tick. It works the same way on
+/// both client and server because it relies on [`TickTimeline`]. On the receiving side, use
+/// [`Self::age`] to get how long ago the value was sampled.
+///
+/// Dereferences to the inner value.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(RepliconPlugins);
+/// app.add_client_event::<Shot>(Channel::Unreliable)
+///     .add_observer(receive_shot)
+///     .add_systems(Update, shoot);
+///
+/// fn shoot(mut commands: Commands, timeline: Res<TickTimeline>, time: Res<Time>) {
+///     if let Some(direction) = AtTick::stamp(Vec3::X, &timeline, time.elapsed()) {
+///         commands.client_trigger(Shot(direction));
+///     }
+/// }
+///
+/// fn receive_shot(shot: On<FromClient<Shot>>, timeline: Res<TickTimeline>, time: Res<Time>) {
+///     if let Some(age) = shot.0.age_secs(&timeline, time.elapsed()) {
+///         info!("received shot fired {age} seconds ago at `{:?}`", shot.0.tick);
+///     }
+/// }
+///
+/// #[derive(Event, Serialize, Deserialize)]
+/// struct Shot(AtTick<Vec3>);
+/// ```
+#[derive(
+    Serialize, Deserialize, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Deref, DerefMut,
+)]
+pub struct AtTick<T> {
+    /// Server tick at which the value was sampled.
+    pub tick: RepliconTick,
+
+    /// Sampled value.
+    #[deref]
+    pub value: T,
+}
+
+impl<T> AtTick<T> {
+    /// Creates a new instance with the given tick.
+    pub fn new(tick: RepliconTick, value: T) -> Self {
+        Self { tick, value }
+    }
+
+    /// Creates a new instance with the tick estimated at the given time.
+    ///
+    /// Pass [`Time::elapsed`] to stamp with the current tick.
+    /// If the tick rate can't be estimated yet, uses the latest recorded tick.
+    ///
+    /// Returns [`None`] if no ticks were recorded.
+    pub fn stamp(value: T, timeline: &TickTimeline, elapsed: Duration) -> Option<Self> {
+        let tick = timeline
+            .tick_at(elapsed)
+            .map(|(tick, _)| tick)
+            .or_else(|| timeline.latest().map(|(tick, _)| tick))?;
+
+        Some(Self::new(tick, value))
+    }
+
+    /// Returns how long ago the value was sampled at the given time.
+    ///
+    /// Pass [`Time::elapsed`] to get the current age. Values from the future
+    /// (for example, due to estimation error) are considered to have zero age.
+    ///
+    /// Returns [`None`] if the tick rate can't be estimated yet.
+    pub fn age(&self, timeline: &TickTimeline, elapsed: Duration) -> Option<Duration> {
+        let (tick, overstep) = timeline.tick_at(elapsed)?;
+        let tick_duration = timeline.tick_duration()?;
+        if self.tick.is_newer(tick) {
+            return Some(Duration::ZERO);
+        }
+
+        Some(tick_duration * (tick - self.tick) + tick_duration.mul_f32(overstep))
+    }
+
+    /// Like [`Self::age`], but returns the number of seconds.
+    pub fn age_secs(&self, timeline: &TickTimeline, elapsed: Duration) -> Option<f32> {
+        self.age(timeline, elapsed).map(|age| age.as_secs_f32())
+    }
+
+    /// Maps the value while keeping the tick.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> AtTick<U> {
+        AtTick::new(self.tick, (f)(self.value))
+    }
+
+    /// Returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamping() {
+        let mut timeline = TickTimeline::default();
+        assert_eq!(AtTick::stamp((), &timeline, Duration::ZERO), None);
+
+        timeline.record(RepliconTick::new(1), Duration::from_millis(10));
+        let stamped = AtTick::stamp((), &timeline, Duration::from_millis(20)).unwrap();
+        assert_eq!(stamped.tick, RepliconTick::new(1));
+
+        timeline.record(RepliconTick::new(2), Duration::from_millis(20));
+        let stamped = AtTick::stamp((), &timeline, Duration::from_millis(45)).unwrap();
+        assert_eq!(stamped.tick, RepliconTick::new(4));
+    }
+
+    #[test]
+    fn age() {
+        let mut timeline = TickTimeline::default();
+        let value = AtTick::new(RepliconTick::new(1), ());
+        assert_eq!(value.age(&timeline, Duration::ZERO), None);
+
+        timeline.record(RepliconTick::new(1), Duration::from_millis(10));
+        timeline.record(RepliconTick::new(2), Duration::from_millis(20));
+        assert_eq!(
+            value.age(&timeline, Duration::from_millis(35)),
+            Some(Duration::from_millis(25))
+        );
+        assert_eq!(
+            value.age(&timeline, Duration::ZERO),
+            Some(Duration::ZERO),
+            "values from the future should have zero age"
+        );
+    }
+}
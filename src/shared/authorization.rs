@@ -0,0 +1,70 @@
+use alloc::{string::String, vec::Vec};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::protocol::{ProtocolBreakdown, ProtocolHash};
+
+/// A client event sent right after connecting to request authorization.
+///
+/// Registered and sent only if [`RepliconSharedPlugin::auth_method`](super::RepliconSharedPlugin::auth_method)
+/// set to [`AuthMethod::Authorizers`](super::AuthMethod::Authorizers). On the server, the request
+/// is checked by [`Authorizers`](crate::server::authorization::Authorizers).
+#[derive(Event, Serialize, Deserialize, Debug, Clone)]
+pub struct AuthRequest {
+    /// Protocol of the client.
+    pub protocol: ProtocolHash,
+
+    /// Hashes of optional replication rules registered on the client.
+    ///
+    /// See [`ProtocolBreakdown::optional`].
+    pub optional: Vec<u64>,
+
+    /// Token from [`AuthToken`] if it was present on the client.
+    pub token: Option<Vec<u8>>,
+}
+
+/// Token that the client sends inside [`AuthRequest`].
+///
+/// The resource isn't added by default and is kept across reconnects.
+/// Insert it on the client before connecting and check it on the server with
+/// [`TokenCheck`](crate::server::authorization::TokenCheck).
+#[derive(Resource, Deref, DerefMut, Debug, Clone)]
+pub struct AuthToken(pub Vec<u8>);
+
+/// A server event to notify the client that it wasn't authorized.
+///
+/// Registered unless [`RepliconSharedPlugin::auth_method`](super::RepliconSharedPlugin::auth_method)
+/// set to [`AuthMethod::None`](super::AuthMethod::None). The server will immediately
+/// disconnect after sending it, so there is no delivery guarantee.
+///
+/// On receive, the client logs the reason. For [`AuthDenial::ProtocolMismatch`], it also
+/// triggers [`ProtocolMismatch`](super::protocol::ProtocolMismatch) locally.
+///
+/// Sent by [`Authorizers`](crate::server::authorization::Authorizers) or manually with
+/// [`DenyAuthorizationExt::deny_authorization`](crate::server::authorization::DenyAuthorizationExt::deny_authorization).
+#[derive(Event, Serialize, Deserialize, Debug, Clone)]
+pub struct AuthorizationFailed {
+    /// Why the authorization was denied.
+    pub reason: AuthDenial,
+}
+
+/// Reason for denying authorization.
+///
+/// See also [`AuthorizationFailed`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub enum AuthDenial {
+    /// Protocol of the client differs from the server's.
+    ///
+    /// Contains the server's [`ProtocolBreakdown`] to compare with the local one.
+    ProtocolMismatch(ProtocolBreakdown),
+
+    /// The client sent no token or the token is invalid.
+    InvalidToken,
+
+    /// The client is banned.
+    Banned,
+
+    /// Application-specific reason.
+    Custom(String),
+}
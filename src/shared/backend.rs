@@ -9,6 +9,11 @@
 //! - Spawn and despawn entities with [`ConnectedClient`](connected_client::ConnectedClient) component.
 //! - React on [`DisconnectRequest`] message.
 //! - Optionally update statistics in [`ClientStats`] resource and [`ConnectedClientStats`] components on connected client entities.
+//! - Optionally insert [`BackendCapabilities`] if the transport doesn't support all channel kinds.
+//! - Optionally send channels in the [send order](channels::RepliconChannels#send-order)
+//!   if the transport can't flush all messages in a tick.
+//! - Optionally update the [`UnconnectedMessages`](unconnected_messages::UnconnectedMessages) resource
+//!   if the transport supports messages without a connection.
 //!
 //! This way, integrations can be provided as separate crates without requiring us or crate authors to maintain them under a feature.
 //! See the documentation on types in this module for details.
@@ -25,10 +30,16 @@
 pub mod channels;
 pub mod client_messages;
 pub mod connected_client;
+mod fragment;
+pub mod message_transforms;
+mod sequence;
 pub mod server_messages;
+pub mod unconnected_messages;
 
 use bevy::prelude::*;
 
+use channels::Channel;
+
 /// Connection state of the client.
 ///
 /// <div class="warning">
@@ -100,6 +111,68 @@ pub struct ClientStats {
     pub received_bps: f64,
 }
 
+/// Delivery guarantees supported by the messaging backend.
+///
+/// Channels from [`RepliconChannels`](channels::RepliconChannels) that require unsupported
+/// guarantees are upgraded to the closest supported ones in
+/// [`RepliconSharedPlugin::finish`](crate::shared::RepliconSharedPlugin), so the backend should
+/// insert this resource during [`Plugin::build`]. Upgrades for messages and events are logged
+/// as warnings. By default, all guarantees are supported.
+///
+/// Upgraded [`Channel::UnreliableLatest`](channels::Channel::UnreliableLatest) channels still drop
+/// stale messages and send only the latest message for each receiver per tick, so outdated messages
+/// don't pile up in reliable queues. Upgraded [`Channel::Unreliable`](channels::Channel::Unreliable)
+/// channels deliver every message, since dropping any of them would lose data.
+///
+/// If a [mutation channel](channels::RepliconChannels::mutation_channel) becomes reliable,
+/// the server considers mutations sent over it acknowledged as soon as they are sent. This way mutations
+/// are not re-sent while waiting for acknowledgments, and only the latest changes are sent.
+///
+/// <div class="warning">
+///
+/// Should only be inserted by the messaging backend.
+///
+/// </div>
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackendCapabilities {
+    /// Supports [`Channel::Unreliable`](channels::Channel::Unreliable)
+    /// and [`Channel::UnreliableLatest`](channels::Channel::UnreliableLatest).
+    pub unreliable: bool,
+
+    /// Supports [`Channel::Unordered`](channels::Channel::Unordered).
+    pub unordered: bool,
+}
+
+impl BackendCapabilities {
+    /// Capabilities of a transport that supports only reliable ordered delivery, such as TCP or WebSocket.
+    pub const ORDERED_ONLY: Self = Self {
+        unreliable: false,
+        unordered: false,
+    };
+
+    /// Returns the weakest supported channel that provides at least the guarantees of `channel`.
+    ///
+    /// [`Channel::Ordered`](channels::Channel::Ordered) is always supported.
+    pub fn resolve(self, channel: Channel) -> Channel {
+        match channel {
+            Channel::Unreliable | Channel::UnreliableLatest if !self.unreliable => {
+                self.resolve(Channel::Unordered)
+            }
+            Channel::Unordered if !self.unordered => Channel::Ordered,
+            _ => channel,
+        }
+    }
+}
+
+impl Default for BackendCapabilities {
+    fn default() -> Self {
+        Self {
+            unreliable: true,
+            unordered: true,
+        }
+    }
+}
+
 /// Statistic for a connected client on the server.
 ///
 /// All values can be zero if not provided by the backend.
@@ -114,19 +187,20 @@ pub struct ConnectedClientStats(pub ClientStats);
 
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
     use test_log::test;
 
     use super::*;
     use crate::{
         prelude::*,
-        shared::backend::channels::{ClientChannel, ServerChannel},
+        shared::backend::channels::{ChannelCreator, ClientChannel, ServerChannel},
     };
 
     #[test]
     fn client_to_server() {
         let channels = RepliconChannels::default();
         let mut client_messages = ClientMessages::default();
-        client_messages.setup_server_channels(channels.server_channels().len());
+        client_messages.setup_channels(&channels);
 
         const MESSAGES: &[&[u8]] = &[&[0], &[1]];
         for &message in MESSAGES {
@@ -134,7 +208,7 @@ mod tests {
         }
 
         let mut server_messages = ServerMessages::default();
-        server_messages.setup_client_channels(channels.client_channels().len());
+        server_messages.setup_channels(&channels);
 
         for (channel_id, message) in client_messages.drain_sent() {
             server_messages.insert_received(Entity::PLACEHOLDER, channel_id, message);
@@ -151,7 +225,7 @@ mod tests {
     fn server_to_client() {
         let channels = RepliconChannels::default();
         let mut server_messages = ServerMessages::default();
-        server_messages.setup_client_channels(channels.client_channels().len());
+        server_messages.setup_channels(&channels);
 
         const MESSAGES: &[&[u8]] = &[&[0], &[1]];
         for &message in MESSAGES {
@@ -159,7 +233,7 @@ mod tests {
         }
 
         let mut client_messages = ClientMessages::default();
-        client_messages.setup_server_channels(channels.server_channels().len());
+        client_messages.setup_channels(&channels);
 
         for (_, channel_id, message) in server_messages.drain_sent() {
             client_messages.insert_received(channel_id, message);
@@ -168,4 +242,113 @@ mod tests {
         let messages: Vec<_> = client_messages.receive(ServerChannel::Mutations).collect();
         assert_eq!(messages, MESSAGES);
     }
+
+    #[test]
+    fn client_to_server_latest() {
+        let mut channels = RepliconChannels::default();
+        let channel_id =
+            channels.create_client_channel(Channel::UnreliableLatest, ChannelCreator::Replication);
+        let mut client_messages = ClientMessages::default();
+        client_messages.setup_channels(&channels);
+        let mut server_messages = ServerMessages::default();
+        server_messages.setup_channels(&channels);
+
+        for message in [0, 1, 2] {
+            client_messages.send(channel_id, vec![message]);
+        }
+
+        let sent: Vec<_> = client_messages.drain_sent().collect();
+        for (channel_id, message) in sent.iter().rev().chain(&sent) {
+            server_messages.insert_received(Entity::PLACEHOLDER, *channel_id, message.clone());
+        }
+
+        let messages: Vec<_> = server_messages
+            .receive(channel_id)
+            .map(|(_, message)| message)
+            .collect();
+        assert_eq!(
+            messages,
+            [Bytes::from_static(&[2])],
+            "only the newest should be delivered"
+        );
+
+        client_messages.send(channel_id, vec![3]);
+        for (channel_id, message) in client_messages.drain_sent().chain(sent) {
+            server_messages.insert_received(Entity::PLACEHOLDER, channel_id, message);
+        }
+
+        let messages: Vec<_> = server_messages
+            .receive(channel_id)
+            .map(|(_, message)| message)
+            .collect();
+        assert_eq!(
+            messages,
+            [Bytes::from_static(&[3])],
+            "stale messages should be dropped"
+        );
+    }
+
+    #[test]
+    fn server_to_client_latest() {
+        let mut channels = RepliconChannels::default();
+        let channel_id =
+            channels.create_server_channel(Channel::UnreliableLatest, ChannelCreator::Replication);
+        let mut server_messages = ServerMessages::default();
+        server_messages.setup_channels(&channels);
+        let mut client_messages = ClientMessages::default();
+        client_messages.setup_channels(&channels);
+
+        for message in [0, 1, 2] {
+            server_messages.send(Entity::PLACEHOLDER, channel_id, vec![message]);
+        }
+
+        let sent: Vec<_> = server_messages.drain_sent().collect();
+        for (_, channel_id, message) in sent.iter().rev().chain(&sent) {
+            client_messages.insert_received(*channel_id, message.clone());
+        }
+
+        let messages: Vec<_> = client_messages.receive(channel_id).collect();
+        assert_eq!(
+            messages,
+            [Bytes::from_static(&[2])],
+            "only the newest should be delivered"
+        );
+
+        for (_, channel_id, message) in sent {
+            client_messages.insert_received(channel_id, message);
+        }
+
+        assert_eq!(
+            client_messages.receive(channel_id).count(),
+            0,
+            "stale messages should be dropped"
+        );
+    }
+
+    #[test]
+    fn capabilities() {
+        let all = BackendCapabilities::default();
+        assert_eq!(all.resolve(Channel::Unreliable), Channel::Unreliable);
+        assert_eq!(
+            all.resolve(Channel::UnreliableLatest),
+            Channel::UnreliableLatest
+        );
+        assert_eq!(all.resolve(Channel::Unordered), Channel::Unordered);
+        assert_eq!(all.resolve(Channel::Ordered), Channel::Ordered);
+
+        let reliable = BackendCapabilities {
+            unreliable: false,
+            unordered: true,
+        };
+        assert_eq!(reliable.resolve(Channel::Unreliable), Channel::Unordered);
+        assert_eq!(
+            reliable.resolve(Channel::UnreliableLatest),
+            Channel::Unordered
+        );
+        assert_eq!(reliable.resolve(Channel::Unordered), Channel::Unordered);
+
+        let ordered = BackendCapabilities::ORDERED_ONLY;
+        assert_eq!(ordered.resolve(Channel::Unreliable), Channel::Ordered);
+        assert_eq!(ordered.resolve(Channel::Unordered), Channel::Ordered);
+    }
 }
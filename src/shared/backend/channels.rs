@@ -1,5 +1,14 @@
+use core::{
+    any::TypeId,
+    cmp::Reverse,
+    fmt::{self, Display, Formatter},
+};
+
 use bevy::prelude::*;
-use log::debug;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use super::{BackendCapabilities, fragment::ReassemblyLimits};
 
 /// A resource with all channels used by Replicon.
 ///
@@ -18,24 +27,75 @@ use log::debug;
 /// If the backend does not distinguish between sending and receiving channels,
 /// create channels for both client and server by chaining them.
 ///
-/// Channel IDs are represented by [`usize`], but backends may limit the number of channels.
+/// Channel IDs are represented by [`ServerChannelId`] and [`ClientChannelId`] to avoid mixing up
+/// directions, but backends may limit the number of channels.
 /// See [`ServerChannel`] and [`ClientChannel`] for channels that are always reserved.
 /// Other channels are used for events, with one channel per event. For more details, see
 /// [`RemoteMessageRegistry`](crate::shared::message::registry::RemoteMessageRegistry).
 ///
 /// The backend needs to provide an API for creating its own channels. This can be done
 /// by writing an extension trait for this struct. Created channels should have the defined
-/// delivery guarantee or stronger.
+/// delivery guarantee or stronger. If the backend doesn't support some guarantees,
+/// it should insert [`BackendCapabilities`] to let Replicon adjust the channels.
+///
+/// To find out what a channel is used for, see [`Self::iter`] and [`Self::server_channel`]
+/// or [`Self::client_channel`] for lookups by ID.
+///
+/// # Send order
+///
+/// If the backend can't flush all messages in a tick due to a limited bandwidth budget,
+/// it should send channels in the order returned by [`Self::server_send_order`] or
+/// [`Self::client_send_order`]:
+///
+/// 1. [`ServerChannel::Updates`] on the server or [`ClientChannel::MutationAcks`] and
+///    [`ClientChannel::UpdateAcks`] on the client. Without them, the other side can't apply anything else.
+/// 2. Mutation channels, see [`Self::mutation_channel`]. Mutations inside them are already
+///    ordered according to the per-client [`PriorityMap`](crate::server::PriorityMap).
+/// 3. Channels for messages, events and raw data, ordered by their send priority. See
+///    [`Self::set_server_send_priority`] and [`Self::set_client_send_priority`].
+///
+/// This way bandwidth-constrained transports behave consistently.
 #[derive(Resource, Clone)]
 pub struct RepliconChannels {
     /// Stores settings for each server channel.
     server: Vec<Channel>,
 
+    /// Stores what created each channel from [`Self::server`].
+    server_creators: Vec<ChannelCreator>,
+
     /// Same as [`Self::server`], but for client.
     client: Vec<Channel>,
+
+    /// Send priorities for each channel from [`Self::server`].
+    server_priorities: Vec<u8>,
+
+    /// Same as [`Self::server_creators`], but for client.
+    client_creators: Vec<ChannelCreator>,
+
+    /// Same as [`Self::server_priorities`], but for client.
+    client_priorities: Vec<u8>,
+
+    /// Whether each channel from [`Self::server`] was created as [`Channel::UnreliableLatest`].
+    ///
+    /// Preserved after [`Self::apply_capabilities`], so upgraded channels still drop stale messages.
+    server_latest: Vec<bool>,
+
+    /// Same as [`Self::server_latest`], but for client.
+    client_latest: Vec<bool>,
+
+    /// Size in bytes above which messages are split into fragments.
+    fragment_threshold: Option<usize>,
+
+    /// Maximum size in bytes of a message reassembled from fragments.
+    max_fragmented_size: usize,
+
+    /// Channel for [`MutationChannel::Secondary`].
+    ///
+    /// Created only when a rule is assigned to it.
+    secondary_mutations: Option<ServerChannelId>,
 }
 
-/// Only stores the replication channel by default.
+/// Only stores the replication channels by default.
 impl Default for RepliconChannels {
     fn default() -> Self {
         Self {
@@ -43,30 +103,397 @@ impl Default for RepliconChannels {
                 ServerChannel::Updates.into(),
                 ServerChannel::Mutations.into(),
             ],
-            client: vec![ClientChannel::MutationAcks.into()],
+            server_creators: vec![ChannelCreator::Replication; 2],
+            server_priorities: vec![0; 2],
+            client: vec![
+                ClientChannel::MutationAcks.into(),
+                ClientChannel::UpdateAcks.into(),
+            ],
+            client_creators: vec![ChannelCreator::Replication; 2],
+            client_priorities: vec![0; 2],
+            server_latest: vec![false; 2],
+            client_latest: vec![false; 2],
+            fragment_threshold: None,
+            max_fragmented_size: 4 * 1024 * 1024,
+            secondary_mutations: None,
         }
     }
 }
 
 impl RepliconChannels {
     /// Creates a new server channel and returns its ID.
-    pub(crate) fn create_server_channel(&mut self, channel: Channel) -> usize {
-        let id = self.server.len();
-        debug!("creating a server channel with ID {id}");
+    pub(crate) fn create_server_channel(
+        &mut self,
+        channel: Channel,
+        creator: ChannelCreator,
+    ) -> ServerChannelId {
+        let id = ServerChannelId(self.server.len());
+        debug!("creating a server channel with ID {id} for {creator}");
         self.server.push(channel);
+        self.server_creators.push(creator);
+        self.server_priorities.push(0);
+        self.server_latest
+            .push(channel == Channel::UnreliableLatest);
 
         id
     }
 
     /// Creates a new client channel and returns its ID.
-    pub(crate) fn create_client_channel(&mut self, channel: Channel) -> usize {
-        let id = self.client.len();
-        debug!("creating a client channel with ID {id}");
+    pub(crate) fn create_client_channel(
+        &mut self,
+        channel: Channel,
+        creator: ChannelCreator,
+    ) -> ClientChannelId {
+        let id = ClientChannelId(self.client.len());
+        debug!("creating a client channel with ID {id} for {creator}");
         self.client.push(channel);
+        self.client_creators.push(creator);
+        self.client_priorities.push(0);
+        self.client_latest
+            .push(channel == Channel::UnreliableLatest);
 
         id
     }
 
+    /// Creates the channel for [`MutationChannel::Secondary`] if it doesn't exist.
+    ///
+    /// Called when a rule is assigned to this channel, so the channel ID depends
+    /// on the registration order like for messages.
+    pub(crate) fn enable_secondary_mutations(&mut self) {
+        if self.secondary_mutations.is_none() {
+            let id = self.create_server_channel(Channel::Unreliable, ChannelCreator::Replication);
+            self.secondary_mutations = Some(id);
+        }
+    }
+
+    /// Returns the ID of the server channel used for a mutation channel.
+    ///
+    /// Returns [`None`] for [`MutationChannel::Secondary`] if no rules were assigned to it.
+    pub fn mutation_channel(&self, channel: MutationChannel) -> Option<ServerChannelId> {
+        match channel {
+            MutationChannel::Primary => Some(ServerChannel::Mutations.into()),
+            MutationChannel::Secondary => self.secondary_mutations,
+        }
+    }
+
+    /**
+    Enables fragmentation for messages and events sent over reliable channels.
+
+    Messages larger than `threshold` bytes are split into fragments and reassembled on
+    the receiving side before deserialization. Useful for sending huge messages, like
+    independent server messages with level data, that exceed the maximum message size
+    of the backend. Set it to the maximum message size of the backend minus 7 bytes
+    for the fragment header.
+
+    Each message on affected channels gets a 1-byte header, so it should be set
+    to the same value on both client and server. Replication channels are not affected.
+
+    Should be called before [`ServerPlugin::finish`](crate::server::ServerPlugin) and
+    [`ClientPlugin::finish`](crate::client::ClientPlugin).
+
+    # Panics
+
+    Panics if `threshold` is 0.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.world_mut()
+        .resource_mut::<RepliconChannels>()
+        .set_fragment_threshold(1200);
+    ```
+    */
+    pub fn set_fragment_threshold(&mut self, threshold: usize) {
+        assert!(threshold > 0, "fragment threshold should be above 0");
+        debug!("enabling fragmentation for messages above {threshold} bytes");
+        self.fragment_threshold = Some(threshold);
+    }
+
+    /// Returns the threshold set by [`Self::set_fragment_threshold`].
+    pub fn fragment_threshold(&self) -> Option<usize> {
+        self.fragment_threshold
+    }
+
+    /**
+    Sets the maximum size in bytes of a message reassembled from fragments.
+
+    Fragments of larger messages are dropped on receive, which prevents peers from making
+    the receiving side allocate memory for huge messages. Should be at least as large as
+    the biggest message sent over fragmented channels.
+
+    By default set to 4 MiB.
+
+    Should be called before [`ServerPlugin::finish`](crate::server::ServerPlugin) and
+    [`ClientPlugin::finish`](crate::client::ClientPlugin).
+
+    # Panics
+
+    Panics if `size` is 0.
+    */
+    pub fn set_max_fragmented_size(&mut self, size: usize) {
+        assert!(
+            size > 0,
+            "maximum fragmented message size should be above 0"
+        );
+        self.max_fragmented_size = size;
+    }
+
+    /// Returns the size set by [`Self::set_max_fragmented_size`].
+    pub fn max_fragmented_size(&self) -> usize {
+        self.max_fragmented_size
+    }
+
+    /// Returns limits for reassembling fragmented messages.
+    ///
+    /// Returns the default value if fragmentation is disabled.
+    pub(super) fn reassembly_limits(&self) -> ReassemblyLimits {
+        self.fragment_threshold
+            .map(|threshold| ReassemblyLimits::new(threshold, self.max_fragmented_size))
+            .unwrap_or_default()
+    }
+
+    /**
+    Sets the send priority for a server channel used by a message or event.
+
+    Channels with higher priority should be sent first when the backend
+    can't flush everything in a tick. All channels have priority 0 by default.
+    Replication channels are always sent before messages and events.
+    See [send order](Self#send-order) for details.
+
+    # Panics
+
+    Panics if the channel doesn't exist or is a replication channel.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::{prelude::*, shared::message::registry::RemoteMessageRegistry};
+    use serde::{Deserialize, Serialize};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .add_server_message::<Chat>(Channel::Ordered);
+
+    let channel_id = app
+        .world()
+        .resource::<RemoteMessageRegistry>()
+        .server_message_channel::<Chat>()
+        .unwrap();
+    app.world_mut()
+        .resource_mut::<RepliconChannels>()
+        .set_server_send_priority(channel_id, 10);
+
+    #[derive(Message, Serialize, Deserialize)]
+    struct Chat(String);
+    ```
+    */
+    pub fn set_server_send_priority(&mut self, id: ServerChannelId, priority: u8) {
+        let creator = self.server_creators[id.get()];
+        assert!(
+            !matches!(creator, ChannelCreator::Replication),
+            "server channel {id} is used for replication and can't have a send priority"
+        );
+        debug!("setting send priority {priority} for server channel {id} used for {creator}");
+        self.server_priorities[id.get()] = priority;
+    }
+
+    /// Same as [`Self::set_server_send_priority`], but for client channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel doesn't exist or is a replication channel.
+    pub fn set_client_send_priority(&mut self, id: ClientChannelId, priority: u8) {
+        let creator = self.client_creators[id.get()];
+        assert!(
+            !matches!(creator, ChannelCreator::Replication),
+            "client channel {id} is used for replication and can't have a send priority"
+        );
+        debug!("setting send priority {priority} for client channel {id} used for {creator}");
+        self.client_priorities[id.get()] = priority;
+    }
+
+    /// Returns IDs of all server channels in the order in which they should be sent.
+    ///
+    /// See [send order](Self#send-order) for details.
+    pub fn server_send_order(&self) -> Vec<ServerChannelId> {
+        let mut ids: Vec<_> = self.server_ids().collect();
+        ids.sort_by_key(|&id| {
+            send_order_key(
+                self.server_creators[id.get()],
+                self.server_priorities[id.get()],
+            )
+        });
+        ids
+    }
+
+    /// Returns IDs of all client channels in the order in which they should be sent.
+    ///
+    /// See [send order](Self#send-order) for details.
+    pub fn client_send_order(&self) -> Vec<ClientChannelId> {
+        let mut ids: Vec<_> = self.client_ids().collect();
+        ids.sort_by_key(|&id| {
+            send_order_key(
+                self.client_creators[id.get()],
+                self.client_priorities[id.get()],
+            )
+        });
+        ids
+    }
+
+    /// Returns `true` for each server channel that uses fragmentation.
+    pub(super) fn server_fragmentation(&self) -> impl Iterator<Item = bool> + '_ {
+        self.server
+            .iter()
+            .zip(&self.server_creators)
+            .zip(&self.server_latest)
+            .map(|((&channel, &creator), &latest)| self.is_fragmented(channel, creator, latest))
+    }
+
+    /// Returns `true` for each client channel that uses fragmentation.
+    pub(super) fn client_fragmentation(&self) -> impl Iterator<Item = bool> + '_ {
+        self.client
+            .iter()
+            .zip(&self.client_creators)
+            .zip(&self.client_latest)
+            .map(|((&channel, &creator), &latest)| self.is_fragmented(channel, creator, latest))
+    }
+
+    fn is_fragmented(&self, channel: Channel, creator: ChannelCreator, latest: bool) -> bool {
+        self.fragment_threshold.is_some()
+            && matches!(channel, Channel::Unordered | Channel::Ordered)
+            && !matches!(creator, ChannelCreator::Replication)
+            && !latest
+    }
+
+    /// Returns `true` for each server channel that was created as [`Channel::UnreliableLatest`].
+    ///
+    /// Messages over such channels are sequenced, even if the channel was upgraded.
+    pub(super) fn server_latest(&self) -> impl Iterator<Item = bool> + '_ {
+        self.server_latest.iter().copied()
+    }
+
+    /// Returns `true` for each client channel that was created as [`Channel::UnreliableLatest`].
+    ///
+    /// Messages over such channels are sequenced, even if the channel was upgraded.
+    pub(super) fn client_latest(&self) -> impl Iterator<Item = bool> + '_ {
+        self.client_latest.iter().copied()
+    }
+
+    /// Returns `true` for each server channel that was created as [`Channel::UnreliableLatest`]
+    /// and upgraded to a reliable channel.
+    ///
+    /// Only the latest message for each client is sent over such channels per tick.
+    pub(super) fn server_coalescing(&self) -> impl Iterator<Item = bool> + '_ {
+        self.server
+            .iter()
+            .zip(&self.server_latest)
+            .map(|(&channel, &latest)| latest && channel != Channel::UnreliableLatest)
+    }
+
+    /// Same as [`Self::server_coalescing`], but for client channels.
+    pub(super) fn client_coalescing(&self) -> impl Iterator<Item = bool> + '_ {
+        self.client
+            .iter()
+            .zip(&self.client_latest)
+            .map(|(&channel, &latest)| latest && channel != Channel::UnreliableLatest)
+    }
+
+    /// Upgrades all channels to the closest kinds supported by the backend.
+    ///
+    /// Upgraded [`Channel::UnreliableLatest`] channels keep their sequencing, so stale messages are
+    /// still dropped, and send only the latest message for each receiver per tick. This way they
+    /// don't build up a queue of outdated messages when reliable delivery stalls.
+    /// Upgraded [`Channel::Unreliable`] channels are not coalesced, since each message carries
+    /// its own data and dropping any of them would lose it.
+    pub(crate) fn apply_capabilities(&mut self, capabilities: BackendCapabilities) {
+        let server = self.server.iter_mut().zip(&self.server_creators);
+        let client = self.client.iter_mut().zip(&self.client_creators);
+        for (channel, creator) in server.chain(client) {
+            let supported = capabilities.resolve(*channel);
+            if supported == *channel {
+                continue;
+            }
+
+            match creator {
+                ChannelCreator::Replication => {
+                    debug!("using `{supported:?}` instead of `{channel:?}` channel for {creator}")
+                }
+                ChannelCreator::Message(_) | ChannelCreator::Event(_) | ChannelCreator::Raw(_) => {
+                    warn!(
+                        "{creator} requires `{channel:?}` channel, which isn't supported by the backend, using `{supported:?}` instead"
+                    )
+                }
+            }
+            *channel = supported;
+        }
+    }
+
+    /// Returns information about a server channel by its ID.
+    pub fn server_channel(&self, id: ServerChannelId) -> Option<ChannelInfo> {
+        let &kind = self.server.get(id.get())?;
+        Some(ChannelInfo {
+            id: ChannelId::Server(id),
+            kind,
+            creator: self.server_creators[id.get()],
+            send_priority: self.server_priorities[id.get()],
+        })
+    }
+
+    /// Returns information about a client channel by its ID.
+    pub fn client_channel(&self, id: ClientChannelId) -> Option<ChannelInfo> {
+        let &kind = self.client.get(id.get())?;
+        Some(ChannelInfo {
+            id: ChannelId::Client(id),
+            kind,
+            creator: self.client_creators[id.get()],
+            send_priority: self.client_priorities[id.get()],
+        })
+    }
+
+    /// Iterates over all created channels, server channels first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::{prelude::*, state::app::StatesPlugin};
+    /// use bevy_replicon::prelude::*;
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins((StatesPlugin, RepliconPlugins));
+    /// let channels = app.world().resource::<RepliconChannels>();
+    /// for info in channels.iter() {
+    ///     info!(
+    ///         "channel {:?} is {:?} and used for {}",
+    ///         info.id, info.kind, info.creator
+    ///     );
+    /// }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = ChannelInfo> + '_ {
+        let server = self.server_ids().filter_map(|id| self.server_channel(id));
+        let client = self.client_ids().filter_map(|id| self.client_channel(id));
+        server.chain(client)
+    }
+
+    /// Returns IDs of all server channels.
+    ///
+    /// Useful for backends to create their own channels with the same IDs.
+    pub fn server_ids(&self) -> impl ExactSizeIterator<Item = ServerChannelId> + use<> {
+        (0..self.server.len()).map(ServerChannelId)
+    }
+
+    /// Returns IDs of all client channels.
+    ///
+    /// Useful for backends to create their own channels with the same IDs.
+    pub fn client_ids(&self) -> impl ExactSizeIterator<Item = ClientChannelId> + use<> {
+        (0..self.client.len()).map(ClientChannelId)
+    }
+
     /// Returns the list of registered server channels, which are used for sending data from server to client.
     ///
     /// For example, if you register a client event, it won't be reflected here.
@@ -83,6 +510,15 @@ impl RepliconChannels {
     }
 }
 
+/// Returns a sort key that puts replication channels first in their reserved order,
+/// followed by other channels with higher send priority.
+///
+/// Relies on stable sorting to preserve the order of channels with the same key.
+fn send_order_key(creator: ChannelCreator, priority: u8) -> (bool, Reverse<u8>) {
+    let replication = matches!(creator, ChannelCreator::Replication);
+    (!replication, Reverse(priority))
+}
+
 /// Constant ID of a channel for sending data from server to client.
 ///
 /// These channels are always reserved, though additional channels may be required
@@ -121,6 +557,7 @@ impl RepliconChannels {
 /// We guarantee that all mutations for a single entity arrive won't be split across messages, even if they are larger
 /// than the packet size. You can also ensure that mutations for specific entities arrive in sync by using
 /// [`SyncRelatedAppExt::sync_related_entities`](crate::server::related_entities::SyncRelatedAppExt::sync_related_entities).
+/// Mutations of specific rules can be sent over a separate channel, see [`MutationChannel`].
 ///
 /// Server events also have minimum required tick. For details, see the documentation on
 /// [`ServerMessageAppExt::make_message_independent`](crate::shared::message::server_message::ServerMessageAppExt::make_message_independent).
@@ -155,6 +592,51 @@ impl From<ServerChannel> for usize {
     }
 }
 
+impl From<ServerChannel> for ServerChannelId {
+    fn from(value: ServerChannel) -> Self {
+        Self(value as usize)
+    }
+}
+
+/**
+Channel over which mutations of a replication rule are sent.
+
+Each channel has its own acknowledgment tracking, so the priority of an entity
+accumulates independently for each channel until its mutations on that channel
+are acknowledged. This allows splitting frequently changing state from rarely
+changing one, so they don't compete for the same congestion window of the backend.
+
+Only mutations are affected. Insertions and removals are always sent over
+[`ServerChannel::Updates`] to keep entity updates atomic. When an entity has
+an insertion or removal, its mutations from all channels are merged into the
+update message.
+
+Because channels are acknowledged independently, mutations of the same entity
+for the same tick may be applied on the client at different times.
+
+See [`RuleFns::on_channel`](crate::shared::replication::registry::rule_fns::RuleFns::on_channel).
+Use [`RepliconChannels::mutation_channel`] to get the ID of the server channel.
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MutationChannel {
+    /// Sent over [`ServerChannel::Mutations`].
+    #[default]
+    Primary,
+    /// Sent over a separate unreliable channel like [`ServerChannel::Mutations`].
+    ///
+    /// The channel is created only when a rule is assigned to it,
+    /// so backends don't need to provide it otherwise.
+    Secondary,
+}
+
+impl MutationChannel {
+    /// All mutation channels.
+    pub(crate) const ALL: [Self; 2] = [Self::Primary, Self::Secondary];
+
+    /// Number of mutation channels.
+    pub(crate) const COUNT: usize = Self::ALL.len();
+}
+
 /// Constant ID of a channel for sending data from client to server.
 ///
 /// These channels are always reserved, though additional channels may be required
@@ -162,16 +644,25 @@ impl From<ServerChannel> for usize {
 ///
 /// See also [`ServerChannel`].
 pub enum ClientChannel {
-    /// For sending acks to acknowledge mutation messages received from [`ServerChannel::Mutations`].
+    /// For sending acks to acknowledge mutation messages received from all mutation channels.
+    ///
+    /// See [`RepliconChannels::mutation_channel`].
     ///
     /// This is an ordered reliable channel.
     MutationAcks,
+    /// For sending acks to acknowledge update messages received from [`ServerChannel::Updates`].
+    ///
+    /// Sent only for messages that request it, see
+    /// [`DespawnAfterAck`](crate::server::despawn_after_ack::DespawnAfterAck).
+    ///
+    /// This is an ordered reliable channel.
+    UpdateAcks,
 }
 
 impl From<ClientChannel> for Channel {
     fn from(value: ClientChannel) -> Self {
         match value {
-            ClientChannel::MutationAcks => Channel::Ordered,
+            ClientChannel::MutationAcks | ClientChannel::UpdateAcks => Channel::Ordered,
         }
     }
 }
@@ -182,13 +673,345 @@ impl From<ClientChannel> for usize {
     }
 }
 
+impl From<ClientChannel> for ClientChannelId {
+    fn from(value: ClientChannel) -> Self {
+        Self(value as usize)
+    }
+}
+
+/// ID of a channel from [`RepliconChannels::server_channels`].
+///
+/// Used for sending data from server to client. Returned by Replicon for registered channels,
+/// so it's always valid unless created manually via [`Self::new`].
+///
+/// See also [`ClientChannelId`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ServerChannelId(usize);
+
+impl ServerChannelId {
+    /// Creates an ID from a raw index.
+    ///
+    /// Intended for backends to convert indices received from the network.
+    /// The index must be less than the number of [`RepliconChannels::server_channels`].
+    pub const fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// Returns the raw index.
+    ///
+    /// Intended for backends to transmit the ID over the network.
+    pub const fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl Display for ServerChannelId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// ID of a channel from [`RepliconChannels::client_channels`].
+///
+/// Used for sending data from client to server. Returned by Replicon for registered channels,
+/// so it's always valid unless created manually via [`Self::new`].
+///
+/// See also [`ServerChannelId`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClientChannelId(usize);
+
+impl ClientChannelId {
+    /// Creates an ID from a raw index.
+    ///
+    /// Intended for backends to convert indices received from the network.
+    /// Messages with an index that isn't less than the number of [`RepliconChannels::client_channels`]
+    /// are discarded by [`ServerMessages::insert_received`](super::server_messages::ServerMessages::insert_received).
+    pub const fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// Returns the raw index.
+    ///
+    /// Intended for backends to transmit the ID over the network.
+    pub const fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl Display for ClientChannelId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Information about a channel from [`RepliconChannels`].
+#[derive(Clone, Copy, Debug)]
+pub struct ChannelInfo {
+    /// Channel ID with its direction.
+    pub id: ChannelId,
+
+    /// Delivery guarantee.
+    pub kind: Channel,
+
+    /// What the channel is used for.
+    pub creator: ChannelCreator,
+
+    /// Send priority of the channel.
+    ///
+    /// Always 0 for replication channels.
+    /// See [`RepliconChannels::set_server_send_priority`] for details.
+    pub send_priority: u8,
+}
+
+/// ID of a channel from [`RepliconChannels`] in either direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChannelId {
+    /// Channel from [`RepliconChannels::server_channels`], which sends data from server to client.
+    Server(ServerChannelId),
+    /// Channel from [`RepliconChannels::client_channels`], which sends data from client to server.
+    Client(ClientChannelId),
+}
+
+/// Describes what created a channel in [`RepliconChannels`].
+#[derive(Clone, Copy, Debug)]
+pub enum ChannelCreator {
+    /// Reserved channel from [`ServerChannel`] or [`ClientChannel`].
+    Replication,
+    /// Remote message with the type name.
+    Message(ShortName<'static>),
+    /// Remote event with the type name.
+    Event(ShortName<'static>),
+    /// Raw channel with the marker type name.
+    ///
+    /// See [`RawChannelAppExt`](crate::shared::raw_channel::RawChannelAppExt).
+    Raw(ShortName<'static>),
+}
+
+impl ChannelCreator {
+    /// Creates an instance for a message `M` with the inner type `I`.
+    ///
+    /// Events are sent using wrapper messages, so the inner type differs only for them.
+    pub(crate) fn new<M: 'static, I: 'static>() -> Self {
+        if TypeId::of::<M>() == TypeId::of::<I>() {
+            Self::Message(ShortName::of::<I>())
+        } else {
+            Self::Event(ShortName::of::<I>())
+        }
+    }
+}
+
+impl Display for ChannelCreator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Replication => write!(f, "replication"),
+            Self::Message(name) => write!(f, "message `{name}`"),
+            Self::Event(name) => write!(f, "event `{name}`"),
+            Self::Raw(name) => write!(f, "raw channel `{name}`"),
+        }
+    }
+}
+
 /// Channel delivery guarantee.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Channel {
     /// Unreliable and unordered.
     Unreliable,
+    /// Unreliable, but only the newest message is delivered.
+    ///
+    /// Replicon prepends a sequence number to each message. On receive, only the newest message
+    /// from each sender is kept, while stale or reordered messages are dropped. Useful for inputs
+    /// or aiming, where only the latest state matters. Replicated mutations already behave this
+    /// way, so it's intended for remote messages and events.
+    ///
+    /// Backends should create it as an unreliable channel. If [`BackendCapabilities`] doesn't
+    /// support unreliable channels, it's upgraded like [`Self::Unreliable`] and delivers
+    /// all messages.
+    UnreliableLatest,
     /// Reliable and unordered.
     Unordered,
     /// Reliable and ordered.
     Ordered,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default() {
+        let channels = RepliconChannels::default();
+        assert_eq!(channels.iter().count(), 4);
+        assert!(
+            channels
+                .mutation_channel(MutationChannel::Secondary)
+                .is_none()
+        );
+        assert!(
+            channels
+                .iter()
+                .all(|info| matches!(info.creator, ChannelCreator::Replication))
+        );
+    }
+
+    #[test]
+    fn lookup() {
+        let mut channels = RepliconChannels::default();
+        let server_id =
+            channels.create_server_channel(Channel::Unordered, ChannelCreator::new::<A, A>());
+        let client_id =
+            channels.create_client_channel(Channel::Unreliable, ChannelCreator::new::<B, A>());
+
+        let server_info = channels.server_channel(server_id).unwrap();
+        assert_eq!(server_info.id, ChannelId::Server(server_id));
+        assert_eq!(server_info.kind, Channel::Unordered);
+        assert!(matches!(server_info.creator, ChannelCreator::Message(_)));
+
+        let client_info = channels.client_channel(client_id).unwrap();
+        assert_eq!(client_info.id, ChannelId::Client(client_id));
+        assert_eq!(client_info.kind, Channel::Unreliable);
+        assert!(matches!(client_info.creator, ChannelCreator::Event(_)));
+
+        assert!(
+            channels
+                .server_channel(ServerChannelId::new(server_id.get() + 1))
+                .is_none()
+        );
+        assert!(
+            channels
+                .client_channel(ClientChannelId::new(client_id.get() + 1))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn capabilities() {
+        let mut channels = RepliconChannels::default();
+        let server_id =
+            channels.create_server_channel(Channel::Unordered, ChannelCreator::new::<A, A>());
+        let client_id =
+            channels.create_client_channel(Channel::Unreliable, ChannelCreator::new::<B, A>());
+
+        channels.apply_capabilities(BackendCapabilities::ORDERED_ONLY);
+
+        let mutations = channels
+            .server_channel(ServerChannel::Mutations.into())
+            .unwrap();
+        assert_eq!(mutations.kind, Channel::Ordered);
+        assert_eq!(
+            channels.server_channel(server_id).unwrap().kind,
+            Channel::Ordered
+        );
+        assert_eq!(
+            channels.client_channel(client_id).unwrap().kind,
+            Channel::Ordered
+        );
+    }
+
+    #[test]
+    fn latest_upgraded() {
+        let mut channels = RepliconChannels::default();
+        channels.set_fragment_threshold(1);
+        channels.create_server_channel(Channel::UnreliableLatest, ChannelCreator::new::<A, A>());
+        channels.create_client_channel(Channel::Unreliable, ChannelCreator::new::<B, A>());
+
+        channels.apply_capabilities(BackendCapabilities::ORDERED_ONLY);
+
+        assert_eq!(
+            channels.server_latest().collect::<Vec<_>>(),
+            [false, false, true]
+        );
+        assert_eq!(
+            channels.server_coalescing().collect::<Vec<_>>(),
+            [false, false, true]
+        );
+        assert_eq!(
+            channels.server_fragmentation().collect::<Vec<_>>(),
+            [false, false, false],
+            "latest channels shouldn't be fragmented"
+        );
+        assert_eq!(
+            channels.client_coalescing().collect::<Vec<_>>(),
+            [false, false, false],
+            "unreliable channels shouldn't be coalesced"
+        );
+        assert_eq!(
+            channels.client_fragmentation().collect::<Vec<_>>(),
+            [false, false, true]
+        );
+    }
+
+    #[test]
+    fn secondary_mutations() {
+        let mut channels = RepliconChannels::default();
+        let message_id =
+            channels.create_server_channel(Channel::Ordered, ChannelCreator::new::<A, A>());
+        channels.enable_secondary_mutations();
+        channels.enable_secondary_mutations();
+
+        let secondary_id = channels
+            .mutation_channel(MutationChannel::Secondary)
+            .unwrap();
+        assert_eq!(secondary_id.get(), message_id.get() + 1);
+        assert_eq!(channels.server_channels().len(), 4);
+
+        let info = channels.server_channel(secondary_id).unwrap();
+        assert_eq!(info.kind, Channel::Unreliable);
+        assert!(matches!(info.creator, ChannelCreator::Replication));
+        assert_eq!(
+            channels.server_send_order(),
+            [
+                ServerChannel::Updates.into(),
+                ServerChannel::Mutations.into(),
+                secondary_id,
+                message_id,
+            ]
+        );
+    }
+
+    #[test]
+    fn send_order() {
+        let mut channels = RepliconChannels::default();
+        let low_id =
+            channels.create_server_channel(Channel::Unordered, ChannelCreator::new::<A, A>());
+        let high_id =
+            channels.create_server_channel(Channel::Ordered, ChannelCreator::new::<B, B>());
+        let default_id =
+            channels.create_server_channel(Channel::Unreliable, ChannelCreator::new::<A, B>());
+        channels.set_server_send_priority(low_id, 1);
+        channels.set_server_send_priority(high_id, 2);
+
+        assert_eq!(
+            channels.server_send_order(),
+            [
+                ServerChannel::Updates.into(),
+                ServerChannel::Mutations.into(),
+                high_id,
+                low_id,
+                default_id,
+            ]
+        );
+        assert_eq!(channels.server_channel(high_id).unwrap().send_priority, 2);
+
+        let client_id =
+            channels.create_client_channel(Channel::Ordered, ChannelCreator::new::<A, A>());
+        channels.set_client_send_priority(client_id, 1);
+        assert_eq!(
+            channels.client_send_order(),
+            [
+                ClientChannel::MutationAcks.into(),
+                ClientChannel::UpdateAcks.into(),
+                client_id
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn replication_send_priority() {
+        let mut channels = RepliconChannels::default();
+        channels.set_server_send_priority(ServerChannel::Updates.into(), 1);
+    }
+
+    struct A;
+    struct B;
+}
@@ -1,6 +1,12 @@
 use bevy::prelude::*;
 use bytes::Bytes;
-use log::trace;
+use log::{debug, trace};
+
+use super::{
+    channels::{ClientChannelId, RepliconChannels, ServerChannelId},
+    fragment::{self, MessageId, Reassembly, ReassemblyLimits},
+    sequence::Sequence,
+};
 
 /// Sent and received messages for exchange between Replicon and the messaging backend.
 ///
@@ -15,26 +21,88 @@ use log::trace;
 pub struct ClientMessages {
     /// List of received messages for each channel.
     ///
-    /// Top index is channel ID.
+    /// Top index is server channel ID.
     /// Inner [`Vec`] stores received messages since the last tick.
     received_messages: Vec<Vec<Bytes>>,
 
     /// List of sent messages and their channels since the last tick.
-    sent_messages: Vec<(usize, Bytes)>,
+    sent_messages: Vec<(ClientChannelId, Bytes)>,
+
+    /// Last delivered sequences.
+    ///
+    /// Top index is server channel ID.
+    /// Present only for channels created as [`Channel::UnreliableLatest`](super::channels::Channel::UnreliableLatest)
+    /// and contains [`None`] until the first message is received.
+    received_sequences: Vec<Option<Option<Sequence>>>,
+
+    /// Next sequences to send.
+    ///
+    /// Top index is client channel ID.
+    /// Present only for channels created as [`Channel::UnreliableLatest`](super::channels::Channel::UnreliableLatest).
+    sent_sequences: Vec<Option<Sequence>>,
+
+    /// Index of the unsent message in [`Self::sent_messages`].
+    ///
+    /// Top index is client channel ID.
+    /// Present only for upgraded [`Channel::UnreliableLatest`](super::channels::Channel::UnreliableLatest),
+    /// see [`RepliconChannels::apply_capabilities`]. Reset whenever the index may become invalid.
+    sent_latest: Vec<Option<Option<usize>>>,
+
+    /// Partially received fragmented messages.
+    ///
+    /// Top index is server channel ID.
+    /// Present only for channels with fragmentation.
+    received_fragments: Vec<Option<Reassembly>>,
+
+    /// Next IDs for fragmented messages.
+    ///
+    /// Top index is client channel ID.
+    /// Present only for channels with fragmentation.
+    sent_message_ids: Vec<Option<MessageId>>,
+
+    /// See [`RepliconChannels::set_fragment_threshold`].
+    fragment_threshold: usize,
+
+    /// See [`RepliconChannels::set_max_fragmented_size`].
+    reassembly_limits: ReassemblyLimits,
 }
 
 impl ClientMessages {
-    /// Changes the size of the receive messages storage according to the number of server channels.
-    pub(crate) fn setup_server_channels(&mut self, channels_count: usize) {
-        self.received_messages.resize(channels_count, Vec::new());
+    /// Changes the size of the messages storage according to the channels.
+    pub(crate) fn setup_channels(&mut self, channels: &RepliconChannels) {
+        let server_channels = channels.server_channels();
+        self.received_messages
+            .resize(server_channels.len(), Vec::new());
+        self.received_sequences = channels
+            .server_latest()
+            .map(|latest| latest.then_some(None))
+            .collect();
+        self.sent_sequences = channels
+            .client_latest()
+            .map(|latest| latest.then(Default::default))
+            .collect();
+        self.sent_latest = channels
+            .client_coalescing()
+            .map(|coalescing| coalescing.then_some(None))
+            .collect();
+        self.received_fragments = channels
+            .server_fragmentation()
+            .map(|fragmented| fragmented.then(Default::default))
+            .collect();
+        self.sent_message_ids = channels
+            .client_fragmentation()
+            .map(|fragmented| fragmented.then(Default::default))
+            .collect();
+        self.fragment_threshold = channels.fragment_threshold().unwrap_or_default();
+        self.reassembly_limits = channels.reassembly_limits();
     }
 
     /// Returns number of received messages for a channel.
-    pub fn received_count<I: Into<usize>>(&self, channel_id: I) -> usize {
+    pub fn received_count<I: Into<ServerChannelId>>(&self, channel_id: I) -> usize {
         let channel_id = channel_id.into();
         let channel_messages = self
             .received_messages
-            .get(channel_id)
+            .get(channel_id.get())
             .unwrap_or_else(|| panic!("client should have a receive channel with id {channel_id}"));
 
         channel_messages.len()
@@ -45,13 +113,13 @@ impl ClientMessages {
     /// The messages stay in the resource. Intended for tools that need to
     /// observe inbound traffic (such as client-side replay recording) before
     /// Replicon consumes them.
-    pub fn iter_received<I: Into<usize>>(
+    pub fn iter_received<I: Into<ServerChannelId>>(
         &self,
         channel_id: I,
     ) -> impl ExactSizeIterator<Item = &Bytes> + '_ {
         let channel_id = channel_id.into();
         self.received_messages
-            .get(channel_id)
+            .get(channel_id.get())
             .unwrap_or_else(|| panic!("client should have a receive channel with id {channel_id}"))
             .iter()
     }
@@ -59,14 +127,14 @@ impl ClientMessages {
     /// Receives all available messages from the server over a channel.
     ///
     /// All messages will be drained.
-    pub(crate) fn receive<I: Into<usize>>(
+    pub(crate) fn receive<I: Into<ServerChannelId>>(
         &mut self,
         channel_id: I,
     ) -> impl Iterator<Item = Bytes> + '_ {
         let channel_id = channel_id.into();
         let channel_messages = self
             .received_messages
-            .get_mut(channel_id)
+            .get_mut(channel_id.get())
             .unwrap_or_else(|| panic!("client should have a receive channel with id {channel_id}"));
 
         if !channel_messages.is_empty() {
@@ -78,6 +146,14 @@ impl ClientMessages {
                     .map(|bytes| bytes.len())
                     .sum::<usize>()
             );
+
+            if let Some(last_sequence) = &mut self.received_sequences[channel_id.get()] {
+                retain_latest(channel_messages, last_sequence);
+            }
+            if let Some(fragments) = &mut self.received_fragments[channel_id.get()] {
+                let limits = self.reassembly_limits;
+                channel_messages.retain_mut(|message| fragments.process(message, limits));
+            }
         }
 
         channel_messages.drain(..)
@@ -90,13 +166,66 @@ impl ClientMessages {
     /// Should only be called from the messaging backend.
     ///
     /// </div>
-    pub fn send<I: Into<usize>, B: Into<Bytes>>(&mut self, channel_id: I, message: B) {
+    pub fn send<I: Into<ClientChannelId>, B: Into<Bytes>>(&mut self, channel_id: I, message: B) {
         let channel_id = channel_id.into();
-        let message: Bytes = message.into();
+        let mut message: Bytes = message.into();
+        if let Some(sequence) = self
+            .sent_sequences
+            .get_mut(channel_id.get())
+            .and_then(Option::as_mut)
+        {
+            message = sequence.advance().prepend(message);
+        }
+
+        if let Some(sent_latest) = self
+            .sent_latest
+            .get_mut(channel_id.get())
+            .and_then(Option::as_mut)
+        {
+            if let Some(index) = *sent_latest {
+                trace!(
+                    "replacing unsent message over channel {channel_id} with {} bytes",
+                    message.len()
+                );
+                self.sent_messages[index].1 = message;
+                return;
+            }
+            *sent_latest = Some(self.sent_messages.len());
+        }
 
         trace!("sending {} bytes over channel {channel_id}", message.len());
 
-        self.sent_messages.push((channel_id, message));
+        if let Some(next_id) = self
+            .sent_message_ids
+            .get_mut(channel_id.get())
+            .and_then(Option::as_mut)
+        {
+            for fragment in fragment::split(message, self.fragment_threshold, next_id) {
+                self.sent_messages.push((channel_id, fragment));
+            }
+        } else {
+            self.sent_messages.push((channel_id, message));
+        }
+    }
+
+    /// Retains only the sent messages specified by the predicate, allowing to modify them.
+    pub(crate) fn retain_sent_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut (ClientChannelId, Bytes)) -> bool,
+    {
+        self.sent_messages.retain_mut(f);
+        self.reset_sent_latest();
+    }
+
+    /// Retains only the received messages specified by the predicate, allowing to modify them.
+    pub(crate) fn retain_received_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(ServerChannelId, &mut Bytes) -> bool,
+    {
+        for (index, channel_messages) in self.received_messages.iter_mut().enumerate() {
+            let channel_id = ServerChannelId::new(index);
+            channel_messages.retain_mut(|message| f(channel_id, message));
+        }
     }
 
     pub(crate) fn clear(&mut self) {
@@ -104,6 +233,19 @@ impl ClientMessages {
             channel_messages.clear();
         }
         self.sent_messages.clear();
+        self.reset_sent_latest();
+        for last_sequence in self.received_sequences.iter_mut().flatten() {
+            *last_sequence = None;
+        }
+        for sequence in self.sent_sequences.iter_mut().flatten() {
+            *sequence = Default::default();
+        }
+        for fragments in self.received_fragments.iter_mut().flatten() {
+            fragments.clear();
+        }
+        for next_id in self.sent_message_ids.iter_mut().flatten() {
+            *next_id = Default::default();
+        }
     }
 
     /// Returns an iterator over sent messages without consuming them.
@@ -111,7 +253,7 @@ impl ClientMessages {
     /// Unlike [`Self::drain_sent`], the messages stay in the resource. Intended for
     /// tools that need to observe outbound traffic before the messaging backend drains
     /// them.
-    pub fn iter_sent(&self) -> impl ExactSizeIterator<Item = (usize, &Bytes)> + '_ {
+    pub fn iter_sent(&self) -> impl ExactSizeIterator<Item = (ClientChannelId, &Bytes)> + '_ {
         self.sent_messages
             .iter()
             .map(|(channel_id, bytes)| (*channel_id, bytes))
@@ -124,10 +266,18 @@ impl ClientMessages {
     /// Should only be called from the messaging backend.
     ///
     /// </div>
-    pub fn drain_sent(&mut self) -> impl Iterator<Item = (usize, Bytes)> + '_ {
+    pub fn drain_sent(&mut self) -> impl Iterator<Item = (ClientChannelId, Bytes)> + '_ {
+        self.reset_sent_latest();
         self.sent_messages.drain(..)
     }
 
+    /// Forgets indices of unsent messages, so the next messages will be added as new ones.
+    fn reset_sent_latest(&mut self) {
+        for sent_latest in self.sent_latest.iter_mut().flatten() {
+            *sent_latest = None;
+        }
+    }
+
     /// Adds a message from the server to the list of received messages.
     ///
     /// <div class="warning">
@@ -135,13 +285,57 @@ impl ClientMessages {
     /// Should only be called from the messaging backend.
     ///
     /// </div>
-    pub fn insert_received<I: Into<usize>, B: Into<Bytes>>(&mut self, channel_id: I, message: B) {
+    pub fn insert_received<I: Into<ServerChannelId>, B: Into<Bytes>>(
+        &mut self,
+        channel_id: I,
+        message: B,
+    ) {
         let channel_id = channel_id.into();
         let channel_messages = self
             .received_messages
-            .get_mut(channel_id)
+            .get_mut(channel_id.get())
             .unwrap_or_else(|| panic!("client should have a channel with id {channel_id}"));
 
         channel_messages.push(message.into());
     }
 }
+
+/// Keeps only the newest message and strips the sequence from it.
+///
+/// Messages that are not newer than the last delivered one are dropped.
+fn retain_latest(messages: &mut Vec<Bytes>, last_sequence: &mut Option<Sequence>) {
+    let newest = messages
+        .iter()
+        .filter_map(|message| Sequence::peek(message))
+        .filter(|&sequence| last_sequence.is_none_or(|last| sequence.is_newer(last)))
+        .reduce(|newest, sequence| {
+            if sequence.is_newer(newest) {
+                sequence
+            } else {
+                newest
+            }
+        });
+
+    let Some(newest) = newest else {
+        trace!("dropping {} outdated message(s)", messages.len());
+        messages.clear();
+        return;
+    };
+
+    *last_sequence = Some(newest);
+    let mut delivered = false;
+    messages.retain_mut(|message| {
+        let Some(sequence) = Sequence::peek(message) else {
+            debug!("dropping message without sequence");
+            return false;
+        };
+        if delivered || sequence != newest {
+            trace!("dropping outdated message");
+            return false;
+        }
+
+        delivered = true;
+        Sequence::strip(message);
+        true
+    });
+}
@@ -0,0 +1,355 @@
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+use bevy::platform::collections::HashMap;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use log::{debug, trace};
+
+/// Marks a message that was sent without splitting.
+const WHOLE: u8 = 0;
+
+/// Marks a fragment of a larger message.
+const FRAGMENT: u8 = 1;
+
+/// Size of [`FRAGMENT`] header: message ID, fragment index and the number of fragments.
+const FRAGMENT_HEADER_SIZE: usize = 1 + 3 * size_of::<u16>();
+
+/// Maximum number of partially received messages from a single sender over a channel.
+///
+/// When exceeded, the least recently updated message is evicted.
+const MAX_PENDING: usize = 8;
+
+/// ID of a fragmented message, unique for each sender and channel.
+///
+/// Wraps around on overflow, which is fine since old messages
+/// are reassembled by the time the ID is reused.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) struct MessageId(u16);
+
+impl MessageId {
+    /// Returns the current ID and advances to the next one.
+    fn advance(&mut self) -> Self {
+        let current = *self;
+        self.0 = self.0.wrapping_add(1);
+        current
+    }
+}
+
+/// Prepends a header to the message, splitting it into fragments if it's larger than `threshold`.
+///
+/// If the message would require more than [`u16::MAX`] fragments, the fragment size is increased.
+pub(super) fn split(message: Bytes, threshold: usize, next_id: &mut MessageId) -> Vec<Bytes> {
+    if message.len() <= threshold {
+        let mut whole = BytesMut::with_capacity(1 + message.len());
+        whole.put_u8(WHOLE);
+        whole.put(message);
+        return vec![whole.freeze()];
+    }
+
+    let fragment_size = threshold.max(message.len().div_ceil(usize::from(u16::MAX)));
+    let count = message.len().div_ceil(fragment_size);
+    let id = next_id.advance();
+    trace!(
+        "splitting {} bytes into {count} fragments with `{id:?}`",
+        message.len()
+    );
+
+    message
+        .chunks(fragment_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut fragment = BytesMut::with_capacity(FRAGMENT_HEADER_SIZE + chunk.len());
+            fragment.put_u8(FRAGMENT);
+            fragment.put_u16_le(id.0);
+            fragment.put_u16_le(index as u16);
+            fragment.put_u16_le(count as u16);
+            fragment.put_slice(chunk);
+            fragment.freeze()
+        })
+        .collect()
+}
+
+/// Limits for messages reassembled from fragments.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct ReassemblyLimits {
+    /// Maximum size of a reassembled message in bytes.
+    max_size: usize,
+
+    /// Maximum number of fragments the sender could split a message of [`Self::max_size`] into.
+    max_fragments: usize,
+}
+
+impl ReassemblyLimits {
+    /// Creates limits for the fragment threshold and the maximum message size.
+    ///
+    /// See [`RepliconChannels::set_max_fragmented_size`](super::channels::RepliconChannels::set_max_fragmented_size).
+    pub(super) fn new(threshold: usize, max_size: usize) -> Self {
+        Self {
+            max_size,
+            max_fragments: max_size.div_ceil(threshold).min(u16::MAX.into()),
+        }
+    }
+}
+
+/// Partially received messages from a single sender over a channel.
+#[derive(Default)]
+pub(super) struct Reassembly {
+    pending: HashMap<u16, PendingMessage>,
+
+    /// Number of processed fragments.
+    ///
+    /// Used to find the least recently updated message for eviction.
+    processed: u64,
+}
+
+impl Reassembly {
+    /// Strips the header from the message.
+    ///
+    /// Returns `true` if the message is whole or completes a fragmented message, in which case
+    /// it's replaced with the reassembled message. Otherwise the fragment is buffered.
+    ///
+    /// Fragments of messages that exceed `limits` are dropped. If there are already
+    /// [`MAX_PENDING`] partially received messages, a fragment of a new message evicts
+    /// the least recently updated one.
+    pub(super) fn process(&mut self, message: &mut Bytes, limits: ReassemblyLimits) -> bool {
+        if message.is_empty() {
+            debug!("dropping message without fragmentation header");
+            return false;
+        }
+
+        match message.get_u8() {
+            WHOLE => true,
+            FRAGMENT => {
+                if message.remaining() < FRAGMENT_HEADER_SIZE - 1 {
+                    debug!("dropping fragment with incomplete header");
+                    return false;
+                }
+                let id = message.get_u16_le();
+                let index: usize = message.get_u16_le().into();
+                let count: usize = message.get_u16_le().into();
+                if index >= count {
+                    debug!("dropping fragment {index} for message {id} with {count} fragments");
+                    return false;
+                }
+                if count > limits.max_fragments {
+                    debug!(
+                        "dropping fragment for message {id} with {count} fragments, which exceeds the limit of {}",
+                        limits.max_fragments
+                    );
+                    return false;
+                }
+
+                if !self.pending.contains_key(&id) && self.pending.len() >= MAX_PENDING {
+                    self.evict_stale();
+                }
+
+                self.processed += 1;
+                let pending = self
+                    .pending
+                    .entry(id)
+                    .or_insert_with(|| PendingMessage::new(count));
+                if pending.fragments.len() != count {
+                    debug!("dropping fragment with mismatched count for message {id}");
+                    return false;
+                }
+                pending.last_processed = self.processed;
+
+                if pending.fragments[index].is_some() {
+                    debug!("dropping duplicate fragment {index} for message {id}");
+                    return false;
+                }
+
+                pending.len += message.remaining();
+                if pending.len > limits.max_size {
+                    debug!(
+                        "dropping message {id} that exceeds the limit of {} bytes",
+                        limits.max_size
+                    );
+                    self.pending.remove(&id);
+                    return false;
+                }
+
+                pending.fragments[index] = Some(mem::take(message));
+                pending.received += 1;
+                if pending.received < count {
+                    return false;
+                }
+
+                let pending = self.pending.remove(&id).expect("message should be pending");
+                let len = pending.len;
+                let mut reassembled = BytesMut::with_capacity(len);
+                for fragment in pending.fragments.into_iter().flatten() {
+                    reassembled.put(fragment);
+                }
+                trace!("reassembled {len} bytes from {count} fragments for message {id}");
+                *message = reassembled.freeze();
+
+                true
+            }
+            kind => {
+                debug!("dropping message with unknown fragmentation header {kind}");
+                false
+            }
+        }
+    }
+
+    /// Removes the least recently updated message.
+    fn evict_stale(&mut self) {
+        let stale = self
+            .pending
+            .iter()
+            .min_by_key(|(_, pending)| pending.last_processed)
+            .map(|(&id, _)| id);
+        if let Some(id) = stale {
+            debug!("evicting stale message {id}");
+            self.pending.remove(&id);
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+struct PendingMessage {
+    fragments: Vec<Option<Bytes>>,
+    received: usize,
+
+    /// Total size of received fragments in bytes.
+    len: usize,
+
+    /// Value of [`Reassembly::processed`] when the last fragment was received.
+    last_processed: u64,
+}
+
+impl PendingMessage {
+    fn new(count: usize) -> Self {
+        Self {
+            fragments: vec![None; count],
+            received: 0,
+            len: 0,
+            last_processed: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: ReassemblyLimits = ReassemblyLimits {
+        max_size: 16,
+        max_fragments: 8,
+    };
+
+    #[test]
+    fn whole() {
+        let mut id = MessageId::default();
+        let mut fragments = split(Bytes::from_static(&[1, 2]), 2, &mut id);
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(id, MessageId(0), "ID should be used only for fragments");
+
+        let mut reassembly = Reassembly::default();
+        let mut message = fragments.pop().unwrap();
+        assert!(reassembly.process(&mut message, LIMITS));
+        assert_eq!(message, [1, 2].as_slice());
+    }
+
+    #[test]
+    fn fragmented() {
+        let mut id = MessageId::default();
+        let fragments = split(Bytes::from_static(&[1, 2, 3, 4, 5]), 2, &mut id);
+        assert_eq!(fragments.len(), 3);
+        assert_eq!(id, MessageId(1));
+
+        let mut reassembly = Reassembly::default();
+        let mut delivered = Vec::new();
+        for mut fragment in fragments.into_iter().rev() {
+            if reassembly.process(&mut fragment, LIMITS) {
+                delivered.push(fragment);
+            }
+        }
+        assert_eq!(delivered, [[1, 2, 3, 4, 5].as_slice()]);
+        assert!(reassembly.pending.is_empty());
+    }
+
+    #[test]
+    fn duplicate() {
+        let mut id = MessageId::default();
+        let fragments = split(Bytes::from_static(&[1, 2, 3]), 2, &mut id);
+
+        let mut reassembly = Reassembly::default();
+        let mut first = fragments[0].clone();
+        assert!(!reassembly.process(&mut first, LIMITS));
+        let mut duplicate = fragments[0].clone();
+        assert!(!reassembly.process(&mut duplicate, LIMITS));
+        let mut last = fragments[1].clone();
+        assert!(reassembly.process(&mut last, LIMITS));
+        assert_eq!(last, [1, 2, 3].as_slice());
+    }
+
+    #[test]
+    fn malformed() {
+        let mut reassembly = Reassembly::default();
+        assert!(!reassembly.process(&mut Bytes::new(), LIMITS));
+        assert!(!reassembly.process(&mut Bytes::from_static(&[FRAGMENT, 0]), LIMITS));
+        assert!(!reassembly.process(
+            &mut Bytes::from_static(&[FRAGMENT, 0, 0, 1, 0, 1, 0]),
+            LIMITS
+        ));
+        assert!(!reassembly.process(&mut Bytes::from_static(&[2]), LIMITS));
+    }
+
+    #[test]
+    fn too_many_fragments() {
+        let mut reassembly = Reassembly::default();
+        let mut fragment = fragment_with_header(0, 0, u16::MAX);
+        assert!(!reassembly.process(&mut fragment, LIMITS));
+        assert!(reassembly.pending.is_empty());
+    }
+
+    #[test]
+    fn too_large() {
+        let mut id = MessageId::default();
+        let fragments = split(Bytes::from_static(&[0; 20]), 4, &mut id);
+        assert_eq!(fragments.len(), 5);
+
+        let mut reassembly = Reassembly::default();
+        for mut fragment in fragments {
+            assert!(!reassembly.process(&mut fragment, LIMITS));
+        }
+        assert!(reassembly.pending.is_empty());
+    }
+
+    #[test]
+    fn pending_limit() {
+        let mut reassembly = Reassembly::default();
+        for id in 0..u16::MAX {
+            let mut fragment = fragment_with_header(id, 0, 2);
+            assert!(!reassembly.process(&mut fragment, LIMITS));
+        }
+        assert_eq!(reassembly.pending.len(), MAX_PENDING);
+
+        let mut fragment = fragment_with_header(u16::MAX - 1, 1, 2);
+        assert!(
+            reassembly.process(&mut fragment, LIMITS),
+            "recent message shouldn't be evicted"
+        );
+
+        let mut fragment = fragment_with_header(0, 1, 2);
+        assert!(
+            !reassembly.process(&mut fragment, LIMITS),
+            "stale message should be evicted"
+        );
+    }
+
+    fn fragment_with_header(id: u16, index: u16, count: u16) -> Bytes {
+        let mut fragment = BytesMut::new();
+        fragment.put_u8(FRAGMENT);
+        fragment.put_u16_le(id);
+        fragment.put_u16_le(index);
+        fragment.put_u16_le(count);
+        fragment.put_u8(0);
+        fragment.freeze()
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use bevy::prelude::*;
+use bytes::Bytes;
+
+/**
+Byte-level transformation applied to messages right before they are passed to the
+messaging backend and right after they are received from it.
+
+Can be used for application-layer encryption or compression with per-client dictionaries
+without modifying the backend.
+
+Transformations are applied to the final messages, after replication messages are split,
+so each transformed message can be decoded independently.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bytes::Bytes;
+
+/// Toy cipher that XORs all bytes with a key.
+struct Xor(u8);
+
+impl MessageTransform for Xor {
+    fn outbound(&mut self, message: Bytes) -> Result<Bytes> {
+        Ok(message.iter().map(|byte| byte ^ self.0).collect())
+    }
+
+    fn inbound(&mut self, message: Bytes) -> Result<Bytes> {
+        self.outbound(message)
+    }
+}
+
+fn add_transforms(add: On<Add, ConnectedClient>, mut commands: Commands) {
+    commands
+        .entity(add.entity)
+        .insert(MessageTransforms::default().with(Xor(0x5A)));
+}
+```
+*/
+pub trait MessageTransform: Send + Sync + 'static {
+    /// Transforms a message before sending.
+    fn outbound(&mut self, message: Bytes) -> Result<Bytes>;
+
+    /// Reverts [`Self::outbound`] for a received message.
+    fn inbound(&mut self, message: Bytes) -> Result<Bytes>;
+}
+
+/// Transformations applied to messages of a single connection.
+///
+/// On the server it's a component that can be inserted on entities with
+/// [`ConnectedClient`](super::connected_client::ConnectedClient). On the client it's
+/// available as [`ClientMessageTransforms`] resource.
+///
+/// For sending, transformations are applied in the order they were added.
+/// For receiving, in reverse order. If any transformation fails, the message
+/// is discarded and reported as [`ReplicationError`](crate::shared::replication_error::ReplicationError).
+///
+/// If a transformation increases the message size, make sure to account for it in
+/// [`ConnectedClient::max_size`](super::connected_client::ConnectedClient::max_size)
+/// to keep mutations within a single packet.
+///
+/// See also [`MessageTransform`].
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct MessageTransforms(Vec<Box<dyn MessageTransform>>);
+
+impl MessageTransforms {
+    /// Adds a transformation to the end and returns itself.
+    #[must_use]
+    pub fn with(mut self, transform: impl MessageTransform) -> Self {
+        self.0.push(Box::new(transform));
+        self
+    }
+
+    /// Applies all transformations for sending.
+    pub(crate) fn outbound(&mut self, mut message: Bytes) -> Result<Bytes> {
+        for transform in &mut self.0 {
+            message = transform.outbound(message)?;
+        }
+
+        Ok(message)
+    }
+
+    /// Applies all transformations in reverse for receiving.
+    pub(crate) fn inbound(&mut self, mut message: Bytes) -> Result<Bytes> {
+        for transform in self.0.iter_mut().rev() {
+            message = transform.inbound(message)?;
+        }
+
+        Ok(message)
+    }
+}
+
+/// Message transformations for the connection to the server.
+///
+/// See [`MessageTransforms`] for details.
+///
+/// Inserted as resource by [`ClientPlugin`](crate::prelude::ClientPlugin) and
+/// kept across reconnects.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct ClientMessageTransforms(pub MessageTransforms);
@@ -0,0 +1,72 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// Sequence number prepended to messages sent over [`Channel::UnreliableLatest`](super::channels::Channel::UnreliableLatest).
+///
+/// Wraps around on overflow. A sequence is considered newer if it's ahead by less than half of the range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) struct Sequence(u16);
+
+impl Sequence {
+    const SIZE: usize = size_of::<u16>();
+
+    /// Returns the current sequence and advances to the next one.
+    pub(super) fn advance(&mut self) -> Self {
+        let current = *self;
+        self.0 = self.0.wrapping_add(1);
+        current
+    }
+
+    /// Returns `true` if this sequence was sent after `other`.
+    pub(super) fn is_newer(self, other: Self) -> bool {
+        self != other && self.0.wrapping_sub(other.0) < u16::MAX / 2
+    }
+
+    /// Returns a new message with the sequence written at the beginning.
+    pub(super) fn prepend(self, message: Bytes) -> Bytes {
+        let mut prefixed = BytesMut::with_capacity(Self::SIZE + message.len());
+        prefixed.put_u16_le(self.0);
+        prefixed.put(message);
+        prefixed.freeze()
+    }
+
+    /// Reads the sequence without consuming it.
+    ///
+    /// Returns [`None`] if the message is too short.
+    pub(super) fn peek(message: &[u8]) -> Option<Self> {
+        let bytes = message.get(..Self::SIZE)?;
+        Some(Self(u16::from_le_bytes(bytes.try_into().ok()?)))
+    }
+
+    /// Removes the sequence from the beginning of the message.
+    ///
+    /// The message should be previously validated with [`Self::peek`].
+    pub(super) fn strip(message: &mut Bytes) {
+        message.advance(Self::SIZE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer() {
+        assert!(Sequence(1).is_newer(Sequence(0)));
+        assert!(!Sequence(0).is_newer(Sequence(1)));
+        assert!(!Sequence(0).is_newer(Sequence(0)));
+        assert!(Sequence(0).is_newer(Sequence(u16::MAX)));
+        assert!(!Sequence(u16::MAX).is_newer(Sequence(0)));
+    }
+
+    #[test]
+    fn prefix() {
+        let mut sequence = Sequence(u16::MAX);
+        let mut message = sequence.advance().prepend(Bytes::from_static(&[1, 2]));
+        assert_eq!(sequence, Sequence(0));
+        assert_eq!(Sequence::peek(&message), Some(Sequence(u16::MAX)));
+
+        Sequence::strip(&mut message);
+        assert_eq!(message, [1, 2].as_slice());
+        assert_eq!(Sequence::peek(&[0]), None);
+    }
+}
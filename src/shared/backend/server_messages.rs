@@ -1,6 +1,12 @@
-use bevy::prelude::*;
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
 use bytes::Bytes;
-use log::trace;
+use log::{debug, trace};
+
+use super::{
+    channels::{ClientChannelId, RepliconChannels, ServerChannelId},
+    fragment::{self, MessageId, Reassembly, ReassemblyLimits},
+    sequence::Sequence,
+};
 
 /// Sent and received messages for exchange between Replicon and the messaging backend.
 ///
@@ -15,18 +21,84 @@ use log::trace;
 pub struct ServerMessages {
     /// List of received messages for each channel.
     ///
-    /// Top index is channel ID.
+    /// Top index is client channel ID.
     /// Inner [`Vec`] stores received messages since the last tick.
     received_messages: Vec<Vec<(Entity, Bytes)>>,
 
     /// List of sent messages for each channel since the last tick.
-    sent_messages: Vec<(Entity, usize, Bytes)>,
+    sent_messages: Vec<(Entity, ServerChannelId, Bytes)>,
+
+    /// Last delivered sequences for each client.
+    ///
+    /// Top index is client channel ID.
+    /// Present only for channels created as [`Channel::UnreliableLatest`](super::channels::Channel::UnreliableLatest).
+    received_sequences: Vec<Option<EntityHashMap<Sequence>>>,
+
+    /// Next sequences to send for each client.
+    ///
+    /// Top index is server channel ID.
+    /// Present only for channels created as [`Channel::UnreliableLatest`](super::channels::Channel::UnreliableLatest).
+    sent_sequences: Vec<Option<EntityHashMap<Sequence>>>,
+
+    /// Indices of unsent messages in [`Self::sent_messages`] for each client.
+    ///
+    /// Top index is server channel ID.
+    /// Present only for upgraded [`Channel::UnreliableLatest`](super::channels::Channel::UnreliableLatest), see [`RepliconChannels::apply_capabilities`].
+    /// Reset whenever the indices may become invalid.
+    sent_latest: Vec<Option<EntityHashMap<usize>>>,
+
+    /// Partially received fragmented messages for each client.
+    ///
+    /// Top index is client channel ID.
+    /// Present only for channels with fragmentation.
+    received_fragments: Vec<Option<EntityHashMap<Reassembly>>>,
+
+    /// Next IDs for fragmented messages for each client.
+    ///
+    /// Top index is server channel ID.
+    /// Present only for channels with fragmentation.
+    sent_message_ids: Vec<Option<EntityHashMap<MessageId>>>,
+
+    /// See [`RepliconChannels::set_fragment_threshold`].
+    fragment_threshold: usize,
+
+    /// See [`RepliconChannels::set_max_fragmented_size`].
+    reassembly_limits: ReassemblyLimits,
+
+    /// Clients that sent messages over channels that aren't registered.
+    ///
+    /// Drained by the server to report errors.
+    invalid_channels: Vec<(Entity, ClientChannelId)>,
 }
 
 impl ServerMessages {
-    /// Changes the size of the receive messages storage according to the number of client channels.
-    pub(crate) fn setup_client_channels(&mut self, channels_count: usize) {
-        self.received_messages.resize(channels_count, Vec::new());
+    /// Changes the size of the messages storage according to the channels.
+    pub(crate) fn setup_channels(&mut self, channels: &RepliconChannels) {
+        let client_channels = channels.client_channels();
+        self.received_messages
+            .resize(client_channels.len(), Vec::new());
+        self.received_sequences = channels
+            .client_latest()
+            .map(|latest| latest.then(Default::default))
+            .collect();
+        self.sent_sequences = channels
+            .server_latest()
+            .map(|latest| latest.then(Default::default))
+            .collect();
+        self.sent_latest = channels
+            .server_coalescing()
+            .map(|coalescing| coalescing.then(Default::default))
+            .collect();
+        self.received_fragments = channels
+            .client_fragmentation()
+            .map(|fragmented| fragmented.then(Default::default))
+            .collect();
+        self.sent_message_ids = channels
+            .server_fragmentation()
+            .map(|fragmented| fragmented.then(Default::default))
+            .collect();
+        self.fragment_threshold = channels.fragment_threshold().unwrap_or_default();
+        self.reassembly_limits = channels.reassembly_limits();
     }
 
     /// Removes a disconnected client.
@@ -35,6 +107,23 @@ impl ServerMessages {
             receive_channel.retain(|&(entity, _)| entity != client);
         }
         self.sent_messages.retain(|&(entity, ..)| entity != client);
+        self.reset_sent_latest();
+        self.invalid_channels
+            .retain(|&(entity, _)| entity != client);
+        for sequences in self
+            .received_sequences
+            .iter_mut()
+            .chain(&mut self.sent_sequences)
+            .flatten()
+        {
+            sequences.remove(&client);
+        }
+        for fragments in self.received_fragments.iter_mut().flatten() {
+            fragments.remove(&client);
+        }
+        for message_ids in self.sent_message_ids.iter_mut().flatten() {
+            message_ids.remove(&client);
+        }
     }
 
     /// Returns an iterator over received messages from clients on a channel without consuming them.
@@ -42,13 +131,13 @@ impl ServerMessages {
     /// The messages stay in the resource. Intended for tools that need to
     /// observe inbound traffic (such as replay recording) before Replicon
     /// consumes them.
-    pub fn iter_received<I: Into<usize>>(
+    pub fn iter_received<I: Into<ClientChannelId>>(
         &self,
         channel_id: I,
     ) -> impl ExactSizeIterator<Item = (Entity, &Bytes)> + '_ {
         let channel_id = channel_id.into();
         self.received_messages
-            .get(channel_id)
+            .get(channel_id.get())
             .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"))
             .iter()
             .map(|(entity, bytes)| (*entity, bytes))
@@ -57,14 +146,14 @@ impl ServerMessages {
     /// Receives all available messages from clients over a channel.
     ///
     /// All messages will be drained.
-    pub(crate) fn receive<I: Into<usize>>(
+    pub(crate) fn receive<I: Into<ClientChannelId>>(
         &mut self,
         channel_id: I,
     ) -> impl Iterator<Item = (Entity, Bytes)> + '_ {
         let channel_id = channel_id.into();
         let channel_messages = self
             .received_messages
-            .get_mut(channel_id)
+            .get_mut(channel_id.get())
             .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"));
 
         if !channel_messages.is_empty() {
@@ -76,6 +165,19 @@ impl ServerMessages {
                     .map(|(_, bytes)| bytes.len())
                     .sum::<usize>()
             );
+
+            if let Some(last_sequences) = &mut self.received_sequences[channel_id.get()] {
+                retain_latest(channel_messages, last_sequences);
+            }
+            if let Some(fragments) = &mut self.received_fragments[channel_id.get()] {
+                let limits = self.reassembly_limits;
+                channel_messages.retain_mut(|(client, message)| {
+                    fragments
+                        .entry(*client)
+                        .or_default()
+                        .process(message, limits)
+                });
+            }
         }
 
         channel_messages.drain(..)
@@ -88,26 +190,82 @@ impl ServerMessages {
     /// Should only be called from the messaging backend.
     ///
     /// </div>
-    pub fn send<I: Into<usize>, B: Into<Bytes>>(
+    pub fn send<I: Into<ServerChannelId>, B: Into<Bytes>>(
         &mut self,
         client: Entity,
         channel_id: I,
         message: B,
     ) {
         let channel_id = channel_id.into();
-        let message: Bytes = message.into();
+        let mut message: Bytes = message.into();
+        if let Some(sequences) = self
+            .sent_sequences
+            .get_mut(channel_id.get())
+            .and_then(Option::as_mut)
+        {
+            let sequence = sequences.entry(client).or_default().advance();
+            message = sequence.prepend(message);
+        }
+
+        if let Some(sent_latest) = self
+            .sent_latest
+            .get_mut(channel_id.get())
+            .and_then(Option::as_mut)
+        {
+            if let Some(&index) = sent_latest.get(&client) {
+                trace!(
+                    "replacing unsent message over channel {channel_id} with {} bytes",
+                    message.len()
+                );
+                self.sent_messages[index].2 = message;
+                return;
+            }
+            sent_latest.insert(client, self.sent_messages.len());
+        }
 
         trace!("sending {} bytes over channel {channel_id}", message.len());
 
-        self.sent_messages.push((client, channel_id, message));
+        if let Some(message_ids) = self
+            .sent_message_ids
+            .get_mut(channel_id.get())
+            .and_then(Option::as_mut)
+        {
+            let next_id = message_ids.entry(client).or_default();
+            for fragment in fragment::split(message, self.fragment_threshold, next_id) {
+                self.sent_messages.push((client, channel_id, fragment));
+            }
+        } else {
+            self.sent_messages.push((client, channel_id, message));
+        }
     }
 
     /// Retains only the messages specified by the predicate.
     pub fn retain_sent<F>(&mut self, f: F)
     where
-        F: FnMut(&(Entity, usize, Bytes)) -> bool,
+        F: FnMut(&(Entity, ServerChannelId, Bytes)) -> bool,
+    {
+        self.sent_messages.retain(f);
+        self.reset_sent_latest();
+    }
+
+    /// Like [`Self::retain_sent`], but allows modifying the messages.
+    pub(crate) fn retain_sent_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut (Entity, ServerChannelId, Bytes)) -> bool,
     {
-        self.sent_messages.retain(f)
+        self.sent_messages.retain_mut(f);
+        self.reset_sent_latest();
+    }
+
+    /// Retains only the received messages specified by the predicate, allowing to modify them.
+    pub(crate) fn retain_received_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(ClientChannelId, &mut (Entity, Bytes)) -> bool,
+    {
+        for (index, channel_messages) in self.received_messages.iter_mut().enumerate() {
+            let channel_id = ClientChannelId::new(index);
+            channel_messages.retain_mut(|message| f(channel_id, message));
+        }
     }
 
     /// Returns an iterator over sent messages without consuming them.
@@ -115,7 +273,9 @@ impl ServerMessages {
     /// Unlike [`Self::drain_sent`], the messages stay in the resource. Intended for
     /// tools that need to observe outbound traffic (e.g. server-side replay recording)
     /// before the messaging backend drains them.
-    pub fn iter_sent(&self) -> impl ExactSizeIterator<Item = (Entity, usize, &Bytes)> + '_ {
+    pub fn iter_sent(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (Entity, ServerChannelId, &Bytes)> + '_ {
         self.sent_messages
             .iter()
             .map(|(entity, channel_id, bytes)| (*entity, *channel_id, bytes))
@@ -128,10 +288,20 @@ impl ServerMessages {
     /// Should only be called from the messaging backend.
     ///
     /// </div>
-    pub fn drain_sent(&mut self) -> impl ExactSizeIterator<Item = (Entity, usize, Bytes)> + '_ {
+    pub fn drain_sent(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = (Entity, ServerChannelId, Bytes)> + '_ {
+        self.reset_sent_latest();
         self.sent_messages.drain(..)
     }
 
+    /// Forgets indices of unsent messages, so the next messages will be added as new ones.
+    fn reset_sent_latest(&mut self) {
+        for sent_latest in self.sent_latest.iter_mut().flatten() {
+            sent_latest.clear();
+        }
+    }
+
     /// Adds a message from a client to the list of received messages.
     ///
     /// <div class="warning">
@@ -139,25 +309,88 @@ impl ServerMessages {
     /// Should only be called from the messaging backend.
     ///
     /// </div>
-    pub fn insert_received<I: Into<usize>, B: Into<Bytes>>(
+    ///
+    /// Messages over channels that aren't registered are discarded and reported as
+    /// [`ReplicationErrorKind::Deserialize`](crate::shared::replication_error::ReplicationErrorKind::Deserialize)
+    /// for the client, so [`ReplicationErrorSettings::client_policy`](crate::shared::replication_error::ReplicationErrorSettings::client_policy)
+    /// applies to it.
+    pub fn insert_received<I: Into<ClientChannelId>, B: Into<Bytes>>(
         &mut self,
         client: Entity,
         channel_id: I,
         message: B,
     ) {
         let channel_id = channel_id.into();
-        let receive_channel = self
-            .received_messages
-            .get_mut(channel_id)
-            .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"));
+        let Some(receive_channel) = self.received_messages.get_mut(channel_id.get()) else {
+            self.invalid_channels.push((client, channel_id));
+            return;
+        };
 
         receive_channel.push((client, message.into()));
     }
 
+    /// Removes all clients that sent messages over unknown channels since the last call.
+    pub(crate) fn drain_invalid_channels(
+        &mut self,
+    ) -> impl Iterator<Item = (Entity, ClientChannelId)> + '_ {
+        self.invalid_channels.drain(..)
+    }
+
     pub(crate) fn clear(&mut self) {
         for receive_channel in &mut self.received_messages {
             receive_channel.clear();
         }
         self.sent_messages.clear();
+        self.reset_sent_latest();
+        self.invalid_channels.clear();
+        for sequences in self
+            .received_sequences
+            .iter_mut()
+            .chain(&mut self.sent_sequences)
+            .flatten()
+        {
+            sequences.clear();
+        }
+        for fragments in self.received_fragments.iter_mut().flatten() {
+            fragments.clear();
+        }
+        for message_ids in self.sent_message_ids.iter_mut().flatten() {
+            message_ids.clear();
+        }
     }
 }
+
+/// Keeps only the newest message from each client and strips sequences from them.
+///
+/// Messages that are not newer than the last delivered ones are dropped.
+fn retain_latest(
+    messages: &mut Vec<(Entity, Bytes)>,
+    last_sequences: &mut EntityHashMap<Sequence>,
+) {
+    let mut newest = EntityHashMap::<Sequence>::default();
+    for (client, message) in &*messages {
+        let Some(sequence) = Sequence::peek(message) else {
+            continue;
+        };
+        let last = newest.get(client).or_else(|| last_sequences.get(client));
+        if last.is_none_or(|&last| sequence.is_newer(last)) {
+            newest.insert(*client, sequence);
+        }
+    }
+
+    messages.retain_mut(|(client, message)| {
+        let Some(sequence) = Sequence::peek(message) else {
+            debug!("dropping message without sequence from client `{client}`");
+            return false;
+        };
+        if newest.get(client) != Some(&sequence) {
+            trace!("dropping outdated message from client `{client}`");
+            return false;
+        }
+
+        newest.remove(client);
+        last_sequences.insert(*client, sequence);
+        Sequence::strip(message);
+        true
+    });
+}
@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+
+use bevy::prelude::*;
+use bytes::Bytes;
+
+/// Messages exchanged with peers without an established connection.
+///
+/// Used by [`ServerDiscoveryPlugin`](crate::shared::server_discovery::ServerDiscoveryPlugin) to answer server list queries
+/// without spawning [`ConnectedClient`](super::connected_client::ConnectedClient) entities.
+///
+/// Optional for backends. If the transport supports unconnected messages, it's responsible for updating this resource:
+/// - Received messages should be forwarded to Replicon via [`Self::insert_received`] in
+///   [`ServerSystems::ReceivePackets`](crate::prelude::ServerSystems::ReceivePackets) or
+///   [`ClientSystems::ReceivePackets`](crate::prelude::ClientSystems::ReceivePackets).
+/// - Replicon messages needs to be forwarded to the backend via [`Self::drain_sent`] in
+///   [`ServerSystems::SendPackets`](crate::prelude::ServerSystems::SendPackets) or
+///   [`ClientSystems::SendPackets`](crate::prelude::ClientSystems::SendPackets).
+///
+/// Since any peer can send such messages, backends should limit their size and rate.
+///
+/// Inserted as resource by [`ServerDiscoveryPlugin`](crate::shared::server_discovery::ServerDiscoveryPlugin).
+#[derive(Resource, Default)]
+pub struct UnconnectedMessages {
+    /// List of received messages and their senders since the last tick.
+    received_messages: Vec<(UnconnectedPeer, Bytes)>,
+
+    /// List of sent messages and their receivers since the last tick.
+    sent_messages: Vec<(UnconnectedPeer, Bytes)>,
+}
+
+impl UnconnectedMessages {
+    /// Removes all received messages, returning them as an iterator with the senders.
+    pub(crate) fn drain_received(&mut self) -> impl Iterator<Item = (UnconnectedPeer, Bytes)> + '_ {
+        self.received_messages.drain(..)
+    }
+
+    /// Sends a message to a peer.
+    pub(crate) fn send(&mut self, peer: UnconnectedPeer, message: impl Into<Bytes>) {
+        self.sent_messages.push((peer, message.into()));
+    }
+
+    /// Adds a message from a peer to the list of received messages.
+    ///
+    /// <div class="warning">
+    ///
+    /// Should only be called from the messaging backend.
+    ///
+    /// </div>
+    pub fn insert_received(&mut self, peer: UnconnectedPeer, message: impl Into<Bytes>) {
+        self.received_messages.push((peer, message.into()));
+    }
+
+    /// Removes all sent messages, returning them as an iterator with the receivers.
+    ///
+    /// <div class="warning">
+    ///
+    /// Should only be called from the messaging backend.
+    ///
+    /// </div>
+    pub fn drain_sent(&mut self) -> impl Iterator<Item = (UnconnectedPeer, Bytes)> + '_ {
+        self.sent_messages.drain(..)
+    }
+}
+
+/// Identifier of a peer without a connection provided by a messaging backend.
+///
+/// The backend decides what it maps to. For example, it could be an index of a socket address.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Ord, PartialOrd)]
+pub struct UnconnectedPeer(u64);
+
+impl UnconnectedPeer {
+    /// Creates a new ID wrapping the given value.
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Gets the value of this ID.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
@@ -1,6 +1,9 @@
 use core::fmt::{self, Display, Formatter};
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::backend::connected_client::{NetworkId, NetworkIdMap};
 
 /// Unique client ID for the current session.
 ///
@@ -40,3 +43,88 @@ impl Display for ClientId {
         }
     }
 }
+
+/// Reference to a client that can be stored inside replicated components.
+///
+/// Client entities are local to the server and aren't replicated, so storing them
+/// as [`Entity`] in a replicated component results in an invalid entity on clients.
+/// Instead, this type is serialized as the client's [`NetworkId`] and resolved
+/// into an entity on each side using [`NetworkIdMap`]:
+///
+/// - On the server it resolves to the connected client entity.
+/// - On clients it resolves to the replicated client entity, which acts as a roster entry.
+///   To make it available, replicate [`NetworkId`] with [`AppRuleExt::replicate`](crate::prelude::AppRuleExt::replicate)
+///   and insert [`Replicated`](crate::prelude::Replicated) on connected client entities on the server.
+/// - Otherwise it resolves to [`None`].
+///
+/// Requires a messaging backend that provides [`NetworkId`].
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     prelude::*,
+///     shared::backend::connected_client::{NetworkId, NetworkIdMap},
+/// };
+/// use serde::{Deserialize, Serialize};
+///
+/// fn spawn_ship(
+///     add: On<Add, AuthorizedClient>,
+///     mut commands: Commands,
+///     clients: Query<&NetworkId>,
+/// ) {
+///     if let Ok(&network_id) = clients.get(add.entity) {
+///         commands.spawn((Replicated, Ship, Owner(network_id.into())));
+///     }
+/// }
+///
+/// fn print_owners(ships: Query<&Owner, With<Ship>>, network_map: Res<NetworkIdMap>) {
+///     for owner in &ships {
+///         match owner.entity(&network_map) {
+///             Some(entity) => info!("ship owned by `{entity}`"),
+///             None => info!("ship owned by unknown client"),
+///         }
+///     }
+/// }
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Ship;
+///
+/// #[derive(Component, Deref, Serialize, Deserialize)]
+/// struct Owner(ClientRef);
+/// ```
+#[derive(Reflect, Serialize, Deserialize, Debug, Default, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct ClientRef(Option<NetworkId>);
+
+impl ClientRef {
+    /// A reference that doesn't point to any client.
+    ///
+    /// Can be used for the listen server or clients without [`NetworkId`].
+    pub const NONE: Self = Self(None);
+
+    /// Creates a reference to a client with the given ID.
+    pub const fn new(network_id: NetworkId) -> Self {
+        Self(Some(network_id))
+    }
+
+    /// Returns the referenced client ID.
+    pub fn network_id(self) -> Option<NetworkId> {
+        self.0
+    }
+
+    /// Resolves the reference into a local entity.
+    ///
+    /// Returns [`None`] if the reference is empty or no entity with the referenced
+    /// [`NetworkId`] exists on this side.
+    pub fn entity(self, network_map: &NetworkIdMap) -> Option<Entity> {
+        self.0
+            .and_then(|network_id| network_map.get(&network_id).copied())
+    }
+}
+
+impl From<NetworkId> for ClientRef {
+    fn from(value: NetworkId) -> Self {
+        Self::new(value)
+    }
+}
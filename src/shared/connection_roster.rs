@@ -0,0 +1,199 @@
+//! Replicated list of all connections for admin panels.
+//!
+//! See [`ConnectionRosterPlugin`] for details.
+
+use bevy::prelude::*;
+#[cfg(feature = "server")]
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+#[cfg(feature = "server")]
+use crate::{
+    server::{authorization::ClientProtocol, visibility::registry::FilterRegistry},
+    shared::backend::connected_client::NetworkId,
+};
+
+/**
+Replicates an entry for each connection to clients with [`Moderator`].
+
+Unlike replicating client entities themselves, entries are spawned right after connecting,
+so the roster also includes clients that are still handshaking or will be denied by
+[`Authorizers`](crate::server::authorization::Authorizers). Each entry is a separate
+replicated entity with [`ConnectionEntry`] that is despawned together with its client.
+
+Entries are hidden from other clients with the [`ModeratorOnly`] visibility filter.
+On the server, entries are regular entities, so they can also be used for a local admin panel.
+
+Not included in [`RepliconPlugins`] and needs to be added manually on both client and server
+after [`RepliconPlugins`].
+
+# Examples
+
+```
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    StatesPlugin,
+    RepliconPlugins,
+    ConnectionRosterPlugin,
+))
+.add_observer(grant_moderator)
+.add_systems(Update, draw_admin_panel.run_if(in_state(ClientState::Connected)));
+
+fn grant_moderator(add: On<Add, AuthorizedClient>, mut commands: Commands) {
+    // Could be checked against an account database.
+    commands.entity(add.entity).insert(Moderator);
+}
+
+fn draw_admin_panel(entries: Query<&ConnectionEntry>) {
+    for entry in &entries {
+        info!("{:?} with {:?} protocol", entry.phase, entry.protocol);
+    }
+}
+```
+*/
+pub struct ConnectionRosterPlugin;
+
+impl Plugin for ConnectionRosterPlugin {
+    fn build(&self, app: &mut App) {
+        app.replicate::<ConnectionEntry>();
+
+        #[cfg(feature = "server")]
+        if app.world().contains_resource::<FilterRegistry>() {
+            app.add_visibility_filter::<ModeratorOnly>()
+                .add_observer(spawn_entry)
+                .add_systems(
+                    PostUpdate,
+                    update_entries
+                        .before(ServerSystems::Send)
+                        .run_if(in_state(ServerState::Running)),
+                );
+        }
+    }
+}
+
+/// Information about a connection from [`ConnectionRosterPlugin`].
+#[derive(Component, Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionEntry {
+    /// Reference to the connected client.
+    ///
+    /// Points to no client if the backend doesn't provide [`NetworkId`](crate::shared::backend::connected_client::NetworkId).
+    pub client: ClientRef,
+
+    /// Current phase of the connection.
+    pub phase: ConnectionPhase,
+
+    /// Result of comparing the client's protocol with the server's.
+    pub protocol: ProtocolStatus,
+}
+
+/// Phase of a connection from [`ConnectionEntry`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionPhase {
+    /// Connected, but not authorized yet.
+    #[default]
+    Handshaking,
+    /// Has [`AuthorizedClient`](crate::server::AuthorizedClient).
+    Authorized,
+}
+
+/// Protocol status of a connection from [`ConnectionEntry`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolStatus {
+    /// The client hasn't sent its protocol yet.
+    ///
+    /// Always the case unless [`RepliconSharedPlugin::auth_method`] is set to
+    /// [`AuthMethod::Authorizers`].
+    #[default]
+    Unknown,
+    /// The client's [`ProtocolHash`] matches the server's.
+    Matched,
+    /// The client's [`ProtocolHash`] differs from the server's.
+    Mismatched,
+}
+
+/// Marks a client that receives entries from [`ConnectionRosterPlugin`].
+///
+/// Should be inserted on the server on client entities.
+#[derive(Component, Debug, Clone, Copy)]
+#[component(immutable)]
+pub struct Moderator;
+
+/// Visibility filter that shows an entity only to clients with [`Moderator`].
+///
+/// Inserted on entries from [`ConnectionRosterPlugin`], but can be used for other entities too.
+/// Registered only with the plugin.
+#[derive(Component, Debug, Clone, Copy)]
+#[component(immutable)]
+pub struct ModeratorOnly;
+
+impl VisibilityFilter for ModeratorOnly {
+    type ClientComponent = Moderator;
+    type Scope = Entity;
+
+    fn is_visible(&self, _client: Entity, component: Option<&Self::ClientComponent>) -> bool {
+        component.is_some()
+    }
+}
+
+/// Points from an entry to its client.
+#[cfg(feature = "server")]
+#[derive(Component)]
+#[relationship(relationship_target = RosterEntry)]
+struct RosterEntryOf(Entity);
+
+/// Entry of a client.
+#[cfg(feature = "server")]
+#[derive(Component)]
+#[relationship_target(relationship = RosterEntryOf, linked_spawn)]
+struct RosterEntry(Entity);
+
+#[cfg(feature = "server")]
+fn spawn_entry(add: On<Add, ConnectedClient>, mut commands: Commands) {
+    debug!("spawning roster entry for client `{}`", add.entity);
+    commands.spawn((
+        Replicated,
+        ModeratorOnly,
+        ConnectionEntry::default(),
+        RosterEntryOf(add.entity),
+    ));
+}
+
+#[cfg(feature = "server")]
+fn update_entries(
+    protocol: Res<ProtocolHash>,
+    clients: Query<(
+        &RosterEntry,
+        Option<&NetworkId>,
+        Option<&ClientProtocol>,
+        Has<AuthorizedClient>,
+    )>,
+    mut entries: Query<&mut ConnectionEntry>,
+) {
+    for (roster_entry, network_id, client_protocol, authorized) in &clients {
+        let Ok(mut entry) = entries.get_mut(roster_entry.0) else {
+            continue;
+        };
+
+        let phase = if authorized {
+            ConnectionPhase::Authorized
+        } else {
+            ConnectionPhase::Handshaking
+        };
+        let protocol = match client_protocol {
+            Some(client_protocol) if **client_protocol == *protocol => ProtocolStatus::Matched,
+            Some(_) => ProtocolStatus::Mismatched,
+            None => ProtocolStatus::Unknown,
+        };
+
+        entry.set_if_neq(ConnectionEntry {
+            client: network_id.map_or(ClientRef::NONE, |&id| ClientRef::new(id)),
+            phase,
+            protocol,
+        });
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/**
+Random seed shared by the server and the client for the current connection.
+
+Useful for deterministic cosmetic variation, such as particle offsets or idle animation phases,
+that should look the same on the server and the client without replicating random outputs.
+Mix it with something both sides know, like a replicated component value, to derive per-entity
+values.
+
+The seed is sent to the client when it receives [`AuthorizedClient`], so it's available before
+any replicated entity. To provide a seed, insert [`ConnectedClientSeed`] on the client entity
+before authorization. If it's missing, the seed is generated with [`Self::random`], which requires
+the `getrandom` feature. Without the feature, no seed is sent to clients that don't have it.
+On the client the seed is inserted as a resource, which is removed on disconnect.
+
+The seed is known to the client, so it shouldn't be used for anything gameplay-relevant
+that clients could exploit.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+fn spawn_sparks(seed: Res<ConnectionSeed>, sparks: Query<(Entity, &Spark), Added<Spark>>) {
+    for (entity, spark) in &sparks {
+        let variation = seed.mix(spark.id) % 4;
+        info!("spawning `{entity}` with variation {variation}");
+    }
+}
+
+#[derive(Component)]
+struct Spark {
+    id: u64,
+}
+```
+*/
+#[derive(
+    Resource, Event, Deref, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy,
+)]
+pub struct ConnectionSeed(pub u64);
+
+impl ConnectionSeed {
+    /// Generates a new seed from the OS random source.
+    #[cfg(feature = "getrandom")]
+    pub fn random() -> Result<Self, getrandom::Error> {
+        getrandom::u64().map(Self)
+    }
+
+    /// Combines the seed with `value` into a well-distributed value.
+    ///
+    /// Returns the same result on the server and the client for the same inputs.
+    pub fn mix(self, value: u64) -> u64 {
+        // SplitMix64 finalizer.
+        let mut hash = self.0 ^ value.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^ (hash >> 31)
+    }
+}
+
+/// [`ConnectionSeed`] for a connected client on the server.
+///
+/// See [`ConnectionSeed`] for details.
+#[derive(Component, Deref, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ConnectedClientSeed(pub ConnectionSeed);
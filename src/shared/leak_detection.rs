@@ -0,0 +1,320 @@
+//! Detection of entities that clients keep after the server stopped replicating them.
+//!
+//! See [`LeakDetectionPlugin`] for details.
+
+#[cfg(feature = "server")]
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+#[cfg(feature = "server")]
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+#[cfg(any(feature = "client", feature = "server"))]
+use bevy::time::common_conditions::on_timer;
+#[cfg(any(feature = "client", feature = "server"))]
+use log::debug;
+#[cfg(feature = "server")]
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+#[cfg(feature = "server")]
+use crate::shared::replication::client_ticks::ClientTicks;
+#[cfg(feature = "client")]
+use crate::{
+    client::{ServerUpdateTick, destructive_gate::DestructiveGates},
+    shared::server_entity_map::ServerEntityMap,
+};
+
+/**
+Reports entities that clients keep after the server stopped replicating them.
+
+Long sessions can accumulate ghost entities on clients, for example, when a custom
+[`ReplicationRegistry::despawn`](crate::shared::replication::registry::ReplicationRegistry::despawn)
+function doesn't actually despawn entities. This plugin helps to detect them by triggering
+[`ReplicationLeak`] on the server. Detection happens in two ways:
+
+- The server requests acknowledgments for update messages with despawns or visibility losses and
+  reports entities that weren't acknowledged within [`Self::ack_timeout`]. Adds no traffic except
+  acknowledgments.
+- If [`Self::report_interval`] is set, the client periodically sends the list of its replicated
+  entities. The server compares it against the entities it replicates to this client. Reports
+  are proportional to the number of replicated entities, so keep the interval large.
+
+Intended for debugging. Not included in [`RepliconPlugins`] and needs to be added manually
+on both client and server.
+
+# Examples
+
+```
+use core::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    StatesPlugin,
+    RepliconPlugins,
+    LeakDetectionPlugin::default().with_report_interval(Duration::from_secs(60)),
+))
+.add_observer(|leak: On<ReplicationLeak>| {
+    error!("client `{}` leaked: {:?}", leak.client, leak.kind);
+});
+```
+*/
+pub struct LeakDetectionPlugin {
+    /// How long the server waits for the acknowledgment of a despawn before reporting
+    /// [`LeakKind::Unacknowledged`].
+    ///
+    /// By default set to 30 seconds.
+    pub ack_timeout: Duration,
+
+    /// How often the client reports its replicated entities to the server.
+    ///
+    /// Reports are disabled if set to [`None`]. Only affects the client.
+    ///
+    /// By default set to [`None`].
+    pub report_interval: Option<Duration>,
+}
+
+impl LeakDetectionPlugin {
+    /// Sets [`Self::report_interval`].
+    #[must_use]
+    pub fn with_report_interval(mut self, interval: Duration) -> Self {
+        self.report_interval = Some(interval);
+        self
+    }
+}
+
+impl Default for LeakDetectionPlugin {
+    fn default() -> Self {
+        Self {
+            ack_timeout: Duration::from_secs(30),
+            report_interval: None,
+        }
+    }
+}
+
+impl Plugin for LeakDetectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_message::<EntityReport>(Channel::Ordered);
+
+        #[cfg(feature = "server")]
+        app.register_required_components::<AuthorizedClient, DespawnHistory>()
+            .add_systems(
+                PreUpdate,
+                (
+                    receive_reports,
+                    check_despawns(self.ack_timeout).run_if(on_timer(self.ack_timeout)),
+                )
+                    .chain()
+                    .after(ServerSystems::Receive)
+                    .run_if(in_state(ServerState::Running)),
+            );
+
+        #[cfg(feature = "client")]
+        if let Some(interval) = self.report_interval {
+            app.add_systems(
+                PostUpdate,
+                send_report
+                    .before(ClientSystems::Send)
+                    .run_if(on_timer(interval))
+                    .run_if(in_state(ClientState::Connected)),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn receive_reports(
+    mut commands: Commands,
+    mut reports: MessageReader<FromClient<EntityReport>>,
+    clients: Query<(&ClientTicks, &DespawnHistory)>,
+) {
+    for report in reports.read() {
+        let Some(client) = report.client_id.entity() else {
+            continue;
+        };
+        let Ok((ticks, history)) = clients.get(client) else {
+            continue;
+        };
+
+        debug!(
+            "received report with {} entities for `{:?}` from client `{client}`",
+            report.entities.len(),
+            report.tick,
+        );
+
+        for &entity in &report.entities {
+            if ticks.entities.contains_key(entity) || ticks.forgotten.contains(&entity) {
+                continue;
+            }
+
+            // The despawn could be sent after the client created the report.
+            if history
+                .tick(entity)
+                .is_some_and(|tick| tick.is_newer(report.tick))
+            {
+                continue;
+            }
+
+            warn!("client `{client}` reported `{entity}` that isn't replicated to it");
+            commands.trigger(ReplicationLeak {
+                client,
+                kind: LeakKind::Unknown(entity),
+            });
+        }
+
+        if report.orphaned > 0 {
+            warn!(
+                "client `{client}` reported {} remote entities without a server entity",
+                report.orphaned
+            );
+            commands.trigger(ReplicationLeak {
+                client,
+                kind: LeakKind::Orphaned(report.orphaned),
+            });
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn check_despawns(
+    ack_timeout: Duration,
+) -> impl FnMut(Commands, Res<Time<Real>>, Query<(Entity, &ClientTicks, &mut DespawnHistory)>) {
+    move |mut commands, time, mut clients| {
+        let min_timestamp = time.elapsed().saturating_sub(ack_timeout);
+        for (client, ticks, mut history) in &mut clients {
+            history.cleanup_older(min_timestamp, |entity, tick| {
+                if ticks
+                    .acked_update_tick
+                    .is_some_and(|acked_tick| !tick.is_newer(acked_tick))
+                {
+                    return;
+                }
+
+                warn!(
+                    "client `{client}` didn't acknowledge despawn of `{entity}` for `{tick:?}` within {ack_timeout:?}"
+                );
+                commands.trigger(ReplicationLeak {
+                    client,
+                    kind: LeakKind::Unacknowledged(entity),
+                });
+            });
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+fn send_report(
+    mut reports: MessageWriter<EntityReport>,
+    update_tick: Res<ServerUpdateTick>,
+    entity_map: Res<ServerEntityMap>,
+    gates: Res<DestructiveGates>,
+    remote: Query<Entity, With<Remote>>,
+) {
+    let orphaned = remote
+        .iter()
+        .filter(|entity| !entity_map.to_server().contains_key(entity) && !gates.is_pending(*entity))
+        .count();
+
+    debug!(
+        "sending report with {} entities and {orphaned} orphaned entities",
+        entity_map.to_client().len()
+    );
+    reports.write(EntityReport {
+        tick: **update_tick,
+        entities: entity_map.to_client().keys().copied().collect(),
+        orphaned,
+    });
+}
+
+/// Despawns and visibility losses sent to a client for [`LeakDetectionPlugin`].
+///
+/// Entries are kept for [`LeakDetectionPlugin::ack_timeout`] to check if they were acknowledged
+/// and to ignore despawns that were in flight when the client created its report.
+#[cfg(feature = "server")]
+#[derive(Component, Default)]
+pub(crate) struct DespawnHistory {
+    /// Latest despawn tick for each entity.
+    ticks: EntityHashMap<RepliconTick>,
+
+    /// Despawns in the order they were sent with their timestamps.
+    despawns: VecDeque<(Entity, RepliconTick, Duration)>,
+}
+
+#[cfg(feature = "server")]
+impl DespawnHistory {
+    /// Records a despawn of an entity sent at the given tick.
+    pub(crate) fn insert(&mut self, entity: Entity, tick: RepliconTick, timestamp: Duration) {
+        self.ticks.insert(entity, tick);
+        self.despawns.push_back((entity, tick, timestamp));
+    }
+
+    /// Returns the tick of the latest despawn sent for an entity.
+    fn tick(&self, entity: Entity) -> Option<RepliconTick> {
+        self.ticks.get(&entity).copied()
+    }
+
+    /// Removes all despawns older then `min_timestamp`.
+    ///
+    /// Calls given function for each removed despawn.
+    fn cleanup_older(&mut self, min_timestamp: Duration, mut f: impl FnMut(Entity, RepliconTick)) {
+        while let Some(&(entity, tick, timestamp)) = self.despawns.front()
+            && timestamp < min_timestamp
+        {
+            self.despawns.pop_front();
+            if self.ticks.get(&entity) == Some(&tick) {
+                self.ticks.remove(&entity);
+            }
+            (f)(entity, tick);
+        }
+    }
+}
+
+/// An event triggered on the server when a client possibly keeps an entity that it shouldn't.
+///
+/// See [`LeakDetectionPlugin`] for details.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ReplicationLeak {
+    /// Client that has the leak.
+    pub client: Entity,
+
+    /// What was detected.
+    pub kind: LeakKind,
+}
+
+/// Kind of [`ReplicationLeak`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LeakKind {
+    /// The client didn't acknowledge the despawn or visibility loss of this entity
+    /// within [`LeakDetectionPlugin::ack_timeout`].
+    Unacknowledged(Entity),
+
+    /// The client reported this entity, but the server doesn't replicate it to the client.
+    Unknown(Entity),
+
+    /// The client reported this number of entities with [`Remote`](crate::client::Remote)
+    /// that are no longer mapped to server entities.
+    ///
+    /// Usually caused by a despawn function that doesn't despawn entities.
+    Orphaned(usize),
+}
+
+/// Entities replicated to the client at the moment of sending.
+///
+/// See [`LeakDetectionPlugin::report_interval`].
+#[derive(Message, Serialize, Deserialize)]
+struct EntityReport {
+    /// Last update tick received by the client.
+    tick: RepliconTick,
+
+    /// Server entities that the client has.
+    entities: Vec<Entity>,
+
+    /// Number of entities with [`Remote`](crate::client::Remote) without a server entity.
+    orphaned: usize,
+}
@@ -1,6 +1,7 @@
 pub mod client_event;
 pub mod client_message;
 pub mod ctx;
+pub mod entity_message;
 pub mod message_fns;
 pub mod registry;
 pub mod server_event;
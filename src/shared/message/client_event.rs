@@ -1,16 +1,22 @@
-use core::any::TypeId;
+use alloc::vec::Vec;
+use core::{any::TypeId, marker::PhantomData};
 
-use bevy::{ecs::entity::MapEntities, prelude::*, ptr::PtrMut};
+use bevy::{
+    ecs::{entity::MapEntities, event::SetEntityEventTarget},
+    prelude::*,
+    ptr::PtrMut,
+};
+use bytes::Bytes;
 use log::debug;
 use serde::{Serialize, de::DeserializeOwned};
 
 use super::{
     client_message::{self, ClientMessage},
-    ctx::{ClientSendCtx, ServerReceiveCtx},
+    ctx::{ClientReceiveCtx, ClientSendCtx, ServerReceiveCtx, ServerSendCtx},
     message_fns::{DeserializeFn, MessageFns, SerializeFn},
     registry::RemoteMessageRegistry,
 };
-use crate::prelude::*;
+use crate::{postcard_utils, prelude::*};
 
 /// An extension trait for [`App`] for creating client events.
 ///
@@ -60,6 +66,104 @@ pub trait ClientEventAppExt {
         serialize: SerializeFn<ClientSendCtx, E>,
         deserialize: DeserializeFn<ServerReceiveCtx, E>,
     ) -> &mut Self;
+
+    /// Registers a remote entity event that can be triggered for multiple targets at once
+    /// using [`ClientTriggerExt::client_trigger_targets`].
+    ///
+    /// All targets are sent in a single message and mapped from client to server entities.
+    /// After receiving, [`FromClient<E>`] will be triggered on the server for each target that passes
+    /// the filter from [`Self::set_client_targets_filter`], with the event target set to it.
+    ///
+    /// Other entities inside `E` are not mapped.
+    fn add_client_targets_event<
+        E: EntityEvent + SetEntityEventTarget + Serialize + DeserializeOwned + Clone,
+    >(
+        &mut self,
+        channel: Channel,
+    ) -> &mut Self;
+
+    /// Sets a function that validates targets of an event registered with [`Self::add_client_targets_event`].
+    ///
+    /// Targets for which the function returns `false` will be skipped. Useful to reject targets
+    /// that aren't owned by or visible to the sender. By default, all targets are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::{ecs::event::SetEntityEventTarget, prelude::*, state::app::StatesPlugin};
+    /// use bevy_replicon::prelude::*;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    /// app.add_client_targets_event::<MoveUnit>(Channel::Ordered)
+    ///     .set_client_targets_filter::<MoveUnit>(owned_by_sender);
+    ///
+    /// fn owned_by_sender(world: &World, client_id: ClientId, target: Entity) -> bool {
+    ///     world
+    ///         .get::<Owner>(target)
+    ///         .is_some_and(|owner| owner.0 == client_id)
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Owner(ClientId);
+    ///
+    /// #[derive(EntityEvent, Serialize, Deserialize, Clone)]
+    /// struct MoveUnit {
+    ///     entity: Entity,
+    ///     destination: Vec2,
+    /// }
+    ///
+    /// impl SetEntityEventTarget for MoveUnit {
+    ///     fn set_event_target(&mut self, entity: Entity) {
+    ///         self.entity = entity;
+    ///     }
+    /// }
+    /// ```
+    fn set_client_targets_filter<E: EntityEvent>(&mut self, filter: TargetsFilterFn) -> &mut Self;
+
+    /**
+    Registers a client event that the server relays to all other clients.
+
+    After triggering `E` event on the client, [`FromClient<E>`] will be triggered on the server as with
+    [`Self::add_client_event`]. Then, if the event passes the filter from [`Self::set_relay_filter`],
+    [`Relayed<E>`] will be triggered on all clients except the sender.
+
+    Entities inside `E` are not mapped.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.add_relayed_client_event::<Emote>(Channel::Unordered)
+        .set_relay_filter::<Emote>(|_world, _client_id, emote| emote.id < 16)
+        .add_observer(play_emote);
+
+    fn play_emote(emote: On<Relayed<Emote>>) {
+        info!("`{}` played emote {}", emote.sender, emote.id);
+    }
+
+    #[derive(Event, Serialize, Deserialize, Clone)]
+    struct Emote {
+        id: u8,
+    }
+    ```
+    */
+    fn add_relayed_client_event<E: Event + Serialize + DeserializeOwned + Clone>(
+        &mut self,
+        channel: Channel,
+    ) -> &mut Self;
+
+    /// Sets a function that validates events registered with [`Self::add_relayed_client_event`].
+    ///
+    /// Events for which the function returns `false` won't be relayed, but [`FromClient<E>`]
+    /// is still triggered on the server. By default, all events are relayed.
+    fn set_relay_filter<E: Event>(&mut self, filter: RelayFilterFn<E>) -> &mut Self;
 }
 
 impl ClientEventAppExt for App {
@@ -80,6 +184,159 @@ impl ClientEventAppExt for App {
 
         self
     }
+
+    fn add_client_targets_event<
+        E: EntityEvent + SetEntityEventTarget + Serialize + DeserializeOwned + Clone,
+    >(
+        &mut self,
+        channel: Channel,
+    ) -> &mut Self {
+        self.add_client_event_with(channel, serialize_targets::<E>, deserialize_targets::<E>)
+            .init_resource::<ClientTargetsFilter<E>>()
+            .add_observer(trigger_targets::<E>)
+    }
+
+    fn set_client_targets_filter<E: EntityEvent>(&mut self, filter: TargetsFilterFn) -> &mut Self {
+        debug!("setting targets filter for `{}`", ShortName::of::<E>());
+        self.world_mut()
+            .resource_mut::<ClientTargetsFilter<E>>()
+            .filter = filter;
+        self
+    }
+
+    fn add_relayed_client_event<E: Event + Serialize + DeserializeOwned + Clone>(
+        &mut self,
+        channel: Channel,
+    ) -> &mut Self {
+        self.add_client_event::<E>(channel)
+            .add_server_event_with(channel, serialize_relayed::<E>, deserialize_relayed::<E>)
+            .init_resource::<RelayFilter<E>>()
+            .add_observer(relay::<E>)
+    }
+
+    fn set_relay_filter<E: Event>(&mut self, filter: RelayFilterFn<E>) -> &mut Self {
+        debug!("setting relay filter for `{}`", ShortName::of::<E>());
+        self.world_mut().resource_mut::<RelayFilter<E>>().filter = filter;
+        self
+    }
+}
+
+/// Triggers [`Relayed<E>`] for all clients except the sender of [`FromClient<E>`].
+fn relay<E: Event + Clone>(event: On<FromClient<E>>, mut commands: Commands, world: &World) {
+    let filter = world.resource::<RelayFilter<E>>().filter;
+    if !(filter)(world, event.client_id, &event.message) {
+        debug!(
+            "rejecting relay of `{}` from `{}`",
+            ShortName::of::<E>(),
+            event.client_id
+        );
+        return;
+    }
+
+    commands.server_trigger(ToClients {
+        targets: SendTargets::AllExcept(event.client_id),
+        message: Relayed {
+            sender: event.client_id,
+            event: event.message.clone(),
+        },
+    });
+}
+
+/// Serializes the relayed event with its sender.
+fn serialize_relayed<E: Serialize>(
+    _ctx: &mut ServerSendCtx,
+    relayed: &Relayed<E>,
+    message: &mut Vec<u8>,
+) -> Result<()> {
+    match relayed.sender {
+        ClientId::Client(entity) => {
+            postcard_utils::to_extend_mut(&true, message)?;
+            postcard_utils::entity_to_extend_mut(&entity, message)?;
+        }
+        ClientId::Server => postcard_utils::to_extend_mut(&false, message)?,
+    }
+    postcard_utils::to_extend_mut(&relayed.event, message)?;
+
+    Ok(())
+}
+
+/// Deserializes an event serialized by [`serialize_relayed`], mapping the sender if possible.
+fn deserialize_relayed<E: DeserializeOwned>(
+    ctx: &mut ClientReceiveCtx,
+    message: &mut Bytes,
+) -> Result<Relayed<E>> {
+    let sender = if postcard_utils::from_buf(message)? {
+        let entity = postcard_utils::entity_from_buf(message)?;
+        let entity = ctx
+            .entity_map
+            .to_client()
+            .get(&entity)
+            .copied()
+            .unwrap_or(Entity::PLACEHOLDER);
+        ClientId::Client(entity)
+    } else {
+        ClientId::Server
+    };
+    let event = postcard_utils::from_buf(message)?;
+
+    Ok(Relayed { sender, event })
+}
+
+/// Triggers [`FromClient<E>`] for each accepted target from the received [`ClientTargets<E>`].
+fn trigger_targets<E: SetEntityEventTarget + Clone>(
+    targets: On<FromClient<ClientTargets<E>>>,
+    mut commands: Commands,
+    world: &World,
+) {
+    let filter = world.resource::<ClientTargetsFilter<E>>().filter;
+    for &target in &targets.targets {
+        if !(filter)(world, targets.client_id, target) {
+            debug!(
+                "rejecting target `{target}` for `{}` from `{}`",
+                ShortName::of::<E>(),
+                targets.client_id
+            );
+            continue;
+        }
+
+        let mut event = targets.event.clone();
+        event.set_event_target(target);
+        commands.trigger(FromClient {
+            client_id: targets.client_id,
+            message: event,
+        });
+    }
+}
+
+/// Serializes the event and its targets, mapping targets to server entities.
+fn serialize_targets<E: Serialize>(
+    ctx: &mut ClientSendCtx,
+    targets: &ClientTargets<E>,
+    message: &mut Vec<u8>,
+) -> Result<()> {
+    postcard_utils::to_extend_mut(&targets.event, message)?;
+    postcard_utils::to_extend_mut(&targets.targets.len(), message)?;
+    for &entity in &targets.targets {
+        let entity = ctx.get_mapped(entity);
+        postcard_utils::entity_to_extend_mut(&entity, message)?;
+    }
+
+    Ok(())
+}
+
+/// Deserializes an event serialized by [`serialize_targets`].
+fn deserialize_targets<E: DeserializeOwned>(
+    _ctx: &mut ServerReceiveCtx,
+    message: &mut Bytes,
+) -> Result<ClientTargets<E>> {
+    let event = postcard_utils::from_buf(message)?;
+    let len: usize = postcard_utils::from_buf(message)?;
+    let mut targets = Vec::new();
+    for _ in 0..len {
+        targets.push(postcard_utils::entity_from_buf(message)?);
+    }
+
+    Ok(ClientTargets { event, targets })
 }
 
 /// Small abstraction on top of [`ClientEvent`] that stores a function to trigger them.
@@ -150,18 +407,110 @@ type TriggerFn = unsafe fn(&mut Commands, PtrMut);
 pub trait ClientTriggerExt {
     /// Like [`Commands::trigger`], but triggers [`FromClient`] on server and locally if the client state is [`ClientState::Disconnected`].
     fn client_trigger(&mut self, event: impl Event);
+
+    /// Like [`Self::client_trigger`], but sends the event once and triggers it on server for each entity from `targets`.
+    ///
+    /// The event should be registered using [`ClientEventAppExt::add_client_targets_event`].
+    fn client_trigger_targets<E: EntityEvent>(
+        &mut self,
+        event: E,
+        targets: impl IntoIterator<Item = Entity>,
+    );
 }
 
 impl ClientTriggerExt for Commands<'_, '_> {
     fn client_trigger(&mut self, event: impl Event) {
         self.write_message(ClientMessageEvent { event });
     }
+
+    fn client_trigger_targets<E: EntityEvent>(
+        &mut self,
+        event: E,
+        targets: impl IntoIterator<Item = Entity>,
+    ) {
+        self.client_trigger(ClientTargets {
+            event,
+            targets: targets.into_iter().collect(),
+        });
+    }
 }
 
 impl ClientTriggerExt for World {
     fn client_trigger(&mut self, event: impl Event) {
         self.write_message(ClientMessageEvent { event });
     }
+
+    fn client_trigger_targets<E: EntityEvent>(
+        &mut self,
+        event: E,
+        targets: impl IntoIterator<Item = Entity>,
+    ) {
+        self.client_trigger(ClientTargets {
+            event,
+            targets: targets.into_iter().collect(),
+        });
+    }
+}
+
+/// Signature of target filters for events registered with [`ClientEventAppExt::add_client_targets_event`].
+pub type TargetsFilterFn = fn(&World, ClientId, Entity) -> bool;
+
+/// Stores a target filter for `E`.
+#[derive(Resource)]
+struct ClientTargetsFilter<E> {
+    filter: TargetsFilterFn,
+    marker: PhantomData<E>,
+}
+
+impl<E> Default for ClientTargetsFilter<E> {
+    fn default() -> Self {
+        Self {
+            filter: |_, _, _| true,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Signature of relay filters for events registered with [`ClientEventAppExt::add_relayed_client_event`].
+pub type RelayFilterFn<E> = fn(&World, ClientId, &E) -> bool;
+
+/// Stores a relay filter for `E`.
+#[derive(Resource)]
+struct RelayFilter<E: Event> {
+    filter: RelayFilterFn<E>,
+}
+
+impl<E: Event> Default for RelayFilter<E> {
+    fn default() -> Self {
+        Self {
+            filter: |_, _, _| true,
+        }
+    }
+}
+
+/// A client event relayed by the server to other clients.
+///
+/// See [`ClientEventAppExt::add_relayed_client_event`].
+#[derive(Event, Deref, DerefMut, Debug, Clone, Copy)]
+pub struct Relayed<E> {
+    /// Client that triggered the event.
+    ///
+    /// On clients, the entity is mapped to the local entity if the sender's entity
+    /// is replicated, otherwise it's [`Entity::PLACEHOLDER`].
+    pub sender: ClientId,
+
+    /// Relayed event.
+    #[deref]
+    pub event: E,
+}
+
+/// An event that used under the hood for multi-target client events.
+///
+/// Sent as a single message and split into separate [`FromClient<E>`] events on server.
+#[derive(Event)]
+struct ClientTargets<E> {
+    event: E,
+    targets: Vec<Entity>,
 }
 
 /// A message that used under the hood for client events.
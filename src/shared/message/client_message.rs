@@ -1,4 +1,4 @@
-use core::any::TypeId;
+use core::{any::TypeId, mem};
 
 use bevy::{
     ecs::{component::ComponentId, entity::MapEntities, message::MessageCursor},
@@ -14,7 +14,15 @@ use super::{
     message_fns::{DeserializeFn, MessageFns, SerializeFn, UntypedMessageFns},
     registry::RemoteMessageRegistry,
 };
-use crate::{postcard_utils, prelude::*};
+use crate::{
+    postcard_utils,
+    prelude::*,
+    shared::{
+        backend::channels::{ChannelCreator, ClientChannelId},
+        replication::registry::serde_fns::catch_panic,
+        replication_error::ReplicationErrorKind,
+    },
+};
 
 /// An extension trait for [`App`] for creating client messages.
 ///
@@ -119,6 +127,45 @@ pub trait ClientMessageAppExt {
         serialize: SerializeFn<ClientSendCtx, M>,
         deserialize: DeserializeFn<ServerReceiveCtx, M>,
     ) -> &mut Self;
+
+    /**
+    Sets how multiple messages `M` written between sends are combined before serialization.
+
+    Useful for messages that are written every frame, like aim updates. If the client
+    hitches, many of them queue up, but only the latest state matters. Coalescing
+    them saves upstream bandwidth, which is usually much more constrained than downstream.
+
+    Messages written locally, when the client is disconnected and [`ServerMessagePlugin`]
+    is enabled, are not affected.
+
+    By default, all messages are sent. See [`CoalescePolicy`] for available policies.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .add_client_message::<Aim>(Channel::UnreliableLatest)
+        .coalesce_client_message::<Aim>(CoalescePolicy::KeepLatest)
+        .add_client_message::<Damage>(Channel::Ordered)
+        .coalesce_client_message::<Damage>(CoalescePolicy::MergeWith(|a, b| Damage(a.0 + b.0)));
+
+    #[derive(Message, Serialize, Deserialize)]
+    struct Aim(Vec2);
+
+    #[derive(Message, Serialize, Deserialize)]
+    struct Damage(u32);
+    ```
+
+    # Panics
+
+    Panics if `M` is not registered as a client message.
+    */
+    fn coalesce_client_message<M: Message>(&mut self, policy: CoalescePolicy<M>) -> &mut Self;
 }
 
 impl ClientMessageAppExt for App {
@@ -139,6 +186,105 @@ impl ClientMessageAppExt for App {
 
         self
     }
+
+    fn coalesce_client_message<M: Message>(&mut self, policy: CoalescePolicy<M>) -> &mut Self {
+        let messages_id = self
+            .world()
+            .components()
+            .component_id::<Messages<M>>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "message `{}` should be previously registered",
+                    ShortName::of::<M>()
+                )
+            });
+
+        let mut registry = self.world_mut().resource_mut::<RemoteMessageRegistry>();
+        let message = registry
+            .iter_client_messages_mut()
+            .find(|m| m.messages_id() == messages_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "message `{}` should be previously registered as a client message",
+                    ShortName::of::<M>()
+                )
+            });
+
+        message.coalesce = policy.into();
+
+        self
+    }
+}
+
+/// Defines how multiple client messages of the same type written between sends are combined.
+///
+/// See [`ClientMessageAppExt::coalesce_client_message`].
+pub enum CoalescePolicy<M> {
+    /// Send all messages.
+    SendAll,
+    /// Send only the last written message.
+    KeepLatest,
+    /// Send only the first written message.
+    KeepFirst,
+    /// Combine all messages into a single one using the given function.
+    ///
+    /// The function is called with the accumulated message and the next one.
+    MergeWith(fn(&M, &M) -> M),
+}
+
+impl<M> Default for CoalescePolicy<M> {
+    fn default() -> Self {
+        Self::SendAll
+    }
+}
+
+impl<M> Clone for CoalescePolicy<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for CoalescePolicy<M> {}
+
+/// Type-erased version of [`CoalescePolicy`].
+#[derive(Default, Clone, Copy)]
+enum UntypedCoalescePolicy {
+    #[default]
+    SendAll,
+    KeepLatest,
+    KeepFirst,
+    MergeWith(unsafe fn()),
+}
+
+impl UntypedCoalescePolicy {
+    /// Restores the original [`CoalescePolicy`] from which this type was created.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the function is called with the same generic with which this instance was created.
+    unsafe fn typed<M>(self) -> CoalescePolicy<M> {
+        match self {
+            Self::SendAll => CoalescePolicy::SendAll,
+            Self::KeepLatest => CoalescePolicy::KeepLatest,
+            Self::KeepFirst => CoalescePolicy::KeepFirst,
+            Self::MergeWith(merge) => CoalescePolicy::MergeWith(unsafe {
+                mem::transmute::<unsafe fn(), fn(&M, &M) -> M>(merge)
+            }),
+        }
+    }
+}
+
+impl<M> From<CoalescePolicy<M>> for UntypedCoalescePolicy {
+    fn from(value: CoalescePolicy<M>) -> Self {
+        match value {
+            CoalescePolicy::SendAll => Self::SendAll,
+            CoalescePolicy::KeepLatest => Self::KeepLatest,
+            CoalescePolicy::KeepFirst => Self::KeepFirst,
+            CoalescePolicy::MergeWith(merge) => {
+                Self::MergeWith(unsafe { mem::transmute::<fn(&M, &M) -> M, unsafe fn()>(merge) })
+            }
+        }
+    }
 }
 
 /// Type-erased functions and metadata for a registered client messages.
@@ -155,11 +301,14 @@ pub(crate) struct ClientMessage {
     from_messages_id: ComponentId,
 
     /// Used channel.
-    channel_id: usize,
+    channel_id: ClientChannelId,
 
     /// ID of `M`.
     type_id: TypeId,
 
+    /// How messages are combined before sending.
+    coalesce: UntypedCoalescePolicy,
+
     send: SendFn,
     receive: ReceiveFn,
     send_locally: SendLocallyFn,
@@ -176,7 +325,7 @@ impl ClientMessage {
         let channel_id = app
             .world_mut()
             .resource_mut::<RepliconChannels>()
-            .create_client_channel(channel);
+            .create_client_channel(channel, ChannelCreator::new::<M, I>());
 
         app.add_message::<M>()
             .add_message::<FromClient<M>>()
@@ -198,6 +347,7 @@ impl ClientMessage {
             from_messages_id,
             channel_id,
             type_id: TypeId::of::<M>(),
+            coalesce: Default::default(),
             send: Self::send_typed::<M, I>,
             receive: Self::receive_typed::<M, I>,
             send_locally: Self::send_locally_typed::<M>,
@@ -222,7 +372,7 @@ impl ClientMessage {
         self.from_messages_id
     }
 
-    pub(super) fn channel_id(&self) -> usize {
+    pub(super) fn channel_id(&self) -> ClientChannelId {
         self.channel_id
     }
 
@@ -261,19 +411,71 @@ impl ClientMessage {
     ) {
         let reader: &mut ClientMessageReader<M> = unsafe { reader.deref_mut() };
         let messages = unsafe { messages.deref() };
-        for message in reader.read(messages) {
-            let mut message_bytes = Vec::new();
-            if let Err(e) = unsafe { self.serialize::<M, I>(ctx, message, &mut message_bytes) } {
+        let mut messages = reader.read(messages);
+        match unsafe { self.coalesce.typed::<M>() } {
+            CoalescePolicy::SendAll => {
+                for message in messages {
+                    unsafe { self.send_message::<M, I>(ctx, message, client_messages) };
+                }
+            }
+            CoalescePolicy::KeepLatest => {
+                if let Some(message) = messages.last() {
+                    unsafe { self.send_message::<M, I>(ctx, message, client_messages) };
+                }
+            }
+            CoalescePolicy::KeepFirst => {
+                if let Some(message) = messages.next() {
+                    // Skip the rest to advance the reader.
+                    for _ in messages {}
+                    unsafe { self.send_message::<M, I>(ctx, message, client_messages) };
+                }
+            }
+            CoalescePolicy::MergeWith(merge) => {
+                let Some(first) = messages.next() else {
+                    return;
+                };
+
+                let mut merged = None;
+                for message in messages {
+                    let accumulated = merged.as_ref().unwrap_or(first);
+                    merged = Some((merge)(accumulated, message));
+                }
+
+                let message = merged.as_ref().unwrap_or(first);
+                unsafe { self.send_message::<M, I>(ctx, message, client_messages) };
+            }
+        }
+    }
+
+    /// Serializes and sends a single message.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that this instance was created for `M` and `I`.
+    unsafe fn send_message<M: Message, I: 'static>(
+        &self,
+        ctx: &mut ClientSendCtx,
+        message: &M,
+        client_messages: &mut ClientMessages,
+    ) {
+        let mut message_bytes = Vec::new();
+        if let Err(e) = unsafe { self.serialize::<M, I>(ctx, message, &mut message_bytes) } {
+            if ctx.errors.report(
+                ReplicationErrorKind::Serialize,
+                Some(self.channel_id.get()),
+                None,
+                format_args!("`{}`: {e}", ShortName::of::<M>()),
+            ) {
                 error!(
                     "ignoring message `{}` that failed to serialize: {e}",
                     ShortName::of::<M>()
                 );
-                continue;
             }
-
-            debug!("sending message `{}`", ShortName::of::<M>());
-            client_messages.send(self.channel_id, message_bytes);
+            return;
         }
+
+        debug!("sending message `{}`", ShortName::of::<M>());
+        client_messages.send(self.channel_id, message_bytes);
     }
 
     /// Receives messages from a client.
@@ -316,10 +518,19 @@ impl ClientMessage {
                         message,
                     });
                 }
-                Err(e) => debug!(
-                    "ignoring message `{}` from client `{client}` that failed to deserialize: {e}",
-                    ShortName::of::<M>()
-                ),
+                Err(e) => {
+                    if ctx.errors.report(
+                        ReplicationErrorKind::Deserialize,
+                        Some(self.channel_id.get()),
+                        Some(client),
+                        format_args!("`{}`: {e}", ShortName::of::<M>()),
+                    ) {
+                        debug!(
+                            "ignoring message `{}` from client `{client}` that failed to deserialize: {e}",
+                            ShortName::of::<M>()
+                        );
+                    }
+                }
             }
         }
     }
@@ -413,6 +624,9 @@ impl ClientMessage {
 
     /// Deserializes a message.
     ///
+    /// Caught panics are returned as errors, so [`ReplicationErrorSettings::client_policy`]
+    /// applies to the client that sent the message.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that this instance was created for `M` and `I`.
@@ -421,11 +635,12 @@ impl ClientMessage {
         ctx: &mut ServerReceiveCtx,
         message: &mut Bytes,
     ) -> Result<M> {
-        unsafe {
+        catch_panic(|| unsafe {
             self.fns
                 .typed::<ClientSendCtx, ServerReceiveCtx, M, I>()
                 .deserialize(ctx, message)
-        }
+        })
+        .unwrap_or_else(|panic| Err(format!("deserialization panicked: {panic}").into()))
     }
 }
 
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::prelude::*;
-use crate::shared::server_entity_map::ServerEntityMap;
+use crate::shared::{replication_error::ErrorReporter, server_entity_map::ServerEntityMap};
 
 /// Message sending context for client.
 #[non_exhaustive]
@@ -19,6 +19,9 @@ pub struct ClientSendCtx<'a> {
     ///
     /// We needed it because [`EntityMapper`] doesn't provide a way to handle errors.
     pub(crate) invalid_entities: Vec<Entity>,
+
+    /// Collects rate-limited errors.
+    pub(crate) errors: &'a mut ErrorReporter,
 }
 
 impl EntityMapper for ClientSendCtx<'_> {
@@ -44,6 +47,9 @@ pub struct ServerReceiveCtx<'a> {
 
     /// Registry of reflected types.
     pub type_registry: &'a AppTypeRegistry,
+
+    /// Collects rate-limited errors.
+    pub(crate) errors: &'a mut ErrorReporter,
 }
 
 /// Message sending context for server.
@@ -72,6 +78,9 @@ pub struct ClientReceiveCtx<'a> {
     ///
     /// We needed it because [`EntityMapper`] doesn't provide a way to handle errors.
     pub(crate) invalid_entities: Vec<Entity>,
+
+    /// Collects rate-limited errors.
+    pub(crate) errors: &'a mut ErrorReporter,
 }
 
 impl EntityMapper for ClientReceiveCtx<'_> {
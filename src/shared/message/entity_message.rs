@@ -0,0 +1,225 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use bevy::{ecs::event::EntityTrigger, prelude::*, ptr::PtrMut};
+use bytes::Bytes;
+use log::{debug, warn};
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{
+    ctx::{ClientReceiveCtx, ServerSendCtx},
+    message_fns::MessageFns,
+    registry::RemoteMessageRegistry,
+    server_event::{ServerEvent, ServerTriggerEvent},
+};
+use crate::{postcard_utils, prelude::*, shared::server_entity_map::ServerEntityMap};
+
+/// An extension trait for [`App`] for creating messages attached to replicated entities.
+pub trait EntityMessageAppExt {
+    /**
+    Registers a server message that targets a replicated entity.
+
+    Send it using [`ServerTriggerExt::server_trigger`] with [`EntityMessage<M>`] that points to
+    a server entity. On clients [`EntityMessage<M>`] will be triggered for the mapped client entity,
+    so it can be observed on the entity itself.
+
+    Like regular server messages, it's received only after the replication message of the same tick,
+    so changes from the tick the message was sent in are already applied. If the entity isn't mapped
+    yet, for example, because it's not visible to the client, the message is queued until
+    it's mapped. Queued messages are discarded on disconnect or if the entity wasn't mapped
+    within [`ENTITY_MESSAGE_MAX_FRAMES`].
+
+    If [`ClientId::Server`] is a recipient of the message, it will be triggered for the server entity.
+
+    Entities inside `M` aren't mapped.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.add_entity_server_message::<Hit>(Channel::Ordered)
+        .add_observer(apply_hit);
+
+    fn apply_hit(hit: On<EntityMessage<Hit>>) {
+        info!("`{}` received {} damage", hit.event_target(), hit.damage);
+    }
+
+    fn send_hit(mut commands: Commands, target: Single<Entity, With<Replicated>>) {
+        commands.server_trigger(ToClients {
+            targets: SendTargets::All,
+            message: EntityMessage {
+                entity: *target,
+                message: Hit { damage: 10 },
+            },
+        });
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Hit {
+        damage: u32,
+    }
+    ```
+    */
+    fn add_entity_server_message<M: Serialize + DeserializeOwned + Send + Sync + 'static>(
+        &mut self,
+        channel: Channel,
+    ) -> &mut Self;
+}
+
+impl EntityMessageAppExt for App {
+    fn add_entity_server_message<M: Serialize + DeserializeOwned + Send + Sync + 'static>(
+        &mut self,
+        channel: Channel,
+    ) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ProtocolHasher>()
+            .add_server_event::<EntityMessage<M>>();
+
+        let fns = MessageFns::new(serialize::<M>, deserialize::<M>)
+            .with_convert::<ServerTriggerEvent<EntityMessage<M>>>();
+        let event = ServerEvent::new(self, channel, fns).with_trigger(trigger::<M>);
+        self.init_resource::<EntityMessageQueue<M>>();
+        let mut registry = self.world_mut().resource_mut::<RemoteMessageRegistry>();
+        registry.register_server_event(event);
+
+        self
+    }
+}
+
+/// Maximum number of frames a received [`EntityMessage`] waits for its entity to be mapped.
+pub const ENTITY_MESSAGE_MAX_FRAMES: u32 = 600;
+
+/// A message attached to a replicated entity.
+///
+/// See [`EntityMessageAppExt::add_entity_server_message`].
+#[derive(Deref, DerefMut, Debug, Clone, Copy)]
+pub struct EntityMessage<M> {
+    /// Target entity.
+    ///
+    /// Server entity when sending, mapped client entity when triggered on a client.
+    pub entity: Entity,
+
+    /// Transmitted message.
+    #[deref]
+    pub message: M,
+}
+
+impl<M: Send + Sync + 'static> Event for EntityMessage<M> {
+    type Trigger<'a> = EntityTrigger;
+}
+
+impl<M: Send + Sync + 'static> EntityEvent for EntityMessage<M> {
+    fn event_target(&self) -> Entity {
+        self.entity
+    }
+}
+
+fn serialize<M: Serialize>(
+    _ctx: &mut ServerSendCtx,
+    message: &EntityMessage<M>,
+    message_bytes: &mut Vec<u8>,
+) -> Result<()> {
+    postcard_utils::entity_to_extend_mut(&message.entity, message_bytes)?;
+    postcard_utils::to_extend_mut(&message.message, message_bytes)?;
+    Ok(())
+}
+
+/// Deserializes the message without mapping the entity.
+///
+/// The entity could be not mapped yet, so it's mapped when triggering.
+fn deserialize<M: DeserializeOwned>(
+    _ctx: &mut ClientReceiveCtx,
+    message_bytes: &mut Bytes,
+) -> Result<EntityMessage<M>> {
+    let entity = postcard_utils::entity_from_buf(message_bytes)?;
+    let message = postcard_utils::from_buf(message_bytes)?;
+    Ok(EntityMessage { entity, message })
+}
+
+/// Drains received messages and queues their delivery.
+///
+/// # Safety
+///
+/// The caller must ensure that `messages` is [`Messages<ServerTriggerEvent<EntityMessage<M>>>`].
+unsafe fn trigger<M: Send + Sync + 'static>(commands: &mut Commands, messages: PtrMut) {
+    let messages: &mut Messages<ServerTriggerEvent<EntityMessage<M>>> =
+        unsafe { messages.deref_mut() };
+    let received: Vec<_> = messages.drain().map(|message| message.event).collect();
+    commands.queue(move |world: &mut World| deliver(world, received));
+}
+
+/// Triggers received messages for mapped entities and queues the rest.
+///
+/// Messages sent locally already target server entities and triggered as is.
+fn deliver<M: Send + Sync + 'static>(world: &mut World, received: Vec<EntityMessage<M>>) {
+    if *world.resource::<State<ClientState>>() != ClientState::Connected {
+        let mut queue = world.resource_mut::<EntityMessageQueue<M>>();
+        if !queue.messages.is_empty() {
+            warn!(
+                "discarding {} queued messages `{}` due to a disconnect",
+                queue.messages.len(),
+                ShortName::of::<M>()
+            );
+            queue.messages.clear();
+        }
+
+        for message in received {
+            debug!("triggering `{}`", ShortName::of::<EntityMessage<M>>());
+            world.trigger(message);
+        }
+        return;
+    }
+
+    let mut ready = Vec::new();
+    world.resource_scope(|world, mut queue: Mut<EntityMessageQueue<M>>| {
+        let entity_map = world.resource::<ServerEntityMap>();
+        let queued = mem::take(&mut queue.messages);
+        for (frames, mut message) in queued
+            .into_iter()
+            .chain(received.into_iter().map(|message| (0, message)))
+        {
+            if let Some(&client_entity) = entity_map.to_client().get(&message.entity) {
+                message.entity = client_entity;
+                ready.push(message);
+            } else if frames < ENTITY_MESSAGE_MAX_FRAMES {
+                queue.messages.push((frames + 1, message));
+            } else {
+                warn!(
+                    "discarding message `{}` for unmapped `{}`",
+                    ShortName::of::<M>(),
+                    message.entity
+                );
+            }
+        }
+    });
+
+    for message in ready {
+        debug!(
+            "triggering `{}` for `{}`",
+            ShortName::of::<EntityMessage<M>>(),
+            message.entity
+        );
+        world.trigger(message);
+    }
+}
+
+/// Received messages for entities that aren't mapped yet.
+///
+/// Stores the number of frames each message was queued for.
+#[derive(Resource)]
+struct EntityMessageQueue<M> {
+    messages: Vec<(u32, EntityMessage<M>)>,
+}
+
+impl<M> Default for EntityMessageQueue<M> {
+    fn default() -> Self {
+        Self {
+            messages: Default::default(),
+        }
+    }
+}
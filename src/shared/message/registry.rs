@@ -6,6 +6,7 @@ use super::{
     client_event::ClientEvent, client_message::ClientMessage, server_event::ServerEvent,
     server_message::ServerMessage, shared_event::SharedEvent, shared_message::SharedMessage,
 };
+use crate::shared::backend::channels::{ClientChannelId, ServerChannelId};
 
 /// Registered server and client messages and events.
 #[derive(Resource, Default)]
@@ -81,6 +82,10 @@ impl RemoteMessageRegistry {
         self.server_events.iter_mut()
     }
 
+    pub(super) fn iter_client_messages_mut(&mut self) -> impl Iterator<Item = &mut ClientMessage> {
+        self.client_messages.iter_mut()
+    }
+
     pub(crate) fn iter_all_server(&self) -> impl Iterator<Item = &ServerMessage> {
         self.server_messages
             .iter()
@@ -114,7 +119,7 @@ impl RemoteMessageRegistry {
     /// Returns registered channel ID for server message `M`.
     ///
     /// See also [`ServerMessageAppExt::add_server_message`](super::server_message::ServerMessageAppExt::add_server_message).
-    pub fn server_message_channel<M: Message>(&self) -> Option<usize> {
+    pub fn server_message_channel<M: Message>(&self) -> Option<ServerChannelId> {
         self.server_messages
             .iter()
             .find(|m| m.type_id() == TypeId::of::<M>())
@@ -124,7 +129,7 @@ impl RemoteMessageRegistry {
     /// Returns registered channel ID for server event `E`.
     ///
     /// See also [`ServerEventAppExt::add_server_event`](super::server_event::ServerEventAppExt::add_server_event).
-    pub fn server_event_channel<E: Event>(&self) -> Option<usize> {
+    pub fn server_event_channel<E: Event>(&self) -> Option<ServerChannelId> {
         self.server_events
             .iter()
             .find(|e| e.type_id() == TypeId::of::<E>())
@@ -134,7 +139,7 @@ impl RemoteMessageRegistry {
     /// Returns registered channel ID for client message `M`.
     ///
     /// See also [`ClientMessageAppExt::add_client_message`](super::client_message::ClientMessageAppExt::add_client_message).
-    pub fn client_message_channel<M: Message>(&self) -> Option<usize> {
+    pub fn client_message_channel<M: Message>(&self) -> Option<ClientChannelId> {
         self.client_messages
             .iter()
             .find(|m| m.type_id() == TypeId::of::<M>())
@@ -144,7 +149,7 @@ impl RemoteMessageRegistry {
     /// Returns registered channel ID for client event `E`.
     ///
     /// See also [`ClientEventAppExt::add_client_event`](super::client_event::ClientEventAppExt::add_client_event).
-    pub fn client_event_channel<E: Event>(&self) -> Option<usize> {
+    pub fn client_event_channel<E: Event>(&self) -> Option<ClientChannelId> {
         self.client_events
             .iter()
             .find(|e| e.type_id() == TypeId::of::<E>())
@@ -154,7 +159,7 @@ impl RemoteMessageRegistry {
     /// Returns registered channel ID for shared message `M`.
     ///
     /// See also [`SharedMessageAppExt::add_shared_message`](super::shared_message::SharedMessageAppExt::add_shared_message).
-    pub fn shared_message_channel<M: Message>(&self) -> Option<usize> {
+    pub fn shared_message_channel<M: Message>(&self) -> Option<ClientChannelId> {
         self.shared_messages
             .iter()
             .find(|m| m.type_id() == TypeId::of::<M>())
@@ -164,7 +169,7 @@ impl RemoteMessageRegistry {
     /// Returns registered channel ID for shared event `E`.
     ///
     /// See also [`SharedEventAppExt::add_shared_event`](super::shared_event::SharedEventAppExt::add_shared_event).
-    pub fn shared_event_channel<E: Event>(&self) -> Option<usize> {
+    pub fn shared_event_channel<E: Event>(&self) -> Option<ClientChannelId> {
         self.shared_events
             .iter()
             .find(|e| e.type_id() == TypeId::of::<E>())
@@ -125,7 +125,7 @@ pub(crate) struct ServerEvent {
 }
 
 impl ServerEvent {
-    fn new<'a, E: Event<Trigger<'a>: Default>>(
+    pub(super) fn new<'a, E: Event<Trigger<'a>: Default>>(
         app: &mut App,
         channel: Channel,
         fns: MessageFns<ServerSendCtx, ClientReceiveCtx, ServerTriggerEvent<E>, E>,
@@ -137,6 +137,12 @@ impl ServerEvent {
         }
     }
 
+    /// Replaces the function that triggers received events.
+    pub(super) fn with_trigger(mut self, trigger: TriggerFn) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
     /// Drains received [`ServerTriggerEvent<E>`] messages and triggers them as `E`.
     ///
     /// # Safety
@@ -177,7 +183,7 @@ impl ServerEvent {
 }
 
 /// Signature of server event trigger functions.
-type TriggerFn = unsafe fn(&mut Commands, PtrMut);
+pub(super) type TriggerFn = unsafe fn(&mut Commands, PtrMut);
 
 /// Extension trait for triggering server events.
 ///
@@ -216,8 +222,8 @@ impl ServerTriggerExt for World {
 /// So we send this message instead and, after receiving it, drain it to trigger regular events.
 /// This also allows us to avoid requiring [`Clone`] because events can't be drained.
 #[derive(Message)]
-struct ServerTriggerEvent<E> {
-    event: E,
+pub(super) struct ServerTriggerEvent<E> {
+    pub(super) event: E,
 }
 
 impl<E> From<E> for ServerTriggerEvent<E> {
@@ -1,5 +1,6 @@
 pub(crate) mod message_buffer;
 mod message_queue;
+pub mod tick_stamped_message;
 
 use core::any::TypeId;
 
@@ -10,7 +11,6 @@ use bevy::{
 };
 use bytes::Bytes;
 use log::{debug, error, warn};
-use postcard::experimental::max_size::MaxSize;
 use serde::{Serialize, de::DeserializeOwned};
 
 use super::{
@@ -18,9 +18,17 @@ use super::{
     message_fns::{DeserializeFn, MessageFns, SerializeFn, UntypedMessageFns},
     registry::RemoteMessageRegistry,
 };
-use crate::{postcard_utils, prelude::*};
-use message_buffer::{MessageBuffer, SerializedMessage};
+use crate::{
+    postcard_utils,
+    prelude::*,
+    shared::{
+        backend::channels::{ChannelCreator, ServerChannelId},
+        replication_error::ReplicationErrorKind,
+    },
+};
+use message_buffer::MessageBuffer;
 use message_queue::MessageQueue;
+use tick_stamped_message::TickStampedMessage;
 
 /// An extension trait for [`App`] for creating server messages.
 ///
@@ -226,7 +234,7 @@ pub(crate) struct ServerMessage {
     queue_id: ComponentId,
 
     /// Used channel.
-    channel_id: usize,
+    channel_id: ServerChannelId,
 
     /// ID of `M`.
     type_id: TypeId,
@@ -247,7 +255,7 @@ impl ServerMessage {
         let channel_id = app
             .world_mut()
             .resource_mut::<RepliconChannels>()
-            .create_server_channel(channel);
+            .create_server_channel(channel, ChannelCreator::new::<M, I>());
 
         app.add_message::<M>()
             .add_message::<ToClients<M>>()
@@ -287,7 +295,7 @@ impl ServerMessage {
         self.queue_id
     }
 
-    pub(super) fn channel_id(&self) -> usize {
+    pub(super) fn channel_id(&self) -> ServerChannelId {
         self.channel_id
     }
 
@@ -436,12 +444,10 @@ impl ServerMessage {
         &self,
         ctx: &mut ServerSendCtx,
         message: &M,
-    ) -> Result<SerializedMessage> {
-        let mut message_bytes = vec![0; RepliconTick::POSTCARD_MAX_SIZE]; // Padding for the tick.
-        unsafe { self.serialize::<M, I>(ctx, message, &mut message_bytes)? }
-        let message = SerializedMessage::Raw(message_bytes);
-
-        Ok(message)
+    ) -> Result<TickStampedMessage> {
+        TickStampedMessage::new(|message_bytes| unsafe {
+            self.serialize::<M, I>(ctx, message, message_bytes)
+        })
     }
 
     /// Receives messages from the server.
@@ -488,10 +494,19 @@ impl ServerMessage {
                         );
                         messages.write(message);
                     }
-                    Err(e) => error!(
-                        "ignoring message `{}` from queue with `{tick:?}` that failed to deserialize: {e}",
-                        ShortName::of::<M>()
-                    ),
+                    Err(e) => {
+                        if ctx.errors.report(
+                            ReplicationErrorKind::Deserialize,
+                            Some(self.channel_id.get()),
+                            None,
+                            format_args!("`{}`: {e}", ShortName::of::<M>()),
+                        ) {
+                            error!(
+                                "ignoring message `{}` from queue with `{tick:?}` that failed to deserialize: {e}",
+                                ShortName::of::<M>()
+                            );
+                        }
+                    }
                 }
             }
         }
@@ -501,10 +516,17 @@ impl ServerMessage {
                 let tick: RepliconTick = match postcard_utils::from_buf(&mut message) {
                     Ok(tick) => tick,
                     Err(e) => {
-                        error!(
-                            "ignoring message `{}` because it's tick failed to deserialize: {e}",
-                            ShortName::of::<M>()
-                        );
+                        if ctx.errors.report(
+                            ReplicationErrorKind::Deserialize,
+                            Some(self.channel_id.get()),
+                            None,
+                            format_args!("`{}` tick: {e}", ShortName::of::<M>()),
+                        ) {
+                            error!(
+                                "ignoring message `{}` because it's tick failed to deserialize: {e}",
+                                ShortName::of::<M>()
+                            );
+                        }
                         continue;
                     }
                 };
@@ -525,10 +547,19 @@ impl ServerMessage {
                     debug!("writing message `{}`", ShortName::of::<M>());
                     messages.write(message);
                 }
-                Err(e) => error!(
-                    "ignoring message `{}` that failed to deserialize: {e}",
-                    ShortName::of::<M>()
-                ),
+                Err(e) => {
+                    if ctx.errors.report(
+                        ReplicationErrorKind::Deserialize,
+                        Some(self.channel_id.get()),
+                        None,
+                        format_args!("`{}`: {e}", ShortName::of::<M>()),
+                    ) {
+                        error!(
+                            "ignoring message `{}` that failed to deserialize: {e}",
+                            ShortName::of::<M>()
+                        );
+                    }
+                }
             }
         }
     }
@@ -1,11 +1,11 @@
-use core::mem;
-
 use bevy::{ecs::entity::hash_set::EntityHashSet, prelude::*};
-use bytes::Bytes;
 use log::{debug, error};
-use postcard::experimental::{max_size::MaxSize, serialized_size};
 
-use crate::{postcard_utils, prelude::*, shared::replication::client_ticks::ClientTicks};
+use super::tick_stamped_message::TickStampedMessage;
+use crate::{
+    prelude::*,
+    shared::{backend::channels::ServerChannelId, replication::client_ticks::ClientTicks},
+};
 
 /// Caches synchronization-dependent server messages until they can be sent with an accurate update tick.
 ///
@@ -34,8 +34,8 @@ impl MessageBuffer {
     pub(super) fn insert(
         &mut self,
         targets: SendTargets,
-        channel_id: usize,
-        message: SerializedMessage,
+        channel_id: ServerChannelId,
+        message: TickStampedMessage,
     ) {
         let buffer = self
             .active_tick()
@@ -48,6 +48,11 @@ impl MessageBuffer {
         });
     }
 
+    /// Returns the number of buffered messages across all ticks.
+    pub(crate) fn len(&self) -> usize {
+        self.ticks.iter().map(|tick| tick.messages.len()).sum()
+    }
+
     /// Used to prevent newly-connected clients from receiving old messages.
     pub(crate) fn exclude_client(&mut self, client: Entity) {
         for set in self.ticks.iter_mut() {
@@ -143,8 +148,8 @@ impl TickMessages {
 
 struct BufferedMessage {
     targets: SendTargets,
-    channel_id: usize,
-    message: SerializedMessage,
+    channel_id: ServerChannelId,
+    message: TickStampedMessage,
 }
 
 impl BufferedMessage {
@@ -154,70 +159,8 @@ impl BufferedMessage {
         client: Entity,
         ticks: &ClientTicks,
     ) -> Result<()> {
-        let message = self.message.get_bytes(ticks.update_tick)?;
+        let message = self.message.stamp(ticks.update_tick)?;
         messages.send(client, self.channel_id, message);
         Ok(())
     }
 }
-
-/// Cached message for use in [`MessageBuffer`].
-pub(super) enum SerializedMessage {
-    /// A message without serialized tick.
-    ///
-    /// `padding | message`
-    ///
-    /// The padding length equals max serialized bytes of [`RepliconTick`]. It should be overwritten before sending
-    /// to clients.
-    Raw(Vec<u8>),
-    /// A message with serialized tick.
-    ///
-    /// `tick | message`
-    Resolved {
-        tick: RepliconTick,
-        tick_size: usize,
-        bytes: Bytes,
-    },
-}
-
-impl SerializedMessage {
-    /// Optimized to avoid reallocations when clients have the same update tick as other clients receiving the
-    /// same message.
-    fn get_bytes(&mut self, update_tick: RepliconTick) -> Result<Bytes> {
-        match self {
-            // Resolve the raw value into a message with serialized tick.
-            Self::Raw(raw) => {
-                let mut bytes = mem::take(raw);
-
-                // Serialize the tick at the end of the pre-allocated space for it,
-                // then shift the buffer to avoid reallocation.
-                let tick_size = serialized_size(&update_tick)?;
-                let padding = RepliconTick::POSTCARD_MAX_SIZE - tick_size;
-                postcard::to_slice(&update_tick, &mut bytes[padding..])?;
-                let bytes = Bytes::from(bytes).slice(padding..);
-
-                *self = Self::Resolved {
-                    tick: update_tick,
-                    tick_size,
-                    bytes: bytes.clone(),
-                };
-                Ok(bytes)
-            }
-            // Get the already-resolved value or reserialize with a different tick.
-            Self::Resolved {
-                tick,
-                tick_size,
-                bytes,
-            } => {
-                if *tick == update_tick {
-                    return Ok(bytes.clone());
-                }
-
-                let new_tick_size = serialized_size(&update_tick)?;
-                let mut new_bytes = Vec::with_capacity(new_tick_size + bytes.len() - *tick_size);
-                postcard_utils::to_extend_mut(&update_tick, &mut new_bytes)?;
-                new_bytes.extend_from_slice(&bytes[*tick_size..]);
-                Ok(new_bytes.into())
-            }
-        }
-    }
-}
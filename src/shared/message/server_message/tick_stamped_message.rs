@@ -0,0 +1,183 @@
+use core::mem;
+
+use bevy::prelude::*;
+use bytes::Bytes;
+use postcard::experimental::{max_size::MaxSize, serialized_size};
+
+use crate::{postcard_utils, prelude::*};
+
+/**
+Serialized message that will be prefixed with an update tick of each client.
+
+Messages that reference replicated entities need to be applied on the client only after
+the corresponding replication update is received. To achieve this, server messages are prefixed
+with the update tick of the receiving client, and the client queues them until its
+[`ServerUpdateTick`](crate::client::ServerUpdateTick) reaches that tick.
+
+But the update tick is different for each client and known only after replication is collected,
+while serializing the message separately for each client would be wasteful. So this type serializes
+the message once with space reserved for the tick and writes the tick for each client at send time.
+Clients with the same tick share the same allocation.
+
+This is what regular server messages use internally. It's exposed for custom message kinds that
+need the same consistency with replication.
+
+# Examples
+
+Sending the message from the server:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    postcard_utils,
+    prelude::*,
+    shared::{
+        backend::channels::ServerChannelId,
+        message::server_message::tick_stamped_message::TickStampedMessage,
+        replication::client_ticks::ClientTicks,
+    },
+};
+
+fn send_explosion(
+    messages: &mut ServerMessages,
+    clients: &Query<(Entity, &ClientTicks)>,
+    channel_id: ServerChannelId,
+) -> Result<()> {
+    let mut message = TickStampedMessage::new(|bytes| {
+        postcard_utils::to_extend_mut(&Vec2::new(1.0, 2.0), bytes)?;
+        Ok(())
+    })?;
+
+    for (client, ticks) in clients {
+        let bytes = message.stamp(ticks.update_tick())?;
+        messages.send(client, channel_id, bytes);
+    }
+
+    Ok(())
+}
+```
+
+On the client, read the tick with [`postcard_utils::from_buf`] and process the message only after
+[`ServerUpdateTick`](crate::client::ServerUpdateTick) reaches it:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{bytes::Bytes, client::ServerUpdateTick, postcard_utils, prelude::*};
+
+fn receive_explosion(
+    update_tick: &ServerUpdateTick,
+    queue: &mut Vec<(RepliconTick, Bytes)>,
+    mut message: Bytes,
+) -> Result<()> {
+    let tick: RepliconTick = postcard_utils::from_buf(&mut message)?;
+    if tick.is_newer(**update_tick) {
+        // The referenced entities haven't been received yet.
+        queue.push((tick, message));
+        return Ok(());
+    }
+
+    let position: Vec2 = postcard_utils::from_buf(&mut message)?;
+    info!("explosion at {position}");
+    Ok(())
+}
+```
+*/
+pub struct TickStampedMessage(StampState);
+
+impl TickStampedMessage {
+    /// Serializes a message with space reserved for the tick.
+    ///
+    /// The passed function should only append to the buffer.
+    pub fn new(serialize: impl FnOnce(&mut Vec<u8>) -> Result<()>) -> Result<Self> {
+        let mut bytes = vec![0; RepliconTick::POSTCARD_MAX_SIZE]; // Padding for the tick.
+        (serialize)(&mut bytes)?;
+        debug_assert!(
+            bytes.len() >= RepliconTick::POSTCARD_MAX_SIZE,
+            "the padding for the tick shouldn't be removed"
+        );
+
+        Ok(Self(StampState::Raw(bytes)))
+    }
+
+    /// Returns the message prefixed with the given tick.
+    ///
+    /// The first call writes the tick into the reserved space without reallocation.
+    /// Subsequent calls with the same tick return the same allocation,
+    /// and calls with different ticks copy the message.
+    pub fn stamp(&mut self, update_tick: RepliconTick) -> Result<Bytes> {
+        match &mut self.0 {
+            StampState::Raw(raw) => {
+                let mut bytes = mem::take(raw);
+
+                // Serialize the tick at the end of the pre-allocated space for it,
+                // then shift the buffer to avoid reallocation.
+                let tick_size = serialized_size(&update_tick)?;
+                let padding = RepliconTick::POSTCARD_MAX_SIZE - tick_size;
+                postcard::to_slice(&update_tick, &mut bytes[padding..])?;
+                let bytes = Bytes::from(bytes).slice(padding..);
+
+                self.0 = StampState::Stamped {
+                    tick: update_tick,
+                    tick_size,
+                    bytes: bytes.clone(),
+                };
+                Ok(bytes)
+            }
+            StampState::Stamped {
+                tick,
+                tick_size,
+                bytes,
+            } => {
+                if *tick == update_tick {
+                    return Ok(bytes.clone());
+                }
+
+                let new_tick_size = serialized_size(&update_tick)?;
+                let mut new_bytes = Vec::with_capacity(new_tick_size + bytes.len() - *tick_size);
+                postcard_utils::to_extend_mut(&update_tick, &mut new_bytes)?;
+                new_bytes.extend_from_slice(&bytes[*tick_size..]);
+                Ok(new_bytes.into())
+            }
+        }
+    }
+}
+
+enum StampState {
+    /// A message without serialized tick.
+    ///
+    /// `padding | message`
+    ///
+    /// The padding length equals max serialized bytes of [`RepliconTick`].
+    Raw(Vec<u8>),
+    /// A message with serialized tick.
+    ///
+    /// `tick | message`
+    Stamped {
+        tick: RepliconTick,
+        tick_size: usize,
+        bytes: Bytes,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamp() {
+        let mut message = TickStampedMessage::new(|bytes| {
+            bytes.push(42);
+            Ok(())
+        })
+        .unwrap();
+
+        let first = message.stamp(RepliconTick::new(1)).unwrap();
+        assert_eq!(first, [1, 42].as_slice());
+
+        let same = message.stamp(RepliconTick::new(1)).unwrap();
+        assert_eq!(same.as_ptr(), first.as_ptr(), "should reuse the allocation");
+
+        let different = message.stamp(RepliconTick::new(300)).unwrap();
+        assert_eq!(different, [172, 2, 42].as_slice());
+    }
+}
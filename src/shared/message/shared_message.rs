@@ -15,7 +15,14 @@ use super::{
     message_fns::{DeserializeFn, MessageFns, SerializeFn, UntypedMessageFns},
     registry::RemoteMessageRegistry,
 };
-use crate::prelude::*;
+use crate::{
+    prelude::*,
+    shared::{
+        backend::channels::{ChannelCreator, ClientChannelId},
+        replication::registry::serde_fns::catch_panic,
+        replication_error::ReplicationErrorKind,
+    },
+};
 
 /// An extension trait for [`App`] for creating shared messages.
 ///
@@ -105,7 +112,7 @@ pub(crate) struct SharedMessage {
     shared_messages_id: ComponentId,
 
     /// Used channel.
-    channel_id: usize,
+    channel_id: ClientChannelId,
 
     /// ID of `M`.
     type_id: TypeId,
@@ -126,7 +133,7 @@ impl SharedMessage {
         let channel_id = app
             .world_mut()
             .resource_mut::<RepliconChannels>()
-            .create_client_channel(channel);
+            .create_client_channel(channel, ChannelCreator::new::<M, I>());
 
         app.add_message::<M>().add_message::<LocalOrRemote<M>>();
 
@@ -157,7 +164,7 @@ impl SharedMessage {
         self.shared_messages_id
     }
 
-    pub(super) fn channel_id(&self) -> usize {
+    pub(super) fn channel_id(&self) -> ClientChannelId {
         self.channel_id
     }
 
@@ -200,10 +207,17 @@ impl SharedMessage {
         for message in messages.drain() {
             let mut message_bytes = Vec::new();
             if let Err(e) = unsafe { self.serialize::<M, I>(ctx, &message, &mut message_bytes) } {
-                error!(
-                    "ignoring message `{}` that failed to serialize: {e}",
-                    ShortName::of::<M>()
-                );
+                if ctx.errors.report(
+                    ReplicationErrorKind::Serialize,
+                    Some(self.channel_id.get()),
+                    None,
+                    format_args!("`{}`: {e}", ShortName::of::<M>()),
+                ) {
+                    error!(
+                        "ignoring message `{}` that failed to serialize: {e}",
+                        ShortName::of::<M>()
+                    );
+                }
                 continue;
             }
 
@@ -257,10 +271,19 @@ impl SharedMessage {
                         message,
                     });
                 }
-                Err(e) => debug!(
-                    "ignoring message `{}` from client `{client}` that failed to deserialize: {e}",
-                    ShortName::of::<M>()
-                ),
+                Err(e) => {
+                    if ctx.errors.report(
+                        ReplicationErrorKind::Deserialize,
+                        Some(self.channel_id.get()),
+                        Some(client),
+                        format_args!("`{}`: {e}", ShortName::of::<M>()),
+                    ) {
+                        debug!(
+                            "ignoring message `{}` from client `{client}` that failed to deserialize: {e}",
+                            ShortName::of::<M>()
+                        );
+                    }
+                }
             }
         }
     }
@@ -355,6 +378,9 @@ impl SharedMessage {
 
     /// Deserializes a message.
     ///
+    /// Caught panics are returned as errors, so [`ReplicationErrorSettings::client_policy`]
+    /// applies to the client that sent the message.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that this instance was created for `M` and `I`.
@@ -363,11 +389,12 @@ impl SharedMessage {
         ctx: &mut ServerReceiveCtx,
         message: &mut Bytes,
     ) -> Result<M> {
-        unsafe {
+        catch_panic(|| unsafe {
             self.fns
                 .typed::<ClientSendCtx, ServerReceiveCtx, M, I>()
                 .deserialize(ctx, message)
-        }
+        })
+        .unwrap_or_else(|panic| Err(format!("deserialization panicked: {panic}").into()))
     }
 }
 
@@ -1,3 +1,6 @@
+pub mod description;
+
+use alloc::{string::String, vec::Vec};
 use core::{
     any,
     fmt::Debug,
@@ -10,6 +13,9 @@ use log::debug;
 use serde::{Deserialize, Serialize};
 use xxhash_rust::xxh3::Xxh3Default;
 
+use crate::shared::replication::registry::ReplicationRegistry;
+use description::{ProtocolRegistrations, Registration, RegistrationKind};
+
 /// Hashes all protocol registrations to calculate [`ProtocolHash`].
 ///
 /// The hash is computed using type names and their use in the protocol. We can't detect
@@ -18,11 +24,22 @@ use xxhash_rust::xxh3::Xxh3Default;
 /// different registration order or accidentally registering different things on
 /// the client and server, which are very difficult to debug.
 ///
-/// You can include custom data (e.g., a game version) via [`Self::add_custom`].
+/// You can include custom data (e.g., a game version) via [`Self::add_custom`]
+/// and content versions via [`Self::mix`].
 ///
-/// Only available during the [`Plugin::build`] stage. Computes [`ProtocolHash`] resource.
+/// Optional replication rules are hashed separately into [`ProtocolBreakdown::optional`],
+/// see [`AppRuleExt::replicate_optional`](crate::shared::replication::rules::AppRuleExt::replicate_optional).
+///
+/// Only available during the [`Plugin::build`] stage. Computes [`ProtocolHash`],
+/// [`ProtocolBreakdown`] and [`ProtocolRegistrations`] resources.
 #[derive(Resource, Default)]
-pub struct ProtocolHasher(DeterministicHasher<Xxh3Default>);
+pub struct ProtocolHasher {
+    hasher: DeterministicHasher<Xxh3Default>,
+    domains: Vec<(String, u64)>,
+    registrations: Vec<Registration>,
+    optional: bool,
+    optional_rules: Vec<u64>,
+}
 
 impl ProtocolHasher {
     /// Adds custom data to the protocol hash calculation.
@@ -47,7 +64,41 @@ impl ProtocolHasher {
     /// ```
     pub fn add_custom<T: Hash + Debug>(&mut self, value: T) {
         debug!("adding `{value:?}`");
-        value.hash(&mut self.0);
+        value.hash(&mut self.hasher);
+    }
+
+    /// Mixes a version of a user domain into the protocol hash.
+    ///
+    /// Unlike [`Self::add_custom`], the value is tracked separately in [`ProtocolBreakdown`],
+    /// so on mismatch the client can tell which domain differs. Use it for content that can change
+    /// independently from the code, such as an asset schema or a hash of balancing tables.
+    ///
+    /// Domains should be mixed in the same order on the client and server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a domain with the same label was already mixed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::{prelude::*, state::app::StatesPlugin};
+    /// use bevy_replicon::prelude::*;
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    ///
+    /// # let balance_hash = 0;
+    /// let mut hasher = app.world_mut().resource_mut::<ProtocolHasher>();
+    /// hasher.mix("assets", 3);
+    /// hasher.mix("balance", balance_hash);
+    /// ```
+    pub fn mix(&mut self, label: &str, value: u64) {
+        assert!(
+            !self.domains.iter().any(|(existing, _)| existing == label),
+            "domain `{label}` should be mixed only once"
+        );
+        debug!("mixing domain `{label}` with value {value}");
+        self.domains.push((label.into(), value));
     }
 
     pub(crate) fn replicate<R>(&mut self, priority: usize) {
@@ -55,7 +106,7 @@ impl ProtocolHasher {
             "adding replication rule `{}` with priority {priority}",
             ShortName::of::<R>()
         );
-        self.hash::<R>(ProtocolPart::Replicate {
+        self.hash_rule::<R>(ProtocolPart::Replicate {
             priority: priority as u64,
         });
     }
@@ -65,7 +116,27 @@ impl ProtocolHasher {
             "adding replication rule for bundle `{}`",
             ShortName::of::<B>()
         );
-        self.hash::<B>(ProtocolPart::ReplicateBundle);
+        self.hash_rule::<B>(ProtocolPart::ReplicateBundle);
+    }
+
+    pub(crate) fn replicate_lod<C>(&mut self, bands: usize) {
+        debug!(
+            "adding LOD replication rule `{}` with {bands} bands",
+            ShortName::of::<C>()
+        );
+        self.hash_rule::<C>(ProtocolPart::ReplicateLod {
+            bands: bands as u64,
+        });
+    }
+
+    pub(crate) fn prefix_component_sizes(&mut self) {
+        debug!("adding component size prefixes");
+        self.hash::<ReplicationRegistry>(ProtocolPart::ComponentSizes);
+    }
+
+    /// Marks replication rules added after this call as optional or required.
+    pub(crate) fn set_optional(&mut self, optional: bool) {
+        self.optional = optional;
     }
 
     pub(crate) fn add_client_message<E>(&mut self) {
@@ -108,15 +179,49 @@ impl ProtocolHasher {
         self.hash::<E>(ProtocolPart::IndependentEvent);
     }
 
+    pub(crate) fn add_raw_channel<C>(&mut self) {
+        debug!("adding raw channel `{}`", ShortName::of::<C>());
+        self.hash::<C>(ProtocolPart::RawChannel);
+    }
+
+    fn hash_rule<T>(&mut self, part: ProtocolPart) {
+        if !self.optional {
+            self.hash::<T>(part);
+            return;
+        }
+
+        debug!("marking `{}` as optional", ShortName::of::<T>());
+        let mut hasher = DeterministicHasher::<Xxh3Default>::default();
+        part.hash(&mut hasher);
+        any::type_name::<T>().hash(&mut hasher);
+        self.optional_rules.push(hasher.finish());
+    }
+
     fn hash<T>(&mut self, part: ProtocolPart) {
-        part.hash(&mut self.0);
-        any::type_name::<T>().hash(&mut self.0);
+        part.hash(&mut self.hasher);
+        any::type_name::<T>().hash(&mut self.hasher);
+        self.registrations.push(Registration {
+            kind: part.into(),
+            type_name: any::type_name::<T>().into(),
+        });
+    }
+
+    pub(crate) fn registrations(&self) -> ProtocolRegistrations {
+        ProtocolRegistrations(self.registrations.clone())
+    }
+
+    pub(crate) fn breakdown(&self) -> ProtocolBreakdown {
+        ProtocolBreakdown {
+            registrations: self.hasher.finish(),
+            domains: self.domains.clone(),
+            optional: self.optional_rules.clone(),
+        }
     }
 
     pub(crate) fn finish(self) -> ProtocolHash {
-        let hash = self.0.finish();
-        debug!("calculated hash: {hash}");
-        ProtocolHash(hash)
+        let hash = self.breakdown().hash();
+        debug!("calculated hash: {hash:?}");
+        hash
     }
 }
 
@@ -139,6 +244,29 @@ enum ProtocolPart {
     IndependentEvent,
     SharedMessage,
     SharedEvent,
+    RawChannel,
+    ReplicateLod { bands: u64 },
+    ComponentSizes,
+}
+
+impl From<ProtocolPart> for RegistrationKind {
+    fn from(part: ProtocolPart) -> Self {
+        match part {
+            ProtocolPart::Replicate { priority } => Self::Replicate { priority },
+            ProtocolPart::ReplicateBundle => Self::ReplicateBundle,
+            ProtocolPart::ReplicateLod { bands } => Self::ReplicateLod { bands },
+            ProtocolPart::ClientMessage => Self::ClientMessage,
+            ProtocolPart::ClientEvent => Self::ClientEvent,
+            ProtocolPart::ServerMessage => Self::ServerMessage,
+            ProtocolPart::ServerEvent => Self::ServerEvent,
+            ProtocolPart::IndependentMessage => Self::IndependentMessage,
+            ProtocolPart::IndependentEvent => Self::IndependentEvent,
+            ProtocolPart::SharedMessage => Self::SharedMessage,
+            ProtocolPart::SharedEvent => Self::SharedEvent,
+            ProtocolPart::RawChannel => Self::RawChannel,
+            ProtocolPart::ComponentSizes => Self::ComponentSizes,
+        }
+    }
 }
 
 /// Hash of all registered events and replication rules.
@@ -149,17 +277,80 @@ enum ProtocolPart {
 #[derive(Resource, Event, Serialize, Deserialize, Reflect, Debug, PartialEq, Eq, Clone, Copy)]
 pub struct ProtocolHash(u64);
 
-/// A server event to notify client for the protocol mismatch.
+/// Parts from which [`ProtocolHash`] is calculated.
+///
+/// Calculated by [`ProtocolHasher`] and available only after [`Plugin::finish`].
+/// Sent to the client inside [`AuthDenial::ProtocolMismatch`](super::authorization::AuthDenial::ProtocolMismatch)
+/// to compare with the local breakdown.
+#[derive(Resource, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+pub struct ProtocolBreakdown {
+    /// Hash of all registrations and data from [`ProtocolHasher::add_custom`].
+    pub registrations: u64,
+
+    /// Domains from [`ProtocolHasher::mix`] with their values in order of mixing.
+    pub domains: Vec<(String, u64)>,
+
+    /// Hashes of optional replication rules in order of registration.
+    ///
+    /// Not part of [`ProtocolHash`]. The client's optional rules should be a prefix of the server's.
+    pub optional: Vec<u64>,
+}
+
+impl ProtocolBreakdown {
+    /// Returns labels of domains whose values differ or that are present only in one of the breakdowns.
+    pub fn mismatched_domains<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a str> {
+        let changed = self
+            .domains
+            .iter()
+            .filter(|(label, value)| other.domain(label) != Some(*value));
+        let missing = other
+            .domains
+            .iter()
+            .filter(|(label, _)| self.domain(label).is_none());
+
+        changed.chain(missing).map(|(label, _)| &**label)
+    }
+
+    /// Returns `true` if optional rules of `self` are a prefix of optional rules of `other`.
+    pub fn optional_prefix_of(&self, other: &Self) -> bool {
+        other.optional.starts_with(&self.optional)
+    }
+
+    /// Returns the value of a mixed domain.
+    pub fn domain(&self, label: &str) -> Option<u64> {
+        self.domains
+            .iter()
+            .find_map(|(existing, value)| (existing == label).then_some(*value))
+    }
+
+    fn hash(&self) -> ProtocolHash {
+        if self.domains.is_empty() {
+            return ProtocolHash(self.registrations);
+        }
+
+        let mut hasher = DeterministicHasher::<Xxh3Default>::default();
+        self.registrations.hash(&mut hasher);
+        self.domains.hash(&mut hasher);
+        ProtocolHash(hasher.finish())
+    }
+}
+
+/// Triggered on the client when the server denies authorization due to the protocol mismatch.
 ///
-/// Registered and sent only if [`RepliconSharedPlugin::auth_method`](super::RepliconSharedPlugin::auth_method)
-/// set to [`AuthMethod::ProtocolCheck`](super::AuthMethod::ProtocolCheck). The server will immediately
-/// disconnect after sending it, so there is no delivery guarantee.
+/// Triggered locally from [`AuthorizationFailed`](super::authorization::AuthorizationFailed)
+/// with [`AuthDenial::ProtocolMismatch`](super::authorization::AuthDenial::ProtocolMismatch).
+///
+/// Contains the server's [`ProtocolBreakdown`]. On trigger, the client logs which parts don't match,
+/// which helps to distinguish a code mismatch from a content mismatch.
 ///
 /// If you need to debug the problem, compare the logs for protocol registrations on both sides.
 /// The ordering is important. You can also log only registrations by filtering with `bevy_replicon::shared::protocol`.
 /// For more details, see the [troubleshooting section](crate#troubleshooting) from the quick start guide.
-#[derive(Event, Serialize, Deserialize)]
-pub struct ProtocolMismatch;
+#[derive(Event, Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ProtocolMismatch {
+    /// Breakdown of the server's protocol.
+    pub server: ProtocolBreakdown,
+}
 
 #[cfg(test)]
 mod tests {
@@ -245,6 +436,92 @@ mod tests {
         assert_eq!(hasher2.finish(), EXPECTED);
     }
 
+    #[test]
+    fn domains() {
+        let mut hasher1 = ProtocolHasher::default();
+        let mut hasher2 = ProtocolHasher::default();
+
+        for hasher in [&mut hasher1, &mut hasher2] {
+            hasher.replicate::<StructA>(1);
+            hasher.mix("assets", 1);
+        }
+        hasher1.mix("balance", 0);
+        hasher2.mix("balance", 1);
+
+        let breakdown1 = hasher1.breakdown();
+        let breakdown2 = hasher2.breakdown();
+        assert_eq!(breakdown1.registrations, breakdown2.registrations);
+        assert_eq!(
+            breakdown1
+                .mismatched_domains(&breakdown2)
+                .collect::<Vec<_>>(),
+            ["balance"]
+        );
+
+        assert_ne!(hasher1.finish(), hasher2.finish());
+    }
+
+    #[test]
+    fn missing_domain() {
+        let mut hasher1 = ProtocolHasher::default();
+        hasher1.mix("assets", 1);
+
+        let hasher2 = ProtocolHasher::default();
+
+        let breakdown1 = hasher1.breakdown();
+        let breakdown2 = hasher2.breakdown();
+        assert_eq!(
+            breakdown1
+                .mismatched_domains(&breakdown2)
+                .collect::<Vec<_>>(),
+            ["assets"]
+        );
+        assert_eq!(
+            breakdown2
+                .mismatched_domains(&breakdown1)
+                .collect::<Vec<_>>(),
+            ["assets"]
+        );
+
+        assert_ne!(hasher1.finish(), hasher2.finish());
+    }
+
+    #[test]
+    fn optional() {
+        let mut hasher1 = ProtocolHasher::default();
+        let mut hasher2 = ProtocolHasher::default();
+
+        for hasher in [&mut hasher1, &mut hasher2] {
+            hasher.replicate::<StructA>(1);
+            hasher.set_optional(true);
+            hasher.replicate::<StructB>(1);
+        }
+        hasher1.replicate::<StructC>(1);
+
+        let breakdown1 = hasher1.breakdown();
+        let breakdown2 = hasher2.breakdown();
+        assert_eq!(breakdown1.optional.len(), 2);
+        assert!(breakdown2.optional_prefix_of(&breakdown1));
+        assert!(!breakdown1.optional_prefix_of(&breakdown2));
+        assert_eq!(hasher1.finish(), hasher2.finish());
+    }
+
+    #[test]
+    fn optional_order() {
+        let mut hasher1 = ProtocolHasher::default();
+        hasher1.set_optional(true);
+        hasher1.replicate::<StructA>(1);
+        hasher1.replicate::<StructB>(1);
+
+        let mut hasher2 = ProtocolHasher::default();
+        hasher2.set_optional(true);
+        hasher2.replicate::<StructB>(1);
+
+        let breakdown1 = hasher1.breakdown();
+        let breakdown2 = hasher2.breakdown();
+        assert!(!breakdown2.optional_prefix_of(&breakdown1));
+    }
+
     struct StructA;
     struct StructB;
     struct StructC;
@@ -0,0 +1,308 @@
+//! Machine-readable description of the protocol for external tools.
+//!
+//! See [`ProtocolDescription`] for details.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use bevy::{ecs::component::ComponentId, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::{ProtocolBreakdown, ProtocolHash};
+use crate::shared::{
+    backend::channels::{Channel, ChannelCreator, ChannelId, ChannelInfo, RepliconChannels},
+    replication::{
+        registry::{FnsId, ReplicationRegistry},
+        rules::ReplicationRules,
+    },
+};
+
+/**
+Full description of the protocol.
+
+Contains everything needed to decode traffic without access to the Rust code, so
+external tools like packet dissectors, server browsers, or bots written in other languages
+can use it. Implements [`Serialize`], so it can be exported in any format, such as JSON.
+
+Can be created only after [`App::finish`], because channels for messages and events
+are allocated during it. The description reflects the local app, so optional rules from
+[`AppRuleExt::replicate_optional`](crate::prelude::AppRuleExt::replicate_optional)
+may differ between the client and server.
+
+Component names are obtained from Bevy, so they require its `debug` feature.
+
+# Examples
+
+```
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, shared::protocol::description::ProtocolDescription};
+use serde::{Deserialize, Serialize};
+
+let mut app = App::new();
+app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+    .replicate::<Health>()
+    .add_server_message::<Chat>(Channel::Ordered)
+    .finish();
+
+let description = ProtocolDescription::new(app.world());
+assert_eq!(description.hash, *app.world().resource::<ProtocolHash>());
+assert!(
+    description
+        .rules
+        .iter()
+        .flat_map(|rule| &rule.components)
+        .any(|component| component.type_name.ends_with("Health"))
+);
+
+// Serialize with any `serde` format, for example, JSON, and write to a file.
+
+#[derive(Component, Serialize, Deserialize)]
+struct Health(u32);
+
+#[derive(Message, Serialize, Deserialize)]
+struct Chat(String);
+```
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProtocolDescription {
+    /// Hash of the protocol that the client sends to the server.
+    pub hash: ProtocolHash,
+
+    /// Parts from which [`Self::hash`] is calculated.
+    pub breakdown: ProtocolBreakdown,
+
+    /// All registrations in the order in which they were hashed.
+    pub registrations: Vec<Registration>,
+
+    /// Replication rules sorted by priority.
+    pub rules: Vec<RuleDescription>,
+
+    /// Channels for sending data from server to client, indexed by their IDs.
+    pub server_channels: Vec<ChannelDescription>,
+
+    /// Channels for sending data from client to server, indexed by their IDs.
+    pub client_channels: Vec<ChannelDescription>,
+}
+
+impl ProtocolDescription {
+    /// Collects the description from the world.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`App::finish`].
+    pub fn new(world: &World) -> Self {
+        let registry = world.resource::<ReplicationRegistry>();
+        let rules = world
+            .resource::<ReplicationRules>()
+            .iter()
+            .map(|rule| RuleDescription {
+                priority: rule.priority,
+                components: rule
+                    .components
+                    .iter()
+                    .map(|component| ComponentDescription {
+                        fns_id: component.fns_id,
+                        type_name: component_name(world, component.id),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let channels = world.resource::<RepliconChannels>();
+        let server_channels = channels
+            .server_ids()
+            .filter_map(|id| channels.server_channel(id))
+            .map(Into::into)
+            .collect();
+        let client_channels = channels
+            .client_ids()
+            .filter_map(|id| channels.client_channel(id))
+            .map(Into::into)
+            .collect();
+
+        let mut registrations = world
+            .get_resource::<ProtocolRegistrations>()
+            .expect("protocol description should be created after `App::finish`")
+            .0
+            .clone();
+
+        // Rule functions can be registered manually without a rule, so include them as well.
+        for (fns_id, component_id) in registry.rule_components() {
+            registrations.push(Registration {
+                kind: RegistrationKind::RuleFns { fns_id },
+                type_name: component_name(world, component_id),
+            });
+        }
+
+        Self {
+            hash: *world.resource::<ProtocolHash>(),
+            breakdown: world.resource::<ProtocolBreakdown>().clone(),
+            registrations,
+            rules,
+            server_channels,
+            client_channels,
+        }
+    }
+}
+
+fn component_name(world: &World, component_id: ComponentId) -> String {
+    world
+        .components()
+        .get_name(component_id)
+        .map(|name| name.to_string())
+        .unwrap_or_default()
+}
+
+/// Registrations from [`ProtocolHasher`](super::ProtocolHasher) in the order in which they were hashed.
+///
+/// Inserted after [`App::finish`]. Used to create [`ProtocolDescription`].
+#[derive(Resource, Deref, Debug, Default, Clone)]
+pub struct ProtocolRegistrations(pub(super) Vec<Registration>);
+
+/// A single protocol registration.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Registration {
+    /// How the type is used in the protocol.
+    pub kind: RegistrationKind,
+
+    /// Full type name.
+    pub type_name: String,
+}
+
+/// Use of a type in the protocol.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationKind {
+    /// Replication rule.
+    Replicate {
+        /// Priority of the rule.
+        priority: u64,
+    },
+    /// Replication rule for a bundle.
+    ReplicateBundle,
+    /// Replication rule with distance-based level of detail.
+    ReplicateLod {
+        /// Number of bands of the rule.
+        bands: u64,
+    },
+    /// Functions of a replicated component, identified on the wire by their ID.
+    ///
+    /// Not part of [`ProtocolHash`].
+    RuleFns {
+        /// ID of the functions.
+        fns_id: FnsId,
+    },
+    /// Client message.
+    ClientMessage,
+    /// Client event.
+    ClientEvent,
+    /// Server message.
+    ServerMessage,
+    /// Server event.
+    ServerEvent,
+    /// Server message that is applied without waiting for replication.
+    IndependentMessage,
+    /// Server event that is applied without waiting for replication.
+    IndependentEvent,
+    /// Message sent in both directions.
+    SharedMessage,
+    /// Event sent in both directions.
+    SharedEvent,
+    /// Raw byte channel.
+    RawChannel,
+    /// Size prefixes for all components.
+    ///
+    /// See [`AppRuleExt::prefix_component_sizes`](crate::prelude::AppRuleExt::prefix_component_sizes).
+    ComponentSizes,
+}
+
+/// Replication rule from [`ReplicationRules`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RuleDescription {
+    /// Priority of the rule.
+    pub priority: usize,
+
+    /// Components of the rule.
+    pub components: Vec<ComponentDescription>,
+}
+
+/// Component from [`RuleDescription`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDescription {
+    /// ID of the functions used to serialize the component on the wire.
+    pub fns_id: FnsId,
+
+    /// Full type name.
+    pub type_name: String,
+}
+
+/// Channel from [`RepliconChannels`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChannelDescription {
+    /// Channel ID, unique for each direction.
+    pub id: usize,
+
+    /// Delivery guarantee.
+    pub kind: Channel,
+
+    /// What the channel is used for.
+    pub purpose: ChannelPurpose,
+
+    /// Send priority of the channel.
+    pub send_priority: u8,
+}
+
+impl From<ChannelInfo> for ChannelDescription {
+    fn from(info: ChannelInfo) -> Self {
+        Self {
+            id: match info.id {
+                ChannelId::Server(id) => id.get(),
+                ChannelId::Client(id) => id.get(),
+            },
+            kind: info.kind,
+            purpose: info.creator.into(),
+            send_priority: info.send_priority,
+        }
+    }
+}
+
+/// Serializable version of [`ChannelCreator`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum ChannelPurpose {
+    /// Reserved channel from [`ServerChannel`](crate::shared::backend::channels::ServerChannel)
+    /// or [`ClientChannel`](crate::shared::backend::channels::ClientChannel).
+    Replication,
+    /// Remote message.
+    Message {
+        /// Short type name.
+        type_name: String,
+    },
+    /// Remote event.
+    Event {
+        /// Short type name.
+        type_name: String,
+    },
+    /// Raw byte channel.
+    Raw {
+        /// Short name of the marker type.
+        type_name: String,
+    },
+}
+
+impl From<ChannelCreator> for ChannelPurpose {
+    fn from(creator: ChannelCreator) -> Self {
+        match creator {
+            ChannelCreator::Replication => Self::Replication,
+            ChannelCreator::Message(name) => Self::Message {
+                type_name: name.to_string(),
+            },
+            ChannelCreator::Event(name) => Self::Event {
+                type_name: name.to_string(),
+            },
+            ChannelCreator::Raw(name) => Self::Raw {
+                type_name: name.to_string(),
+            },
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use core::marker::PhantomData;
+
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    utils::{TypeIdMap, TypeIdMapExt},
+};
+use bytes::Bytes;
+use log::trace;
+
+use crate::{
+    prelude::*,
+    shared::backend::channels::{ChannelCreator, ClientChannelId, ServerChannelId},
+};
+
+/// An extension trait for [`App`] for registering raw byte channels.
+pub trait RawChannelAppExt {
+    /**
+    Registers a channel for raw bytes identified by the marker type `C`.
+
+    Intended for data that doesn't map to messages or components, such as streamed voice or
+    file uploads. The data is sent as is without serialization through the same backend
+    connection, so games don't need a second transport just for binary streams.
+
+    Creates a channel in each direction with the given delivery guarantee. Use
+    [`ClientRawChannel`] and [`ServerRawChannel`] to send and receive the data.
+    Like other non-replication channels, reliable channels are fragmented if
+    [`RepliconChannels::set_fragment_threshold`] is set.
+
+    Should be called in the same order on both the client and the server.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .add_raw_channel::<Voice>(Channel::Unreliable)
+        .add_systems(Update, send_voice.run_if(in_state(ClientState::Connected)));
+
+    fn send_voice(mut voice: ClientRawChannel<Voice>) {
+        let frame = vec![0; 160]; // Could be an encoded frame from a microphone.
+        if voice.send(frame).is_err() {
+            debug!("dropping voice frame due to backpressure");
+        }
+    }
+
+    struct Voice;
+    ```
+
+    # Panics
+
+    Panics if the channel is already registered.
+    */
+    fn add_raw_channel<C: 'static>(&mut self, channel: Channel) -> &mut Self;
+}
+
+impl RawChannelAppExt for App {
+    fn add_raw_channel<C: 'static>(&mut self, channel: Channel) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ProtocolHasher>()
+            .add_raw_channel::<C>();
+
+        let name = ShortName::of::<C>();
+        let mut channels = self.world_mut().resource_mut::<RepliconChannels>();
+        let server_id = channels.create_server_channel(channel, ChannelCreator::Raw(name));
+        let client_id = channels.create_client_channel(channel, ChannelCreator::Raw(name));
+
+        let mut raw_channels = self.world_mut().resource_mut::<RawChannels>();
+        let info = RawChannelInfo {
+            server_id,
+            client_id,
+            capacity: None,
+        };
+        if raw_channels.channels.insert_type::<C>(info).is_some() {
+            panic!("raw channel `{name}` can't be registered more than once");
+        }
+
+        self
+    }
+}
+
+/// Registered channels from [`RawChannelAppExt::add_raw_channel`].
+#[derive(Resource, Default)]
+pub struct RawChannels {
+    channels: TypeIdMap<RawChannelInfo>,
+}
+
+impl RawChannels {
+    /// Limits the number of bytes that can be pending on the channel `C` for each receiver.
+    ///
+    /// Pending bytes are bytes sent over the channel that the messaging backend hasn't
+    /// taken for transmission yet. Backends usually take them once per tick, so this acts
+    /// as a per-tick budget that prevents large streams from starving other traffic.
+    ///
+    /// When the limit is reached, [`ClientRawChannel::send`] and [`ServerRawChannel::send`]
+    /// return the data back, so it can be retried on the next tick.
+    ///
+    /// By default, the channel is unlimited.
+    pub fn set_capacity<C: 'static>(&mut self, capacity: Option<usize>) {
+        self.get_mut::<C>().capacity = capacity;
+    }
+
+    /// Returns the limit set by [`Self::set_capacity`].
+    pub fn capacity<C: 'static>(&self) -> Option<usize> {
+        self.get::<C>().capacity
+    }
+
+    /// Returns the ID of the server channel for `C`.
+    pub fn server_channel<C: 'static>(&self) -> ServerChannelId {
+        self.get::<C>().server_id
+    }
+
+    /// Returns the ID of the client channel for `C`.
+    pub fn client_channel<C: 'static>(&self) -> ClientChannelId {
+        self.get::<C>().client_id
+    }
+
+    fn get<C: 'static>(&self) -> &RawChannelInfo {
+        self.channels.get_type::<C>().unwrap_or_else(|| {
+            panic!(
+                "raw channel `{}` should be registered",
+                ShortName::of::<C>()
+            )
+        })
+    }
+
+    fn get_mut<C: 'static>(&mut self) -> &mut RawChannelInfo {
+        self.channels.get_type_mut::<C>().unwrap_or_else(|| {
+            panic!(
+                "raw channel `{}` should be registered",
+                ShortName::of::<C>()
+            )
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct RawChannelInfo {
+    server_id: ServerChannelId,
+    client_id: ClientChannelId,
+    capacity: Option<usize>,
+}
+
+impl RawChannelInfo {
+    /// Returns `true` if `len` bytes can be added to `pending` bytes.
+    fn fits(self, pending: usize, len: usize) -> bool {
+        self.capacity
+            .is_none_or(|capacity| pending == 0 || pending + len <= capacity)
+    }
+}
+
+/**
+Sends and receives raw bytes on the client over the channel registered for `C`.
+
+Received data stays in the buffer until [`Self::receive`] is called, so it
+should be drained every tick.
+
+See also [`RawChannelAppExt::add_raw_channel`].
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+fn play_voice(mut voice: ClientRawChannel<Voice>) {
+    for frame in voice.receive() {
+        info!("received {} bytes of voice", frame.len());
+    }
+}
+
+struct Voice;
+```
+*/
+#[derive(SystemParam)]
+pub struct ClientRawChannel<'w, C: Send + Sync + 'static> {
+    channels: Res<'w, RawChannels>,
+    messages: ResMut<'w, ClientMessages>,
+    marker: PhantomData<C>,
+}
+
+impl<C: Send + Sync + 'static> ClientRawChannel<'_, C> {
+    /// Sends bytes to the server.
+    ///
+    /// Returns the bytes back if they exceed the capacity from [`RawChannels::set_capacity`].
+    /// A single payload larger than the capacity is still accepted if nothing is pending.
+    pub fn send(&mut self, bytes: impl Into<Bytes>) -> Result<(), Bytes> {
+        let bytes = bytes.into();
+        let info = self.channels.get::<C>();
+        if !info.fits(self.pending_bytes(), bytes.len()) {
+            trace!(
+                "rejecting {} bytes for raw channel `{}` due to backpressure",
+                bytes.len(),
+                ShortName::of::<C>()
+            );
+            return Err(bytes);
+        }
+
+        self.messages.send(info.client_id, bytes);
+        Ok(())
+    }
+
+    /// Returns the number of bytes sent over the channel that weren't taken by the backend yet.
+    ///
+    /// Includes the overhead added by Replicon, such as fragment headers.
+    pub fn pending_bytes(&self) -> usize {
+        let channel_id = self.channels.get::<C>().client_id;
+        self.messages
+            .iter_sent()
+            .filter(|&(id, _)| id == channel_id)
+            .map(|(_, bytes)| bytes.len())
+            .sum()
+    }
+
+    /// Receives all available data from the server.
+    pub fn receive(&mut self) -> impl Iterator<Item = Bytes> + '_ {
+        let channel_id = self.channels.get::<C>().server_id;
+        self.messages.receive(channel_id)
+    }
+}
+
+/**
+Sends and receives raw bytes on the server over the channel registered for `C`.
+
+Data can be sent only to connected clients. Received data stays in the buffer until
+[`Self::receive`] is called, so it should be drained every tick.
+
+See also [`RawChannelAppExt::add_raw_channel`].
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+fn relay_voice(mut voice: ServerRawChannel<Voice>, clients: Query<Entity, With<AuthorizedClient>>) {
+    let frames: Vec<_> = voice.receive().collect();
+    for (sender, frame) in frames {
+        for client in clients.iter().filter(|&client| client != sender) {
+            let _ = voice.send(client, frame.clone());
+        }
+    }
+}
+
+struct Voice;
+```
+*/
+#[derive(SystemParam)]
+pub struct ServerRawChannel<'w, C: Send + Sync + 'static> {
+    channels: Res<'w, RawChannels>,
+    messages: ResMut<'w, ServerMessages>,
+    marker: PhantomData<C>,
+}
+
+impl<C: Send + Sync + 'static> ServerRawChannel<'_, C> {
+    /// Sends bytes to a client.
+    ///
+    /// Returns the bytes back if they exceed the capacity from [`RawChannels::set_capacity`]
+    /// for this client. A single payload larger than the capacity is still accepted if nothing
+    /// is pending.
+    pub fn send(&mut self, client: Entity, bytes: impl Into<Bytes>) -> Result<(), Bytes> {
+        let bytes = bytes.into();
+        let info = self.channels.get::<C>();
+        if !info.fits(self.pending_bytes(client), bytes.len()) {
+            trace!(
+                "rejecting {} bytes for `{client}` on raw channel `{}` due to backpressure",
+                bytes.len(),
+                ShortName::of::<C>()
+            );
+            return Err(bytes);
+        }
+
+        self.messages.send(client, info.server_id, bytes);
+        Ok(())
+    }
+
+    /// Returns the number of bytes sent to a client over the channel that weren't taken
+    /// by the backend yet.
+    ///
+    /// Includes the overhead added by Replicon, such as fragment headers.
+    pub fn pending_bytes(&self, client: Entity) -> usize {
+        let channel_id = self.channels.get::<C>().server_id;
+        self.messages
+            .iter_sent()
+            .filter(|&(entity, id, _)| entity == client && id == channel_id)
+            .map(|(.., bytes)| bytes.len())
+            .sum()
+    }
+
+    /// Receives all available data from clients.
+    pub fn receive(&mut self) -> impl Iterator<Item = (Entity, Bytes)> + '_ {
+        let channel_id = self.channels.get::<C>().client_id;
+        self.messages.receive(channel_id)
+    }
+}
@@ -1,13 +1,25 @@
+#[cfg(feature = "asset")]
+pub mod asset_handle;
 pub mod client_ticks;
+pub mod crdt;
 pub mod deferred_entity;
 pub mod diff;
+pub mod entity_pool;
+pub mod keyframe;
 pub mod message_flags;
-pub(crate) mod mutate_index;
+pub mod mutate_index;
+pub mod possession;
+pub mod presence_bitmap;
 pub mod receive_markers;
 pub mod registry;
 pub mod rules;
 pub mod signature;
+pub mod state;
 pub mod storage;
+pub mod template;
+pub mod timer;
+pub mod topic;
+pub mod upload;
 pub mod visibility;
 
 use bevy::prelude::*;
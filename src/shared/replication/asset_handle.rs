@@ -0,0 +1,215 @@
+use core::ops::Deref;
+
+use bevy::{asset::AssetPath, ecs::component::Mutable, prelude::*};
+use bytes::Bytes;
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{
+    deferred_entity::DeferredEntity,
+    registry::{
+        ctx::{SerializeCtx, WriteCtx},
+        receive_fns,
+        rule_fns::DeserializeFn,
+    },
+};
+use crate::{postcard_utils, prelude::*};
+
+/// Replication of components that store asset handles.
+pub trait AppAssetHandleExt {
+    /**
+    Replicates a component with a [`Handle`] by sending a stable key instead of the handle.
+
+    Handles are only valid within a single app, so the server converts each handle into `K`
+    using [`AssetKey::from_handle`], and the client resolves it back using [`AssetKey::to_handle`]
+    with its [`AssetServer`].
+
+    The component is inserted on the client right away. If the key triggers loading,
+    the handle acts as a placeholder until the asset is loaded, just like any handle returned by
+    [`AssetServer::load`]. Use [`AssetServer::is_loaded`] if you need to wait for it.
+
+    Requires [`AssetPlugin`] on both client and server.
+
+    # Examples
+
+    Replicate handles loaded from files using their paths:
+
+    ```
+    use bevy::{asset::AssetPath, prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, AssetPlugin::default(), RepliconPlugins));
+    app.init_asset::<Level>()
+        .replicate_asset_handle::<LevelHandle, AssetPath<'static>>();
+
+    #[derive(Component, Deref)]
+    struct LevelHandle(Handle<Level>);
+
+    impl From<Handle<Level>> for LevelHandle {
+        fn from(handle: Handle<Level>) -> Self {
+            Self(handle)
+        }
+    }
+
+    #[derive(Asset, TypePath)]
+    struct Level;
+    ```
+    **/
+    fn replicate_asset_handle<C, K>(&mut self) -> &mut Self
+    where
+        C: AssetHandleComponent,
+        K: AssetKey<C::Asset>;
+}
+
+impl AppAssetHandleExt for App {
+    fn replicate_asset_handle<C, K>(&mut self) -> &mut Self
+    where
+        C: AssetHandleComponent,
+        K: AssetKey<C::Asset>,
+    {
+        self.replicate_with(
+            RuleFns::new(serialize_handle::<C, K>, deserialize_handle::<C>)
+                .with_consume(consume_key::<C, K>),
+        )
+        .set_receive_fns::<C>(write_handle::<C, K>, receive_fns::default_remove::<C>)
+    }
+}
+
+/// A component that wraps a single [`Handle`].
+///
+/// Automatically implemented for all mutable components that dereference into a handle
+/// and can be created from it.
+///
+/// See [`AppAssetHandleExt::replicate_asset_handle`].
+pub trait AssetHandleComponent:
+    Component<Mutability = Mutable> + Deref<Target = Handle<Self::Asset>> + From<Handle<Self::Asset>>
+{
+    /// Asset type of the handle.
+    type Asset: Asset;
+}
+
+impl<C, A> AssetHandleComponent for C
+where
+    C: Component<Mutability = Mutable> + Deref<Target = Handle<A>> + From<Handle<A>>,
+    A: Asset,
+{
+    type Asset = A;
+}
+
+/// A stable representation of a [`Handle`] that can be sent over the network.
+///
+/// See [`AppAssetHandleExt::replicate_asset_handle`].
+///
+/// # Examples
+///
+/// Send only names of levels from a fixed folder:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct LevelName(String);
+///
+/// impl AssetKey<Level> for LevelName {
+///     fn from_handle(handle: &Handle<Level>) -> Option<Self> {
+///         let path = handle.path()?;
+///         let name = path.path().file_stem()?.to_str()?;
+///         Some(Self(name.to_string()))
+///     }
+///
+///     fn to_handle(&self, asset_server: &AssetServer) -> Handle<Level> {
+///         asset_server.load(format!("levels/{}.ron", self.0))
+///     }
+/// }
+///
+/// #[derive(Asset, TypePath)]
+/// struct Level;
+/// ```
+pub trait AssetKey<A: Asset>: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Returns a key for the handle on the server.
+    ///
+    /// Returns [`None`] if the handle can't be represented as a key,
+    /// which results in a serialization error for the replication system.
+    fn from_handle(handle: &Handle<A>) -> Option<Self>;
+
+    /// Returns a handle for the key on the client.
+    fn to_handle(&self, asset_server: &AssetServer) -> Handle<A>;
+}
+
+/// Uses the path from which the asset was loaded.
+///
+/// Handles for assets added at runtime without a path can't be replicated with this key.
+impl<A: Asset> AssetKey<A> for AssetPath<'static> {
+    fn from_handle(handle: &Handle<A>) -> Option<Self> {
+        handle.path().cloned()
+    }
+
+    fn to_handle(&self, asset_server: &AssetServer) -> Handle<A> {
+        asset_server.load(self.clone())
+    }
+}
+
+fn serialize_handle<C: AssetHandleComponent, K: AssetKey<C::Asset>>(
+    _ctx: &mut SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> Result<()> {
+    let key = K::from_handle(component).ok_or_else(|| {
+        format!(
+            "`{:?}` from `{}` can't be converted into `{}`",
+            component.id(),
+            ShortName::of::<C>(),
+            ShortName::of::<K>(),
+        )
+    })?;
+    postcard_utils::to_extend_mut(&key, message)?;
+
+    Ok(())
+}
+
+/// Resolving a key requires [`AssetServer`], which is accessed in [`write_handle`] instead.
+fn deserialize_handle<C: AssetHandleComponent>(
+    _ctx: &mut WriteCtx,
+    _message: &mut Bytes,
+) -> Result<C> {
+    Err(format!(
+        "`{}` can only be written by its asset handle write function",
+        ShortName::of::<C>()
+    )
+    .into())
+}
+
+fn consume_key<C: AssetHandleComponent, K: AssetKey<C::Asset>>(
+    _deserialize: DeserializeFn<C>,
+    _ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> Result<()> {
+    let _: K = postcard_utils::from_buf(message)?;
+    Ok(())
+}
+
+fn write_handle<C: AssetHandleComponent, K: AssetKey<C::Asset>>(
+    _ctx: &mut WriteCtx,
+    _rule_fns: &RuleFns<C>,
+    entity: &mut DeferredEntity,
+    message: &mut Bytes,
+) -> Result<()> {
+    let key: K = postcard_utils::from_buf(message)?;
+    let asset_server = entity
+        .world()
+        .get_resource::<AssetServer>()
+        .ok_or("`AssetServer` is required to resolve asset handles")?;
+    let handle = key.to_handle(asset_server);
+
+    if let Some(mut component) = entity.get_mut::<C>() {
+        if **component != handle {
+            *component = handle.into();
+        }
+    } else {
+        entity.insert(C::from(handle));
+    }
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
-use core::time::Duration;
+use alloc::collections::VecDeque;
+use core::{mem, time::Duration};
 
 use bevy::{
-    ecs::{change_detection::Tick, entity::hash_map::EntityHashMap},
-    platform::collections::HashMap,
+    ecs::{
+        archetype::ArchetypeId,
+        change_detection::Tick,
+        entity::{hash_map::EntityHashMap, hash_set::EntityHashSet},
+    },
+    platform::collections::{HashMap, hash_map::Entry},
     prelude::*,
 };
 use log::{debug, trace};
@@ -11,7 +16,10 @@ use smallvec::SmallVec;
 use super::mutate_index::MutateIndex;
 use crate::{
     prelude::*,
-    shared::replication::registry::{ComponentIndex, component_mask::ComponentMask},
+    shared::replication::{
+        registry::{ComponentIndex, component_mask::ComponentMask},
+        rules::component::InsertionBoost,
+    },
 };
 
 /// Alias for cursors associated with components.
@@ -21,12 +29,29 @@ use crate::{
 pub(crate) type DiffCursors = SmallVec<[(ComponentIndex, DiffIndex); 3]>;
 
 /// Tracks replication ticks for a client.
+///
+/// Present on authorized clients on the server.
 #[derive(Component, Default)]
-pub(crate) struct ClientTicks {
+pub struct ClientTicks {
     /// Last acknowledged tick for each visible entity with its components.
     ///
     /// Used to track what the client has already received.
-    pub(crate) entities: EntityHashMap<EntityTicks>,
+    pub(crate) entities: TrackedEntities,
+
+    /// Entities with [`ReplicateOnceThenForget`](crate::server::ReplicateOnceThenForget)
+    /// that were fully acknowledged and are no longer tracked in [`Self::entities`].
+    ///
+    /// Only used to skip them during replication and to send despawns. Without it, the server
+    /// would need to either re-send the entity as new or broadcast its despawn to all clients.
+    /// Stores only entity IDs and entries are removed on despawn or visibility loss, so it
+    /// doesn't grow beyond the number of forgotten entities that are alive.
+    pub(crate) forgotten: EntityHashSet,
+
+    /// Entities scheduled for [`Self::forgotten`] with the tick of the update message
+    /// whose acknowledgment is awaited.
+    ///
+    /// Sorted by tick.
+    pending_forget: VecDeque<(Entity, RepliconTick)>,
 
     /// The last tick in which a replicated entity had an insertion, removal, or gained/lost a component from the
     /// perspective of the client.
@@ -35,6 +60,12 @@ pub(crate) struct ClientTicks {
     /// message to arrive.
     pub(crate) update_tick: RepliconTick,
 
+    /// Tick of the last update message acknowledged by the client.
+    ///
+    /// Clients acknowledge only update messages that request it.
+    /// Used by [`DespawnAfterAck`](crate::server::despawn_after_ack::DespawnAfterAck).
+    pub(crate) acked_update_tick: Option<RepliconTick>,
+
     /// Mutate message indices mapped to their info.
     mutations: HashMap<MutateIndex, MutateInfo>,
 
@@ -42,9 +73,93 @@ pub(crate) struct ClientTicks {
     ///
     /// See also [`Self::register_mutate_message`].
     mutate_index: MutateIndex,
+
+    /// Whether components with [`ComponentRule::full_refresh`](crate::shared::replication::rules::component::ComponentRule::full_refresh)
+    /// should be re-sent as insertions on the next tick.
+    pub(crate) full_refresh: bool,
+
+    /// Time when the messaging backend had no pending messages for this client.
+    ///
+    /// Used by [`ClientOrder::LongestWaiting`](crate::server::client_order::ClientOrder::LongestWaiting).
+    pub(crate) served_at: Duration,
 }
 
 impl ClientTicks {
+    /// Returns the last tick in which a replicated entity had an insertion, removal, or gained/lost
+    /// a component from the perspective of the client.
+    ///
+    /// Messages that reference replicated entities should be applied on the client after this tick.
+    /// See [`TickStampedMessage`](crate::shared::message::server_message::tick_stamped_message::TickStampedMessage).
+    pub fn update_tick(&self) -> RepliconTick {
+        self.update_tick
+    }
+
+    /// Returns components of a visible entity that were replicated to the client.
+    ///
+    /// Returns [`None`] if the entity isn't replicated to the client.
+    /// See [`ComponentIndex`] for how to interpret the mask.
+    pub fn replicated_components(&self, entity: Entity) -> Option<&ComponentMask> {
+        self.entities.get(entity).map(|ticks| &ticks.components)
+    }
+
+    /// Records a write of a component on an entity from this client accepted at the given tick.
+    ///
+    /// Ignored if the entity isn't replicated to the client yet.
+    pub(crate) fn accept_write(
+        &mut self,
+        entity: Entity,
+        component: ComponentIndex,
+        tick: RepliconTick,
+    ) {
+        if let Some(entity_ticks) = self.entities.get_mut(entity) {
+            entity_ticks.accept_write(component, tick);
+        }
+    }
+
+    /// Forgets that the component on the entity was received by this client,
+    /// so it will be sent again as an insertion.
+    ///
+    /// Ignored if the entity isn't replicated to the client yet.
+    pub(crate) fn resend(&mut self, entity: Entity, component: ComponentIndex) {
+        if let Some(entity_ticks) = self.entities.get_mut(entity) {
+            entity_ticks.remove_component(component);
+        }
+    }
+
+    /// Marks update messages up to the given tick as acknowledged.
+    pub(crate) fn ack_update_message(&mut self, client: Entity, message_tick: RepliconTick) {
+        if self
+            .acked_update_tick
+            .is_some_and(|tick| !message_tick.is_newer(tick))
+        {
+            trace!("ignoring outdated update ack for `{message_tick:?}` from client `{client}`");
+            return;
+        }
+
+        trace!("acknowledging update message for `{message_tick:?}` from client `{client}`");
+        self.acked_update_tick = Some(message_tick);
+
+        while let Some(&(entity, tick)) = self.pending_forget.front() {
+            if tick.is_newer(message_tick) {
+                break;
+            }
+
+            self.pending_forget.pop_front();
+            // The entity could be despawned or hidden while waiting.
+            if self.entities.remove(entity).is_some() {
+                trace!("forgetting `{entity}` for client `{client}`");
+                self.forgotten.insert(entity);
+            }
+        }
+    }
+
+    /// Schedules forgetting the entity after the update message with the given tick is acknowledged.
+    ///
+    /// The message should request acknowledgment.
+    pub(crate) fn forget_after_ack(&mut self, entity: Entity, message_tick: RepliconTick) {
+        self.pending_forget.push_back((entity, message_tick));
+    }
+
     /// Allocates a new index for update message.
     ///
     /// The message later needs to be registered via [`Self::register_update_message`].
@@ -58,28 +173,38 @@ impl ClientTicks {
         self.mutations.insert(index, info);
     }
 
+    /// Returns the number of registered mutate messages that weren't acknowledged yet.
+    pub(crate) fn pending_mutations(&self) -> usize {
+        self.mutations.len()
+    }
+
     /// Marks mutate message as acknowledged by its index.
     ///
-    /// Returns associated entities and their component IDs.
+    /// Returns the server tick of the message or [`None`] if the index is unknown.
     ///
     /// Updates the tick and components of all entities from this mutation message if the tick is higher.
-    pub(crate) fn ack_mutate_message(&mut self, client: Entity, mutate_index: MutateIndex) {
+    pub(crate) fn ack_mutate_message(
+        &mut self,
+        client: Entity,
+        mutate_index: MutateIndex,
+    ) -> Option<RepliconTick> {
         let Some(mutate_info) = self.mutations.remove(&mutate_index) else {
             debug!("received unknown `{mutate_index:?}` from client `{client}`");
-            return;
+            return None;
         };
 
         for info in mutate_info.entities {
-            let Some(entity_ticks) = self.entities.get_mut(&info.entity) else {
+            let Some(entity_ticks) = self.entities.get_mut(info.entity) else {
                 // We ignore missing entities, since they were probably despawned.
                 continue;
             };
 
             // Received tick could be outdated because we bump it
             // if we detect any insertion on the entity in `collect_changes`.
-            if entity_ticks.server_tick.is_older(mutate_info.server_tick) {
-                entity_ticks.server_tick = mutate_info.server_tick;
-                entity_ticks.system_tick = mutate_info.system_tick;
+            let channel_ticks = entity_ticks.channel_mut(mutate_info.channel);
+            if channel_ticks.server_tick.is_older(mutate_info.server_tick) {
+                channel_ticks.server_tick = mutate_info.server_tick;
+                channel_ticks.system_tick = mutate_info.system_tick;
                 entity_ticks.components |= &info.components;
 
                 for (component, cursor) in info.diff_cursors {
@@ -90,38 +215,209 @@ impl ClientTicks {
             }
         }
         trace!(
-            "acknowledged mutate message with `{:?}` from client `{client}`",
-            mutate_info.server_tick,
+            "acknowledged mutate message with `{:?}` on `{:?}` from client `{client}`",
+            mutate_info.server_tick, mutate_info.channel,
         );
+
+        Some(mutate_info.server_tick)
     }
 
     /// Removes all mutate messages older then `min_timestamp`.
     ///
     /// Calls given function for each removed message.
-    pub(crate) fn cleanup_older_mutations(&mut self, min_timestamp: Duration) {
-        self.mutations
-            .retain(|_, mutate_info| mutate_info.timestamp >= min_timestamp);
+    pub(crate) fn cleanup_older_mutations(
+        &mut self,
+        min_timestamp: Duration,
+        mut f: impl FnMut(MutateIndex, RepliconTick),
+    ) {
+        self.mutations.retain(|&mutate_index, mutate_info| {
+            if mutate_info.timestamp >= min_timestamp {
+                return true;
+            }
+
+            (f)(mutate_index, mutate_info.server_tick);
+            false
+        });
+    }
+}
+
+/// Storage for [`EntityTicks`] optimized for lookups during replication.
+///
+/// Ticks are stored in slots indexed by a hash map. Additionally, slot indices are cached
+/// per archetype row, so iteration over archetypes in
+/// [`ServerSystems::Send`](crate::server::ServerSystems::Send) avoids hashing.
+/// The cache is validated by entity on each lookup, so entities that moved to a different
+/// row or archetype fall back to the hash map and update the cache.
+///
+/// Each slot remembers its cached row, so the row is cleared when the entity moves or is removed.
+/// This way the cache holds only tracked entities and shrinks with them.
+#[derive(Default)]
+pub(crate) struct TrackedEntities {
+    slots: Vec<Option<TrackedEntity>>,
+
+    /// Indices of unused slots from [`Self::slots`].
+    free: Vec<usize>,
+
+    indices: EntityHashMap<usize>,
+
+    /// Cached slot indices, indexed by archetype index and then by archetype row.
+    rows: Vec<Vec<Option<usize>>>,
+}
+
+impl TrackedEntities {
+    pub(crate) fn get(&self, entity: Entity) -> Option<&EntityTicks> {
+        let &slot = self.indices.get(&entity)?;
+        self.slots[slot].as_ref().map(|tracked| &tracked.ticks)
+    }
+
+    pub(crate) fn get_mut(&mut self, entity: Entity) -> Option<&mut EntityTicks> {
+        let &slot = self.indices.get(&entity)?;
+        self.slots[slot].as_mut().map(|tracked| &mut tracked.ticks)
+    }
+
+    /// Like [`Self::get_mut`], but uses the cache for the entity location.
+    ///
+    /// `row` is the index of the entity inside [`Archetype::entities`](bevy::ecs::archetype::Archetype::entities).
+    pub(crate) fn get_mut_at(
+        &mut self,
+        archetype_id: ArchetypeId,
+        row: usize,
+        entity: Entity,
+    ) -> Option<&mut EntityTicks> {
+        let slot = self.locate(archetype_id, row, entity)?;
+        self.slots[slot].as_mut().map(|tracked| &mut tracked.ticks)
+    }
+
+    pub(crate) fn contains_key(&self, entity: Entity) -> bool {
+        self.indices.contains_key(&entity)
+    }
+
+    /// Inserts ticks for an entity, replacing existing ones, and returns them.
+    pub(crate) fn insert(&mut self, entity: Entity, ticks: EntityTicks) -> &mut EntityTicks {
+        let slot = match self.indices.entry(entity) {
+            Entry::Occupied(entry) => {
+                // SAFETY: the slot is obtained from the map, so it's occupied.
+                let tracked = unsafe { self.slots[*entry.get()].as_mut().unwrap_unchecked() };
+                tracked.ticks = ticks;
+                return &mut tracked.ticks;
+            }
+            Entry::Vacant(entry) => {
+                let slot = self.free.pop().unwrap_or_else(|| {
+                    self.slots.push(None);
+                    self.slots.len() - 1
+                });
+                *entry.insert(slot)
+            }
+        };
+
+        let tracked = self.slots[slot].insert(TrackedEntity {
+            entity,
+            ticks,
+            row: None,
+        });
+        &mut tracked.ticks
+    }
+
+    pub(crate) fn remove(&mut self, entity: Entity) -> Option<EntityTicks> {
+        let slot = self.indices.remove(&entity)?;
+        self.uncache(slot);
+        self.free.push(slot);
+        self.slots[slot].take().map(|tracked| tracked.ticks)
+    }
+
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &Entity> {
+        self.indices.keys()
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut EntityTicks> {
+        self.slots
+            .iter_mut()
+            .flatten()
+            .map(|tracked| &mut tracked.ticks)
+    }
+
+    /// Returns the slot for the entity at the given location.
+    ///
+    /// Falls back to the hash map if the cache is outdated and updates it.
+    /// Locations of untracked entities aren't cached.
+    fn locate(&mut self, archetype_id: ArchetypeId, row: usize, entity: Entity) -> Option<usize> {
+        let index = archetype_id.index();
+        if let Some(&Some(slot)) = self.rows.get(index).and_then(|rows| rows.get(row))
+            && self.slots[slot]
+                .as_ref()
+                .is_some_and(|tracked| tracked.entity == entity)
+        {
+            return Some(slot);
+        }
+
+        let &slot = self.indices.get(&entity)?;
+        self.uncache(slot);
+
+        if self.rows.len() <= index {
+            self.rows.resize_with(index + 1, Vec::new);
+        }
+        let rows = &mut self.rows[index];
+        if rows.len() <= row {
+            rows.resize(row + 1, None);
+        }
+
+        // The row could be cached for an entity that moved away.
+        if let Some(previous) = rows[row].replace(slot)
+            && let Some(tracked) = &mut self.slots[previous]
+        {
+            tracked.row = None;
+        }
+
+        // SAFETY: the slot is obtained from the map, so it's occupied.
+        let tracked = unsafe { self.slots[slot].as_mut().unwrap_unchecked() };
+        tracked.row = Some((index, row));
+
+        Some(slot)
+    }
+
+    /// Clears the cached row of the slot and trims trailing empty rows.
+    fn uncache(&mut self, slot: usize) {
+        let Some((index, row)) = self.slots[slot]
+            .as_mut()
+            .and_then(|tracked| tracked.row.take())
+        else {
+            return;
+        };
+
+        let rows = &mut self.rows[index];
+        rows[row] = None;
+        while rows.last().is_some_and(Option::is_none) {
+            rows.pop();
+        }
+        while self.rows.last().is_some_and(Vec::is_empty) {
+            self.rows.pop();
+        }
     }
 }
 
+/// Slot of [`TrackedEntities`].
+struct TrackedEntity {
+    entity: Entity,
+    ticks: EntityTicks,
+
+    /// Archetype index and row under which the slot is cached.
+    row: Option<(usize, usize)>,
+}
+
 /// Acknowledgment information about an entity.
 pub(crate) struct EntityTicks {
-    /// The last server tick for which data for this entity was sent.
+    /// Acknowledged ticks for each [`MutationChannel`].
     ///
-    /// This tick serves as the reference point for determining whether components
-    /// on the entity have changed and need to be replicated. Component changes
-    /// older than this update tick are assumed to have been acknowledged by the client.
-    pub(crate) server_tick: RepliconTick,
-
-    /// The corresponding tick for change detection.
-    pub(crate) system_tick: Tick,
+    /// Channels are acknowledged independently, so components from rules on different channels
+    /// are checked for changes against different ticks.
+    channels: [ChannelTicks; MutationChannel::COUNT],
 
     /// The list of components that were replicated on this tick.
     pub(crate) components: ComponentMask,
 
     /// Last acknowledged diff cursor for components.
     ///
-    /// This is separate from [`Self::server_tick`]: the server tick controls change
+    /// This is separate from [`ChannelTicks::server_tick`]: the server tick controls change
     /// detection for the component as a whole, while the cursor controls the
     /// acknowledged base used to serialize only diffs that the client has not yet
     /// acknowledged.
@@ -131,6 +427,28 @@ pub(crate) struct EntityTicks {
     ///
     /// Cursors are pruned when the component is removed.
     diff_cursors: DiffCursors,
+
+    /// Server ticks at which a write from this client was accepted for components.
+    ///
+    /// See [`ComponentRule::echo_suppression`](crate::shared::replication::rules::component::ComponentRule::echo_suppression).
+    accepted_writes: SmallVec<[(ComponentIndex, RepliconTick); 1]>,
+
+    /// Server ticks at which components were inserted for this client.
+    ///
+    /// Recorded only for components with
+    /// [`ComponentRule::insertion_boost`](crate::shared::replication::rules::component::ComponentRule::insertion_boost).
+    insertions: SmallVec<[(ComponentIndex, RepliconTick); 1]>,
+
+    /// Server ticks at which components were last sent as mutations for this client.
+    ///
+    /// Recorded only for components with
+    /// [`ReplicationMode::Refresh`](crate::shared::replication::rules::component::ReplicationMode::Refresh).
+    refreshes: SmallVec<[(ComponentIndex, RepliconTick); 1]>,
+
+    /// Whether mutations for this entity exceeded the client's max size at least once.
+    ///
+    /// Used to warn only once per entity.
+    oversized: bool,
 }
 
 impl EntityTicks {
@@ -140,10 +458,45 @@ impl EntityTicks {
         components: ComponentMask,
     ) -> Self {
         Self {
-            server_tick,
-            system_tick,
+            channels: [ChannelTicks {
+                server_tick,
+                system_tick,
+            }; MutationChannel::COUNT],
             components,
             diff_cursors: Default::default(),
+            accepted_writes: Default::default(),
+            insertions: Default::default(),
+            refreshes: Default::default(),
+            oversized: false,
+        }
+    }
+
+    /// Marks mutations for this entity as exceeding the client's max size.
+    ///
+    /// Returns `true` if it happened for the first time.
+    pub(crate) fn mark_oversized(&mut self) -> bool {
+        !mem::replace(&mut self.oversized, true)
+    }
+
+    pub(crate) fn channel(&self, channel: MutationChannel) -> &ChannelTicks {
+        &self.channels[channel as usize]
+    }
+
+    pub(crate) fn channel_mut(&mut self, channel: MutationChannel) -> &mut ChannelTicks {
+        &mut self.channels[channel as usize]
+    }
+
+    pub(crate) fn channels_mut(&mut self) -> impl Iterator<Item = &mut ChannelTicks> {
+        self.channels.iter_mut()
+    }
+
+    /// Sets ticks for all channels.
+    ///
+    /// Used when the entity data is sent reliably, which acknowledges mutations from all channels.
+    pub(crate) fn set_all(&mut self, server_tick: RepliconTick, system_tick: Tick) {
+        for channel_ticks in &mut self.channels {
+            channel_ticks.server_tick = server_tick;
+            channel_ticks.system_tick = system_tick;
         }
     }
 
@@ -179,11 +532,106 @@ impl EntityTicks {
         {
             self.diff_cursors.remove(index);
         }
+        self.accepted_writes
+            .retain(|(index, _)| *index != component);
+        self.insertions.retain(|(index, _)| *index != component);
+        self.refreshes.retain(|(index, _)| *index != component);
+    }
+
+    /// Records a write from the client accepted at the given tick.
+    pub(crate) fn accept_write(&mut self, component: ComponentIndex, tick: RepliconTick) {
+        if let Some((_, existing)) = self
+            .accepted_writes
+            .iter_mut()
+            .find(|(index, _)| *index == component)
+        {
+            *existing = tick;
+        } else {
+            self.accepted_writes.push((component, tick));
+        }
+    }
+
+    /// Returns `true` if a write from the client was accepted for the component
+    /// within the last `window` ticks.
+    pub(crate) fn is_echo_suppressed(
+        &self,
+        component: ComponentIndex,
+        tick: RepliconTick,
+        window: u32,
+    ) -> bool {
+        self.accepted_writes
+            .iter()
+            .any(|&(index, accepted)| index == component && tick - accepted <= window)
+    }
+
+    /// Records an insertion of the component at the given tick.
+    pub(crate) fn insert_component(&mut self, component: ComponentIndex, tick: RepliconTick) {
+        if let Some((_, existing)) = self
+            .insertions
+            .iter_mut()
+            .find(|(index, _)| *index == component)
+        {
+            *existing = tick;
+        } else {
+            self.insertions.push((component, tick));
+        }
+    }
+
+    /// Records that the component was sent as a mutation at the given tick.
+    pub(crate) fn refresh_component(&mut self, component: ComponentIndex, tick: RepliconTick) {
+        if let Some((_, existing)) = self
+            .refreshes
+            .iter_mut()
+            .find(|(index, _)| *index == component)
+        {
+            *existing = tick;
+        } else {
+            self.refreshes.push((component, tick));
+        }
+    }
+
+    /// Returns the tick at which the component was last sent as a mutation.
+    ///
+    /// Returns [`None`] if it wasn't recorded.
+    pub(crate) fn last_refresh(&self, component: ComponentIndex) -> Option<RepliconTick> {
+        self.refreshes
+            .iter()
+            .find_map(|&(index, tick)| (index == component).then_some(tick))
+    }
+
+    /// Returns the priority multiplier for mutations of the component at the given tick.
+    ///
+    /// Returns 1.0 if the insertion wasn't recorded or the boost window is over.
+    pub(crate) fn insertion_boost(
+        &self,
+        component: ComponentIndex,
+        tick: RepliconTick,
+        boost: InsertionBoost,
+    ) -> f32 {
+        self.insertions
+            .iter()
+            .find(|&&(index, _)| index == component)
+            .map_or(1.0, |&(_, inserted)| boost.multiplier_at(tick - inserted))
     }
 }
 
+/// Acknowledgment ticks of an entity for a single [`MutationChannel`].
+#[derive(Clone, Copy)]
+pub(crate) struct ChannelTicks {
+    /// The last server tick for which data for this entity was sent.
+    ///
+    /// This tick serves as the reference point for determining whether components
+    /// on the entity have changed and need to be replicated. Component changes
+    /// older than this update tick are assumed to have been acknowledged by the client.
+    pub(crate) server_tick: RepliconTick,
+
+    /// The corresponding tick for change detection.
+    pub(crate) system_tick: Tick,
+}
+
 /// Information about a mutation message.
 pub(crate) struct MutateInfo {
+    pub(crate) channel: MutationChannel,
     pub(crate) server_tick: RepliconTick,
     pub(crate) system_tick: Tick,
     pub(crate) timestamp: Duration,
@@ -196,3 +644,62 @@ pub(crate) struct MutatedEntityInfo {
     pub(crate) components: ComponentMask,
     pub(crate) diff_cursors: DiffCursors,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_location() {
+        let mut entities = TrackedEntities::default();
+        let entity_a = Entity::from_raw_u32(1).unwrap();
+        let entity_b = Entity::from_raw_u32(2).unwrap();
+
+        entities.insert(entity_a, ticks(1));
+        assert_eq!(
+            server_tick(entities.get_mut_at(ArchetypeId::EMPTY, 0, entity_a)),
+            Some(1)
+        );
+
+        // Reuses the slot of the removed entity.
+        entities.remove(entity_a);
+        entities.insert(entity_b, ticks(2));
+        assert_eq!(
+            server_tick(entities.get_mut_at(ArchetypeId::EMPTY, 0, entity_a)),
+            None
+        );
+        assert_eq!(
+            server_tick(entities.get_mut_at(ArchetypeId::EMPTY, 0, entity_b)),
+            Some(2)
+        );
+
+        // Simulate a move to a different row.
+        assert_eq!(
+            server_tick(entities.get_mut_at(ArchetypeId::EMPTY, 1, entity_b)),
+            Some(2)
+        );
+        assert_eq!(
+            server_tick(entities.get_mut_at(ArchetypeId::EMPTY, 0, entity_a)),
+            None
+        );
+        assert_eq!(entities.rows[0], [None, Some(0)]);
+
+        entities.remove(entity_b);
+        assert!(
+            entities.rows.is_empty(),
+            "cache should shrink with removed entities"
+        );
+    }
+
+    fn ticks(server_tick: u32) -> EntityTicks {
+        EntityTicks::new(
+            RepliconTick::new(server_tick),
+            Tick::new(0),
+            Default::default(),
+        )
+    }
+
+    fn server_tick(ticks: Option<&mut EntityTicks>) -> Option<u32> {
+        ticks.map(|ticks| ticks.channel(MutationChannel::Primary).server_tick.get())
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::{ecs::component::Mutable, prelude::*};
+use serde::{Serialize, de::DeserializeOwned};
+
+/**
+Component whose received state is merged into the local value instead of overwriting it.
+
+Useful for sets and maps that are edited on both sides, like a shared whiteboard or
+a mirror of a guild roster. The client can apply its own edits locally and send them
+to the server using messages, while the server state is merged into them instead of
+discarding local changes.
+
+For this to converge, [`Self::merge`] should be commutative, associative and idempotent,
+like a union of grow-only sets or a per-key last-writer-wins map.
+
+Since replication messages can be lost or applied out of order, the server also periodically
+re-sends the full state every [`Self::REFRESH_INTERVAL`] ticks even if the component wasn't
+changed. See [`ReplicationMode::Refresh`](super::rules::component::ReplicationMode::Refresh) for details.
+
+Register with [`AppRuleExt::replicate_crdt`](super::rules::AppRuleExt::replicate_crdt).
+
+# Example
+
+```
+use std::collections::BTreeSet;
+
+# use bevy::state::app::StatesPlugin;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+app.replicate_crdt::<Strokes>();
+
+#[derive(Component, Serialize, Deserialize)]
+struct Strokes(BTreeSet<u32>);
+
+impl CrdtMerge for Strokes {
+    const REFRESH_INTERVAL: u32 = 120;
+
+    fn merge(&mut self, remote: Self) {
+        self.0.extend(remote.0);
+    }
+}
+```
+*/
+pub trait CrdtMerge:
+    Component<Mutability = Mutable> + Serialize + DeserializeOwned + Sized
+{
+    /// How often the server re-sends the full state in server ticks.
+    ///
+    /// Set to 0 to disable periodic re-sending.
+    const REFRESH_INTERVAL: u32 = 64;
+
+    /// Merges the received state into the local value.
+    ///
+    /// Called on the receiving side when the component is already present.
+    /// Entities inside `remote` are already mapped.
+    fn merge(&mut self, remote: Self);
+}
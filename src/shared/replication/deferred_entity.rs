@@ -36,6 +36,16 @@ impl<'w> DeferredEntity<'w> {
         }
     }
 
+    /// Like [`Self::new`], but keeps changes previously buffered in the scratch.
+    ///
+    /// Used to flush changes that were staged for the entity earlier.
+    pub(crate) fn resume(entity: EntityWorldMut<'w>, scratch: &'w mut EntityScratch) -> Self {
+        Self {
+            entity,
+            buffer: scratch.resume_buffer(),
+        }
+    }
+
     /// Like [`EntityWorldMut::insert`], but accepts only a single component insertion and buffers it.
     ///
     /// Calling this function multiple times for different components is equivalent to inserting a bundle with them.
@@ -157,6 +167,13 @@ impl EntityScratch {
         }
     }
 
+    fn resume_buffer<'a>(&'a mut self) -> EntityBuffer<'a> {
+        EntityBuffer {
+            removals: &mut self.removals,
+            insertions: self.insertions.writer(),
+        }
+    }
+
     /// Drops all components currently stored in the scratch space.
     ///
     /// # Safety
@@ -266,6 +283,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resuming() {
+        let mut world = World::new();
+        let mut scratch = EntityScratch::default();
+        let mut entity = DeferredEntity::new(world.spawn(Unit), &mut scratch);
+        let entity_id = entity.id();
+
+        entity.insert(Trivial(1)).remove::<Unit>();
+
+        let entity = DeferredEntity::resume(world.entity_mut(entity_id), &mut scratch);
+        assert!(!entity.contains::<Trivial>());
+        assert!(entity.contains::<Unit>());
+
+        entity.flush();
+
+        let entity = world.entity(entity_id);
+        assert_eq!(**entity.get::<Trivial>().unwrap(), 1);
+        assert!(!entity.contains::<Unit>());
+    }
+
     #[derive(Component)]
     struct Unit;
 
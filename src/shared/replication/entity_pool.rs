@@ -0,0 +1,259 @@
+//! Recycling of client entities for frequently spawned templates.
+//!
+//! See [`EntityPoolPlugin`] for details.
+
+#[cfg(feature = "client")]
+use alloc::vec::Vec;
+
+use bevy::prelude::*;
+#[cfg(feature = "client")]
+use bevy::{
+    ecs::{component::ComponentId, entity_disabling::Disabled},
+    platform::collections::HashMap,
+};
+#[cfg(feature = "client")]
+use bytes::{Buf, Bytes};
+#[cfg(feature = "client")]
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "client")]
+use smallvec::SmallVec;
+
+use crate::prelude::*;
+#[cfg(feature = "client")]
+use crate::{
+    client::confirm_history::ConfirmHistory,
+    postcard_utils,
+    shared::replication::registry::{self, FnsId, ReplicationRegistry},
+};
+
+/**
+Reuses despawned client entities with [`Poolable`] for new entities with the same [`Template`].
+
+When the server despawns a poolable entity, the client strips all replicated components,
+disables the entity with [`Disabled`](bevy::ecs::entity_disabling::Disabled) and stores it in
+[`EntityPool`] under its template. When the server spawns a new entity with the same template,
+the client takes the stored entity instead of spawning a new one. Client-only components,
+such as visuals inserted by observers, are kept, which reduces archetype churn and observer
+work for short-lived entities like projectiles.
+
+Only entities with both [`Poolable`] and [`Template`] are pooled. Entities with children are
+despawned as usual, since their hierarchy can't be reused. Pooled entities are despawned
+on disconnect.
+
+Not included in [`RepliconPlugins`] and needs to be added manually on both client and server
+after [`RepliconPlugins`]. Enables [`AppRuleExt::prefix_component_sizes`] to find the template
+without deserializing other components.
+
+# Examples
+
+```
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const BULLET: Template = Template(0);
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins, EntityPoolPlugin))
+    .replicate::<Speed>()
+    .register_template(BULLET, EntityTemplate::default().with(Speed(10.0)))
+    .add_observer(init_trail);
+
+fn spawn_bullet(mut commands: Commands) {
+    commands.spawn((Replicated, Poolable, BULLET));
+}
+
+fn init_trail(add: On<Add, Speed>, mut commands: Commands, trails: Query<(), With<Trail>>) {
+    // Reused entities already have the trail.
+    if !trails.contains(add.entity) {
+        commands.entity(add.entity).insert(Trail::default());
+    }
+}
+
+#[derive(Component, Deserialize, Serialize, Clone, PartialEq)]
+struct Speed(f32);
+
+/// Client-only visuals that are expensive to set up.
+#[derive(Component, Default)]
+struct Trail {
+    points: Vec<Vec3>,
+}
+```
+*/
+pub struct EntityPoolPlugin;
+
+impl Plugin for EntityPoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.replicate::<Poolable>().prefix_component_sizes();
+
+        #[cfg(feature = "client")]
+        {
+            let pool = EntityPool {
+                template_id: app.world_mut().register_component::<Template>(),
+                capacity: 256,
+                entities: Default::default(),
+            };
+            app.insert_resource(pool).add_systems(
+                OnExit(ClientState::Connected),
+                reset.in_set(ClientSystems::Reset),
+            );
+        }
+    }
+}
+
+/// Marks an entity that can be recycled on the client after despawn.
+///
+/// See [`EntityPoolPlugin`] for details.
+#[derive(Component, Serialize, Deserialize, Debug, Default, Clone, Copy)]
+#[component(immutable)]
+pub struct Poolable;
+
+/// Disabled entities stored by [`EntityPoolPlugin`] for reuse.
+#[cfg(feature = "client")]
+#[derive(Resource)]
+pub struct EntityPool {
+    /// ID of the [`Template`] component.
+    template_id: ComponentId,
+
+    /// Maximum number of stored entities for each template.
+    capacity: usize,
+
+    entities: HashMap<Template, Vec<Entity>>,
+}
+
+#[cfg(feature = "client")]
+impl EntityPool {
+    /// Limits the number of stored entities for each template.
+    ///
+    /// Poolable entities above the limit are despawned as usual.
+    ///
+    /// By default set to 256.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+    }
+
+    /// Returns the limit set by [`Self::set_capacity`].
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of stored entities for a template.
+    pub fn len(&self, template: Template) -> usize {
+        self.entities.get(&template).map_or(0, Vec::len)
+    }
+
+    /// Returns `true` if there are no stored entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.values().all(Vec::is_empty)
+    }
+
+    /// Takes a stored entity for the template of a new entity and enables it.
+    ///
+    /// The template is read from serialized components of the entity without deserializing
+    /// other components.
+    pub(crate) fn take(
+        world: &mut World,
+        registry: &ReplicationRegistry,
+        components: &Bytes,
+        data_size: usize,
+    ) -> Option<Entity> {
+        let pool = world.get_resource::<Self>()?;
+        if pool.is_empty() || data_size > components.len() {
+            return None;
+        }
+
+        let template = peek_template(registry, pool.template_id, components.slice(..data_size))?;
+        loop {
+            let entity = world
+                .resource_mut::<Self>()
+                .entities
+                .get_mut(&template)?
+                .pop()?;
+
+            // Could be despawned by the user.
+            if let Ok(mut entity) = world.get_entity_mut(entity) {
+                trace!("reusing `{}` for `{template:?}`", entity.id());
+                entity.remove::<Disabled>();
+                return Some(entity.id());
+            }
+        }
+    }
+
+    /// Strips replicated components from a despawned entity and stores it if it's poolable.
+    ///
+    /// Returns the entity back if it should be despawned.
+    pub(crate) fn release<'w>(
+        registry: &ReplicationRegistry,
+        mut entity: EntityWorldMut<'w>,
+    ) -> Result<(), EntityWorldMut<'w>> {
+        let Some(&template) = entity.get::<Template>() else {
+            return Err(entity);
+        };
+        let full = entity
+            .world()
+            .get_resource::<Self>()
+            .is_none_or(|pool| pool.len(template) >= pool.capacity);
+        if full || !entity.contains::<Poolable>() || entity.contains::<Children>() {
+            return Err(entity);
+        }
+
+        trace!("returning `{}` to the pool for `{template:?}`", entity.id());
+        let mut ids: SmallVec<[ComponentId; 16]> = registry
+            .component_ids()
+            .filter(|&id| entity.contains_id(id))
+            .collect();
+        ids.extend(entity.world().component_id::<Remote>());
+        ids.extend(entity.world().component_id::<ConfirmHistory>());
+        entity.remove_by_ids(&ids);
+
+        // Could be despawned by an observer.
+        if entity.is_despawned() {
+            return Ok(());
+        }
+
+        entity.insert(Disabled);
+        let id = entity.id();
+        entity.world_scope(|world| {
+            let mut pool = world.resource_mut::<Self>();
+            pool.entities.entry(template).or_default().push(id);
+        });
+
+        Ok(())
+    }
+}
+
+/// Finds the [`Template`] among serialized components.
+#[cfg(feature = "client")]
+fn peek_template(
+    registry: &ReplicationRegistry,
+    template_id: ComponentId,
+    mut components: Bytes,
+) -> Option<Template> {
+    while components.has_remaining() {
+        let fns_id: FnsId = postcard_utils::from_buf(&mut components).ok()?;
+        let component_size = registry::read_component_size(&mut components)?;
+        if component_size > components.remaining() {
+            return None;
+        }
+
+        let (_, component_id, _) = registry.try_get(fns_id)?;
+        if component_id == template_id {
+            return postcard_utils::from_buf(&mut components).ok();
+        }
+        components.advance(component_size);
+    }
+
+    None
+}
+
+#[cfg(feature = "client")]
+fn reset(mut commands: Commands, mut pool: ResMut<EntityPool>) {
+    let count: usize = pool.entities.values().map(Vec::len).sum();
+    debug!("despawning {count} pooled entities");
+    for (_, entities) in pool.entities.drain() {
+        for entity in entities {
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
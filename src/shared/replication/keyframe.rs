@@ -0,0 +1,374 @@
+use bevy::{ecs::component::Mutable, prelude::*};
+use log::trace;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::prelude::*;
+
+/**
+Component that is replicated as periodic full keyframes with deltas in between.
+
+Useful for continuously changing values, like animation parameters or audio occlusion,
+that are sent over the unreliable mutation channel. Every [`Self::KEYFRAME_INTERVAL`]-th send
+contains the full value, while sends in between contain only [`Self::Delta`] relative to the
+last keyframe. Deltas are computed once per tick and shared by all clients, so unlike
+[`Diffable`](super::diff::Diffable), the server doesn't track a baseline for each client.
+
+If a keyframe is lost, the client ignores deltas until it receives the next one.
+So the stored value may lag behind until the next keyframe, which is why this works best
+for values that change on most ticks. Clients that receive the component for the first time
+get both the current value and the last keyframe.
+
+The interval can be overridden per rule with [`AppRuleExt::with_keyframe_interval`](super::rules::AppRuleExt::with_keyframe_interval).
+
+Register with [`AppRuleExt::replicate_keyframed`](super::rules::AppRuleExt::replicate_keyframed).
+
+# Example
+
+```
+# use bevy::state::app::StatesPlugin;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+app.replicate_keyframed::<BlendWeight>();
+
+#[derive(Component, Serialize, Deserialize, Clone, Copy)]
+struct BlendWeight(f32);
+
+impl Keyframed for BlendWeight {
+    // Quantized difference from the keyframe.
+    type Delta = i16;
+
+    fn delta(&self, keyframe: &Self) -> Self::Delta {
+        ((self.0 - keyframe.0) * 1000.0) as i16
+    }
+
+    fn from_delta(keyframe: &Self, delta: Self::Delta) -> Self {
+        Self(keyframe.0 + delta as f32 / 1000.0)
+    }
+}
+```
+*/
+pub trait Keyframed:
+    Component<Mutability = Mutable> + Clone + Serialize + DeserializeOwned + Sized
+{
+    /// Difference between a value and a keyframe.
+    type Delta: Serialize + DeserializeOwned;
+
+    /// Default number of sends between keyframes.
+    ///
+    /// Set to 0 or 1 to send only keyframes.
+    const KEYFRAME_INTERVAL: u16 = 8;
+
+    /// Returns the difference between this value and the keyframe.
+    fn delta(&self, keyframe: &Self) -> Self::Delta;
+
+    /// Restores a value from the keyframe and the difference returned by [`Self::delta`].
+    fn from_delta(keyframe: &Self, delta: Self::Delta) -> Self;
+}
+
+/// Last keyframe sent by the server for a component.
+///
+/// Stored inside [`ReplicationStorage`].
+#[derive(Debug, Clone)]
+pub struct KeyframeHistory<C> {
+    index: u16,
+    keyframe: Option<C>,
+    sends: u16,
+    keyframe_tick: Option<RepliconTick>,
+    send_tick: Option<RepliconTick>,
+    insertion_tick: Option<RepliconTick>,
+}
+
+impl<C: Keyframed> KeyframeHistory<C> {
+    /// Registers a mutation send of the component.
+    ///
+    /// Starts a new keyframe on every `interval`-th send. Multiple sends
+    /// within the same tick count as one.
+    ///
+    /// If the component was already sent as an insertion in this tick,
+    /// the new keyframe is postponed to the next send, since clients that
+    /// received the insertion won't receive this keyframe.
+    pub(crate) fn send(&mut self, component: &C, tick: RepliconTick, interval: u16) {
+        if self.keyframe.is_none() {
+            self.set_keyframe(component, tick);
+        }
+        if self.send_tick == Some(tick) {
+            return;
+        }
+
+        if self.keyframe_tick != Some(tick)
+            && self.insertion_tick != Some(tick)
+            && self.sends >= interval
+        {
+            self.set_keyframe(component, tick);
+        }
+        self.sends += 1;
+        self.send_tick = Some(tick);
+    }
+
+    /// Registers an insertion send of the component.
+    ///
+    /// Insertions contain the full value, so they aren't counted as sends
+    /// and start a keyframe only if there is none yet.
+    pub(crate) fn insert(&mut self, component: &C, tick: RepliconTick) {
+        if self.keyframe.is_none() {
+            self.set_keyframe(component, tick);
+        }
+        self.insertion_tick = Some(tick);
+    }
+
+    fn set_keyframe(&mut self, component: &C, tick: RepliconTick) {
+        self.index = self.index.wrapping_add(1);
+        self.keyframe = Some(component.clone());
+        self.sends = 0;
+        self.keyframe_tick = Some(tick);
+        trace!("starting keyframe {} at `{tick:?}`", self.index);
+    }
+
+    /// Returns the delta for the component, or [`KeyframeDeltaRef::Keyframe`]
+    /// if the keyframe was started at the given tick.
+    ///
+    /// If `full` is set, the last keyframe will be included together with the full value.
+    pub(crate) fn delta<'a>(
+        &'a self,
+        component: &'a C,
+        tick: RepliconTick,
+        full: bool,
+    ) -> KeyframeDeltaRef<'a, C> {
+        let keyframe = self
+            .keyframe
+            .as_ref()
+            .expect("keyframe should be started before calculating deltas");
+
+        if self.keyframe_tick == Some(tick) {
+            KeyframeDeltaRef::Keyframe {
+                index: self.index,
+                component,
+            }
+        } else if full {
+            KeyframeDeltaRef::Initial {
+                index: self.index,
+                keyframe,
+                component,
+            }
+        } else {
+            KeyframeDeltaRef::Delta {
+                index: self.index,
+                delta: component.delta(keyframe),
+            }
+        }
+    }
+}
+
+impl<C> Default for KeyframeHistory<C> {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            keyframe: None,
+            sends: 0,
+            keyframe_tick: None,
+            send_tick: None,
+            insertion_tick: None,
+        }
+    }
+}
+
+/// Last keyframe received by the client for a component.
+///
+/// Stored inside [`ReplicationStorage`].
+#[derive(Debug, Clone)]
+pub struct ReceivedKeyframe<C> {
+    index: u16,
+    keyframe: Option<C>,
+}
+
+impl<C: Keyframed> ReceivedKeyframe<C> {
+    /// Stores the keyframe if it's newer than the current one.
+    pub(crate) fn set(&mut self, index: u16, keyframe: C) {
+        if self.keyframe.is_some() && !is_newer(index, self.index) {
+            trace!(
+                "ignoring keyframe {index} that is older than {}",
+                self.index
+            );
+            return;
+        }
+
+        self.index = index;
+        self.keyframe = Some(keyframe);
+    }
+
+    /// Restores a value from the delta if the keyframe with the given index was received.
+    pub(crate) fn apply(&self, index: u16, delta: C::Delta) -> Option<C> {
+        match &self.keyframe {
+            Some(keyframe) if self.index == index => Some(C::from_delta(keyframe, delta)),
+            _ => {
+                trace!("ignoring delta for missing keyframe {index}");
+                None
+            }
+        }
+    }
+}
+
+impl<C> Default for ReceivedKeyframe<C> {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            keyframe: None,
+        }
+    }
+}
+
+/// Tests if `index` is greater than `other` using wrapping semantics.
+fn is_newer(index: u16, other: u16) -> bool {
+    let distance = index.wrapping_sub(other);
+    distance != 0 && distance <= u16::MAX / 2
+}
+
+/// A deserializable keyframe or delta.
+///
+/// See also [`KeyframeDeltaRef`].
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "C: Keyframed"))]
+pub enum KeyframeDelta<C: Keyframed> {
+    Keyframe {
+        /// Index of the keyframe.
+        index: u16,
+        /// Component value that becomes the new keyframe.
+        component: C,
+    },
+    Delta {
+        /// Index of the keyframe the delta is relative to.
+        index: u16,
+        /// Difference from the keyframe.
+        delta: C::Delta,
+    },
+    Initial {
+        /// Index of the keyframe.
+        index: u16,
+        /// Last keyframe.
+        keyframe: C,
+        /// Current component value.
+        component: C,
+    },
+}
+
+/// A serializable keyframe or delta.
+///
+/// Separate from [`KeyframeDelta`] to avoid cloning.
+#[derive(Serialize)]
+pub enum KeyframeDeltaRef<'a, C: Keyframed> {
+    Keyframe {
+        /// Index of the keyframe.
+        index: u16,
+        /// Component value that becomes the new keyframe.
+        component: &'a C,
+    },
+    Delta {
+        /// Index of the keyframe the delta is relative to.
+        index: u16,
+        /// Difference from the keyframe.
+        delta: C::Delta,
+    },
+    Initial {
+        /// Index of the keyframe.
+        index: u16,
+        /// Last keyframe.
+        keyframe: &'a C,
+        /// Current component value.
+        component: &'a C,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyframes() {
+        let mut history = KeyframeHistory::<Value>::default();
+
+        for tick in 0..5 {
+            let tick = RepliconTick::new(tick);
+            history.send(&Value(tick.get() as u8), tick, 2);
+            history.send(&Value(tick.get() as u8), tick, 2);
+        }
+
+        assert_eq!(history.index, 3, "every second send should be a keyframe");
+        assert_eq!(history.keyframe, Some(Value(4)));
+        assert_eq!(history.sends, 1);
+    }
+
+    #[test]
+    fn insertions() {
+        let mut history = KeyframeHistory::<Value>::default();
+
+        let tick = RepliconTick::new(0);
+        history.insert(&Value(0), tick);
+        assert_eq!(history.index, 1, "first insertion should start a keyframe");
+
+        let tick = RepliconTick::new(1);
+        history.send(&Value(1), tick, 1);
+        assert_eq!(history.index, 1, "insertions shouldn't be counted");
+
+        let tick = RepliconTick::new(2);
+        history.insert(&Value(2), tick);
+        assert!(matches!(
+            history.delta(&Value(2), tick, true),
+            KeyframeDeltaRef::Initial { index: 1, .. }
+        ));
+
+        history.send(&Value(2), tick, 1);
+        assert_eq!(
+            history.index, 1,
+            "keyframe should be postponed after an insertion in the same tick"
+        );
+        assert!(matches!(
+            history.delta(&Value(2), tick, false),
+            KeyframeDeltaRef::Delta { index: 1, delta: 2 }
+        ));
+    }
+
+    #[test]
+    fn receiving() {
+        let mut received = ReceivedKeyframe::<Value>::default();
+        assert_eq!(received.apply(1, 2), None);
+
+        received.set(1, Value(1));
+        assert_eq!(received.apply(1, 2), Some(Value(3)));
+        assert_eq!(received.apply(2, 2), None);
+
+        received.set(0, Value(0));
+        assert_eq!(
+            received.apply(1, 2),
+            Some(Value(3)),
+            "older keyframes should be ignored"
+        );
+
+        let mut received = ReceivedKeyframe::<Value>::default();
+        received.set(u16::MAX, Value(0));
+        received.set(0, Value(1));
+        assert_eq!(
+            received.apply(0, 2),
+            Some(Value(3)),
+            "indices should wrap around"
+        );
+    }
+
+    #[derive(Component, Deserialize, Serialize, PartialEq, Debug, Clone, Copy)]
+    struct Value(u8);
+
+    impl Keyframed for Value {
+        type Delta = u8;
+
+        fn delta(&self, keyframe: &Self) -> Self::Delta {
+            self.0 - keyframe.0
+        }
+
+        fn from_delta(keyframe: &Self, delta: Self::Delta) -> Self {
+            Self(keyframe.0 + delta)
+        }
+    }
+}
@@ -12,16 +12,22 @@ bitflags! {
         const DESPAWNS = 0b00000100;
         const REMOVALS = 0b00001000;
         const CHANGES = 0b00010000;
+        /// The client should acknowledge the message. Doesn't have any data,
+        /// so it's never considered the last flag.
+        const ACK = 0b01000000;
     }
 }
 
 impl UpdateFlags {
-    /// Returns the last set flag in the message.
+    /// Returns the last set flag with data in the message.
+    ///
+    /// [`Self::ACK`] is ignored since it doesn't have any data.
     pub(crate) fn last(self) -> UpdateFlags {
-        if self.is_empty() {
+        let flags = self - Self::ACK;
+        if flags.is_empty() {
             Self::empty()
         } else {
-            let zeroes = u8::BITS - 1 - self.bits().leading_zeros();
+            let zeroes = u8::BITS - 1 - flags.bits().leading_zeros();
             UpdateFlags::from_bits_retain(1 << zeroes)
         }
     }
@@ -47,6 +53,11 @@ mod tests {
         assert_eq!(UpdateFlags::CHANGES.last(), UpdateFlags::CHANGES);
         assert_eq!(UpdateFlags::MAPPINGS.last(), UpdateFlags::MAPPINGS);
         assert_eq!(UpdateFlags::all().last(), UpdateFlags::CHANGES);
+        assert_eq!(UpdateFlags::ACK.last(), UpdateFlags::empty());
+        assert_eq!(
+            (UpdateFlags::MAPPINGS | UpdateFlags::ACK).last(),
+            UpdateFlags::MAPPINGS
+        );
         assert_eq!(
             (UpdateFlags::DESPAWNS | UpdateFlags::REMOVALS).last(),
             UpdateFlags::REMOVALS
@@ -5,14 +5,21 @@ use serde::{Deserialize, Serialize};
 ///
 /// Use for mutations acknowledgement.
 ///
+/// See also [`MutateMessageStatus`](crate::server::mutate_tracking::MutateMessageStatus).
+///
 /// Its serialization uses fixint encoding as serializing ticks as varints increases the average message size.
 /// A tick >= 2^14 will be [5 bytes](https://postcard.jamesmunns.com/wire-format.html#maximum-encoded-length)
 /// At 60 ticks/sec, that will happen after ~5 minutes. So any session over this time period would transmit
 /// more total bytes with varint encoding.
 #[derive(Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, MaxSize)]
-pub(crate) struct MutateIndex(#[serde(with = "postcard::fixint::le")] u16);
+pub struct MutateIndex(#[serde(with = "postcard::fixint::le")] u16);
 
 impl MutateIndex {
+    /// Returns the inner value.
+    pub fn get(self) -> u16 {
+        self.0
+    }
+
     /// Returns the current value and increments `self` by 1.
     ///
     /// Wraps on overflow.
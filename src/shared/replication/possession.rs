@@ -0,0 +1,275 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+#[cfg(feature = "server")]
+use crate::shared::backend::connected_client::{NetworkId, NetworkIdMap};
+
+/// Server-initiated possession of entities by clients for [`App`].
+pub trait AppPossessionExt {
+    /**
+    Replicates [`PossessedBy`] and notifies clients when they start or stop possessing entities.
+
+    Possession covers cases like character switching or entering and exiting vehicles.
+    The server inserts [`PossessedBy`] on an entity to mark it as possessed by a client and
+    removes it or inserts a different client to end the possession. The component is replicated
+    to all clients that see the entity, so they can resolve the possessing client with [`ClientRef::entity`].
+
+    On the possessing client, [`Possessed`] is inserted on the entity and [`PossessionStarted`] is triggered.
+    When the possession ends, [`Possessed`] is removed and [`PossessionEnded`] is triggered.
+    On disconnect, all possessions end on the client.
+
+    Notifications are sent with
+    [`SendAfterReplicationExt::send_after_replication`](crate::server::after_replication::SendAfterReplicationExt::send_after_replication),
+    so the entity is guaranteed to exist on the client by the time they arrive. If the client reconnects
+    with the same [`NetworkId`](crate::shared::backend::connected_client::NetworkId), for example, while
+    [`DespawnOnDisconnect`](crate::server::despawn_on_disconnect::DespawnOnDisconnect) waits for its grace period,
+    the possession is restored.
+
+    Requires a messaging backend that provides [`NetworkId`](crate::shared::backend::connected_client::NetworkId).
+    For the listen server, insert [`Possessed`] directly.
+
+    Needs to be called on both the client and server at the same point relative
+    to other messages, since it registers a server message.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::{prelude::*, shared::backend::connected_client::NetworkId};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate_possession()
+        .replicate::<Character>()
+        .add_observer(spawn_character)
+        .add_observer(follow_character);
+
+    // On the server.
+    fn spawn_character(
+        add: On<Add, AuthorizedClient>,
+        mut commands: Commands,
+        clients: Query<&NetworkId>,
+    ) {
+        if let Ok(&network_id) = clients.get(add.entity) {
+            commands.spawn((Replicated, Character, PossessedBy::new(network_id)));
+        }
+    }
+
+    // On the client.
+    fn follow_character(started: On<PossessionStarted>) {
+        info!("following `{}`", started.entity);
+    }
+
+    #[derive(Component, Serialize, Deserialize)]
+    struct Character;
+    ```
+    */
+    fn replicate_possession(&mut self) -> &mut Self;
+}
+
+impl AppPossessionExt for App {
+    fn replicate_possession(&mut self) -> &mut Self {
+        self.replicate::<PossessedBy>()
+            .add_mapped_server_message::<PossessionChanged>(Channel::Ordered);
+
+        #[cfg(feature = "server")]
+        self.add_observer(send_possession)
+            .add_observer(send_unpossession)
+            .add_observer(restore_possession);
+
+        #[cfg(feature = "client")]
+        self.add_systems(
+            PreUpdate,
+            apply_possession
+                .after(ClientSystems::Receive)
+                .run_if(in_state(ClientState::Connected)),
+        )
+        .add_systems(OnExit(ClientState::Connected), end_possession);
+
+        self
+    }
+}
+
+#[cfg(feature = "server")]
+fn send_possession(
+    insert: On<Insert, PossessedBy>,
+    mut commands: Commands,
+    state: Res<State<ServerState>>,
+    network_map: Res<NetworkIdMap>,
+    possessed: Query<&PossessedBy>,
+) {
+    if *state != ServerState::Running {
+        return;
+    }
+
+    let possessed_by = possessed.get(insert.entity).unwrap();
+    let Some(client) = possessed_by.entity(&network_map) else {
+        debug!(
+            "ignoring possession of `{}` by disconnected `{:?}`",
+            insert.entity, **possessed_by
+        );
+        return;
+    };
+
+    debug!("sending possession of `{}` to `{client}`", insert.entity);
+    commands.send_after_replication(
+        insert.entity,
+        ToClients {
+            targets: SendTargets::Single(client.into()),
+            message: PossessionChanged {
+                entity: insert.entity,
+                possessed: true,
+            },
+        },
+    );
+}
+
+#[cfg(feature = "server")]
+fn send_unpossession(
+    replace: On<Replace, PossessedBy>,
+    mut commands: Commands,
+    state: Res<State<ServerState>>,
+    network_map: Res<NetworkIdMap>,
+    possessed: Query<&PossessedBy>,
+) {
+    if *state != ServerState::Running {
+        return;
+    }
+
+    let possessed_by = possessed.get(replace.entity).unwrap();
+    let Some(client) = possessed_by.entity(&network_map) else {
+        return;
+    };
+
+    // Sent the same way as possession to preserve the order if it wasn't delivered yet.
+    debug!(
+        "sending end of possession of `{}` to `{client}`",
+        replace.entity
+    );
+    commands.send_after_replication(
+        replace.entity,
+        ToClients {
+            targets: SendTargets::Single(client.into()),
+            message: PossessionChanged {
+                entity: replace.entity,
+                possessed: false,
+            },
+        },
+    );
+}
+
+#[cfg(feature = "server")]
+fn restore_possession(
+    add: On<Add, AuthorizedClient>,
+    mut commands: Commands,
+    clients: Query<&NetworkId>,
+    possessed: Query<(Entity, &PossessedBy)>,
+) {
+    let Ok(&network_id) = clients.get(add.entity) else {
+        return;
+    };
+
+    for (entity, possessed_by) in &possessed {
+        if possessed_by.network_id() == Some(network_id) {
+            debug!("restoring possession of `{entity}` for `{}`", add.entity);
+            commands.send_after_replication(
+                entity,
+                ToClients {
+                    targets: SendTargets::Single(add.entity.into()),
+                    message: PossessionChanged {
+                        entity,
+                        possessed: true,
+                    },
+                },
+            );
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+fn apply_possession(mut commands: Commands, mut changes: MessageReader<PossessionChanged>) {
+    for change in changes.read() {
+        let Ok(mut entity) = commands.get_entity(change.entity) else {
+            debug!("ignoring possession change for missing `{}`", change.entity);
+            continue;
+        };
+
+        if change.possessed {
+            debug!("starting possession of `{}`", change.entity);
+            entity.insert(Possessed);
+            commands.trigger(PossessionStarted {
+                entity: change.entity,
+            });
+        } else {
+            debug!("ending possession of `{}`", change.entity);
+            entity.remove::<Possessed>();
+            commands.trigger(PossessionEnded {
+                entity: change.entity,
+            });
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+fn end_possession(mut commands: Commands, possessed: Query<Entity, With<Possessed>>) {
+    for entity in &possessed {
+        debug!("ending possession of `{entity}` due to disconnect");
+        commands.entity(entity).remove::<Possessed>();
+        commands.trigger(PossessionEnded { entity });
+    }
+}
+
+/// Client that possesses an entity.
+///
+/// Inserted by the server and replicated to clients.
+///
+/// See [`AppPossessionExt::replicate_possession`] for details.
+#[derive(Component, Deref, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[component(immutable)]
+pub struct PossessedBy(ClientRef);
+
+impl PossessedBy {
+    /// Creates a new instance for the given client.
+    pub fn new(client: impl Into<ClientRef>) -> Self {
+        Self(client.into())
+    }
+}
+
+/// Marker for entities possessed by this client.
+///
+/// Automatically inserted and removed on the client.
+///
+/// See [`AppPossessionExt::replicate_possession`] for details.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Possessed;
+
+/// Triggered on the client when it starts possessing an entity.
+///
+/// See [`AppPossessionExt::replicate_possession`] for details.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct PossessionStarted {
+    /// Possessed entity.
+    pub entity: Entity,
+}
+
+/// Triggered on the client when it stops possessing an entity.
+///
+/// Not triggered if the entity is despawned.
+///
+/// See [`AppPossessionExt::replicate_possession`] for details.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct PossessionEnded {
+    /// Previously possessed entity.
+    pub entity: Entity,
+}
+
+/// Notifies a client about the start or end of possession.
+#[derive(Message, Serialize, Deserialize, MapEntities, Clone, Copy)]
+struct PossessionChanged {
+    #[entities]
+    entity: Entity,
+    possessed: bool,
+}
@@ -0,0 +1,79 @@
+use bytes::Bytes;
+
+/**
+Derives [`PresenceBitmap`] for a struct.
+
+Fields with type `Option<T>` are detected by name, so type aliases for [`Option`]
+are serialized as regular fields.
+
+All fields need to implement [`Serialize`](serde::Serialize) and
+[`Deserialize`](serde::Deserialize) or contain such type inside [`Option`].
+
+# Examples
+
+```
+# use bevy::prelude::*;
+# use bevy_replicon::prelude::*;
+# use serde::{Deserialize, Serialize};
+#[derive(Component, PresenceBitmap)]
+struct Loadout {
+    primary: Option<Weapon>,
+    secondary: Option<Weapon>,
+    grenades: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Weapon(u16);
+```
+*/
+pub use bevy_replicon_macros::PresenceBitmap;
+
+/**
+Type that is serialized as a bitmap of present [`Option`] fields followed by their values.
+
+By default, [`postcard`] writes a tag byte for each [`Option`]. For structs with many fields that
+are mostly [`None`], this adds up. With this trait, presence is packed into bits and only present
+values are written. So a struct with 8 optional fields spends a single byte on their tags.
+
+For common cases, the trait can be derived, see [`derive@PresenceBitmap`].
+
+Register with [`AppRuleExt::replicate_bitmap`](super::rules::AppRuleExt::replicate_bitmap).
+
+# Examples
+
+```
+# use bevy::state::app::StatesPlugin;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+app.replicate_bitmap::<Equipment>();
+
+#[derive(Component, PresenceBitmap)]
+struct Equipment {
+    head: Option<Item>,
+    chest: Option<Item>,
+    legs: Option<Item>,
+    feet: Option<Item>,
+    hands: Option<Item>,
+    ring: Option<Item>,
+    amulet: Option<Item>,
+    durability: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Item {
+    id: u32,
+    level: u8,
+}
+```
+*/
+pub trait PresenceBitmap: Sized {
+    /// Serializes the value to an [`Extend`] writer.
+    fn to_extend_mut<W: Extend<u8>>(&self, writer: &mut W) -> postcard::Result<()>;
+
+    /// Deserializes the value written by [`Self::to_extend_mut`] from a buffer.
+    fn from_buf(buf: &mut Bytes) -> postcard::Result<Self>;
+}
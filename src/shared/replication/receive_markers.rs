@@ -174,6 +174,69 @@ pub trait AppMarkerExt {
         write: WriteFn<C>,
         remove: RemoveFn,
     ) -> &mut Self;
+
+    /**
+    Like [`Self::set_receive_fns`], but replaces only the removal function.
+
+    The currently assigned writing function stays unchanged.
+
+    Useful to intercept removals received from the server instead of removing
+    the component immediately.
+
+    # Examples
+
+    Keep `Health` for a few frames after the server removes it to play a death animation:
+
+    ```
+    # use bevy::state::app::StatesPlugin;
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        prelude::*,
+        shared::replication::{deferred_entity::DeferredEntity, registry::ctx::RemoveCtx},
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((StatesPlugin, RepliconPlugins));
+    app.replicate::<Health>()
+        .set_remove_fn::<Health>(start_dying)
+        .add_systems(Update, finish_dying);
+
+    /// Instead of removing `Health`, marks the entity as dying.
+    fn start_dying(_ctx: &mut RemoveCtx, entity: &mut DeferredEntity) {
+        entity.insert(Dying(30));
+    }
+
+    fn finish_dying(mut commands: Commands, mut dying: Query<(Entity, &mut Dying)>) {
+        for (entity, mut dying) in &mut dying {
+            **dying = dying.saturating_sub(1);
+            if **dying == 0 {
+                commands.entity(entity).remove::<(Health, Dying)>();
+            }
+        }
+    }
+
+    /// Number of frames left before the removal.
+    #[derive(Component, Deref, DerefMut)]
+    struct Dying(u32);
+
+    #[derive(Component, Serialize, Deserialize)]
+    struct Health(u32);
+    ```
+    */
+    fn set_remove_fn<C: Component<Mutability: MutWrite<C>>>(
+        &mut self,
+        remove: RemoveFn,
+    ) -> &mut Self;
+
+    /// Like [`Self::set_marker_fns`], but replaces only the removal function.
+    ///
+    /// If no functions were previously assigned for this marker and component,
+    /// the default writing function will be used for writes.
+    fn set_marker_remove_fn<M: Component, C: Component<Mutability: MutWrite<C>>>(
+        &mut self,
+        remove: RemoveFn,
+    ) -> &mut Self;
 }
 
 impl AppMarkerExt for App {
@@ -233,6 +296,39 @@ impl AppMarkerExt for App {
 
         self
     }
+
+    fn set_remove_fn<C: Component<Mutability: MutWrite<C>>>(
+        &mut self,
+        remove: RemoveFn,
+    ) -> &mut Self {
+        debug!("setting remove fn for component `{}`", ShortName::of::<C>());
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.set_remove_fn::<C>(world, remove);
+            });
+
+        self
+    }
+
+    fn set_marker_remove_fn<M: Component, C: Component<Mutability: MutWrite<C>>>(
+        &mut self,
+        remove: RemoveFn,
+    ) -> &mut Self {
+        debug!(
+            "setting remove fn for marker `{}` for component `{}`",
+            ShortName::of::<M>(),
+            ShortName::of::<C>()
+        );
+        let component_id = self.world_mut().register_component::<M>();
+        let receive_markers = self.world().resource::<ReceiveMarkers>();
+        let marker_id = receive_markers.marker_id(component_id);
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.set_marker_remove_fn::<C>(world, marker_id, remove);
+            });
+
+        self
+    }
 }
 
 /// Registered markers that override receive functions if present.
@@ -268,6 +364,11 @@ impl ReceiveMarkers {
         ReceiveMarkerIndex(index)
     }
 
+    /// Returns IDs of all marker components.
+    pub(crate) fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.0.iter().map(|marker| marker.component_id)
+    }
+
     pub(super) fn iter_require_history(&self) -> impl Iterator<Item = bool> + '_ {
         self.0.iter().map(|marker| marker.config.need_history)
     }
@@ -1,12 +1,17 @@
 pub mod component_fns;
-pub(crate) mod component_mask;
+pub mod component_mask;
 pub mod ctx;
 pub mod receive_fns;
+pub(crate) mod removal_id;
 pub mod rule_fns;
 pub(crate) mod serde_fns;
 pub mod test_fns;
 
+use core::mem;
+
 use bevy::{ecs::component::ComponentId, prelude::*};
+#[cfg(feature = "client")]
+use bytes::Buf;
 use log::trace;
 use serde::{Deserialize, Serialize};
 
@@ -42,6 +47,22 @@ pub struct ReplicationRegistry {
     ///
     /// Used to initialize new [`ComponentFns`] with the registered number of slots.
     marker_slots: usize,
+
+    /// Whether newly registered rule functions are optional.
+    ///
+    /// See [`AppRuleExt::replicate_optional`].
+    optional: bool,
+
+    /// Index of the first optional rule functions in [`Self::rules`].
+    ///
+    /// Required functions can't be registered after it, otherwise their IDs would
+    /// differ on clients without optional rules.
+    first_optional: Option<usize>,
+
+    /// Whether data of all components is prefixed with its size.
+    ///
+    /// See [`AppRuleExt::prefix_component_sizes`].
+    size_prefixes: bool,
 }
 
 impl ReplicationRegistry {
@@ -101,6 +122,45 @@ impl ReplicationRegistry {
         }
     }
 
+    /// Replaces only the removal function for a component when there are no markers.
+    ///
+    /// The writing function stays unchanged.
+    ///
+    /// See also [`Self::set_receive_fns`].
+    pub(super) fn set_remove_fn<C: Component<Mutability: MutWrite<C>>>(
+        &mut self,
+        world: &mut World,
+        remove: RemoveFn,
+    ) {
+        let (index, _) = self.init_component_fns::<C>(world);
+        let (_, component_fns) = &mut self.components[index.0];
+        component_fns.set_remove_fn(remove);
+    }
+
+    /// Replaces only the removal function associated with a marker for a component.
+    ///
+    /// **Must** be called **after** calling [`Self::register_marker`] with `marker_id`.
+    ///
+    /// See also [`Self::set_marker_fns`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the marker wasn't registered. Use [`Self::register_marker`] first.
+    pub(super) fn set_marker_remove_fn<C: Component<Mutability: MutWrite<C>>>(
+        &mut self,
+        world: &mut World,
+        marker_id: ReceiveMarkerIndex,
+        remove: RemoveFn,
+    ) {
+        let (index, _) = self.init_component_fns::<C>(world);
+        let (_, component_fns) = &mut self.components[index.0];
+
+        // SAFETY: `component_fns` was created for `C`.
+        unsafe {
+            component_fns.set_marker_remove_fn::<C>(marker_id, remove);
+        }
+    }
+
     /// Registers serialization/deserialization functions for a component.
     ///
     /// Returned data can be assigned to a
@@ -110,6 +170,15 @@ impl ReplicationRegistry {
         world: &mut World,
         rule_fns: RuleFns<C>,
     ) -> (ComponentId, FnsId) {
+        if self.optional {
+            self.first_optional.get_or_insert(self.rules.len());
+        } else if self.first_optional.is_some() {
+            panic!(
+                "`{}` should be registered before optional rules",
+                ShortName::of::<C>()
+            );
+        }
+
         let (index, component_id) = self.init_component_fns::<C>(world);
         self.rules.push((index, rule_fns.into()));
         let fns_id = FnsId(self.rules.len() - 1);
@@ -118,6 +187,28 @@ impl ReplicationRegistry {
         (component_id, fns_id)
     }
 
+    /// Marks rule functions registered after this call as optional or required.
+    pub(super) fn set_optional(&mut self, optional: bool) {
+        self.optional = optional;
+    }
+
+    /// Prefixes data of all components with its size.
+    ///
+    /// Returns `false` if prefixes were already enabled.
+    pub(super) fn enable_size_prefixes(&mut self) -> bool {
+        !mem::replace(&mut self.size_prefixes, true)
+    }
+
+    /// Returns `true` if component data serialized with the functions is prefixed with its size.
+    ///
+    /// Always `true` for optional and unknown functions, since clients may need to skip them.
+    /// For other functions, only if [`AppRuleExt::prefix_component_sizes`] was called.
+    ///
+    /// Required rules are the same on both sides, so the server and clients always agree on it.
+    pub(crate) fn is_size_prefixed(&self, fns_id: FnsId) -> bool {
+        self.size_prefixes || fns_id.0 >= self.first_optional.unwrap_or(self.rules.len())
+    }
+
     /// Initializes [`ComponentFns`] for a component and returns its index and ID.
     ///
     /// If a [`ComponentFns`] has already been created for this component,
@@ -144,10 +235,18 @@ impl ReplicationRegistry {
     ///
     /// See also [`Self::register_rule_fns`].
     pub(crate) fn get<'a>(&'a self, fns_id: FnsId) -> (ComponentIndex, ComponentId, SerdeFns<'a>) {
-        let (index, rule_fns) = self
-            .rules
-            .get(fns_id.0)
-            .unwrap_or_else(|| panic!("replication `{fns_id:?}` should be registered first"));
+        self.try_get(fns_id)
+            .unwrap_or_else(|| panic!("replication `{fns_id:?}` should be registered first"))
+    }
+
+    /// Like [`Self::get`], but returns [`None`] if the ID wasn't registered.
+    ///
+    /// Should be used for IDs received from the network.
+    pub(crate) fn try_get<'a>(
+        &'a self,
+        fns_id: FnsId,
+    ) -> Option<(ComponentIndex, ComponentId, SerdeFns<'a>)> {
+        let (index, rule_fns) = self.rules.get(fns_id.0)?;
 
         // SAFETY: index obtained from `rules` is always valid.
         let (component_id, component_fns) = unsafe { self.get_by_index(*index).unwrap_unchecked() };
@@ -155,7 +254,49 @@ impl ReplicationRegistry {
         // SAFETY: `RuleFns` and `ComponentFns` belong to the same type.
         let fns = unsafe { SerdeFns::new(component_fns, rule_fns) };
 
-        (*index, *component_id, fns)
+        Some((*index, *component_id, fns))
+    }
+
+    /// Returns IDs of components for all registered rule functions in the order of their [`FnsId`].
+    pub(crate) fn rule_components(&self) -> impl Iterator<Item = (FnsId, ComponentId)> + '_ {
+        self.rules
+            .iter()
+            .enumerate()
+            .map(|(index, &(component_index, _))| {
+                let (component_id, _) = self.components[component_index.0];
+                (FnsId(index), component_id)
+            })
+    }
+
+    /// Returns IDs of all replicated components.
+    pub(crate) fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.iter().map(|&(id, _)| id)
+    }
+
+    /// Returns the index of a replicated component.
+    pub fn component_index(&self, component_id: ComponentId) -> Option<ComponentIndex> {
+        self.components
+            .iter()
+            .position(|&(id, _)| id == component_id)
+            .map(ComponentIndex)
+    }
+
+    /// Like [`Self::component_index`], but looks up the component by its type.
+    pub fn component_index_of<C: Component>(&self, world: &World) -> Option<ComponentIndex> {
+        let component_id = world.component_id::<C>()?;
+        self.component_index(component_id)
+    }
+
+    /// Returns the index of the component for which the functions were registered.
+    pub fn fns_component_index(&self, fns_id: FnsId) -> Option<ComponentIndex> {
+        self.rules.get(fns_id.0).map(|&(index, _)| index)
+    }
+
+    /// Returns the ID of a replicated component by its index.
+    ///
+    /// Can be used to interpret a [`ComponentMask`](component_mask::ComponentMask).
+    pub fn component_id(&self, index: ComponentIndex) -> Option<ComponentId> {
+        self.components.get(index.0).map(|&(id, _)| id)
     }
 
     /// Returns component ID and its functions from the index.
@@ -174,10 +315,25 @@ impl Default for ReplicationRegistry {
             components: Default::default(),
             rules: Default::default(),
             marker_slots: 0,
+            optional: false,
+            first_optional: None,
+            size_prefixes: false,
         }
     }
 }
 
+/// Reads the size prefix of component data.
+///
+/// The size is written as a fixed-width integer, so the server can reserve it before
+/// serialization and fill it afterward.
+///
+/// See [`ReplicationRegistry::is_size_prefixed`].
+#[cfg(feature = "client")]
+pub(crate) fn read_component_size(message: &mut impl Buf) -> Option<usize> {
+    let size = message.try_get_u32_le().ok()?;
+    Some(size as usize)
+}
+
 /// ID of replication functions registered for a
 /// [`ComponentRule`](super::rules::component::ComponentRule).
 ///
@@ -186,8 +342,22 @@ impl Default for ReplicationRegistry {
 pub struct FnsId(usize);
 
 /// Index of a component inside [`ReplicationRegistry`].
-#[derive(Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Clone, Copy)]
-pub(crate) struct ComponentIndex(usize);
+///
+/// Unlike [`ComponentId`], indices are assigned in the order in which components
+/// are registered for replication. So they stay the same across runs and match between
+/// the client and server as long as their [`ProtocolHash`] matches. This makes them
+/// suitable for external tools that need to interpret a [`ComponentMask`](component_mask::ComponentMask).
+///
+/// Can be obtained from [`ReplicationRegistry::component_index`],
+/// [`ReplicationRegistry::component_index_of`] or [`ReplicationRegistry::fns_component_index`].
+#[derive(Deref, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Clone, Copy)]
+pub struct ComponentIndex(usize);
+
+impl From<ComponentIndex> for usize {
+    fn from(value: ComponentIndex) -> Self {
+        value.0
+    }
+}
 
 /// Signature of the entity despawn function in [`ReplicationRegistry::despawn`].
 pub type DespawnFn = fn(&DespawnCtx, EntityWorldMut);
@@ -236,6 +406,69 @@ mod tests {
         assert_eq!(registry.components.len(), 2);
     }
 
+    #[test]
+    fn component_indices() {
+        let mut world = World::new();
+        let mut registry = ReplicationRegistry::default();
+        let (component_b, fns_b) = registry.register_rule_fns(&mut world, RuleFns::<B>::default());
+        registry.register_rule_fns(&mut world, RuleFns::<A>::default());
+        let (_, fns_a) = registry.register_rule_fns(&mut world, RuleFns::<A>::default());
+
+        let index_a = registry.component_index_of::<A>(&world).unwrap();
+        let index_b = registry.component_index_of::<B>(&world).unwrap();
+        assert_eq!(*index_a, 1, "indices should follow registration order");
+        assert_eq!(*index_b, 0);
+        assert_eq!(registry.fns_component_index(fns_a), Some(index_a));
+        assert_eq!(registry.fns_component_index(fns_b), Some(index_b));
+        assert_eq!(registry.component_id(index_b), Some(component_b));
+        assert_eq!(registry.component_index(component_b), Some(index_b));
+    }
+
+    #[test]
+    fn optional_rule_fns() {
+        let mut world = World::new();
+        let mut registry = ReplicationRegistry::default();
+        registry.register_rule_fns(&mut world, RuleFns::<A>::default());
+        registry.set_optional(true);
+        let (_, fns_id) = registry.register_rule_fns(&mut world, RuleFns::<B>::default());
+        registry.set_optional(false);
+
+        assert_eq!(fns_id, FnsId(1));
+        assert_eq!(registry.first_optional, Some(1));
+    }
+
+    #[test]
+    fn size_prefixes() {
+        let mut world = World::new();
+        let mut registry = ReplicationRegistry::default();
+        let (_, required_id) = registry.register_rule_fns(&mut world, RuleFns::<A>::default());
+        registry.set_optional(true);
+        let (_, optional_id) = registry.register_rule_fns(&mut world, RuleFns::<B>::default());
+        registry.set_optional(false);
+
+        assert!(!registry.is_size_prefixed(required_id));
+        assert!(registry.is_size_prefixed(optional_id));
+        assert!(
+            registry.is_size_prefixed(FnsId(2)),
+            "unknown IDs should always be prefixed"
+        );
+
+        assert!(registry.enable_size_prefixes());
+        assert!(!registry.enable_size_prefixes());
+        assert!(registry.is_size_prefixed(required_id));
+    }
+
+    #[test]
+    #[should_panic]
+    fn required_after_optional() {
+        let mut world = World::new();
+        let mut registry = ReplicationRegistry::default();
+        registry.set_optional(true);
+        registry.register_rule_fns(&mut world, RuleFns::<A>::default());
+        registry.set_optional(false);
+        registry.register_rule_fns(&mut world, RuleFns::<B>::default());
+    }
+
     #[derive(Component, Serialize, Deserialize)]
     struct A;
 
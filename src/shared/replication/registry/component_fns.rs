@@ -3,7 +3,7 @@ use bytes::Bytes;
 
 use super::{
     ctx::{RemoveCtx, SerializeCtx, WriteCtx},
-    receive_fns::{MutWrite, UntypedReceiveFns},
+    receive_fns::{MutWrite, RemoveFn, UntypedReceiveFns},
     rule_fns::UntypedRuleFns,
 };
 use crate::shared::replication::{
@@ -77,6 +77,38 @@ impl ComponentFns {
         self.receive = receive_fns;
     }
 
+    /// Replaces only the removal function in the default functions.
+    ///
+    /// See also [`Self::set_receive_fns`].
+    pub(super) fn set_remove_fn(&mut self, remove: RemoveFn) {
+        self.receive.set_remove(remove);
+    }
+
+    /// Replaces only the removal function for a marker slot.
+    ///
+    /// If no functions were assigned to the slot, it will be initialized with the default functions for `C`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that this instance was created for `C`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such slot for the marker. Use [`Self::add_marker_slot`] to assign.
+    pub(super) unsafe fn set_marker_remove_fn<C: Component<Mutability: MutWrite<C>>>(
+        &mut self,
+        marker_id: ReceiveMarkerIndex,
+        remove: RemoveFn,
+    ) {
+        let fns = self
+            .markers
+            .get_mut(*marker_id)
+            .unwrap_or_else(|| panic!("receive fns should have a slot for {marker_id:?}"));
+
+        fns.get_or_insert_with(UntypedReceiveFns::default_fns::<C>)
+            .set_remove(remove);
+    }
+
     /// Restores erased type from `ptr` and `rule_fns` to the type for which this instance was created,
     /// then serializes it.
     ///
@@ -89,7 +121,7 @@ impl ComponentFns {
         rule_fns: &UntypedRuleFns,
         ptr: Ptr,
         message: &mut Vec<u8>,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         unsafe { (self.serialize)(ctx, rule_fns, ptr, message) }
     }
 
@@ -174,7 +206,7 @@ impl ComponentFns {
 
 /// Signature of component serialization functions that restore the original type.
 type UntypedSerializeFn =
-    unsafe fn(&mut SerializeCtx, &UntypedRuleFns, Ptr, &mut Vec<u8>) -> Result<()>;
+    unsafe fn(&mut SerializeCtx, &UntypedRuleFns, Ptr, &mut Vec<u8>) -> Result<bool>;
 
 /// Signature of component writing functions that restore the original type.
 type UntypedWriteFn = unsafe fn(
@@ -198,7 +230,7 @@ unsafe fn untyped_serialize<C: Component>(
     rule_fns: &UntypedRuleFns,
     ptr: Ptr,
     message: &mut Vec<u8>,
-) -> Result<()> {
+) -> Result<bool> {
     unsafe {
         let rule_fns = rule_fns.typed::<C>();
         rule_fns.serialize(ctx, ptr.deref::<C>(), message)
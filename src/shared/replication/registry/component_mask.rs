@@ -5,6 +5,9 @@ use smallbitvec::SmallBitVec;
 use super::ComponentIndex;
 
 /// Wraps a bitvec to provide a dynamically growing bitmask for compactly storing component IDs.
+///
+/// Each bit corresponds to a [`ComponentIndex`]. Use [`ReplicationRegistry`](super::ReplicationRegistry)
+/// to map indices to components.
 #[derive(Default, Debug, Clone)]
 pub struct ComponentMask {
     /// Each bit corresponds to a [`ComponentIndex`].
@@ -12,7 +15,8 @@ pub struct ComponentMask {
 }
 
 impl ComponentMask {
-    pub(crate) fn contains(&self, index: ComponentIndex) -> bool {
+    /// Returns `true` if the mask contains the component.
+    pub fn contains(&self, index: ComponentIndex) -> bool {
         self.bits.get(index.0).unwrap_or(false)
     }
 
@@ -27,11 +31,13 @@ impl ComponentMask {
         self.bits.set(index.0, false);
     }
 
-    pub(crate) fn is_empty(&self) -> bool {
-        self.bits.is_empty()
+    /// Returns `true` if the mask contains no components.
+    pub fn is_empty(&self) -> bool {
+        self.bits.all_false()
     }
 
-    pub(crate) fn iter(&self) -> impl Iterator<Item = ComponentIndex> {
+    /// Returns an iterator over contained components in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = ComponentIndex> {
         self.bits
             .iter()
             .enumerate()
@@ -81,6 +87,12 @@ mod tests {
 
         mask.remove(ComponentIndex(2));
         assert!(!mask.contains(ComponentIndex(2)));
+
+        assert!(mask.iter().eq([ComponentIndex(0), ComponentIndex(10)]));
+
+        mask.remove(ComponentIndex(0));
+        mask.remove(ComponentIndex(10));
+        assert!(mask.is_empty());
     }
 
     #[test]
@@ -24,6 +24,14 @@ pub struct SerializeCtx<'a> {
     /// Last diff index acknowledged by this client.
     pub diff_cursor: Option<DiffIndex>,
 
+    /// Number of sends between keyframes for the serializing component.
+    ///
+    /// See [`AppRuleExt::with_keyframe_interval`].
+    pub keyframe_interval: u16,
+
+    /// Whether the receiver doesn't have the component yet.
+    pub insertion: bool,
+
     /// Storage for serialization/deserialization state.
     pub storage: &'a mut ReplicationStorage,
 
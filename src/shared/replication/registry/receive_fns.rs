@@ -1,12 +1,18 @@
-use core::{any::TypeId, mem};
+use core::{any::TypeId, marker::PhantomData, mem};
 
 use bevy::{
-    ecs::component::{Immutable, Mutable},
+    ecs::{
+        change_detection::Tick,
+        component::{Immutable, Mutable},
+    },
     prelude::*,
 };
 use bytes::Bytes;
 
-use super::ctx::{RemoveCtx, WriteCtx};
+use super::{
+    ctx::{RemoveCtx, WriteCtx},
+    rule_fns::ConflictPolicy,
+};
 use crate::{prelude::*, shared::replication::deferred_entity::DeferredEntity};
 
 /// Writing and removal functions for component receiving.
@@ -60,6 +66,11 @@ impl UntypedReceiveFns {
         (write)(ctx, rule_fns, entity, message)
     }
 
+    /// Replaces the assigned removal function.
+    pub(super) fn set_remove(&mut self, remove: RemoveFn) {
+        self.remove = remove;
+    }
+
     /// Calls the assigned removal function.
     pub(super) fn remove(&self, ctx: &mut RemoveCtx, entity: &mut DeferredEntity) {
         (self.remove)(ctx, entity);
@@ -95,6 +106,8 @@ pub type RemoveFn = fn(&mut RemoveCtx, &mut DeferredEntity);
 /// If the component does not exist on the entity, it will be deserialized with [`RuleFns::deserialize`] and inserted.
 /// If the component exists on the entity, [`RuleFns::deserialize_in_place`] will be used directly on the entity's component.
 ///
+/// If the component was changed locally since the last write, [`RuleFns::conflict_policy`] will be used instead.
+///
 /// See also [`default_insert_write`].
 pub fn default_write<C: Component<Mutability = Mutable>>(
     ctx: &mut WriteCtx,
@@ -102,16 +115,61 @@ pub fn default_write<C: Component<Mutability = Mutable>>(
     entity: &mut DeferredEntity,
     message: &mut Bytes,
 ) -> Result<()> {
-    if let Some(mut component) = entity.get_mut::<C>() {
-        rule_fns.deserialize_in_place(ctx, &mut *component, message)?;
+    let conflict_policy = rule_fns.conflict_policy();
+    if matches!(conflict_policy, ConflictPolicy::Overwrite) {
+        if let Some(mut component) = entity.get_mut::<C>() {
+            rule_fns.deserialize_in_place(ctx, &mut *component, message)?;
+        } else {
+            let component: C = rule_fns.deserialize(ctx, message)?;
+            entity.insert(component);
+        }
+
+        return Ok(());
+    }
+
+    let this_run = entity.world().read_change_tick();
+    if let Some(ticks) = entity.get_change_ticks::<C>() {
+        let last_write = ctx.get_or_default::<LastWrite<C>>().tick;
+        let mut component = entity.get_mut::<C>().expect("component should be present");
+        if ticks.changed.is_newer_than(last_write, this_run) {
+            match conflict_policy {
+                ConflictPolicy::Overwrite => unreachable!("handled above"),
+                ConflictPolicy::KeepLocal => rule_fns.consume(ctx, message)?,
+                ConflictPolicy::Custom(merge) => {
+                    let received = rule_fns.deserialize(ctx, message)?;
+                    (merge)(&mut component, received, ctx);
+                }
+            }
+        } else {
+            rule_fns.deserialize_in_place(ctx, &mut *component, message)?;
+        }
     } else {
         let component: C = rule_fns.deserialize(ctx, message)?;
         entity.insert(component);
     }
 
+    ctx.get_or_default::<LastWrite<C>>().tick = this_run;
+
     Ok(())
 }
 
+/// Tick of the last write of `C` from the server.
+///
+/// Stored in [`ReplicationStorage`] only for components with a conflict policy.
+struct LastWrite<C> {
+    tick: Tick,
+    marker: PhantomData<C>,
+}
+
+impl<C> Default for LastWrite<C> {
+    fn default() -> Self {
+        Self {
+            tick: Tick::new(0),
+            marker: PhantomData,
+        }
+    }
+}
+
 /// Writes the component only if it is not equal to the current value.
 ///
 /// This is not done by default because it would require [`PartialEq`] for all replicated components,
@@ -0,0 +1,138 @@
+use core::fmt::{self, Formatter};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, SeqAccess, Visitor},
+};
+
+use super::FnsId;
+
+/// Removal of one or more components inside update messages.
+///
+/// Components of a rule get consecutive [`FnsId`]s on registration, so removal of all
+/// components from a rule is written as a single record. This reduces message size for common
+/// group removals and lets the client remove them as a single bundle.
+///
+/// Serialized as the functions ID shifted by 1 bit with the lowest bit indicating a bundle.
+/// For bundles, the number of components follows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RemovalId {
+    /// A single component.
+    Component(FnsId),
+    /// Multiple components with consecutive IDs starting from `first`.
+    Bundle { first: FnsId, len: usize },
+}
+
+impl RemovalId {
+    /// Creates a bundle removal if there are at least 2 IDs and they are consecutive.
+    pub(crate) fn bundle(mut fns_ids: impl Iterator<Item = FnsId>) -> Option<Self> {
+        let first = fns_ids.next()?;
+        let mut len = 1;
+        for fns_id in fns_ids {
+            if fns_id.0 != first.0 + len {
+                return None;
+            }
+            len += 1;
+        }
+
+        (len > 1).then_some(Self::Bundle { first, len })
+    }
+
+    /// Returns IDs of all removed components.
+    pub(crate) fn fns_ids(self) -> impl Iterator<Item = FnsId> {
+        let (first, len) = match self {
+            Self::Component(fns_id) => (fns_id, 1),
+            Self::Bundle { first, len } => (first, len),
+        };
+
+        (first.0..first.0.saturating_add(len)).map(FnsId)
+    }
+}
+
+impl Serialize for RemovalId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Self::Component(fns_id) => (fns_id.0 << 1).serialize(serializer),
+            Self::Bundle { first, len } => ((first.0 << 1) | 1, len).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RemovalId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(2, RemovalIdVisitor)
+    }
+}
+
+struct RemovalIdVisitor;
+
+impl<'de> Visitor<'de> for RemovalIdVisitor {
+    type Value = RemovalId;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("functions ID with optional bundle length")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let id: usize = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let fns_id = FnsId(id >> 1);
+        if (id & 1) == 0 {
+            return Ok(RemovalId::Component(fns_id));
+        }
+
+        let len = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(RemovalId::Bundle { first: fns_id, len })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::postcard_utils;
+
+    #[test]
+    fn bundle() {
+        assert_eq!(RemovalId::bundle([].into_iter()), None);
+        assert_eq!(RemovalId::bundle([FnsId(1)].into_iter()), None);
+        assert_eq!(RemovalId::bundle([FnsId(1), FnsId(3)].into_iter()), None);
+        assert_eq!(RemovalId::bundle([FnsId(2), FnsId(1)].into_iter()), None);
+        assert_eq!(
+            RemovalId::bundle([FnsId(1), FnsId(2), FnsId(3)].into_iter()),
+            Some(RemovalId::Bundle {
+                first: FnsId(1),
+                len: 3
+            })
+        );
+    }
+
+    #[test]
+    fn serialization() {
+        for removal_id in [
+            RemovalId::Component(FnsId(0)),
+            RemovalId::Component(FnsId(200)),
+            RemovalId::Bundle {
+                first: FnsId(0),
+                len: 2,
+            },
+            RemovalId::Bundle {
+                first: FnsId(200),
+                len: 5,
+            },
+        ] {
+            let mut bytes = Vec::new();
+            postcard_utils::to_extend_mut(&removal_id, &mut bytes).unwrap();
+            let mut buf = bytes.as_slice();
+            let deserialized: RemovalId = postcard_utils::from_buf(&mut buf).unwrap();
+            assert_eq!(removal_id, deserialized);
+            assert!(buf.is_empty());
+        }
+    }
+}
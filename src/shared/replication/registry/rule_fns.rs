@@ -8,7 +8,11 @@ use super::ctx::{SerializeCtx, WriteCtx};
 use crate::{
     postcard_utils,
     prelude::*,
-    shared::replication::diff::{ComponentDelta, ComponentDeltaRef, DiffBuffer, DiffHistory},
+    shared::replication::{
+        diff::{ComponentDelta, ComponentDeltaRef, DiffBuffer, DiffHistory},
+        keyframe::{KeyframeDelta, KeyframeHistory, ReceivedKeyframe},
+        presence_bitmap::PresenceBitmap,
+    },
 };
 
 /// Type-erased version of [`RuleFns`].
@@ -22,9 +26,18 @@ pub(crate) struct UntypedRuleFns {
     deserialize: unsafe fn(),
     deserialize_in_place: unsafe fn(),
     consume: unsafe fn(),
+    conflict_policy: UntypedConflictPolicy,
+    versioning: Option<(u16, unsafe fn())>,
+    outbound: Option<unsafe fn()>,
+    channel: MutationChannel,
 }
 
 impl UntypedRuleFns {
+    /// Returns the name of the component for which this instance was created.
+    pub(super) fn type_name(&self) -> &ShortName<'static> {
+        &self.type_name
+    }
+
     /// Restores the original [`RuleFns`] from which this type was created.
     ///
     /// # Safety
@@ -48,6 +61,21 @@ impl UntypedRuleFns {
                 mem::transmute::<unsafe fn(), DeserializeInPlaceFn<C>>(self.deserialize_in_place)
             },
             consume: unsafe { mem::transmute::<unsafe fn(), ConsumeFn<C>>(self.consume) },
+            conflict_policy: match self.conflict_policy {
+                UntypedConflictPolicy::Overwrite => ConflictPolicy::Overwrite,
+                UntypedConflictPolicy::KeepLocal => ConflictPolicy::KeepLocal,
+                UntypedConflictPolicy::Custom(merge) => ConflictPolicy::Custom(unsafe {
+                    mem::transmute::<unsafe fn(), MergeFn<C>>(merge)
+                }),
+            },
+            versioning: self.versioning.map(|(current, fallback)| Versioning {
+                current,
+                fallback: unsafe { mem::transmute::<unsafe fn(), FallbackFn<C>>(fallback) },
+            }),
+            outbound: self
+                .outbound
+                .map(|outbound| unsafe { mem::transmute::<unsafe fn(), OutboundFn<C>>(outbound) }),
+            channel: self.channel,
         }
     }
 }
@@ -66,10 +94,34 @@ impl<C: Component> From<RuleFns<C>> for UntypedRuleFns {
                 mem::transmute::<DeserializeInPlaceFn<C>, unsafe fn()>(value.deserialize_in_place)
             },
             consume: unsafe { mem::transmute::<ConsumeFn<C>, unsafe fn()>(value.consume) },
+            conflict_policy: match value.conflict_policy {
+                ConflictPolicy::Overwrite => UntypedConflictPolicy::Overwrite,
+                ConflictPolicy::KeepLocal => UntypedConflictPolicy::KeepLocal,
+                ConflictPolicy::Custom(merge) => UntypedConflictPolicy::Custom(unsafe {
+                    mem::transmute::<MergeFn<C>, unsafe fn()>(merge)
+                }),
+            },
+            versioning: value.versioning.map(|versioning| {
+                (versioning.current, unsafe {
+                    mem::transmute::<FallbackFn<C>, unsafe fn()>(versioning.fallback)
+                })
+            }),
+            outbound: value
+                .outbound
+                .map(|outbound| unsafe { mem::transmute::<OutboundFn<C>, unsafe fn()>(outbound) }),
+            channel: value.channel,
         }
     }
 }
 
+/// Type-erased version of [`ConflictPolicy`].
+#[derive(Clone, Copy)]
+enum UntypedConflictPolicy {
+    Overwrite,
+    KeepLocal,
+    Custom(unsafe fn()),
+}
+
 /// Serialization and deserialization functions for a component.
 ///
 /// See also [`AppRuleExt`]
@@ -79,6 +131,10 @@ pub struct RuleFns<C> {
     deserialize: DeserializeFn<C>,
     deserialize_in_place: DeserializeInPlaceFn<C>,
     consume: ConsumeFn<C>,
+    conflict_policy: ConflictPolicy<C>,
+    versioning: Option<Versioning<C>>,
+    outbound: Option<OutboundFn<C>>,
+    channel: MutationChannel,
 }
 
 impl<C: Component> RuleFns<C> {
@@ -91,6 +147,10 @@ impl<C: Component> RuleFns<C> {
             deserialize,
             deserialize_in_place: in_place_as_deserialize::<C>,
             consume: consume_as_deserialize,
+            conflict_policy: ConflictPolicy::Overwrite,
+            versioning: None,
+            outbound: None,
+            channel: MutationChannel::Primary,
         }
     }
 
@@ -133,20 +193,211 @@ impl<C: Component> RuleFns<C> {
         self
     }
 
+    /// Replaces the default [`ConflictPolicy::Overwrite`] with a custom policy.
+    ///
+    /// The policy is used on the client when a received value arrives for a component
+    /// that was changed locally since the last write from the server.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::{prelude::*, state::app::StatesPlugin};
+    /// use bevy_replicon::{
+    ///     prelude::*,
+    ///     shared::replication::registry::{ctx::WriteCtx, rule_fns::ConflictPolicy},
+    /// };
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins((StatesPlugin, RepliconPlugins));
+    /// app.replicate_with(
+    ///     RuleFns::<Health>::default().with_conflict_policy(ConflictPolicy::Custom(keep_lower)),
+    /// );
+    ///
+    /// /// Keeps predicted damage until the server confirms it.
+    /// fn keep_lower(health: &mut Health, received: Health, _ctx: &mut WriteCtx) {
+    ///     health.0 = health.0.min(received.0);
+    /// }
+    ///
+    /// #[derive(Component, Deserialize, Serialize)]
+    /// struct Health(u32);
+    /// ```
+    pub fn with_conflict_policy(mut self, conflict_policy: ConflictPolicy<C>) -> Self {
+        self.conflict_policy = conflict_policy;
+        self
+    }
+
+    /// Returns the assigned conflict policy.
+    pub fn conflict_policy(&self) -> ConflictPolicy<C> {
+        self.conflict_policy
+    }
+
+    /**
+    Prefixes serialized values with a layout version and decodes older versions with a fallback function.
+
+    Allows staged rollouts when the component layout changes: a peer with a newer layout can still
+    receive values from a peer with an older one. Bump the version each time the layout changes and
+    handle all previous versions in the fallback function. The current version is always deserialized
+    with the regular functions, while older versions are decoded as whole values with the fallback,
+    even for in-place updates.
+
+    Values with a version newer than the current one can't be decoded and produce an error,
+    so the receiving side should be updated first.
+
+    Peers still need the same [`ProtocolHash`], which doesn't depend on component layouts.
+    So avoid mixing values that change between such rollouts, like a game version,
+    via [`ProtocolHasher::add_custom`].
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::{
+        bytes::Bytes, postcard_utils, prelude::*, shared::replication::registry::ctx::WriteCtx,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((StatesPlugin, RepliconPlugins));
+    app.replicate_with(RuleFns::<Health>::default().versioned(1, decode_health));
+
+    fn decode_health(version: u16, message: &mut Bytes, _ctx: &mut WriteCtx) -> Result<Health> {
+        match version {
+            // Health used to be stored as `u8`.
+            0 => {
+                let health: u8 = postcard_utils::from_buf(message)?;
+                Ok(Health(health.into()))
+            }
+            _ => Err(format!("unsupported health version {version}").into()),
+        }
+    }
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+    ```
+    */
+    pub fn versioned(mut self, current_version: u16, fallback: FallbackFn<C>) -> Self {
+        self.versioning = Some(Versioning {
+            current: current_version,
+            fallback,
+        });
+        self
+    }
+
+    /**
+    Sends mutations of the component over the given channel.
+
+    By default, all mutations are sent over [`MutationChannel::Primary`]. Rules on
+    different channels are acknowledged independently, so rarely changing state
+    won't compete with frequently changing one. See [`MutationChannel`] for details.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((StatesPlugin, RepliconPlugins));
+    app.replicate::<Transform>().replicate_with(
+        RuleFns::<PlayerStatus>::default().on_channel(MutationChannel::Secondary),
+    );
+
+    #[derive(Component, Deserialize, Serialize)]
+    enum PlayerStatus {
+        Online,
+        Away,
+    }
+    ```
+    */
+    pub fn on_channel(mut self, channel: MutationChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Returns the assigned mutation channel.
+    pub fn channel(&self) -> MutationChannel {
+        self.channel
+    }
+
+    /**
+    Runs a function on the server right before each serialization of the component.
+
+    The function can return a modified value to send instead, or [`None`] to veto sending.
+    It's called once per serialization and the result is shared by all clients, so it can't
+    depend on the receiver. Useful for clamping or sanitizing values, redacting debug fields
+    in release builds, or converting units at the last moment. The value in the world stays unchanged.
+
+    A vetoed insertion or mutation isn't considered sent, so the function will be called again
+    on the next tick. Vetoed components are also skipped in snapshots and
+    [`HostSnapshot::capture`](crate::server::host_migration::HostSnapshot::capture).
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::{prelude::*, shared::replication::registry::ctx::SerializeCtx};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((StatesPlugin, RepliconPlugins));
+    app.replicate_with(RuleFns::<Health>::default().with_outbound(clamp_health));
+
+    fn clamp_health(health: &Health, _ctx: &SerializeCtx) -> Option<Health> {
+        Some(Health(health.0.min(Health::MAX)))
+    }
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+
+    impl Health {
+        const MAX: u32 = 100;
+    }
+    ```
+    */
+    pub fn with_outbound(mut self, outbound: OutboundFn<C>) -> Self {
+        self.outbound = Some(outbound);
+        self
+    }
+
     /// Serializes a component into a message.
+    ///
+    /// Returns `false` without writing anything if the function
+    /// from [`Self::with_outbound`] vetoed sending.
     pub(super) fn serialize(
         &self,
         ctx: &mut SerializeCtx,
         component: &C,
         message: &mut Vec<u8>,
-    ) -> Result<()> {
-        (self.serialize)(ctx, component, message)
+    ) -> Result<bool> {
+        let replaced;
+        let component = match self.outbound {
+            Some(outbound) => match (outbound)(component, ctx) {
+                Some(value) => {
+                    replaced = value;
+                    &replaced
+                }
+                None => return Ok(false),
+            },
+            None => component,
+        };
+
+        if let Some(versioning) = &self.versioning {
+            postcard_utils::to_extend_mut(&versioning.current, message)?;
+        }
+        (self.serialize)(ctx, component, message)?;
+
+        Ok(true)
     }
 
     /// Deserializes a component from a message.
     ///
     /// Use this function when inserting a new component.
     pub fn deserialize(&self, ctx: &mut WriteCtx, message: &mut Bytes) -> Result<C> {
+        if let Some((version, fallback)) = self.read_outdated(message)? {
+            return (fallback)(version, message, ctx);
+        }
         (self.deserialize)(ctx, message)
     }
 
@@ -159,13 +410,47 @@ impl<C: Component> RuleFns<C> {
         component: &mut C,
         message: &mut Bytes,
     ) -> Result<()> {
+        if let Some((version, fallback)) = self.read_outdated(message)? {
+            *component = (fallback)(version, message, ctx)?;
+            return Ok(());
+        }
         (self.deserialize_in_place)(self.deserialize, ctx, component, message)
     }
 
     /// Consumes a component from a message.
     pub(super) fn consume(&self, ctx: &mut WriteCtx, message: &mut Bytes) -> Result<()> {
+        if let Some((version, fallback)) = self.read_outdated(message)? {
+            ctx.ignore_mapping = true;
+            (fallback)(version, message, ctx)?;
+            ctx.ignore_mapping = false;
+            return Ok(());
+        }
         (self.consume)(self.deserialize, ctx, message)
     }
+
+    /// Reads the version prefix if versioning is enabled.
+    ///
+    /// Returns the version with the fallback function if the value
+    /// should be decoded with it instead of the regular functions.
+    fn read_outdated(&self, message: &mut Bytes) -> Result<Option<(u16, FallbackFn<C>)>> {
+        let Some(versioning) = &self.versioning else {
+            return Ok(None);
+        };
+
+        let version: u16 = postcard_utils::from_buf(message)?;
+        if version == versioning.current {
+            Ok(None)
+        } else if version < versioning.current {
+            Ok(Some((version, versioning.fallback)))
+        } else {
+            Err(format!(
+                "received `{}` with version {version}, but the latest supported is {}",
+                ShortName::of::<C>(),
+                versioning.current
+            )
+            .into())
+        }
+    }
 }
 
 impl<C: Diffable> RuleFns<C> {
@@ -176,6 +461,31 @@ impl<C: Diffable> RuleFns<C> {
     }
 }
 
+impl<C: Keyframed> RuleFns<C> {
+    /// Creates a new instance for keyframe-based replication.
+    ///
+    /// Should be combined with [`AppRuleExt::with_keyframe_interval`],
+    /// otherwise every send will be a keyframe.
+    pub fn new_keyframed() -> Self {
+        Self::new(serialize_keyframed::<C>, deserialize_keyframed::<C>)
+            .with_in_place(deserialize_keyframed_in_place)
+    }
+}
+
+impl<C: Component + PresenceBitmap> RuleFns<C> {
+    /// Creates a new instance that serializes the component using [`PresenceBitmap`].
+    pub fn new_bitmap() -> Self {
+        Self::new(serialize_bitmap::<C>, deserialize_bitmap::<C>)
+    }
+}
+
+impl<C: CrdtMerge> RuleFns<C> {
+    /// Creates a new instance that merges received values using [`CrdtMerge::merge`].
+    pub fn new_crdt() -> Self {
+        Self::default().with_in_place(merge_in_place)
+    }
+}
+
 impl<C: Component + Serialize + DeserializeOwned> Default for RuleFns<C> {
     /// Creates a new instance with default functions for a component.
     ///
@@ -185,6 +495,56 @@ impl<C: Component + Serialize + DeserializeOwned> Default for RuleFns<C> {
     }
 }
 
+/// Defines how to resolve a conflict between a received value and local changes on the client.
+///
+/// A conflict happens when a component was changed locally after the last write from the server.
+/// Applied by [`default_write`](super::receive_fns::default_write). Custom write functions
+/// can access it via [`RuleFns::conflict_policy`].
+///
+/// See also [`RuleFns::with_conflict_policy`].
+pub enum ConflictPolicy<C> {
+    /// Always apply received values, discarding local changes.
+    Overwrite,
+
+    /// Ignore the received value if the component was changed locally since the last write.
+    ///
+    /// The ignored value still counts as a write, so the next received value is applied
+    /// unless the component was changed locally again.
+    KeepLocal,
+
+    /// Merge the received value into the locally changed component.
+    Custom(MergeFn<C>),
+}
+
+impl<C> Clone for ConflictPolicy<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for ConflictPolicy<C> {}
+
+/// Version of the component layout with a decoder for older versions.
+///
+/// See [`RuleFns::versioned`].
+struct Versioning<C> {
+    current: u16,
+    fallback: FallbackFn<C>,
+}
+
+/// Signature of component merge functions for [`ConflictPolicy::Custom`].
+pub type MergeFn<C> = fn(&mut C, C, &mut WriteCtx);
+
+/// Signature of functions that decode older layouts of a component.
+///
+/// See [`RuleFns::versioned`].
+pub type FallbackFn<C> = fn(u16, &mut Bytes, &mut WriteCtx) -> Result<C>;
+
+/// Signature of functions that run before serialization.
+///
+/// See [`RuleFns::with_outbound`].
+pub type OutboundFn<C> = fn(&C, &SerializeCtx) -> Option<C>;
+
 /// Signature of component serialization functions.
 pub type SerializeFn<C> = fn(&mut SerializeCtx, &C, &mut Vec<u8>) -> Result<()>;
 
@@ -267,6 +627,26 @@ pub fn deserialize_as<C: Component + From<T>, T: DeserializeOwned>(
     Ok(component)
 }
 
+/// Serializes a component with [`PresenceBitmap::to_extend_mut`].
+pub fn serialize_bitmap<C: Component + PresenceBitmap>(
+    _ctx: &mut SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> Result<()> {
+    component.to_extend_mut(message)?;
+    Ok(())
+}
+
+/// Deserializes a component with [`PresenceBitmap::from_buf`].
+pub fn deserialize_bitmap<C: Component + PresenceBitmap>(
+    ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> Result<C> {
+    let mut component = C::from_buf(message)?;
+    C::map_entities(&mut component, ctx);
+    Ok(component)
+}
+
 /// Serializes a component diff.
 pub fn serialize_diff<C: Diffable>(
     ctx: &mut SerializeCtx,
@@ -346,3 +726,111 @@ pub fn deserialize_diff_in_place<C: Diffable>(
     }
     Ok(())
 }
+
+/// Serializes a component as a keyframe or a delta relative to the last keyframe.
+///
+/// Clients that don't have the component yet receive the full value.
+pub fn serialize_keyframed<C: Keyframed>(
+    ctx: &mut SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> Result<()> {
+    let server_tick = ctx.server_tick;
+    let insertion = ctx.insertion;
+    let interval = ctx.keyframe_interval.max(1);
+    let history = ctx.get_or_default::<KeyframeHistory<C>>();
+
+    if insertion {
+        history.insert(component, server_tick);
+    } else {
+        history.send(component, server_tick, interval);
+    }
+    let delta = history.delta(component, server_tick, insertion);
+    postcard_utils::to_extend_mut(&delta, message)?;
+
+    Ok(())
+}
+
+/// Deserializes a keyframed component.
+///
+/// Deltas can be applied only if the keyframe they're based on was received.
+pub fn deserialize_keyframed<C: Keyframed>(ctx: &mut WriteCtx, message: &mut Bytes) -> Result<C> {
+    let mut component = match postcard_utils::from_buf(message)? {
+        KeyframeDelta::<C>::Keyframe { index, component } => {
+            let received = ctx.get_or_default::<ReceivedKeyframe<C>>();
+            received.set(index, component.clone());
+            component
+        }
+        KeyframeDelta::<C>::Initial {
+            index,
+            keyframe,
+            component,
+        } => {
+            let received = ctx.get_or_default::<ReceivedKeyframe<C>>();
+            received.set(index, keyframe);
+            component
+        }
+        KeyframeDelta::<C>::Delta { index, delta } => {
+            let received = ctx.get_or_default::<ReceivedKeyframe<C>>();
+            received.apply(index, delta).ok_or_else(|| {
+                format!(
+                    "cannot insert `{}` from a delta without keyframe {index}",
+                    ShortName::of::<C>()
+                )
+            })?
+        }
+    };
+
+    C::map_entities(&mut component, ctx);
+    Ok(component)
+}
+
+/// Deserializes a keyframed component and applies it to the passed component.
+///
+/// Deltas based on a missing keyframe are ignored until the next keyframe arrives.
+pub fn deserialize_keyframed_in_place<C: Keyframed>(
+    _deserialize: DeserializeFn<C>,
+    ctx: &mut WriteCtx,
+    component: &mut C,
+    message: &mut Bytes,
+) -> Result<()> {
+    let new_component = match postcard_utils::from_buf(message)? {
+        KeyframeDelta::<C>::Keyframe { index, component } => {
+            let received = ctx.get_or_default::<ReceivedKeyframe<C>>();
+            received.set(index, component.clone());
+            Some(component)
+        }
+        KeyframeDelta::<C>::Initial {
+            index,
+            keyframe,
+            component,
+        } => {
+            let received = ctx.get_or_default::<ReceivedKeyframe<C>>();
+            received.set(index, keyframe);
+            Some(component)
+        }
+        KeyframeDelta::<C>::Delta { index, delta } => {
+            let received = ctx.get_or_default::<ReceivedKeyframe<C>>();
+            received.apply(index, delta)
+        }
+    };
+
+    if let Some(new_component) = new_component {
+        *component = new_component;
+        C::map_entities(component, ctx);
+    }
+
+    Ok(())
+}
+
+/// Deserializes a component and merges it into the passed component using [`CrdtMerge::merge`].
+pub fn merge_in_place<C: CrdtMerge>(
+    deserialize: DeserializeFn<C>,
+    ctx: &mut WriteCtx,
+    component: &mut C,
+    message: &mut Bytes,
+) -> Result<()> {
+    let remote = (deserialize)(ctx, message)?;
+    component.merge(remote);
+    Ok(())
+}
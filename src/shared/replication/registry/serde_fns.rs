@@ -1,5 +1,14 @@
+#[cfg(feature = "catch_panics")]
+use alloc::boxed::Box;
+use alloc::string::String;
+#[cfg(feature = "catch_panics")]
+use core::{any::Any, panic::AssertUnwindSafe};
+#[cfg(feature = "catch_panics")]
+use std::panic::catch_unwind;
+
 use bevy::{prelude::*, ptr::Ptr};
 use bytes::Bytes;
+use log::error;
 
 use super::ctx::{RemoveCtx, SerializeCtx, WriteCtx};
 use crate::shared::replication::{
@@ -17,6 +26,10 @@ use crate::shared::replication::{
 /// However, always working with two function structs is verbose and potentially unsafe
 /// if they correspond to different underlying types. This struct reduces boilerplate and
 /// improves safety by encapsulating the unsafety within its creation.
+///
+/// With the `catch_panics` feature, panics inside user-provided functions are also caught,
+/// so a single faulty component can't bring down the app. See the methods for how each
+/// panic is handled.
 pub(crate) struct SerdeFns<'a> {
     component_fns: &'a ComponentFns,
     rule_fns: &'a UntypedRuleFns,
@@ -37,9 +50,14 @@ impl<'a> SerdeFns<'a> {
             rule_fns,
         }
     }
+
     /// Restores the erased type from `ptr` to the type for which this instance was created,
     /// and serializes it.
     ///
+    /// Returns `false` without writing anything if sending was vetoed by
+    /// [`RuleFns::with_outbound`](super::rule_fns::RuleFns::with_outbound).
+    /// Caught panics are logged and treated the same way, so the component is skipped.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that `ptr` was created for the same type as this instance.
@@ -48,15 +66,29 @@ impl<'a> SerdeFns<'a> {
         ctx: &mut SerializeCtx,
         ptr: Ptr,
         message: &mut Vec<u8>,
-    ) -> Result<()> {
-        // SAFETY: `RuleFns`, `ComponentFns` and `ptr` belong to the same type.
-        unsafe {
-            self.component_fns
-                .serialize(ctx, self.rule_fns, ptr, message)
-        }
+    ) -> Result<bool> {
+        let entity = ctx.entity;
+        let len = message.len();
+        catch_panic(|| {
+            // SAFETY: `RuleFns`, `ComponentFns` and `ptr` belong to the same type.
+            unsafe {
+                self.component_fns
+                    .serialize(ctx, self.rule_fns, ptr, message)
+            }
+        })
+        .unwrap_or_else(|panic| {
+            error!(
+                "skipping `{}` for `{entity}` because its serialization panicked: {panic}",
+                self.rule_fns.type_name(),
+            );
+            message.truncate(len);
+            Ok(false)
+        })
     }
 
     /// Calls the assigned writing function based on entity markers.
+    ///
+    /// Caught panics are returned as errors.
     pub(crate) fn write(
         &self,
         ctx: &mut WriteCtx,
@@ -64,14 +96,20 @@ impl<'a> SerdeFns<'a> {
         entity: &mut DeferredEntity,
         message: &mut Bytes,
     ) -> Result<()> {
-        // SAFETY: `RuleFns` and `ComponentFns` belong to the same type.
-        unsafe {
-            self.component_fns
-                .write(ctx, self.rule_fns, entity_markers, entity, message)
-        }
+        let entity_id = ctx.entity;
+        catch_panic(|| {
+            // SAFETY: `RuleFns` and `ComponentFns` belong to the same type.
+            unsafe {
+                self.component_fns
+                    .write(ctx, self.rule_fns, entity_markers, entity, message)
+            }
+        })
+        .unwrap_or_else(|panic| Err(self.panic_error(entity_id, panic)))
     }
 
     /// Calls the assigned writing or consuming function based on entity markers.
+    ///
+    /// Caught panics are returned as errors.
     pub(crate) fn consume_or_write(
         &self,
         ctx: &mut WriteCtx,
@@ -80,26 +118,71 @@ impl<'a> SerdeFns<'a> {
         entity: &mut DeferredEntity,
         message: &mut Bytes,
     ) -> Result<()> {
-        // SAFETY: `RuleFns` and `ComponentFns` belong to the same type.
-        unsafe {
-            self.component_fns.consume_or_write(
-                ctx,
-                self.rule_fns,
-                entity_markers,
-                receive_markers,
-                entity,
-                message,
-            )
-        }
+        let entity_id = ctx.entity;
+        catch_panic(|| {
+            // SAFETY: `RuleFns` and `ComponentFns` belong to the same type.
+            unsafe {
+                self.component_fns.consume_or_write(
+                    ctx,
+                    self.rule_fns,
+                    entity_markers,
+                    receive_markers,
+                    entity,
+                    message,
+                )
+            }
+        })
+        .unwrap_or_else(|panic| Err(self.panic_error(entity_id, panic)))
     }
 
     /// Same as [`Self::write`], but calls the assigned remove function.
+    ///
+    /// Caught panics are logged, since removals can't fail.
     pub(crate) fn remove(
         &self,
         ctx: &mut RemoveCtx,
         entity_markers: &EntityMarkers,
         entity: &mut DeferredEntity,
     ) {
-        self.component_fns.remove(ctx, entity_markers, entity);
+        let entity_id = entity.id();
+        if let Err(panic) = catch_panic(|| self.component_fns.remove(ctx, entity_markers, entity)) {
+            error!(
+                "removal of `{}` for `{entity_id}` panicked: {panic}",
+                self.rule_fns.type_name(),
+            );
+        }
+    }
+
+    fn panic_error(&self, entity: Entity, panic: String) -> BevyError {
+        format!(
+            "writing `{}` for `{entity}` panicked: {panic}",
+            self.rule_fns.type_name()
+        )
+        .into()
+    }
+}
+
+/// Calls `f` and returns the panic message if it panics.
+///
+/// Panics are caught only with the `catch_panics` feature.
+#[cfg(feature = "catch_panics")]
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+}
+
+#[cfg(not(feature = "catch_panics"))]
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    Ok(f())
+}
+
+/// Extracts the message from a panic payload.
+#[cfg(feature = "catch_panics")]
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).into()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".into()
     }
 }
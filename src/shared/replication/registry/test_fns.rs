@@ -72,6 +72,8 @@ struct ExampleComponent;
 pub trait TestFnsEntityExt {
     /// Returns a component serialized using a registered function for it.
     ///
+    /// Returns an empty message if sending was vetoed by [`RuleFns::with_outbound`].
+    ///
     /// See also [`ReplicationRegistry::register_rule_fns`].
     #[must_use]
     fn serialize(&mut self, fns_id: FnsId, server_tick: RepliconTick) -> Vec<u8>;
@@ -137,6 +139,8 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
                 component_id,
                 type_registry,
                 diff_cursor,
+                keyframe_interval: 0,
+                insertion: true,
                 last_changed: ticks.changed,
                 storage: &mut storage,
             };
@@ -1,15 +1,22 @@
 pub mod component;
 pub mod filter;
+pub mod lod;
+pub mod scope;
 
-use core::cmp::Reverse;
+use core::{cmp::Reverse, num::NonZeroU32};
 
 use bevy::{ecs::archetype::Archetype, prelude::*};
 use serde::{Serialize, de::DeserializeOwned};
 
 use super::registry::{ReplicationRegistry, receive_fns::MutWrite};
 use crate::prelude::*;
-use component::{BundleRules, ComponentRule, IntoComponentRules, IntoResourceRule};
+use component::{
+    BundleRules, ComponentRule, InsertionBoost, IntoComponentRule, IntoComponentRules,
+    IntoResourceRule,
+};
 use filter::{FilterRule, FilterRules};
+use lod::{LodBand, LodDistanceFn, LodRule};
+use scope::ReplicationScope;
 
 /// Replication functions for [`App`].
 pub trait AppRuleExt {
@@ -57,6 +64,73 @@ pub trait AppRuleExt {
         self.replicate_with_filtered::<_, F>(RuleFns::<C>::new_diff())
     }
 
+    /// Like [`Self::replicate`], but sends full values only as periodic keyframes
+    /// and deltas relative to the last keyframe in between.
+    ///
+    /// Uses [`Keyframed::KEYFRAME_INTERVAL`], which can be overridden with
+    /// [`Self::with_keyframe_interval`].
+    ///
+    /// See [`Keyframed`] for more details.
+    fn replicate_keyframed<C>(&mut self) -> &mut Self
+    where
+        C: Keyframed,
+    {
+        self.replicate_keyframed_filtered::<C, ()>()
+    }
+
+    /// Like [`Self::replicate_keyframed`], but also adds filters like [`Self::replicate_filtered`].
+    fn replicate_keyframed_filtered<C, F: FilterRules>(&mut self) -> &mut Self
+    where
+        C: Keyframed,
+    {
+        self.replicate_with_filtered::<_, F>(RuleFns::<C>::new_keyframed())
+            .with_keyframe_interval(C::KEYFRAME_INTERVAL)
+    }
+
+    /// Like [`Self::replicate`], but merges received values into existing components
+    /// instead of overwriting them.
+    ///
+    /// Uses [`ReplicationMode::Refresh`] with [`CrdtMerge::REFRESH_INTERVAL`]
+    /// or [`ReplicationMode::OnChange`] if the interval is 0.
+    ///
+    /// See [`CrdtMerge`] for more details.
+    fn replicate_crdt<C>(&mut self) -> &mut Self
+    where
+        C: CrdtMerge,
+    {
+        self.replicate_crdt_filtered::<C, ()>()
+    }
+
+    /// Like [`Self::replicate_crdt`], but also adds filters like [`Self::replicate_filtered`].
+    fn replicate_crdt_filtered<C, F: FilterRules>(&mut self) -> &mut Self
+    where
+        C: CrdtMerge,
+    {
+        let mode = NonZeroU32::new(C::REFRESH_INTERVAL)
+            .map_or(ReplicationMode::OnChange, |interval| {
+                ReplicationMode::Refresh { interval }
+            });
+        self.replicate_with_filtered::<_, F>((RuleFns::<C>::new_crdt(), mode))
+    }
+
+    /// Like [`Self::replicate`], but serializes the component using [`PresenceBitmap`].
+    ///
+    /// See [`PresenceBitmap`] for more details.
+    fn replicate_bitmap<C>(&mut self) -> &mut Self
+    where
+        C: Component<Mutability: MutWrite<C>> + PresenceBitmap,
+    {
+        self.replicate_bitmap_filtered::<C, ()>()
+    }
+
+    /// Like [`Self::replicate_bitmap`], but also adds filters like [`Self::replicate_filtered`].
+    fn replicate_bitmap_filtered<C, F: FilterRules>(&mut self) -> &mut Self
+    where
+        C: Component<Mutability: MutWrite<C>> + PresenceBitmap,
+    {
+        self.replicate_with_filtered::<_, F>(RuleFns::<C>::new_bitmap())
+    }
+
     /// Like [`Self::replicate`], but converts the component into `T` before serialization
     /// and back into `C` after deserialization.
     ///
@@ -909,6 +983,445 @@ pub trait AppRuleExt {
         &mut self,
         priority: usize,
     ) -> &mut Self;
+
+    /**
+    Defines a [`ReplicationRule`] for a single component with distance-based level of detail.
+
+    Each band is a pair of an exclusive maximum distance and functions to use for clients
+    within it. Bands should be sorted by their distance in ascending order. The server calls
+    `distance_fn` with an authorized client entity that has `I` and a replicated entity
+    matching the rule and serializes the component with functions of the band that covers
+    the distance. Serialized bytes are shared between clients of the same band.
+
+    Distances are re-evaluated only when `C` changes on the entity or `I` changes on the client,
+    so `distance_fn` should depend only on them. Clients without `I` and clients beyond the last
+    band don't receive the component. Like with [`ClientWith`](filter::ClientWith), components
+    that were already sent won't be removed, only further updates will stop. When the band
+    for a client changes, the component is resent as a whole using the functions of the new band,
+    so bands should serialize complete values rather than diffs or keyframes.
+
+    The mutation channel and the replication mode are taken from the first band.
+
+    # Panics
+
+    Panics if no bands are provided or they aren't sorted.
+
+    # Examples
+
+    ```
+    # use bevy::state::app::StatesPlugin;
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        bytes::Bytes,
+        prelude::*,
+        shared::replication::registry::ctx::{SerializeCtx, WriteCtx},
+    };
+
+    # let mut app = App::new();
+    # app.add_plugins((StatesPlugin, RepliconPlugins));
+    app.replicate_lod::<Transform, Interest>(
+        interest_distance,
+        [
+            (50.0, RuleFns::default()),
+            (200.0, RuleFns::new(serialize_translation, deserialize_translation)),
+        ],
+    );
+
+    fn interest_distance(client: EntityRef, entity: EntityRef) -> f32 {
+        let interest = client.get::<Interest>().unwrap();
+        let transform = entity.get::<Transform>().unwrap();
+        interest.distance(transform.translation)
+    }
+
+    /// Point of interest for a client, usually the position of its player.
+    #[derive(Component, Deref)]
+    struct Interest(Vec3);
+
+    # fn serialize_translation(_: &mut SerializeCtx, _: &Transform, _: &mut Vec<u8>) -> Result<()> { unimplemented!() }
+    # fn deserialize_translation(_: &mut WriteCtx, _: &mut Bytes) -> Result<Transform> { unimplemented!() }
+    ```
+    **/
+    fn replicate_lod<C: Component<Mutability: MutWrite<C>>, I: Component>(
+        &mut self,
+        distance_fn: LodDistanceFn,
+        bands: impl IntoIterator<Item = (f32, RuleFns<C>)>,
+    ) -> &mut Self;
+
+    /**
+    Registers all replication rules defined inside `f` as optional.
+
+    Optional rules aren't included in [`ProtocolHash`], so clients can omit them. Components
+    from rules that the client didn't register are skipped with a warning. Useful for things like
+    visualization plugins that only some builds register.
+
+    Optional rules must be registered after all required rules, and clients can omit only
+    the last of them in the server's registration order. Otherwise functions IDs won't match
+    and the client will deserialize components using the wrong functions. To catch this,
+    optional rules are hashed separately into [`ProtocolBreakdown::optional`] and
+    [`ProtocolCheck`](crate::server::authorization::ProtocolCheck) denies clients whose
+    optional rules aren't a prefix of the server's.
+
+    # Panics
+
+    Panics if a required rule is registered after an optional one.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate::<Health>();
+
+    // Could be registered only in builds with debug visualization.
+    app.replicate_optional(|app| {
+        app.replicate::<DebugPath>();
+    });
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct DebugPath(Vec<Vec3>);
+    ```
+    **/
+    fn replicate_optional(&mut self, f: impl FnOnce(&mut Self)) -> &mut Self;
+
+    /**
+    Prefixes data of all replicated components with its size.
+
+    By default, only components from [optional rules](Self::replicate_optional) are prefixed,
+    since only they can be unknown to clients. The prefix lets code that reads serialized
+    components skip the ones it doesn't need without deserializing them, like
+    [`EntityPoolPlugin`] does to find the [`Template`] of a new entity.
+    Also required to decode messages with `ServerTestAppExt::capture_replication`.
+
+    Adds 4 bytes to each serialized component. Included in [`ProtocolHash`], so it should be
+    called on both client and server. Calling it more than once has no effect.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.prefix_component_sizes();
+    ```
+    **/
+    fn prefix_component_sizes(&mut self) -> &mut Self;
+
+    /**
+    Registers all replication rules defined inside `f` with the filter `F`.
+
+    Produces the same rules as calling `*_filtered` methods with `F` for each of them,
+    including their default priorities. Useful when many components share the same filter,
+    so it's declared once.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replication_scope::<With<Player>>(|scope| {
+        scope
+            .replicate::<Health>()
+            .replicate::<Mana>()
+            .replicate_once::<Class>()
+            .replicate_bundle::<(Transform, Name)>();
+    });
+
+    // Equivalent to:
+    // app.replicate_filtered::<Health, With<Player>>()
+    //     .replicate_filtered::<Mana, With<Player>>()
+    //     .replicate_once_filtered::<Class, With<Player>>()
+    //     .replicate_bundle_filtered::<(Transform, Name), With<Player>>();
+
+    #[derive(Component)]
+    struct Player;
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Mana(u32);
+
+    #[derive(Component, Deserialize, Serialize)]
+    enum Class {
+        Warrior,
+        Mage,
+    }
+    ```
+    **/
+    fn replication_scope<F: FilterRules>(
+        &mut self,
+        f: impl FnOnce(&mut ReplicationScope<F>),
+    ) -> &mut Self;
+
+    /**
+    Sets [`ReplicationRule::priority_fn`] for the last defined rule.
+
+    The function is evaluated on the server for each entity that matches the rule on every tick.
+    The returned value is multiplied into the entity priority from [`PriorityMap`](crate::prelude::PriorityMap)
+    for all clients. Useful to adjust priorities based on component values without updating
+    [`PriorityMap`](crate::prelude::PriorityMap) for each client manually.
+
+    If multiple rules with priority functions match an entity, their values are multiplied.
+
+    Has no effect on the client.
+
+    # Panics
+
+    Panics if no rules were defined before.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate::<Velocity>().with_priority_fn(|entity| {
+        let velocity = entity.get::<Velocity>().unwrap();
+        // Send mutations for slow bodies less often.
+        velocity.length().clamp(0.25, 1.0)
+    });
+
+    #[derive(Component, Deref, Deserialize, Serialize)]
+    struct Velocity(Vec3);
+    ```
+    **/
+    fn with_priority_fn(&mut self, priority_fn: PriorityFn) -> &mut Self;
+
+    /**
+    Enables [`ComponentRule::cached`] for all components of the last defined rule.
+
+    By default, the server serializes a component once per tick and shares the bytes between
+    all clients that need it. But if the component is sent on the next tick without being changed,
+    for example, to a newly connected client or as part of a resent mutation, it's serialized again.
+
+    With the cache enabled, the server stores serialized bytes along with the change tick of the
+    component and reuses them until the component changes. This trades memory for CPU time,
+    so it's useful for components that are expensive to serialize and rarely change,
+    especially on servers with many clients.
+
+    Components serialized as diffs (see [`Diffable`](crate::prelude::Diffable)) or keyframes
+    (see [`Keyframed`](crate::prelude::Keyframed)) aren't cached.
+    The serialization function should produce the same bytes for the same value, without relying on
+    [`SerializeCtx::server_tick`](crate::shared::replication::registry::ctx::SerializeCtx::server_tick).
+
+    Has no effect on the client.
+
+    # Panics
+
+    Panics if no rules were defined before.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate::<Terrain>().with_serialization_cache();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Terrain(Vec<u16>);
+    ```
+    **/
+    fn with_serialization_cache(&mut self) -> &mut Self;
+
+    /**
+    Sets [`ComponentRule::echo_suppression`] for all components of the last defined rule.
+
+    Useful for client-authoritative components: the client writes a value locally and sends it
+    to the server, the server validates and applies it, then replicates it back. Because of latency,
+    the echoed value is slightly stale compared to the client's local one and causes jitter.
+
+    After the server accepts a write via
+    [`EntityEchoExt::accept_client_write`](crate::server::client_writes::EntityEchoExt::accept_client_write),
+    mutations of the component on the entity aren't sent back to the writing client for the given
+    number of server ticks. Other clients still receive them. Each accepted write restarts the window.
+
+    Mutations are skipped, not delayed, so if the server itself modifies the component during
+    the window, for example, to correct an invalid value, the writing client may not receive
+    the change until the component changes again after the window.
+
+    Only mutations are suppressed; insertions and removals are always sent.
+
+    Has no effect on the client.
+
+    # Panics
+
+    Panics if no rules were defined before.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate::<Aim>()
+        .with_echo_suppression(10)
+        .add_client_event::<AimInput>(Channel::Unreliable)
+        .add_observer(apply_aim);
+
+    fn apply_aim(
+        aim: On<FromClient<AimInput>>,
+        mut commands: Commands,
+        players: Query<(Entity, &Player)>,
+    ) {
+        let Some(client) = aim.client_id.entity() else {
+            return;
+        };
+
+        // Validation is omitted for brevity.
+        if let Some((entity, _)) = players.iter().find(|(_, player)| player.0 == client) {
+            commands
+                .entity(entity)
+                .insert(Aim(aim.0))
+                .accept_client_write::<Aim>(client);
+        }
+    }
+
+    #[derive(Component)]
+    struct Player(Entity);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Aim(Vec2);
+
+    #[derive(Event, Deserialize, Serialize)]
+    struct AimInput(Vec2);
+    ```
+    **/
+    fn with_echo_suppression(&mut self, ticks: u32) -> &mut Self;
+
+    /**
+    Sets [`ComponentRule::keyframe_interval`] for all components of the last defined rule.
+
+    Every `interval`-th send of a [`Keyframed`] component will contain the full value, while
+    sends in between will contain only the delta relative to the last keyframe. Sends are counted
+    per tick, not per client. A lower interval makes recovery from a lost keyframe faster, while
+    a higher one reduces bandwidth. Set to 0 or 1 to send only keyframes.
+
+    Affects only components registered with [`RuleFns::new_keyframed`], such as
+    via [`Self::replicate_keyframed`]. Keyframed components can't be cached with
+    [`Self::with_serialization_cache`].
+
+    Has no effect on the client.
+
+    # Panics
+
+    Panics if no rules were defined before.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate_keyframed::<Occlusion>()
+        .with_keyframe_interval(30);
+
+    #[derive(Component, Deserialize, Serialize, Clone, Copy)]
+    struct Occlusion(f32);
+
+    impl Keyframed for Occlusion {
+        type Delta = i8;
+
+        fn delta(&self, keyframe: &Self) -> Self::Delta {
+            ((self.0 - keyframe.0) * 100.0) as i8
+        }
+
+        fn from_delta(keyframe: &Self, delta: Self::Delta) -> Self {
+            Self(keyframe.0 + delta as f32 / 100.0)
+        }
+    }
+    ```
+    **/
+    fn with_keyframe_interval(&mut self, interval: u16) -> &mut Self;
+
+    /**
+    Sets [`ComponentRule::full_refresh`] to `false` for all components of the last defined rule.
+
+    Excludes the components from full refreshes configured with [`ServerPlugin::full_refresh_interval`]
+    or requested with [`EntityResendExt::resend_all`](crate::server::resend::EntityResendExt::resend_all).
+    Useful for huge components that are expensive to re-send.
+
+    Has no effect on the client.
+
+    # Panics
+
+    Panics if no rules were defined before.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate::<Terrain>().without_full_refresh();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Terrain(Vec<u8>);
+    ```
+    **/
+    fn without_full_refresh(&mut self) -> &mut Self;
+
+    /**
+    Sets [`ComponentRule::insertion_boost`] for all components of the last defined rule.
+
+    Mutations that follow an insertion are often critical, like an initial velocity or the
+    first frames of a spawn animation. For `ticks` server ticks after a component is inserted
+    for a client, priority of its mutations is multiplied by a factor that starts at `multiplier`
+    and decays linearly back to 1.0. This way clients throttled by
+    [`PriorityMap`](crate::server::PriorityMap) or under
+    [`ReplicationPressure`](crate::server::pressure::ReplicationPressure) receive early-life
+    mutations promptly. The window is tracked per client and restarts on each insertion.
+
+    Has no effect on the client.
+
+    # Panics
+
+    Panics if no rules were defined before.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate::<Velocity>().with_insertion_boost(10, 4.0);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Velocity(Vec3);
+    ```
+    **/
+    fn with_insertion_boost(&mut self, ticks: u32, multiplier: f32) -> &mut Self;
 }
 
 impl AppRuleExt for App {
@@ -943,6 +1456,9 @@ impl AppRuleExt for App {
                 priority,
                 components,
                 filters,
+                priority_fn: None,
+                enabled: true,
+                lod: None,
             });
 
         self
@@ -970,20 +1486,204 @@ impl AppRuleExt for App {
                 priority,
                 components,
                 filters,
+                priority_fn: None,
+                enabled: true,
+                lod: None,
             });
 
         self
     }
+
+    fn replicate_lod<C: Component<Mutability: MutWrite<C>>, I: Component>(
+        &mut self,
+        distance_fn: LodDistanceFn,
+        bands: impl IntoIterator<Item = (f32, RuleFns<C>)>,
+    ) -> &mut Self {
+        let bands: Vec<_> = bands.into_iter().collect();
+        assert!(
+            !bands.is_empty(),
+            "LOD rule for `{}` should have at least one band",
+            ShortName::of::<C>()
+        );
+        assert!(
+            bands.is_sorted_by(|(a, _), (b, _)| a < b),
+            "LOD bands for `{}` should be sorted by distance",
+            ShortName::of::<C>()
+        );
+
+        self.world_mut()
+            .resource_mut::<ProtocolHasher>()
+            .replicate_lod::<C>(bands.len());
+
+        let interest_id = self.world_mut().register_component::<I>();
+
+        // All bands are registered for the same component, but only the first one
+        // is used as the component rule to take the channel and mode from it.
+        let (component, bands) =
+            self.world_mut()
+                .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                    let mut component = None;
+                    let mut lod_bands = Vec::with_capacity(bands.len());
+                    for (max_distance, rule_fns) in bands {
+                        let rule = rule_fns.into_rule(world, &mut registry);
+                        lod_bands.push(LodBand {
+                            max_distance,
+                            fns_id: rule.fns_id,
+                        });
+                        component.get_or_insert(rule);
+                    }
+                    (component, lod_bands)
+                });
+
+        self.world_mut()
+            .resource_mut::<ReplicationRules>()
+            .insert(ReplicationRule {
+                priority: 1,
+                components: component.into_iter().collect(),
+                filters: Vec::new(),
+                priority_fn: None,
+                enabled: true,
+                lod: Some(LodRule {
+                    distance_fn,
+                    interest_id,
+                    bands,
+                }),
+            });
+
+        self
+    }
+
+    fn replicate_optional(&mut self, f: impl FnOnce(&mut Self)) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ReplicationRegistry>()
+            .set_optional(true);
+        self.world_mut()
+            .resource_mut::<ProtocolHasher>()
+            .set_optional(true);
+        (f)(self);
+        self.world_mut()
+            .resource_mut::<ReplicationRegistry>()
+            .set_optional(false);
+        self.world_mut()
+            .resource_mut::<ProtocolHasher>()
+            .set_optional(false);
+
+        self
+    }
+
+    fn prefix_component_sizes(&mut self) -> &mut Self {
+        if self
+            .world_mut()
+            .resource_mut::<ReplicationRegistry>()
+            .enable_size_prefixes()
+        {
+            self.world_mut()
+                .resource_mut::<ProtocolHasher>()
+                .prefix_component_sizes();
+        }
+
+        self
+    }
+
+    fn replication_scope<F: FilterRules>(
+        &mut self,
+        f: impl FnOnce(&mut ReplicationScope<F>),
+    ) -> &mut Self {
+        (f)(&mut ReplicationScope::new(self));
+        self
+    }
+
+    fn with_priority_fn(&mut self, priority_fn: PriorityFn) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ReplicationRules>()
+            .last_mut()
+            .expect("priority function should be set after defining a rule")
+            .priority_fn = Some(priority_fn);
+
+        self
+    }
+
+    fn with_serialization_cache(&mut self) -> &mut Self {
+        let rule = self
+            .world_mut()
+            .resource_mut::<ReplicationRules>()
+            .into_inner()
+            .last_mut()
+            .expect("serialization cache should be enabled after defining a rule");
+        for component in &mut rule.components {
+            component.cached = true;
+        }
+
+        self
+    }
+
+    fn with_echo_suppression(&mut self, ticks: u32) -> &mut Self {
+        let rule = self
+            .world_mut()
+            .resource_mut::<ReplicationRules>()
+            .into_inner()
+            .last_mut()
+            .expect("echo suppression should be set after defining a rule");
+        for component in &mut rule.components {
+            component.echo_suppression = ticks;
+        }
+
+        self
+    }
+
+    fn with_keyframe_interval(&mut self, interval: u16) -> &mut Self {
+        let rule = self
+            .world_mut()
+            .resource_mut::<ReplicationRules>()
+            .into_inner()
+            .last_mut()
+            .expect("keyframe interval should be set after defining a rule");
+        for component in &mut rule.components {
+            component.keyframe_interval = interval;
+        }
+
+        self
+    }
+
+    fn without_full_refresh(&mut self) -> &mut Self {
+        let rule = self
+            .world_mut()
+            .resource_mut::<ReplicationRules>()
+            .into_inner()
+            .last_mut()
+            .expect("full refresh should be disabled after defining a rule");
+        for component in &mut rule.components {
+            component.full_refresh = false;
+        }
+
+        self
+    }
+
+    fn with_insertion_boost(&mut self, ticks: u32, multiplier: f32) -> &mut Self {
+        let rule = self
+            .world_mut()
+            .resource_mut::<ReplicationRules>()
+            .into_inner()
+            .last_mut()
+            .expect("insertion boost should be set after defining a rule");
+        for component in &mut rule.components {
+            component.insertion_boost = Some(InsertionBoost { ticks, multiplier });
+        }
+
+        self
+    }
 }
 
 /// All registered rules for components replication.
 #[derive(Resource, Deref, Default, Clone)]
-pub struct ReplicationRules(Vec<ReplicationRule>);
+pub struct ReplicationRules(#[deref] Vec<ReplicationRule>, Option<usize>);
 
 impl ReplicationRules {
     /// Inserts a new rule, maintaining sorting by their priority in descending order.
     fn insert(&mut self, rule: ReplicationRule) {
-        match self.binary_search_by_key(&Reverse(rule.priority), |rule| Reverse(rule.priority)) {
+        let index = match self
+            .binary_search_by_key(&Reverse(rule.priority), |rule| Reverse(rule.priority))
+        {
             Ok(index) => {
                 // Insert last to preserve entry creation order.
                 let last_priority_index = self
@@ -991,13 +1691,36 @@ impl ReplicationRules {
                     .skip(index + 1)
                     .position(|other| other.priority != rule.priority)
                     .unwrap_or_default();
-                self.0.insert(index + last_priority_index + 1, rule);
+                index + last_priority_index + 1
             }
-            Err(index) => self.0.insert(index, rule),
-        }
+            Err(index) => index,
+        };
+        self.0.insert(index, rule);
+        self.1 = Some(index);
+    }
+
+    /// Enables or disables the rule at the given index.
+    ///
+    /// See [`ReplicationRule::enabled`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        self.0[index].enabled = enabled;
+    }
+
+    /// Returns the last inserted rule.
+    fn last_mut(&mut self) -> Option<&mut ReplicationRule> {
+        self.1.map(|index| &mut self.0[index])
     }
 }
 
+/// Function that returns a priority multiplier for an entity.
+///
+/// See [`AppRuleExt::with_priority_fn`].
+pub type PriorityFn = fn(EntityRef) -> f32;
+
 /// Describes how component(s) will be replicated.
 ///
 /// Created using methods from [`AppRuleExt`].
@@ -1014,12 +1737,35 @@ pub struct ReplicationRule {
 
     /// Associated filters.
     pub filters: Vec<FilterRule>,
+
+    /// Optional priority multiplier for entities matching this rule.
+    ///
+    /// See [`AppRuleExt::with_priority_fn`].
+    pub priority_fn: Option<PriorityFn>,
+
+    /// Whether the server replicates components from this rule.
+    ///
+    /// Enabled by default. Can be toggled at runtime via [`ReplicationRules::set_enabled`].
+    /// Only archetypes affected by the toggled rule are re-evaluated.
+    ///
+    /// Toggling doesn't send anything by itself: components from an enabled rule will be
+    /// replicated on their next change and components from a disabled rule will stay on clients.
+    pub enabled: bool,
+
+    /// Distance-based level of detail for the component of this rule.
+    ///
+    /// See [`AppRuleExt::replicate_lod`].
+    pub lod: Option<LodRule>,
 }
 
 impl ReplicationRule {
-    /// Determines whether an archetype contains all components required by the rule.
+    /// Determines whether the rule is enabled and an archetype contains all components required by it.
     #[must_use]
     pub(crate) fn matches(&self, archetype: &Archetype) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
         if !self.filters.iter().all(|filter| filter.matches(archetype)) {
             return false;
         }
@@ -1159,6 +1905,45 @@ mod tests {
         assert!(!rule_a_b.matches(cda));
     }
 
+    #[test]
+    fn scope() {
+        let mut app = App::new();
+        app.init_resource::<ProtocolHasher>()
+            .init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationRegistry>()
+            .replication_scope::<With<B>>(|scope| {
+                scope
+                    .replicate::<A>()
+                    .replicate_once::<C>()
+                    .replicate_bundle::<(A, C)>();
+            });
+
+        let rules = app
+            .world_mut()
+            .remove_resource::<ReplicationRules>()
+            .unwrap();
+        let [rule_ac_b, rule_a_b, rule_c_b] = rules.0.try_into().unwrap();
+        assert_eq!(rule_ac_b.priority, 3);
+        assert_eq!(rule_a_b.priority, 2);
+        assert_eq!(rule_c_b.priority, 2);
+        assert_eq!(rule_c_b.components[0].mode, ReplicationMode::Once);
+
+        let abc = app.world_mut().spawn((A, B, C)).archetype().id();
+        let cda = app.world_mut().spawn((C, D, A)).archetype().id();
+
+        let abc = app.world().archetypes().get(abc).unwrap();
+        let cda = app.world().archetypes().get(cda).unwrap();
+
+        assert!(rule_ac_b.matches(abc));
+        assert!(!rule_ac_b.matches(cda));
+
+        assert!(rule_a_b.matches(abc));
+        assert!(!rule_a_b.matches(cda));
+
+        assert!(rule_c_b.matches(abc));
+        assert!(!rule_c_b.matches(cda));
+    }
+
     #[test]
     fn with() {
         let mut app = App::new();
@@ -1334,6 +2119,45 @@ mod tests {
         assert!(!rule_ab_c.matches(cda));
     }
 
+    #[test]
+    fn lod() {
+        let mut app = App::new();
+        app.init_resource::<ProtocolHasher>()
+            .init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationRegistry>()
+            .replicate_lod::<A, B>(
+                |_, _| 0.0,
+                [(10.0, RuleFns::default()), (50.0, RuleFns::default())],
+            );
+
+        let rules = app
+            .world_mut()
+            .remove_resource::<ReplicationRules>()
+            .unwrap();
+        let [rule] = rules.0.try_into().unwrap();
+        assert_eq!(rule.priority, 1);
+
+        assert_eq!(rule.components.len(), 1);
+        let lod = rule.lod.as_ref().unwrap();
+        assert_eq!(lod.bands.len(), 2);
+        assert_eq!(rule.components[0].fns_id, lod.bands[0].fns_id);
+        assert_ne!(lod.bands[0].fns_id, lod.bands[1].fns_id);
+
+        assert_eq!(lod.band(0.0), Some(0));
+        assert_eq!(lod.band(10.0), Some(1));
+        assert_eq!(lod.band(49.9), Some(1));
+        assert_eq!(lod.band(50.0), None);
+
+        let a = app.world_mut().spawn(A).archetype().id();
+        let b = app.world_mut().spawn(B).archetype().id();
+
+        let a = app.world().archetypes().get(a).unwrap();
+        let b = app.world().archetypes().get(b).unwrap();
+
+        assert!(rule.matches(a));
+        assert!(!rule.matches(b));
+    }
+
     #[derive(Component, Serialize, Deserialize, Clone, Copy)]
     struct A;
 
@@ -1,3 +1,5 @@
+use core::num::NonZeroU32;
+
 use bevy::{ecs::component::ComponentId, prelude::*};
 use serde::{Serialize, de::DeserializeOwned};
 
@@ -15,6 +17,31 @@ pub struct ComponentRule {
     pub fns_id: FnsId,
     /// Replication configuration.
     pub mode: ReplicationMode,
+    /// Whether serialized bytes are reused across ticks while the component is unchanged.
+    ///
+    /// See [`AppRuleExt::with_serialization_cache`].
+    pub cached: bool,
+    /// Channel over which mutations are sent.
+    ///
+    /// See [`RuleFns::on_channel`].
+    pub channel: MutationChannel,
+    /// Number of server ticks after an accepted client write during which mutations
+    /// aren't sent back to that client.
+    ///
+    /// See [`AppRuleExt::with_echo_suppression`].
+    pub echo_suppression: u32,
+    /// Number of sends between full keyframes for [`Keyframed`] components.
+    ///
+    /// See [`AppRuleExt::with_keyframe_interval`].
+    pub keyframe_interval: u16,
+    /// Whether the component is re-sent during a full refresh.
+    ///
+    /// See [`AppRuleExt::without_full_refresh`].
+    pub full_refresh: bool,
+    /// Temporary priority increase for mutations after insertion.
+    ///
+    /// See [`AppRuleExt::with_insertion_boost`].
+    pub insertion_boost: Option<InsertionBoost>,
 }
 
 impl ComponentRule {
@@ -24,6 +51,12 @@ impl ComponentRule {
             id,
             fns_id,
             mode: Default::default(),
+            cached: false,
+            channel: Default::default(),
+            echo_suppression: 0,
+            keyframe_interval: 0,
+            full_refresh: true,
+            insertion_boost: None,
         }
     }
 }
@@ -44,6 +77,65 @@ pub enum ReplicationMode {
     ///
     /// Component mutations and re-insertions won't be sent.
     Once,
+
+    /// Like [`Self::OnChange`], but also re-sends the whole component
+    /// if it wasn't sent to a client for `interval` server ticks.
+    ///
+    /// The first refresh for an entity is shifted by an offset based on its index,
+    /// so refreshes of entities sent on the same tick are spread across the interval.
+    ///
+    /// Useful for components that merge received state, like [`CrdtMerge`](crate::prelude::CrdtMerge),
+    /// to converge after missed or reordered updates.
+    Refresh {
+        /// How often to re-send the component in server ticks.
+        interval: NonZeroU32,
+    },
+}
+
+impl ReplicationMode {
+    /// Returns `true` if an unchanged component last sent at `last_sent` should be re-sent at `tick`.
+    pub(crate) fn should_refresh(self, last_sent: RepliconTick, tick: RepliconTick) -> bool {
+        match self {
+            ReplicationMode::Refresh { interval } => tick - last_sent >= interval.get(),
+            ReplicationMode::OnChange | ReplicationMode::Once => false,
+        }
+    }
+
+    /// Returns the number of ticks by which the first refresh for the entity is shifted.
+    pub(crate) fn refresh_offset(self, entity: Entity) -> u32 {
+        match self {
+            ReplicationMode::Refresh { interval } => entity.index_u32() % interval,
+            ReplicationMode::OnChange | ReplicationMode::Once => 0,
+        }
+    }
+}
+
+/// Priority multiplier for mutations sent shortly after insertion.
+///
+/// Used inside [`ComponentRule`].
+///
+/// See [`AppRuleExt::with_insertion_boost`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InsertionBoost {
+    /// Number of server ticks after insertion during which the boost applies.
+    pub ticks: u32,
+
+    /// Priority multiplier right after insertion.
+    ///
+    /// Decays linearly to 1.0 by the end of the window.
+    pub multiplier: f32,
+}
+
+impl InsertionBoost {
+    /// Returns the priority multiplier for a mutation sent `elapsed` ticks after insertion.
+    pub(crate) fn multiplier_at(self, elapsed: u32) -> f32 {
+        if elapsed >= self.ticks {
+            return 1.0;
+        }
+
+        let remaining = 1.0 - elapsed as f32 / self.ticks as f32;
+        1.0 + (self.multiplier - 1.0) * remaining
+    }
 }
 
 /// Parameters that can be turned into a component replication rule.
@@ -59,16 +151,42 @@ pub trait IntoComponentRule {
 
 impl<C: Component<Mutability: MutWrite<C>>> IntoComponentRule for RuleFns<C> {
     fn into_rule(self, world: &mut World, registry: &mut ReplicationRegistry) -> ComponentRule {
+        let channel = self.channel();
+        enable_channel(world, channel);
         let (id, fns_id) = registry.register_rule_fns(world, self);
-        ComponentRule::new(id, fns_id)
+        ComponentRule {
+            channel,
+            ..ComponentRule::new(id, fns_id)
+        }
     }
 }
 
 impl<C: Component<Mutability: MutWrite<C>>> IntoComponentRule for (RuleFns<C>, ReplicationMode) {
     fn into_rule(self, world: &mut World, registry: &mut ReplicationRegistry) -> ComponentRule {
         let (rule_fns, mode) = self;
+        let channel = rule_fns.channel();
+        enable_channel(world, channel);
         let (id, fns_id) = registry.register_rule_fns(world, rule_fns);
-        ComponentRule { id, fns_id, mode }
+        ComponentRule {
+            id,
+            fns_id,
+            mode,
+            cached: false,
+            channel,
+            echo_suppression: 0,
+            keyframe_interval: 0,
+            full_refresh: true,
+            insertion_boost: None,
+        }
+    }
+}
+
+/// Creates the server channel for the mutation channel if needed.
+fn enable_channel(world: &mut World, channel: MutationChannel) {
+    if channel == MutationChannel::Secondary {
+        world
+            .resource_mut::<RepliconChannels>()
+            .enable_secondary_mutations();
     }
 }
 
@@ -162,6 +280,13 @@ macro_rules! impl_into_bundle_rules {
                                 id,
                                 fns_id,
                                 mode: Default::default(),
+                                cached: false,
+                                channel: Default::default(),
+                                echo_suppression: 0,
+                                keyframe_interval: 0,
+                                full_refresh: true,
+                                insertion_boost: None,
+            insertion_boost: None,
                             }
                         },
                     )*
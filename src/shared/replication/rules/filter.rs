@@ -1,3 +1,5 @@
+use core::marker::PhantomData;
+
 use bevy::{
     ecs::{archetype::Archetype, component::ComponentId},
     prelude::*,
@@ -12,14 +14,44 @@ pub enum FilterRule {
     Without(ComponentId),
     /// Corresponds to [`Or`].
     Or(Vec<FilterRule>),
+    /// Corresponds to [`ClientWith`].
+    ClientWith(ComponentId),
 }
 
 impl FilterRule {
+    /// Checks the filter against an entity archetype.
+    ///
+    /// Client filters are assumed to match since they depend on the receiving client.
     pub(super) fn matches(&self, archetype: &Archetype) -> bool {
         match self {
             Self::With(id) => archetype.contains(*id),
             Self::Without(id) => !archetype.contains(*id),
             Self::Or(children) => children.iter().any(|child| child.matches(archetype)),
+            Self::ClientWith(_) => true,
+        }
+    }
+
+    /// Like [`Self::matches`], but also checks client filters against the archetype of a client entity.
+    pub(crate) fn matches_client(
+        &self,
+        archetype: &Archetype,
+        client_archetype: &Archetype,
+    ) -> bool {
+        match self {
+            Self::With(_) | Self::Without(_) => self.matches(archetype),
+            Self::Or(children) => children
+                .iter()
+                .any(|child| child.matches_client(archetype, client_archetype)),
+            Self::ClientWith(id) => client_archetype.contains(*id),
+        }
+    }
+
+    /// Returns `true` if the filter or any of its children is a client filter.
+    pub(crate) fn is_client(&self) -> bool {
+        match self {
+            Self::With(_) | Self::Without(_) => false,
+            Self::Or(children) => children.iter().any(Self::is_client),
+            Self::ClientWith(_) => true,
         }
     }
 }
@@ -67,6 +99,41 @@ impl<T: Component> FilterRules for Without<T> {
     }
 }
 
+/// Filter that matches only for clients whose client entity has component `C`.
+/// Unlike other filters, it's checked against the receiving client entity instead of the
+/// replicated entity. Can be combined with other filters.
+///
+/// The rule is still selected per archetype by priority, so other rules for the same
+/// components won't apply to clients that don't match. Components that were already
+/// sent won't be removed from a client if it loses `C`, only further updates will stop.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::{prelude::*, state::app::StatesPlugin};
+/// # use bevy_replicon::prelude::*;
+/// # use serde::{Deserialize, Serialize};
+/// # let mut app = App::new();
+/// # app.add_plugins((StatesPlugin, RepliconPlugins));
+/// // Replicate physics state only to clients in developer mode.
+/// app.replicate_filtered::<DebugPhysicsState, ClientWith<DeveloperMode>>();
+/// # #[derive(Component, Serialize, Deserialize)]
+/// # struct DebugPhysicsState;
+/// # #[derive(Component)]
+/// # struct DeveloperMode;
+/// ```
+pub struct ClientWith<C: Component>(PhantomData<C>);
+
+impl<C: Component> FilterRules for ClientWith<C> {
+    const ARITY: usize = 1;
+    const DEFAULT_PRIORITY: usize = 1;
+
+    fn push_filters(world: &mut World, filters: &mut Vec<FilterRule>) {
+        let id = world.register_component::<C>();
+        filters.push(FilterRule::ClientWith(id));
+    }
+}
+
 impl<F: FilterRules> FilterRules for Or<F> {
     const ARITY: usize = 1;
     const DEFAULT_PRIORITY: usize = F::DEFAULT_PRIORITY;
@@ -168,6 +235,28 @@ mod tests {
         assert!(with_b_or_c.matches(ab));
     }
 
+    #[test]
+    fn client_with() {
+        type Filters = Or<(With<A>, ClientWith<B>)>;
+
+        let mut world = World::new();
+        let [rule] = Filters::filter_rules(&mut world).try_into().unwrap();
+        assert!(rule.is_client());
+
+        let a = world.spawn(A).archetype().id();
+        let b = world.spawn(B).archetype().id();
+        let c = world.spawn(C).archetype().id();
+
+        let a = world.archetypes().get(a).unwrap();
+        let b = world.archetypes().get(b).unwrap();
+        let c = world.archetypes().get(c).unwrap();
+
+        assert!(rule.matches(c), "client filters should be optimistic");
+        assert!(rule.matches_client(a, c));
+        assert!(rule.matches_client(c, b));
+        assert!(!rule.matches_client(c, c));
+    }
+
     #[derive(Component)]
     struct A;
 
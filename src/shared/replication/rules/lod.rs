@@ -0,0 +1,45 @@
+use alloc::vec::Vec;
+
+use bevy::{ecs::component::ComponentId, prelude::*};
+
+use crate::shared::replication::registry::FnsId;
+
+/// Distance-based level of detail for [`ReplicationRule`](super::ReplicationRule).
+///
+/// See [`AppRuleExt::replicate_lod`](super::AppRuleExt::replicate_lod).
+#[derive(Clone, Debug)]
+pub struct LodRule {
+    /// Function that returns the distance between a client and a replicated entity.
+    pub distance_fn: LodDistanceFn,
+    /// ID of the client component whose changes trigger re-evaluation of bands.
+    pub interest_id: ComponentId,
+    /// Bands sorted by their maximum distance in ascending order.
+    pub bands: Vec<LodBand>,
+}
+
+impl LodRule {
+    /// Returns the index of the first band that covers the distance.
+    ///
+    /// Returns [`None`] if the distance is beyond all bands.
+    pub(crate) fn band(&self, distance: f32) -> Option<usize> {
+        self.bands
+            .iter()
+            .position(|band| distance < band.max_distance)
+    }
+}
+
+/// Band of [`LodRule`].
+#[derive(Clone, Copy, Debug)]
+pub struct LodBand {
+    /// Exclusive upper bound of the distance covered by this band.
+    ///
+    /// The lower bound is the maximum distance of the previous band.
+    pub max_distance: f32,
+    /// ID of the replication functions used for clients within this band.
+    pub fns_id: FnsId,
+}
+
+/// Function that returns the distance between a client entity and a replicated entity.
+///
+/// See [`AppRuleExt::replicate_lod`](super::AppRuleExt::replicate_lod).
+pub type LodDistanceFn = fn(client: EntityRef, entity: EntityRef) -> f32;
@@ -0,0 +1,161 @@
+use core::marker::PhantomData;
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{
+    AppRuleExt, PriorityFn,
+    component::{BundleRules, IntoComponentRules},
+    filter::FilterRules,
+};
+use crate::{prelude::*, shared::replication::registry::receive_fns::MutWrite};
+
+/// Registers replication rules that share the filter `F`.
+///
+/// Each method is equivalent to the corresponding `*_filtered` method from [`AppRuleExt`]
+/// with `F` as the filter, including the default priority.
+///
+/// Created by [`AppRuleExt::replication_scope`].
+pub struct ReplicationScope<'a, F: FilterRules> {
+    app: &'a mut App,
+    marker: PhantomData<F>,
+}
+
+impl<'a, F: FilterRules> ReplicationScope<'a, F> {
+    pub(super) fn new(app: &'a mut App) -> Self {
+        Self {
+            app,
+            marker: PhantomData,
+        }
+    }
+
+    /// Like [`AppRuleExt::replicate_filtered`].
+    pub fn replicate<C>(&mut self) -> &mut Self
+    where
+        C: Component<Mutability: MutWrite<C>> + Serialize + DeserializeOwned,
+    {
+        self.app.replicate_filtered::<C, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_once_filtered`].
+    pub fn replicate_once<C>(&mut self) -> &mut Self
+    where
+        C: Component<Mutability: MutWrite<C>> + Serialize + DeserializeOwned,
+    {
+        self.app.replicate_once_filtered::<C, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_diff_filtered`].
+    pub fn replicate_diff<C: Diffable>(&mut self) -> &mut Self {
+        self.app.replicate_diff_filtered::<C, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_keyframed_filtered`].
+    pub fn replicate_keyframed<C: Keyframed>(&mut self) -> &mut Self {
+        self.app.replicate_keyframed_filtered::<C, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_crdt_filtered`].
+    pub fn replicate_crdt<C: CrdtMerge>(&mut self) -> &mut Self {
+        self.app.replicate_crdt_filtered::<C, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_bitmap_filtered`].
+    pub fn replicate_bitmap<C>(&mut self) -> &mut Self
+    where
+        C: Component<Mutability: MutWrite<C>> + PresenceBitmap,
+    {
+        self.app.replicate_bitmap_filtered::<C, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_filtered_as`].
+    pub fn replicate_as<C, T>(&mut self) -> &mut Self
+    where
+        C: Component<Mutability: MutWrite<C>> + Clone + Into<T> + From<T>,
+        T: Serialize + DeserializeOwned,
+    {
+        self.app.replicate_filtered_as::<C, T, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_once_filtered_as`].
+    pub fn replicate_once_as<C, T>(&mut self) -> &mut Self
+    where
+        C: Component<Mutability: MutWrite<C>> + Clone + Into<T> + From<T>,
+        T: Serialize + DeserializeOwned,
+    {
+        self.app.replicate_once_filtered_as::<C, T, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_with_filtered`].
+    pub fn replicate_with<R: IntoComponentRules>(&mut self, component_rules: R) -> &mut Self {
+        self.app.replicate_with_filtered::<R, F>(component_rules);
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_with_priority_filtered`].
+    pub fn replicate_with_priority<R: IntoComponentRules>(
+        &mut self,
+        priority: usize,
+        component_rules: R,
+    ) -> &mut Self {
+        self.app
+            .replicate_with_priority_filtered::<R, F>(priority, component_rules);
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_bundle_filtered`].
+    pub fn replicate_bundle<B: BundleRules>(&mut self) -> &mut Self {
+        self.app.replicate_bundle_filtered::<B, F>();
+        self
+    }
+
+    /// Like [`AppRuleExt::replicate_bundle_with_filtered`].
+    pub fn replicate_bundle_with<B: BundleRules>(&mut self, priority: usize) -> &mut Self {
+        self.app.replicate_bundle_with_filtered::<B, F>(priority);
+        self
+    }
+
+    /// Like [`AppRuleExt::with_priority_fn`].
+    pub fn with_priority_fn(&mut self, priority_fn: PriorityFn) -> &mut Self {
+        self.app.with_priority_fn(priority_fn);
+        self
+    }
+
+    /// Like [`AppRuleExt::with_serialization_cache`].
+    pub fn with_serialization_cache(&mut self) -> &mut Self {
+        self.app.with_serialization_cache();
+        self
+    }
+
+    /// Like [`AppRuleExt::with_echo_suppression`].
+    pub fn with_echo_suppression(&mut self, ticks: u32) -> &mut Self {
+        self.app.with_echo_suppression(ticks);
+        self
+    }
+
+    /// Like [`AppRuleExt::with_keyframe_interval`].
+    pub fn with_keyframe_interval(&mut self, interval: u16) -> &mut Self {
+        self.app.with_keyframe_interval(interval);
+        self
+    }
+
+    /// Like [`AppRuleExt::without_full_refresh`].
+    pub fn without_full_refresh(&mut self) -> &mut Self {
+        self.app.without_full_refresh();
+        self
+    }
+
+    /// Like [`AppRuleExt::with_insertion_boost`].
+    pub fn with_insertion_boost(&mut self, ticks: u32, multiplier: f32) -> &mut Self {
+        self.app.with_insertion_boost(ticks, multiplier);
+        self
+    }
+}
@@ -9,7 +9,7 @@ use bevy::{
     prelude::*,
 };
 use deterministic_hash::DeterministicHasher;
-use log::{debug, error};
+use log::{debug, error, warn};
 use xxhash_rust::xxh3::Xxh3Default;
 
 /// Describes how to calculate a deterministic hash that identifies an entity.
@@ -29,6 +29,14 @@ use xxhash_rust::xxh3::Xxh3Default;
 ///
 /// Signatures can also be relevant only to a specific client. In this case, the signature
 /// will be sent only to that client.
+///
+/// An entity can be spawned with the same signature as an existing one, for example,
+/// to predict a respawn. It will be matched only after the existing entity is despawned.
+/// If the server reuses a signature within the same tick, the client entity is rebound
+/// to the new server entity, see [`SignatureRebound`](crate::client::SignatureRebound).
+///
+/// If an entity is spawned with a signature that is already registered, [`SignatureCollision`]
+/// is triggered. Current bindings can be inspected via [`SignatureMap`].
 #[derive(Component, Reflect, Debug, Clone, Copy)]
 #[component(on_add = register_hash, on_remove = unregister_hash)]
 pub struct Signature {
@@ -305,24 +313,35 @@ fn register_hash(mut world: DeferredWorld, ctx: HookContext) {
     let mut signature = entity.get_mut::<Signature>().unwrap();
     signature.hash = hash;
 
-    if let Some(mut map) = world.get_resource_mut::<SignatureMap>() {
-        map.insert(ctx.entity, hash);
-    } else {
-        // Can't be handled manually like with unregistering below.
-        // However, when it's inserted during replication, entities
-        // don't need to be mapped. For example, the signature could
-        // be a required component to map local player entities,
-        // but ignored for remote ones.
-        debug!("ignoring hash 0x{hash:016x} for `{}`", ctx.entity);
+    let insertion = world
+        .get_resource_mut::<SignatureMap>()
+        .map(|mut map| map.insert(ctx.entity, hash));
+    match insertion {
+        Some(Some(existing)) => world.trigger(SignatureCollision {
+            entity: ctx.entity,
+            existing,
+            hash,
+        }),
+        Some(None) => (),
+        None => {
+            // Can't be handled manually like with unregistering below.
+            // However, when it's inserted during replication, entities
+            // don't need to be mapped. For example, the signature could
+            // be a required component to map local player entities,
+            // but ignored for remote ones.
+            debug!("ignoring hash 0x{hash:016x} for `{}`", ctx.entity);
+        }
     }
 }
 
 fn unregister_hash(mut world: DeferredWorld, ctx: HookContext) {
     // The map will be unavailable while receiving replication because the
-    // resource is temporarily removed from the world, so cleanup is handled
-    // manually in the receive logic.
+    // resource is temporarily removed from the world, so removals are buffered
+    // and applied after the receive.
     if let Some(mut map) = world.get_resource_mut::<SignatureMap>() {
         map.remove(ctx.entity);
+    } else if let Some(mut removals) = world.get_resource_mut::<SignatureRemovals>() {
+        removals.push(ctx.entity);
     }
 }
 
@@ -332,37 +351,166 @@ fn unregister_hash(mut world: DeferredWorld, ctx: HookContext) {
 /// Used to detect hash collisions and on the client it's used to map received
 /// hashes from server to client's entities.
 ///
-/// Automatically updated via hooks.
+/// If a hash is already registered, the entity waits until the current one is removed and
+/// then takes its place. This allows spawning an entity with the same signature before the
+/// previous one is despawned, for example, to predict a respawn.
+///
+/// Automatically updated via hooks, including removals that happen while
+/// replication is being applied.
 #[derive(Resource, Default)]
-pub(crate) struct SignatureMap {
+pub struct SignatureMap {
     to_hashes: EntityHashMap<u64>,
     to_entities: HashMap<u64, Entity, NoOpHash>, // Skip hashing because the key is already a hash.
+    waiting: HashMap<u64, Vec<Entity>, NoOpHash>,
+
+    /// Server entities whose mapped client entities were despawned locally.
+    ///
+    /// See [`SignatureSettings::rebind_on_respawn`].
+    released: HashMap<u64, Entity, NoOpHash>,
 }
 
 impl SignatureMap {
-    pub(crate) fn get(&self, hash: u64) -> Option<Entity> {
+    /// Returns the entity that currently owns the hash.
+    pub fn get(&self, hash: u64) -> Option<Entity> {
         self.to_entities.get(&hash).copied()
     }
 
-    fn insert(&mut self, entity: Entity, hash: u64) {
+    /// Returns the hash registered for the entity.
+    ///
+    /// Also returns hashes of entities that wait for the current owner to be removed.
+    pub fn hash(&self, entity: Entity) -> Option<u64> {
+        self.to_hashes.get(&entity).copied()
+    }
+
+    /// Returns an iterator over entities waiting for the hash in the order they will take it.
+    pub fn waiting(&self, hash: u64) -> impl Iterator<Item = Entity> + '_ {
+        self.waiting.get(&hash).into_iter().flatten().copied()
+    }
+
+    /// Returns an iterator over all hashes with their current owners.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, Entity)> + '_ {
+        self.to_entities
+            .iter()
+            .map(|(&hash, &entity)| (hash, entity))
+    }
+
+    /// Registers a hash for an entity.
+    ///
+    /// Returns the current owner if the hash is already registered.
+    fn insert(&mut self, entity: Entity, hash: u64) -> Option<Entity> {
+        self.to_hashes.insert(entity, hash);
         match self.to_entities.try_insert(hash, entity) {
             Ok(_) => {
                 debug!("inserting hash 0x{hash:016x} for `{entity}`");
-                self.to_hashes.insert(entity, hash);
+                None
+            }
+            Err(e) => {
+                let existing = *e.entry.get();
+                warn!(
+                    "hash 0x{hash:016x} for `{entity}` already corresponds to `{existing}`, waiting for it to be removed",
+                );
+                self.waiting.entry(hash).or_default().push(entity);
+                Some(existing)
             }
-            Err(e) => error!(
-                "hash 0x{hash:016x} for `{entity}` already corresponds to `{}` and will be ignored",
-                e.value
-            ),
         }
     }
 
     pub(crate) fn remove(&mut self, entity: Entity) {
-        if let Some(hash) = self.to_hashes.remove(&entity) {
-            debug!("removing hash 0x{hash:016x} for `{entity}`");
+        let Some(hash) = self.to_hashes.remove(&entity) else {
+            return;
+        };
+
+        if self.to_entities.get(&hash) != Some(&entity) {
+            debug!("removing waiting hash 0x{hash:016x} for `{entity}`");
+            if let Some(waiting) = self.waiting.get_mut(&hash) {
+                waiting.retain(|&waiting_entity| waiting_entity != entity);
+                if waiting.is_empty() {
+                    self.waiting.remove(&hash);
+                }
+            }
+            return;
+        }
+
+        debug!("removing hash 0x{hash:016x} for `{entity}`");
+        let Some(mut waiting) = self.waiting.remove(&hash) else {
             self.to_entities.remove(&hash);
+            return;
+        };
+
+        let next = waiting.remove(0);
+        debug!("passing hash 0x{hash:016x} to waiting `{next}`");
+        self.to_entities.insert(hash, next);
+        if !waiting.is_empty() {
+            self.waiting.insert(hash, waiting);
         }
     }
+
+    /// Remembers the server entity of a locally despawned entity to bind it to the next entity
+    /// with the same hash.
+    pub(crate) fn release(&mut self, hash: u64, server_entity: Entity) {
+        debug!("releasing hash 0x{hash:016x} of `{server_entity}` for rebinding");
+        self.released.insert(hash, server_entity);
+    }
+
+    /// Takes the server entity released via [`Self::release`].
+    pub(crate) fn take_released(&mut self, hash: u64) -> Option<Entity> {
+        self.released.remove(&hash)
+    }
+
+    /// Forgets a released server entity, for example, when it's despawned on the server.
+    pub(crate) fn forget_released(&mut self, server_entity: Entity) {
+        self.released
+            .retain(|_, &mut entity| entity != server_entity);
+    }
+
+    /// Forgets all released server entities.
+    ///
+    /// Should be called on disconnect because server entities become invalid.
+    pub(crate) fn clear_released(&mut self) {
+        self.released.clear();
+    }
+}
+
+/// Entities whose [`Signature`] was removed while [`SignatureMap`] was unavailable.
+///
+/// Applied to the map after receiving replication.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub(crate) struct SignatureRemovals(Vec<Entity>);
+
+/// Client settings for entities mapped via [`Signature`].
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct SignatureSettings {
+    /**
+    Binds the server entity to a new local entity with the same signature after the
+    previously mapped entity was despawned locally, not via replication.
+
+    Without it, the next replication for the server entity spawns a new client entity
+    since the server doesn't know about the local despawn. With it, the respawned entity
+    continues to receive replication for the server entity. The server won't resend components
+    the client already acknowledged, so the respawned entity needs to have them.
+
+    If another entity with the same signature is waiting, it's bound right away.
+
+    Disabled by default.
+    */
+    pub rebind_on_respawn: bool,
+}
+
+/// Triggered when an entity is spawned with a [`Signature`] whose hash is already registered.
+///
+/// The entity waits until the existing one is removed. This is expected when predicting
+/// a respawn, but otherwise likely means that the signature isn't unique enough,
+/// which can be solved by hashing more components or adjusting the salt via [`Signature::with_salt`].
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SignatureCollision {
+    /// Entity with the colliding signature.
+    pub entity: Entity,
+
+    /// Entity that currently owns the hash.
+    pub existing: Entity,
+
+    /// Colliding hash.
+    pub hash: u64,
 }
 
 /// Tuple-impls are limited to at most 6 components.
@@ -487,6 +635,34 @@ mod tests {
         assert_ne!(hash_a, hash_b);
     }
 
+    #[test]
+    fn waiting() {
+        let mut map = SignatureMap::default();
+        let entity1 = Entity::from_raw_u32(1).unwrap();
+        let entity2 = Entity::from_raw_u32(2).unwrap();
+        let entity3 = Entity::from_raw_u32(3).unwrap();
+
+        map.insert(entity1, 0);
+        map.insert(entity2, 0);
+        map.insert(entity3, 0);
+        assert_eq!(map.get(0), Some(entity1));
+
+        map.remove(entity2);
+        assert_eq!(map.get(0), Some(entity1));
+
+        map.remove(entity1);
+        assert_eq!(
+            map.get(0),
+            Some(entity3),
+            "waiting entity should take the hash"
+        );
+
+        map.remove(entity3);
+        assert_eq!(map.get(0), None);
+        assert!(map.to_hashes.is_empty());
+        assert!(map.waiting.is_empty());
+    }
+
     #[derive(Component, Hash)]
     struct A;
 
@@ -0,0 +1,120 @@
+use bevy::{prelude::*, state::state::FreelyMutableState};
+use log::debug;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::prelude::*;
+
+/// Replication of Bevy states for [`App`].
+pub trait AppStateExt {
+    /**
+    Mirrors the server's [`State<S>`] to clients.
+
+    On the server, the current state is written into [`ReplicatedState<S>`] resource before
+    [`ServerSystems::Send`], which is replicated like any other resource. On clients, the received
+    value is applied via [`NextState<S>`] in [`PreUpdate`], so [`OnEnter`], [`OnExit`] and
+    [`StateTransitionEvent`] are triggered as usual in the same frame.
+
+    Since the state is replicated as part of regular replication, all entity changes from
+    the same server tick are already applied when the transition happens.
+
+    The state needs to be initialized on both the client and server. [`ReplicatedState<S>`]
+    is removed on the client after a disconnect, but the client's state stays unchanged.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.init_state::<Phase>()
+        .replicate_state::<Phase>()
+        .add_systems(OnEnter(Phase::Ended), show_results);
+
+    fn show_results() {
+        // Runs on both the client and server.
+    }
+
+    #[derive(States, Serialize, Deserialize, Default, Debug, Hash, PartialEq, Eq, Clone)]
+    enum Phase {
+        #[default]
+        Lobby,
+        Playing,
+        Ended,
+    }
+    ```
+    */
+    fn replicate_state<S: FreelyMutableState + Serialize + DeserializeOwned>(
+        &mut self,
+    ) -> &mut Self;
+}
+
+impl AppStateExt for App {
+    fn replicate_state<S: FreelyMutableState + Serialize + DeserializeOwned>(
+        &mut self,
+    ) -> &mut Self {
+        self.replicate_resource::<ReplicatedState<S>>();
+
+        #[cfg(feature = "server")]
+        self.add_systems(
+            PostUpdate,
+            write_state::<S>
+                .before(ServerSystems::Send)
+                .run_if(in_state(ServerState::Running)),
+        );
+
+        #[cfg(feature = "client")]
+        self.add_systems(
+            PreUpdate,
+            apply_state::<S>
+                .after(ClientSystems::Receive)
+                .run_if(in_state(ClientState::Connected))
+                .run_if(resource_exists_and_changed::<ReplicatedState<S>>),
+        )
+        .add_systems(OnExit(ClientState::Connected), remove_state::<S>);
+
+        self
+    }
+}
+
+#[cfg(feature = "server")]
+fn write_state<S: States>(
+    mut commands: Commands,
+    state: Res<State<S>>,
+    replicated_state: Option<ResMut<ReplicatedState<S>>>,
+) {
+    let state = ReplicatedState(state.get().clone());
+    match replicated_state {
+        Some(mut replicated_state) => {
+            if replicated_state.set_if_neq(state) {
+                debug!("writing changed `{:?}`", **replicated_state);
+            }
+        }
+        None => {
+            debug!("writing initial `{:?}`", *state);
+            commands.insert_resource(state);
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+fn apply_state<S: FreelyMutableState>(
+    replicated_state: Res<ReplicatedState<S>>,
+    mut next_state: ResMut<NextState<S>>,
+) {
+    debug!("applying received `{:?}`", **replicated_state);
+    next_state.set((**replicated_state).clone());
+}
+
+#[cfg(feature = "client")]
+fn remove_state<S: States>(mut commands: Commands) {
+    commands.remove_resource::<ReplicatedState<S>>();
+}
+
+/// Server's state replicated to clients.
+///
+/// See [`AppStateExt::replicate_state`].
+#[derive(Resource, Deref, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ReplicatedState<S: States>(S);
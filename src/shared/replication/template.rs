@@ -0,0 +1,209 @@
+use bevy::{ecs::component::ComponentId, platform::collections::HashMap, prelude::*, ptr::Ptr};
+use log::{debug, error};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Entity templates for reducing bandwidth of spawning many similar entities.
+pub trait AppTemplateExt {
+    /**
+    Registers a template with component values that will be inserted into entities with
+    [`Template`] if they are missing.
+
+    On the server, components that are equal to their template values are not sent.
+    Only the [`Template`] itself and components that differ from the template (overrides)
+    are replicated. The client instantiates the rest from its own registered templates.
+    This makes spawning many nearly-identical entities, like projectiles or NPCs,
+    much cheaper.
+
+    Once a component changes to a value that differs from the template, it will be replicated
+    as usual. Components inserted from a template are still replicated on removal.
+
+    Should be registered on both client and server in the same order, since the first
+    registration also adds [`Template`] as a replicated component.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    const GOBLIN: Template = Template(0);
+
+    app.replicate::<Health>()
+        .replicate::<Speed>()
+        .register_template(
+            GOBLIN,
+            EntityTemplate::default().with(Health(50)).with(Speed(2.0)),
+        );
+
+    fn spawn_wave(mut commands: Commands) {
+        for index in 0..100 {
+            let mut goblin = commands.spawn((Replicated, GOBLIN));
+            if index % 10 == 0 {
+                // Only overridden value will be sent.
+                goblin.insert(Health(100));
+            }
+        }
+    }
+
+    #[derive(Component, Serialize, Deserialize, Clone, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component, Serialize, Deserialize, Clone, PartialEq)]
+    struct Speed(f32);
+    ```
+    **/
+    fn register_template(
+        &mut self,
+        template: Template,
+        entity_template: EntityTemplate,
+    ) -> &mut Self;
+}
+
+impl AppTemplateExt for App {
+    fn register_template(
+        &mut self,
+        template: Template,
+        entity_template: EntityTemplate,
+    ) -> &mut Self {
+        if !self.world().contains_resource::<EntityTemplates>() {
+            let templates = EntityTemplates {
+                #[cfg(feature = "server")]
+                component_id: self.world_mut().register_component::<Template>(),
+                templates: Default::default(),
+            };
+            self.insert_resource(templates)
+                .replicate::<Template>()
+                .add_observer(instantiate);
+        }
+
+        debug!(
+            "registering `{template:?}` with {} components",
+            entity_template.components.len()
+        );
+
+        let components = entity_template
+            .components
+            .into_iter()
+            .map(|component| ((component.register)(self.world_mut()), component))
+            .collect();
+
+        let mut templates = self.world_mut().resource_mut::<EntityTemplates>();
+        if templates.templates.insert(template, components).is_some() {
+            panic!("`{template:?}` can't be registered more than once");
+        }
+
+        self
+    }
+}
+
+/// Marks an entity as instantiated from a template registered with [`AppTemplateExt::register_template`].
+///
+/// Missing components from the template are inserted on both client and server.
+#[derive(Component, Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Template(pub u32);
+
+/// Component values for a [`Template`].
+///
+/// See [`AppTemplateExt::register_template`].
+#[derive(Default)]
+pub struct EntityTemplate {
+    components: Vec<TemplateComponent>,
+}
+
+impl EntityTemplate {
+    /// Adds a component value to the template.
+    ///
+    /// The value is compared on the server to decide if the component needs to be sent
+    /// and cloned on insertion.
+    pub fn with<C: Component + PartialEq + Clone>(mut self, component: C) -> Self {
+        let value = component.clone();
+        self.components.push(TemplateComponent {
+            register: World::register_component::<C>,
+            eq: Box::new(move |ptr: Ptr| {
+                // SAFETY: the pointer is obtained for the component with the ID registered for `C`.
+                let other = unsafe { ptr.deref::<C>() };
+                *other == value
+            }),
+            insert: Box::new(move |entity: &mut EntityCommands| {
+                entity.insert_if_new(component.clone());
+            }),
+        });
+        self
+    }
+}
+
+/// Type-erased component value of a template.
+struct TemplateComponent {
+    register: fn(&mut World) -> ComponentId,
+    eq: Box<dyn Fn(Ptr) -> bool + Send + Sync>,
+    insert: Box<dyn Fn(&mut EntityCommands) + Send + Sync>,
+}
+
+/// Registered templates.
+///
+/// Initialized on the first call of [`AppTemplateExt::register_template`].
+#[derive(Resource)]
+pub(crate) struct EntityTemplates {
+    #[cfg(feature = "server")]
+    component_id: ComponentId,
+    templates: HashMap<Template, Vec<(ComponentId, TemplateComponent)>>,
+}
+
+impl EntityTemplates {
+    /// Returns ID of the [`Template`] component.
+    #[cfg(feature = "server")]
+    pub(crate) fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Returns `true` if the template has the same value for the component.
+    ///
+    /// # Safety
+    ///
+    /// The pointer should point to the component with the specified ID.
+    #[cfg(feature = "server")]
+    pub(crate) unsafe fn matches(
+        &self,
+        template: Template,
+        component_id: ComponentId,
+        ptr: Ptr,
+    ) -> bool {
+        let Some(components) = self.templates.get(&template) else {
+            return false;
+        };
+
+        components
+            .iter()
+            .find(|&&(id, _)| id == component_id)
+            .is_some_and(|(_, component)| (component.eq)(ptr))
+    }
+}
+
+fn instantiate(
+    insert: On<Insert, Template>,
+    mut commands: Commands,
+    templates: Res<EntityTemplates>,
+    entities: Query<&Template>,
+) {
+    let Ok(&template) = entities.get(insert.entity) else {
+        return;
+    };
+    let Some(components) = templates.templates.get(&template) else {
+        error!(
+            "`{}` has unregistered `{template:?}`, ignoring instantiation",
+            insert.entity
+        );
+        return;
+    };
+
+    debug!("instantiating `{}` from `{template:?}`", insert.entity);
+    let mut entity = commands.entity(insert.entity);
+    for (_, component) in components {
+        (component.insert)(&mut entity);
+    }
+}
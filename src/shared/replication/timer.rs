@@ -0,0 +1,177 @@
+use core::{marker::PhantomData, time::Duration};
+
+use bevy::{ecs::component::Mutable, prelude::*};
+use bytes::Bytes;
+#[cfg(feature = "client")]
+use log::trace;
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{
+    registry::{
+        ctx::WriteCtx,
+        rule_fns::{self, DeserializeFn},
+    },
+    rules::filter::FilterRules,
+};
+use crate::prelude::*;
+
+/// Replication of timer-like components for [`App`].
+pub trait AppTimerExt {
+    /// Like [`AppRuleExt::replicate`], but compensates for the time the received
+    /// value spent in transit on the client.
+    ///
+    /// See [`TimerFns`] for more details.
+    fn replicate_timer<C>(&mut self, timer_fns: TimerFns<C>) -> &mut Self
+    where
+        C: Component<Mutability = Mutable> + Serialize + DeserializeOwned,
+    {
+        self.replicate_timer_filtered::<C, ()>(timer_fns)
+    }
+
+    /// Like [`Self::replicate_timer`], but also adds filters like [`AppRuleExt::replicate_filtered`].
+    fn replicate_timer_filtered<C, F: FilterRules>(&mut self, timer_fns: TimerFns<C>) -> &mut Self
+    where
+        C: Component<Mutability = Mutable> + Serialize + DeserializeOwned;
+}
+
+impl AppTimerExt for App {
+    fn replicate_timer_filtered<C, F: FilterRules>(&mut self, timer_fns: TimerFns<C>) -> &mut Self
+    where
+        C: Component<Mutability = Mutable> + Serialize + DeserializeOwned,
+    {
+        let rule_fns = RuleFns::new(rule_fns::default_serialize::<C>, deserialize_timer::<C>)
+            .with_consume(consume_timer::<C>);
+        self.replicate_with_filtered::<_, F>(rule_fns)
+            .insert_resource(timer_fns);
+
+        #[cfg(feature = "client")]
+        self.add_systems(
+            PreUpdate,
+            correct_timers::<C>
+                .after(ClientSystems::Receive)
+                .run_if(in_state(ClientState::Connected)),
+        );
+
+        self
+    }
+}
+
+/**
+Functions to correct received values of a timer-like component.
+
+A component like a cooldown or a match clock that ticks on both the server and the client
+is already stale when the client applies it. The value was captured on the server tick of
+the message, while the server kept ticking during transit and while the message was held,
+for example, by [`MutationJitterBuffer`](crate::client::MutationJitterBuffer).
+
+With [`AppTimerExt::replicate_timer`], the component is sent as usual, but after applying
+a received value, the client estimates the current server tick with [`TickTimeline`] and
+advances the component by the time passed since the message tick using [`Self::advance`].
+No correction is applied until the client has enough samples to estimate the tick duration.
+Local changes are not affected.
+
+# Examples
+
+```
+# use bevy::state::app::StatesPlugin;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+app.replicate_timer(TimerFns::new(|cooldown: &mut Cooldown, delta| {
+    cooldown.0.tick(delta);
+}));
+
+#[derive(Component, Serialize, Deserialize)]
+struct Cooldown(Timer);
+```
+*/
+#[derive(Resource)]
+pub struct TimerFns<C> {
+    /// Advances the component by the given duration.
+    ///
+    /// For [`Timer`] and [`Stopwatch`](bevy::time::Stopwatch) it's usually
+    /// just a call to `tick`, which also respects pausing.
+    pub advance: fn(&mut C, Duration),
+}
+
+impl<C> TimerFns<C> {
+    /// Creates a new instance with the given function for [`Self::advance`].
+    pub fn new(advance: fn(&mut C, Duration)) -> Self {
+        Self { advance }
+    }
+}
+
+impl<C> Clone for TimerFns<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for TimerFns<C> {}
+
+/// Tick of the last received value of a timer component.
+///
+/// Stored inside [`ReplicationStorage`] until the value is corrected.
+struct ReceivedTimer<C> {
+    tick: RepliconTick,
+    marker: PhantomData<C>,
+}
+
+/// Deserializes a timer component and remembers the message tick for correction.
+fn deserialize_timer<C: Component + DeserializeOwned>(
+    ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> Result<C> {
+    let component = rule_fns::default_deserialize::<C>(ctx, message)?;
+    ctx.insert(ReceivedTimer::<C> {
+        tick: ctx.message_tick,
+        marker: PhantomData,
+    });
+    Ok(component)
+}
+
+/// Consumes a timer component without scheduling a correction since it won't be applied.
+fn consume_timer<C: Component + DeserializeOwned>(
+    _deserialize: DeserializeFn<C>,
+    ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> Result<()> {
+    rule_fns::consume_as_deserialize(rule_fns::default_deserialize::<C>, ctx, message)
+}
+
+/// Advances timers received in this frame by the time passed on the server since their message tick.
+#[cfg(feature = "client")]
+fn correct_timers<C: Component<Mutability = Mutable>>(
+    timer_fns: Res<TimerFns<C>>,
+    time: Res<Time>,
+    timeline: Res<TickTimeline>,
+    mut storage: ResMut<ReplicationStorage>,
+    mut timers: Query<(Entity, &mut C), Changed<C>>,
+) {
+    let estimate = timeline
+        .tick_at(time.elapsed())
+        .zip(timeline.tick_duration());
+
+    for (entity, mut timer) in &mut timers {
+        let Some(received) = storage.remove::<ReceivedTimer<C>>(entity) else {
+            continue;
+        };
+        let Some(((tick, overstep), tick_duration)) = estimate else {
+            continue;
+        };
+        if tick.is_older(received.tick) {
+            continue;
+        }
+
+        let lag = tick_duration * (tick - received.tick) + tick_duration.mul_f32(overstep);
+        trace!(
+            "advancing `{}` on `{entity}` by {lag:?} received for `{:?}`",
+            ShortName::of::<C>(),
+            received.tick
+        );
+        (timer_fns.advance)(&mut timer, lag);
+    }
+}
@@ -0,0 +1,183 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use bevy::prelude::*;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+#[cfg(feature = "server")]
+use crate::server::visibility::{registry::FilterRegistry, topic::TopicIndex};
+#[cfg(feature = "server")]
+use crate::shared::replication::registry::ReplicationRegistry;
+
+/// Client-driven interest subscriptions for [`App`].
+pub trait AppTopicExt {
+    /// Same as [`Self::add_topic_with`], but allows any client to subscribe.
+    fn add_topic<S: FilterScope>(&mut self, name: &'static str) -> &mut Self {
+        self.add_topic_with::<S>(name, |_| true)
+    }
+
+    /**
+    Registers a named topic that clients can subscribe to.
+
+    Clients request interest in a topic by triggering [`Subscribe`] and stop it by triggering
+    [`Unsubscribe`]. On the server, entities are added to topics via
+    [`InTopics`](crate::server::visibility::topic::InTopics). Until a client subscribes
+    to a topic, the associated [`FilterScope`] of all entities in it is hidden from the client.
+    Topics of a client are stored in [`Subscriptions`](crate::server::visibility::topic::Subscriptions).
+
+    Requests are validated on the server: unknown topics are ignored, and for known topics,
+    `validate` is called with the client entity. Requests that fail the validation are ignored.
+
+    Topics need to be registered on both the client and server.
+    The first registration also registers [`Subscribe`] and [`Unsubscribe`] events,
+    so it should happen at the same point relative to other events.
+
+    Each topic occupies one visibility bit, see
+    [`AppVisibilityExt::add_visibility_filter`] for the limit.
+
+    # Panics
+
+    Panics if a topic with the same name is already registered.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    // Only admins can watch the whole map.
+    app.add_topic_with::<Entity>("map", |client| client.contains::<Admin>())
+        // Detailed stats are hidden until the client opens the inspector.
+        .add_topic::<SingleComponent<Stats>>("details");
+
+    fn spawn_unit(mut commands: Commands) {
+        commands.spawn((Replicated, Stats::default(), InTopics::new(["map", "details"])));
+    }
+
+    fn open_map(mut commands: Commands) {
+        commands.client_trigger(Subscribe::new("map"));
+    }
+
+    #[derive(Component)]
+    struct Admin;
+
+    #[derive(Component, Default)]
+    struct Stats {
+        // ...
+    }
+    ```
+    */
+    fn add_topic_with<S: FilterScope>(
+        &mut self,
+        name: &'static str,
+        validate: fn(EntityRef) -> bool,
+    ) -> &mut Self;
+}
+
+impl AppTopicExt for App {
+    fn add_topic_with<S: FilterScope>(
+        &mut self,
+        name: &'static str,
+        validate: fn(EntityRef) -> bool,
+    ) -> &mut Self {
+        debug!("adding topic `{name}`");
+        #[cfg(not(feature = "server"))]
+        let _ = validate;
+
+        if !self.world().contains_resource::<TopicRegistry>() {
+            self.init_resource::<TopicRegistry>()
+                .add_client_event::<Subscribe>(Channel::Ordered)
+                .add_client_event::<Unsubscribe>(Channel::Ordered);
+        }
+
+        let mut topics = self.world_mut().resource_mut::<TopicRegistry>();
+        if topics.index(name).is_some() {
+            panic!("topic `{name}` can't be registered more than once");
+        }
+        topics.names.push(name);
+
+        #[cfg(feature = "server")]
+        if self.world().contains_resource::<FilterRegistry>() {
+            let bit = self.world_mut().resource_scope(
+                |world, mut filter_registry: Mut<FilterRegistry>| {
+                    world.resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                        filter_registry.register_named_scope::<S>(
+                            world,
+                            &mut registry,
+                            ShortName(name),
+                        )
+                    })
+                },
+            );
+
+            if !self.world().contains_resource::<TopicIndex>() {
+                self.init_resource::<TopicIndex>();
+                crate::server::visibility::topic::add_observers(self);
+            }
+            self.world_mut()
+                .resource_mut::<TopicIndex>()
+                .register(bit, validate);
+        }
+
+        self
+    }
+}
+
+/// Names of topics registered via [`AppTopicExt`].
+///
+/// Topics are identified by their names.
+#[derive(Resource, Default)]
+pub struct TopicRegistry {
+    names: Vec<&'static str>,
+}
+
+impl TopicRegistry {
+    /// Returns the registration index of a topic.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|&topic| topic == name)
+    }
+
+    /// Returns an iterator over names of all registered topics.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.names.iter().copied()
+    }
+}
+
+/// A client event to request interest in a topic.
+///
+/// See [`AppTopicExt`] for details.
+#[derive(Event, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Subscribe {
+    /// Name of the topic.
+    pub topic: Cow<'static, str>,
+}
+
+impl Subscribe {
+    /// Creates a new instance for the given topic.
+    pub fn new(topic: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            topic: topic.into(),
+        }
+    }
+}
+
+/// A client event to stop interest in a topic.
+///
+/// See [`AppTopicExt`] for details.
+#[derive(Event, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Unsubscribe {
+    /// Name of the topic.
+    pub topic: Cow<'static, str>,
+}
+
+impl Unsubscribe {
+    /// Creates a new instance for the given topic.
+    pub fn new(topic: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            topic: topic.into(),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use core::{marker::PhantomData, time::Duration};
+
+use bevy::{
+    ecs::{
+        component::Mutable,
+        entity::{EntityMapper, MapEntities},
+    },
+    prelude::*,
+};
+use log::debug;
+#[cfg(any(feature = "client", feature = "server"))]
+use log::trace;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::prelude::*;
+#[cfg(feature = "server")]
+use crate::shared::backend::connected_client::NetworkIdMap;
+
+/// Client uploads of components for [`App`].
+pub trait AppUploadExt {
+    /**
+    Sends changes of `C` on entities possessed by the client to the server over the given channel.
+
+    The counterpart of replication rules for the client-to-server direction. On the client,
+    changes of `C` on entities with [`Possessed`] are sent as [`Upload`] messages after
+    [`Update`]. On the server, each upload is accepted only if the entity has [`PossessedBy`]
+    pointing to the sender and passes the validation from [`UploadRule::with_validation`].
+    Accepted values are written to the entity and marked with
+    [`EntityEchoExt::accept_client_write`](crate::server::client_writes::EntityEchoExt::accept_client_write),
+    so the regular replication re-broadcasts them to other clients.
+
+    Requires [`AppPossessionExt::replicate_possession`]. `C` should be replicated separately,
+    preferably with [`AppRuleExt::with_echo_suppression`] to avoid jitter on the uploading
+    client. Received uploads can also be read as [`FromClient<Upload<C>>`](FromClient)
+    messages for custom processing.
+
+    Should be called in the same order on both the client and the server, since it registers
+    a client message.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, state::app::StatesPlugin};
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    app.replicate_possession()
+        .replicate::<Aim>()
+        .with_echo_suppression(10);
+
+    app.upload::<Aim>(Channel::Unreliable)
+        .with_rate(30)
+        .with_validation(|_client, _current, aim| aim.0.is_normalized());
+
+    #[derive(Component, Deserialize, Serialize, Clone)]
+    struct Aim(Vec2);
+    ```
+    */
+    fn upload<C>(&mut self, channel: Channel) -> UploadRule<'_, C>
+    where
+        C: Component<Mutability = Mutable> + Clone + Serialize + DeserializeOwned;
+}
+
+impl AppUploadExt for App {
+    fn upload<C>(&mut self, channel: Channel) -> UploadRule<'_, C>
+    where
+        C: Component<Mutability = Mutable> + Clone + Serialize + DeserializeOwned,
+    {
+        debug!("registering upload of `{}`", ShortName::of::<C>());
+
+        self.init_resource::<UploadSettings<C>>()
+            .add_mapped_client_message::<Upload<C>>(channel);
+
+        #[cfg(feature = "client")]
+        self.add_systems(
+            PostUpdate,
+            send_uploads::<C>
+                .before(ClientSystems::Send)
+                .run_if(in_state(ClientState::Connected))
+                .run_if(upload_ready::<C>),
+        );
+
+        #[cfg(feature = "server")]
+        self.add_systems(
+            PreUpdate,
+            receive_uploads::<C>
+                .after(ServerSystems::Receive)
+                .run_if(in_state(ServerState::Running)),
+        );
+
+        UploadRule {
+            app: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Configures uploads of `C`.
+///
+/// Created by [`AppUploadExt::upload`].
+pub struct UploadRule<'a, C> {
+    app: &'a mut App,
+    marker: PhantomData<C>,
+}
+
+impl<C: Component> UploadRule<'_, C> {
+    /// Limits how many times per second changes are sent.
+    ///
+    /// Changes made between sends are accumulated, so only the latest value is sent.
+    /// Has no effect on the server.
+    ///
+    /// By default, changes are sent every frame.
+    pub fn with_rate(&mut self, rate: u32) -> &mut Self {
+        let interval = Duration::from_secs(1) / rate.max(1);
+        self.app
+            .world_mut()
+            .resource_mut::<UploadSettings<C>>()
+            .interval = Some(interval);
+        self
+    }
+
+    /// Sets a function to validate uploads on the server.
+    ///
+    /// The function receives the sender, the current value and the uploaded value.
+    /// Uploads for which it returns `false` are discarded. Has no effect on the client.
+    ///
+    /// By default, only possession is checked.
+    pub fn with_validation(&mut self, validate: ValidateUploadFn<C>) -> &mut Self {
+        self.app
+            .world_mut()
+            .resource_mut::<UploadSettings<C>>()
+            .validate = Some(validate);
+        self
+    }
+}
+
+/// Signature of the validation function for [`UploadRule::with_validation`].
+///
+/// Arguments are the client entity, the current value and the uploaded value.
+pub type ValidateUploadFn<C> = fn(Entity, &C, &C) -> bool;
+
+/// Message with the value of `C` sent by [`AppUploadExt::upload`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Upload<C> {
+    /// Entity on which the value changed.
+    pub entity: Entity,
+
+    /// New value.
+    pub component: C,
+}
+
+impl<C: Send + Sync + 'static> Message for Upload<C> {}
+
+impl<C> MapEntities for Upload<C> {
+    fn map_entities<M: EntityMapper>(&mut self, mapper: &mut M) {
+        self.entity = mapper.get_mapped(self.entity);
+    }
+}
+
+#[derive(Resource)]
+struct UploadSettings<C> {
+    interval: Option<Duration>,
+    validate: Option<ValidateUploadFn<C>>,
+}
+
+impl<C> Default for UploadSettings<C> {
+    fn default() -> Self {
+        Self {
+            interval: None,
+            validate: None,
+        }
+    }
+}
+
+/// Returns `true` if enough time has passed since the last send according to [`UploadRule::with_rate`].
+#[cfg(feature = "client")]
+fn upload_ready<C: Component>(
+    mut last_send: Local<Option<Duration>>,
+    time: Res<Time<Real>>,
+    settings: Res<UploadSettings<C>>,
+) -> bool {
+    let Some(interval) = settings.interval else {
+        return true;
+    };
+
+    let elapsed = time.elapsed();
+    if last_send.is_some_and(|last_send| elapsed - last_send < interval) {
+        return false;
+    }
+
+    *last_send = Some(elapsed);
+    true
+}
+
+#[cfg(feature = "client")]
+fn send_uploads<C: Component + Clone>(
+    mut uploads: MessageWriter<Upload<C>>,
+    components: Query<(Entity, &C), (Changed<C>, With<Possessed>, With<Remote>)>,
+) {
+    for (entity, component) in &components {
+        trace!("uploading `{}` for `{entity}`", ShortName::of::<C>());
+        uploads.write(Upload {
+            entity,
+            component: component.clone(),
+        });
+    }
+}
+
+#[cfg(feature = "server")]
+fn receive_uploads<C: Component<Mutability = Mutable> + Clone>(
+    mut commands: Commands,
+    mut uploads: MessageReader<FromClient<Upload<C>>>,
+    settings: Res<UploadSettings<C>>,
+    network_map: Res<NetworkIdMap>,
+    mut components: Query<(&mut C, &PossessedBy)>,
+) {
+    for upload in uploads.read() {
+        let Some(client) = upload.client_id.entity() else {
+            continue;
+        };
+        let Ok((mut component, possessed_by)) = components.get_mut(upload.entity) else {
+            debug!(
+                "ignoring upload of `{}` from `{client}` for `{}` without possession",
+                ShortName::of::<C>(),
+                upload.entity
+            );
+            continue;
+        };
+        if possessed_by.entity(&network_map) != Some(client) {
+            debug!(
+                "ignoring upload of `{}` from `{client}` for `{}` possessed by another client",
+                ShortName::of::<C>(),
+                upload.entity
+            );
+            continue;
+        }
+        if let Some(validate) = settings.validate
+            && !validate(client, &component, &upload.component)
+        {
+            debug!(
+                "rejecting upload of `{}` from `{client}` for `{}`",
+                ShortName::of::<C>(),
+                upload.entity
+            );
+            continue;
+        }
+
+        trace!(
+            "accepting upload of `{}` from `{client}` for `{}`",
+            ShortName::of::<C>(),
+            upload.entity
+        );
+        *component = upload.component.clone();
+        commands
+            .entity(upload.entity)
+            .accept_client_write::<C>(client);
+    }
+}
@@ -6,9 +6,60 @@ use crate::shared::replication::registry::{
     ReplicationRegistry, component_mask::ComponentMask, receive_fns::MutWrite,
 };
 
+/**
+Derives [`VisibilityFilter`] for the common cases.
+
+The client component is always `Self`. The visibility mode is required and selected with
+one of the attributes:
+
+- `#[visibility(equal)]` - visible if the client has an equal component.
+  Requires [`PartialEq`].
+- `#[visibility(bitmask)]` - visible if the client component has all bits of the entity
+  component. For tuple structs with a single field, the bits are taken from the field.
+  Otherwise, the type needs to implement [`Copy`], [`PartialEq`] and [`BitAnd`](core::ops::BitAnd).
+
+The scope is [`Entity`] by default and can be overridden with `#[visibility(scope(...))]`.
+A single component is wrapped into [`SingleComponent`], and multiple components are
+combined into a tuple.
+
+Like with the manual implementation, the component needs to be immutable, otherwise the
+derived implementation won't compile.
+
+# Examples
+
+```
+# use bevy::prelude::*;
+# use bevy_replicon::prelude::*;
+#[derive(Component, VisibilityFilter, PartialEq)]
+#[component(immutable)]
+#[visibility(equal)]
+struct Team(u8);
+
+#[derive(Component, VisibilityFilter)]
+#[component(immutable)]
+#[visibility(bitmask, scope(Health, Stats))]
+struct Layers(u32);
+
+#[derive(Component)]
+struct Health(u8);
+
+#[derive(Component)]
+struct Stats {
+// ...
+}
+```
+*/
+pub use bevy_replicon_macros::VisibilityFilter;
+
 /// Component that controls remote entity visibility.
 ///
 /// Should be registered via [`crate::server::visibility::AppVisibilityExt`].
+///
+/// For common cases, the trait can be derived, see [`derive@VisibilityFilter`].
+///
+/// When a client gains visibility, hidden components are sent as insertions with their current
+/// values. If they were also mutated in the same tick, the mutations are folded into the insertions
+/// and never sent separately. See [`InsertionStats`](crate::server::InsertionStats).
 pub trait VisibilityFilter: Component<Mutability = Immutable> {
     /**
     Component on the client entity that will be passed to [`Self::is_visible`].
@@ -0,0 +1,202 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Display, time::Duration};
+
+use bevy::prelude::*;
+use log::{debug, warn};
+
+use crate::shared::backend::DisconnectRequest;
+
+/// An error that occurred during replication or remote messaging.
+///
+/// Written at most [`ReplicationErrorSettings::max_per_second`] times per second for each
+/// [`ReplicationErrorKind`] in [`Last`]. Errors above the limit are only counted and reported
+/// as a single warning, which keeps logs usable even under attack.
+#[derive(Message, Debug, Clone)]
+pub struct ReplicationError {
+    /// Category of the error.
+    pub kind: ReplicationErrorKind,
+
+    /// Channel ID for which the error occurred, if any.
+    ///
+    /// Raw index of [`ServerChannelId`](crate::shared::backend::channels::ServerChannelId) on clients
+    /// and [`ClientChannelId`](crate::shared::backend::channels::ClientChannelId) on the server.
+    /// See [`RepliconChannels`](crate::shared::backend::channels::RepliconChannels) for details.
+    pub channel_id: Option<usize>,
+
+    /// Client entity on the server that sent the data, if any.
+    pub client: Option<Entity>,
+
+    /// Error description.
+    pub error: String,
+}
+
+/// Category of [`ReplicationError`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ReplicationErrorKind {
+    /// A message failed to serialize before sending.
+    Serialize,
+    /// Received data failed to deserialize.
+    Deserialize,
+    /// A received replication message couldn't be applied.
+    Apply,
+}
+
+impl ReplicationErrorKind {
+    const ALL: [Self; 3] = [Self::Serialize, Self::Deserialize, Self::Apply];
+
+    /// Returns `true` if the error is caused by received data.
+    fn is_received(self) -> bool {
+        self != Self::Serialize
+    }
+}
+
+/// Limits for [`ReplicationError`].
+///
+/// Can be modified at runtime.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ReplicationErrorSettings {
+    /// Maximum number of reported errors per second for each [`ReplicationErrorKind`].
+    ///
+    /// If [`Time<Real>`] is not available, the limit applies per app update.
+    ///
+    /// By default set to 10.
+    pub max_per_second: u32,
+
+    /// What to do with a client on the server when data received from it causes an error.
+    ///
+    /// Applied to every error, including suppressed ones.
+    ///
+    /// By default set to [`ClientErrorPolicy::Discard`].
+    pub client_policy: ClientErrorPolicy,
+}
+
+impl Default for ReplicationErrorSettings {
+    fn default() -> Self {
+        Self {
+            max_per_second: 10,
+            client_policy: Default::default(),
+        }
+    }
+}
+
+/// Action for [`ReplicationErrorSettings::client_policy`].
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ClientErrorPolicy {
+    /// Discard the data and keep the client connected.
+    ///
+    /// Suitable for development or when clients are trusted.
+    #[default]
+    Discard,
+    /// Discard the data and write [`DisconnectRequest`] for the client.
+    ///
+    /// Useful in production, since a client that sends invalid data is either outdated or malicious.
+    Disconnect,
+}
+
+/// Collects and rate-limits errors until they are written as [`ReplicationError`] messages.
+#[derive(Resource)]
+pub(crate) struct ErrorReporter {
+    max_per_second: u32,
+    window_start: Duration,
+    reported: [u32; ReplicationErrorKind::ALL.len()],
+    suppressed: [u32; ReplicationErrorKind::ALL.len()],
+    pending: Vec<ReplicationError>,
+
+    /// Clients that sent data which caused an error since the last [`write_errors`].
+    ///
+    /// Collected regardless of the limit to apply [`ReplicationErrorSettings::client_policy`].
+    offenders: Vec<Entity>,
+}
+
+impl ErrorReporter {
+    /// Reports an error if the limit for its kind hasn't been reached.
+    ///
+    /// Returns `true` if the error was accepted and should be logged by the caller.
+    pub(crate) fn report(
+        &mut self,
+        kind: ReplicationErrorKind,
+        channel_id: Option<usize>,
+        client: Option<Entity>,
+        error: impl Display,
+    ) -> bool {
+        if let Some(client) = client
+            && kind.is_received()
+            && !self.offenders.contains(&client)
+        {
+            self.offenders.push(client);
+        }
+
+        let index = kind as usize;
+        if self.reported[index] >= self.max_per_second {
+            self.suppressed[index] += 1;
+            return false;
+        }
+
+        self.reported[index] += 1;
+        self.pending.push(ReplicationError {
+            kind,
+            channel_id,
+            client,
+            error: error.to_string(),
+        });
+
+        true
+    }
+
+    /// Starts a new window if a second has passed since the last one.
+    ///
+    /// Always starts a new window if `elapsed` is [`None`].
+    fn advance(&mut self, elapsed: Option<Duration>, max_per_second: u32) {
+        self.max_per_second = max_per_second;
+        if let Some(elapsed) = elapsed
+            && elapsed.saturating_sub(self.window_start) < Duration::from_secs(1)
+        {
+            return;
+        }
+
+        self.window_start = elapsed.unwrap_or_default();
+        for (kind, suppressed) in ReplicationErrorKind::ALL.iter().zip(&mut self.suppressed) {
+            if *suppressed != 0 {
+                warn!("suppressed {suppressed} errors of kind `{kind:?}`");
+                *suppressed = 0;
+            }
+        }
+        self.reported = Default::default();
+    }
+}
+
+impl Default for ErrorReporter {
+    fn default() -> Self {
+        Self {
+            max_per_second: ReplicationErrorSettings::default().max_per_second,
+            window_start: Default::default(),
+            reported: Default::default(),
+            suppressed: Default::default(),
+            pending: Default::default(),
+            offenders: Default::default(),
+        }
+    }
+}
+
+/// Writes pending errors as messages, applies [`ReplicationErrorSettings::client_policy`]
+/// and advances the rate limit window.
+pub(crate) fn write_errors(
+    mut reporter: ResMut<ErrorReporter>,
+    mut errors: MessageWriter<ReplicationError>,
+    mut disconnects: MessageWriter<DisconnectRequest>,
+    settings: Res<ReplicationErrorSettings>,
+    time: Option<Res<Time<Real>>>,
+) {
+    errors.write_batch(reporter.pending.drain(..));
+    if settings.client_policy == ClientErrorPolicy::Disconnect {
+        for &client in &reporter.offenders {
+            debug!("disconnecting client `{client}` that sent invalid data");
+            disconnects.write(DisconnectRequest { client });
+        }
+    }
+    reporter.offenders.clear();
+    reporter.advance(time.map(|time| time.elapsed()), settings.max_per_second);
+}
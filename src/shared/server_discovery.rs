@@ -0,0 +1,234 @@
+//! Backend-agnostic queries for server lists.
+//!
+//! See [`ServerDiscoveryPlugin`] for details.
+
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+use bevy::prelude::*;
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    postcard_utils,
+    prelude::*,
+    shared::backend::unconnected_messages::{UnconnectedMessages, UnconnectedPeer},
+};
+
+/// Answers pings from peers without a connection to populate server lists.
+///
+/// Pings are sent by triggering [`PingServer`] on the client. The server answers only if it's
+/// [`ServerState::Running`] and has the [`ServerInfo`] resource. Each answer is triggered on the client
+/// as [`ServerDiscovered`]. No [`ConnectedClient`] entities are spawned for the pinging peers.
+///
+/// Requires the messaging backend to support [`UnconnectedMessages`].
+/// If the backend doesn't support them, pings are never answered.
+///
+/// Not included in [`RepliconPlugins`] and needs to be added manually on both client and server.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::{prelude::*, state::app::StatesPlugin};
+/// use bevy_replicon::{
+///     prelude::*, shared::backend::unconnected_messages::UnconnectedPeer,
+/// };
+///
+/// # let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     StatesPlugin,
+///     RepliconPlugins,
+///     ServerDiscoveryPlugin,
+/// ))
+/// .insert_resource(ServerInfo {
+///     name: "My server".into(),
+///     ..Default::default()
+/// })
+/// .add_observer(list_server)
+/// .add_systems(Startup, ping_server);
+///
+/// fn ping_server(mut commands: Commands) {
+///     // Peer identifiers are provided by the messaging backend.
+///     commands.trigger(PingServer {
+///         peer: UnconnectedPeer::new(0),
+///     });
+/// }
+///
+/// fn list_server(discovered: On<ServerDiscovered>, protocol: Res<ProtocolHash>) {
+///     if discovered.protocol == *protocol {
+///         info!(
+///             "found \"{}\" with {} players and {:?} ping",
+///             discovered.info.name, discovered.players, discovered.rtt
+///         );
+///     }
+/// }
+/// ```
+pub struct ServerDiscoveryPlugin;
+
+impl Plugin for ServerDiscoveryPlugin {
+    fn build(&self, app: &mut App) {
+        let receive = receive_unconnected.into_configs();
+        #[cfg(feature = "server")]
+        let receive = receive.in_set(ServerSystems::Receive);
+        #[cfg(feature = "client")]
+        let receive = receive.in_set(ClientSystems::Receive);
+
+        app.init_resource::<UnconnectedMessages>()
+            .add_observer(send_ping)
+            .add_systems(PreUpdate, receive);
+    }
+}
+
+fn send_ping(
+    ping: On<PingServer>,
+    mut messages: ResMut<UnconnectedMessages>,
+    time: Option<Res<Time<Real>>>,
+) {
+    let packet = DiscoveryPacket::Ping {
+        timestamp: elapsed(time.as_deref()),
+    };
+    let mut message = Vec::new();
+    postcard_utils::to_extend_mut(&packet, &mut message)
+        .expect("ping should always be serializable");
+
+    debug!("pinging `{:?}`", ping.peer);
+    messages.send(ping.peer, message);
+}
+
+fn receive_unconnected(
+    mut commands: Commands,
+    mut messages: ResMut<UnconnectedMessages>,
+    server_state: Res<State<ServerState>>,
+    info: Option<Res<ServerInfo>>,
+    protocol: Res<ProtocolHash>,
+    clients: Query<(), With<ConnectedClient>>,
+    time: Option<Res<Time<Real>>>,
+) {
+    let mut answers = Vec::new();
+    for (peer, mut message) in messages.drain_received() {
+        let packet = match postcard_utils::from_buf(&mut message) {
+            Ok(packet) => packet,
+            Err(e) => {
+                debug!("ignoring invalid unconnected message from `{peer:?}`: {e}");
+                continue;
+            }
+        };
+
+        match packet {
+            DiscoveryPacket::Ping { timestamp } => {
+                let Some(info) = &info else {
+                    trace!("ignoring ping from `{peer:?}` without server info");
+                    continue;
+                };
+                if *server_state != ServerState::Running {
+                    trace!("ignoring ping from `{peer:?}` while not running");
+                    continue;
+                }
+
+                trace!("answering ping from `{peer:?}`");
+                let packet = DiscoveryPacket::Pong {
+                    timestamp,
+                    info: ServerInfo::clone(info),
+                    players: clients.iter().count(),
+                    protocol: *protocol,
+                };
+                let mut message = Vec::new();
+                match postcard_utils::to_extend_mut(&packet, &mut message) {
+                    Ok(()) => answers.push((peer, message)),
+                    Err(e) => debug!("unable to serialize answer for `{peer:?}`: {e}"),
+                }
+            }
+            DiscoveryPacket::Pong {
+                timestamp,
+                info,
+                players,
+                protocol,
+            } => {
+                let rtt = elapsed(time.as_deref()).saturating_sub(timestamp);
+                debug!("discovered `{peer:?}` with `{protocol:?}`");
+                commands.trigger(ServerDiscovered {
+                    peer,
+                    info,
+                    players,
+                    protocol,
+                    rtt,
+                });
+            }
+        }
+    }
+
+    for (peer, message) in answers {
+        messages.send(peer, message);
+    }
+}
+
+fn elapsed(time: Option<&Time<Real>>) -> Duration {
+    time.map(Time::elapsed).unwrap_or_default()
+}
+
+/// Information about the server sent in answers to [`PingServer`].
+///
+/// Insert it on the server to let [`ServerDiscoveryPlugin`] answer pings.
+/// Can be modified at runtime, for example, to update the current map in [`Self::payload`].
+///
+/// Since the answer is sent to peers without a connection, keep it small to avoid fragmentation
+/// and to make the server less attractive for traffic amplification.
+#[derive(Resource, Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ServerInfo {
+    /// Displayed name of the server.
+    pub name: String,
+
+    /// Maximum number of players, if limited.
+    pub max_players: Option<usize>,
+
+    /// Custom game-specific data, such as the current map or game mode.
+    pub payload: Vec<u8>,
+}
+
+/// An event that sends a ping to a server without a connection.
+///
+/// Answers are triggered as [`ServerDiscovered`].
+///
+/// See [`ServerDiscoveryPlugin`] for details.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PingServer {
+    /// Backend-specific address of the server.
+    pub peer: UnconnectedPeer,
+}
+
+/// An event triggered when a server answers [`PingServer`].
+#[derive(Event, Debug, Clone)]
+pub struct ServerDiscovered {
+    /// Backend-specific address of the server.
+    pub peer: UnconnectedPeer,
+
+    /// Information provided by the server.
+    pub info: ServerInfo,
+
+    /// Number of connected clients.
+    pub players: usize,
+
+    /// Protocol of the server.
+    ///
+    /// Compare it with the local [`ProtocolHash`] to check if the server is compatible.
+    pub protocol: ProtocolHash,
+
+    /// Time between sending the ping and receiving the answer.
+    ///
+    /// Measured with [`Time<Real>`]. If it's not available, will be zero.
+    pub rtt: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+enum DiscoveryPacket {
+    Ping {
+        timestamp: Duration,
+    },
+    Pong {
+        timestamp: Duration,
+        info: ServerInfo,
+        players: usize,
+        protocol: ProtocolHash,
+    },
+}
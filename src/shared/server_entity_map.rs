@@ -19,7 +19,11 @@ pub struct ServerEntityMap {
 
 impl ServerEntityMap {
     /// Inserts a server-client pair into the map.
-    pub(crate) fn insert(&mut self, server_entity: Entity, client_entity: Entity) {
+    ///
+    /// The map is updated automatically on replication receive, but manual insertion
+    /// can be used to keep existing client entities after reconnecting to a different server.
+    /// See [`HostSnapshot`](crate::server::host_migration::HostSnapshot) for details.
+    pub fn insert(&mut self, server_entity: Entity, client_entity: Entity) {
         if let Some(existing_entity) = self.server_to_client.insert(server_entity, client_entity) {
             if client_entity != existing_entity {
                 error!(
@@ -0,0 +1,167 @@
+use alloc::collections::VecDeque;
+use core::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Observed mapping between server ticks and local time.
+///
+/// On the server it records every [`ServerTick`](crate::server::server_tick::ServerTick)
+/// change in [`ServerSystems::Send`](crate::prelude::ServerSystems::Send). On the client it records
+/// the newest tick from received replication messages in
+/// [`ClientSystems::Receive`](crate::prelude::ClientSystems::Receive). Time is taken from [`Time`]
+/// at the moment of recording and ticks older than the latest recorded are ignored.
+///
+/// Use it to convert between ticks and durations instead of deriving the tick rate from
+/// configuration. For example, to interpolate between received states or to estimate the
+/// current server tick on a client.
+///
+/// Only the last [`Self::MAX_SAMPLES`] samples are used for estimation.
+/// Cleared on disconnect and when the server stops.
+///
+/// # Fixed timestep
+///
+/// By default [`ServerTick`](crate::server::server_tick::ServerTick) is incremented in
+/// [`FixedPostUpdate`], so each tick corresponds to a single [`FixedUpdate`] run.
+/// Use [`Self::fixed_steps_per_tick`] to verify it or convert ticks into fixed step counts
+/// when the tick is incremented at a different rate.
+///
+/// # Examples
+///
+/// Estimate the current server tick on a client.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::prelude::*;
+///
+/// fn estimate_tick(timeline: Res<TickTimeline>, time: Res<Time>) {
+///     if let Some((tick, overstep)) = timeline.tick_at(time.elapsed()) {
+///         info!("server is at `{tick:?}` with overstep {overstep}");
+///     }
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Clone)]
+pub struct TickTimeline {
+    samples: VecDeque<(RepliconTick, Duration)>,
+}
+
+impl TickTimeline {
+    /// Maximum number of stored samples.
+    pub const MAX_SAMPLES: usize = 64;
+
+    /// Records that `tick` was observed at the given time.
+    ///
+    /// Ignored if `tick` isn't newer than the latest recorded tick.
+    pub(crate) fn record(&mut self, tick: RepliconTick, elapsed: Duration) {
+        if let Some((last_tick, _)) = self.latest()
+            && tick.is_older_or_eq(last_tick)
+        {
+            return;
+        }
+
+        if self.samples.len() == Self::MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((tick, elapsed));
+    }
+
+    /// Returns the latest recorded tick and the time at which it was observed.
+    pub fn latest(&self) -> Option<(RepliconTick, Duration)> {
+        self.samples.back().copied()
+    }
+
+    /// Returns the average duration of a single tick.
+    ///
+    /// Returns [`None`] if less than 2 ticks were recorded or the average rounds down to zero,
+    /// for example when they were observed at the same time.
+    pub fn tick_duration(&self) -> Option<Duration> {
+        let &(first_tick, first_time) = self.samples.front()?;
+        let &(last_tick, last_time) = self.samples.back()?;
+        let elapsed = last_time.checked_sub(first_time)?;
+        let duration = elapsed.checked_div(last_tick - first_tick)?;
+        if duration.is_zero() {
+            return None;
+        }
+
+        Some(duration)
+    }
+
+    /// Returns the average number of ticks per second.
+    ///
+    /// See also [`Self::tick_duration`].
+    pub fn tick_rate(&self) -> Option<f64> {
+        self.tick_duration()
+            .map(|duration| 1.0 / duration.as_secs_f64())
+    }
+
+    /// Converts a number of ticks into duration.
+    ///
+    /// See also [`Self::tick_duration`].
+    pub fn ticks_to_duration(&self, ticks: u32) -> Option<Duration> {
+        self.tick_duration().map(|duration| duration * ticks)
+    }
+
+    /// Converts a duration into a fractional number of ticks.
+    ///
+    /// See also [`Self::tick_duration`].
+    pub fn duration_to_ticks(&self, duration: Duration) -> Option<f64> {
+        self.tick_duration()
+            .map(|tick_duration| duration.as_nanos() as f64 / tick_duration.as_nanos() as f64)
+    }
+
+    /// Estimates the tick at the given time.
+    ///
+    /// Extrapolates from the latest recorded tick. Returns the tick and the elapsed
+    /// fraction of the next tick in the range `[0, 1)`.
+    ///
+    /// Pass [`Time::elapsed`] to get the estimated current tick.
+    pub fn tick_at(&self, elapsed: Duration) -> Option<(RepliconTick, f32)> {
+        let (last_tick, last_time) = self.latest()?;
+        let tick_nanos = self.tick_duration()?.as_nanos();
+        if elapsed >= last_time {
+            let nanos = (elapsed - last_time).as_nanos();
+            let ticks = (nanos / tick_nanos) as u32;
+            let overstep = (nanos % tick_nanos) as f32 / tick_nanos as f32;
+            Some((last_tick + ticks, overstep))
+        } else {
+            let nanos = (last_time - elapsed).as_nanos();
+            let ticks = (nanos / tick_nanos) as u32;
+            let remainder = nanos % tick_nanos;
+            if remainder == 0 {
+                Some((last_tick - ticks, 0.0))
+            } else {
+                let overstep = 1.0 - remainder as f32 / tick_nanos as f32;
+                Some((last_tick - (ticks + 1), overstep))
+            }
+        }
+    }
+
+    /// Returns the average number of [`FixedUpdate`] runs per tick.
+    ///
+    /// Equals 1 if the tick is incremented in a fixed schedule on each run.
+    pub fn fixed_steps_per_tick(&self, fixed: &Time<Fixed>) -> Option<f64> {
+        self.tick_duration()
+            .map(|duration| duration.as_secs_f64() / fixed.timestep().as_secs_f64())
+    }
+
+    /// Removes all recorded samples.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sub_nanosecond_ticks() {
+        let mut timeline = TickTimeline::default();
+        timeline.record(RepliconTick::new(0), Duration::ZERO);
+        timeline.record(RepliconTick::new(10), Duration::from_nanos(5));
+
+        assert_eq!(timeline.tick_duration(), None);
+        assert_eq!(timeline.tick_at(Duration::from_nanos(10)), None);
+    }
+}
@@ -1,6 +1,10 @@
+pub mod capture;
+pub mod scenario;
+
 use bevy::prelude::*;
 
 use crate::prelude::*;
+use capture::CapturedMessage;
 
 /**
 Extension for [`App`] to communicate with other instances like it's a server.
@@ -77,6 +81,20 @@ pub trait ServerTestAppExt {
     ///
     /// Panics if a client app hasn't been connected before.
     fn exchange_with_client(&mut self, client_app: &mut App);
+
+    /// Decodes replication messages sent to a client app that weren't exchanged yet.
+    ///
+    /// Call it after updating [`self`] and before [`Self::exchange_with_client`] to assert
+    /// exactly what gets sent for a given world change. Messages are left untouched.
+    ///
+    /// Entities inside the returned messages are server entities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a client app hasn't been connected before or if messages can't be decoded,
+    /// for example, when [`MessageTransforms`] are inserted for the client or component changes
+    /// are sent without [`AppRuleExt::prefix_component_sizes`].
+    fn capture_replication(&self, client_app: &App) -> Vec<CapturedMessage>;
 }
 
 impl ServerTestAppExt for App {
@@ -151,6 +169,11 @@ impl ServerTestAppExt for App {
             }
         })
     }
+
+    fn capture_replication(&self, client_app: &App) -> Vec<CapturedMessage> {
+        let client_entity = **client_app.world().resource::<TestClientEntity>();
+        capture::capture(self.world(), client_entity)
+    }
 }
 
 /// Stores connected client entity from server on client.
@@ -0,0 +1,297 @@
+//! Decoded replication messages for golden tests.
+//!
+//! See [`ServerTestAppExt::capture_replication`](super::ServerTestAppExt::capture_replication).
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use bevy::prelude::*;
+use bytes::{Buf, Bytes};
+
+use crate::{
+    postcard_utils,
+    prelude::*,
+    shared::{
+        backend::channels::ServerChannel,
+        replication::{
+            message_flags::{MutateFlags, UpdateFlags},
+            mutate_index::MutateIndex,
+            registry::{self, FnsId, ReplicationRegistry, removal_id::RemovalId},
+        },
+    },
+};
+
+/// Replication message sent to a client, decoded from the wire format.
+///
+/// See [`replication_messages`](crate::server::replication_messages) for details about each field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapturedMessage {
+    /// Message sent over [`ServerChannel::Updates`].
+    Update(CapturedUpdate),
+    /// Message sent over a [mutation channel](crate::shared::backend::channels::RepliconChannels::mutation_channel).
+    Mutate(CapturedMutate),
+}
+
+/// Decoded update message.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CapturedUpdate {
+    /// Server tick of the message.
+    pub tick: RepliconTick,
+    /// Whether the server requested an acknowledgment.
+    pub ack: bool,
+    /// User-defined bytes.
+    pub userdata: Option<Bytes>,
+    /// Server entities with signature hashes.
+    pub mappings: Vec<(Entity, u64)>,
+    /// Despawned server entities.
+    pub despawns: Vec<Entity>,
+    /// Removed components.
+    pub removals: Vec<CapturedRemovals>,
+    /// Inserted or mutated components.
+    pub changes: Vec<CapturedChanges>,
+}
+
+/// Decoded mutate message.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CapturedMutate {
+    /// Channel over which the message was sent.
+    pub channel: MutationChannel,
+    /// Tick of the update message required to apply this message.
+    pub update_tick: RepliconTick,
+    /// Server tick of the message.
+    pub tick: RepliconTick,
+    /// User-defined bytes.
+    pub userdata: Option<Bytes>,
+    /// Number of mutate messages sent for this tick.
+    pub messages_count: Option<usize>,
+    /// Mutated components.
+    pub mutations: Vec<CapturedChanges>,
+}
+
+/// Removed components for a server entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedRemovals {
+    /// Server entity.
+    pub entity: Entity,
+    /// Names of removed components.
+    pub components: Vec<String>,
+}
+
+/// Changed components for a server entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedChanges {
+    /// Server entity.
+    pub entity: Entity,
+    /// Changed components in the order they were written.
+    pub components: Vec<CapturedComponent>,
+}
+
+/// Component data written by the serialization function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedComponent {
+    /// Name of the component.
+    pub name: String,
+    /// Serialized value.
+    pub bytes: Bytes,
+}
+
+/// Decodes all replication messages sent to the client and not yet drained.
+pub(super) fn capture(world: &World, client: Entity) -> Vec<CapturedMessage> {
+    let messages = world.resource::<ServerMessages>();
+    let channels = world.resource::<RepliconChannels>();
+    let decoder = Decoder {
+        world,
+        registry: world.resource::<ReplicationRegistry>(),
+    };
+
+    messages
+        .iter_sent()
+        .filter(|&(entity, ..)| entity == client)
+        .filter_map(|(_, channel_id, message)| {
+            let mut message = message.clone();
+            if channel_id == ServerChannel::Updates.into() {
+                let update = decoder
+                    .update(&mut message)
+                    .unwrap_or_else(|e| panic!("update message should be valid: {e}"));
+                Some(CapturedMessage::Update(update))
+            } else {
+                let channel = MutationChannel::ALL
+                    .into_iter()
+                    .find(|&channel| channels.mutation_channel(channel) == Some(channel_id))?;
+                let mut mutate = decoder
+                    .mutate(&mut message)
+                    .unwrap_or_else(|e| panic!("mutate message should be valid: {e}"));
+                mutate.channel = channel;
+                Some(CapturedMessage::Mutate(mutate))
+            }
+        })
+        .collect()
+}
+
+struct Decoder<'a> {
+    world: &'a World,
+    registry: &'a ReplicationRegistry,
+}
+
+impl Decoder<'_> {
+    fn update(&self, message: &mut Bytes) -> Result<CapturedUpdate> {
+        let flags: UpdateFlags = postcard_utils::from_buf(message)?;
+        if flags.contains_unknown_bits() {
+            return Err(format!("unknown update flags `{flags:?}`").into());
+        }
+
+        let mut update = CapturedUpdate {
+            tick: postcard_utils::from_buf(message)?,
+            ..Default::default()
+        };
+
+        let last_flag = flags.last();
+        for (_, flag) in flags.iter_names() {
+            let sized = flag != last_flag;
+            match flag {
+                UpdateFlags::ACK => update.ack = true,
+                UpdateFlags::USERDATA => update.userdata = Some(split_data(message)?),
+                UpdateFlags::MAPPINGS => {
+                    update.mappings = read_array(sized, message, |message| {
+                        let entity = postcard_utils::entity_from_buf(message)?;
+                        let hash = u64::from_le_bytes(postcard_utils::from_buf(message)?);
+                        Ok((entity, hash))
+                    })?;
+                }
+                UpdateFlags::DESPAWNS => {
+                    update.despawns = read_array(sized, message, |message| {
+                        Ok(postcard_utils::entity_from_buf(message)?)
+                    })?;
+                }
+                UpdateFlags::REMOVALS => {
+                    update.removals = read_array(sized, message, |message| {
+                        let entity = postcard_utils::entity_from_buf(message)?;
+                        let mut data = split_data(message)?;
+                        let removal_ids = read_array(false, &mut data, |data| {
+                            let removal_id: RemovalId = postcard_utils::from_buf(data)?;
+                            Ok(removal_id)
+                        })?;
+                        let components = removal_ids
+                            .into_iter()
+                            .flat_map(RemovalId::fns_ids)
+                            .map(|fns_id| self.name(fns_id))
+                            .collect::<Result<_>>()?;
+                        Ok(CapturedRemovals { entity, components })
+                    })?;
+                }
+                UpdateFlags::CHANGES => {
+                    update.changes = read_array(sized, message, |message| self.changes(message))?;
+                }
+                _ => unreachable!("iteration should yield only named flags"),
+            }
+        }
+
+        Ok(update)
+    }
+
+    fn mutate(&self, message: &mut Bytes) -> Result<CapturedMutate> {
+        let flags: MutateFlags = postcard_utils::from_buf(message)?;
+        if flags.contains_unknown_bits() {
+            return Err(format!("unknown mutate flags `{flags:?}`").into());
+        }
+
+        let _: MutateIndex = postcard_utils::from_buf(message)?;
+        let mut mutate = CapturedMutate {
+            update_tick: postcard_utils::from_buf(message)?,
+            tick: postcard_utils::from_buf(message)?,
+            ..Default::default()
+        };
+
+        for (_, flag) in flags.iter_names() {
+            match flag {
+                MutateFlags::USERDATA => mutate.userdata = Some(split_data(message)?),
+                MutateFlags::MESSAGES_COUNT => {
+                    mutate.messages_count = Some(postcard_utils::from_buf(message)?);
+                }
+                MutateFlags::MUTATIONS => {
+                    mutate.mutations = read_array(false, message, |message| self.changes(message))?;
+                }
+                _ => unreachable!("iteration should yield only named flags"),
+            }
+        }
+
+        Ok(mutate)
+    }
+
+    fn changes(&self, message: &mut Bytes) -> Result<CapturedChanges> {
+        let entity = postcard_utils::entity_from_buf(message)?;
+        let mut data = split_data(message)?;
+        let components = read_array(false, &mut data, |data| {
+            let fns_id = postcard_utils::from_buf(data)?;
+            let name = self.name(fns_id)?;
+            if !self.registry.is_size_prefixed(fns_id) {
+                return Err(format!(
+                    "`{name}` data isn't prefixed with its size, enable `AppRuleExt::prefix_component_sizes`"
+                )
+                .into());
+            }
+            let size = registry::read_component_size(data)
+                .ok_or_else(|| format!("missing size for `{name}` data"))?;
+            let bytes = split_sized(data, size)?;
+            Ok(CapturedComponent { name, bytes })
+        })?;
+
+        Ok(CapturedChanges { entity, components })
+    }
+
+    fn name(&self, fns_id: FnsId) -> Result<String> {
+        let (_, component_id, _) = self
+            .registry
+            .try_get(fns_id)
+            .ok_or_else(|| format!("unknown `{fns_id:?}`"))?;
+        let name = self
+            .world
+            .components()
+            .get_name(component_id)
+            .ok_or_else(|| format!("unknown `{component_id:?}` for `{fns_id:?}`"))?;
+
+        Ok(name.to_string())
+    }
+}
+
+/// Reads an array prefixed with its length if `sized`, or until the end of the message otherwise.
+fn read_array<T>(
+    sized: bool,
+    message: &mut Bytes,
+    mut f: impl FnMut(&mut Bytes) -> Result<T>,
+) -> Result<Vec<T>> {
+    let mut array = Vec::new();
+    if sized {
+        let len: usize = postcard_utils::from_buf(message)?;
+        for _ in 0..len {
+            array.push((f)(message)?);
+        }
+    } else {
+        while message.has_remaining() {
+            array.push((f)(message)?);
+        }
+    }
+
+    Ok(array)
+}
+
+/// Reads data prefixed with its size.
+fn split_data(message: &mut Bytes) -> Result<Bytes> {
+    let size = postcard_utils::from_buf(message)?;
+    split_sized(message, size)
+}
+
+/// Splits off the next `size` bytes from the message.
+fn split_sized(message: &mut Bytes, size: usize) -> Result<Bytes> {
+    if size > message.len() {
+        return Err(format!(
+            "data size ({size}) exceeds remaining message length ({})",
+            message.len()
+        )
+        .into());
+    }
+
+    Ok(message.split_to(size))
+}
@@ -0,0 +1,272 @@
+//! Builder-style DSL for replication test scenarios.
+//!
+//! See [`Scenario`] for details.
+
+use alloc::vec::Vec;
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+
+use super::ServerTestAppExt;
+use crate::{prelude::*, shared::server_entity_map::ServerEntityMap};
+
+/**
+Server and client apps driven by a chain of steps.
+
+Replaces the update and exchange boilerplate of [`ServerTestAppExt`] with short steps.
+Steps panic on failed expectations, so the scenario can be used directly in tests.
+
+Both apps are created with [`MinimalPlugins`], [`StatesPlugin`] and [`RepliconPlugins`]
+with a server tick on each update. Use [`Self::setup`] to register replication rules,
+messages, etc. The apps are finished and connected on the first step.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::scenario::Scenario};
+use serde::{Deserialize, Serialize};
+
+Scenario::new()
+    .setup(|app| {
+        app.replicate::<A>().replicate::<B>();
+    })
+    .spawn_on_server((A, B))
+    .tick()
+    .expect_client_has::<A>()
+    .disconnect()
+    .reconnect()
+    .expect_resync();
+
+#[derive(Component, Serialize, Deserialize)]
+struct A;
+
+#[derive(Component, Serialize, Deserialize)]
+struct B;
+```
+*/
+pub struct Scenario {
+    server_app: App,
+    client_app: App,
+    connected: bool,
+    finished: bool,
+}
+
+impl Scenario {
+    /// Creates server and client apps without connecting them.
+    pub fn new() -> Self {
+        let mut server_app = App::new();
+        let mut client_app = App::new();
+        for app in [&mut server_app, &mut client_app] {
+            app.add_plugins((
+                MinimalPlugins,
+                StatesPlugin,
+                RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            ));
+        }
+
+        Self {
+            server_app,
+            client_app,
+            connected: false,
+            finished: false,
+        }
+    }
+
+    /// Calls a function on both apps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after any step that connects the apps.
+    #[must_use]
+    pub fn setup(mut self, f: impl Fn(&mut App)) -> Self {
+        assert!(!self.finished, "setup should be called before other steps");
+        f(&mut self.server_app);
+        f(&mut self.client_app);
+        self
+    }
+
+    /// Calls a function on the server app.
+    #[must_use]
+    pub fn on_server(mut self, f: impl FnOnce(&mut App)) -> Self {
+        self.start();
+        f(&mut self.server_app);
+        self
+    }
+
+    /// Calls a function on the client app.
+    #[must_use]
+    pub fn on_client(mut self, f: impl FnOnce(&mut App)) -> Self {
+        self.start();
+        f(&mut self.client_app);
+        self
+    }
+
+    /// Spawns an entity with [`Replicated`] and the given bundle on the server.
+    #[must_use]
+    pub fn spawn_on_server(mut self, bundle: impl Bundle) -> Self {
+        self.start();
+        self.server_app.world_mut().spawn((Replicated, bundle));
+        self
+    }
+
+    /// Updates the server, delivers its messages to the client, updates the client
+    /// and delivers its messages, such as acknowledgments, back to the server.
+    #[must_use]
+    pub fn tick(mut self) -> Self {
+        self.start();
+        self.server_app.update();
+        self.server_app.exchange_with_client(&mut self.client_app);
+        self.client_app.update();
+        self.server_app.exchange_with_client(&mut self.client_app);
+        self
+    }
+
+    /// Asserts that the client has `C` on the same number of entities as
+    /// the server has on replicated entities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the numbers don't match or if the server has no such entities.
+    #[must_use]
+    pub fn expect_client_has<C: Component>(mut self) -> Self {
+        let server_count = self
+            .server_app
+            .world_mut()
+            .query_filtered::<(), (With<Replicated>, With<C>)>()
+            .iter(self.server_app.world())
+            .count();
+        let client_count = self
+            .client_app
+            .world_mut()
+            .query_filtered::<(), (With<Remote>, With<C>)>()
+            .iter(self.client_app.world())
+            .count();
+
+        assert_ne!(
+            server_count,
+            0,
+            "server should have replicated entities with `{}`",
+            ShortName::of::<C>()
+        );
+        assert_eq!(
+            client_count,
+            server_count,
+            "client should have `{}` on all replicated entities",
+            ShortName::of::<C>()
+        );
+        self
+    }
+
+    /// Asserts that the client has no entities with `C`.
+    #[must_use]
+    pub fn expect_client_lacks<C: Component>(mut self) -> Self {
+        let client_count = self
+            .client_app
+            .world_mut()
+            .query_filtered::<(), With<C>>()
+            .iter(self.client_app.world())
+            .count();
+
+        assert_eq!(
+            client_count,
+            0,
+            "client shouldn't have `{}`",
+            ShortName::of::<C>()
+        );
+        self
+    }
+
+    /// Disconnects the client from the server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client isn't connected.
+    #[must_use]
+    pub fn disconnect(mut self) -> Self {
+        self.start();
+        assert!(self.connected, "client should be connected to disconnect");
+        self.server_app.disconnect_client(&mut self.client_app);
+        self.connected = false;
+        self
+    }
+
+    /// Connects the client to the server again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client is already connected.
+    #[must_use]
+    pub fn reconnect(mut self) -> Self {
+        self.start();
+        assert!(
+            !self.connected,
+            "client should be disconnected to reconnect"
+        );
+        self.server_app.connect_client(&mut self.client_app);
+        self.connected = true;
+        self
+    }
+
+    /// Runs a tick and asserts that all replicated server entities are mapped on the client.
+    ///
+    /// Useful after [`Self::reconnect`] to check that the server sent the whole world again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any replicated entity is missing on the client.
+    #[must_use]
+    pub fn expect_resync(self) -> Self {
+        let mut scenario = self.tick();
+        let server_entities: Vec<_> = scenario
+            .server_app
+            .world_mut()
+            .query_filtered::<Entity, With<Replicated>>()
+            .iter(scenario.server_app.world())
+            .collect();
+
+        let entity_map = scenario.client_app.world().resource::<ServerEntityMap>();
+        for server_entity in server_entities {
+            let client_entity = entity_map
+                .to_client()
+                .get(&server_entity)
+                .unwrap_or_else(|| panic!("server entity `{server_entity}` should be resynced"));
+            assert!(
+                scenario
+                    .client_app
+                    .world()
+                    .get_entity(*client_entity)
+                    .is_ok(),
+                "client entity `{client_entity}` for `{server_entity}` should exist"
+            );
+        }
+
+        scenario
+    }
+
+    /// Returns the server app.
+    pub fn server_app(&mut self) -> &mut App {
+        &mut self.server_app
+    }
+
+    /// Returns the client app.
+    pub fn client_app(&mut self) -> &mut App {
+        &mut self.client_app
+    }
+
+    /// Finishes and connects the apps if this is the first step.
+    fn start(&mut self) {
+        if !self.finished {
+            self.server_app.finish();
+            self.client_app.finish();
+            self.server_app.connect_client(&mut self.client_app);
+            self.finished = true;
+            self.connected = true;
+        }
+    }
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    shared::replication::client_ticks::ClientTicks,
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use test_log::test;
+
+#[test]
+fn applied() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            AppliedTickPlugin,
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    let update_tick = server_app
+        .world()
+        .get::<ClientTicks>(client)
+        .unwrap()
+        .update_tick();
+    let applied_tick = *server_app.world().get::<ClientAppliedTick>(client).unwrap();
+    assert!(
+        !applied_tick.contains(update_tick),
+        "update shouldn't be applied before the client runs"
+    );
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let applied_tick = *server_app.world().get::<ClientAppliedTick>(client).unwrap();
+    assert!(applied_tick.contains(update_tick));
+}
@@ -0,0 +1,72 @@
+use bevy::{asset::AssetPath, prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use test_log::test;
+
+#[test]
+fn path() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            AssetPlugin::default(),
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .init_asset::<TestAsset>()
+        .replicate_asset_handle::<TestHandle, AssetPath<'static>>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let handle = server_app
+        .world()
+        .resource::<AssetServer>()
+        .load::<TestAsset>("first.test");
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestHandle(handle)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut handles = client_app.world_mut().query::<&TestHandle>();
+    let client_handle = handles.single(client_app.world()).unwrap();
+    assert_eq!(
+        client_handle.path(),
+        Some(&AssetPath::from("first.test")),
+        "handle should be resolved by the client's asset server"
+    );
+
+    let handle = server_app
+        .world()
+        .resource::<AssetServer>()
+        .load::<TestAsset>("second.test");
+    **server_app
+        .world_mut()
+        .get_mut::<TestHandle>(server_entity)
+        .unwrap() = handle;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_handle = handles.single(client_app.world()).unwrap();
+    assert_eq!(client_handle.path(), Some(&AssetPath::from("second.test")));
+}
+
+#[derive(Component, Deref, DerefMut)]
+struct TestHandle(Handle<TestAsset>);
+
+impl From<Handle<TestAsset>> for TestHandle {
+    fn from(handle: Handle<TestAsset>) -> Self {
+        Self(handle)
+    }
+}
+
+#[derive(Asset, TypePath)]
+struct TestAsset;
@@ -1,6 +1,11 @@
 use bevy::{prelude::*, state::app::StatesPlugin};
 use bevy_replicon::{
-    prelude::*, shared::message::registry::RemoteMessageRegistry, test_app::ServerTestAppExt,
+    prelude::*,
+    shared::{
+        backend::channels::{ClientChannelId, ServerChannelId},
+        message::registry::RemoteMessageRegistry,
+    },
+    test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
 use test_log::test;
@@ -17,8 +22,14 @@ fn message() {
     }
 
     let registry = server_app.world().resource::<RemoteMessageRegistry>();
-    assert_eq!(registry.client_message_channel::<Test>(), Some(2));
-    assert_eq!(registry.server_message_channel::<Test>(), Some(3));
+    assert_eq!(
+        registry.client_message_channel::<Test>(),
+        Some(ClientChannelId::new(3))
+    );
+    assert_eq!(
+        registry.server_message_channel::<Test>(),
+        Some(ServerChannelId::new(4))
+    );
     assert_eq!(registry.client_event_channel::<Test>(), None);
     assert_eq!(registry.server_event_channel::<Test>(), None);
 
@@ -65,8 +76,14 @@ fn event() {
     let registry = server_app.world().resource::<RemoteMessageRegistry>();
     assert_eq!(registry.client_message_channel::<Test>(), None);
     assert_eq!(registry.server_message_channel::<Test>(), None);
-    assert_eq!(registry.client_event_channel::<Test>(), Some(2));
-    assert_eq!(registry.server_event_channel::<Test>(), Some(3));
+    assert_eq!(
+        registry.client_event_channel::<Test>(),
+        Some(ClientChannelId::new(3))
+    );
+    assert_eq!(
+        registry.server_event_channel::<Test>(),
+        Some(ServerChannelId::new(4))
+    );
 
     server_app.connect_client(&mut client_app);
 
@@ -0,0 +1,60 @@
+use core::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::prelude::*;
+use test_log::test;
+
+#[test]
+fn configuration() {
+    let replicon = RepliconPlugins::builder()
+        .with_auth_method(AuthMethod::None)
+        .with_tick_schedule(PostUpdate)
+        .with_mutate_messages_tracking(true)
+        .with_client_order(ClientOrder::RoundRobin);
+
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, replicon))
+            .finish();
+    }
+
+    for app in [&server_app, &client_app] {
+        assert_eq!(*app.world().resource::<AuthMethod>(), AuthMethod::None);
+        assert!(**app.world().resource::<TrackMutateMessages>());
+        assert_eq!(
+            *app.world().resource::<ClientOrder>(),
+            ClientOrder::RoundRobin
+        );
+    }
+
+    let client = server_app
+        .world_mut()
+        .spawn(ConnectedClient { max_size: 1200 });
+    assert!(
+        client.contains::<AuthorizedClient>(),
+        "client should be authorized immediately"
+    );
+}
+
+#[test]
+#[should_panic]
+fn zero_mutations_timeout() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        RepliconPlugins::builder().with_mutations_timeout(Duration::ZERO),
+    ));
+}
+
+#[test]
+#[should_panic]
+fn zero_full_refresh_interval() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        RepliconPlugins::builder().with_full_refresh_interval(Duration::ZERO),
+    ));
+}
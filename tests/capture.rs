@@ -0,0 +1,159 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    test_app::{
+        ServerTestAppExt,
+        capture::{CapturedChanges, CapturedMessage},
+    },
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn insertion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .prefix_component_sizes()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(5)))
+        .id();
+
+    server_app.update();
+
+    let messages = server_app.capture_replication(&client_app);
+    let [CapturedMessage::Update(update)] = messages.as_slice() else {
+        panic!("only update message should be sent, got {messages:?}");
+    };
+    assert!(update.despawns.is_empty());
+    assert!(update.removals.is_empty());
+    assert_eq!(test_bytes(&update.changes, server_entity), [5]);
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&TestComponent>();
+    let component = components.single(client_app.world()).unwrap();
+    assert_eq!(**component, 5, "capturing shouldn't consume messages");
+}
+
+#[test]
+fn mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .prefix_component_sizes()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(5)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap();
+    **component = 7;
+
+    server_app.update();
+
+    let messages = server_app.capture_replication(&client_app);
+    let [CapturedMessage::Mutate(mutate)] = messages.as_slice() else {
+        panic!("only mutate message should be sent, got {messages:?}");
+    };
+    assert!(mutate.tick.is_newer(mutate.update_tick));
+    assert_eq!(test_bytes(&mutate.mutations, server_entity), [7]);
+}
+
+#[test]
+fn removal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .prefix_component_sizes()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(5)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<TestComponent>();
+
+    server_app.update();
+
+    let messages = server_app.capture_replication(&client_app);
+    let [CapturedMessage::Update(update)] = messages.as_slice() else {
+        panic!("only update message should be sent, got {messages:?}");
+    };
+    let [removals] = update.removals.as_slice() else {
+        panic!("removal should be sent for a single entity");
+    };
+    assert_eq!(removals.entity, server_entity);
+    let [name] = removals.components.as_slice() else {
+        panic!("only one component should be removed");
+    };
+    assert!(name.ends_with("TestComponent"));
+}
+
+/// Returns serialized bytes of [`TestComponent`] for the entity.
+fn test_bytes(changes: &[CapturedChanges], entity: Entity) -> &[u8] {
+    let changes = changes
+        .iter()
+        .find(|changes| changes.entity == entity)
+        .expect("entity should be changed");
+    let component = changes
+        .components
+        .iter()
+        .find(|component| component.name.ends_with("TestComponent"))
+        .expect("component should be changed");
+
+    &component.bytes
+}
+
+#[derive(Component, Deref, DerefMut, Serialize, Deserialize)]
+struct TestComponent(u8);
@@ -0,0 +1,190 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    shared::{
+        message::{client_message, ctx::ServerReceiveCtx},
+        replication::registry::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns,
+        },
+    },
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn serialization() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_with(RuleFns::new(
+            serialize_health,
+            rule_fns::default_deserialize,
+        ))
+        .replicate::<Position>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Health(0), Position(1)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(Has<Health>, &Position), With<Remote>>();
+    let (has_health, &position) = components.single(client_app.world()).unwrap();
+    assert!(!has_health, "panicked component should be skipped");
+    assert_eq!(position, Position(1));
+
+    server_app
+        .world_mut()
+        .get_mut::<Health>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut healths = client_app
+        .world_mut()
+        .query_filtered::<&Health, With<Remote>>();
+    let &health = healths.single(client_app.world()).unwrap();
+    assert_eq!(
+        health,
+        Health(1),
+        "component should be sent once it's valid"
+    );
+}
+
+#[test]
+fn deserialization() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ));
+    }
+    server_app.replicate::<Health>().finish();
+    client_app
+        .replicate_with(RuleFns::new(
+            rule_fns::default_serialize,
+            deserialize_health,
+        ))
+        .finish();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, Health(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut healths = client_app.world_mut().query_filtered::<(), With<Health>>();
+    assert_eq!(healths.iter(client_app.world()).len(), 0);
+
+    let errors: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Messages<ReplicationError>>()
+        .drain()
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ReplicationErrorKind::Apply);
+}
+
+#[test]
+fn client_message() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .add_client_message_with(
+                Channel::Ordered,
+                client_message::default_serialize::<Damage>,
+                deserialize_damage,
+            )
+            .insert_resource(ReplicationErrorSettings {
+                client_policy: ClientErrorPolicy::Disconnect,
+                ..Default::default()
+            })
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world_mut().write_message(Damage(0));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let messages = server_app
+        .world()
+        .resource::<Messages<FromClient<Damage>>>();
+    assert!(messages.is_empty());
+
+    let errors: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<ReplicationError>>()
+        .drain()
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ReplicationErrorKind::Deserialize);
+
+    let client_entity = **client_app.world().resource::<TestClientEntity>();
+    let disconnects: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<DisconnectRequest>>()
+        .drain()
+        .collect();
+    assert_eq!(disconnects.len(), 1);
+    assert_eq!(disconnects[0].client, client_entity);
+}
+
+/// Panics on zero health to emulate a bug in user code.
+fn serialize_health(ctx: &mut SerializeCtx, health: &Health, message: &mut Vec<u8>) -> Result<()> {
+    assert_ne!(health.0, 0, "health can't be zero");
+    rule_fns::default_serialize(ctx, health, message)
+}
+
+/// Like [`deserialize_health`], but for a client message.
+fn deserialize_damage(ctx: &mut ServerReceiveCtx, message: &mut Bytes) -> Result<Damage> {
+    let damage: Damage = client_message::default_deserialize(ctx, message)?;
+    assert_ne!(damage.0, 0, "damage can't be zero");
+    Ok(damage)
+}
+
+/// Like [`serialize_health`], but on deserialization.
+fn deserialize_health(ctx: &mut WriteCtx, message: &mut Bytes) -> Result<Health> {
+    let health: Health = rule_fns::default_deserialize(ctx, message)?;
+    assert_ne!(health.0, 0, "health can't be zero");
+    Ok(health)
+}
+
+#[derive(Component, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Health(u32);
+
+#[derive(Component, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Position(u32);
+
+#[derive(Message, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Damage(u32);
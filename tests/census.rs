@@ -0,0 +1,90 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    server::census::{CensusPlugin, ReplicationCensus},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn archetypes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate::<B>();
+    }
+    server_app.add_plugins(CensusPlugin::default()).finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let single = server_app.world_mut().spawn((Replicated, A)).id();
+    server_app.world_mut().spawn((Replicated, A, B));
+    server_app.world_mut().spawn((Replicated, A, B));
+
+    server_app.update();
+
+    let census = server_app.world().resource::<ReplicationCensus>();
+    assert_eq!(census.entities(), 3);
+    assert!(census.bytes() > 0);
+    assert_eq!(census.average_components(), 5.0 / 3.0);
+
+    let location = server_app.world().entity(single).location();
+    let archetype = census.get(location.archetype_id).unwrap();
+    assert_eq!(archetype.entities, 1);
+    assert_eq!(archetype.components, 1);
+    assert_eq!(archetype.rules.len(), 1);
+    assert!(archetype.bytes > 0);
+
+    server_app.update();
+
+    let census = server_app.world().resource::<ReplicationCensus>();
+    assert_eq!(census.entities(), 3);
+    assert_eq!(census.bytes(), 0, "nothing changed");
+}
+
+#[test]
+fn reset() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>();
+    }
+    server_app.add_plugins(CensusPlugin::default()).finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, A));
+    server_app.update();
+
+    let census = server_app.world().resource::<ReplicationCensus>();
+    assert_eq!(census.entities(), 1);
+
+    server_app
+        .world_mut()
+        .resource_mut::<NextState<ServerState>>()
+        .set(ServerState::Stopped);
+    server_app.update();
+
+    let census = server_app.world().resource::<ReplicationCensus>();
+    assert_eq!(census.iter().count(), 0);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct A;
+
+#[derive(Component, Deserialize, Serialize)]
+struct B;
@@ -0,0 +1,108 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    server::changed_this_tick::{ChangedThisTick, EntityChanges},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn changes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate::<B>();
+    }
+    server_app.init_resource::<ChangedThisTick>().finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, A, B)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let changed = server_app.world().resource::<ChangedThisTick>();
+    assert_eq!(changed.len(), 1);
+    assert_eq!(
+        changed.get(server_entity),
+        Some(EntityChanges {
+            inserted: true,
+            ..Default::default()
+        })
+    );
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<B>()
+        .get_mut::<A>()
+        .unwrap()
+        .set_changed();
+
+    server_app.update();
+
+    let changed = server_app.world().resource::<ChangedThisTick>();
+    assert_eq!(
+        changed.get(server_entity),
+        Some(EntityChanges {
+            mutated: true,
+            removed: true,
+            ..Default::default()
+        })
+    );
+
+    server_app.update();
+
+    let changed = server_app.world().resource::<ChangedThisTick>();
+    assert!(changed.is_empty(), "nothing changed");
+}
+
+#[test]
+fn reset() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>();
+    }
+    server_app.init_resource::<ChangedThisTick>().finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, A));
+    server_app.update();
+
+    let changed = server_app.world().resource::<ChangedThisTick>();
+    assert_eq!(changed.len(), 1);
+
+    server_app
+        .world_mut()
+        .resource_mut::<NextState<ServerState>>()
+        .set(ServerState::Stopped);
+    server_app.update();
+
+    let changed = server_app.world().resource::<ChangedThisTick>();
+    assert!(changed.is_empty());
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct A;
+
+#[derive(Component, Deserialize, Serialize)]
+struct B;
@@ -1,4 +1,11 @@
-use bevy::{ecs::entity::MapEntities, prelude::*, state::app::StatesPlugin, time::TimePlugin};
+use core::marker::PhantomData;
+
+use bevy::{
+    ecs::{entity::MapEntities, event::SetEntityEventTarget},
+    prelude::*,
+    state::app::StatesPlugin,
+    time::TimePlugin,
+};
 use bevy_replicon::{
     prelude::*, shared::server_entity_map::ServerEntityMap, test_app::ServerTestAppExt,
 };
@@ -71,6 +78,192 @@ fn mapped() {
     assert_eq!(mapped_entities, [server_entity]);
 }
 
+#[test]
+fn targets() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_client_targets_event::<WithTarget>(Channel::Ordered)
+        .finish();
+    }
+    server_app.init_resource::<EventReader<WithTarget>>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity1 = server_app.world_mut().spawn(Replicated).id();
+    let server_entity2 = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity1 = *entity_map.to_client().get(&server_entity1).unwrap();
+    let client_entity2 = *entity_map.to_client().get(&server_entity2).unwrap();
+
+    client_app.world_mut().client_trigger_targets(
+        WithTarget {
+            entity: Entity::PLACEHOLDER,
+        },
+        [client_entity1, client_entity2],
+    );
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let reader = server_app.world().resource::<EventReader<WithTarget>>();
+    let targets: Vec<_> = reader.events.iter().map(|event| event.entity).collect();
+    assert_eq!(targets, [server_entity1, server_entity2]);
+}
+
+#[test]
+fn targets_filter() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_client_targets_event::<WithTarget>(Channel::Ordered)
+        .set_client_targets_filter::<WithTarget>(|world, _, target| {
+            world.get::<Allowed>(target).is_some()
+        })
+        .finish();
+    }
+    server_app.init_resource::<EventReader<WithTarget>>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity1 = server_app.world_mut().spawn((Replicated, Allowed)).id();
+    let server_entity2 = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity1 = *entity_map.to_client().get(&server_entity1).unwrap();
+    let client_entity2 = *entity_map.to_client().get(&server_entity2).unwrap();
+
+    client_app.world_mut().client_trigger_targets(
+        WithTarget {
+            entity: Entity::PLACEHOLDER,
+        },
+        [client_entity1, client_entity2],
+    );
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let reader = server_app.world().resource::<EventReader<WithTarget>>();
+    let targets: Vec<_> = reader.events.iter().map(|event| event.entity).collect();
+    assert_eq!(
+        targets,
+        [server_entity1],
+        "only allowed target should be triggered"
+    );
+}
+
+#[test]
+fn relayed() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_relayed_client_event::<Test>(Channel::Ordered)
+        .finish();
+    }
+    server_app.init_resource::<EventReader<Test>>();
+    client_app1.init_resource::<RelayedReader<Test>>();
+    client_app2.init_resource::<RelayedReader<Test>>();
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    client_app1.world_mut().client_trigger(Test);
+
+    client_app1.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app1.update();
+    client_app2.update();
+
+    let reader = server_app.world().resource::<EventReader<Test>>();
+    assert_eq!(
+        reader.events.len(),
+        1,
+        "server should receive the event as usual"
+    );
+
+    let reader = client_app1.world().resource::<RelayedReader<Test>>();
+    assert!(
+        reader.senders.is_empty(),
+        "event shouldn't be relayed back to the sender"
+    );
+
+    let reader = client_app2.world().resource::<RelayedReader<Test>>();
+    assert_eq!(
+        reader.senders,
+        [ClientId::Client(Entity::PLACEHOLDER)],
+        "sender's entity isn't replicated, so it can't be mapped"
+    );
+}
+
+#[test]
+fn relayed_filter() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_relayed_client_event::<Test>(Channel::Ordered)
+        .set_relay_filter::<Test>(|_, _, _| false)
+        .finish();
+    }
+    server_app.init_resource::<EventReader<Test>>();
+    client_app2.init_resource::<RelayedReader<Test>>();
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    client_app1.world_mut().client_trigger(Test);
+
+    client_app1.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app2);
+    client_app2.update();
+
+    let reader = server_app.world().resource::<EventReader<Test>>();
+    assert_eq!(reader.events.len(), 1);
+
+    let reader = client_app2.world().resource::<RelayedReader<Test>>();
+    assert!(
+        reader.senders.is_empty(),
+        "rejected event shouldn't be relayed"
+    );
+}
+
 #[test]
 fn without_plugins() {
     let mut server_app = App::new();
@@ -160,6 +353,20 @@ struct Test;
 #[derive(Deserialize, Event, Serialize, Clone, MapEntities)]
 struct WithEntity(#[entities] Entity);
 
+#[derive(Deserialize, EntityEvent, Serialize, Clone)]
+struct WithTarget {
+    entity: Entity,
+}
+
+impl SetEntityEventTarget for WithTarget {
+    fn set_event_target(&mut self, entity: Entity) {
+        self.entity = entity;
+    }
+}
+
+#[derive(Component)]
+struct Allowed;
+
 #[derive(Resource)]
 struct EventReader<E: Event> {
     events: Vec<FromClient<E>>,
@@ -176,3 +383,22 @@ impl<E: Event + Clone> FromWorld for EventReader<E> {
         }
     }
 }
+
+#[derive(Resource)]
+struct RelayedReader<E: Event> {
+    senders: Vec<ClientId>,
+    marker: PhantomData<E>,
+}
+
+impl<E: Event> FromWorld for RelayedReader<E> {
+    fn from_world(world: &mut World) -> Self {
+        world.add_observer(|on: On<Relayed<E>>, mut reader: ResMut<Self>| {
+            reader.senders.push(on.sender);
+        });
+
+        Self {
+            senders: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
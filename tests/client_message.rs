@@ -1,6 +1,8 @@
 use bevy::{ecs::entity::MapEntities, prelude::*, state::app::StatesPlugin, time::TimePlugin};
 use bevy_replicon::{
-    prelude::*, shared::server_entity_map::ServerEntityMap, test_app::ServerTestAppExt,
+    prelude::*,
+    shared::{backend::channels::ClientChannelId, server_entity_map::ServerEntityMap},
+    test_app::{ServerTestAppExt, TestClientEntity},
 };
 use serde::{Deserialize, Serialize};
 use test_log::test;
@@ -27,6 +29,161 @@ fn regular() {
     assert_eq!(messages.len(), 1);
 }
 
+#[test]
+fn latest() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .add_client_message::<Aim>(Channel::UnreliableLatest)
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world_mut()
+        .write_message_batch([Aim(0), Aim(1), Aim(2)]);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let messages = server_app.world().resource::<Messages<FromClient<Aim>>>();
+    let aims: Vec<_> = messages
+        .iter_current_update_messages()
+        .map(|aim| aim.0)
+        .collect();
+    assert_eq!(aims, [2], "only the newest message should be received");
+}
+
+#[test]
+fn latest_upgraded() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .insert_resource(BackendCapabilities::ORDERED_ONLY)
+            .add_client_message::<Aim>(Channel::UnreliableLatest)
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world_mut()
+        .write_message_batch([Aim(0), Aim(1), Aim(2)]);
+
+    client_app.update();
+
+    let channels = client_app.world().resource::<RepliconChannels>();
+    let channel_id = ClientChannelId::new(channels.client_channels().len() - 1);
+    assert_eq!(
+        channels.client_channels()[channel_id.get()],
+        Channel::Ordered
+    );
+    let messages = client_app.world().resource::<ClientMessages>();
+    assert_eq!(
+        messages
+            .iter_sent()
+            .filter(|&(id, _)| id == channel_id)
+            .count(),
+        1,
+        "upgraded channel should send only the newest message"
+    );
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let messages = server_app.world().resource::<Messages<FromClient<Aim>>>();
+    let aims: Vec<_> = messages
+        .iter_current_update_messages()
+        .map(|aim| aim.0)
+        .collect();
+    assert_eq!(aims, [2]);
+}
+
+#[test]
+fn coalescing() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .add_client_message::<Aim>(Channel::Ordered)
+            .add_client_message::<First>(Channel::Ordered)
+            .add_client_message::<Sum>(Channel::Ordered)
+            .finish();
+    }
+    client_app
+        .coalesce_client_message::<Aim>(CoalescePolicy::KeepLatest)
+        .coalesce_client_message::<First>(CoalescePolicy::KeepFirst)
+        .coalesce_client_message::<Sum>(CoalescePolicy::MergeWith(|a, b| Sum(a.0 + b.0)));
+
+    server_app.connect_client(&mut client_app);
+
+    for _ in 0..2 {
+        client_app
+            .world_mut()
+            .write_message_batch([Aim(0), Aim(1), Aim(2)]);
+        client_app
+            .world_mut()
+            .write_message_batch([First(0), First(1), First(2)]);
+        client_app
+            .world_mut()
+            .write_message_batch([Sum(1), Sum(2), Sum(3)]);
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+
+        let aims = server_app.world().resource::<Messages<FromClient<Aim>>>();
+        let aims: Vec<_> = aims.iter_current_update_messages().map(|m| m.0).collect();
+        assert_eq!(aims, [2]);
+
+        let firsts = server_app.world().resource::<Messages<FromClient<First>>>();
+        let firsts: Vec<_> = firsts.iter_current_update_messages().map(|m| m.0).collect();
+        assert_eq!(firsts, [0]);
+
+        let sums = server_app.world().resource::<Messages<FromClient<Sum>>>();
+        let sums: Vec<_> = sums.iter_current_update_messages().map(|m| m.0).collect();
+        assert_eq!(sums, [6]);
+    }
+}
+
+#[test]
+fn fragmented() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .add_client_message::<WithString>(Channel::Unordered);
+        app.world_mut()
+            .resource_mut::<RepliconChannels>()
+            .set_fragment_threshold(8);
+        app.finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let text = "a message that doesn't fit into a single fragment";
+    client_app
+        .world_mut()
+        .write_message(WithString(text.to_string()));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let messages = server_app
+        .world()
+        .resource::<Messages<FromClient<WithString>>>();
+    let received: Vec<_> = messages
+        .iter_current_update_messages()
+        .map(|message| message.0.as_str())
+        .collect();
+    assert_eq!(received, [text]);
+}
+
 #[test]
 fn mapped() {
     let mut server_app = App::new();
@@ -156,8 +313,154 @@ fn with_disconnect() {
     );
 }
 
+#[test]
+fn deserialization_error() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .add_client_message::<WithString>(Channel::Ordered)
+            .insert_resource(ReplicationErrorSettings {
+                max_per_second: 2,
+                ..Default::default()
+            })
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let channels = client_app.world().resource::<RepliconChannels>();
+    let channel_id = ClientChannelId::new(channels.client_channels().len() - 1);
+    let mut messages = client_app.world_mut().resource_mut::<ClientMessages>();
+    for _ in 0..3 {
+        // Unterminated varint for the string length.
+        messages.send(channel_id, vec![u8::MAX]);
+    }
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let messages = server_app
+        .world()
+        .resource::<Messages<FromClient<WithString>>>();
+    assert!(messages.is_empty());
+
+    let client_entity = **client_app.world().resource::<TestClientEntity>();
+    let errors: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<ReplicationError>>()
+        .drain()
+        .collect();
+    assert_eq!(
+        errors.len(),
+        2,
+        "errors above the limit should be suppressed"
+    );
+    for error in errors {
+        assert_eq!(error.kind, ReplicationErrorKind::Deserialize);
+        assert_eq!(error.channel_id, Some(channel_id.get()));
+        assert_eq!(error.client, Some(client_entity));
+    }
+}
+
+#[test]
+fn disconnect_on_error() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .add_client_message::<WithString>(Channel::Ordered)
+            .insert_resource(ReplicationErrorSettings {
+                max_per_second: 1,
+                client_policy: ClientErrorPolicy::Disconnect,
+            })
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let channels = client_app.world().resource::<RepliconChannels>();
+    let channel_id = ClientChannelId::new(channels.client_channels().len() - 1);
+    let mut messages = client_app.world_mut().resource_mut::<ClientMessages>();
+    for _ in 0..2 {
+        // Unterminated varint for the string length.
+        messages.send(channel_id, vec![u8::MAX]);
+    }
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_entity = **client_app.world().resource::<TestClientEntity>();
+    let disconnects: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<DisconnectRequest>>()
+        .drain()
+        .collect();
+    assert_eq!(
+        disconnects.len(),
+        1,
+        "client should be disconnected once even with suppressed errors"
+    );
+    assert_eq!(disconnects[0].client, client_entity);
+}
+
+#[test]
+fn invalid_channel() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .insert_resource(ReplicationErrorSettings {
+                client_policy: ClientErrorPolicy::Disconnect,
+                ..Default::default()
+            })
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = **client_app.world().resource::<TestClientEntity>();
+    let channel_id = ClientChannelId::new(u8::MAX.into());
+    server_app
+        .world_mut()
+        .resource_mut::<ServerMessages>()
+        .insert_received(client_entity, channel_id, vec![0]);
+
+    server_app.update();
+
+    let errors: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<ReplicationError>>()
+        .drain()
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ReplicationErrorKind::Deserialize);
+    assert_eq!(errors[0].channel_id, Some(channel_id.get()));
+    assert_eq!(errors[0].client, Some(client_entity));
+
+    let disconnects: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<DisconnectRequest>>()
+        .drain()
+        .collect();
+    assert_eq!(disconnects.len(), 1);
+    assert_eq!(disconnects[0].client, client_entity);
+}
+
 #[derive(Deserialize, Message, Serialize)]
 struct Test;
 
+#[derive(Deserialize, Message, Serialize)]
+struct WithString(String);
+
+#[derive(Deserialize, Message, Serialize)]
+struct Aim(u8);
+
+#[derive(Deserialize, Message, Serialize)]
+struct First(u8);
+
+#[derive(Deserialize, Message, Serialize)]
+struct Sum(u8);
+
 #[derive(Deserialize, Message, Serialize, Clone, MapEntities)]
 struct WithEntity(#[entities] Entity);
@@ -0,0 +1,91 @@
+use bevy::{platform::collections::HashSet, prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick};
+use test_log::test;
+
+#[test]
+fn round_robin() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        RepliconPlugins.set(ServerPlugin {
+            tick_schedule: None,
+            client_order: ClientOrder::RoundRobin,
+            ..Default::default()
+        }),
+    ))
+    .finish();
+
+    app.world_mut()
+        .resource_mut::<NextState<ServerState>>()
+        .set(ServerState::Running);
+    for _ in 0..CLIENTS_COUNT {
+        app.world_mut()
+            .spawn((ConnectedClient { max_size: 1200 }, AuthorizedClient));
+    }
+
+    let mut first_clients = HashSet::new();
+    for _ in 0..CLIENTS_COUNT {
+        app.world_mut().spawn(Replicated);
+        app.world_mut().resource_mut::<ServerTick>().increment();
+        app.update();
+
+        let mut messages = app.world_mut().resource_mut::<ServerMessages>();
+        let (client, ..) = messages.iter_sent().next().unwrap();
+        first_clients.insert(client);
+        assert_eq!(messages.drain_sent().len(), CLIENTS_COUNT);
+    }
+
+    assert_eq!(
+        first_clients.len(),
+        CLIENTS_COUNT,
+        "each client should come first once"
+    );
+}
+
+#[test]
+fn longest_waiting() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        RepliconPlugins.set(ServerPlugin {
+            tick_schedule: None,
+            client_order: ClientOrder::LongestWaiting,
+            ..Default::default()
+        }),
+    ))
+    .finish();
+
+    app.world_mut()
+        .resource_mut::<NextState<ServerState>>()
+        .set(ServerState::Running);
+    for _ in 0..CLIENTS_COUNT {
+        app.world_mut()
+            .spawn((ConnectedClient { max_size: 1200 }, AuthorizedClient));
+    }
+
+    app.world_mut().spawn(Replicated);
+    app.world_mut().resource_mut::<ServerTick>().increment();
+    app.update();
+
+    // Simulate a backend with a budget that couldn't send to the last client.
+    let mut messages = app.world_mut().resource_mut::<ServerMessages>();
+    let (starved_client, ..) = messages.iter_sent().last().unwrap();
+    messages.retain_sent(|&(client, ..)| client == starved_client);
+    let pending = messages.iter_sent().len();
+
+    app.world_mut().spawn(Replicated);
+    app.world_mut().resource_mut::<ServerTick>().increment();
+    app.update();
+
+    let messages = app.world().resource::<ServerMessages>();
+    assert_eq!(messages.iter_sent().len(), pending + CLIENTS_COUNT);
+    let (client, ..) = messages.iter_sent().nth(pending).unwrap();
+    assert_eq!(
+        client, starved_client,
+        "starved client should be served first"
+    );
+}
+
+const CLIENTS_COUNT: usize = 16;
@@ -1,11 +1,14 @@
-use core::marker::PhantomData;
+use core::{marker::PhantomData, time::Duration};
 
 use bevy::{prelude::*, state::app::StatesPlugin};
 use bevy_replicon::{
     prelude::*,
-    server::server_tick::ServerTick,
-    shared::backend::connected_client::{ConnectedClient, NetworkId, NetworkIdMap},
-    test_app::ServerTestAppExt,
+    server::{authorization::TokenCheck, server_tick::ServerTick},
+    shared::{
+        authorization::AuthRequest,
+        backend::connected_client::{ConnectedClient, NetworkId, NetworkIdMap},
+    },
+    test_app::{ServerTestAppExt, TestClientEntity, scenario::Scenario},
 };
 use serde::{Deserialize, Serialize};
 use test_log::test;
@@ -93,6 +96,104 @@ fn protocol_mismatch() {
     assert_eq!(counter.events, 1);
 }
 
+#[test]
+fn domain_mismatch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+    }
+    server_app
+        .world_mut()
+        .resource_mut::<ProtocolHasher>()
+        .mix("assets", 1);
+    client_app
+        .world_mut()
+        .resource_mut::<ProtocolHasher>()
+        .mix("assets", 2);
+    client_app.init_resource::<ReceivedMismatch>().add_observer(
+        |mismatch: On<ProtocolMismatch>, mut received: ResMut<ReceivedMismatch>| {
+            received.0 = Some(mismatch.server.clone());
+        },
+    );
+    server_app.finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let mut clients = server_app
+        .world_mut()
+        .query_filtered::<Entity, With<AuthorizedClient>>();
+    assert_eq!(clients.iter(server_app.world()).len(), 0);
+
+    let received = client_app.world().resource::<ReceivedMismatch>();
+    let server = received.0.as_ref().unwrap();
+    let client = client_app.world().resource::<ProtocolBreakdown>();
+    assert_eq!(server.registrations, client.registrations);
+    assert_eq!(server.domain("assets"), Some(1));
+    assert_eq!(
+        client.mismatched_domains(server).collect::<Vec<_>>(),
+        ["assets"]
+    );
+}
+
+#[test]
+fn optional_rules() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .replicate::<A>();
+    }
+    server_app.replicate_optional(|app| {
+        app.replicate::<B>().replicate::<C>();
+    });
+    client_app.replicate_optional(|app| {
+        app.replicate::<B>();
+    });
+    server_app.finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let mut clients = server_app
+        .world_mut()
+        .query_filtered::<Entity, With<AuthorizedClient>>();
+    assert_eq!(clients.iter(server_app.world()).len(), 1);
+}
+
+#[test]
+fn optional_rules_mismatch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .replicate::<A>();
+    }
+    server_app.replicate_optional(|app| {
+        app.replicate::<B>().replicate::<C>();
+    });
+    client_app
+        .init_resource::<EventCounter<ProtocolMismatch>>()
+        .replicate_optional(|app| {
+            app.replicate::<C>();
+        });
+    server_app.finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let mut clients = server_app
+        .world_mut()
+        .query_filtered::<Entity, With<AuthorizedClient>>();
+    assert_eq!(clients.iter(server_app.world()).len(), 0);
+
+    let counter = client_app
+        .world()
+        .resource::<EventCounter<ProtocolMismatch>>();
+    assert_eq!(counter.events, 1);
+}
+
 #[test]
 fn custom_auth() {
     let mut server_app = App::new();
@@ -116,6 +217,88 @@ fn custom_auth() {
     assert_eq!(clients.iter(server_app.world()).len(), 1);
 }
 
+#[test]
+fn token_auth() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .finish();
+    }
+    server_app
+        .insert_resource(Authorizers::default().with(TokenCheck::new(|token| token == b"secret")));
+    client_app.init_resource::<ReceivedDenial>().add_observer(
+        |failed: On<AuthorizationFailed>, mut received: ResMut<ReceivedDenial>| {
+            received.0 = Some(failed.reason.clone());
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let mut clients = server_app
+        .world_mut()
+        .query_filtered::<Entity, With<AuthorizedClient>>();
+    assert_eq!(clients.iter(server_app.world()).len(), 0);
+
+    let received = client_app.world().resource::<ReceivedDenial>();
+    assert_eq!(received.0, Some(AuthDenial::InvalidToken));
+
+    server_app.disconnect_client(&mut client_app);
+    client_app.insert_resource(AuthToken(b"secret".to_vec()));
+    server_app.connect_client(&mut client_app);
+
+    assert_eq!(clients.iter(server_app.world()).len(), 1);
+}
+
+#[test]
+fn authorizers_order() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    server_app
+        .add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .add_client_message::<Test>(Channel::Ordered)
+        .finish();
+    client_app
+        .init_resource::<EventCounter<ProtocolMismatch>>()
+        .init_resource::<ReceivedDenial>()
+        .add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .add_observer(
+            |failed: On<AuthorizationFailed>, mut received: ResMut<ReceivedDenial>| {
+                received.0 = Some(failed.reason.clone());
+            },
+        )
+        .finish();
+
+    server_app.world_mut().resource_mut::<Authorizers>().insert(
+        0,
+        |_world: &World, _client: Entity, _request: &AuthRequest| -> Result<(), AuthDenial> {
+            Err(AuthDenial::Custom("full".into()))
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let mut clients = server_app
+        .world_mut()
+        .query_filtered::<Entity, With<AuthorizedClient>>();
+    assert_eq!(clients.iter(server_app.world()).len(), 0);
+
+    let received = client_app.world().resource::<ReceivedDenial>();
+    assert_eq!(
+        received.0,
+        Some(AuthDenial::Custom("full".into())),
+        "first authorizer should veto"
+    );
+
+    let counter = client_app
+        .world()
+        .resource::<EventCounter<ProtocolMismatch>>();
+    assert_eq!(
+        counter.events, 0,
+        "authorizers after the veto shouldn't be evaluated"
+    );
+}
+
 #[test]
 fn disabled_auth() {
     let mut app = App::new();
@@ -132,6 +315,67 @@ fn disabled_auth() {
     assert!(entity.contains::<AuthorizedClient>());
 }
 
+#[test]
+fn connection_seed() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .finish();
+    }
+
+    server_app.add_observer(|add: On<Add, ConnectedClient>, mut commands: Commands| {
+        commands
+            .entity(add.entity)
+            .insert(ConnectedClientSeed(ConnectionSeed(42)));
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let client_seed = *client_app.world().resource::<ConnectionSeed>();
+    assert_eq!(client_seed, ConnectionSeed(42));
+    assert_eq!(client_seed.mix(1), ConnectionSeed(42).mix(1));
+
+    server_app.disconnect_client(&mut client_app);
+    assert!(!client_app.world().contains_resource::<ConnectionSeed>());
+}
+
+#[test]
+#[cfg(feature = "getrandom")]
+fn random_connection_seed() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    let server_seed = **server_app
+        .world()
+        .get::<ConnectedClientSeed>(client)
+        .unwrap();
+    let client_seed = *client_app.world().resource::<ConnectionSeed>();
+    assert_eq!(client_seed, server_seed);
+}
+
+#[test]
+#[cfg(not(feature = "getrandom"))]
+fn no_connection_seed() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    assert!(!client_app.world().contains_resource::<ConnectionSeed>());
+}
+
 #[test]
 fn network_id_map() {
     let mut app = App::new();
@@ -145,9 +389,168 @@ fn network_id_map() {
     assert!(app.world().resource::<NetworkIdMap>().is_empty());
 }
 
+#[test]
+fn client_ref() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<NetworkId>()
+        .replicate::<Owner>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let network_id = NetworkId::new(0);
+    let client_entity = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert((Replicated, network_id));
+
+    let owner = Owner(network_id.into());
+    let network_map = server_app.world().resource::<NetworkIdMap>();
+    assert_eq!(owner.entity(network_map), Some(client_entity));
+
+    server_app.world_mut().spawn((Replicated, owner));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (roster_entity, _) = client_app
+        .world_mut()
+        .query::<(Entity, &NetworkId)>()
+        .single(client_app.world())
+        .unwrap();
+    let owner = *client_app
+        .world_mut()
+        .query::<&Owner>()
+        .single(client_app.world())
+        .unwrap();
+    let network_map = client_app.world().resource::<NetworkIdMap>();
+    assert_eq!(owner.entity(network_map), Some(roster_entity));
+    assert_eq!(ClientRef::NONE.entity(network_map), None);
+}
+
+#[test]
+fn despawn_on_disconnect() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .finish();
+
+    app.world_mut()
+        .resource_mut::<NextState<ServerState>>()
+        .set(ServerState::Running);
+    app.update();
+
+    let network_id = NetworkId::new(0);
+    let client_entity = app
+        .world_mut()
+        .spawn((ConnectedClient { max_size: 1200 }, network_id))
+        .id();
+    let despawned = app
+        .world_mut()
+        .spawn(DespawnOnDisconnect::new(network_id))
+        .id();
+    let neutralized = app
+        .world_mut()
+        .spawn((
+            Controlled,
+            DespawnOnDisconnect::new(network_id).with_action(DisconnectAction::Neutralize(
+                |entity| {
+                    entity.remove::<Controlled>();
+                },
+            )),
+        ))
+        .id();
+    let unrelated = app
+        .world_mut()
+        .spawn(DespawnOnDisconnect::new(NetworkId::new(1)))
+        .id();
+
+    app.update();
+    assert!(app.world().get_entity(despawned).is_ok());
+
+    app.world_mut().despawn(client_entity);
+    app.update();
+
+    assert!(app.world().get_entity(despawned).is_err());
+    let neutralized = app.world().entity(neutralized);
+    assert!(!neutralized.contains::<Controlled>());
+    assert!(!neutralized.contains::<DespawnOnDisconnect>());
+    assert!(app.world().get_entity(unrelated).is_ok());
+}
+
+#[test]
+fn reconnect_within_grace_period() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins))
+        .finish();
+
+    app.world_mut()
+        .resource_mut::<NextState<ServerState>>()
+        .set(ServerState::Running);
+    app.update();
+
+    let network_id = NetworkId::new(0);
+    let client_entity = app
+        .world_mut()
+        .spawn((ConnectedClient { max_size: 1200 }, network_id))
+        .id();
+    let entity = app
+        .world_mut()
+        .spawn(DespawnOnDisconnect::new(network_id).with_grace_period(Duration::from_secs(60)))
+        .id();
+
+    app.world_mut().despawn(client_entity);
+    app.update();
+    assert!(
+        app.world().get_entity(entity).is_ok(),
+        "entity should be kept during the grace period"
+    );
+
+    app.world_mut()
+        .spawn((ConnectedClient { max_size: 1200 }, network_id));
+    app.update();
+    assert!(app.world().get_entity(entity).is_ok());
+}
+
+#[test]
+fn resync_after_reconnect() {
+    Scenario::new()
+        .setup(|app| {
+            app.replicate::<A>();
+        })
+        .spawn_on_server(A)
+        .tick()
+        .expect_client_has::<A>()
+        .disconnect()
+        .spawn_on_server(A)
+        .reconnect()
+        .expect_resync();
+}
+
 #[derive(Message, Serialize, Deserialize)]
 struct Test;
 
+#[derive(Component, Deref, Serialize, Deserialize, Clone, Copy)]
+struct Owner(ClientRef);
+
+#[derive(Component)]
+struct Controlled;
+
+#[derive(Resource, Default)]
+struct ReceivedMismatch(Option<ProtocolBreakdown>);
+
+#[derive(Resource, Default)]
+struct ReceivedDenial(Option<AuthDenial>);
+
 #[derive(Resource)]
 struct EventCounter<E: Event> {
     events: usize,
@@ -166,3 +569,12 @@ impl<E: Event> FromWorld for EventCounter<E> {
         }
     }
 }
+
+#[derive(Component, Serialize, Deserialize)]
+struct A;
+
+#[derive(Component, Serialize, Deserialize)]
+struct B;
+
+#[derive(Component, Serialize, Deserialize)]
+struct C;
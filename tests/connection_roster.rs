@@ -0,0 +1,109 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use test_log::test;
+
+#[test]
+fn moderator() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            ConnectionRosterPlugin,
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app.world_mut().entity_mut(client).insert(Moderator);
+    server_app
+        .world_mut()
+        .spawn(ConnectedClient { max_size: 1200 });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut entries: Vec<_> = client_app
+        .world_mut()
+        .query::<&ConnectionEntry>()
+        .iter(client_app.world())
+        .map(|entry| (entry.phase, entry.protocol))
+        .collect();
+    entries.sort_by_key(|&(phase, _)| phase == ConnectionPhase::Handshaking);
+    assert_eq!(
+        entries,
+        [
+            (ConnectionPhase::Authorized, ProtocolStatus::Matched),
+            (ConnectionPhase::Handshaking, ProtocolStatus::Unknown),
+        ]
+    );
+}
+
+#[test]
+fn regular_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            ConnectionRosterPlugin,
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut entries = client_app.world_mut().query::<&ConnectionEntry>();
+    assert_eq!(
+        entries.iter(client_app.world()).len(),
+        0,
+        "only moderators should receive the roster"
+    );
+    assert_eq!(
+        server_app
+            .world_mut()
+            .query::<&ConnectionEntry>()
+            .iter(server_app.world())
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            ConnectionRosterPlugin,
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+    server_app.disconnect_client(&mut client_app);
+
+    let mut entries = server_app.world_mut().query::<&ConnectionEntry>();
+    assert_eq!(
+        entries.iter(server_app.world()).len(),
+        0,
+        "entry should be despawned with its client"
+    );
+}
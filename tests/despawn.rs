@@ -1,5 +1,8 @@
 use bevy::{prelude::*, state::app::StatesPlugin};
 use bevy_replicon::{
+    client::destructive_gate::{
+        AppDestructiveGateExt, DestructiveGates, DestructiveKind, GateDecision,
+    },
     prelude::*,
     shared::server_entity_map::ServerEntityMap,
     test_app::{ServerTestAppExt, TestClientEntity},
@@ -49,6 +52,118 @@ fn single() {
     assert!(entity_map.to_server().is_empty());
 }
 
+#[test]
+fn forgotten() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, ReplicateOnceThenForget, TestComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Let the server forget the entity.
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = *client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .unwrap();
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app.world().get_entity(client_entity).is_err(),
+        "despawn should be replicated for forgotten entities"
+    );
+}
+
+#[test]
+fn gated() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ));
+    }
+    client_app
+        .insert_resource(Busy(true))
+        .set_destructive_gate::<Interactable>(defer_if_busy, u32::MAX)
+        .finish();
+    server_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = *client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .unwrap();
+
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(Interactable);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world().get_entity(client_entity).is_ok());
+    let gates = client_app.world().resource::<DestructiveGates>();
+    assert!(gates.is_pending(client_entity));
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+
+    client_app.update();
+    assert!(client_app.world().get_entity(client_entity).is_ok());
+
+    client_app.world_mut().resource_mut::<Busy>().0 = false;
+    client_app.update();
+
+    assert!(client_app.world().get_entity(client_entity).is_err());
+    let gates = client_app.world().resource::<DestructiveGates>();
+    assert!(!gates.is_pending(client_entity));
+}
+
 #[test]
 fn resource() {
     let mut server_app = App::new();
@@ -357,6 +472,117 @@ fn visibility_lose() {
     assert_eq!(remote.iter(client_app.world()).len(), 0);
 }
 
+#[test]
+fn forgotten_visibility_lose() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_visibility_filter::<EntityVisibility>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(EntityVisibility);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, ReplicateOnceThenForget, EntityVisibility));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Let the server forget the entity.
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut remote = client_app.world_mut().query::<&Remote>();
+    assert_eq!(remote.iter(client_app.world()).len(), 1);
+
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .remove::<EntityVisibility>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        remote.iter(client_app.world()).len(),
+        0,
+        "visibility loss should be replicated for forgotten entities"
+    );
+}
+
+#[test]
+fn level_switch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_level_visibility()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(LevelTag(0));
+
+    let entity1 = server_app.world_mut().spawn((Replicated, LevelTag(0))).id();
+    let entity2 = server_app.world_mut().spawn((Replicated, LevelTag(1))).id();
+    let entity3 = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut remote = client_app.world_mut().query::<&Remote>();
+    assert_eq!(remote.iter(client_app.world()).len(), 2);
+
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(LevelTag(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        remote.iter(client_app.world()).len(),
+        2,
+        "entity from the old level should be despawned and from the new level spawned"
+    );
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(!entity_map.to_client().contains_key(&entity1));
+    assert!(entity_map.to_client().contains_key(&entity2));
+    assert!(entity_map.to_client().contains_key(&entity3));
+}
+
 #[test]
 fn visibility_lose_with_component_scope() {
     let mut server_app = App::new();
@@ -436,12 +662,79 @@ fn with_visibility_gain_and_signature() {
     assert_eq!(messages.drain_sent().len(), 0);
 }
 
+#[test]
+fn after_ack() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = *client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .unwrap();
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(DespawnAfterAck::default());
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world().get_entity(client_entity).is_err());
+
+    let server_entity_ref = server_app.world().entity(server_entity);
+    assert!(!server_entity_ref.contains::<Replicated>());
+    let despawn = server_entity_ref.get::<DespawnAfterAck>().unwrap();
+    assert!(despawn.is_hidden());
+    assert_eq!(despawn.pending().count(), 1);
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    assert!(server_app.world().get_entity(server_entity).is_err());
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct TestComponent;
 
 #[derive(Resource, Deserialize, Serialize)]
 struct TestResource;
 
+#[derive(Component)]
+struct Interactable;
+
+#[derive(Resource)]
+struct Busy(bool);
+
+fn defer_if_busy(world: &World, _entity: Entity, kind: DestructiveKind) -> GateDecision {
+    assert_eq!(kind, DestructiveKind::Despawn);
+    if world.resource::<Busy>().0 {
+        GateDecision::Defer
+    } else {
+        GateDecision::Apply
+    }
+}
+
 #[derive(Component)]
 #[component(immutable)]
 struct EntityVisibility;
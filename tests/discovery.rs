@@ -0,0 +1,106 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    shared::backend::unconnected_messages::{UnconnectedMessages, UnconnectedPeer},
+    test_app::ServerTestAppExt,
+};
+use test_log::test;
+
+#[test]
+fn ping() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    let mut connected_app = App::new();
+    for app in [&mut server_app, &mut client_app, &mut connected_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            ServerDiscoveryPlugin,
+        ))
+        .finish();
+    }
+
+    let info = ServerInfo {
+        name: "Test".into(),
+        max_players: Some(2),
+        payload: vec![1, 2],
+    };
+    server_app.insert_resource(info.clone());
+    server_app.connect_client(&mut connected_app);
+
+    client_app.init_resource::<Discovered>().add_observer(
+        |discovered: On<ServerDiscovered>, mut servers: ResMut<Discovered>| {
+            servers.push(discovered.event().clone());
+        },
+    );
+
+    client_app.world_mut().trigger(PingServer {
+        peer: UnconnectedPeer::new(0),
+    });
+
+    forward(&mut client_app, &mut server_app, UnconnectedPeer::new(1));
+    server_app.update();
+    forward(&mut server_app, &mut client_app, UnconnectedPeer::new(0));
+    client_app.update();
+
+    let mut clients = server_app
+        .world_mut()
+        .query_filtered::<(), With<ConnectedClient>>();
+    assert_eq!(
+        clients.iter(server_app.world()).count(),
+        1,
+        "pinging peer shouldn't be connected"
+    );
+
+    let protocol = *server_app.world().resource::<ProtocolHash>();
+    let discovered = client_app.world().resource::<Discovered>();
+    let [server] = discovered.as_slice() else {
+        panic!("server should answer once");
+    };
+    assert_eq!(server.peer, UnconnectedPeer::new(0));
+    assert_eq!(server.info, info);
+    assert_eq!(server.players, 1);
+    assert_eq!(server.protocol, protocol);
+}
+
+#[test]
+fn not_running() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            ServerDiscoveryPlugin,
+        ))
+        .finish();
+    }
+
+    server_app.init_resource::<ServerInfo>();
+
+    client_app.world_mut().trigger(PingServer {
+        peer: UnconnectedPeer::new(0),
+    });
+
+    forward(&mut client_app, &mut server_app, UnconnectedPeer::new(1));
+    server_app.update();
+
+    let mut messages = server_app.world_mut().resource_mut::<UnconnectedMessages>();
+    assert_eq!(messages.drain_sent().count(), 0);
+}
+
+/// Emulates the backend by moving unconnected messages between apps.
+fn forward(from_app: &mut App, to_app: &mut App, sender: UnconnectedPeer) {
+    let mut from_messages = from_app.world_mut().resource_mut::<UnconnectedMessages>();
+    let sent: Vec<_> = from_messages.drain_sent().collect();
+
+    let mut to_messages = to_app.world_mut().resource_mut::<UnconnectedMessages>();
+    for (_, message) in sent {
+        to_messages.insert_received(sender, message);
+    }
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Discovered(Vec<ServerDiscovered>);
@@ -0,0 +1,57 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    server::dry_run::{DryRunClient, DryRunStats},
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn discard() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+    ))
+    .replicate::<A>()
+    .finish();
+
+    app.world_mut()
+        .resource_mut::<NextState<ServerState>>()
+        .set(ServerState::Running);
+    let client = app
+        .world_mut()
+        .spawn((ConnectedClient { max_size: 1200 }, DryRunClient))
+        .id();
+
+    let entity = app.world_mut().spawn((Replicated, A(0))).id();
+
+    app.update();
+
+    let messages = app.world().resource::<ServerMessages>();
+    assert_eq!(messages.iter_sent().len(), 0);
+
+    let stats = *app.world().get::<DryRunStats>(client).unwrap();
+    assert_eq!(stats.messages, 1);
+    assert!(stats.bytes > 0);
+
+    app.world_mut().get_mut::<A>(entity).unwrap().0 = 1;
+
+    app.update();
+
+    let mutations_stats = *app.world().get::<DryRunStats>(client).unwrap();
+    assert_eq!(mutations_stats.messages, 2);
+    assert!(mutations_stats.bytes > stats.bytes);
+
+    app.update();
+
+    let last_stats = *app.world().get::<DryRunStats>(client).unwrap();
+    assert_eq!(
+        last_stats.messages, mutations_stats.messages,
+        "acknowledged mutations shouldn't be resent"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct A(u8);
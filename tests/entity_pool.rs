@@ -0,0 +1,224 @@
+use bevy::{ecs::entity_disabling::Disabled, prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn reuse() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            EntityPoolPlugin,
+        ))
+        .replicate::<Health>()
+        .register_template(TEMPLATE, EntityTemplate::default().with(Health(1)))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Poolable, TEMPLATE))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<Template>>()
+        .single(client_app.world())
+        .unwrap();
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(Visual);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let pooled = client_app.world().entity(client_entity);
+    assert!(pooled.contains::<Disabled>());
+    assert!(
+        pooled.contains::<Visual>(),
+        "client-only components should be kept"
+    );
+    assert!(!pooled.contains::<Remote>());
+    assert!(!pooled.contains::<Template>());
+    assert!(!pooled.contains::<Health>());
+    assert_eq!(client_app.world().resource::<EntityPool>().len(TEMPLATE), 1);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, Poolable, TEMPLATE, Health(2)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let reused = client_app.world().entity(client_entity);
+    assert!(!reused.contains::<Disabled>());
+    assert!(reused.contains::<Visual>());
+    assert!(reused.contains::<Remote>());
+    assert_eq!(*reused.get::<Health>().unwrap(), Health(2));
+    assert!(client_app.world().resource::<EntityPool>().is_empty());
+}
+
+#[test]
+fn not_poolable() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            EntityPoolPlugin,
+        ))
+        .replicate::<Health>()
+        .register_template(TEMPLATE, EntityTemplate::default().with(Health(1)))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, TEMPLATE)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<Template>>()
+        .single(client_app.world())
+        .unwrap();
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world().get_entity(client_entity).is_err());
+    assert!(client_app.world().resource::<EntityPool>().is_empty());
+}
+
+#[test]
+fn capacity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            EntityPoolPlugin,
+        ))
+        .replicate::<Health>()
+        .register_template(TEMPLATE, EntityTemplate::default().with(Health(1)))
+        .finish();
+    }
+    client_app
+        .world_mut()
+        .resource_mut::<EntityPool>()
+        .set_capacity(1);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity1 = server_app
+        .world_mut()
+        .spawn((Replicated, Poolable, TEMPLATE))
+        .id();
+    let server_entity2 = server_app
+        .world_mut()
+        .spawn((Replicated, Poolable, TEMPLATE))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(server_entity1);
+    server_app.world_mut().despawn(server_entity2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut entities = client_app
+        .world_mut()
+        .query_filtered::<(), With<Disabled>>();
+    assert_eq!(
+        entities.iter(client_app.world()).len(),
+        1,
+        "entities above the capacity should be despawned"
+    );
+    assert_eq!(client_app.world().resource::<EntityPool>().len(TEMPLATE), 1);
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            EntityPoolPlugin,
+        ))
+        .replicate::<Health>()
+        .register_template(TEMPLATE, EntityTemplate::default().with(Health(1)))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Poolable, TEMPLATE))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert_eq!(client_app.world().resource::<EntityPool>().len(TEMPLATE), 1);
+
+    server_app.disconnect_client(&mut client_app);
+
+    assert!(client_app.world().resource::<EntityPool>().is_empty());
+    let mut entities = client_app
+        .world_mut()
+        .query_filtered::<(), With<Disabled>>();
+    assert_eq!(entities.iter(client_app.world()).len(), 0);
+}
+
+const TEMPLATE: Template = Template(0);
+
+#[derive(Component, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Health(u32);
+
+/// Client-only component.
+#[derive(Component)]
+struct Visual;
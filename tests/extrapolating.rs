@@ -0,0 +1,132 @@
+use core::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    client::extrapolating::AppExtrapolationExt, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn extrapolation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TICK_DURATION))
+        .replicate::<Position>()
+        .finish();
+    }
+    client_app
+        .insert_resource(InterpolationDelay::Ticks(1))
+        .extrapolate(advance_position, blend_position);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Position(0.0)))
+        .id();
+
+    for value in 1..=5 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        server_app
+            .world_mut()
+            .get_mut::<Position>(server_entity)
+            .unwrap()
+            .0 = value as f32;
+    }
+
+    let mut positions = client_app
+        .world_mut()
+        .query_filtered::<&Position, With<Remote>>();
+    let &last_position = positions.single(client_app.world()).unwrap();
+    assert_eq!(last_position, Position(4.0));
+
+    // Emulate lost mutations.
+    for _ in 0..3 {
+        client_app.update();
+    }
+
+    let &extrapolated = positions.single(client_app.world()).unwrap();
+    assert!(
+        extrapolated.0 > last_position.0,
+        "position should be advanced after the delay"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let &blended = positions.single(client_app.world()).unwrap();
+    assert!(
+        blended.0 > 5.0 && blended.0 < extrapolated.0,
+        "position should be blended towards the received value"
+    );
+
+    client_app.update();
+
+    let &position = positions.single(client_app.world()).unwrap();
+    assert_eq!(position, Position(5.0));
+}
+
+#[test]
+fn extrapolation_without_delay() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TICK_DURATION))
+        .replicate::<Position>()
+        .finish();
+    }
+    client_app.extrapolate(advance_position, blend_position);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, Position(0.0)));
+
+    for _ in 0..3 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    for _ in 0..3 {
+        client_app.update();
+    }
+
+    let mut positions = client_app
+        .world_mut()
+        .query_filtered::<&Position, With<Remote>>();
+    let &position = positions.single(client_app.world()).unwrap();
+    assert_eq!(position, Position(0.0));
+}
+
+const TICK_DURATION: Duration = Duration::from_millis(10);
+
+/// Moves by 1 unit per tick.
+fn advance_position(position: &mut Position, dt: Duration) {
+    position.0 += dt.as_secs_f32() / TICK_DURATION.as_secs_f32();
+}
+
+fn blend_position(from: &Position, to: &Position, factor: f32) -> Position {
+    Position(from.0 + (to.0 - from.0) * factor)
+}
+
+#[derive(Component, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Position(f32);
@@ -84,6 +84,34 @@ fn write_diff() {
     assert_eq!(component.0, 3);
 }
 
+#[test]
+fn write_bitmap() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RepliconPlugins));
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<BitmapComponent>::new_bitmap())
+            });
+
+    let component = BitmapComponent {
+        a: Some(1),
+        c: Some(3),
+        i: Some(9),
+        value: 10,
+        ..Default::default()
+    };
+    let mut entity = app.world_mut().spawn(component.clone());
+    let data = entity.serialize(fns_id, tick);
+    assert_eq!(data.len(), 2 + 3 + 1, "bitmap, 3 present values and value");
+
+    entity.remove::<BitmapComponent>();
+    entity.apply_write(data, fns_id, tick);
+    assert_eq!(*entity.get::<BitmapComponent>().unwrap(), component);
+}
+
 #[test]
 fn remove() {
     let mut app = App::new();
@@ -410,6 +438,20 @@ fn mark_despawned(_ctx: &DespawnCtx, mut entity: EntityWorldMut) {
 #[derive(Component, Serialize, Deserialize, PartialEq, Debug)]
 struct DiffComponent(u8);
 
+#[derive(Component, PresenceBitmap, Default, PartialEq, Debug, Clone)]
+struct BitmapComponent {
+    a: Option<u8>,
+    b: Option<u8>,
+    c: Option<u8>,
+    d: Option<u8>,
+    e: Option<u8>,
+    f: Option<u8>,
+    g: Option<u8>,
+    h: Option<u8>,
+    i: Option<u8>,
+    value: u8,
+}
+
 #[derive(Serialize, Deserialize)]
 struct AddValue(u8);
 
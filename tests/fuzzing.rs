@@ -0,0 +1,121 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    bytes::Bytes, client::fuzzing, postcard_utils, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn unknown_flags() {
+    let mut app = create_app();
+
+    let mut message = Bytes::from_static(&[u8::MAX, 0]);
+    assert!(fuzzing::apply_update_message(app.world_mut(), &mut message).is_err());
+
+    let mut message = Bytes::from_static(&[u8::MAX, 0, 0, 0, 0]);
+    assert!(fuzzing::apply_mutate_message(app.world_mut(), &mut message).is_err());
+}
+
+#[test]
+fn invalid_data_size() {
+    let mut app = create_app();
+
+    let mut message = Vec::new();
+    postcard_utils::to_extend_mut(&CHANGES_FLAG, &mut message).unwrap();
+    postcard_utils::to_extend_mut(&RepliconTick::new(1), &mut message).unwrap();
+    postcard_utils::entity_to_extend_mut(&Entity::PLACEHOLDER, &mut message).unwrap();
+    postcard_utils::to_extend_mut(&usize::MAX, &mut message).unwrap();
+
+    let mut message = Bytes::from(message);
+    assert!(fuzzing::apply_update_message(app.world_mut(), &mut message).is_err());
+}
+
+#[test]
+fn unknown_fns_id() {
+    let mut app = create_app();
+
+    let mut data = Vec::new();
+    postcard_utils::to_extend_mut(&usize::MAX, &mut data).unwrap(); // Functions ID.
+    data.extend(1u32.to_le_bytes()); // Component size.
+    data.push(0);
+
+    let mut message = Vec::new();
+    postcard_utils::to_extend_mut(&CHANGES_FLAG, &mut message).unwrap();
+    postcard_utils::to_extend_mut(&RepliconTick::new(1), &mut message).unwrap();
+    postcard_utils::entity_to_extend_mut(&Entity::PLACEHOLDER, &mut message).unwrap();
+    postcard_utils::to_extend_mut(&data.len(), &mut message).unwrap();
+    message.extend(data);
+
+    let mut message = Bytes::from(message);
+    assert!(
+        fuzzing::apply_update_message(app.world_mut(), &mut message).is_ok(),
+        "unknown components should be skipped"
+    );
+}
+
+#[test]
+fn invalid_component_size() {
+    let mut app = create_app();
+
+    let mut data = Vec::new();
+    postcard_utils::to_extend_mut(&usize::MAX, &mut data).unwrap(); // Functions ID.
+    data.extend(u32::MAX.to_le_bytes()); // Component size.
+
+    let mut message = Vec::new();
+    postcard_utils::to_extend_mut(&CHANGES_FLAG, &mut message).unwrap();
+    postcard_utils::to_extend_mut(&RepliconTick::new(1), &mut message).unwrap();
+    postcard_utils::entity_to_extend_mut(&Entity::PLACEHOLDER, &mut message).unwrap();
+    postcard_utils::to_extend_mut(&data.len(), &mut message).unwrap();
+    message.extend(data);
+
+    let mut message = Bytes::from(message);
+    assert!(fuzzing::apply_update_message(app.world_mut(), &mut message).is_err());
+}
+
+#[test]
+fn arbitrary_bytes() {
+    let mut app = create_app();
+
+    // Simple deterministic generator to cover various prefixes without extra dependencies.
+    let mut state = 0x2545_f491_u32;
+    for len in 0..256 {
+        let data: Vec<u8> = (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+
+        let mut message = Bytes::from(data.clone());
+        let _ = fuzzing::apply_update_message(app.world_mut(), &mut message);
+
+        let mut message = Bytes::from(data);
+        let _ = fuzzing::apply_mutate_message(app.world_mut(), &mut message);
+    }
+}
+
+fn create_app() -> App {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+}
+
+/// Bit for changes in update message flags.
+const CHANGES_FLAG: u8 = 0b00010000;
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(usize);
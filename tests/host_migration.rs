@@ -0,0 +1,164 @@
+use bevy::{ecs::entity::hash_map::EntityHashMap, prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    server::{host_migration::HostSnapshot, server_tick::ServerTick},
+    shared::server_entity_map::ServerEntityMap,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn restore() {
+    let mut old_host = App::new();
+    let mut new_host = App::new();
+    for app in [&mut old_host, &mut new_host] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .replicate::<MappedComponent>()
+        .finish();
+    }
+
+    let old_entity1 = old_host
+        .world_mut()
+        .spawn((Replicated, TestComponent(1)))
+        .id();
+    let old_entity2 = old_host
+        .world_mut()
+        .spawn((Replicated, MappedComponent(old_entity1)))
+        .id();
+
+    old_host.update();
+    old_host.update();
+
+    let snapshot = HostSnapshot::capture(old_host.world_mut()).unwrap();
+    assert_eq!(snapshot.entities.len(), 2);
+
+    let entity_map = snapshot
+        .restore(new_host.world_mut(), &Default::default())
+        .unwrap();
+
+    let new_entity1 = *entity_map.get(&old_entity1).unwrap();
+    let new_entity2 = *entity_map.get(&old_entity2).unwrap();
+
+    let component = new_host.world().get::<TestComponent>(new_entity1).unwrap();
+    assert_eq!(component.0, 1);
+
+    let mapped_component = new_host
+        .world()
+        .get::<MappedComponent>(new_entity2)
+        .unwrap();
+    assert_eq!(
+        mapped_component.0, new_entity1,
+        "entities inside components should be mapped"
+    );
+
+    for entity in [new_entity1, new_entity2] {
+        assert!(new_host.world().get::<Replicated>(entity).is_some());
+    }
+
+    let old_tick = **old_host.world().resource::<ServerTick>();
+    let new_tick = **new_host.world().resource::<ServerTick>();
+    assert_eq!(new_tick, old_tick);
+}
+
+#[test]
+fn restore_existing() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(1)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let existing: EntityHashMap<_> = client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .clone();
+    let client_entity = *existing.get(&server_entity).unwrap();
+
+    server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
+
+    let snapshot = HostSnapshot::capture(server_app.world_mut()).unwrap();
+    assert_eq!(snapshot.clients.len(), 0, "test client has no network ID");
+
+    let entity_map = snapshot.restore(client_app.world_mut(), &existing).unwrap();
+    assert_eq!(entity_map.get(&server_entity), Some(&client_entity));
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(client_entity.contains::<Replicated>());
+    assert!(!client_entity.contains::<Remote>());
+    assert_eq!(client_entity.get::<TestComponent>().unwrap().0, 2);
+
+    let mut entities = client_app.world_mut().query::<&TestComponent>();
+    assert_eq!(
+        entities.iter(client_app.world()).len(),
+        1,
+        "existing entity should be reused"
+    );
+}
+
+#[test]
+fn restore_without_plugins() {
+    let mut old_host = App::new();
+    old_host
+        .add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+
+    old_host.world_mut().spawn((Replicated, TestComponent(1)));
+
+    old_host.update();
+
+    let snapshot = HostSnapshot::capture(old_host.world_mut()).unwrap();
+
+    let mut new_host = App::new();
+    new_host.add_plugins(MinimalPlugins).finish();
+    let entities_before = new_host.world().entities().count_spawned();
+
+    assert!(
+        snapshot
+            .restore(new_host.world_mut(), &Default::default())
+            .is_err()
+    );
+    assert_eq!(
+        new_host.world().entities().count_spawned(),
+        entities_before,
+        "world shouldn't be modified on error"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u8);
+
+#[derive(Component, Deserialize, Serialize)]
+struct MappedComponent(#[entities] Entity);
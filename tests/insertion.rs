@@ -1,12 +1,17 @@
 use bevy::{ecs::system::SystemState, prelude::*, state::app::StatesPlugin};
 use bevy_replicon::{
     client::confirm_history::{ConfirmHistory, EntityReplicated},
+    postcard_utils,
     prelude::*,
     server::server_tick::ServerTick,
     shared::{
+        backend::channels::ServerChannel,
         replication::{
             deferred_entity::DeferredEntity,
-            registry::{ctx::WriteCtx, receive_fns},
+            registry::{
+                ctx::{SerializeCtx, WriteCtx},
+                receive_fns, rule_fns,
+            },
         },
         server_entity_map::ServerEntityMap,
     },
@@ -312,6 +317,51 @@ fn multiple_components_sequential() {
     assert_eq!(components.iter(client_app.world()).len(), 1);
 }
 
+#[test]
+fn optional() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>();
+    }
+    server_app
+        .replicate_optional(|app| {
+            app.replicate::<Immutable>();
+        })
+        .finish();
+    client_app.finish();
+
+    assert_eq!(
+        server_app.world().resource::<ProtocolHash>(),
+        client_app.world().resource::<ProtocolHash>(),
+        "optional rules shouldn't affect the protocol"
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, Immutable(true), A));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<&Remote, (With<A>, Without<Immutable>)>();
+    assert_eq!(
+        components.iter(client_app.world()).len(),
+        1,
+        "unknown component should be skipped"
+    );
+}
+
 #[test]
 fn rule_split_across_ticks() {
     let mut server_app = App::new();
@@ -388,6 +438,63 @@ fn receive_fns() {
     assert_eq!(components.iter(client_app.world()).len(), 1);
 }
 
+#[test]
+fn outdated_version() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ));
+    }
+
+    // Emulate a server that hasn't been updated to the new layout yet.
+    server_app
+        .replicate_with(
+            RuleFns::new(serialize_old_health, rule_fns::default_deserialize)
+                .versioned(0, decode_health),
+        )
+        .finish();
+    client_app
+        .replicate_with(RuleFns::<Health>::default().versioned(1, decode_health))
+        .finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, Health(5))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let health = client_app
+        .world_mut()
+        .query::<&Health>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(health.0, 5);
+
+    server_app
+        .world_mut()
+        .get_mut::<Health>(server_entity)
+        .unwrap()
+        .0 = 7;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let health = client_app
+        .world_mut()
+        .query::<&Health>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(health.0, 7);
+}
+
 #[test]
 fn marker() {
     let mut server_app = App::new();
@@ -855,6 +962,155 @@ fn visibility_gain() {
     assert_eq!(components.iter(client_app.world()).len(), 1);
 }
 
+#[test]
+fn visibility_gain_with_mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<Health>()
+        .add_visibility_filter::<EntityVisibility>()
+        .finish();
+    }
+    server_app.init_resource::<InsertionStats>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Health(1), EntityVisibility))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&Health>();
+    assert_eq!(components.iter(client_app.world()).len(), 0);
+
+    // Mutate in the same tick as the client gains visibility.
+    let mut health = server_app
+        .world_mut()
+        .get_mut::<Health>(server_entity)
+        .unwrap();
+    health.0 = 2;
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(EntityVisibility);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let messages = client_app.world().resource::<ClientMessages>();
+    assert_eq!(messages.received_count(ServerChannel::Updates), 1);
+    assert_eq!(
+        messages.received_count(ServerChannel::Mutations),
+        0,
+        "mutation should be folded into the insertion"
+    );
+
+    client_app.update();
+
+    let health = components.single(client_app.world()).unwrap();
+    assert_eq!(health.0, 2, "insertion should contain the current value");
+
+    let stats = server_app.world().resource::<InsertionStats>();
+    assert_eq!(stats.insertions, 1);
+    assert_eq!(stats.folded_mutations, 1);
+}
+
+#[test]
+fn client_filter() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate_filtered::<B, ClientWith<Developer>>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, A, B));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query_filtered::<Has<B>, With<A>>();
+    let has_b = components.single(client_app.world()).unwrap();
+    assert!(!has_b, "client without developer mode shouldn't receive it");
+
+    let client_entity = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(Developer);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let has_b = components.single(client_app.world()).unwrap();
+    assert!(
+        has_b,
+        "component should be sent after the client gains the marker"
+    );
+}
+
+#[test]
+fn replication_batch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .finish();
+    }
+
+    client_app.init_resource::<BatchInsertions>().add_observer(
+        |_: On<Insert, A>,
+         batch: Res<ReplicationBatch>,
+         mut insertions: ResMut<BatchInsertions>| {
+            insertions.push(batch.is_active());
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, A));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app.world_mut().spawn(A);
+
+    let insertions = client_app.world().resource::<BatchInsertions>();
+    assert_eq!(**insertions, [true, false]);
+
+    let batch = client_app.world().resource::<ReplicationBatch>();
+    assert!(!batch.is_active());
+}
+
 #[derive(Component, Deserialize, Serialize)]
 #[component(storage = "Table")]
 struct Table;
@@ -876,6 +1132,9 @@ struct A;
 #[derive(Component, Deserialize, Serialize)]
 struct B;
 
+#[derive(Resource, Default, Deref, DerefMut)]
+struct BatchInsertions(Vec<bool>);
+
 #[derive(Component)]
 struct ReplaceMarker;
 
@@ -924,6 +1183,30 @@ impl VisibilityFilter for AllExceptVisibility {
     }
 }
 
+#[derive(Component)]
+struct Developer;
+
+#[derive(Component, Deserialize, Serialize)]
+struct Health(u32);
+
+/// Serializes [`Health`] with the layout from version 0.
+fn serialize_old_health(
+    _ctx: &mut SerializeCtx,
+    health: &Health,
+    message: &mut Vec<u8>,
+) -> Result<()> {
+    let health = health.0 as u8;
+    postcard_utils::to_extend_mut(&health, message)?;
+    Ok(())
+}
+
+/// Decodes [`Health`] from older layouts.
+fn decode_health(version: u16, message: &mut Bytes, _ctx: &mut WriteCtx) -> Result<Health> {
+    assert_eq!(version, 0);
+    let health: u8 = postcard_utils::from_buf(message)?;
+    Ok(Health(health.into()))
+}
+
 /// Deserializes [`OriginalComponent`], but ignores it and inserts [`ReplacedComponent`].
 fn replace(
     ctx: &mut WriteCtx,
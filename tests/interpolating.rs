@@ -0,0 +1,88 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn hold_back() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+    client_app.insert_resource(InterpolationDelay::Ticks(2));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let spawn_tick = **server_app.world().resource::<ServerTick>();
+    let mut interpolating = client_app.world_mut().query::<&Interpolating>();
+    let &Interpolating { ready_at_tick } = interpolating.single(client_app.world()).unwrap();
+    assert_eq!(ready_at_tick, spawn_tick + 2);
+
+    for value in 1..=2 {
+        assert_eq!(interpolating.iter(client_app.world()).len(), 1);
+
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<TestComponent>(server_entity)
+            .unwrap();
+        component.0 = value;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    assert_eq!(
+        interpolating.iter(client_app.world()).len(),
+        0,
+        "should be removed after receiving enough ticks"
+    );
+}
+
+#[test]
+fn without_delay() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, TestComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut interpolating = client_app.world_mut().query::<&Interpolating>();
+    assert_eq!(interpolating.iter(client_app.world()).len(), 0);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u32);
@@ -0,0 +1,157 @@
+use core::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    prelude::*,
+    shared::{replication::registry::ReplicationRegistry, server_entity_map::ServerEntityMap},
+    test_app::ServerTestAppExt,
+};
+use test_log::test;
+
+#[test]
+fn unacknowledged() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            LeakDetectionPlugin {
+                ack_timeout: TIMEOUT,
+                ..Default::default()
+            },
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TIMEOUT))
+        .finish();
+    }
+    server_app
+        .init_resource::<Leaks>()
+        .add_observer(record_leaks);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(server_entity);
+
+    // Don't exchange to simulate a client that never acknowledges.
+    for _ in 0..3 {
+        server_app.update();
+    }
+
+    let leaks = server_app.world().resource::<Leaks>();
+    assert_eq!(leaks.0, [LeakKind::Unacknowledged(server_entity)]);
+}
+
+#[test]
+fn acknowledged() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            LeakDetectionPlugin {
+                ack_timeout: TIMEOUT,
+                ..Default::default()
+            }
+            .with_report_interval(TIMEOUT),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TIMEOUT))
+        .finish();
+    }
+    server_app
+        .init_resource::<Leaks>()
+        .add_observer(record_leaks);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(server_entity);
+
+    for _ in 0..3 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let leaks = server_app.world().resource::<Leaks>();
+    assert!(leaks.0.is_empty());
+}
+
+#[test]
+fn report() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            LeakDetectionPlugin::default().with_report_interval(TIMEOUT),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TIMEOUT))
+        .finish();
+    }
+    server_app
+        .init_resource::<Leaks>()
+        .add_observer(record_leaks);
+
+    // Simulate a despawn function that doesn't despawn.
+    let mut registry = client_app.world_mut().resource_mut::<ReplicationRegistry>();
+    registry.despawn = |_, _| {};
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Simulate an entity that the server never replicated.
+    let unknown_entity = server_app.world_mut().spawn_empty().id();
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    client_app
+        .world_mut()
+        .resource_mut::<ServerEntityMap>()
+        .insert(unknown_entity, client_entity);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let leaks = server_app.world().resource::<Leaks>();
+    assert_eq!(
+        leaks.0,
+        [LeakKind::Unknown(unknown_entity), LeakKind::Orphaned(1)]
+    );
+}
+
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+fn record_leaks(leak: On<ReplicationLeak>, mut leaks: ResMut<Leaks>) {
+    leaks.0.push(leak.kind);
+}
+
+#[derive(Resource, Default)]
+struct Leaks(Vec<LeakKind>);
@@ -0,0 +1,196 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    shared::replication::registry::{ctx::SerializeCtx, rule_fns},
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn bands() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_lod::<Position, Interest>(
+            interest_distance,
+            [
+                (10.0, RuleFns::default()),
+                (
+                    100.0,
+                    RuleFns::new(serialize_rounded, rule_fns::default_deserialize),
+                ),
+            ],
+        )
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(Interest(0.0));
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Position(1.4)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut positions = client_app
+        .world_mut()
+        .query_filtered::<&Position, With<Remote>>();
+    let &position = positions.single(client_app.world()).unwrap();
+    assert_eq!(
+        position,
+        Position(1.4),
+        "near entities should be sent as is"
+    );
+
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(Interest(50.0));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let &position = positions.single(client_app.world()).unwrap();
+    assert_eq!(
+        position,
+        Position(1.0),
+        "band change should resend the component"
+    );
+
+    server_app
+        .world_mut()
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 2.4;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let &position = positions.single(client_app.world()).unwrap();
+    assert_eq!(
+        position,
+        Position(2.0),
+        "far entities should be sent with the functions of their band"
+    );
+
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(Interest(200.0));
+    server_app
+        .world_mut()
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 3.4;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let &position = positions.single(client_app.world()).unwrap();
+    assert_eq!(
+        position,
+        Position(2.0),
+        "entities beyond all bands shouldn't be updated"
+    );
+}
+
+#[test]
+fn per_client() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_lod::<Position, Interest>(
+            interest_distance,
+            [
+                (10.0, RuleFns::default()),
+                (
+                    100.0,
+                    RuleFns::new(serialize_rounded, rule_fns::default_deserialize),
+                ),
+            ],
+        )
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    for (client_app, interest) in [(&client_app1, 0.0), (&client_app2, 50.0)] {
+        let client = **client_app.world().resource::<TestClientEntity>();
+        server_app
+            .world_mut()
+            .entity_mut(client)
+            .insert(Interest(interest));
+    }
+
+    server_app.world_mut().spawn((Replicated, Position(1.4)));
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+    }
+
+    let position1 = client_app1
+        .world_mut()
+        .query::<&Position>()
+        .single(client_app1.world())
+        .unwrap();
+    assert_eq!(*position1, Position(1.4));
+
+    let position2 = client_app2
+        .world_mut()
+        .query::<&Position>()
+        .single(client_app2.world())
+        .unwrap();
+    assert_eq!(*position2, Position(1.0));
+}
+
+fn interest_distance(client: EntityRef, entity: EntityRef) -> f32 {
+    let interest = client.get::<Interest>().unwrap();
+    let position = entity.get::<Position>().unwrap();
+    (interest.0 - position.0).abs()
+}
+
+fn serialize_rounded(
+    ctx: &mut SerializeCtx,
+    position: &Position,
+    message: &mut Vec<u8>,
+) -> Result<()> {
+    rule_fns::default_serialize(ctx, &Position(position.0.round()), message)
+}
+
+/// Position of interest for a client.
+#[derive(Component)]
+struct Interest(f32);
+
+#[derive(Component, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Position(f32);
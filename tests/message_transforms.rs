@@ -0,0 +1,211 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn round_trip() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .add_client_message::<TestMessage>(Channel::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(MessageTransforms::default().with(Xor).with(Tag));
+    client_app
+        .world_mut()
+        .insert_resource(ClientMessageTransforms(
+            MessageTransforms::default().with(Xor).with(Tag),
+        ));
+
+    server_app.world_mut().spawn((Replicated, TestComponent));
+    client_app.world_mut().write_message(TestMessage);
+
+    server_app.update();
+    client_app.update();
+
+    let messages = server_app.world().resource::<ServerMessages>();
+    assert!(
+        messages
+            .iter_sent()
+            .all(|(_, _, message)| message.last() == Some(&Tag::BYTE)),
+        "all messages should be transformed"
+    );
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.update();
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(), With<TestComponent>>();
+    assert_eq!(components.iter(client_app.world()).len(), 1);
+
+    let messages = server_app
+        .world()
+        .resource::<Messages<FromClient<TestMessage>>>();
+    assert_eq!(messages.len(), 1);
+
+    let errors = server_app.world().resource::<Messages<ReplicationError>>();
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn outbound_error() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(MessageTransforms::default().with(Fail));
+
+    server_app.world_mut().spawn((Replicated, TestComponent));
+
+    server_app.update();
+
+    let messages = server_app.world().resource::<ServerMessages>();
+    assert_eq!(messages.iter_sent().len(), 0);
+
+    let errors: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<ReplicationError>>()
+        .drain()
+        .collect();
+    assert!(!errors.is_empty());
+    for error in errors {
+        assert_eq!(error.kind, ReplicationErrorKind::Serialize);
+        assert_eq!(error.client, Some(client_entity));
+    }
+}
+
+#[test]
+fn inbound_error() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Only the client expects tagged messages.
+    client_app
+        .world_mut()
+        .insert_resource(ClientMessageTransforms(
+            MessageTransforms::default().with(Tag),
+        ));
+
+    server_app.world_mut().spawn((Replicated, TestComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(), With<TestComponent>>();
+    assert_eq!(components.iter(client_app.world()).len(), 0);
+
+    let errors: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Messages<ReplicationError>>()
+        .drain()
+        .collect();
+    assert!(!errors.is_empty());
+    for error in errors {
+        assert_eq!(error.kind, ReplicationErrorKind::Deserialize);
+        assert_eq!(error.client, None);
+    }
+}
+
+/// Inverts all bits.
+struct Xor;
+
+impl MessageTransform for Xor {
+    fn outbound(&mut self, message: Bytes) -> Result<Bytes> {
+        Ok(message.iter().map(|byte| !byte).collect())
+    }
+
+    fn inbound(&mut self, message: Bytes) -> Result<Bytes> {
+        self.outbound(message)
+    }
+}
+
+/// Appends a known byte and verifies it on receive.
+///
+/// Used together with [`Xor`] to ensure that transformations are reverted in the right order.
+struct Tag;
+
+impl Tag {
+    const BYTE: u8 = 0x7F;
+}
+
+impl MessageTransform for Tag {
+    fn outbound(&mut self, message: Bytes) -> Result<Bytes> {
+        let mut tagged = message.to_vec();
+        tagged.push(Self::BYTE);
+        Ok(tagged.into())
+    }
+
+    fn inbound(&mut self, mut message: Bytes) -> Result<Bytes> {
+        if message.last() != Some(&Self::BYTE) {
+            return Err("message should end with a tag".into());
+        }
+        message.truncate(message.len() - 1);
+        Ok(message)
+    }
+}
+
+struct Fail;
+
+impl MessageTransform for Fail {
+    fn outbound(&mut self, _message: Bytes) -> Result<Bytes> {
+        Err("unable to transform".into())
+    }
+
+    fn inbound(&mut self, _message: Bytes) -> Result<Bytes> {
+        Err("unable to transform".into())
+    }
+}
+
+#[derive(Component, Serialize, Deserialize)]
+struct TestComponent;
+
+#[derive(Message, Serialize, Deserialize)]
+struct TestMessage;
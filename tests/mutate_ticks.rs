@@ -43,6 +43,55 @@ fn without_changes() {
     );
 }
 
+#[test]
+fn statuses() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin {
+                track_mutate_messages: true,
+                ..ServerPlugin::new(PostUpdate)
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let statuses: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<MutateMessageStatus>>()
+        .drain()
+        .collect();
+    let sent_position = statuses
+        .iter()
+        .position(|status| {
+            status.server_tick == server_tick
+                && status.status == MutateStatus::Sent { messages_count: 1 }
+        })
+        .expect("empty message should be sent for the tick");
+    let sent = statuses[sent_position];
+    let acknowledged_position = statuses
+        .iter()
+        .position(|status| {
+            status.index == sent.index && status.status == MutateStatus::Acknowledged
+        })
+        .expect("sent message should be acknowledged");
+    assert!(acknowledged_position > sent_position);
+    assert_eq!(statuses[acknowledged_position].server_tick, server_tick);
+}
+
 #[test]
 fn one_message() {
     let mut server_app = App::new();
@@ -1,7 +1,11 @@
-use core::time::Duration;
+use core::{
+    num::NonZeroU32,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 use test_log::test;
 
-use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
 use bevy_replicon::{
     client::{
         ServerUpdateTick,
@@ -10,10 +14,15 @@ use bevy_replicon::{
     prelude::*,
     server::server_tick::ServerTick,
     shared::{
+        backend::channels::ServerChannel,
         replication::{
             deferred_entity::DeferredEntity,
             receive_markers::MarkerConfig,
-            registry::{ctx::WriteCtx, receive_fns},
+            registry::{
+                ctx::{SerializeCtx, WriteCtx},
+                receive_fns,
+                rule_fns::{self, ConflictPolicy},
+            },
         },
         server_entity_map::ServerEntityMap,
     },
@@ -118,6 +127,88 @@ fn package_size_component() {
     assert_eq!(component.0, BIG_DATA);
 }
 
+#[test]
+fn oversized_entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<VecComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let oversized_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false), VecComponent::default()))
+        .id();
+    let small_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Can't fit into a single packet even without other entities.
+    const BIG_DATA: &[u8] = &[1; 2000];
+    let mut entity = server_app.world_mut().entity_mut(oversized_entity);
+    entity.get_mut::<BoolComponent>().unwrap().0 = true;
+    entity.get_mut::<VecComponent>().unwrap().0 = BIG_DATA.to_vec();
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(small_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+
+    let messages = server_app.world().resource::<ServerMessages>();
+    for channel in [ServerChannel::Updates, ServerChannel::Mutations] {
+        let count = messages
+            .iter_sent()
+            .filter(|&(_, channel_id, _)| channel_id == channel.into())
+            .count();
+        assert_eq!(
+            count, 1,
+            "oversized entity should be sent in updates and the small one in mutations"
+        );
+    }
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app
+        .world_mut()
+        .query::<(&BoolComponent, Option<&VecComponent>)>();
+    assert_eq!(components.iter(client_app.world()).count(), 2);
+    for (bool_component, vec_component) in components.iter(client_app.world()) {
+        assert!(bool_component.0);
+        if let Some(vec_component) = vec_component {
+            assert_eq!(vec_component.0, BIG_DATA);
+        }
+    }
+
+    server_app.update();
+
+    let messages = server_app.world().resource::<ServerMessages>();
+    assert_eq!(
+        messages.iter_sent().count(),
+        0,
+        "update messages are reliable, so the entity shouldn't be resent"
+    );
+}
+
 #[test]
 fn many_components() {
     let mut server_app = App::new();
@@ -699,7 +790,7 @@ fn marker_with_history_old_update() {
 }
 
 #[test]
-fn many_entities() {
+fn conflict_keep_local() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -708,77 +799,71 @@ fn many_entities() {
             StatesPlugin,
             RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
         ))
-        .replicate::<BoolComponent>()
+        .replicate_with(
+            RuleFns::<IntComponent>::default().with_conflict_policy(ConflictPolicy::KeepLocal),
+        )
         .finish();
     }
 
     server_app.connect_client(&mut client_app);
 
-    // Spawn many entities to cover message splitting.
-    const ENTITIES_COUNT: usize = 300;
-    server_app
+    let server_entity = server_app
         .world_mut()
-        .spawn_batch([(Replicated, BoolComponent(false)); ENTITIES_COUNT]);
+        .spawn((Replicated, IntComponent(0)))
+        .id();
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    let mut remote = client_app.world_mut().query::<&Remote>();
-    assert_eq!(remote.iter(client_app.world()).len(), ENTITIES_COUNT);
-
-    for mut component in server_app
+    // Change value on server without local changes.
+    server_app
         .world_mut()
-        .query::<&mut BoolComponent>()
-        .iter_mut(server_app.world_mut())
-    {
-        component.0 = true;
-    }
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
+    server_app.exchange_with_client(&mut client_app);
 
-    for component in client_app
+    let mut component = client_app
         .world_mut()
-        .query::<&BoolComponent>()
-        .iter(client_app.world())
-    {
-        assert!(component.0);
-    }
-}
-
-#[test]
-fn with_insertion() {
-    let mut server_app = App::new();
-    let mut client_app = App::new();
-    for app in [&mut server_app, &mut client_app] {
-        app.add_plugins((
-            MinimalPlugins,
-            StatesPlugin,
-            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
-        ))
-        .replicate::<BoolComponent>()
-        .replicate::<TestComponent>()
-        .finish();
-    }
+        .query::<&mut IntComponent>()
+        .single_mut(client_app.world_mut())
+        .unwrap();
+    assert_eq!(
+        component.0, 1,
+        "value without local changes should be updated"
+    );
 
-    server_app.connect_client(&mut client_app);
+    // Change value locally.
+    component.0 = 5;
 
-    let server_entity = server_app
+    server_app
         .world_mut()
-        .spawn((Replicated, BoolComponent(false)))
-        .id();
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
-    server_app.exchange_with_client(&mut client_app);
 
-    let mut server_entity = server_app.world_mut().entity_mut(server_entity);
-    server_entity.get_mut::<BoolComponent>().unwrap().0 = true;
-    server_entity.insert(TestComponent);
+    let component = client_app
+        .world_mut()
+        .query::<&IntComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(component.0, 5, "local value should be kept");
+
+    server_app
+        .world_mut()
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = 3;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
@@ -786,14 +871,17 @@ fn with_insertion() {
 
     let component = client_app
         .world_mut()
-        .query_filtered::<&BoolComponent, With<TestComponent>>()
+        .query::<&IntComponent>()
         .single(client_app.world())
         .unwrap();
-    assert!(component.0);
+    assert_eq!(
+        component.0, 3,
+        "value without new local changes should be updated"
+    );
 }
 
 #[test]
-fn with_removal() {
+fn conflict_custom() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -802,8 +890,9 @@ fn with_removal() {
             StatesPlugin,
             RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
         ))
-        .replicate::<BoolComponent>()
-        .replicate::<TestComponent>()
+        .replicate_with(
+            RuleFns::<IntComponent>::default().with_conflict_policy(ConflictPolicy::Custom(merge)),
+        )
         .finish();
     }
 
@@ -811,7 +900,7 @@ fn with_removal() {
 
     let server_entity = server_app
         .world_mut()
-        .spawn((Replicated, BoolComponent(false), TestComponent))
+        .spawn((Replicated, IntComponent(0)))
         .id();
 
     server_app.update();
@@ -819,24 +908,54 @@ fn with_removal() {
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    let mut server_entity = server_app.world_mut().entity_mut(server_entity);
-    server_entity.get_mut::<BoolComponent>().unwrap().0 = true;
-    server_entity.remove::<TestComponent>();
+    client_app
+        .world_mut()
+        .query::<&mut IntComponent>()
+        .single_mut(client_app.world_mut())
+        .unwrap()
+        .0 = 5;
+
+    server_app
+        .world_mut()
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
+    server_app.exchange_with_client(&mut client_app);
 
     let component = client_app
         .world_mut()
-        .query_filtered::<&BoolComponent, Without<TestComponent>>()
+        .query::<&IntComponent>()
         .single(client_app.world())
         .unwrap();
-    assert!(component.0);
+    assert_eq!(component.0, 7, "received value should be merged");
+
+    server_app
+        .world_mut()
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = 3;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&IntComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(
+        component.0, 3,
+        "value should be overwritten after merge without new local changes"
+    );
 }
 
 #[test]
-fn with_despawn() {
+fn crdt_merge() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -845,7 +964,7 @@ fn with_despawn() {
             StatesPlugin,
             RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
         ))
-        .replicate::<BoolComponent>()
+        .replicate_crdt::<MaxComponent>()
         .finish();
     }
 
@@ -853,7 +972,7 @@ fn with_despawn() {
 
     let server_entity = server_app
         .world_mut()
-        .spawn((Replicated, BoolComponent(false)))
+        .spawn((Replicated, MaxComponent(0)))
         .id();
 
     server_app.update();
@@ -861,31 +980,51 @@ fn with_despawn() {
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    let mut components = client_app.world_mut().query::<&BoolComponent>();
-    assert_eq!(components.iter(client_app.world()).len(), 1);
+    client_app
+        .world_mut()
+        .query::<&mut MaxComponent>()
+        .single_mut(client_app.world_mut())
+        .unwrap()
+        .0 = 5;
 
-    let mut component = server_app
+    server_app
         .world_mut()
-        .get_mut::<BoolComponent>(server_entity)
-        .unwrap();
-    component.0 = true;
+        .get_mut::<MaxComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
 
-    // Update without client to send mutate message.
     server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
 
-    server_app.world_mut().despawn(server_entity);
+    let component = client_app
+        .world_mut()
+        .query::<&MaxComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(component.0, 5, "received value should be merged");
+
+    server_app
+        .world_mut()
+        .get_mut::<MaxComponent>(server_entity)
+        .unwrap()
+        .0 = 7;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
-    server_app.exchange_with_client(&mut client_app);
-    server_app.update(); // Let server receive an update to trigger acknowledgment.
 
-    assert_eq!(components.iter(client_app.world()).len(), 0);
+    let component = client_app
+        .world_mut()
+        .query::<&MaxComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(component.0, 7);
 }
 
 #[test]
-fn with_client_despawn() {
+fn crdt_refresh() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -894,45 +1033,47 @@ fn with_client_despawn() {
             StatesPlugin,
             RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
         ))
-        .replicate::<BoolComponent>()
+        .replicate_crdt::<MaxComponent>()
         .finish();
     }
 
     server_app.connect_client(&mut client_app);
 
-    let server_entity = server_app
-        .world_mut()
-        .spawn((Replicated, BoolComponent(false)))
-        .id();
+    server_app.world_mut().spawn((Replicated, MaxComponent(3)));
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    let mut components = client_app
+    // Simulate diverged state.
+    client_app
         .world_mut()
-        .query_filtered::<Entity, With<BoolComponent>>();
-    let client_entity = components.single(client_app.world()).unwrap();
+        .query::<&mut MaxComponent>()
+        .single_mut(client_app.world_mut())
+        .unwrap()
+        .0 = 0;
 
-    let mut component = server_app
+    for _ in 0..MaxComponent::REFRESH_INTERVAL {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let component = client_app
         .world_mut()
-        .get_mut::<BoolComponent>(server_entity)
+        .query::<&MaxComponent>()
+        .single(client_app.world())
         .unwrap();
-    component.0 = true;
-
-    // Despawn the entity that should receive the mutation on the client.
-    client_app.world_mut().despawn(client_entity);
-
-    server_app.update();
-    server_app.exchange_with_client(&mut client_app);
-    client_app.update();
-
-    assert_eq!(components.iter(client_app.world()).len(), 0);
+    assert_eq!(
+        component.0, 3,
+        "unchanged value should be periodically re-sent"
+    );
 }
 
 #[test]
-fn buffering() {
+fn refresh_offset() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -941,54 +1082,60 @@ fn buffering() {
             StatesPlugin,
             RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
         ))
-        .replicate::<BoolComponent>()
+        .replicate_with((
+            RuleFns::<IntComponent>::default(),
+            ReplicationMode::Refresh {
+                interval: NonZeroU32::new(2).unwrap(),
+            },
+        ))
         .finish();
     }
 
     server_app.connect_client(&mut client_app);
 
-    let server_entity = server_app
+    server_app
         .world_mut()
-        .spawn((Replicated, BoolComponent(false)))
-        .id();
+        .spawn_batch([(Replicated, IntComponent(3)), (Replicated, IntComponent(3))]);
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    // Artificially reset the update tick to force the next received mutation to be buffered.
-    let mut update_tick = client_app.world_mut().resource_mut::<ServerUpdateTick>();
-    let previous_tick = *update_tick;
-    *update_tick = Default::default();
-    let mut component = server_app
-        .world_mut()
-        .get_mut::<BoolComponent>(server_entity)
-        .unwrap();
-    component.0 = true;
+    // Simulate diverged state.
+    let mut components = client_app.world_mut().query::<&mut IntComponent>();
+    for mut component in components.iter_mut(client_app.world_mut()) {
+        component.0 = 0;
+    }
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    let mut components = client_app.world_mut().query::<&BoolComponent>();
-    let component = components.single(client_app.world()).unwrap();
-    assert!(!component.0, "client should buffer the mutation");
-
-    // Restore the update tick to let the buffered mutation apply
-    *client_app.world_mut().resource_mut::<ServerUpdateTick>() = previous_tick;
+    let refreshed = components
+        .iter(client_app.world())
+        .filter(|component| component.0 == 3)
+        .count();
+    assert_eq!(
+        refreshed, 1,
+        "refreshes of entities sent on the same tick should be spread"
+    );
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
+    server_app.exchange_with_client(&mut client_app);
 
-    let component = components.single(client_app.world()).unwrap();
-    assert!(component.0, "buffered mutation should be applied");
+    assert!(
+        components
+            .iter(client_app.world())
+            .all(|component| component.0 == 3)
+    );
 }
 
 #[test]
-fn old_ignored() {
+fn serialization_cache() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -997,38 +1144,1097 @@ fn old_ignored() {
             StatesPlugin,
             RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
         ))
-        .replicate::<MappedComponent>()
+        .replicate_with(RuleFns::new(
+            serialize_counted,
+            rule_fns::default_deserialize::<CountedComponent>,
+        ))
+        .with_serialization_cache()
         .finish();
     }
 
     server_app.connect_client(&mut client_app);
 
-    let entities_before = client_app.world().entities().count_spawned();
-
-    let server_map_entity = server_app.world_mut().spawn_empty().id();
     let server_entity = server_app
         .world_mut()
-        .spawn((Replicated, MappedComponent(server_map_entity)))
+        .spawn((Replicated, CountedComponent(false)))
         .id();
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
+    assert_eq!(SERIALIZATIONS.load(Ordering::Relaxed), 1);
 
-    // Change the value, but don't process it on client.
-    let new_entity1 = server_app.world_mut().spawn_empty().id();
+    // Change value.
     let mut component = server_app
         .world_mut()
-        .get_mut::<MappedComponent>(server_entity)
+        .get_mut::<CountedComponent>(server_entity)
         .unwrap();
-    component.0 = new_entity1;
+    component.0 = true;
 
+    // Don't exchange to keep the mutation unacknowledged, so it's sent again on the next tick.
+    server_app.update();
     server_app.update();
+    assert_eq!(
+        SERIALIZATIONS.load(Ordering::Relaxed),
+        2,
+        "unchanged component should be serialized only once"
+    );
+
     server_app.exchange_with_client(&mut client_app);
+    client_app.update();
 
-    // Change the value again to trigger another message.
-    let new_entity2 = server_app.world_mut().spawn_empty().id();
+    let component = client_app
+        .world_mut()
+        .query::<&CountedComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert!(component.0);
+}
+
+#[test]
+fn echo_suppression() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .with_echo_suppression(1)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+    }
+
+    // Simulate an accepted write from the first client.
+    let client = **client_app1.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(BoolComponent(true))
+        .accept_client_write::<BoolComponent>(client);
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+    }
+
+    let component1 = client_app1
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app1.world())
+        .unwrap();
+    assert!(!component1.0, "mutation shouldn't be echoed to the writer");
+
+    let component2 = client_app2
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app2.world())
+        .unwrap();
+    assert!(component2.0, "other clients should receive the mutation");
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    client_app1.update();
+
+    let component1 = client_app1
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app1.world())
+        .unwrap();
+    assert!(
+        component1.0,
+        "unacknowledged change should be sent after the window"
+    );
+}
+
+#[test]
+fn resend() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Corrupt the state on the client.
+    let mut components = client_app.world_mut().query::<&mut BoolComponent>();
+    components.single_mut(client_app.world_mut()).unwrap().0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = components.single(client_app.world()).unwrap();
+    assert!(component.0, "unchanged component shouldn't be sent");
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .resend::<BoolComponent>(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = components.single(client_app.world()).unwrap();
+    assert!(!component.0, "component should be resent");
+}
+
+#[test]
+fn resend_all() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<SecondaryComponent>()
+        .without_full_refresh()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false), SecondaryComponent(false)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Corrupt the state on the client.
+    let mut components = client_app
+        .world_mut()
+        .query::<(&mut BoolComponent, &mut SecondaryComponent)>();
+    let (mut component, mut secondary) = components.single_mut(client_app.world_mut()).unwrap();
+    component.0 = true;
+    secondary.0 = true;
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app.world_mut().entity_mut(client).resend_all();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let (component, secondary) = components.single(client_app.world()).unwrap();
+    assert!(!component.0, "component should be refreshed");
+    assert!(
+        secondary.0,
+        "component excluded from refresh shouldn't be sent"
+    );
+}
+
+#[test]
+fn keyframes() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_keyframed::<KeyframedComponent>()
+        .with_keyframe_interval(2)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app1);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, KeyframedComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    client_app1.update();
+    server_app.exchange_with_client(&mut client_app1);
+
+    for value in 1..=3 {
+        server_app
+            .world_mut()
+            .get_mut::<KeyframedComponent>(server_entity)
+            .unwrap()
+            .0 = value;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app1);
+        client_app1.update();
+        server_app.exchange_with_client(&mut client_app1);
+
+        let component = client_app1
+            .world_mut()
+            .query::<&KeyframedComponent>()
+            .single(client_app1.world())
+            .unwrap();
+        assert_eq!(component.0, value, "keyframes and deltas should be applied");
+    }
+
+    // Connect in the middle of the interval to receive the value with the last keyframe.
+    server_app.connect_client(&mut client_app2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app2);
+    client_app2.update();
+    server_app.exchange_with_client(&mut client_app2);
+
+    server_app
+        .world_mut()
+        .get_mut::<KeyframedComponent>(server_entity)
+        .unwrap()
+        .0 = 4;
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+
+        let component = client_app
+            .world_mut()
+            .query::<&KeyframedComponent>()
+            .single(client_app.world())
+            .unwrap();
+        assert_eq!(component.0, 4);
+    }
+}
+
+#[test]
+fn outbound() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_with(RuleFns::<IntComponent>::default().with_outbound(clamp_or_veto))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, IntComponent(VETOED)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(), (With<Remote>, With<IntComponent>)>();
+    assert_eq!(
+        components.iter(client_app.world()).len(),
+        0,
+        "vetoed insertion shouldn't be sent"
+    );
+
+    server_app
+        .world_mut()
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = u8::MAX;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&IntComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(component.0, CLAMP);
+
+    let component = server_app
+        .world()
+        .get::<IntComponent>(server_entity)
+        .unwrap();
+    assert_eq!(
+        component.0,
+        u8::MAX,
+        "value in the world shouldn't be changed"
+    );
+
+    server_app
+        .world_mut()
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = VETOED;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&IntComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(component.0, CLAMP, "vetoed mutation shouldn't be sent");
+
+    server_app
+        .world_mut()
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&IntComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert_eq!(component.0, 1);
+}
+
+#[test]
+fn forgotten() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, ReplicateOnceThenForget, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert!(
+        !component.0,
+        "mutations shouldn't be replicated for forgotten entities"
+    );
+}
+
+#[test]
+fn forgotten_after_ack() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, ReplicateOnceThenForget, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Mutate before the client acknowledges the entity.
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert!(
+        component.0,
+        "mutations should be replicated until the entity is acknowledged"
+    );
+}
+
+#[test]
+fn many_entities() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Spawn many entities to cover message splitting.
+    const ENTITIES_COUNT: usize = 300;
+    server_app
+        .world_mut()
+        .spawn_batch([(Replicated, BoolComponent(false)); ENTITIES_COUNT]);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut remote = client_app.world_mut().query::<&Remote>();
+    assert_eq!(remote.iter(client_app.world()).len(), ENTITIES_COUNT);
+
+    for mut component in server_app
+        .world_mut()
+        .query::<&mut BoolComponent>()
+        .iter_mut(server_app.world_mut())
+    {
+        component.0 = true;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    for component in client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .iter(client_app.world())
+    {
+        assert!(component.0);
+    }
+}
+
+#[test]
+fn with_insertion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut server_entity = server_app.world_mut().entity_mut(server_entity);
+    server_entity.get_mut::<BoolComponent>().unwrap().0 = true;
+    server_entity.insert(TestComponent);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query_filtered::<&BoolComponent, With<TestComponent>>()
+        .single(client_app.world())
+        .unwrap();
+    assert!(component.0);
+}
+
+#[test]
+fn with_removal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false), TestComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut server_entity = server_app.world_mut().entity_mut(server_entity);
+    server_entity.get_mut::<BoolComponent>().unwrap().0 = true;
+    server_entity.remove::<TestComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query_filtered::<&BoolComponent, Without<TestComponent>>()
+        .single(client_app.world())
+        .unwrap();
+    assert!(component.0);
+}
+
+#[test]
+fn with_despawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    assert_eq!(components.iter(client_app.world()).len(), 1);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    // Update without client to send mutate message.
+    server_app.update();
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update(); // Let server receive an update to trigger acknowledgment.
+
+    assert_eq!(components.iter(client_app.world()).len(), 0);
+}
+
+#[test]
+fn with_client_despawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<BoolComponent>>();
+    let client_entity = components.single(client_app.world()).unwrap();
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    // Despawn the entity that should receive the mutation on the client.
+    client_app.world_mut().despawn(client_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(components.iter(client_app.world()).len(), 0);
+}
+
+#[test]
+fn buffering() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Artificially reset the update tick to force the next received mutation to be buffered.
+    let mut update_tick = client_app.world_mut().resource_mut::<ServerUpdateTick>();
+    let previous_tick = *update_tick;
+    *update_tick = Default::default();
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    let component = components.single(client_app.world()).unwrap();
+    assert!(!component.0, "client should buffer the mutation");
+
+    // Restore the update tick to let the buffered mutation apply
+    *client_app.world_mut().resource_mut::<ServerUpdateTick>() = previous_tick;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = components.single(client_app.world()).unwrap();
+    assert!(component.0, "buffered mutation should be applied");
+}
+
+#[test]
+fn jitter_buffer() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+    client_app.insert_resource(MutationJitterBuffer::Ticks(2));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    assert_eq!(
+        components.iter(client_app.world()).len(),
+        1,
+        "updates shouldn't be delayed"
+    );
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = components.single(client_app.world()).unwrap();
+    assert!(!component.0, "mutation should be held");
+
+    client_app.update();
+
+    let component = components.single(client_app.world()).unwrap();
+    assert!(!component.0, "mutation should be held for 2 ticks");
+
+    client_app.update();
+
+    let component = components.single(client_app.world()).unwrap();
+    assert!(component.0, "mutation should be released after the delay");
+}
+
+#[test]
+fn jitter_buffer_without_tick_rate() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+    client_app.insert_resource(MutationJitterBuffer::Time(Duration::from_millis(10)));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    // Receive both ticks at once, so the client can't estimate the tick rate.
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    let component = components.single(client_app.world()).unwrap();
+    assert!(
+        !component.0,
+        "mutation should be held until the tick rate is estimated"
+    );
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = false;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = components.single(client_app.world()).unwrap();
+    assert!(
+        component.0,
+        "mutation should be released after the delay once the tick rate is estimated"
+    );
+}
+
+#[test]
+fn applied_triggers() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+    client_app
+        .init_resource::<AppliedEntities>()
+        .add_observer(
+            |updated: On<UpdateApplied>, mut applied: ResMut<AppliedEntities>| {
+                applied.updated.push(updated.entities.clone());
+            },
+        )
+        .add_observer(
+            |mutated: On<MutationsApplied>, mut applied: ResMut<AppliedEntities>| {
+                applied.mutated.push(mutated.entities.clone());
+            },
+        );
+
+    server_app.connect_client(&mut client_app);
+    client_app
+        .world_mut()
+        .insert_resource(AppliedEntities::default());
+
+    let server_entity1 = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    let server_entity2 = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let mut client_entities = [
+        *entity_map.to_client().get(&server_entity1).unwrap(),
+        *entity_map.to_client().get(&server_entity2).unwrap(),
+    ];
+    client_entities.sort();
+
+    let mut applied = client_app.world_mut().resource_mut::<AppliedEntities>();
+    assert_eq!(applied.updated, [client_entities]);
+    assert!(applied.mutated.is_empty());
+    applied.updated.clear();
+
+    for server_entity in [server_entity1, server_entity2] {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<BoolComponent>(server_entity)
+            .unwrap();
+        component.0 = true;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let applied = client_app.world().resource::<AppliedEntities>();
+    assert!(applied.updated.is_empty());
+    assert_eq!(
+        applied.mutated,
+        [client_entities],
+        "mutations for a tick should be reported once"
+    );
+}
+
+#[test]
+fn timer_correction() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )))
+        .replicate_timer(TimerFns::new(|timer: &mut TimerComponent, delta| {
+            timer.0 += delta
+        }))
+        .finish();
+    }
+    client_app.insert_resource(MutationJitterBuffer::Ticks(2));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TimerComponent(Duration::ZERO)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for _ in 0..5 {
+        let mut timer = server_app
+            .world_mut()
+            .get_mut::<TimerComponent>(server_entity)
+            .unwrap();
+        timer.0 = Duration::from_secs(1);
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let mut components = client_app.world_mut().query::<&TimerComponent>();
+    let timer = components.single(client_app.world()).unwrap();
+    assert!(
+        timer.0 > Duration::from_secs(1),
+        "received value should be advanced by the time it was held"
+    );
+
+    let server_timer = server_app
+        .world()
+        .get::<TimerComponent>(server_entity)
+        .unwrap();
+    assert_eq!(
+        server_timer.0,
+        Duration::from_secs(1),
+        "server shouldn't correct its own values"
+    );
+}
+
+#[test]
+fn old_ignored() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<MappedComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let entities_before = client_app.world().entities().count_spawned();
+
+    let server_map_entity = server_app.world_mut().spawn_empty().id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, MappedComponent(server_map_entity)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change the value, but don't process it on client.
+    let new_entity1 = server_app.world_mut().spawn_empty().id();
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<MappedComponent>(server_entity)
+        .unwrap();
+    component.0 = new_entity1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change the value again to trigger another message.
+    let new_entity2 = server_app.world_mut().spawn_empty().id();
     let mut component = server_app
         .world_mut()
         .get_mut::<MappedComponent>(server_entity)
@@ -1130,6 +2336,67 @@ fn acknowledgment() {
     );
 }
 
+#[test]
+fn reliable_acknowledgment() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .insert_resource(BackendCapabilities::ORDERED_ONLY)
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert!(component.0);
+
+    // Take and drop ack message.
+    let mut messages = client_app.world_mut().resource_mut::<ClientMessages>();
+    assert_eq!(messages.drain_sent().count(), 1);
+
+    server_app.update();
+
+    let messages = server_app.world().resource::<ServerMessages>();
+    let mutations = messages
+        .iter_sent()
+        .filter(|&(_, channel_id, _)| channel_id == ServerChannel::Mutations.into())
+        .count();
+    assert_eq!(
+        mutations, 0,
+        "server shouldn't wait for acks when mutations are reliable"
+    );
+}
+
 #[test]
 fn before_acknowledgement() {
     let mut server_app = App::new();
@@ -1348,15 +2615,218 @@ fn after_pause() {
     );
 }
 
+#[test]
+fn secondary_channel() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate_with(
+            RuleFns::<SecondaryComponent>::default().on_channel(MutationChannel::Secondary),
+        )
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false), SecondaryComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut entity = server_app.world_mut().entity_mut(server_entity);
+    entity.get_mut::<BoolComponent>().unwrap().0 = true;
+    entity.get_mut::<SecondaryComponent>().unwrap().0 = true;
+
+    server_app.update();
+
+    let channels = server_app.world().resource::<RepliconChannels>();
+    let messages = server_app.world().resource::<ServerMessages>();
+    for channel in [MutationChannel::Primary, MutationChannel::Secondary] {
+        let channel_id = channels.mutation_channel(channel).unwrap();
+        let count = messages
+            .iter_sent()
+            .filter(|&(_, id, _)| id == channel_id)
+            .count();
+        assert_eq!(count, 1, "each channel should contain its own mutations");
+    }
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (bool_component, secondary_component) = client_app
+        .world_mut()
+        .query::<(&BoolComponent, &SecondaryComponent)>()
+        .single(client_app.world())
+        .unwrap();
+    assert!(bool_component.0);
+    assert!(secondary_component.0);
+}
+
+#[test]
+fn secondary_acknowledgment() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate_with(
+            RuleFns::<SecondaryComponent>::default().on_channel(MutationChannel::Secondary),
+        )
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false), SecondaryComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut entity = server_app.world_mut().entity_mut(server_entity);
+    entity.get_mut::<BoolComponent>().unwrap().0 = true;
+    entity.get_mut::<SecondaryComponent>().unwrap().0 = true;
+
+    server_app.update();
+
+    let secondary_id = server_app
+        .world()
+        .resource::<RepliconChannels>()
+        .mutation_channel(MutationChannel::Secondary)
+        .unwrap();
+
+    // Drop the message from the secondary channel.
+    server_app
+        .world_mut()
+        .resource_mut::<ServerMessages>()
+        .retain_sent(|&(_, channel_id, _)| channel_id != secondary_id);
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.update();
+
+    let messages = server_app.world().resource::<ServerMessages>();
+    let mutations: Vec<_> = messages
+        .iter_sent()
+        .filter(|&(_, channel_id, _)| {
+            channel_id == ServerChannel::Mutations.into() || channel_id == secondary_id
+        })
+        .map(|(_, channel_id, _)| channel_id)
+        .collect();
+    assert_eq!(
+        mutations,
+        [secondary_id],
+        "only unacknowledged channel should be resent"
+    );
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&SecondaryComponent>()
+        .single(client_app.world())
+        .unwrap();
+    assert!(component.0);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct TestComponent;
 
 #[derive(Component, Deserialize, Serialize, PartialEq, Clone, Copy)]
 struct BoolComponent(bool);
 
+#[derive(Resource, Default)]
+struct AppliedEntities {
+    updated: Vec<Vec<Entity>>,
+    mutated: Vec<Vec<Entity>>,
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct SecondaryComponent(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct IntComponent(u8);
+
+const VETOED: u8 = 5;
+const CLAMP: u8 = 10;
+
+fn clamp_or_veto(component: &IntComponent, _ctx: &SerializeCtx) -> Option<IntComponent> {
+    if component.0 == VETOED {
+        None
+    } else {
+        Some(IntComponent(component.0.min(CLAMP)))
+    }
+}
+
+/// Number of [`CountedComponent`] serializations.
+static SERIALIZATIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Component, Deserialize, Serialize)]
+struct CountedComponent(bool);
+
+fn serialize_counted(
+    ctx: &mut SerializeCtx,
+    component: &CountedComponent,
+    message: &mut Vec<u8>,
+) -> Result<()> {
+    SERIALIZATIONS.fetch_add(1, Ordering::Relaxed);
+    rule_fns::default_serialize(ctx, component, message)
+}
+
 #[derive(Component, Default, Deserialize, Serialize)]
 struct VecComponent(Vec<u8>);
 
+#[derive(Component, Deserialize, Serialize)]
+struct MaxComponent(u8);
+
+impl CrdtMerge for MaxComponent {
+    const REFRESH_INTERVAL: u32 = 2;
+
+    fn merge(&mut self, remote: Self) {
+        self.0 = self.0.max(remote.0);
+    }
+}
+
+#[derive(Component, Deserialize, Serialize, Clone, Copy)]
+struct KeyframedComponent(u8);
+
+impl Keyframed for KeyframedComponent {
+    type Delta = u8;
+
+    fn delta(&self, keyframe: &Self) -> Self::Delta {
+        self.0.wrapping_sub(keyframe.0)
+    }
+
+    fn from_delta(keyframe: &Self, delta: Self::Delta) -> Self {
+        Self(keyframe.0.wrapping_add(delta))
+    }
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TimerComponent(Duration);
+
 #[derive(Component, Deserialize, Serialize)]
 struct MappedComponent(#[entities] Entity);
 
@@ -1378,6 +2848,11 @@ struct HistoryMarker;
 #[derive(Component, Deref, DerefMut)]
 struct BoolHistory(Vec<bool>);
 
+/// Adds the received value to the local value.
+fn merge(component: &mut IntComponent, received: IntComponent, _ctx: &mut WriteCtx) {
+    component.0 += received.0;
+}
+
 /// Deserializes [`OriginalComponent`], but inserts it as [`ReplacedComponent`].
 fn replace(
     ctx: &mut WriteCtx,
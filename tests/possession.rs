@@ -0,0 +1,183 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    shared::{backend::connected_client::NetworkId, server_entity_map::ServerEntityMap},
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use test_log::test;
+
+#[test]
+fn possess_and_release() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_possession()
+        .finish();
+    }
+    client_app
+        .init_resource::<PossessionTriggers>()
+        .add_observer(
+            |_on: On<PossessionStarted>, mut triggers: ResMut<PossessionTriggers>| {
+                triggers.started += 1;
+            },
+        )
+        .add_observer(
+            |_on: On<PossessionEnded>, mut triggers: ResMut<PossessionTriggers>| {
+                triggers.ended += 1;
+            },
+        );
+
+    server_app.connect_client(&mut client_app);
+
+    let network_id = NetworkId::new(0);
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app.world_mut().entity_mut(client).insert(network_id);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, PossessedBy::new(network_id)))
+        .id();
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let client_entity = *client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .unwrap();
+    let entity = client_app.world().entity(client_entity);
+    assert_eq!(
+        entity.get::<PossessedBy>(),
+        Some(&PossessedBy::new(network_id))
+    );
+    assert!(entity.contains::<Possessed>());
+
+    let triggers = client_app.world().resource::<PossessionTriggers>();
+    assert_eq!(triggers.started, 1);
+    assert_eq!(triggers.ended, 0);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<PossessedBy>();
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let entity = client_app.world().entity(client_entity);
+    assert!(!entity.contains::<PossessedBy>());
+    assert!(!entity.contains::<Possessed>());
+
+    let triggers = client_app.world().resource::<PossessionTriggers>();
+    assert_eq!(triggers.started, 1);
+    assert_eq!(triggers.ended, 1);
+}
+
+#[test]
+fn other_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_possession()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(NetworkId::new(0));
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, PossessedBy::new(NetworkId::new(1))));
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let mut possessed_by = client_app
+        .world_mut()
+        .query_filtered::<&PossessedBy, Without<Possessed>>();
+    assert_eq!(
+        possessed_by.iter(client_app.world()).len(),
+        1,
+        "only the possessing client should receive the marker"
+    );
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_possession()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let network_id = NetworkId::new(0);
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app.world_mut().entity_mut(client).insert(network_id);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, PossessedBy::new(network_id)));
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let mut possessed = client_app
+        .world_mut()
+        .query_filtered::<(), With<Possessed>>();
+    assert_eq!(possessed.iter(client_app.world()).len(), 1);
+
+    server_app.disconnect_client(&mut client_app);
+
+    assert_eq!(
+        possessed.iter(client_app.world()).len(),
+        0,
+        "possession should end on disconnect"
+    );
+}
+
+#[derive(Resource, Default)]
+struct PossessionTriggers {
+    started: usize,
+    ended: usize,
+}
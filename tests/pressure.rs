@@ -0,0 +1,129 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn serialized_size() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.insert_resource(ReplicationPressureSettings {
+        max_serialized_size: Some(0),
+        degraded_priority: 0.5,
+        ..Default::default()
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let pressure: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<ReplicationPressure>>()
+        .drain()
+        .collect();
+    assert_eq!(pressure.len(), 1);
+    assert_eq!(pressure[0].kind, PressureKind::SerializedSize);
+    assert_eq!(pressure[0].limit, 0);
+    assert!(pressure[0].value > 0);
+
+    // Change value.
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    let component = components.single(client_app.world()).unwrap();
+    assert!(!component.0, "mutation should be deprioritized");
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = components.single(client_app.world()).unwrap();
+    assert!(component.0);
+}
+
+#[test]
+fn pending_mutations() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.insert_resource(ReplicationPressureSettings {
+        max_pending_mutations: Some(0),
+        ..Default::default()
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for value in [true, false] {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<BoolComponent>(server_entity)
+            .unwrap();
+        component.0 = value;
+
+        // Don't exchange to keep mutations unacknowledged.
+        server_app.update();
+    }
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    let pressure: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Messages<ReplicationPressure>>()
+        .drain()
+        .collect();
+    assert_eq!(pressure.len(), 1);
+    assert_eq!(pressure[0].kind, PressureKind::PendingMutations(client));
+    assert_eq!(pressure[0].value, 1);
+}
+
+#[derive(Clone, Component, Copy, Deserialize, Serialize)]
+struct BoolComponent(bool);
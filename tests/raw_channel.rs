@@ -0,0 +1,106 @@
+use bevy::{ecs::system::SystemState, prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use bytes::Bytes;
+use test_log::test;
+
+#[test]
+fn client_to_server() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_raw_channel::<Test>(Channel::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut client_state = SystemState::<ClientRawChannel<Test>>::new(client_app.world_mut());
+    let mut client_channel = client_state.get_mut(client_app.world_mut()).unwrap();
+    client_channel.send(DATA).unwrap();
+
+    server_app.exchange_with_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    let mut server_state = SystemState::<ServerRawChannel<Test>>::new(server_app.world_mut());
+    let mut server_channel = server_state.get_mut(server_app.world_mut()).unwrap();
+    let received: Vec<_> = server_channel.receive().collect();
+    assert_eq!(received, [(client, Bytes::from_static(DATA))]);
+    assert_eq!(server_channel.receive().count(), 0, "should be drained");
+}
+
+#[test]
+fn server_to_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_raw_channel::<Test>(Channel::Unreliable)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    let mut server_state = SystemState::<ServerRawChannel<Test>>::new(server_app.world_mut());
+    let mut server_channel = server_state.get_mut(server_app.world_mut()).unwrap();
+    server_channel.send(client, DATA).unwrap();
+
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut client_state = SystemState::<ClientRawChannel<Test>>::new(client_app.world_mut());
+    let mut client_channel = client_state.get_mut(client_app.world_mut()).unwrap();
+    let received: Vec<_> = client_channel.receive().collect();
+    assert_eq!(received, [Bytes::from_static(DATA)]);
+}
+
+#[test]
+fn capacity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_raw_channel::<Test>(Channel::Unreliable)
+        .finish();
+    }
+    server_app
+        .world_mut()
+        .resource_mut::<RawChannels>()
+        .set_capacity::<Test>(Some(DATA.len() + 1));
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    let mut server_state = SystemState::<ServerRawChannel<Test>>::new(server_app.world_mut());
+    let mut server_channel = server_state.get_mut(server_app.world_mut()).unwrap();
+    server_channel.send(client, DATA).unwrap();
+    assert_eq!(server_channel.pending_bytes(client), DATA.len());
+
+    let rejected = server_channel.send(client, DATA).unwrap_err();
+    assert_eq!(rejected, DATA, "should return the data back");
+
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut server_channel = server_state.get_mut(server_app.world_mut()).unwrap();
+    assert_eq!(server_channel.pending_bytes(client), 0);
+    server_channel.send(client, DATA).unwrap();
+}
+
+const DATA: &[u8] = &[1, 2, 3];
+
+struct Test;
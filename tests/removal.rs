@@ -1,10 +1,16 @@
 use bevy::{prelude::*, state::app::StatesPlugin};
 use bevy_replicon::{
-    client::confirm_history::{ConfirmHistory, EntityReplicated},
+    client::{
+        confirm_history::{ConfirmHistory, EntityReplicated},
+        destructive_gate::{AppDestructiveGateExt, DestructiveKind, GateDecision},
+    },
     prelude::*,
     server::server_tick::ServerTick,
-    shared::replication::registry::receive_fns,
-    test_app::{ServerTestAppExt, TestClientEntity},
+    shared::replication::{
+        deferred_entity::DeferredEntity,
+        registry::{ctx::RemoveCtx, receive_fns},
+    },
+    test_app::{ServerTestAppExt, TestClientEntity, capture::CapturedMessage},
 };
 use serde::{Deserialize, Serialize};
 use test_log::test;
@@ -193,6 +199,98 @@ fn marker() {
     assert!(!client_entity.contains::<Required>());
 }
 
+#[test]
+fn remove_fn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .set_remove_fn::<A>(mark_removed)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, A)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<A>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(), (With<A>, With<Removed>)>();
+    assert_eq!(
+        components.iter(client_app.world()).len(),
+        1,
+        "removal should be intercepted"
+    );
+}
+
+#[test]
+fn marker_remove_fn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .register_marker::<ReplaceMarker>()
+        .replicate::<A>()
+        .set_marker_remove_fn::<ReplaceMarker, A>(mark_removed)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+
+    let client_entity = client_app
+        .world_mut()
+        .spawn((ReplaceMarker, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app.world().get::<A>(client_entity).is_some());
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<A>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(client_entity.contains::<A>());
+    assert!(client_entity.contains::<Removed>());
+}
+
 #[test]
 fn group() {
     let mut server_app = App::new();
@@ -222,18 +320,35 @@ fn group() {
         .single(client_app.world())
         .unwrap();
 
+    let before_archetypes = client_app.world().archetypes().len();
+
     server_app
         .world_mut()
         .entity_mut(server_entity)
         .remove::<(A, B)>();
 
     server_app.update();
+
+    let messages = server_app.capture_replication(&client_app);
+    let [CapturedMessage::Update(update)] = messages.as_slice() else {
+        panic!("only update message should be sent, got {messages:?}");
+    };
+    let [removals] = update.removals.as_slice() else {
+        panic!("removals should be sent for a single entity");
+    };
+    assert_eq!(removals.components.len(), 2);
+
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
 
     let client_entity = client_app.world().entity(client_entity);
     assert!(!client_entity.contains::<A>());
     assert!(!client_entity.contains::<B>());
+    assert_eq!(
+        client_app.world().archetypes().len() - before_archetypes,
+        1,
+        "should cause only a single archetype move"
+    );
 }
 
 #[test]
@@ -843,6 +958,253 @@ fn visibility_lose() {
     assert_eq!(components.iter(client_app.world()).len(), 0);
 }
 
+#[test]
+fn gated() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>();
+    }
+    client_app
+        .set_destructive_gate::<Interactable>(always_defer, 2)
+        .finish();
+    server_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, A)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<A>>()
+        .single(client_app.world())
+        .unwrap();
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(Interactable);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<A>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world().get::<A>(client_entity).is_some());
+
+    client_app.update();
+    assert!(client_app.world().get::<A>(client_entity).is_some());
+
+    client_app.update();
+    assert!(
+        client_app.world().get::<A>(client_entity).is_none(),
+        "removal should be applied after the limit"
+    );
+}
+
+#[test]
+fn gated_before_insertion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate::<B>();
+    }
+    client_app
+        .set_destructive_gate::<Interactable>(always_defer, u32::MAX)
+        .finish();
+    server_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, A)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<A>>()
+        .single(client_app.world())
+        .unwrap();
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(Interactable);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<A>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app.world().get::<A>(client_entity).is_some());
+
+    server_app.world_mut().entity_mut(server_entity).insert(B);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(
+        !client_entity.contains::<A>(),
+        "deferred removal should be applied before insertion"
+    );
+    assert!(client_entity.contains::<B>());
+}
+
+#[test]
+fn gated_before_mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate::<IntComponent>();
+    }
+    client_app
+        .set_destructive_gate::<Interactable>(always_defer, u32::MAX)
+        .finish();
+    server_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, IntComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<A>>()
+        .single(client_app.world())
+        .unwrap();
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(Interactable);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<A>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app.world().get::<A>(client_entity).is_some());
+
+    server_app
+        .world_mut()
+        .get_mut::<IntComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(
+        !client_entity.contains::<A>(),
+        "deferred removal should be applied before mutation"
+    );
+    assert_eq!(client_entity.get::<IntComponent>().unwrap().0, 1);
+}
+
+#[test]
+fn with_insertion_atomic() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate::<B>()
+        .replicate::<C>()
+        .finish();
+    }
+
+    client_app.add_observer(
+        |_: On<Insert, C>, entities: Query<(Has<A>, Has<B>), (With<Remote>, Without<C>)>| {
+            for (has_a, has_b) in &entities {
+                assert_ne!(
+                    has_a, has_b,
+                    "other entities shouldn't be observed partially updated"
+                );
+            }
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity1 = server_app.world_mut().spawn((Replicated, A)).id();
+    let server_entity2 = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Insert first to write the second entity before the first one.
+    server_app.world_mut().entity_mut(server_entity2).insert(C);
+    server_app
+        .world_mut()
+        .entity_mut(server_entity1)
+        .remove::<A>()
+        .insert(B);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(Has<A>, Has<B>), (With<Remote>, Without<C>)>();
+    let (has_a, has_b) = components.single(client_app.world()).unwrap();
+    assert!(!has_a);
+    assert!(has_b);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 #[require(Required)]
 struct A;
@@ -853,6 +1215,9 @@ struct B;
 #[derive(Component, Deserialize, Serialize)]
 struct C;
 
+#[derive(Component, Deserialize, Serialize)]
+struct IntComponent(u8);
+
 #[derive(Component, Deserialize, Serialize)]
 struct NotReplicated;
 
@@ -862,6 +1227,21 @@ struct ReplaceMarker;
 #[derive(Component, Default)]
 struct Required;
 
+#[derive(Component)]
+struct Removed;
+
+#[derive(Component)]
+struct Interactable;
+
+fn mark_removed(_ctx: &mut RemoveCtx, entity: &mut DeferredEntity) {
+    entity.insert(Removed);
+}
+
+fn always_defer(_world: &World, _entity: Entity, kind: DestructiveKind) -> GateDecision {
+    assert_eq!(kind, DestructiveKind::Removal);
+    GateDecision::Defer
+}
+
 #[derive(Component)]
 #[component(immutable)]
 struct EntityVisibility;
@@ -0,0 +1,136 @@
+use core::mem;
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    client::{
+        ServerUpdateTick,
+        replication_sink::{ClientReplicationSink, SinkMode},
+    },
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn secondary() {
+    let mut server_app = create_app();
+    let mut client_app = create_app();
+    let mut rollback_app = create_app();
+    client_app.insert_resource(ClientReplicationSink::new(
+        mem::take(rollback_app.world_mut()),
+        SinkMode::Secondary,
+    ));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(), With<TestComponent>>();
+    assert_eq!(
+        components.iter(client_app.world()).len(),
+        0,
+        "replication shouldn't be applied to the main world"
+    );
+
+    let update_tick = **client_app.world().resource::<ServerUpdateTick>();
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    assert_eq!(update_tick, server_tick);
+
+    server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut sink = client_app
+        .world_mut()
+        .resource_mut::<ClientReplicationSink>();
+    let sink_world = sink.world();
+    let mut components = sink_world.query_filtered::<&TestComponent, With<Remote>>();
+    let component = components.single(sink_world).unwrap();
+    assert_eq!(component.0, 1);
+}
+
+#[test]
+fn both() {
+    let mut server_app = create_app();
+    let mut client_app = create_app();
+    let mut rollback_app = create_app();
+    client_app.insert_resource(ClientReplicationSink::new(
+        mem::take(rollback_app.world_mut()),
+        SinkMode::Both,
+    ));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, TestComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(), (With<TestComponent>, With<Remote>)>();
+    assert_eq!(components.iter(client_app.world()).len(), 1);
+
+    let mut sink = client_app
+        .world_mut()
+        .resource_mut::<ClientReplicationSink>();
+    let sink_world = sink.world();
+    let mut components = sink_world.query_filtered::<(), (With<TestComponent>, With<Remote>)>();
+    assert_eq!(components.iter(sink_world).len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn mismatched_ids() {
+    let mut server_app = create_app();
+    let mut client_app = create_app();
+    client_app.insert_resource(ClientReplicationSink::new(
+        World::new(),
+        SinkMode::Secondary,
+    ));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, TestComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+}
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+    ))
+    .replicate::<TestComponent>()
+    .finish();
+
+    app
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u8);
@@ -0,0 +1,135 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    server::rule_stats::{RuleStats, RuleStatsPlugin},
+    shared::replication::rules::ReplicationRules,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn insertions_and_mutations() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate::<B>();
+    }
+    server_app.add_plugins(RuleStatsPlugin::default()).finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, A(0), B)).id();
+    server_app.world_mut().spawn((Replicated, A(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let a_rule = rule_index::<A>(&mut server_app);
+    let b_rule = rule_index::<B>(&mut server_app);
+
+    let stats = server_app
+        .world_mut()
+        .query::<&RuleStats>()
+        .single(server_app.world())
+        .unwrap();
+    assert_eq!(stats.iter().count(), 2);
+    assert!(stats.bytes() > 0);
+
+    let a_stat = stats.get(a_rule).unwrap();
+    assert_eq!(a_stat.components, 2);
+    assert_eq!(a_stat.messages, 1);
+    assert!(a_stat.bytes > 0);
+
+    let b_stat = stats.get(b_rule).unwrap();
+    assert_eq!(b_stat.components, 1);
+    assert_eq!(b_stat.messages, 1);
+    assert!(b_stat.bytes > 0);
+    assert!(a_stat.bytes > b_stat.bytes);
+
+    let mut component = server_app.world_mut().get_mut::<A>(server_entity).unwrap();
+    component.0 = 1;
+
+    server_app.update();
+
+    let stats = server_app
+        .world_mut()
+        .query::<&RuleStats>()
+        .single(server_app.world())
+        .unwrap();
+    assert_eq!(stats.iter().count(), 1);
+    assert!(stats.get(b_rule).is_none());
+
+    let a_stat = stats.get(a_rule).unwrap();
+    assert_eq!(a_stat.components, 1);
+    assert_eq!(a_stat.messages, 1);
+    assert_eq!(a_stat.bytes, stats.bytes());
+
+    server_app.update();
+
+    let stats = server_app
+        .world_mut()
+        .query::<&RuleStats>()
+        .single(server_app.world())
+        .unwrap();
+    assert_eq!(stats.iter().count(), 0, "nothing changed");
+    assert_eq!(stats.bytes(), 0);
+}
+
+#[test]
+fn without_stats() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>();
+    }
+    server_app.finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, A(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut stats = server_app.world_mut().query::<&RuleStats>();
+    assert!(stats.iter(server_app.world()).next().is_none());
+
+    let mut components = client_app.world_mut().query::<&A>();
+    assert_eq!(components.iter(client_app.world()).count(), 1);
+}
+
+fn rule_index<C: Component>(app: &mut App) -> usize {
+    let component_id = app.world_mut().register_component::<C>();
+    let rules = app.world().resource::<ReplicationRules>();
+    rules
+        .iter()
+        .position(|rule| {
+            rule.components
+                .iter()
+                .any(|component| component.id == component_id)
+        })
+        .unwrap()
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct A(u8);
+
+#[derive(Component, Deserialize, Serialize)]
+struct B;
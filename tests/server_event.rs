@@ -1,7 +1,9 @@
 use bevy::{ecs::entity::MapEntities, prelude::*, state::app::StatesPlugin, time::TimePlugin};
 use bevy_replicon::{
-    client::ServerUpdateTick, prelude::*, shared::server_entity_map::ServerEntityMap,
-    test_app::ServerTestAppExt,
+    client::ServerUpdateTick,
+    prelude::*,
+    shared::server_entity_map::ServerEntityMap,
+    test_app::{ServerTestAppExt, TestClientEntity},
 };
 use serde::{Deserialize, Serialize};
 use test_log::test;
@@ -76,6 +78,52 @@ fn mapped() {
     assert_eq!(mapped_entities, [client_entity]);
 }
 
+#[test]
+fn ordering() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_server_event::<Test>(Channel::Ordered)
+        .finish();
+    }
+    client_app
+        .init_resource::<EventReader<Test>>()
+        .init_resource::<Observed>()
+        .add_systems(
+            PreUpdate,
+            observe
+                .after(ClientSystems::ApplyReplication)
+                .before(ClientSystems::EmitQueuedEvents),
+        );
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+    server_app.world_mut().server_trigger(ToClients {
+        targets: SendTargets::All,
+        message: Test,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let observed = client_app.world().resource::<Observed>();
+    assert_eq!(
+        **observed,
+        Some((1, 0)),
+        "replication should be applied before events are emitted"
+    );
+
+    let reader = client_app.world().resource::<EventReader<Test>>();
+    assert_eq!(reader.events.len(), 1);
+}
+
 #[test]
 fn without_plugins() {
     let mut server_app = App::new();
@@ -200,15 +248,160 @@ fn independent() {
     assert_eq!(independent_reader.events.len(), 1);
 }
 
+#[test]
+fn entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_entity_server_message::<Ping>(Channel::Ordered)
+        .finish();
+    }
+    client_app.init_resource::<EventReader<EntityMessage<Ping>>>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().server_trigger(ToClients {
+        targets: SendTargets::All,
+        message: EntityMessage {
+            entity: server_entity,
+            message: Ping,
+        },
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = *client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .unwrap();
+
+    let reader = client_app
+        .world()
+        .resource::<EventReader<EntityMessage<Ping>>>();
+    let entities: Vec<_> = reader.events.iter().map(|event| event.entity).collect();
+    assert_eq!(entities, [client_entity]);
+}
+
+#[test]
+fn entity_after_visibility() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_visibility_filter::<EntityVisibility>()
+        .add_entity_server_message::<Ping>(Channel::Ordered)
+        .finish();
+    }
+    client_app.init_resource::<EventReader<EntityMessage<Ping>>>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, EntityVisibility))
+        .id();
+
+    server_app.world_mut().server_trigger(ToClients {
+        targets: SendTargets::All,
+        message: EntityMessage {
+            entity: server_entity,
+            message: Ping,
+        },
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let reader = client_app
+        .world()
+        .resource::<EventReader<EntityMessage<Ping>>>();
+    assert!(reader.events.is_empty());
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert(EntityVisibility);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = *client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .unwrap();
+
+    let reader = client_app
+        .world()
+        .resource::<EventReader<EntityMessage<Ping>>>();
+    let entities: Vec<_> = reader.events.iter().map(|event| event.entity).collect();
+    assert_eq!(entities, [client_entity]);
+}
+
 #[derive(Event, Serialize, Deserialize, Clone)]
 struct Test;
 
+/// Number of replicated entities and received events between replication and events.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Observed(Option<(usize, usize)>);
+
+fn observe(
+    mut observed: ResMut<Observed>,
+    reader: Res<EventReader<Test>>,
+    entities: Query<(), With<Remote>>,
+) {
+    if !entities.is_empty() && observed.is_none() {
+        **observed = Some((entities.iter().count(), reader.events.len()));
+    }
+}
+
 #[derive(Event, Serialize, Deserialize, Clone)]
 struct Independent;
 
 #[derive(Event, Serialize, Deserialize, MapEntities, Clone)]
 struct WithEntity(#[entities] Entity);
 
+#[derive(Serialize, Deserialize, Clone)]
+struct Ping;
+
+#[derive(Component)]
+#[component(immutable)]
+struct EntityVisibility;
+
+impl VisibilityFilter for EntityVisibility {
+    type ClientComponent = Self;
+    type Scope = Entity;
+
+    fn is_visible(&self, _client: Entity, component: Option<&Self::ClientComponent>) -> bool {
+        component.is_some()
+    }
+}
+
 #[derive(Resource)]
 struct EventReader<E: Event> {
     events: Vec<E>,
@@ -2,7 +2,7 @@ use bevy::{ecs::entity::MapEntities, prelude::*, state::app::StatesPlugin, time:
 use bevy_replicon::{
     client::ServerUpdateTick,
     prelude::*,
-    server::server_tick::ServerTick,
+    server::{message_sender::ServerMessageSender, server_tick::ServerTick},
     shared::server_entity_map::ServerEntityMap,
     test_app::{ServerTestAppExt, TestClientEntity},
 };
@@ -52,6 +52,40 @@ fn regular() {
     }
 }
 
+#[test]
+fn sender() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_server_message::<Test>(Channel::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let sender = server_app.world().resource::<ServerMessageSender>().clone();
+    std::thread::spawn(move || {
+        sender.send(ToClients {
+            targets: SendTargets::All,
+            message: Test,
+        })
+    })
+    .join()
+    .unwrap();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let messages = client_app.world().resource::<Messages<Test>>();
+    assert_eq!(messages.len(), 1);
+}
+
 #[test]
 fn mapped() {
     let mut server_app = App::new();
@@ -470,6 +504,51 @@ fn independent() {
     }
 }
 
+#[test]
+fn fragmented() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_server_message::<Large>(Channel::Ordered)
+        .make_message_independent::<Large>();
+        app.world_mut()
+            .resource_mut::<RepliconChannels>()
+            .set_fragment_threshold(16);
+        app.finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let data: Vec<_> = (0..100).collect();
+    server_app.world_mut().write_message(ToClients {
+        targets: SendTargets::All,
+        message: Large(data.clone()),
+    });
+
+    server_app.update();
+
+    let messages = server_app.world().resource::<ServerMessages>();
+    assert!(
+        messages.iter_sent().len() > 1,
+        "message should be split into fragments"
+    );
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let messages = client_app.world().resource::<Messages<Large>>();
+    let received: Vec<_> = messages
+        .iter_current_update_messages()
+        .map(|message| &message.0)
+        .collect();
+    assert_eq!(received, [&data]);
+}
+
 #[test]
 fn before_started_replication() {
     let mut server_app = App::new();
@@ -608,11 +687,58 @@ fn different_ticks() {
     assert_eq!(messages2.len(), 1);
 }
 
-#[derive(Message, Serialize, Deserialize)]
+#[test]
+fn after_replication() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .add_server_message::<Test>(Channel::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+    server_app.world_mut().send_after_replication(
+        server_entity,
+        ToClients {
+            targets: SendTargets::All,
+            message: Test,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let messages = client_app.world().resource::<Messages<Test>>();
+    assert!(
+        messages.is_empty(),
+        "message should be held until the entity is acknowledged"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let messages = client_app.world().resource::<Messages<Test>>();
+    assert_eq!(messages.len(), 1);
+}
+
+#[derive(Message, Serialize, Deserialize, Clone)]
 struct Test;
 
 #[derive(Message, Serialize, Deserialize)]
 struct Independent;
 
+#[derive(Message, Serialize, Deserialize)]
+struct Large(Vec<u8>);
+
 #[derive(Message, Serialize, Deserialize, MapEntities)]
 struct WithEntity(#[entities] Entity);
@@ -0,0 +1,131 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    postcard_utils,
+    prelude::*,
+    server::snapshot::{Snapshot, SnapshotHistory, SnapshotPlugin},
+    test_app::ServerTestAppExt,
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn interval() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate::<B>();
+    }
+    server_app
+        .add_plugins(SnapshotPlugin::<With<A>>::new(2).with_history_len(2))
+        .finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, A(1))).id();
+    let other_entity = server_app.world_mut().spawn((Replicated, B)).id();
+
+    for _ in 0..4 {
+        server_app.update();
+    }
+
+    let history = server_app.world().resource::<SnapshotHistory<With<A>>>();
+    assert_eq!(history.len(), 2, "old snapshots should be removed");
+
+    let mut snapshots = history.iter();
+    let first = snapshots.next().unwrap();
+    let last = snapshots.next().unwrap();
+    assert_eq!(last.tick() - first.tick(), 2);
+    assert_eq!(history.latest().unwrap().tick(), last.tick());
+    assert!(history.get(first.tick()).is_some());
+
+    assert_eq!(last.len(), 1);
+    assert!(!last.get(server_entity).unwrap().is_empty());
+    assert!(
+        last.get(other_entity).is_none(),
+        "entities that don't match the filter shouldn't be captured"
+    );
+}
+
+#[test]
+fn reset() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>();
+    }
+    server_app
+        .add_plugins(SnapshotPlugin::<()>::new(1))
+        .finish();
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, A(1)));
+    server_app.update();
+
+    let history = server_app.world().resource::<SnapshotHistory>();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.latest().unwrap().len(), 1);
+
+    server_app
+        .world_mut()
+        .resource_mut::<NextState<ServerState>>()
+        .set(ServerState::Stopped);
+    server_app.update();
+
+    let history = server_app.world().resource::<SnapshotHistory>();
+    assert!(history.is_empty());
+}
+
+#[test]
+fn invalid_ranges() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+            SnapshotPlugin::<()>::new(1),
+        ))
+        .replicate::<A>()
+        .finish();
+
+    server_app.world_mut().spawn((Replicated, A(1)));
+    server_app.update();
+
+    let history = server_app.world().resource::<SnapshotHistory>();
+    let mut message = Vec::new();
+    postcard_utils::to_extend_mut(history.latest().unwrap(), &mut message).unwrap();
+    let snapshot: Snapshot = postcard_utils::from_buf(&mut Bytes::from(message)).unwrap();
+    assert_eq!(snapshot.len(), 1);
+
+    // Same layout as `Snapshot`, but the range points past the data.
+    let entities = vec![(Entity::PLACEHOLDER, 0..10usize)];
+    let mut message = Vec::new();
+    postcard_utils::to_extend_mut(
+        &(RepliconTick::default(), entities, vec![0u8; 2]),
+        &mut message,
+    )
+    .unwrap();
+    let result: postcard::Result<Snapshot> = postcard_utils::from_buf(&mut Bytes::from(message));
+    assert!(result.is_err(), "out-of-range entries should be rejected");
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct A(u8);
+
+#[derive(Component, Deserialize, Serialize)]
+struct B;
@@ -183,6 +183,57 @@ fn related() {
     assert_eq!(remote.iter(client_app.world()).len(), 2);
 }
 
+#[test]
+fn related_deferred() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<ChildOf>()
+        .finish();
+    }
+
+    client_app
+        .insert_resource(UpdateApplyMode::Deferred)
+        .init_resource::<AppliedChildren>()
+        .add_observer(
+            |_on: On<UpdateApplied>,
+             mut applied: ResMut<AppliedChildren>,
+             children: Query<&ChildOf>,
+             remote: Query<(), With<Remote>>| {
+                for child_of in &children {
+                    assert!(
+                        remote.contains(child_of.parent()),
+                        "parent should be written when the message is applied"
+                    );
+                    **applied += 1;
+                }
+            },
+        );
+
+    server_app.connect_client(&mut client_app);
+
+    let server_parent = server_app.world_mut().spawn(Replicated).id();
+    server_app
+        .world_mut()
+        .spawn((Replicated, ChildOf(server_parent)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(**client_app.world().resource::<AppliedChildren>(), 1);
+
+    let mut remote = client_app.world_mut().query::<&Remote>();
+    assert_eq!(remote.iter(client_app.world()).len(), 2);
+}
+
 #[test]
 fn resource() {
     let mut server_app = App::new();
@@ -459,6 +510,327 @@ fn signature_for_client() {
     assert!(client_app2.world().get::<A>(client_entity2).is_none());
 }
 
+#[test]
+fn signature_respawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .replicate::<B>()
+        .finish();
+    }
+    client_app.init_resource::<Rebounds>().add_observer(
+        |rebound: On<SignatureRebound>, mut rebounds: ResMut<Rebounds>| {
+            rebounds.push(*rebound);
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn(Signature::from(0)).id();
+    let previous_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(previous_entity);
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, B, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(
+        client_entity.contains::<B>(),
+        "entity should be reused for the new server entity"
+    );
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity.id())
+    );
+    assert!(!entity_map.to_client().contains_key(&previous_entity));
+
+    let rebounds = client_app.world().resource::<Rebounds>();
+    assert_eq!(rebounds.len(), 1);
+    let rebound = rebounds[0];
+    assert_eq!(rebound.entity, client_entity.id());
+    assert_eq!(rebound.previous, previous_entity);
+    assert_eq!(rebound.server_entity, server_entity);
+}
+
+#[test]
+fn signature_respawn_before_despawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn(Signature::from(0)).id();
+    let previous_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Spawn before the despawn, so the signature has to wait for the previous entity.
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+    server_app.world_mut().despawn(previous_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world().get_entity(client_entity).is_ok());
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity)
+    );
+
+    let mut remote = client_app.world_mut().query::<&Remote>();
+    assert_eq!(remote.iter(client_app.world()).len(), 1);
+}
+
+#[test]
+fn signature_respawn_next_tick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .finish();
+    }
+    client_app.init_resource::<Rebounds>().add_observer(
+        |rebound: On<SignatureRebound>, mut rebounds: ResMut<Rebounds>| {
+            rebounds.push(*rebound);
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let previous_client_entity = client_app.world_mut().spawn(Signature::from(0)).id();
+    let previous_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(previous_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(
+        client_app
+            .world()
+            .get_entity(previous_client_entity)
+            .is_err()
+    );
+
+    let client_entity = client_app.world_mut().spawn(Signature::from(0)).id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity)
+    );
+    assert!(client_app.world().resource::<Rebounds>().is_empty());
+}
+
+#[test]
+fn signature_predicted_respawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let previous_client_entity = client_app.world_mut().spawn(Signature::from(0)).id();
+    let previous_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Predict the respawn before the server despawns the previous entity.
+    let client_entity = client_app.world_mut().spawn(Signature::from(0)).id();
+    server_app.world_mut().despawn(previous_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(
+        client_app
+            .world()
+            .get_entity(previous_client_entity)
+            .is_err()
+    );
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity),
+        "predicted entity should take the signature after the despawn"
+    );
+
+    let mut remote = client_app.world_mut().query::<&Remote>();
+    assert_eq!(remote.iter(client_app.world()).len(), 1);
+}
+
+#[test]
+fn signature_rebind_on_respawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .finish();
+    }
+
+    client_app.insert_resource(SignatureSettings {
+        rebind_on_respawn: true,
+    });
+    server_app.connect_client(&mut client_app);
+
+    let previous_client_entity = client_app.world_mut().spawn(Signature::from(0)).id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, A, Signature::from(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Respawn locally while the server entity is still alive.
+    client_app.world_mut().despawn(previous_client_entity);
+    let client_entity = client_app.world_mut().spawn(Signature::from(0)).id();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity),
+        "server entity should be rebound to the respawned entity"
+    );
+    assert!(client_app.world().get::<Remote>(client_entity).is_some());
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app.world().get_entity(client_entity).is_err(),
+        "despawn from server should be applied to the respawned entity"
+    );
+}
+
+#[test]
+fn signature_collision() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+    ))
+    .init_resource::<Collisions>()
+    .add_observer(
+        |collision: On<SignatureCollision>, mut collisions: ResMut<Collisions>| {
+            collisions.push(*collision);
+        },
+    )
+    .finish();
+
+    let existing = app.world_mut().spawn(Signature::from(0)).id();
+    let entity = app.world_mut().spawn(Signature::from(0)).id();
+
+    let collisions = app.world().resource::<Collisions>();
+    assert_eq!(collisions.len(), 1);
+    let collision = collisions[0];
+    assert_eq!(collision.entity, entity);
+    assert_eq!(collision.existing, existing);
+}
+
 #[test]
 fn before_started_replication() {
     let mut server_app = App::new();
@@ -655,6 +1027,150 @@ fn visibility_gain_with_signature() {
     assert!(client_app.world().get::<A>(client_entity).is_some());
 }
 
+#[test]
+fn derived_visibility_filter() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .add_visibility_filter::<Team>()
+        .add_visibility_filter::<Layers>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app
+        .world_mut()
+        .entity_mut(client)
+        .insert((Team(0), Layers(0b011)));
+
+    server_app.world_mut().spawn((Replicated, Team(0), A));
+    server_app.world_mut().spawn((Replicated, Team(1), A));
+    server_app.world_mut().spawn((Replicated, Layers(0b001), A));
+    server_app.world_mut().spawn((Replicated, Layers(0b101), A));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&A>();
+    assert_eq!(components.iter(client_app.world()).len(), 2);
+}
+
+#[test]
+fn views() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .add_visibility_filter::<Team>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app.world_mut().spawn((ViewOf(client), Team(0)));
+    let view = server_app.world_mut().spawn((ViewOf(client), Team(1))).id();
+
+    server_app.world_mut().spawn((Replicated, Team(0), A));
+    server_app.world_mut().spawn((Replicated, Team(1), A));
+    server_app.world_mut().spawn((Replicated, Team(2), A));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&A>();
+    assert_eq!(components.iter(client_app.world()).len(), 2);
+
+    server_app.world_mut().despawn(view);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(components.iter(client_app.world()).len(), 1);
+}
+
+#[test]
+fn topics() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<A>()
+        .add_topic::<Entity>("units")
+        .add_topic_with::<Entity>("secrets", |_| false)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, InTopics::new(["units"]), A));
+    server_app
+        .world_mut()
+        .spawn((Replicated, InTopics::new(["secrets"]), A));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&A>();
+    assert_eq!(components.iter(client_app.world()).len(), 0);
+
+    client_app
+        .world_mut()
+        .client_trigger(Subscribe::new("units"));
+    client_app
+        .world_mut()
+        .client_trigger(Subscribe::new("secrets"));
+    client_app
+        .world_mut()
+        .client_trigger(Subscribe::new("unknown"));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(components.iter(client_app.world()).len(), 1);
+
+    client_app
+        .world_mut()
+        .client_trigger(Unsubscribe::new("units"));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(components.iter(client_app.world()).len(), 0);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct A;
 
@@ -664,6 +1180,15 @@ struct B;
 #[derive(Resource, Deserialize, Serialize)]
 struct R;
 
+#[derive(Resource, Default, Deref, DerefMut)]
+struct AppliedChildren(usize);
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Rebounds(Vec<SignatureRebound>);
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Collisions(Vec<SignatureCollision>);
+
 #[derive(Component)]
 #[component(immutable)]
 struct EntityVisibility;
@@ -676,3 +1201,13 @@ impl VisibilityFilter for EntityVisibility {
         component.is_some()
     }
 }
+
+#[derive(Component, VisibilityFilter, PartialEq)]
+#[component(immutable)]
+#[visibility(equal)]
+struct Team(u8);
+
+#[derive(Component, VisibilityFilter)]
+#[component(immutable)]
+#[visibility(bitmask)]
+struct Layers(u8);
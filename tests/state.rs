@@ -0,0 +1,137 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn initial() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .init_state::<Phase>()
+        .replicate_state::<Phase>()
+        .finish();
+    }
+
+    server_app
+        .world_mut()
+        .resource_mut::<NextState<Phase>>()
+        .set(Phase::Playing);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let state = client_app.world().resource::<State<Phase>>();
+    assert_eq!(**state, Phase::Playing);
+}
+
+#[test]
+fn transition() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .init_state::<Phase>()
+        .replicate_state::<Phase>()
+        .finish();
+    }
+    client_app
+        .init_resource::<Entered>()
+        .add_systems(OnEnter(Phase::Ended), count_entered);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let state = client_app.world().resource::<State<Phase>>();
+    assert_eq!(**state, Phase::Lobby);
+
+    server_app
+        .world_mut()
+        .resource_mut::<NextState<Phase>>()
+        .set(Phase::Ended);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let state = client_app.world().resource::<State<Phase>>();
+    assert_eq!(**state, Phase::Ended);
+    assert_eq!(**client_app.world().resource::<Entered>(), 1);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        **client_app.world().resource::<Entered>(),
+        1,
+        "state shouldn't be re-entered without changes"
+    );
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .init_state::<Phase>()
+        .replicate_state::<Phase>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app
+            .world()
+            .contains_resource::<ReplicatedState<Phase>>()
+    );
+
+    server_app.disconnect_client(&mut client_app);
+
+    assert!(
+        !client_app
+            .world()
+            .contains_resource::<ReplicatedState<Phase>>()
+    );
+}
+
+fn count_entered(mut entered: ResMut<Entered>) {
+    **entered += 1;
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Entered(usize);
+
+#[derive(States, Serialize, Deserialize, Default, Debug, Hash, PartialEq, Eq, Clone)]
+enum Phase {
+    #[default]
+    Lobby,
+    Playing,
+    Ended,
+}
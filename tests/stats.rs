@@ -76,5 +76,119 @@ fn client_stats() {
     assert_eq!(stats.bytes, 0);
 }
 
+#[test]
+fn apply_stats() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+    client_app.init_resource::<ReplicationApplyStats>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let stats = client_app.world().resource::<ReplicationApplyStats>();
+    assert_eq!(stats.messages, 1);
+    assert_ne!(stats.bytes, 0);
+    assert_eq!(stats.entities_spawned, 1);
+    assert_eq!(stats.components_inserted, 1);
+    assert_eq!(stats.components_mutated, 0);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let stats = client_app.world().resource::<ReplicationApplyStats>();
+    assert_eq!(stats.messages, 1);
+    assert_eq!(stats.entities_spawned, 0, "should be reset for each run");
+    assert_eq!(stats.components_inserted, 0);
+    assert_eq!(stats.entities_despawned, 1);
+
+    client_app.update();
+
+    let stats = client_app.world().resource::<ReplicationApplyStats>();
+    assert_eq!(stats.messages, 0);
+    assert_eq!(stats.entities_despawned, 0);
+}
+
+#[test]
+fn removal_stats() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<TestComponent>()
+        .replicate::<Projectile>()
+        .finish();
+    }
+    server_app.init_resource::<RemovalStats>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<TestComponent>();
+
+    // Removed before being sent to any client.
+    server_app
+        .world_mut()
+        .spawn((Replicated, Projectile))
+        .remove::<Projectile>();
+
+    // Sent for another entity, but not for this one.
+    server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent))
+        .remove::<TestComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let stats = server_app.world().resource::<RemovalStats>();
+    assert_eq!(stats.removals, 3);
+    assert_eq!(stats.skipped, 2);
+    assert_eq!(stats.client_checks, 1);
+
+    let mut components = client_app.world_mut().query::<&TestComponent>();
+    assert_eq!(components.iter(client_app.world()).len(), 0);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct TestComponent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct Projectile;
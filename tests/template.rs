@@ -0,0 +1,104 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn instantiation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<Health>()
+        .replicate::<Speed>()
+        .register_template(
+            TEMPLATE,
+            EntityTemplate::default().with(Health(1)).with(Speed(1)),
+        )
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TEMPLATE, Health(2)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let server_speed = server_app.world().get::<Speed>(server_entity).unwrap();
+    assert_eq!(
+        *server_speed,
+        Speed(1),
+        "missing components should be inserted on server too"
+    );
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<(&Health, &Speed), With<Template>>();
+    let (&health, &speed) = components.single(client_app.world()).unwrap();
+    assert_eq!(health, Health(2), "override should be replicated");
+    assert_eq!(speed, Speed(1));
+}
+
+#[test]
+fn matching_not_sent() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for (app, speed) in [(&mut server_app, 1), (&mut client_app, 2)] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate::<Speed>()
+        // Use different values to check which one is used on the client.
+        .register_template(TEMPLATE, EntityTemplate::default().with(Speed(speed)))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, TEMPLATE)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&Speed>();
+    let &speed = components.single(client_app.world()).unwrap();
+    assert_eq!(
+        speed,
+        Speed(2),
+        "component matching the template should be instantiated by the client"
+    );
+
+    *server_app
+        .world_mut()
+        .get_mut::<Speed>(server_entity)
+        .unwrap() = Speed(3);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let &speed = components.single(client_app.world()).unwrap();
+    assert_eq!(speed, Speed(3), "changed component should be replicated");
+}
+
+const TEMPLATE: Template = Template(0);
+
+#[derive(Component, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Health(u32);
+
+#[derive(Component, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Speed(u32);
@@ -0,0 +1,79 @@
+use core::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn server_and_client() {
+    const TICK_DURATION: Duration = Duration::from_millis(10);
+
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TICK_DURATION))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    for value in 1..=5 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        server_app
+            .world_mut()
+            .get_mut::<TestComponent>(server_entity)
+            .unwrap()
+            .0 = value;
+    }
+
+    let server_timeline = server_app.world().resource::<TickTimeline>();
+    assert_eq!(server_timeline.tick_duration(), Some(TICK_DURATION));
+    assert_eq!(
+        server_timeline.ticks_to_duration(2),
+        Some(TICK_DURATION * 2)
+    );
+    assert_eq!(
+        server_timeline.duration_to_ticks(TICK_DURATION * 3),
+        Some(3.0)
+    );
+
+    let (server_tick, server_time) = server_timeline.latest().unwrap();
+    assert_eq!(
+        server_timeline.tick_at(server_time + TICK_DURATION / 2),
+        Some((server_tick, 0.5))
+    );
+    assert_eq!(
+        server_timeline.tick_at(server_time - TICK_DURATION / 2),
+        Some((server_tick - 1, 0.5))
+    );
+
+    let client_timeline = client_app.world().resource::<TickTimeline>();
+    assert_eq!(client_timeline.tick_duration(), Some(TICK_DURATION));
+
+    server_app.disconnect_client(&mut client_app);
+
+    let client_timeline = client_app.world().resource::<TickTimeline>();
+    assert!(
+        client_timeline.latest().is_none(),
+        "timeline should be cleared on disconnect"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u8);
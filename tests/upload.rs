@@ -0,0 +1,158 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_replicon::{
+    prelude::*,
+    shared::{backend::connected_client::NetworkId, server_entity_map::ServerEntityMap},
+    test_app::{ServerTestAppExt, TestClientEntity},
+};
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+#[test]
+fn accepted() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_possession()
+        .replicate::<Aim>();
+        app.upload::<Aim>(Channel::Ordered);
+        app.finish();
+    }
+
+    let (server_entity, client_entity) = possess(&mut server_app, &mut client_app);
+
+    client_app
+        .world_mut()
+        .get_mut::<Aim>(client_entity)
+        .unwrap()
+        .0 = 1;
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let aim = *server_app.world().get::<Aim>(server_entity).unwrap();
+    assert_eq!(aim, Aim(1));
+}
+
+#[test]
+fn rejected() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_possession()
+        .replicate::<Aim>();
+        app.upload::<Aim>(Channel::Ordered)
+            .with_validation(|_client, current, aim| current.0.abs_diff(aim.0) <= 1);
+        app.finish();
+    }
+
+    let (server_entity, client_entity) = possess(&mut server_app, &mut client_app);
+
+    client_app
+        .world_mut()
+        .get_mut::<Aim>(client_entity)
+        .unwrap()
+        .0 = 2;
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let aim = *server_app.world().get::<Aim>(server_entity).unwrap();
+    assert_eq!(aim, Aim(0), "upload should be rejected by validation");
+}
+
+#[test]
+fn not_possessed() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins.set(ServerPlugin::new(PostUpdate)),
+        ))
+        .replicate_possession()
+        .replicate::<Aim>();
+        app.upload::<Aim>(Channel::Ordered);
+        app.finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn((Replicated, Aim(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = *client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .unwrap();
+
+    // Simulate a malicious client.
+    client_app.world_mut().write_message(Upload {
+        entity: client_entity,
+        component: Aim(1),
+    });
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let aim = *server_app.world().get::<Aim>(server_entity).unwrap();
+    assert_eq!(aim, Aim(0), "only possessed entities should accept uploads");
+}
+
+/// Spawns an entity possessed by the client and returns its server and client entities.
+fn possess(server_app: &mut App, client_app: &mut App) -> (Entity, Entity) {
+    server_app.connect_client(client_app);
+
+    let network_id = NetworkId::new(0);
+    let client = **client_app.world().resource::<TestClientEntity>();
+    server_app.world_mut().entity_mut(client).insert(network_id);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, PossessedBy::new(network_id), Aim(0)))
+        .id();
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+    }
+
+    let client_entity = *client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .unwrap();
+    assert!(
+        client_app
+            .world()
+            .entity(client_entity)
+            .contains::<Possessed>()
+    );
+
+    (server_entity, client_entity)
+}
+
+#[derive(Component, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+struct Aim(u8);